@@ -0,0 +1,91 @@
+//! 批量创建性能对比示例（SQLite版本）
+//!
+//! 对比逐条 `save()` 与 `ModelManager::create_many()` 写入同样数量记录的耗时
+
+use rat_quickdb::*;
+use rat_quickdb::model::{ModelManager, Model, string_field, integer_field, boolean_field};
+use rat_logger::debug;
+use std::time::Instant;
+
+define_model! {
+    /// 基准测试用户模型
+    struct BenchUser {
+        id: String,
+        username: String,
+        email: String,
+        age: Option<i32>,
+        is_active: bool,
+    }
+    collection = "bulk_bench_users",
+    fields = {
+        id: string_field(None, None, None).required().unique(),
+        username: string_field(Some(50), Some(3), None).required(),
+        email: string_field(None, None, Some(r"^[^@\s]+@[^@\s]+$".to_string())).required(),
+        age: integer_field(Some(0), Some(150)),
+        is_active: boolean_field().required(),
+    }
+    indexes = [
+        { fields: ["username"], unique: false, name: "idx_bench_username" },
+    ],
+}
+
+const RECORD_COUNT: usize = 2000;
+
+fn build_users(prefix: &str) -> Vec<BenchUser> {
+    (0..RECORD_COUNT)
+        .map(|i| BenchUser {
+            id: String::new(),
+            username: format!("{}_{}", prefix, i),
+            email: format!("{}_{}@example.com", prefix, i),
+            age: Some((i % 80) as i32 + 18),
+            is_active: i % 2 == 0,
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    rat_logger::init();
+
+    println!("=== rat_quickdb 批量创建性能对比（SQLite版本）===\n");
+
+    let _ = std::fs::remove_file("./bulk_create_benchmark.db");
+    let config = sqlite_config(
+        "default",
+        "./bulk_create_benchmark.db",
+        PoolConfig::default(),
+        Some(IdStrategy::Uuid),
+    )?;
+    add_database(config).await?;
+    let _ = rat_quickdb::drop_table("default", "bulk_bench_users").await;
+
+    // 逐条保存
+    let start = Instant::now();
+    for user in build_users("loop") {
+        user.save().await?;
+    }
+    let loop_elapsed = start.elapsed();
+    println!("逐条 save():        {} 条记录耗时 {:?}", RECORD_COUNT, loop_elapsed);
+
+    // 批量创建
+    let start = Instant::now();
+    let ids = ModelManager::<BenchUser>::create_many(build_users("batch")).await?;
+    let batch_elapsed = start.elapsed();
+    println!("create_many():      {} 条记录耗时 {:?}", ids.len(), batch_elapsed);
+
+    if batch_elapsed.as_nanos() > 0 {
+        println!(
+            "加速比: {:.1}x",
+            loop_elapsed.as_secs_f64() / batch_elapsed.as_secs_f64()
+        );
+    }
+
+    let total = ModelManager::<BenchUser>::count(vec![]).await?;
+    println!("表内记录总数: {}", total);
+
+    let _ = rat_quickdb::drop_table("default", "bulk_bench_users").await;
+    let _ = std::fs::remove_file("./bulk_create_benchmark.db");
+
+    println!("\n✅ 批量创建性能对比完成！");
+    Ok(())
+}
@@ -174,6 +174,21 @@ impl CachePerformanceTest {
             alias: "cached_db".to_string(),
             cache: Some(cache_config),
             id_strategy: IdStrategy::Uuid,
+            missing_table_returns_empty: false,
+            write_behind: None,
+            log_queries: Default::default(),
+            sensitive_fields: Vec::new(),
+            read_only: false,
+            dry_run: false,
+            detect_n_plus_one: None,
+            gridfs_bucket: None,
+            busy_retry_count: None,
+            mongo_id_field: None,
+            mongo_null_matching: None,
+            application_name: None,
+            datetime_precision: None,
+            mysql_json_text_detection: false,
+            workloads: Default::default(),
         }
     }
 
@@ -200,6 +215,21 @@ impl CachePerformanceTest {
             alias: "non_cached_db".to_string(),
             cache: None, // 明确禁用缓存
             id_strategy: IdStrategy::Uuid,
+            missing_table_returns_empty: false,
+            write_behind: None,
+            log_queries: Default::default(),
+            sensitive_fields: Vec::new(),
+            read_only: false,
+            dry_run: false,
+            detect_n_plus_one: None,
+            gridfs_bucket: None,
+            busy_retry_count: None,
+            mongo_id_field: None,
+            mongo_null_matching: None,
+            application_name: None,
+            datetime_precision: None,
+            mysql_json_text_detection: false,
+            workloads: Default::default(),
         }
     }
 
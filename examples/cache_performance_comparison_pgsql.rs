@@ -173,6 +173,7 @@ impl PgCachePerformanceTest {
                     min_tls_version: None,
                     cipher_suites: None,
                 }),
+                create_database_if_missing: false,
             },
             pool: PoolConfig {
                 min_connections: 1,
@@ -180,14 +181,37 @@ impl PgCachePerformanceTest {
                 connection_timeout: 10000,  // 增加到10秒
                 idle_timeout: 600,
                 max_lifetime: 3600,
+                max_lifetime_jitter: 0,
                 max_retries: 5,  // 增加重试次数
                 retry_interval_ms: 500,  // 减少重试间隔
+                retry_attempts: 0,
+                retry_base_delay_ms: 100,
+                retry_max_delay_ms: 5000,
                 keepalive_interval_sec: 60,  // 增加保活间隔
                 health_check_timeout_sec: 10,  // 增加健康检查超时
+                test_before_acquire: true,
+                sampling: None,
+                reserved_interactive_connections: 0,
+                max_batch_operations: 0,
             },
             alias: "cached_db".to_string(),
             cache: Some(cache_config),
             id_strategy: IdStrategy::Uuid,
+            missing_table_returns_empty: false,
+            write_behind: None,
+            log_queries: Default::default(),
+            sensitive_fields: Vec::new(),
+            read_only: false,
+            dry_run: false,
+            detect_n_plus_one: None,
+            gridfs_bucket: None,
+            busy_retry_count: None,
+            mongo_id_field: None,
+            mongo_null_matching: None,
+            application_name: None,
+            datetime_precision: None,
+            mysql_json_text_detection: false,
+            workloads: Default::default(),
         };
 
         db_config
@@ -214,6 +238,7 @@ impl PgCachePerformanceTest {
                     min_tls_version: None,
                     cipher_suites: None,
                 }),
+                create_database_if_missing: false,
             },
             pool: PoolConfig {
                 min_connections: 1,
@@ -221,14 +246,37 @@ impl PgCachePerformanceTest {
                 connection_timeout: 10000,  // 增加到10秒
                 idle_timeout: 600,
                 max_lifetime: 3600,
+                max_lifetime_jitter: 0,
                 max_retries: 5,  // 增加重试次数
                 retry_interval_ms: 500,  // 减少重试间隔
+                retry_attempts: 0,
+                retry_base_delay_ms: 100,
+                retry_max_delay_ms: 5000,
                 keepalive_interval_sec: 60,  // 增加保活间隔
                 health_check_timeout_sec: 10,  // 增加健康检查超时
+                test_before_acquire: true,
+                sampling: None,
+                reserved_interactive_connections: 0,
+                max_batch_operations: 0,
             },
             alias: "non_cached_db".to_string(),
             cache: None, // 明确禁用缓存
             id_strategy: IdStrategy::Uuid,
+            missing_table_returns_empty: false,
+            write_behind: None,
+            log_queries: Default::default(),
+            sensitive_fields: Vec::new(),
+            read_only: false,
+            dry_run: false,
+            detect_n_plus_one: None,
+            gridfs_bucket: None,
+            busy_retry_count: None,
+            mongo_id_field: None,
+            mongo_null_matching: None,
+            application_name: None,
+            datetime_precision: None,
+            mysql_json_text_detection: false,
+            workloads: Default::default(),
         }
     }
 
@@ -261,6 +261,10 @@ async fn main() -> QuickDbResult<()> {
             skip: 0,
         }),
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     let sorted_result = ModelManager::<User>::find_with_groups(
@@ -58,6 +58,21 @@ async fn main() -> QuickDbResult<()> {
         alias: "default".to_string(),
         cache: None,
         id_strategy: IdStrategy::Uuid,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     // 初始化数据库
@@ -278,6 +293,10 @@ async fn main() -> QuickDbResult<()> {
             skip: 0,
         }),
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     let sorted_result = ModelManager::<User>::find_with_groups(
@@ -32,6 +32,7 @@ async fn main() -> QuickDbResult<()> {
             password: "testdb123456".to_string(),
             ssl_mode: Some("prefer".to_string()),
             tls_config: None,
+            create_database_if_missing: false,
         },
         pool: PoolConfig::builder()
             .min_connections(2)
@@ -47,6 +48,21 @@ async fn main() -> QuickDbResult<()> {
         alias: "default".to_string(),
         cache: None,
         id_strategy: IdStrategy::Uuid,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     // 初始化数据库
@@ -267,6 +283,10 @@ async fn main() -> QuickDbResult<()> {
             skip: 0,
         }),
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     let sorted_result = ModelManager::<User>::find_with_groups(
@@ -91,6 +91,21 @@ async fn test_auto_increment() -> QuickDbResult<()> {
                 .unwrap(),
         id_strategy: IdStrategy::AutoIncrement,
         cache: None,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -166,6 +181,21 @@ async fn test_uuid() -> QuickDbResult<()> {
                 .unwrap(),
         id_strategy: IdStrategy::Uuid,
         cache: None,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -246,6 +276,21 @@ async fn test_snowflake() -> QuickDbResult<()> {
                 .unwrap(),
         id_strategy: IdStrategy::snowflake(1, 1),
         cache: None,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
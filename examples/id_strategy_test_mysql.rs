@@ -84,6 +84,7 @@ async fn test_auto_increment() -> QuickDbResult<()> {
                 Some(opts)
             },
             tls_config: None,
+            create_database_if_missing: false,
         },
         pool: PoolConfig::builder()
             .min_connections(1)
@@ -99,6 +100,21 @@ async fn test_auto_increment() -> QuickDbResult<()> {
         alias: "auto_increment_db".to_string(),
         cache: None,
         id_strategy: IdStrategy::AutoIncrement,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -182,6 +198,7 @@ async fn test_uuid() -> QuickDbResult<()> {
                 Some(opts)
             },
             tls_config: None,
+            create_database_if_missing: false,
         },
         pool: PoolConfig::builder()
             .min_connections(1)
@@ -197,6 +214,21 @@ async fn test_uuid() -> QuickDbResult<()> {
         alias: "uuid_db".to_string(),
         cache: None,
         id_strategy: IdStrategy::Uuid,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -273,6 +305,7 @@ async fn test_snowflake() -> QuickDbResult<()> {
                 Some(opts)
             },
             tls_config: None,
+            create_database_if_missing: false,
         },
         pool: PoolConfig::builder()
             .min_connections(1)
@@ -288,6 +321,21 @@ async fn test_snowflake() -> QuickDbResult<()> {
         alias: "snowflake_db".to_string(),
         cache: None,
         id_strategy: IdStrategy::snowflake(1, 1),
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -80,6 +80,7 @@ async fn test_auto_increment() -> QuickDbResult<()> {
             password: "testdb123456".to_string(),
             ssl_mode: Some("prefer".to_string()),
             tls_config: None,
+            create_database_if_missing: false,
         },
         pool: PoolConfig::builder()
                 .max_connections(5)
@@ -96,6 +97,21 @@ async fn test_auto_increment() -> QuickDbResult<()> {
         alias: "auto_increment_db".to_string(),
         cache: None,
         id_strategy: IdStrategy::AutoIncrement,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -164,6 +180,7 @@ async fn test_uuid() -> QuickDbResult<()> {
             password: "testdb123456".to_string(),
             ssl_mode: Some("prefer".to_string()),
             tls_config: None,
+            create_database_if_missing: false,
         },
         pool: PoolConfig::builder()
                 .max_connections(5)
@@ -179,6 +196,21 @@ async fn test_uuid() -> QuickDbResult<()> {
                 .unwrap(),
         id_strategy: IdStrategy::Uuid,
         cache: None,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -252,6 +284,7 @@ async fn test_snowflake() -> QuickDbResult<()> {
             password: "testdb123456".to_string(),
             ssl_mode: Some("prefer".to_string()),
             tls_config: None,
+            create_database_if_missing: false,
         },
         pool: PoolConfig::builder()
                 .max_connections(5)
@@ -267,6 +300,21 @@ async fn test_snowflake() -> QuickDbResult<()> {
                 .unwrap(),
         id_strategy: IdStrategy::Snowflake { machine_id: 1, datacenter_id: 1 },
         cache: None,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -113,6 +113,21 @@ async fn setup_database() -> QuickDbResult<()> {
         alias: "main_db".to_string(),
         cache: None,
         id_strategy: IdStrategy::Uuid,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(config).await?;
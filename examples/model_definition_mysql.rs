@@ -669,7 +669,7 @@ async fn demonstrate_error_handling() -> QuickDbResult<()> {
 
     match invalid_user.validate() {
         Ok(_) => println!("❌ 意外：无效用户数据验证通过"),
-        Err(e) => println!("✅ 预期错误（数据验证失败）: {}", e),
+        Err(e) => println!("✅ 预期错误（数据验证失败，字段 {:?}）: {}", e.field_name(), e),
     }
 
     // 2. 尝试查询不存在的用户
@@ -724,7 +724,8 @@ async fn demonstrate_error_handling() -> QuickDbResult<()> {
 
             match duplicate_user.save().await {
                 Ok(id) => println!("❌ 意外成功：重复用户创建成功: {}", id),
-                Err(e) => println!("✅ 预期错误（重复用户名）: {}", e),
+                Err(e) if e.is_unique_violation() => println!("✅ 预期错误（重复用户名）: {}", e),
+                Err(e) => println!("❌ 非唯一约束错误 {:?}: {}", e.kind(), e),
             }
         },
         Err(e) => println!("第一次创建失败: {}", e),
@@ -1140,6 +1141,7 @@ async fn main() -> QuickDbResult<()> {
                 Some(opts)
             },
             tls_config: None,
+            create_database_if_missing: false,
         })
         .pool(pool_config)
         .alias("default")
@@ -1599,6 +1601,10 @@ async fn demonstrate_complex_queries() -> QuickDbResult<()> {
         }],
         pagination: None,
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     match ModelManager::<User>::find(vec![], Some(sort_options)).await {
@@ -1622,6 +1628,10 @@ async fn demonstrate_complex_queries() -> QuickDbResult<()> {
             skip: 0,
         }),
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     match ModelManager::<User>::find(vec![], Some(page_options)).await {
@@ -670,7 +670,7 @@ async fn demonstrate_error_handling() -> QuickDbResult<()> {
 
     match invalid_user.validate() {
         Ok(_) => println!("❌ 意外：无效用户数据验证通过"),
-        Err(e) => println!("✅ 预期错误（数据验证失败）: {}", e),
+        Err(e) => println!("✅ 预期错误（数据验证失败，字段 {:?}）: {}", e.field_name(), e),
     }
 
     // 2. 尝试查询不存在的用户
@@ -725,7 +725,8 @@ async fn demonstrate_error_handling() -> QuickDbResult<()> {
 
             match duplicate_user.save().await {
                 Ok(id) => println!("❌ 意外成功：重复用户创建成功: {}", id),
-                Err(e) => println!("✅ 预期错误（重复用户名）: {}", e),
+                Err(e) if e.is_unique_violation() => println!("✅ 预期错误（重复用户名）: {}", e),
+                Err(e) => println!("❌ 非唯一约束错误 {:?}: {}", e.kind(), e),
             }
         },
         Err(e) => println!("第一次创建失败: {}", e),
@@ -1124,6 +1125,7 @@ async fn main() -> QuickDbResult<()> {
             password: "testdb123456".to_string(),
             ssl_mode: Some("prefer".to_string()),
             tls_config: None,
+            create_database_if_missing: false,
         },
         pool: PoolConfig::builder()
             .max_connections(10)
@@ -1140,6 +1142,21 @@ async fn main() -> QuickDbResult<()> {
         alias: "default".to_string(),
         id_strategy: IdStrategy::Uuid,
         cache: None,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     // 添加数据库到连接池管理器
@@ -1633,6 +1650,10 @@ async fn demonstrate_complex_queries() -> QuickDbResult<()> {
         }],
         pagination: None,
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     match ModelManager::<User>::find(vec![], Some(sort_options)).await {
@@ -1656,6 +1677,10 @@ async fn demonstrate_complex_queries() -> QuickDbResult<()> {
             skip: 0,
         }),
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     match ModelManager::<User>::find(vec![], Some(page_options)).await {
@@ -237,6 +237,21 @@ async fn main() -> QuickDbResult<()> {
             .unwrap(),
         id_strategy: IdStrategy::Uuid,
         cache: None,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -311,6 +326,10 @@ async fn main() -> QuickDbResult<()> {
                 skip,
             }),
             fields: vec![],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(vec![], Some(page_options)).await {
@@ -363,6 +382,10 @@ async fn main() -> QuickDbResult<()> {
             skip: 0,
         }),
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     match ModelManager::<Employee>::find(vec![], Some(sort_options)).await {
@@ -420,6 +443,10 @@ async fn main() -> QuickDbResult<()> {
                 skip: 0,
             }),
             fields: vec![],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(filter_conditions, Some(filter_options)).await {
@@ -463,6 +490,10 @@ async fn main() -> QuickDbResult<()> {
             "position".to_string(),
             "hire_date".to_string()
         ],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     match ModelManager::<Employee>::find(vec![], Some(fields_options)).await {
@@ -531,6 +562,10 @@ async fn main() -> QuickDbResult<()> {
                 skip: 0,
             }),
             fields: vec![],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(complex_conditions, Some(complex_options)).await {
@@ -585,6 +620,10 @@ async fn main() -> QuickDbResult<()> {
                 skip: 0,
             }),
             fields: vec!["name".to_string(), "salary".to_string(), "department".to_string()],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(vec![], Some(performance_options)).await {
@@ -205,6 +205,7 @@ async fn main() -> QuickDbResult<()> {
                 Some(opts)
             },
             tls_config: None,
+            create_database_if_missing: false,
         },
         pool: PoolConfig {
                 min_connections: 1,
@@ -212,13 +213,36 @@ async fn main() -> QuickDbResult<()> {
                 connection_timeout: 10000,  // 10秒
                 idle_timeout: 600,          // 10分钟
                 max_lifetime: 1800,         // 30分钟
+                max_lifetime_jitter: 0,
                 max_retries: 3,
                 retry_interval_ms: 1000,
+                retry_attempts: 0,
+                retry_base_delay_ms: 100,
+                retry_max_delay_ms: 5000,
                 keepalive_interval_sec: 60,
                 health_check_timeout_sec: 10,
+                test_before_acquire: true,
+                sampling: None,
+                reserved_interactive_connections: 0,
+                max_batch_operations: 0,
             },
         id_strategy: IdStrategy::Uuid,
         cache: None,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -290,6 +314,10 @@ async fn main() -> QuickDbResult<()> {
                 skip,
             }),
             fields: vec![],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(vec![], Some(page_options)).await {
@@ -342,6 +370,10 @@ async fn main() -> QuickDbResult<()> {
             skip: 0,
         }),
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     match ModelManager::<Employee>::find(vec![], Some(sort_options)).await {
@@ -399,6 +431,10 @@ async fn main() -> QuickDbResult<()> {
                 skip: 0,
             }),
             fields: vec![],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(filter_conditions, Some(filter_options)).await {
@@ -442,6 +478,10 @@ async fn main() -> QuickDbResult<()> {
             "position".to_string(),
             "hire_date".to_string()
         ],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     match ModelManager::<Employee>::find(vec![], Some(fields_options)).await {
@@ -510,6 +550,10 @@ async fn main() -> QuickDbResult<()> {
                 skip: 0,
             }),
             fields: vec![],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(complex_conditions, Some(complex_options)).await {
@@ -564,6 +608,10 @@ async fn main() -> QuickDbResult<()> {
                 skip: 0,
             }),
             fields: vec!["name".to_string(), "salary".to_string(), "department".to_string()],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(vec![], Some(performance_options)).await {
@@ -201,6 +201,7 @@ async fn main() -> QuickDbResult<()> {
             password: "testdb123456".to_string(),
             ssl_mode: Some("prefer".to_string()),
             tls_config: None,
+            create_database_if_missing: false,
         },
         pool: PoolConfig {
                 min_connections: 1,
@@ -208,13 +209,36 @@ async fn main() -> QuickDbResult<()> {
                 connection_timeout: 10000,  // 增加到10秒
                 idle_timeout: 600,
                 max_lifetime: 3600,
+                max_lifetime_jitter: 0,
                 max_retries: 5,  // 增加重试次数
                 retry_interval_ms: 500,  // 减少重试间隔
+                retry_attempts: 0,
+                retry_base_delay_ms: 100,
+                retry_max_delay_ms: 5000,
                 keepalive_interval_sec: 60,  // 增加保活间隔
                 health_check_timeout_sec: 10,  // 增加健康检查超时
+                test_before_acquire: true,
+                sampling: None,
+                reserved_interactive_connections: 0,
+                max_batch_operations: 0,
             },
         id_strategy: IdStrategy::Uuid,
         cache: None,
+        missing_table_returns_empty: false,
+        write_behind: None,
+        log_queries: Default::default(),
+        sensitive_fields: Vec::new(),
+        read_only: false,
+        dry_run: false,
+        detect_n_plus_one: None,
+        gridfs_bucket: None,
+        busy_retry_count: None,
+        mongo_id_field: None,
+        mongo_null_matching: None,
+        application_name: None,
+        datetime_precision: None,
+        mysql_json_text_detection: false,
+        workloads: Default::default(),
     };
 
     add_database(db_config).await?;
@@ -286,6 +310,10 @@ async fn main() -> QuickDbResult<()> {
                 skip,
             }),
             fields: vec![],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(vec![], Some(page_options)).await {
@@ -338,6 +366,10 @@ async fn main() -> QuickDbResult<()> {
             skip: 0,
         }),
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     match ModelManager::<Employee>::find(vec![], Some(sort_options)).await {
@@ -395,6 +427,10 @@ async fn main() -> QuickDbResult<()> {
                 skip: 0,
             }),
             fields: vec![],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(filter_conditions, Some(filter_options)).await {
@@ -438,6 +474,10 @@ async fn main() -> QuickDbResult<()> {
             "position".to_string(),
             "hire_date".to_string()
         ],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     match ModelManager::<Employee>::find(vec![], Some(fields_options)).await {
@@ -506,6 +546,10 @@ async fn main() -> QuickDbResult<()> {
                 skip: 0,
             }),
             fields: vec![],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(complex_conditions, Some(complex_options)).await {
@@ -560,6 +604,10 @@ async fn main() -> QuickDbResult<()> {
                 skip: 0,
             }),
             fields: vec!["name".to_string(), "salary".to_string(), "department".to_string()],
+            collation: None,
+            select_exprs: vec![],
+            workload: None,
+            cursor: None,
         };
 
         match ModelManager::<Employee>::find(vec![], Some(performance_options)).await {
@@ -0,0 +1,76 @@
+//! PostgreSQL 会话上下文 + 行级安全（RLS）多租户示例
+//!
+//! 每个请求通过 `with_session_context` 设置 `app.current_tenant`，
+//! RLS 策略据此在数据库层过滤数据。设置以 `SET LOCAL` 语义作用于单个操作的事务，
+//! 操作结束后自动复位。
+//!
+//! 注意：表的所有者默认不受 RLS 约束，实际使用时应以非所有者角色连接，
+//! 或通过 `SessionContext::role` 切换到受限角色；此处使用 FORCE ROW LEVEL SECURITY 演示。
+
+use rat_quickdb::*;
+use rat_quickdb::model::{Model, string_field};
+use rat_logger::debug;
+
+define_model! {
+    /// 租户文档
+    struct TenantDoc {
+        id: String,
+        tenant: String,
+        title: String,
+    }
+    collection = "rls_tenant_docs",
+    fields = {
+        id: string_field(None, None, None).required().unique(),
+        tenant: string_field(Some(50), None, None).required(),
+        title: string_field(Some(200), None, None).required(),
+    }
+}
+
+fn tenant(name: &str) -> SessionContext {
+    SessionContext::new().set("app.current_tenant", name)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    rat_logger::init();
+
+    println!("=== PostgreSQL 会话上下文 / RLS 示例 ===\n");
+
+    let config = postgres_config(
+        "default",
+        "172.16.0.23",
+        5432,
+        "testdb",
+        "testdb",
+        "testdb123456",
+        PoolConfig::default(),
+        Some(IdStrategy::Uuid),
+    )?;
+    add_database(config).await?;
+    let _ = rat_quickdb::drop_table("default", "rls_tenant_docs").await;
+
+    // 写入数据（尚未启用RLS，tenant 字段由调用方填写）
+    for (tenant_name, title) in [("acme", "acme 报表"), ("acme", "acme 合同"), ("globex", "globex 计划")] {
+        TenantDoc { id: String::new(), tenant: tenant_name.to_string(), title: title.to_string() }.save().await?;
+    }
+
+    // 启用RLS：只能看到 tenant 等于会话变量的行
+    if let DatabaseConnection::PostgreSQL(pg) = get_raw_connection(None).await? {
+        for sql in [
+            "ALTER TABLE rls_tenant_docs ENABLE ROW LEVEL SECURITY",
+            "ALTER TABLE rls_tenant_docs FORCE ROW LEVEL SECURITY",
+            "CREATE POLICY tenant_isolation ON rls_tenant_docs USING (tenant = current_setting('app.current_tenant', true))",
+        ] {
+            sqlx::query(sql).execute(&pg).await?;
+        }
+    }
+
+    for name in ["acme", "globex", "initech"] {
+        let docs = with_session_context(tenant(name), ModelManager::<TenantDoc>::find(vec![], None)).await?;
+        println!("租户 {:<8} 可见 {} 条: {:?}", name, docs.len(), docs.iter().map(|d| &d.title).collect::<Vec<_>>());
+    }
+
+    let _ = rat_quickdb::drop_table("default", "rls_tenant_docs").await;
+    println!("\n✅ 会话上下文示例完成！");
+    Ok(())
+}
@@ -54,6 +54,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Some(opts)
             },
             tls_config: None,
+            create_database_if_missing: false,
         })
         .pool(PoolConfig::builder()
             .max_connections(10)
@@ -82,6 +82,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     rat_quickdb::i18n::set_language("ja-JP");
     println!("   PostgreSQL設定不一致: {}", rat_quickdb::i18n::t("error.postgres_config_mismatch"));
 
+    println!();
+
+    // QuickDbError 的 Display 跟随当前语言，kind() 不受语言影响
+    println!("7. QuickDbError 多语言显示：");
+    let error = QuickDbError::AliasNotFound { alias: "analytics".to_string() };
+    for lang in ["zh-CN", "en-US", "ja-JP"] {
+        rat_quickdb::i18n::set_language(lang);
+        println!("   [{}] {} (kind: {:?})", lang, error, error.kind());
+    }
+
+    // 注册自定义语言的消息模板
+    rat_quickdb::i18n::register_message("error.alias_not_found", "de-DE", "Datenbank-Alias '{alias}' nicht gefunden");
+    rat_quickdb::i18n::set_language("de-DE");
+    println!("   [de-DE] {}", error);
+
     println!("\n=== 测试完成 ===");
 
     Ok(())
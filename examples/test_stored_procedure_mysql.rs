@@ -80,6 +80,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Some(opts)
             },
             tls_config: None,
+            create_database_if_missing: false,
         })
         .pool(pool_config)
         .alias("test_db")
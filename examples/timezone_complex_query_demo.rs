@@ -318,6 +318,10 @@ async fn main() -> QuickDbResult<()> {
             skip: 0,
         }),
         fields: vec![],
+        collation: None,
+        select_exprs: vec![],
+        workload: None,
+        cursor: None,
     };
 
     let large_event_result = ModelManager::<TimeZoneEvent>::find_with_groups(
@@ -313,6 +313,27 @@ class RatQuickDB:
                 "error": f"删除表失败: {str(e)}"
             }
 
+    def rename_table(self, from_table: str, to_table: str, alias: str = "default"):
+        """重命名数据表
+
+        Args:
+            from_table: 原表名
+            to_table: 新表名
+            alias: 数据库别名，默认为"default"
+
+        Returns:
+            重命名结果字典
+        """
+        bridge = self.get_bridge()
+        try:
+            result = bridge.rename_table(from_table, to_table, alias)
+            return result
+        except Exception as e:
+            return {
+                "success": False,
+                "error": f"重命名表失败: {str(e)}"
+            }
+
 
 # 全局应用实例
 _app = None
@@ -282,6 +282,8 @@ impl PyDbQueueBridge {
         // 解析ID策略，默认使用AutoIncrement
         let id_strategy_enum = match id_strategy.as_deref() {
             Some("Uuid") => IdStrategy::Uuid,
+            Some("UuidV7") => IdStrategy::UuidV7,
+            Some("Ulid") => IdStrategy::Ulid,
             Some("Snowflake") => IdStrategy::Snowflake { machine_id: 1, datacenter_id: 1 },
             Some("ObjectId") => IdStrategy::ObjectId,
             Some(custom) if custom.starts_with("Custom:") => {
@@ -493,6 +495,8 @@ impl PyDbQueueBridge {
         // 解析ID策略，默认使用AutoIncrement
         let id_strategy_enum = match id_strategy.as_deref() {
             Some("Uuid") => IdStrategy::Uuid,
+            Some("UuidV7") => IdStrategy::UuidV7,
+            Some("Ulid") => IdStrategy::Ulid,
             Some("Snowflake") => IdStrategy::Snowflake { machine_id: 1, datacenter_id: 1 },
             Some("ObjectId") => IdStrategy::ObjectId,
             Some(custom) if custom.starts_with("Custom:") => {
@@ -512,6 +516,7 @@ impl PyDbQueueBridge {
                 password,
                 ssl_opts: None,
                 tls_config: None,
+                create_database_if_missing: false,
             })
             .pool(pool_config)
             .alias(alias.clone())
@@ -588,6 +593,8 @@ impl PyDbQueueBridge {
         // 解析ID策略，默认使用AutoIncrement
         let id_strategy_enum = match id_strategy.as_deref() {
             Some("Uuid") => IdStrategy::Uuid,
+            Some("UuidV7") => IdStrategy::UuidV7,
+            Some("Ulid") => IdStrategy::Ulid,
             Some("Snowflake") => IdStrategy::Snowflake { machine_id: 1, datacenter_id: 1 },
             Some("ObjectId") => IdStrategy::ObjectId,
             Some(custom) if custom.starts_with("Custom:") => {
@@ -607,6 +614,7 @@ impl PyDbQueueBridge {
                 password,
                 ssl_mode: None,
                 tls_config: None,
+                create_database_if_missing: false,
             })
             .pool(pool_config)
             .alias(alias.clone())
@@ -677,6 +685,24 @@ impl PyDbQueueBridge {
         self.send_action_request("drop_table", &body)
     }
 
+    /// 重命名表
+    pub fn rename_table(
+        &self,
+        from: String,
+        to: String,
+        alias: Option<String>,
+    ) -> PyResult<String> {
+        self.check_initialized()?;
+
+        let body = serde_json::json!({
+            "from": from,
+            "to": to,
+            "alias": alias
+        }).to_string();
+
+        self.send_action_request("rename_table", &body)
+    }
+
     /// 创建表
     pub fn create_table(
         &self,
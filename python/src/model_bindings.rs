@@ -240,6 +240,7 @@ impl PyIndexDefinition {
                 fields,
                 unique,
                 name,
+                ..Default::default()
             },
         }
     }
@@ -310,6 +311,7 @@ impl PyModelMeta {
                 fields: field_map,
                 indexes: index_vec,
                 description,
+                soft_delete_field: None,
             },
         })
     }
@@ -6,7 +6,7 @@ use super::DatabaseAdapter;
 use crate::cache::CacheManager;
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
-use crate::model::{FieldType, FieldDefinition};
+use crate::model::{FieldType, FieldDefinition, IndexDefinition};
 use crate::pool::DatabaseConnection;
 use async_trait::async_trait;
 use serde_json::Value;
@@ -31,10 +31,35 @@ impl CachedDatabaseAdapter {
             cache_manager,
         }
     }
+
+    /// 清理单条记录缓存及表查询缓存
+    async fn invalidate_record_and_queries(&self, table: &str, id: &DataValue) {
+        match IdType::from_data_value(id) {
+            Some(id_value) => {
+                if let Err(e) = self.cache_manager.invalidate_record(table, &id_value).await {
+                    warn!("清理记录缓存失败: {}", e);
+                }
+            }
+            None => {
+                warn!("无法将DataValue转换为IdType: {:?}", id);
+                return;
+            }
+        }
+
+        if let Err(e) = self.cache_manager.clear_table_query_cache(table).await {
+            warn!("清理表查询缓存失败: {}", e);
+        }
+
+        debug!("已清理记录和查询缓存: table={}, id={:?}", table, id);
+    }
 }
 
 #[async_trait]
 impl DatabaseAdapter for CachedDatabaseAdapter {
+    fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+
     /// 创建记录 - 创建成功后智能清理相关缓存
     async fn create(
         &self,
@@ -56,6 +81,83 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         result
     }
 
+    /// 批量创建记录 - 无论成功与否都清理查询缓存（失败时可能已写入部分记录）
+    async fn create_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        data_list: &[HashMap<String, DataValue>],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let result = self.inner.create_many(connection, table, data_list, id_strategy).await;
+
+        if let Err(e) = self.cache_manager.clear_table_query_cache(table).await {
+            warn!("清理表查询缓存失败: {}", e);
+        }
+        debug!("批量创建后已清理表查询缓存: table={}, count={}", table, data_list.len());
+
+        result
+    }
+
+    /// 批量插入或更新记录 - 无论成功与否都清理整张表的缓存
+    ///
+    /// 被更新记录的ID事先未知（可能按非ID字段冲突），因此记录缓存也一并清理
+    async fn upsert_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        rows: &[HashMap<String, DataValue>],
+        conflict_fields: &[String],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<UpsertResult> {
+        let result = self.inner.upsert_many(connection, table, rows, conflict_fields, id_strategy).await;
+
+        if let Err(e) = self.cache_manager.invalidate_table(table).await {
+            warn!("清理表缓存失败: {}", e);
+        }
+        debug!("批量插入或更新后已清理表缓存: table={}, count={}", table, rows.len());
+
+        result
+    }
+
+    /// 插入或更新单条记录 - 与批量插入或更新一样清理整张表的缓存
+    async fn upsert(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        data: &HashMap<String, DataValue>,
+        conflict_fields: &[String],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<UpsertOutcome> {
+        let result = self.inner.upsert(connection, table, data, conflict_fields, id_strategy).await;
+
+        if let Err(e) = self.cache_manager.invalidate_table(table).await {
+            warn!("清理表缓存失败: {}", e);
+        }
+        debug!("插入或更新后已清理表缓存: table={}", table);
+
+        result
+    }
+
+    /// 事务性写入多张表 - 无论成功与否都清理涉及表的查询缓存
+    async fn seed(
+        &self,
+        connection: &DatabaseConnection,
+        fixtures: &[(String, Vec<HashMap<String, DataValue>>)],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<Vec<(String, u64)>> {
+        let result = self.inner.seed(connection, fixtures, id_strategy).await;
+
+        for (table, _) in fixtures {
+            if let Err(e) = self.cache_manager.clear_table_query_cache(table).await {
+                warn!("清理表查询缓存失败: {}", e);
+            }
+        }
+        debug!("初始数据写入后已清理查询缓存: tables={}", fixtures.len());
+
+        result
+    }
+
     /// 根据ID查找记录 - 先检查缓存，缓存未命中时查询数据库并缓存结果
     async fn find_by_id(
         &self,
@@ -63,11 +165,15 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         table: &str,
         id: &DataValue,
     ) -> QuickDbResult<Option<DataValue>> {
+        // 携带会话上下文时结果可能因租户而异，不读写缓存
+        if current_session_context().is_some() {
+            return self.inner.find_by_id(connection, table, id).await;
+        }
+
         // 将DataValue转换为IdType
-        let id_type = match id {
-            DataValue::Int(n) => IdType::Number(*n),
-            DataValue::String(s) => IdType::String(s.clone()),
-            _ => {
+        let id_type = match IdType::from_data_value(id) {
+            Some(id_type) => id_type,
+            None => {
                 warn!("无法将DataValue转换为IdType: {:?}", id);
                 return self.inner.find_by_id(connection, table, id).await;
             }
@@ -100,6 +206,68 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         result
     }
 
+    /// 根据ID列表批量查找记录 - 先逐条检查缓存，未命中的ID合并为一次数据库查询并缓存结果
+    async fn find_by_ids(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        ids: &[DataValue],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        // 携带会话上下文时结果可能因租户而异，不读写缓存
+        if current_session_context().is_some() {
+            return self.inner.find_by_ids(connection, table, ids).await;
+        }
+
+        let mut records = Vec::with_capacity(ids.len());
+        let mut missing = Vec::new();
+        let mut missing_ids: HashMap<String, IdType> = HashMap::new();
+        for id in ids {
+            let id_type = match IdType::from_data_value(id) {
+                Some(id_type) => id_type,
+                None => {
+                    warn!("无法将DataValue转换为IdType: {:?}", id);
+                    missing.push(id.clone());
+                    continue;
+                }
+            };
+            match self.cache_manager.get_cached_record(table, &id_type).await {
+                Ok(Some(cached_result)) => records.push(cached_result),
+                Ok(None) => {
+                    missing_ids.insert(id_type.to_string(), id_type);
+                    missing.push(id.clone());
+                }
+                Err(e) => {
+                    warn!("缓存查询失败: {}, 继续查询数据库", e);
+                    missing_ids.insert(id_type.to_string(), id_type);
+                    missing.push(id.clone());
+                }
+            }
+        }
+        debug!("批量查找缓存命中: 表={}, 命中={}, 未命中={}", table, records.len(), missing.len());
+
+        if missing.is_empty() {
+            return Ok(records);
+        }
+
+        let fetched = self.inner.find_by_ids(connection, table, &missing).await?;
+        for record in fetched {
+            let id_type = match &record {
+                DataValue::Object(map) => map.get("id").or_else(|| map.get("_id"))
+                    .and_then(IdType::from_data_value)
+                    .and_then(|id| missing_ids.get(&id.to_string())),
+                _ => None,
+            };
+            if let Some(id_type) = id_type {
+                if let Err(e) = self.cache_manager.cache_record(table, id_type, &record).await {
+                    warn!("缓存记录失败: {}", e);
+                }
+            }
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+
     /// 查找记录 - 内部统一使用 find_with_groups 实现
     async fn find(
         &self,
@@ -109,18 +277,8 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         options: &QueryOptions,
     ) -> QuickDbResult<Vec<DataValue>> {
         // 将简单条件转换为条件组合（AND逻辑）
-        let condition_groups = if conditions.is_empty() {
-            vec![]
-        } else {
-            let group_conditions = conditions.iter()
-                .map(|c| QueryConditionGroup::Single(c.clone()))
-                .collect();
-            vec![QueryConditionGroup::Group {
-                operator: LogicalOperator::And,
-                conditions: group_conditions,
-            }]
-        };
-        
+        let condition_groups = CacheManager::find_condition_groups(conditions);
+
         // 统一使用 find_with_groups 实现
         self.find_with_groups(connection, table, &condition_groups, options).await
     }
@@ -133,6 +291,11 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         condition_groups: &[QueryConditionGroup],
         options: &QueryOptions,
     ) -> QuickDbResult<Vec<DataValue>> {
+        // 携带会话上下文时结果可能因租户而异，不读写缓存
+        if current_session_context().is_some() {
+            return self.inner.find_with_groups(connection, table, condition_groups, options).await;
+        }
+
         // 生成条件组合查询缓存键
         let cache_key = self.cache_manager.generate_condition_groups_cache_key(table, condition_groups, options);
         
@@ -225,10 +388,9 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         // 更新成功后精确清理相关缓存
         if let Ok(true) = result {
             // 清理特定记录的缓存
-            let id_value = match id {
-                DataValue::Int(n) => IdType::Number(*n),
-                DataValue::String(s) => IdType::String(s.clone()),
-                _ => {
+            let id_value = match IdType::from_data_value(id) {
+                Some(id_value) => id_value,
+                None => {
                     warn!("无法将DataValue转换为IdType: {:?}", id);
                     return result;
                 }
@@ -250,6 +412,25 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         result
     }
 
+    /// 根据ID更新记录并返回匹配/修改数 - 匹配到记录时清理相关缓存
+    async fn update_by_id_detailed(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        id: &DataValue,
+        data: &HashMap<String, DataValue>,
+    ) -> QuickDbResult<WriteResult> {
+        let result = self.inner.update_by_id_detailed(connection, table, id, data).await;
+
+        if let Ok(write_result) = &result {
+            if write_result.modified > 0 {
+                self.invalidate_record_and_queries(table, id).await;
+            }
+        }
+
+        result
+    }
+
     /// 删除记录 - 删除成功后智能清理相关缓存
     async fn delete(
         &self,
@@ -290,10 +471,9 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         // 删除成功后精确清理相关缓存
         if let Ok(true) = result {
             // 清理特定记录的缓存
-            let id_value = match id {
-                DataValue::Int(n) => IdType::Number(*n),
-                DataValue::String(s) => IdType::String(s.clone()),
-                _ => {
+            let id_value = match IdType::from_data_value(id) {
+                Some(id_value) => id_value,
+                None => {
                     warn!("无法将DataValue转换为IdType: {:?}", id);
                     return result;
                 }
@@ -315,6 +495,88 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         result
     }
 
+    /// 根据ID删除记录并返回匹配/删除数 - 删除成功后清理相关缓存
+    async fn delete_by_id_detailed(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        id: &DataValue,
+    ) -> QuickDbResult<WriteResult> {
+        let result = self.inner.delete_by_id_detailed(connection, table, id).await;
+
+        if let Ok(write_result) = &result {
+            if write_result.modified > 0 {
+                self.invalidate_record_and_queries(table, id).await;
+            }
+        }
+
+        result
+    }
+
+    /// 根据ID列表批量删除记录 - 删除成功后逐条清理记录缓存并清理查询缓存
+    async fn delete_by_ids(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        ids: &[DataValue],
+    ) -> QuickDbResult<u64> {
+        let result = self.inner.delete_by_ids(connection, table, ids).await;
+
+        if let Ok(deleted_count) = result {
+            if deleted_count > 0 {
+                let id_values: Vec<IdType> = ids.iter()
+                    .filter_map(|id| {
+                        let id_value = IdType::from_data_value(id);
+                        if id_value.is_none() {
+                            warn!("无法将DataValue转换为IdType: {:?}", id);
+                        }
+                        id_value
+                    })
+                    .collect();
+
+                if let Err(e) = self.cache_manager.clear_records_batch(table, &id_values).await {
+                    warn!("批量清理记录缓存失败: {}", e);
+                }
+                if let Err(e) = self.cache_manager.clear_table_query_cache(table).await {
+                    warn!("清理表查询缓存失败: {}", e);
+                }
+                debug!("已清理记录和查询缓存: table={}, ids={}", table, id_values.len());
+            }
+        }
+
+        result
+    }
+
+    /// 读取二进制片段 - 直接调用内部适配器，不读写缓存
+    async fn read_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        offset: u64,
+        len: u64,
+        gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<Option<Vec<u8>>> {
+        self.inner.read_blob_chunk(connection, location, offset, len, gridfs_bucket).await
+    }
+
+    /// 写入二进制片段 - 写入成功后清理该记录及表查询缓存
+    async fn write_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        data: &[u8],
+        append: bool,
+        gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<bool> {
+        let result = self.inner.write_blob_chunk(connection, location, data, append, gridfs_bucket).await;
+
+        if let Ok(true) = result {
+            self.invalidate_record_and_queries(&location.table, &location.id).await;
+        }
+
+        result
+    }
+
     /// 统计记录数量 - 直接调用内部适配器，不缓存统计结果
     async fn count(
         &self,
@@ -326,7 +588,50 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         self.inner.count(connection, table, conditions).await
     }
 
+    /// 估算记录数量 - 直接调用内部适配器，不缓存统计结果
+    async fn estimated_count(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<u64> {
+        self.inner.estimated_count(connection, table).await
+    }
+
     /// 检查记录是否存在 - 直接调用内部适配器，不缓存存在性检查结果
+    async fn time_bucket(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        field: &str,
+        interval: Interval,
+        conditions: &[QueryCondition],
+    ) -> QuickDbResult<Vec<(DataValue, u64)>> {
+        // 按时间段统计不缓存，直接调用内部适配器
+        self.inner.time_bucket(connection, table, field, interval, conditions).await
+    }
+
+    async fn aggregate(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        aggregations: &[Aggregation],
+        conditions: &[QueryCondition],
+        group_by: &[String],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        // 聚合统计不缓存，直接调用内部适配器
+        self.inner.aggregate(connection, table, aggregations, conditions, group_by).await
+    }
+
+    async fn mongo_aggregate(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        pipeline: &[serde_json::Value],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        // 聚合管道的结果形状不固定，不缓存
+        self.inner.mongo_aggregate(connection, table, pipeline).await
+    }
+
     async fn exists(
         &self,
         connection: &DatabaseConnection,
@@ -355,11 +660,10 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         connection: &DatabaseConnection,
         table: &str,
         index_name: &str,
-        fields: &[String],
-        unique: bool,
+        index: &IndexDefinition,
     ) -> QuickDbResult<()> {
         // 索引操作不缓存，直接调用内部适配器
-        self.inner.create_index(connection, table, index_name, fields, unique).await
+        self.inner.create_index(connection, table, index_name, index).await
     }
 
     /// 检查表是否存在 - 直接调用内部适配器
@@ -371,6 +675,39 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         self.inner.table_exists(connection, table).await
     }
 
+    /// 获取表列名 - 直接调用内部适配器
+    async fn get_table_columns(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        self.inner.get_table_columns(connection, table).await
+    }
+
+    /// 获取索引名 - 直接调用内部适配器
+    async fn list_index_names(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        self.inner.list_index_names(connection, table).await
+    }
+
+    /// 添加列 - 结构变化后清理该表的查询缓存
+    async fn add_column(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        column: &str,
+        field_definition: &FieldDefinition,
+    ) -> QuickDbResult<()> {
+        self.inner.add_column(connection, table, column, field_definition).await?;
+        if let Err(e) = self.cache_manager.clear_table_query_cache(table).await {
+            warn!("添加列后清理查询缓存失败: {}", e);
+        }
+        Ok(())
+    }
+
     /// 删除表 - 删除成功后清理所有相关缓存
     async fn drop_table(
         &self,
@@ -390,6 +727,27 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         result
     }
 
+    /// 重命名表 - 成功后清理新旧两个表名的记录和查询缓存
+    async fn rename_table(
+        &self,
+        connection: &DatabaseConnection,
+        from: &str,
+        to: &str,
+    ) -> QuickDbResult<()> {
+        let result = self.inner.rename_table(connection, from, to).await;
+
+        if result.is_ok() {
+            for table in [from, to] {
+                if let Err(e) = self.cache_manager.invalidate_table(table).await {
+                    warn!("清理表缓存失败: {}", e);
+                }
+            }
+            debug!("已清理表缓存: from={}, to={}", from, to);
+        }
+
+        result
+    }
+
     async fn get_server_version(
         &self,
         connection: &DatabaseConnection,
@@ -419,4 +777,50 @@ impl DatabaseAdapter for CachedDatabaseAdapter {
         // 存储过程执行不缓存，直接调用内部适配器
         self.inner.execute_stored_procedure(connection, procedure_name, database, params).await
     }
+
+    /// 订阅数据变更 - 直接调用内部适配器
+    async fn watch(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<ChangeStream> {
+        self.inner.watch(connection, table).await
+    }
+
+    /// 执行SQL脚本 - 无法判断涉及的表，执行后清空全部缓存
+    async fn execute_batch(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+    ) -> QuickDbResult<u64> {
+        let result = self.inner.execute_batch(connection, sql).await?;
+        if let Err(e) = self.cache_manager.clear_all().await {
+            warn!("执行SQL脚本后清空缓存失败: {}", e);
+        }
+        Ok(result)
+    }
+
+    /// 执行原始SQL查询 - 无法判断涉及的表，不缓存，直接调用内部适配器
+    async fn query_raw(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+        params: &[DataValue],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        self.inner.query_raw(connection, sql, params).await
+    }
+
+    /// 执行原始SQL写入 - 无法判断涉及的表，执行后清空全部缓存
+    async fn execute_raw_update(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+        params: &[DataValue],
+    ) -> QuickDbResult<u64> {
+        let result = self.inner.execute_raw_update(connection, sql, params).await?;
+        if let Err(e) = self.cache_manager.clear_all().await {
+            warn!("执行原始SQL写入后清空缓存失败: {}", e);
+        }
+        Ok(result)
+    }
 }
\ No newline at end of file
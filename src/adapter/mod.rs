@@ -4,7 +4,7 @@
 
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
-use crate::model::{FieldType, FieldDefinition};
+use crate::model::{FieldType, FieldDefinition, IndexDefinition};
 use crate::pool::DatabaseConnection;
 use async_trait::async_trait;
 
@@ -21,11 +21,12 @@ mod mysql;
 mod mongodb;
 mod query_builder;
 mod cached;
+pub(crate) mod transaction;
 mod postgres_utils;
 
 // 条件导出适配器
 #[cfg(feature = "sqlite-support")]
-pub use sqlite::SqliteAdapter;
+pub use sqlite::{SqliteAdapter, DEFAULT_BUSY_RETRY_COUNT};
 #[cfg(feature = "postgres-support")]
 pub use postgres::PostgresAdapter;
 #[cfg(feature = "mysql-support")]
@@ -34,11 +35,14 @@ pub use mysql::MysqlAdapter;
 pub use mongodb::MongoAdapter;
 pub use query_builder::*;
 pub use cached::CachedDatabaseAdapter;
-pub use postgres_utils::{build_json_query_condition, convert_to_jsonb_value};
+pub use postgres_utils::{build_json_query_condition, convert_to_jsonb_value, postgres_change_trigger_sql, postgres_change_channel};
 
 /// 数据库适配器trait，定义统一的数据库操作接口
 #[async_trait]
 pub trait DatabaseAdapter: Send + Sync {
+    /// 适配器支持的功能
+    fn capabilities(&self) -> Capabilities;
+
     /// 创建记录
     async fn create(
         &self,
@@ -48,6 +52,105 @@ pub trait DatabaseAdapter: Send + Sync {
         id_strategy: &IdStrategy,
     ) -> QuickDbResult<DataValue>;
 
+    /// 批量创建记录，按输入顺序返回每条记录的创建结果
+    ///
+    /// 默认实现逐条调用 `create`，整批只占用一次连接池往返；SQL适配器按批生成多行 `INSERT`，
+    /// MongoDB适配器使用 `insert_many`
+    async fn create_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        data_list: &[HashMap<String, DataValue>],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let mut results = Vec::with_capacity(data_list.len());
+        for data in data_list {
+            results.push(self.create(connection, table, data, id_strategy).await?);
+        }
+        Ok(results)
+    }
+
+    /// 批量插入或更新记录，`conflict_fields` 上已存在的记录用新值覆盖其余字段
+    ///
+    /// 所有记录的字段集合必须一致；记录较多时按数据库的参数上限拆成多条语句依次执行
+    async fn upsert_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        rows: &[HashMap<String, DataValue>],
+        conflict_fields: &[String],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<UpsertResult>;
+
+    /// 插入或更新单条记录，返回插入了新记录还是更新了已有记录
+    ///
+    /// 默认实现以单条记录调用 `upsert_many`，适用于能够区分插入与更新的数据库；
+    /// 无法区分的数据库需在事务中先检查冲突字段上是否已有记录
+    async fn upsert(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        data: &HashMap<String, DataValue>,
+        conflict_fields: &[String],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<UpsertOutcome> {
+        let result = self.upsert_many(connection, table, std::slice::from_ref(data), conflict_fields, id_strategy).await?;
+        match result.inserted {
+            Some(0) => Ok(UpsertOutcome::Updated),
+            Some(_) => Ok(UpsertOutcome::Inserted),
+            None => Err(QuickDbError::UnsupportedDatabase {
+                db_type: "当前数据库无法区分插入与更新".to_string(),
+            }),
+        }
+    }
+
+    /// 在单个事务中按顺序写入多张表的记录，任一记录失败时整体回滚，按写入顺序返回每张表插入的行数
+    ///
+    /// 默认实现先按注册的模型元数据补建缺失的表，再开启适配器事务逐条调用 `create`；
+    /// 不支持事务的数据库（如 MongoDB）返回错误
+    async fn seed(
+        &self,
+        connection: &DatabaseConnection,
+        fixtures: &[(String, Vec<HashMap<String, DataValue>>)],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<Vec<(String, u64)>> {
+        transaction::AdapterTransaction::ensure_supported(connection)?;
+
+        // 建表是DDL，需在事务外完成
+        for (table, _) in fixtures {
+            if !self.table_exists(connection, table).await? {
+                if let Some(model_meta) = crate::manager::get_model(table) {
+                    self.create_table(connection, table, &model_meta.fields, id_strategy).await?;
+                }
+            }
+        }
+
+        let transaction = transaction::AdapterTransaction::begin(connection, crate::transaction::IsolationLevel::BackendDefault).await?;
+        let result = transaction.scope(async {
+            let mut inserted = Vec::with_capacity(fixtures.len());
+            for (table, records) in fixtures {
+                for record in records {
+                    self.create(connection, table, record, id_strategy).await?;
+                }
+                inserted.push((table.clone(), records.len() as u64));
+            }
+            Ok(inserted)
+        }).await;
+
+        match result {
+            Ok(inserted) => {
+                transaction.commit().await?;
+                Ok(inserted)
+            }
+            Err(e) => {
+                if let Err(rollback_error) = transaction.rollback().await {
+                    rat_logger::error!("回滚初始数据事务失败: {}", rollback_error);
+                }
+                Err(e)
+            }
+        }
+    }
+
     /// 根据ID查找记录
     async fn find_by_id(
         &self,
@@ -56,6 +159,26 @@ pub trait DatabaseAdapter: Send + Sync {
         id: &DataValue,
     ) -> QuickDbResult<Option<DataValue>>;
 
+    /// 根据ID列表批量查找记录，不存在的ID不出现在结果中
+    ///
+    /// 默认实现为单条 `id IN (...)` 查询语句
+    async fn find_by_ids(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        ids: &[DataValue],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let conditions = vec![QueryCondition {
+            field: "id".to_string(),
+            operator: QueryOperator::In,
+            value: DataValue::Array(ids.to_vec()),
+        }];
+        self.find(connection, table, &conditions, &QueryOptions::default()).await
+    }
+
     /// 查找记录
     async fn find(
         &self,
@@ -101,6 +224,25 @@ pub trait DatabaseAdapter: Send + Sync {
         data: &HashMap<String, DataValue>,
     ) -> QuickDbResult<bool>;
 
+    /// 根据ID更新记录，返回匹配数与修改数
+    ///
+    /// 默认实现基于 `update` 的影响行数，SQL 数据库的匹配数与修改数相同
+    async fn update_by_id_detailed(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        id: &DataValue,
+        data: &HashMap<String, DataValue>,
+    ) -> QuickDbResult<WriteResult> {
+        let conditions = vec![QueryCondition {
+            field: "id".to_string(),
+            operator: QueryOperator::Eq,
+            value: id.clone(),
+        }];
+        let affected = self.update(connection, table, &conditions, data).await?;
+        Ok(WriteResult::uniform(affected))
+    }
+
     /// 删除记录
     async fn delete(
         &self,
@@ -117,6 +259,77 @@ pub trait DatabaseAdapter: Send + Sync {
         id: &DataValue,
     ) -> QuickDbResult<bool>;
 
+    /// 根据ID删除记录，返回匹配数与删除数
+    async fn delete_by_id_detailed(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        id: &DataValue,
+    ) -> QuickDbResult<WriteResult> {
+        let conditions = vec![QueryCondition {
+            field: "id".to_string(),
+            operator: QueryOperator::Eq,
+            value: id.clone(),
+        }];
+        let affected = self.delete(connection, table, &conditions).await?;
+        Ok(WriteResult::uniform(affected))
+    }
+
+    /// 根据ID列表批量删除记录，返回删除数量
+    ///
+    /// 默认实现为单条 `id IN (...)` 删除语句
+    async fn delete_by_ids(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        ids: &[DataValue],
+    ) -> QuickDbResult<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let conditions = vec![QueryCondition {
+            field: "id".to_string(),
+            operator: QueryOperator::In,
+            value: DataValue::Array(ids.to_vec()),
+        }];
+        self.delete(connection, table, &conditions).await
+    }
+
+    /// 读取二进制列中从 `offset` 字节开始的至多 `len` 个字节
+    ///
+    /// 记录不存在时返回 `None`，列为 NULL 时返回空数据。MongoDB 从 `gridfs_bucket` 指定的
+    /// GridFS 存储桶读取，未配置时返回不支持的错误
+    async fn read_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        offset: u64,
+        len: u64,
+        gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<Option<Vec<u8>>> {
+        let _ = (connection, location, offset, len, gridfs_bucket);
+        Err(QuickDbError::UnsupportedDatabase {
+            db_type: "当前数据库不支持二进制流".to_string(),
+        })
+    }
+
+    /// 写入二进制列的一段数据，返回记录是否存在
+    ///
+    /// `append` 为 `false` 时覆盖原值，否则追加到现有数据之后
+    async fn write_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        data: &[u8],
+        append: bool,
+        gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<bool> {
+        let _ = (connection, location, data, append, gridfs_bucket);
+        Err(QuickDbError::UnsupportedDatabase {
+            db_type: "当前数据库不支持二进制流".to_string(),
+        })
+    }
+
     /// 统计记录数量
     async fn count(
         &self,
@@ -125,6 +338,57 @@ pub trait DatabaseAdapter: Send + Sync {
         conditions: &[QueryCondition],
     ) -> QuickDbResult<u64>;
 
+    /// 按时间段统计记录数量
+    ///
+    /// 时间字段按UTC截断到 `interval` 的起点后分组计数，结果按时间段升序排列，
+    /// 时间段为 `DataValue::DateTime`；时间字段为空的记录不计入
+    async fn time_bucket(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        field: &str,
+        interval: Interval,
+        conditions: &[QueryCondition],
+    ) -> QuickDbResult<Vec<(DataValue, u64)>>;
+
+    /// 聚合统计
+    ///
+    /// 按 `group_by` 分组计算各聚合项，每组返回一个 `DataValue::Object`，键为分组字段名和聚合项的 `alias`，
+    /// 存在分组字段时按分组字段升序排列；没有分组字段时总是返回一行，没有匹配记录时计数为 0、其他聚合为 null
+    async fn aggregate(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        aggregations: &[Aggregation],
+        conditions: &[QueryCondition],
+        group_by: &[String],
+    ) -> QuickDbResult<Vec<DataValue>>;
+
+    /// 原样执行MongoDB聚合管道，管道为JSON对象数组，每个输出文档以 `DataValue::Object` 返回
+    ///
+    /// 默认不支持，只有 MongoDB 实现
+    async fn mongo_aggregate(
+        &self,
+        _connection: &DatabaseConnection,
+        table: &str,
+        _pipeline: &[serde_json::Value],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        Err(QuickDbError::UnsupportedDatabase {
+            db_type: format!("聚合管道只支持MongoDB，无法在 {} 上执行", table),
+        })
+    }
+
+    /// 估算整张表的记录数量，结果可能与精确计数略有出入
+    ///
+    /// 默认实现为不带条件的精确计数；MongoDB 读取集合元数据，不扫描文档
+    async fn estimated_count(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<u64> {
+        self.count(connection, table, &[]).await
+    }
+
     /// 检查记录是否存在
     async fn exists(
         &self,
@@ -148,8 +412,7 @@ pub trait DatabaseAdapter: Send + Sync {
         connection: &DatabaseConnection,
         table: &str,
         index_name: &str,
-        fields: &[String],
-        unique: bool,
+        index: &IndexDefinition,
     ) -> QuickDbResult<()>;
 
     /// 检查表是否存在
@@ -159,34 +422,131 @@ pub trait DatabaseAdapter: Send + Sync {
         table: &str,
     ) -> QuickDbResult<bool>;
 
+    /// 获取表的列名列表
+    ///
+    /// MongoDB等无固定结构的数据库没有列的概念，默认返回空列表
+    async fn get_table_columns(
+        &self,
+        _connection: &DatabaseConnection,
+        _table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// 获取表/集合上已存在的索引名
+    async fn list_index_names(
+        &self,
+        _connection: &DatabaseConnection,
+        _table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// 为已存在的表添加列
+    ///
+    /// 新增列总是允许NULL，避免已有数据违反约束；无固定结构的数据库默认不做任何操作
+    async fn add_column(
+        &self,
+        _connection: &DatabaseConnection,
+        _table: &str,
+        _column: &str,
+        _field_definition: &FieldDefinition,
+    ) -> QuickDbResult<()> {
+        Ok(())
+    }
+
     /// 删除表/集合
     async fn drop_table(
+        &self,
+        _connection: &DatabaseConnection,
+        _table: &str,
+    ) -> QuickDbResult<()>;
+
+    /// 重命名表/集合，原表不存在或目标名称已被占用时返回错误
+    async fn rename_table(
         &self,
         connection: &DatabaseConnection,
-        table: &str,
+        from: &str,
+        to: &str,
     ) -> QuickDbResult<()>;
 
     /// 获取数据库服务器版本信息
     async fn get_server_version(
         &self,
-        connection: &DatabaseConnection,
+        _connection: &DatabaseConnection,
     ) -> QuickDbResult<String>;
 
     /// 创建存储过程
     async fn create_stored_procedure(
         &self,
-        connection: &DatabaseConnection,
+        _connection: &DatabaseConnection,
         config: &crate::stored_procedure::StoredProcedureConfig,
     ) -> QuickDbResult<crate::stored_procedure::StoredProcedureCreateResult>;
 
     /// 执行存储过程查询
     async fn execute_stored_procedure(
         &self,
-        connection: &DatabaseConnection,
+        _connection: &DatabaseConnection,
         procedure_name: &str,
         database: &str,
         params: Option<std::collections::HashMap<String, crate::types::DataValue>>,
     ) -> QuickDbResult<crate::stored_procedure::StoredProcedureQueryResult>;
+
+    /// 订阅表/集合的数据变更
+    ///
+    /// 默认不支持，仅PostgreSQL（LISTEN/NOTIFY）和MongoDB（Change Stream）提供实现
+    async fn watch(
+        &self,
+        _connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<ChangeStream> {
+        Err(QuickDbError::UnsupportedDatabase {
+            db_type: format!("当前数据库不支持变更订阅: {}", table),
+        })
+    }
+
+    /// 按顺序执行包含多条语句的SQL脚本（如迁移脚本），返回执行的语句数
+    ///
+    /// 支持事务的数据库在同一个事务中执行，任一语句失败时整体回滚。
+    /// 默认不支持，MongoDB 等非SQL数据库返回错误
+    async fn execute_batch(
+        &self,
+        _connection: &DatabaseConnection,
+        _sql: &str,
+    ) -> QuickDbResult<u64> {
+        Err(QuickDbError::UnsupportedDatabase {
+            db_type: "当前数据库不支持执行SQL脚本".to_string(),
+        })
+    }
+
+    /// 执行原始SQL查询，每行结果以 `DataValue::Object` 返回
+    ///
+    /// 参数按占位符顺序绑定（SQLite/MySQL 使用 `?`，PostgreSQL 使用 `$1`、`$2`...）。
+    /// 默认不支持，MongoDB 等非SQL数据库返回错误
+    async fn query_raw(
+        &self,
+        _connection: &DatabaseConnection,
+        sql: &str,
+        _params: &[DataValue],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        Err(QuickDbError::UnsupportedDatabase {
+            db_type: format!("当前数据库不支持原始SQL查询: {}", sql),
+        })
+    }
+
+    /// 执行原始SQL写入语句（INSERT/UPDATE/DELETE等），返回受影响的行数
+    ///
+    /// 参数绑定规则与 `query_raw` 相同。默认不支持，MongoDB 等非SQL数据库返回错误
+    async fn execute_raw_update(
+        &self,
+        _connection: &DatabaseConnection,
+        sql: &str,
+        _params: &[DataValue],
+    ) -> QuickDbResult<u64> {
+        Err(QuickDbError::UnsupportedDatabase {
+            db_type: format!("当前数据库不支持原始SQL写入: {}", sql),
+        })
+    }
 }
 
 /// 根据数据库类型创建适配器
@@ -206,6 +566,57 @@ pub fn create_adapter(db_type: &DatabaseType) -> QuickDbResult<Box<dyn DatabaseA
     }
 }
 
+/// 按数据库配置创建适配器
+///
+/// MongoDB按配置设置主键在结果中的字段名，MySQL/PostgreSQL按配置设置日期时间列精度
+pub(crate) fn create_configured_adapter(db_config: &crate::types::DatabaseConfig) -> QuickDbResult<Box<dyn DatabaseAdapter>> {
+    match db_config.db_type {
+        #[cfg(feature = "mongodb-support")]
+        DatabaseType::MongoDB => Ok(Box::new(
+            MongoAdapter::new()
+                .with_id_field(db_config.mongo_id_field.unwrap_or_default())
+                .with_null_matching(db_config.mongo_null_matching.unwrap_or_default()),
+        )),
+        #[cfg(feature = "mysql-support")]
+        DatabaseType::MySQL => Ok(Box::new(
+            MysqlAdapter::new()
+                .with_datetime_precision(db_config.datetime_precision)
+                .with_json_text_detection(db_config.mysql_json_text_detection),
+        )),
+        #[cfg(feature = "postgres-support")]
+        DatabaseType::PostgreSQL => Ok(Box::new(
+            PostgresAdapter::new().with_datetime_precision(db_config.datetime_precision),
+        )),
+        _ => create_adapter(&db_config.db_type),
+    }
+}
+
+/// 按连接配置在服务器上创建缺失的数据库
+///
+/// 只在添加数据库时、建立任何连接池之前执行一次；未开启 `create_database_if_missing` 或其他数据库类型直接返回
+pub(crate) async fn create_database_if_missing(db_config: &crate::types::DatabaseConfig) -> QuickDbResult<()> {
+    match &db_config.connection {
+        #[cfg(feature = "postgres-support")]
+        crate::types::ConnectionConfig::PostgreSQL { host, port, database, username, password, create_database_if_missing: true, .. } => {
+            let maintenance_url = format!(
+                "postgresql://{}:{}@{}:{}/postgres?application_name={}",
+                username,
+                urlencoding::encode(password),
+                host,
+                port,
+                urlencoding::encode(&db_config.effective_application_name())
+            );
+            postgres::schema::create_database_if_missing(&maintenance_url, database).await
+        }
+        #[cfg(feature = "mysql-support")]
+        crate::types::ConnectionConfig::MySQL { host, port, database, username, password, create_database_if_missing: true, .. } => {
+            let server_url = format!("mysql://{}:{}@{}:{}", username, urlencoding::encode(password), host, port);
+            mysql::schema::create_database_if_missing(&server_url, database).await
+        }
+        _ => Ok(()),
+    }
+}
+
 /// 根据数据库类型和缓存管理器创建带缓存的适配器
 pub fn create_adapter_with_cache(
     db_type: &DatabaseType,
@@ -13,6 +13,10 @@ pub struct MongoAdapter {
     creation_locks: Arc<Mutex<HashMap<String, ()>>>,
     /// 存储过程映射表，存储已创建的存储过程信息
     pub(crate) stored_procedures: Arc<Mutex<HashMap<String, crate::stored_procedure::StoredProcedureInfo>>>,
+    /// 主键 `_id` 在查询结果中使用的字段名
+    pub(crate) id_field: crate::types::MongoIdField,
+    /// 空值条件的匹配语义
+    pub(crate) null_matching: crate::types::MongoNullMatching,
 }
 
 impl MongoAdapter {
@@ -21,9 +25,23 @@ impl MongoAdapter {
         Self {
             creation_locks: Arc::new(Mutex::new(HashMap::new())),
             stored_procedures: Arc::new(Mutex::new(HashMap::new())),
+            id_field: crate::types::MongoIdField::default(),
+            null_matching: crate::types::MongoNullMatching::default(),
         }
     }
 
+    /// 设置主键 `_id` 在查询结果中使用的字段名
+    pub fn with_id_field(mut self, id_field: crate::types::MongoIdField) -> Self {
+        self.id_field = id_field;
+        self
+    }
+
+    /// 设置空值条件的匹配语义
+    pub fn with_null_matching(mut self, null_matching: crate::types::MongoNullMatching) -> Self {
+        self.null_matching = null_matching;
+        self
+    }
+
     /// 获取表创建锁
     pub(crate) async fn acquire_table_lock(&self, table: &str) -> tokio::sync::MutexGuard<'_, HashMap<String, ()>> {
         let mut locks = self.creation_locks.lock().await;
@@ -529,4 +547,44 @@ impl MongoAdapter {
             })
         }
     }
+
+    /// 原样执行MongoDB聚合管道，每个输出文档以 `DataValue::Object` 返回
+    ///
+    /// 可以使用 `$lookup`、`$unwind`、`$facet` 等任意阶段，结果按通用的 BSON → DataValue 规则转换
+    pub async fn aggregate_pipeline(
+        &self,
+        connection: &crate::pool::DatabaseConnection,
+        collection_name: &str,
+        pipeline: Vec<mongodb::bson::Document>,
+    ) -> crate::error::QuickDbResult<Vec<crate::types::DataValue>> {
+        if let crate::pool::DatabaseConnection::MongoDB(db) = connection {
+            let collection = crate::adapter::mongodb::utils::get_collection(self, db, collection_name);
+            let stages: Vec<&mongodb::bson::Document> = pipeline.iter().collect();
+            crate::adapter::mongodb::utils::log_command("aggregate", collection_name, &stages);
+
+            let mut cursor = collection.aggregate(pipeline, None)
+                .await
+                .map_err(|e| crate::error::QuickDbError::QueryError {
+                    message: format!("MongoDB聚合查询失败: {}", e),
+                })?;
+
+            let mut results = Vec::new();
+            while cursor.advance().await.map_err(|e| crate::error::QuickDbError::QueryError {
+                message: format!("MongoDB聚合游标遍历失败: {}", e),
+            })? {
+                let doc = cursor.deserialize_current().map_err(|e| crate::error::QuickDbError::QueryError {
+                    message: format!("MongoDB聚合文档反序列化失败: {}", e),
+                })?;
+                let data_map = crate::adapter::mongodb::utils::document_to_data_map(self, &doc)?;
+                results.push(crate::types::DataValue::Object(data_map));
+            }
+
+            debug!("MongoDB聚合管道执行完成: 集合={}, 返回 {} 条记录", collection_name, results.len());
+            Ok(results)
+        } else {
+            Err(crate::error::QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MongoDB连接".to_string(),
+            })
+        }
+    }
 }
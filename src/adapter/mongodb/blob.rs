@@ -0,0 +1,199 @@
+//! MongoDB二进制流（GridFS）
+//!
+//! 按 GridFS 格式把数据存放在 `{bucket}.files` 和 `{bucket}.chunks` 集合中，
+//! 文件名为 [`BlobLocation::gridfs_filename`]，块大小为 [`BLOB_CHUNK_SIZE`]。
+//! 记录本身不保存二进制数据，删除记录不会删除对应的 GridFS 文件
+
+use crate::adapter::mongodb::MongoAdapter;
+use crate::adapter::DatabaseConnection;
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::*;
+use futures::TryStreamExt;
+use mongodb::bson::{doc, oid::ObjectId, spec::BinarySubtype, Binary, Bson, Document};
+use mongodb::options::{FindOneOptions, FindOptions};
+use mongodb::{Collection, Database};
+
+/// 读取 GridFS 文件的一段数据，记录不存在时返回 `None`，尚未写入文件时返回空数据
+pub(crate) async fn read_chunk(
+    adapter: &MongoAdapter,
+    connection: &DatabaseConnection,
+    location: &BlobLocation,
+    offset: u64,
+    len: u64,
+    bucket: Option<&str>,
+) -> QuickDbResult<Option<Vec<u8>>> {
+    let (db, bucket) = gridfs_target(connection, bucket)?;
+    if super::query::find_by_id(adapter, connection, &location.table, &location.id).await?.is_none() {
+        return Ok(None);
+    }
+
+    let Some(file) = find_file(db, bucket, &location.gridfs_filename()).await? else {
+        return Ok(Some(Vec::new()));
+    };
+    let (length, chunk_size) = file_sizes(&file)?;
+    let end = offset.saturating_add(len).min(length);
+    if offset >= end {
+        return Ok(Some(Vec::new()));
+    }
+
+    let first = offset / chunk_size;
+    let last = (end - 1) / chunk_size;
+    let options = FindOptions::builder().sort(doc! { "n": 1 }).build();
+    let filter = doc! { "files_id": file.get("_id").cloned().unwrap_or(Bson::Null), "n": { "$gte": first as i64, "$lte": last as i64 } };
+    crate::adapter::mongodb::utils::log_command("find", &format!("{}.chunks", bucket), &[&filter]);
+    let chunks: Vec<Document> = chunks_collection(db, bucket)
+        .find(filter, options)
+        .await
+        .map_err(gridfs_error)?
+        .try_collect()
+        .await
+        .map_err(gridfs_error)?;
+
+    let mut data = Vec::with_capacity((end - offset) as usize);
+    for chunk in &chunks {
+        let bytes = chunk.get_binary_generic("data").map_err(|e| QuickDbError::QueryError {
+            message: format!("GridFS块数据格式错误: {}", e),
+        })?;
+        data.extend_from_slice(bytes);
+    }
+    let start = (offset - first * chunk_size) as usize;
+    let stop = ((end - first * chunk_size) as usize).min(data.len());
+    Ok(Some(data.get(start..stop).map(<[u8]>::to_vec).unwrap_or_default()))
+}
+
+/// 写入 GridFS 文件的一段数据，返回记录是否存在
+///
+/// `append` 为 `false` 时先删除同名文件再创建新文件；追加时现有长度必须是块大小的整数倍
+pub(crate) async fn write_chunk(
+    adapter: &MongoAdapter,
+    connection: &DatabaseConnection,
+    location: &BlobLocation,
+    data: &[u8],
+    append: bool,
+    bucket: Option<&str>,
+) -> QuickDbResult<bool> {
+    let (db, bucket) = gridfs_target(connection, bucket)?;
+    if super::query::find_by_id(adapter, connection, &location.table, &location.id).await?.is_none() {
+        return Ok(false);
+    }
+
+    let filename = location.gridfs_filename();
+    let files = files_collection(db, bucket);
+    let chunks = chunks_collection(db, bucket);
+    let file = match (append, find_file(db, bucket, &filename).await?) {
+        (true, Some(file)) => file,
+        (_, existing) => {
+            if existing.is_some() {
+                delete_file(db, bucket, &filename).await?;
+            }
+            let file = doc! {
+                "_id": ObjectId::new(),
+                "filename": &filename,
+                "length": 0_i64,
+                "chunkSize": BLOB_CHUNK_SIZE as i32,
+                "uploadDate": mongodb::bson::DateTime::now(),
+            };
+            crate::adapter::mongodb::utils::log_command("insert", &format!("{}.files", bucket), &[&file]);
+            files.insert_one(file.clone(), None).await.map_err(gridfs_error)?;
+            file
+        }
+    };
+    if data.is_empty() {
+        return Ok(true);
+    }
+
+    let (length, chunk_size) = file_sizes(&file)?;
+    if length % chunk_size != 0 {
+        return Err(QuickDbError::QueryError {
+            message: format!("GridFS文件 {} 的最后一块未写满，无法继续追加", filename),
+        });
+    }
+    let file_id = file.get("_id").cloned().unwrap_or(Bson::Null);
+    let mut n = length / chunk_size;
+    for piece in data.chunks(chunk_size as usize) {
+        let chunk = doc! {
+            "files_id": file_id.clone(),
+            "n": n as i64,
+            "data": Binary { subtype: BinarySubtype::Generic, bytes: piece.to_vec() },
+        };
+        chunks.insert_one(chunk, None).await.map_err(gridfs_error)?;
+        n += 1;
+    }
+    let filter = doc! { "_id": file_id };
+    crate::adapter::mongodb::utils::log_command("update", &format!("{}.files", bucket), &[&filter]);
+    files.update_one(filter, doc! { "$inc": { "length": data.len() as i64 } }, None)
+        .await
+        .map_err(gridfs_error)?;
+    Ok(true)
+}
+
+/// 取出 MongoDB 连接和存储桶名称，未配置存储桶时返回不支持的错误
+fn gridfs_target<'a>(connection: &'a DatabaseConnection, bucket: Option<&'a str>) -> QuickDbResult<(&'a Database, &'a str)> {
+    let DatabaseConnection::MongoDB(db) = connection else {
+        return Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望MongoDB连接".to_string(),
+        });
+    };
+    let bucket = bucket.ok_or_else(|| QuickDbError::UnsupportedDatabase {
+        db_type: "MongoDB 二进制流需要在配置中设置 gridfs_bucket".to_string(),
+    })?;
+    Ok((db, bucket))
+}
+
+fn files_collection(db: &Database, bucket: &str) -> Collection<Document> {
+    db.collection::<Document>(&format!("{}.files", bucket))
+}
+
+fn chunks_collection(db: &Database, bucket: &str) -> Collection<Document> {
+    db.collection::<Document>(&format!("{}.chunks", bucket))
+}
+
+/// 查找同名文件中最新上传的一个
+async fn find_file(db: &Database, bucket: &str, filename: &str) -> QuickDbResult<Option<Document>> {
+    let options = FindOneOptions::builder().sort(doc! { "uploadDate": -1 }).build();
+    let filter = doc! { "filename": filename };
+    crate::adapter::mongodb::utils::log_command("findOne", &format!("{}.files", bucket), &[&filter]);
+    files_collection(db, bucket).find_one(filter, options).await.map_err(gridfs_error)
+}
+
+/// 删除同名文件及其全部块
+async fn delete_file(db: &Database, bucket: &str, filename: &str) -> QuickDbResult<()> {
+    let files = files_collection(db, bucket);
+    let filter = doc! { "filename": filename };
+    let ids: Vec<Bson> = files.find(filter.clone(), None)
+        .await
+        .map_err(gridfs_error)?
+        .try_collect::<Vec<Document>>()
+        .await
+        .map_err(gridfs_error)?
+        .into_iter()
+        .filter_map(|file| file.get("_id").cloned())
+        .collect();
+    crate::adapter::mongodb::utils::log_command("delete", &format!("{}.chunks", bucket), &[&filter]);
+    chunks_collection(db, bucket).delete_many(doc! { "files_id": { "$in": ids } }, None)
+        .await
+        .map_err(gridfs_error)?;
+    files.delete_many(filter, None).await.map_err(gridfs_error)?;
+    Ok(())
+}
+
+/// 读取文件文档中的长度和块大小
+fn file_sizes(file: &Document) -> QuickDbResult<(u64, u64)> {
+    let number = |key: &str| match file.get(key) {
+        Some(Bson::Int32(n)) if *n >= 0 => Some(*n as u64),
+        Some(Bson::Int64(n)) if *n >= 0 => Some(*n as u64),
+        _ => None,
+    };
+    match (number("length"), number("chunkSize")) {
+        (Some(length), Some(chunk_size)) if chunk_size > 0 => Ok((length, chunk_size)),
+        _ => Err(QuickDbError::QueryError {
+            message: "GridFS文件文档缺少有效的 length 或 chunkSize".to_string(),
+        }),
+    }
+}
+
+fn gridfs_error(e: mongodb::error::Error) -> QuickDbError {
+    QuickDbError::QueryError {
+        message: format!("MongoDB GridFS操作失败: {}", e),
+    }
+}
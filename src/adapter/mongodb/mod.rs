@@ -12,6 +12,8 @@ pub mod operations;
 pub mod utils;
 pub mod query;
 pub mod schema;
+pub mod blob;
+pub mod watch;
 
 // 重新导出核心类型
-pub use adapter::MongoAdapter;
\ No newline at end of file
+pub use adapter::MongoAdapter;
@@ -5,7 +5,7 @@ use crate::adapter::DatabaseAdapter;
 use crate::pool::DatabaseConnection;
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
-use crate::model::{FieldType, FieldDefinition};
+use crate::model::{FieldType, FieldDefinition, IndexDefinition};
 use crate::manager;
 use async_trait::async_trait;
 use rat_logger::debug;
@@ -15,10 +15,21 @@ use serde_json::json;
 
 use super::query as mongodb_query;
 use super::schema as mongodb_schema;
+use super::blob as mongodb_blob;
 use super::utils as mongodb_utils;
 
+/// 单条 `update` 命令携带的 upsert 语句数，避免命令超过MongoDB的消息大小上限
+const MONGO_UPSERT_BATCH_SIZE: usize = 1000;
+
+/// 单次 `insert_many` 提交的文档数
+const MONGO_INSERT_BATCH_SIZE: usize = 1000;
+
 #[async_trait]
 impl DatabaseAdapter for MongoAdapter {
+    fn capabilities(&self) -> Capabilities {
+        DatabaseType::MongoDB.capabilities()
+    }
+
     async fn create(
         &self,
         connection: &DatabaseConnection,
@@ -27,153 +38,176 @@ impl DatabaseAdapter for MongoAdapter {
         id_strategy: &IdStrategy,
     ) -> QuickDbResult<DataValue> {
         if let DatabaseConnection::MongoDB(db) = connection {
-            // 调试：打印原始接收到的数据
-            // 自动建表逻辑：检查集合是否存在，如果不存在则创建
-            if !mongodb_schema::table_exists(self, connection, table).await? {
-                // 获取表创建锁，防止并发创建
-                let _lock = self.acquire_table_lock(table).await;
-
-                // 双重检查：再次确认集合不存在
-                if !mongodb_schema::table_exists(self, connection, table).await? {
-                    // 尝试从模型管理器获取预定义的元数据
-                    if let Some(model_meta) = crate::manager::get_model(table) {
-                        debug!("集合 {} 不存在，使用预定义模型元数据创建", table);
-
-                        // MongoDB不需要预创建表结构，集合是无模式的
-                    } else {
-                        return Err(QuickDbError::ValidationError {
-                            field: "collection_creation".to_string(),
-                            message: format!("集合 '{}' 不存在，且没有预定义的模型元数据。MongoDB使用无模式设计，但建议先定义模型。", table),
-                        });
-                    }
-
-                    // 等待一小段时间确保数据库事务完成
-                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                }
-            }
+            // 自动建表逻辑：集合不存在时要求有预定义的模型元数据
+            mongodb_schema::ensure_collection(self, connection, table).await?;
 
             let collection = mongodb_utils::get_collection(self, db, table);
-
-            // 映射字段名（id -> _id）并处理ID策略
-            let mut mapped_data = mongodb_utils::map_data_fields(self, data);
-
-            // 调试：打印接收到的数据
-
-            // 根据ID策略处理ID字段
-            if mapped_data.contains_key("_id") {
-                let strategy = id_strategy;
-                match strategy {
-                    IdStrategy::AutoIncrement | IdStrategy::ObjectId => {
-                        // 对于这些策略，移除空的ID字段，让MongoDB自动生成
-                        if let Some(DataValue::String(s)) = mapped_data.get("_id") {
-                            if s.is_empty() {
-                                mapped_data.remove("_id");
-                            }
-                        }
-                    },
-                    IdStrategy::Snowflake { .. } | IdStrategy::Uuid => {
-                        // 对于雪花和UUID策略，移除空的ID字段，让ODM层生成的ID生效
-                        if let Some(DataValue::String(s)) = mapped_data.get("_id") {
-                            if s.is_empty() {
-                                mapped_data.remove("_id");
-                            }
-                        }
-                    },
-                    IdStrategy::Custom(_) => {
-                        // 自定义策略保留ID字段
-                    }
-                }
-            } else {
-                // 没有ID字段，检查策略是否需要ID
-                match id_strategy {
-                    IdStrategy::Snowflake { .. } => {
-                        // 雪花策略需要ID字段
-                        return Err(QuickDbError::ValidationError {
-                            field: "_id".to_string(),
-                            message: format!("使用{:?}策略时必须提供ID字段", id_strategy),
-                        });
-                    },
-                    IdStrategy::Uuid => {
-                        // MongoDB的UUID策略不要求提供ID字段，可以自动生成字符串UUID
-                        // 符合我们的设计：MongoDB将UUID作为字符串处理
-                    },
-                    _ => {} // 其他策略不需要ID字段
-                }
-            }
-
-            let mut doc = Document::new();
-            for (key, value) in &mapped_data {
-                // 特殊处理_id字段，根据ID策略决定BSON类型
-                if key == "_id" {
-                    let bson_value = match (value, id_strategy) {
-                        (crate::types::DataValue::String(s), crate::types::IdStrategy::Uuid) => {
-                            // UUID策略：保持字符串格式，防止被MongoDB转换为ObjectId
-                            // 使用Bson::String包装，MongoDB应该保持字符串格式
-                            Bson::String(s.clone())
-                        },
-                        (crate::types::DataValue::String(s), crate::types::IdStrategy::ObjectId) => {
-                            // ObjectId策略：尝试转换为ObjectId
-                            if let Ok(object_id) = mongodb::bson::oid::ObjectId::parse_str(s) {
-                                Bson::ObjectId(object_id)
-                            } else {
-                                Bson::String(s.clone()) // 如果解析失败，保持字符串
-                            }
-                        },
-                        _ => {
-                            // 其他情况，使用默认转换
-                            mongodb_utils::data_value_to_bson(self, value)
-                        }
-                    };
-                    doc.insert(key, bson_value);
-                } else {
-                    doc.insert(key, mongodb_utils::data_value_to_bson(self, value));
-                }
-            }
+            let (doc, preset_id) = mongodb_utils::build_insert_document(self, table, data, id_strategy)?;
 
             debug!("执行MongoDB插入到集合 {}: {:?}", table, doc);
+            mongodb_utils::log_command("insert", table, &[&doc]);
 
-            let result = collection.insert_one(doc, None)
-                .await
+            // 处于显式事务作用域时在事务会话中执行
+            let result = match crate::adapter::transaction::current_mongo_session().await {
+                Some(mut session) => collection.insert_one_with_session(doc, None, &mut session).await,
+                None => collection.insert_one(doc, None).await,
+            }
                 .map_err(|e| QuickDbError::QueryError {
                     message: format!("MongoDB插入失败: {}", e),
                 })?;
 
+            // 有ODM层生成的ID时使用它，否则使用MongoDB生成的ID
+            let id = preset_id.unwrap_or_else(|| mongodb_utils::inserted_id_to_string(&result.inserted_id));
             let mut result_map = HashMap::new();
+            result_map.insert("id".to_string(), DataValue::String(id));
+            Ok(DataValue::Object(result_map))
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MongoDB连接".to_string(),
+            })
+        }
+    }
 
-            // 检查是否有ODM层生成的ID，如果有则使用它，否则使用MongoDB生成的ID
-            if let Some(id_value) = mapped_data.get("_id") {
-                if let DataValue::String(id_str) = id_value {
-                    if !id_str.is_empty() {
-                        // 使用ODM层生成的ID
-                        result_map.insert("id".to_string(), DataValue::String(id_str.clone()));
-                        Ok(DataValue::Object(result_map))
-                    } else {
-                        // 使用MongoDB生成的ID，确保转换为纯字符串格式
-                        let id_str = match result.inserted_id {
-                            mongodb::bson::Bson::ObjectId(oid) => oid.to_hex(),
-                            _ => result.inserted_id.to_string(),
+    async fn create_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        data_list: &[HashMap<String, DataValue>],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if let DatabaseConnection::MongoDB(db) = connection {
+            if data_list.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            mongodb_schema::ensure_collection(self, connection, table).await?;
+
+            let collection = mongodb_utils::get_collection(self, db, table);
+            let mut results = Vec::with_capacity(data_list.len());
+            for chunk in data_list.chunks(MONGO_INSERT_BATCH_SIZE) {
+                let mut docs = Vec::with_capacity(chunk.len());
+                let mut preset_ids = Vec::with_capacity(chunk.len());
+                for data in chunk {
+                    let (doc, preset_id) = mongodb_utils::build_insert_document(self, table, data, id_strategy)?;
+                    docs.push(doc);
+                    preset_ids.push(preset_id);
+                }
+
+                let doc_refs: Vec<&Document> = docs.iter().collect();
+                mongodb_utils::log_command("insert", table, &doc_refs);
+
+                // 有序插入：失败时停在出错的文档，之前的文档已写入
+                let result = match crate::adapter::transaction::current_mongo_session().await {
+                    Some(mut session) => collection.insert_many_with_session(docs, None, &mut session).await,
+                    None => collection.insert_many(docs, None).await,
+                }
+                    .map_err(|e| QuickDbError::QueryError {
+                        message: format!("MongoDB批量插入失败: {}", e),
+                    })?;
+
+                for (index, preset_id) in preset_ids.into_iter().enumerate() {
+                    let id = match preset_id {
+                        Some(id) => id,
+                        None => result.inserted_ids.get(&index)
+                            .map(mongodb_utils::inserted_id_to_string)
+                            .ok_or_else(|| QuickDbError::QueryError {
+                                message: format!("MongoDB批量插入结果缺少第 {} 条记录的ID", index + 1),
+                            })?,
+                    };
+                    let mut result_map = HashMap::new();
+                    result_map.insert("id".to_string(), DataValue::String(id));
+                    results.push(DataValue::Object(result_map));
+                }
+            }
+            Ok(results)
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MongoDB连接".to_string(),
+            })
+        }
+    }
+
+    async fn upsert_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        rows: &[HashMap<String, DataValue>],
+        conflict_fields: &[String],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<UpsertResult> {
+        if let DatabaseConnection::MongoDB(db) = connection {
+            crate::adapter::upsert_columns(rows, conflict_fields)?;
+            let conflict_fields: Vec<String> = conflict_fields.iter()
+                .map(|field| mongodb_utils::map_field_name(self, field))
+                .collect();
+
+            let mut inserted = 0u64;
+            for chunk in rows.chunks(MONGO_UPSERT_BATCH_SIZE) {
+                let mut updates = Vec::with_capacity(chunk.len());
+                for row in chunk {
+                    let mapped_data = mongodb_utils::map_data_fields(self, row);
+                    // 模型默认值只在插入时生效，不覆盖已有记录
+                    let mut with_defaults = mapped_data.clone();
+                    mongodb_utils::apply_field_defaults(table, &mut with_defaults);
+
+                    let mut filter = Document::new();
+                    let mut set_doc = Document::new();
+                    let mut set_on_insert = Document::new();
+                    for (key, value) in &with_defaults {
+                        let bson_value = if key == "_id" {
+                            mongodb_utils::id_value_to_bson(self, value, id_strategy)
+                        } else {
+                            mongodb_utils::data_value_to_bson(self, value)
                         };
-                        result_map.insert("id".to_string(), DataValue::String(id_str));
-                        Ok(DataValue::Object(result_map))
+                        if conflict_fields.contains(key) {
+                            filter.insert(key, bson_value);
+                        } else if key == "_id" || !mapped_data.contains_key(key) {
+                            set_on_insert.insert(key, bson_value);
+                        } else {
+                            set_doc.insert(key, bson_value);
+                        }
                     }
-                } else {
-                    // 使用MongoDB生成的ID，确保转换为纯字符串格式
-                    let id_str = match result.inserted_id {
-                        mongodb::bson::Bson::ObjectId(oid) => oid.to_hex(),
-                        _ => result.inserted_id.to_string(),
-                    };
-                    result_map.insert("id".to_string(), DataValue::String(id_str));
-                    Ok(DataValue::Object(result_map))
+
+                    let mut update = Document::new();
+                    if !set_doc.is_empty() {
+                        update.insert("$set", set_doc);
+                    }
+                    if set_on_insert.is_empty() && update.is_empty() {
+                        // 空更新文档会被当作替换文档，用冲突字段的原值占位
+                        set_on_insert = filter.clone();
+                    }
+                    if !set_on_insert.is_empty() {
+                        update.insert("$setOnInsert", set_on_insert);
+                    }
+                    updates.push(Bson::Document(doc! { "q": filter, "u": update, "upsert": true }));
                 }
-            } else {
-                // 使用MongoDB生成的ID，确保转换为纯字符串格式
-                let id_str = match result.inserted_id {
-                    mongodb::bson::Bson::ObjectId(oid) => oid.to_hex(),
-                    _ => result.inserted_id.to_string(),
+
+                let command = doc! {
+                    "update": table,
+                    "updates": updates,
+                    "ordered": true,
                 };
-                result_map.insert("id".to_string(), DataValue::String(id_str));
-                Ok(DataValue::Object(result_map))
+                debug!("执行MongoDB批量upsert到集合 {}: {} 条", table, chunk.len());
+                let result = db.run_command(command, None).await
+                    .map_err(|e| QuickDbError::QueryError {
+                        message: format!("MongoDB批量upsert失败: {}", e),
+                    })?;
+
+                // update 命令的写入错误出现在结果中，而不是作为命令错误返回
+                if let Ok(write_errors) = result.get_array("writeErrors") {
+                    if let Some(Bson::Document(error)) = write_errors.first() {
+                        return Err(QuickDbError::QueryError {
+                            message: format!(
+                                "MongoDB批量upsert失败: {}",
+                                error.get_str("errmsg").unwrap_or("未知错误")
+                            ),
+                        });
+                    }
+                }
+                inserted += result.get_array("upserted").map(|upserted| upserted.len()).unwrap_or(0) as u64;
             }
+
+            Ok(UpsertResult::with_inserted(rows.len() as u64, inserted))
         } else {
             Err(QuickDbError::ConnectionError {
                 message: "连接类型不匹配，期望MongoDB连接".to_string(),
@@ -224,9 +258,12 @@ impl DatabaseAdapter for MongoAdapter {
             let update = mongodb_utils::build_update_document(self, data);
 
             debug!("执行MongoDB更新: 查询={:?}, 更新={:?}", query, update);
+            mongodb_utils::log_command("update", table, &[&query, &update]);
 
-            let result = collection.update_many(query, update, None)
-                .await
+            let result = match crate::adapter::transaction::current_mongo_session().await {
+                Some(mut session) => collection.update_many_with_session(query, update, None, &mut session).await,
+                None => collection.update_many(query, update, None).await,
+            }
                 .map_err(|e| QuickDbError::QueryError {
                     message: format!("MongoDB更新失败: {}", e),
                 })?;
@@ -256,6 +293,44 @@ impl DatabaseAdapter for MongoAdapter {
         Ok(affected > 0)
     }
 
+    async fn update_by_id_detailed(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        id: &DataValue,
+        data: &HashMap<String, DataValue>,
+    ) -> QuickDbResult<WriteResult> {
+        if let DatabaseConnection::MongoDB(db) = connection {
+            let collection = mongodb_utils::get_collection(self, db, table);
+
+            let conditions = vec![QueryCondition {
+                field: "_id".to_string(), // MongoDB使用_id作为主键
+                operator: QueryOperator::Eq,
+                value: id.clone(),
+            }];
+            let query = mongodb_utils::build_query_document(self, &conditions)?;
+            let update = mongodb_utils::build_update_document(self, data);
+
+            debug!("执行MongoDB根据ID更新: 查询={:?}, 更新={:?}", query, update);
+            mongodb_utils::log_command("update", table, &[&query, &update]);
+
+            let result = collection.update_one(query, update, None)
+                .await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("MongoDB更新失败: {}", e),
+                })?;
+
+            Ok(WriteResult {
+                matched: result.matched_count,
+                modified: result.modified_count,
+            })
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MongoDB连接".to_string(),
+            })
+        }
+    }
+
     async fn update_with_operations(
         &self,
         connection: &DatabaseConnection,
@@ -271,6 +346,7 @@ impl DatabaseAdapter for MongoAdapter {
 
             let mut set_doc = Document::new();
             let mut inc_doc = Document::new();
+            let mut unset_doc = Document::new();
 
             for operation in operations {
                 match &operation.operation {
@@ -357,6 +433,11 @@ impl DatabaseAdapter for MongoAdapter {
                         let mul_doc = set_doc.get_mut("$mul").unwrap().as_document_mut().unwrap();
                         mul_doc.insert(&operation.field, bson_value);
                     }
+                    crate::types::UpdateOperator::JsonMerge => {
+                        // JSON合并补丁：展开为点号路径的$set，null键转为$unset
+                        let patch = operation.json_merge_patch()?;
+                        mongodb_utils::flatten_json_merge_patch(&operation.field, &patch, &mut set_doc, &mut unset_doc)?;
+                    }
                 }
             }
 
@@ -373,6 +454,10 @@ impl DatabaseAdapter for MongoAdapter {
                 update_doc.insert("$inc", inc_doc);
             }
 
+            if !unset_doc.is_empty() {
+                update_doc.insert("$unset", unset_doc);
+            }
+
             if update_doc.is_empty() {
                 return Err(QuickDbError::ValidationError {
                     field: "operations".to_string(),
@@ -381,9 +466,12 @@ impl DatabaseAdapter for MongoAdapter {
             }
 
             debug!("执行MongoDB操作更新: query={:?}, update={:?}", query, update_doc);
+            mongodb_utils::log_command("update", table, &[&query, &update_doc]);
 
-            let result = collection.update_many(query, update_doc, None)
-                .await
+            let result = match crate::adapter::transaction::current_mongo_session().await {
+                Some(mut session) => collection.update_many_with_session(query, update_doc, None, &mut session).await,
+                None => collection.update_many(query, update_doc, None).await,
+            }
                 .map_err(|e| QuickDbError::QueryError {
                     message: format!("MongoDB更新失败: {}", e),
                 })?;
@@ -408,9 +496,12 @@ impl DatabaseAdapter for MongoAdapter {
             let query = mongodb_utils::build_query_document(self, conditions)?;
 
             debug!("执行MongoDB删除: {:?}", query);
+            mongodb_utils::log_command("delete", table, &[&query]);
 
-            let result = collection.delete_many(query, None)
-                .await
+            let result = match crate::adapter::transaction::current_mongo_session().await {
+                Some(mut session) => collection.delete_many_with_session(query, None, &mut session).await,
+                None => collection.delete_many(query, None).await,
+            }
                 .map_err(|e| QuickDbError::QueryError {
                     message: format!("MongoDB删除失败: {}", e),
                 })?;
@@ -439,6 +530,44 @@ impl DatabaseAdapter for MongoAdapter {
         Ok(affected > 0)
     }
 
+    async fn delete_by_id_detailed(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        id: &DataValue,
+    ) -> QuickDbResult<WriteResult> {
+        let conditions = vec![QueryCondition {
+            field: "_id".to_string(), // MongoDB使用_id作为主键
+            operator: QueryOperator::Eq,
+            value: id.clone(),
+        }];
+
+        let affected = self.delete(connection, table, &conditions).await?;
+        Ok(WriteResult::uniform(affected))
+    }
+
+    async fn read_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        offset: u64,
+        len: u64,
+        gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<Option<Vec<u8>>> {
+        mongodb_blob::read_chunk(self, connection, location, offset, len, gridfs_bucket).await
+    }
+
+    async fn write_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        data: &[u8],
+        append: bool,
+        gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<bool> {
+        mongodb_blob::write_chunk(self, connection, location, data, append, gridfs_bucket).await
+    }
+
     async fn count(
         &self,
         connection: &DatabaseConnection,
@@ -448,6 +577,46 @@ impl DatabaseAdapter for MongoAdapter {
         mongodb_query::count(self, connection, table, conditions).await
     }
 
+    async fn estimated_count(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<u64> {
+        mongodb_query::estimated_count(self, connection, table).await
+    }
+
+    async fn time_bucket(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        field: &str,
+        interval: Interval,
+        conditions: &[QueryCondition],
+    ) -> QuickDbResult<Vec<(DataValue, u64)>> {
+        mongodb_query::time_bucket(self, connection, table, field, interval, conditions).await
+    }
+
+    async fn aggregate(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        aggregations: &[Aggregation],
+        conditions: &[QueryCondition],
+        group_by: &[String],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        mongodb_query::aggregate(self, connection, table, aggregations, conditions, group_by).await
+    }
+
+    async fn mongo_aggregate(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        pipeline: &[serde_json::Value],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let pipeline = mongodb_utils::pipeline_from_json(pipeline)?;
+        self.aggregate_pipeline(connection, table, pipeline).await
+    }
+
     async fn exists(
         &self,
         connection: &DatabaseConnection,
@@ -472,10 +641,9 @@ impl DatabaseAdapter for MongoAdapter {
         connection: &DatabaseConnection,
         table: &str,
         index_name: &str,
-        fields: &[String],
-        unique: bool,
+        index: &IndexDefinition,
     ) -> QuickDbResult<()> {
-        mongodb_schema::create_index(self, connection, table, index_name, fields, unique).await
+        mongodb_schema::create_index(self, connection, table, index_name, index).await
     }
 
     async fn table_exists(
@@ -486,6 +654,14 @@ impl DatabaseAdapter for MongoAdapter {
         mongodb_schema::table_exists(self, connection, table).await
     }
 
+    async fn list_index_names(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        mongodb_schema::list_index_names(self, connection, table).await
+    }
+
     async fn drop_table(
         &self,
         connection: &DatabaseConnection,
@@ -494,6 +670,15 @@ impl DatabaseAdapter for MongoAdapter {
         mongodb_schema::drop_table(self, connection, table).await
     }
 
+    async fn rename_table(
+        &self,
+        connection: &DatabaseConnection,
+        from: &str,
+        to: &str,
+    ) -> QuickDbResult<()> {
+        mongodb_schema::rename_table(self, connection, from, to).await
+    }
+
     async fn get_server_version(
         &self,
         connection: &DatabaseConnection,
@@ -623,6 +808,14 @@ impl DatabaseAdapter for MongoAdapter {
         debug!("MongoDB存储过程 {} 执行完成，返回 {} 条记录", procedure_name, result.len());
         Ok(result)
     }
+
+    async fn watch(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<ChangeStream> {
+        super::watch::watch(self, connection, table).await
+    }
 }
 
 impl MongoAdapter {
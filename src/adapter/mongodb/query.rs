@@ -36,10 +36,13 @@ pub(crate) async fn find_by_id(
                         doc! { "_id": actual_id }
                     }
                 },
+                // 数字ID同时匹配整数和字符串形式（雪花ID等以字符串存储）
+                DataValue::Int(n) => doc! { "_id": { "$in": [*n, n.to_string()] } },
                 _ => doc! { "_id": crate::adapter::mongodb::utils::data_value_to_bson(adapter, id) }
             };
             
             debug!("执行MongoDB根据ID查询: {:?}", query);
+            crate::adapter::mongodb::utils::log_command("findOne", table, &[&query]);
             
             let result = collection.find_one(query, None)
                 .await
@@ -98,42 +101,115 @@ pub(crate) async fn find_by_id(
             let query = crate::adapter::mongodb::utils::build_condition_groups_document(adapter, condition_groups)?;
             
             debug!("执行MongoDB条件组合查询: {:?}", query);
+            crate::adapter::mongodb::utils::log_command("find", table, &[&query]);
             
+            // 排序字段必须是模型字段或计算字段的别名
+            for sort_field in &options.sort {
+                if !options.select_exprs.iter().any(|(_, alias)| *alias == sort_field.field) {
+                    crate::adapter::validate_sort_field(table, &sort_field.field)?;
+                }
+            }
+
             let mut find_options = mongodb::options::FindOptions::default();
             
             // 添加排序
-            if !options.sort.is_empty() {
-                let mut sort_doc = Document::new();
-                for sort_field in &options.sort {
-                    let sort_value = match sort_field.direction {
-                        SortDirection::Asc => 1,
-                        SortDirection::Desc => -1,
-                    };
-                    sort_doc.insert(&sort_field.field, sort_value);
-                }
-                find_options.sort = Some(sort_doc);
+            // 分页时若排序字段不唯一，会自动追加_id保证分页边界稳定
+            find_options.sort = crate::adapter::mongodb::utils::build_sort_document(adapter, table, options);
+
+            // 添加排序规则，同时作用于查询条件中的字符串比较和排序
+            if let Some(locale) = &options.collation {
+                find_options.collation = Some(
+                    mongodb::options::Collation::builder()
+                        .locale(locale.clone())
+                        .build(),
+                );
             }
             
             // 添加分页
             if let Some(pagination) = &options.pagination {
-                find_options.limit = Some(pagination.limit as i64);
-                find_options.skip = Some(pagination.skip);
+                // limit 为 0 表示不限制；聚合管道中 $limit 不接受 0
+                find_options.limit = (pagination.limit > 0).then_some(pagination.limit as i64);
+                find_options.skip = (pagination.skip > 0).then_some(pagination.skip);
             }
-            
-            let mut cursor = collection.find(query, find_options)
-                .await
-                .map_err(|e| QuickDbError::QueryError {
-                    message: format!("MongoDB条件组合查询失败: {}", e),
-                })?;
-            
-            let mut results = Vec::new();
-            while cursor.advance().await.map_err(|e| QuickDbError::QueryError {
+
+            // 添加字段选择
+            find_options.projection = crate::adapter::mongodb::utils::build_projection_document(adapter, options);
+
+            // 有计算字段时改用聚合管道，在分页之后通过 $addFields 附加派生字段；
+            // 按计算字段排序时需先附加派生字段，$sort 才能引用
+            let aggregation = if options.select_exprs.is_empty() {
+                None
+            } else {
+                let add_fields = doc! {
+                    "$addFields": crate::adapter::mongodb::utils::build_add_fields_document(adapter, &options.select_exprs)?
+                };
+                let sort_by_alias = options.sort.iter()
+                    .any(|s| options.select_exprs.iter().any(|(_, alias)| *alias == s.field));
+                let mut pipeline = vec![doc! { "$match": query.clone() }];
+                if sort_by_alias {
+                    pipeline.push(add_fields.clone());
+                }
+                if let Some(sort) = find_options.sort.take() {
+                    pipeline.push(doc! { "$sort": sort });
+                }
+                if let Some(skip) = find_options.skip {
+                    pipeline.push(doc! { "$skip": skip as i64 });
+                }
+                if let Some(limit) = find_options.limit {
+                    pipeline.push(doc! { "$limit": limit });
+                }
+                if !sort_by_alias {
+                    pipeline.push(add_fields);
+                }
+                if let Some(projection) = find_options.projection.take() {
+                    pipeline.push(doc! { "$project": projection });
+                }
+                let aggregate_options = mongodb::options::AggregateOptions::builder()
+                    .collation(find_options.collation.take())
+                    .build();
+                Some((pipeline, aggregate_options))
+            };
+            let query_error = |e: mongodb::error::Error| QuickDbError::QueryError {
+                message: format!("MongoDB条件组合查询失败: {}", e),
+            };
+            let cursor_error = |e: mongodb::error::Error| QuickDbError::QueryError {
                 message: format!("MongoDB游标遍历失败: {}", e),
-            })? {
-                let doc = cursor.deserialize_current().map_err(|e| QuickDbError::QueryError {
-                    message: format!("MongoDB文档反序列化失败: {}", e),
-                })?;
-                let data_map = crate::adapter::mongodb::utils::document_to_data_map(adapter, &doc)?;
+            };
+
+            let mut documents = Vec::new();
+            match crate::adapter::transaction::current_mongo_session().await {
+                // 处于显式事务作用域时在事务会话中执行，可读到事务内未提交的写入
+                Some(mut session) => {
+                    let mut cursor = match aggregation {
+                        None => collection.find_with_session(query, find_options, &mut session).await,
+                        Some((pipeline, aggregate_options)) => {
+                            collection.aggregate_with_session(pipeline, aggregate_options, &mut session).await
+                        }
+                    }
+                    .map_err(query_error)?;
+                    while cursor.advance(&mut session).await.map_err(cursor_error)? {
+                        documents.push(cursor.deserialize_current().map_err(|e| QuickDbError::QueryError {
+                            message: format!("MongoDB文档反序列化失败: {}", e),
+                        })?);
+                    }
+                }
+                None => {
+                    let mut cursor = match aggregation {
+                        None => collection.find(query, find_options).await,
+                        Some((pipeline, aggregate_options)) => collection.aggregate(pipeline, aggregate_options).await,
+                    }
+                    .map_err(query_error)?;
+                    while cursor.advance().await.map_err(cursor_error)? {
+                        documents.push(cursor.deserialize_current().map_err(|e| QuickDbError::QueryError {
+                            message: format!("MongoDB文档反序列化失败: {}", e),
+                        })?);
+                    }
+                }
+            }
+
+            let mut results = Vec::with_capacity(documents.len());
+            for doc in &documents {
+                let data_map = crate::adapter::mongodb::utils::document_to_data_map(adapter, doc)?;
                 // 直接返回Object，避免双重包装
                 results.push(DataValue::Object(data_map));
             }
@@ -157,6 +233,7 @@ pub(crate) async fn find_by_id(
             let query = crate::adapter::mongodb::utils::build_query_document(adapter, conditions)?;
             
             debug!("执行MongoDB计数: {:?}", query);
+            crate::adapter::mongodb::utils::log_command("count", table, &[&query]);
             
             let count = collection.count_documents(query, None)
                 .await
@@ -172,13 +249,194 @@ pub(crate) async fn find_by_id(
         }
     }
 
+    /// 按时间段统计文档数量，使用 `$dateTrunc` 截断时间（需要 MongoDB 5.0+）
+    pub(crate) async fn time_bucket(
+    adapter: &MongoAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    field: &str,
+    interval: Interval,
+    conditions: &[QueryCondition],
+) -> QuickDbResult<Vec<(DataValue, u64)>> {
+        if let DatabaseConnection::MongoDB(db) = connection {
+            let collection = crate::adapter::mongodb::utils::get_collection(adapter, db, table);
+            let field = crate::adapter::mongodb::utils::map_field_name(adapter, field);
+
+            let mut filter = crate::adapter::mongodb::utils::build_query_document(adapter, conditions)?;
+            let not_null = doc! { field.as_str(): { "$ne": null } };
+            if !filter.is_empty() {
+                filter = doc! { "$and": [filter, not_null] };
+            } else {
+                filter = not_null;
+            }
+            let unit = match interval {
+                Interval::Minute => "minute",
+                Interval::Hour => "hour",
+                Interval::Day => "day",
+                Interval::Week => "week",
+                Interval::Month => "month",
+            };
+            let group = doc! {
+                "_id": {
+                    "$dateTrunc": {
+                        "date": format!("${}", field),
+                        "unit": unit,
+                        "timezone": "UTC",
+                        "startOfWeek": "monday",
+                    }
+                },
+                "count": { "$sum": 1 },
+            };
+            let sort = doc! { "_id": 1 };
+            crate::adapter::mongodb::utils::log_command("aggregate", table, &[&filter, &group, &sort]);
+            let pipeline = vec![doc! { "$match": filter }, doc! { "$group": group }, doc! { "$sort": sort }];
+
+            let mut cursor = collection.aggregate(pipeline, None).await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("MongoDB按时间段统计失败: {}", e),
+                })?;
+
+            let mut buckets = Vec::new();
+            while cursor.advance().await.map_err(|e| QuickDbError::QueryError {
+                message: format!("MongoDB游标遍历失败: {}", e),
+            })? {
+                let doc = cursor.deserialize_current().map_err(|e| QuickDbError::QueryError {
+                    message: format!("MongoDB文档反序列化失败: {}", e),
+                })?;
+                // 字段值不是日期时间的文档截断结果为 null，不计入
+                let Ok(bucket) = doc.get_datetime("_id") else {
+                    continue;
+                };
+                let count = match doc.get("count") {
+                    Some(mongodb::bson::Bson::Int32(count)) => *count as u64,
+                    Some(mongodb::bson::Bson::Int64(count)) => *count as u64,
+                    other => return Err(QuickDbError::QueryError {
+                        message: format!("MongoDB按时间段统计的计数格式错误: {:?}", other),
+                    }),
+                };
+                let bucket = chrono::DateTime::<chrono::Utc>::from(bucket.to_system_time());
+                buckets.push((DataValue::DateTime(bucket), count));
+            }
+
+            Ok(buckets)
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MongoDB连接".to_string(),
+            })
+        }
+    }
+
+    /// MongoDB聚合操作
+    pub(crate) async fn aggregate(
+    adapter: &MongoAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    aggregations: &[Aggregation],
+    conditions: &[QueryCondition],
+    group_by: &[String],
+) -> QuickDbResult<Vec<DataValue>> {
+        if let DatabaseConnection::MongoDB(db) = connection {
+            let collection = crate::adapter::mongodb::utils::get_collection(adapter, db, table);
+            let filter = crate::adapter::mongodb::utils::build_query_document(adapter, conditions)?;
+            let group = crate::adapter::mongodb::utils::build_group_document(adapter, aggregations, group_by)?;
+            let mut sort = Document::new();
+            for index in 0..group_by.len() {
+                sort.insert(format!("_id.g{}", index), 1);
+            }
+            crate::adapter::mongodb::utils::log_command("aggregate", table, &[&filter, &group, &sort]);
+
+            let mut pipeline = vec![doc! { "$match": filter }, doc! { "$group": group }];
+            if !sort.is_empty() {
+                pipeline.push(doc! { "$sort": sort });
+            }
+
+            let mut cursor = collection.aggregate(pipeline, None).await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("MongoDB聚合查询失败: {}", e),
+                })?;
+
+            let mut rows = Vec::new();
+            while cursor.advance().await.map_err(|e| QuickDbError::QueryError {
+                message: format!("MongoDB游标遍历失败: {}", e),
+            })? {
+                let doc = cursor.deserialize_current().map_err(|e| QuickDbError::QueryError {
+                    message: format!("MongoDB文档反序列化失败: {}", e),
+                })?;
+                rows.push(crate::adapter::mongodb::utils::group_document_to_row(adapter, &doc, aggregations, group_by)?);
+            }
+
+            // 没有匹配文档时 $group 不输出结果，与 SQL 一致地返回一行：计数为 0，其他聚合为 null
+            if rows.is_empty() && group_by.is_empty() {
+                let row = aggregations.iter()
+                    .map(|aggregation| {
+                        let value = if aggregation.func == AggFunc::Count { DataValue::Int(0) } else { DataValue::Null };
+                        (aggregation.alias.clone(), value)
+                    })
+                    .collect();
+                rows.push(DataValue::Object(row));
+            }
+
+            Ok(rows)
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MongoDB连接".to_string(),
+            })
+        }
+    }
+
+    /// 使用集合元数据估算文档数量，不扫描文档
+    pub(crate) async fn estimated_count(
+    adapter: &MongoAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+) -> QuickDbResult<u64> {
+        if let DatabaseConnection::MongoDB(db) = connection {
+            let collection = crate::adapter::mongodb::utils::get_collection(adapter, db, table);
+
+            debug!("执行MongoDB估算计数: {}", table);
+            crate::adapter::mongodb::utils::log_command("estimatedDocumentCount", table, &[]);
+
+            collection.estimated_document_count(None)
+                .await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("MongoDB估算计数失败: {}", e),
+                })
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MongoDB连接".to_string(),
+            })
+        }
+    }
+
     pub(crate) async fn exists(
     adapter: &MongoAdapter,
     connection: &DatabaseConnection,
     table: &str,
     conditions: &[QueryCondition],
 ) -> QuickDbResult<bool> {
-    let count = count(adapter, connection, table, conditions).await?;
-    Ok(count > 0)
-}
+        if let DatabaseConnection::MongoDB(db) = connection {
+            let collection = crate::adapter::mongodb::utils::get_collection(adapter, db, table);
+
+            let query = crate::adapter::mongodb::utils::build_query_document(adapter, conditions)?;
+
+            debug!("执行MongoDB存在检查: {:?}", query);
+
+            // 只取第一条匹配文档的 _id，命中即返回
+            let options = mongodb::options::FindOneOptions::builder()
+                .projection(doc! { "_id": 1 })
+                .build();
+
+            let result = collection.find_one(query, options)
+                .await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("MongoDB存在检查失败: {}", e),
+                })?;
+
+            Ok(result.is_some())
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MongoDB连接".to_string(),
+            })
+        }
+    }
 
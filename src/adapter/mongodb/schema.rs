@@ -4,7 +4,7 @@ use crate::adapter::mongodb::MongoAdapter;
 use crate::adapter::DatabaseConnection;
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
-use crate::model::{FieldType, FieldDefinition};
+use crate::model::{FieldType, FieldDefinition, IndexDefinition};
 use rat_logger::debug;
 use std::collections::HashMap;
 use mongodb::bson::{doc, Document};
@@ -48,20 +48,49 @@ pub(crate) async fn create_table(
     connection: &DatabaseConnection,
     table: &str,
     index_name: &str,
-    fields: &[String],
-    unique: bool,
+    index: &IndexDefinition,
 ) -> QuickDbResult<()> {
         if let DatabaseConnection::MongoDB(db) = connection {
             let collection = crate::adapter::mongodb::utils::get_collection(adapter, db, table);
-            
+
+            index.validate()?;
             let mut index_doc = Document::new();
-            for field in fields {
-                index_doc.insert(field, 1); // 1表示升序索引
+            for (i, field) in index.fields.iter().enumerate() {
+                // 1表示升序索引，-1表示降序索引
+                let direction = match index.direction_at(i) {
+                    SortDirection::Asc => 1,
+                    SortDirection::Desc => -1,
+                };
+                index_doc.insert(field, direction);
             }
             
             let mut index_options = mongodb::options::IndexOptions::default();
             index_options.name = Some(index_name.to_string());
-            index_options.unique = Some(unique);
+            index_options.unique = Some(index.unique);
+            if index.sparse {
+                index_options.sparse = Some(true);
+            }
+            // 强度为2的排序规则只比较字母和重音，不区分大小写
+            if index.case_insensitive {
+                index_options.collation = Some(
+                    mongodb::options::Collation::builder()
+                        .locale("en".to_string())
+                        .strength(mongodb::options::CollationStrength::Secondary)
+                        .build(),
+                );
+            }
+            if !index.partial_filter.is_empty() {
+                let mut filter = crate::adapter::mongodb::utils::build_query_document(adapter, &index.partial_filter)?;
+                // 部分索引表达式不支持 null 相等匹配，空值判断改写为 BSON 类型 10（显式存储的 null）
+                for condition in &index.partial_filter {
+                    let is_null = matches!(condition.operator, QueryOperator::IsNull)
+                        || (matches!(condition.operator, QueryOperator::Eq) && matches!(condition.value, DataValue::Null));
+                    if is_null {
+                        filter.insert(condition.field.as_str(), doc! { "$type": 10 });
+                    }
+                }
+                index_options.partial_filter_expression = Some(filter);
+            }
             
             let index_model = mongodb::IndexModel::builder()
                 .keys(index_doc)
@@ -84,6 +113,39 @@ pub(crate) async fn create_table(
         }
     }
 
+/// 写入前确保集合可用
+///
+/// MongoDB集合无模式，写入时自动创建；集合不存在且没有预定义的模型元数据时返回错误
+pub(crate) async fn ensure_collection(
+    adapter: &MongoAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+) -> QuickDbResult<()> {
+    if !table_exists(adapter, connection, table).await? {
+        // 获取表创建锁，防止并发创建
+        let _lock = adapter.acquire_table_lock(table).await;
+
+        // 双重检查：再次确认集合不存在
+        if !table_exists(adapter, connection, table).await? {
+            // 尝试从模型管理器获取预定义的元数据
+            if crate::manager::get_model(table).is_some() {
+                debug!("集合 {} 不存在，使用预定义模型元数据创建", table);
+
+                // MongoDB不需要预创建表结构，集合是无模式的
+            } else {
+                return Err(QuickDbError::ValidationError {
+                    field: "collection_creation".to_string(),
+                    message: format!("集合 '{}' 不存在，且没有预定义的模型元数据。MongoDB使用无模式设计，但建议先定义模型。", table),
+                });
+            }
+
+            // 等待一小段时间确保数据库事务完成
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+    }
+    Ok(())
+}
+
     pub(crate) async fn table_exists(
     adapter: &MongoAdapter,
     connection: &DatabaseConnection,
@@ -104,6 +166,25 @@ pub(crate) async fn create_table(
         }
     }
 
+    pub(crate) async fn list_index_names(
+    adapter: &MongoAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+) -> QuickDbResult<Vec<String>> {
+        if let DatabaseConnection::MongoDB(db) = connection {
+            let collection = crate::adapter::mongodb::utils::get_collection(adapter, db, table);
+            collection.list_index_names()
+                .await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("获取MongoDB索引信息失败: {}", e),
+                })
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MongoDB连接".to_string(),
+            })
+        }
+    }
+
     pub(crate) async fn drop_table(
     adapter: &MongoAdapter,
     connection: &DatabaseConnection,
@@ -127,6 +208,35 @@ pub(crate) async fn create_table(
         }
     }
 
+    /// MongoDB重命名集合操作，`renameCollection` 只能在 admin 库上执行
+    pub(crate) async fn rename_table(
+    adapter: &MongoAdapter,
+    connection: &DatabaseConnection,
+    from: &str,
+    to: &str,
+) -> QuickDbResult<()> {
+        if let DatabaseConnection::MongoDB(db) = connection {
+            debug!("执行MongoDB重命名集合: {} -> {}", from, to);
+
+            let command = doc! {
+                "renameCollection": format!("{}.{}", db.name(), from),
+                "to": format!("{}.{}", db.name(), to),
+            };
+            let admin = db.collection::<Document>(from).client().database("admin");
+            admin.run_command(command, None).await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("重命名MongoDB集合失败: {}", e),
+                })?;
+
+            debug!("成功重命名MongoDB集合: {} -> {}", from, to);
+            Ok(())
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MongoDB连接".to_string(),
+            })
+        }
+    }
+
     pub(crate) async fn get_server_version(
     adapter: &MongoAdapter,
     connection: &DatabaseConnection,
@@ -6,6 +6,7 @@ use crate::error::{QuickDbError, QuickDbResult};
 use mongodb::{Collection, Database};
 use mongodb::bson::{doc, Bson, Document};
 use std::collections::HashMap;
+use crate::types::query_log::{QueryLogScope, REDACTED};
 use rat_logger::debug;
 
 /// 将DataValue转换为BSON值
@@ -47,6 +48,20 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
         }
     }
 
+/// 按ID策略把 `_id` 的值转换为BSON
+///
+/// UUID策略保持字符串，防止被当作ObjectId；ObjectId策略尝试解析为ObjectId，解析失败时保持字符串
+pub(crate) fn id_value_to_bson(adapter: &MongoAdapter, value: &DataValue, id_strategy: &IdStrategy) -> Bson {
+    match (value, id_strategy) {
+        (DataValue::String(s), IdStrategy::Uuid | IdStrategy::UuidV7) => Bson::String(s.clone()),
+        (DataValue::String(s), IdStrategy::ObjectId) => match mongodb::bson::oid::ObjectId::parse_str(s) {
+            Ok(object_id) => Bson::ObjectId(object_id),
+            Err(_) => Bson::String(s.clone()),
+        },
+        _ => data_value_to_bson(adapter, value),
+    }
+}
+
     /// 将BSON文档转换为DataValue映射（不包装在Object中）
     pub(crate) fn document_to_data_map(adapter: &MongoAdapter, doc: &Document) -> QuickDbResult<HashMap<String, DataValue>> {
         let mut data_map = HashMap::new();
@@ -54,7 +69,7 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
         for (key, value) in doc {
             let mut data_value = bson_to_data_value(adapter, value)?;
 
-            // 特殊处理_id字段，映射为id并进行类型转换
+            // 特殊处理_id字段，按配置映射为模型的ID字段名并进行类型转换
             if key == "_id" {
                 match &data_value {
                     DataValue::String(s) => {
@@ -68,8 +83,7 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
                     },
                     _ => {}
                 }
-                // 将_id映射为id
-                data_map.insert("id".to_string(), data_value);
+                data_map.insert(adapter.id_field.field_name().to_string(), data_value);
             } else {
                 // 保持原始字段名
                 data_map.insert(key.clone(), data_value);
@@ -180,6 +194,11 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
         let mut query_doc = Document::new();
 
         for (index, condition) in conditions.iter().enumerate() {
+            if matches!(condition.operator, QueryOperator::Raw) {
+                return Err(QuickDbError::UnsupportedDatabase {
+                    db_type: "MongoDB 不支持原始SQL条件".to_string(),
+                });
+            }
             let field_name = map_field_name(adapter, &condition.field);
 
             // 特殊处理_id字段的ObjectId格式
@@ -210,10 +229,16 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
                    index, condition.field, field_name, condition.operator, condition.value, bson_value);
 
             match condition.operator {
+                QueryOperator::Eq if bson_value == Bson::Null => {
+                    query_doc.insert(field_name, null_match_document(adapter.null_matching, true));
+                },
                 QueryOperator::Eq => {
                     debug!("[MongoDB] 处理Eq操作符: {} = {:?}", field_name, bson_value);
                     query_doc.insert(field_name, bson_value);
                 },
+                QueryOperator::Ne if bson_value == Bson::Null => {
+                    query_doc.insert(field_name, null_match_document(adapter.null_matching, false));
+                },
                 QueryOperator::Ne => {
                     query_doc.insert(field_name, doc! { "$ne": bson_value });
                 },
@@ -229,9 +254,9 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
                 QueryOperator::Lte => {
                     query_doc.insert(field_name, doc! { "$lte": bson_value });
                 },
-                QueryOperator::Contains => {
+                QueryOperator::Contains | QueryOperator::IContains => {
                     if let Bson::String(s) = bson_value {
-                        let regex_doc = doc! { "$regex": s.clone(), "$options": "i" };
+                        let regex_doc = doc! { "$regex": regex::escape(&s), "$options": "i" };
                         debug!("[MongoDB] 处理Contains操作符(字符串): {} = {:?}", field_name, regex_doc);
                         query_doc.insert(field_name, regex_doc);
                     } else {
@@ -275,11 +300,19 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
                     }
                 },
                 QueryOperator::IsNull => {
-                    query_doc.insert(field_name, Bson::Null);
+                    query_doc.insert(field_name, null_match_document(adapter.null_matching, true));
                 },
                 QueryOperator::IsNotNull => {
-                    query_doc.insert(field_name, doc! { "$ne": Bson::Null });
+                    query_doc.insert(field_name, null_match_document(adapter.null_matching, false));
                 },
+                QueryOperator::ArrayContainsAll => {
+                    condition.array_elements()?;
+                    query_doc.insert(field_name, doc! { "$all": bson_value });
+                },
+                QueryOperator::ArraySize => {
+                    query_doc.insert(field_name, doc! { "$size": condition.array_size()? });
+                },
+                QueryOperator::Raw => unreachable!("原始条件已在前面拒绝"),
             }
         }
         
@@ -287,6 +320,69 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
         Ok(query_doc)
     }
 
+    /// 按查询日志模式输出一条MongoDB命令，未开启查询日志时不渲染文档
+    /// 把JSON数组形式的聚合管道转换为BSON文档，支持 `{"$oid": ...}`、`{"$date": ...}` 等扩展JSON写法
+    pub(crate) fn pipeline_from_json(pipeline: &[serde_json::Value]) -> QuickDbResult<Vec<Document>> {
+        pipeline.iter()
+            .enumerate()
+            .map(|(index, stage)| {
+                let invalid = |reason: String| QuickDbError::ValidationError {
+                    field: "pipeline".to_string(),
+                    message: format!("聚合管道第 {} 个阶段无效: {}", index + 1, reason),
+                };
+                match Bson::try_from(stage.clone()).map_err(|e| invalid(e.to_string()))? {
+                    Bson::Document(document) => Ok(document),
+                    other => Err(invalid(format!("阶段必须是对象，实际为 {:?}", other.element_type()))),
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn log_command(command: &str, collection: &str, documents: &[&Document]) {
+        crate::types::query_log::log_command(command, collection, |scope| {
+            documents
+                .iter()
+                .map(|document| render_logged_document(scope, document).to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+    }
+
+    /// 按查询日志模式渲染命令文档：`SqlOnly` 只保留结构，脱敏模式替换敏感键的值
+    fn render_logged_document(scope: &QueryLogScope, document: &Document) -> Document {
+        document
+            .iter()
+            .map(|(key, value)| {
+                let rendered = if scope.is_sensitive_field(key) {
+                    Bson::String(REDACTED.to_string())
+                } else {
+                    render_logged_bson(scope, value)
+                };
+                (key.clone(), rendered)
+            })
+            .collect()
+    }
+
+    fn render_logged_bson(scope: &QueryLogScope, value: &Bson) -> Bson {
+        match value {
+            Bson::Document(document) => Bson::Document(render_logged_document(scope, document)),
+            Bson::Array(values) => Bson::Array(values.iter().map(|v| render_logged_bson(scope, v)).collect()),
+            _ if scope.mode() == QueryLogMode::SqlOnly => Bson::String("?".to_string()),
+            other => other.clone(),
+        }
+    }
+
+    /// 按空值匹配语义构建"为空"或"不为空"的条件文档
+    fn null_match_document(mode: MongoNullMatching, is_null: bool) -> Document {
+        match (mode, is_null) {
+            (MongoNullMatching::NullOrMissing, true) => doc! { "$eq": Bson::Null },
+            (MongoNullMatching::NullOrMissing, false) => doc! { "$ne": Bson::Null },
+            // BSON 类型 10 即 null，字段缺失时 $type 不匹配
+            (MongoNullMatching::NullOnly, true) => doc! { "$type": 10 },
+            (MongoNullMatching::NullOnly, false) => doc! { "$not": { "$type": 10 } },
+        }
+    }
+
     /// 构建条件组合查询文档
     pub(crate) fn build_condition_groups_document(adapter: &MongoAdapter, condition_groups: &[QueryConditionGroup]) -> QuickDbResult<Document> {
         debug!("[MongoDB] 开始构建条件组查询文档，组数量: {}", condition_groups.len());
@@ -344,7 +440,7 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
                     return Ok(Document::new());
                 }
                 
-                if conditions.len() == 1 {
+                if conditions.len() == 1 && !matches!(operator, LogicalOperator::Not) {
                      // 单个条件组，递归处理
                      debug!("[MongoDB] 条件组只有一个条件，递归处理");
                      return build_single_condition_group_document(adapter, &conditions[0]);
@@ -375,6 +471,17 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
                     return Ok(Document::new());
                 }
                 
+                if matches!(operator, LogicalOperator::Not) {
+                    // NOT 对子条件整体（AND 连接）取反
+                    debug!("[MongoDB] 使用$nor对文档取反");
+                    let inner = if non_empty_docs.len() == 1 {
+                        non_empty_docs.into_iter().next().unwrap()
+                    } else {
+                        doc! { "$and": non_empty_docs }
+                    };
+                    return Ok(doc! { "$nor": [inner] });
+                }
+
                 if non_empty_docs.len() == 1 {
                     debug!("[MongoDB] 只有一个有效文档，直接返回");
                     return Ok(non_empty_docs.into_iter().next().unwrap());
@@ -389,6 +496,7 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
                         debug!("[MongoDB] 使用$or连接文档");
                         doc! { "$or": non_empty_docs }
                     },
+                    LogicalOperator::Not => unreachable!("NOT 已在上方处理"),
                 };
                 
                 debug!("[MongoDB] 条件组最终结果: {:?}", result_doc);
@@ -417,6 +525,194 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
         update_doc
     }
 
+    /// 构建排序文档
+    ///
+    /// 分页查询时，若排序字段不能唯一确定文档顺序，skip/limit在相同排序值的文档之间
+    /// 没有稳定顺序，会导致跨页重复或遗漏记录。此时自动追加`_id`作为最后的排序键，
+    /// 其方向与最后一个排序字段保持一致（无排序时按`_id`升序）。
+    pub(crate) fn build_sort_document(adapter: &MongoAdapter, table: &str, options: &QueryOptions) -> Option<Document> {
+        let mut sort_doc = Document::new();
+        for sort_field in &options.sort {
+            let sort_value = match sort_field.direction {
+                SortDirection::Asc => 1,
+                SortDirection::Desc => -1,
+            };
+            sort_doc.insert(map_field_name(adapter, &sort_field.field), sort_value);
+        }
+
+        if options.pagination.is_some() && !is_sort_unique(table, &sort_doc) {
+            let tiebreaker = options.sort.last()
+                .map(|s| if matches!(s.direction, SortDirection::Desc) { -1 } else { 1 })
+                .unwrap_or(1);
+            debug!("MongoDB分页排序字段不唯一，追加_id作为排序决胜键: table={}", table);
+            sort_doc.insert("_id", tiebreaker);
+        }
+
+        if sort_doc.is_empty() {
+            None
+        } else {
+            Some(sort_doc)
+        }
+    }
+
+    /// 判断排序字段组合是否能唯一确定文档（包含_id、唯一字段或完整的唯一索引）
+    fn is_sort_unique(table: &str, sort_doc: &Document) -> bool {
+        if sort_doc.contains_key("_id") {
+            return true;
+        }
+        let Some(model_meta) = crate::manager::get_model(table) else {
+            return false;
+        };
+        let field_unique = sort_doc.keys().any(|key| {
+            model_meta.fields.get(key).map(|f| f.unique).unwrap_or(false)
+        });
+        field_unique || model_meta.indexes.iter().any(|index| {
+            index.unique
+                && !index.fields.is_empty()
+                && index.fields.iter().all(|f| sort_doc.contains_key(f))
+        })
+    }
+
+    /// 将计算表达式转换为MongoDB聚合表达式
+    ///
+    /// 字段引用转为 `$字段名`，字面量用 `$literal` 包裹，避免以 `$` 开头的字符串被当作字段路径
+    pub(crate) fn expr_to_bson(adapter: &MongoAdapter, expr: &Expr) -> QuickDbResult<Bson> {
+        match expr {
+            Expr::Column(name) => Ok(Bson::String(format!("${}", map_field_name(adapter, name)))),
+            Expr::Literal(value) => Ok(Bson::Document(doc! { "$literal": data_value_to_bson(adapter, value) })),
+            Expr::Binary { left, op, right } => {
+                let operator = match op {
+                    BinaryOperator::Add => "$add",
+                    BinaryOperator::Sub => "$subtract",
+                    BinaryOperator::Mul => "$multiply",
+                    BinaryOperator::Div => "$divide",
+                    BinaryOperator::Mod => "$mod",
+                };
+                let operands = vec![expr_to_bson(adapter, left)?, expr_to_bson(adapter, right)?];
+                Ok(Bson::Document(doc! { operator: operands }))
+            }
+            Expr::Function { function, args } => {
+                expr.validate()?;
+                let operands = args
+                    .iter()
+                    .map(|arg| expr_to_bson(adapter, arg))
+                    .collect::<QuickDbResult<Vec<_>>>()?;
+                let operator = match function {
+                    ScalarFunction::Lower => "$toLower",
+                    ScalarFunction::Upper => "$toUpper",
+                    ScalarFunction::Length => "$strLenCP",
+                    ScalarFunction::Abs => "$abs",
+                    ScalarFunction::Round => "$round",
+                    ScalarFunction::Coalesce => "$ifNull",
+                    ScalarFunction::Concat => "$concat",
+                };
+                // $ifNull 至少需要两个操作数，单参数时补 null
+                let operands = if matches!(function, ScalarFunction::Coalesce) && operands.len() == 1 {
+                    vec![operands[0].clone(), Bson::Null]
+                } else {
+                    operands
+                };
+                Ok(Bson::Document(doc! { operator: operands }))
+            }
+        }
+    }
+
+    /// 构建计算字段的 `$addFields` 文档，保留文档原有字段
+    pub(crate) fn build_add_fields_document(adapter: &MongoAdapter, select_exprs: &[(Expr, String)]) -> QuickDbResult<Document> {
+        let mut fields = Document::new();
+        for (expr, alias) in select_exprs {
+            if alias.is_empty() || alias.starts_with('$') || alias.contains('.') {
+                return Err(QuickDbError::ValidationError {
+                    field: alias.clone(),
+                    message: "计算字段名不能为空，且不能以$开头或包含点号".to_string(),
+                });
+            }
+            fields.insert(alias.clone(), expr_to_bson(adapter, expr)?);
+        }
+        Ok(fields)
+    }
+
+    /// 构建聚合查询的 `$group` 文档
+    ///
+    /// 分组字段依次放在 `_id.g0`、`_id.g1`… 下，避免字段路径中的点号成为文档键；
+    /// 没有分组字段时 `_id` 为 null，整个集合聚合为一组
+    pub(crate) fn build_group_document(adapter: &MongoAdapter, aggregations: &[Aggregation], group_by: &[String]) -> QuickDbResult<Document> {
+        Aggregation::validate(aggregations, group_by)?;
+        let id = if group_by.is_empty() {
+            Bson::Null
+        } else {
+            let mut keys = Document::new();
+            for (index, field) in group_by.iter().enumerate() {
+                keys.insert(format!("g{}", index), format!("${}", map_field_name(adapter, field)));
+            }
+            Bson::Document(keys)
+        };
+
+        let mut group = doc! { "_id": id };
+        for aggregation in aggregations {
+            let alias = &aggregation.alias;
+            if alias.is_empty() || alias == "_id" || alias.starts_with('$') || alias.contains('.') {
+                return Err(QuickDbError::ValidationError {
+                    field: alias.clone(),
+                    message: "聚合结果字段名不能为空或_id，且不能以$开头或包含点号".to_string(),
+                });
+            }
+            let field = format!("${}", map_field_name(adapter, &aggregation.field));
+            let accumulator = match aggregation.func {
+                AggFunc::Count if aggregation.field == "*" => doc! { "$sum": 1 },
+                // 与 SQL 的 COUNT(字段) 一致，只统计字段存在且不为 null 的文档
+                AggFunc::Count => doc! { "$sum": { "$cond": [{ "$gt": [field, null] }, 1, 0] } },
+                AggFunc::Sum => doc! { "$sum": field },
+                AggFunc::Avg => doc! { "$avg": field },
+                AggFunc::Min => doc! { "$min": field },
+                AggFunc::Max => doc! { "$max": field },
+            };
+            group.insert(alias.clone(), accumulator);
+        }
+        Ok(group)
+    }
+
+    /// 将 `$group` 结果文档还原为以分组字段和聚合结果字段名为键的对象
+    pub(crate) fn group_document_to_row(adapter: &MongoAdapter, doc: &Document, aggregations: &[Aggregation], group_by: &[String]) -> QuickDbResult<DataValue> {
+        let mut row = HashMap::new();
+        let keys = doc.get_document("_id").ok();
+        for (index, field) in group_by.iter().enumerate() {
+            let value = match keys.and_then(|keys| keys.get(format!("g{}", index))) {
+                Some(value) => bson_to_data_value(adapter, value)?,
+                None => DataValue::Null,
+            };
+            row.insert(field.clone(), value);
+        }
+        for aggregation in aggregations {
+            let value = match doc.get(&aggregation.alias) {
+                Some(value) => bson_to_data_value(adapter, value)?,
+                None => DataValue::Null,
+            };
+            row.insert(aggregation.alias.clone(), value);
+        }
+        Ok(DataValue::Object(row))
+    }
+
+    /// 构建字段选择的投影文档，未选择字段时返回 `None`
+    ///
+    /// 计算字段的别名始终保留；未选择 `id` 时排除默认返回的 `_id`
+    pub(crate) fn build_projection_document(adapter: &MongoAdapter, options: &QueryOptions) -> Option<Document> {
+        if options.fields.is_empty() {
+            return None;
+        }
+        let mut projection = Document::new();
+        for field in &options.fields {
+            projection.insert(map_field_name(adapter, field), 1);
+        }
+        for (_, alias) in &options.select_exprs {
+            projection.insert(alias.clone(), 1);
+        }
+        if !projection.contains_key("_id") {
+            projection.insert("_id", 0);
+        }
+        Some(projection)
+    }
+
     /// 获取集合引用
     pub(crate) fn get_collection(adapter: &MongoAdapter, db: &mongodb::Database, table: &str) -> Collection<Document> {
         db.collection::<Document>(table)
@@ -449,3 +745,351 @@ pub(crate) fn data_value_to_bson(adapter: &MongoAdapter, value: &DataValue) -> B
         mapped_data
     }
 
+/// 构建插入文档
+///
+/// 字段名映射为MongoDB字段名（id -> _id），填充模型默认值，并按ID策略处理ID字段；
+/// 同时返回ODM层生成的ID，没有时由MongoDB生成
+pub(crate) fn build_insert_document(
+    adapter: &MongoAdapter,
+    table: &str,
+    data: &HashMap<String, DataValue>,
+    id_strategy: &IdStrategy,
+) -> QuickDbResult<(Document, Option<String>)> {
+    // 映射字段名（id -> _id）并处理ID策略
+    let mut mapped_data = map_data_fields(adapter, data);
+    apply_field_defaults(table, &mut mapped_data);
+
+    // 根据ID策略处理ID字段
+    if mapped_data.contains_key("_id") {
+        let strategy = id_strategy;
+        match strategy {
+            IdStrategy::AutoIncrement | IdStrategy::ObjectId => {
+                // 对于这些策略，移除空的ID字段，让MongoDB自动生成
+                if let Some(DataValue::String(s)) = mapped_data.get("_id") {
+                    if s.is_empty() {
+                        mapped_data.remove("_id");
+                    }
+                }
+            },
+            IdStrategy::Snowflake { .. } | IdStrategy::Uuid | IdStrategy::UuidV7 | IdStrategy::Ulid => {
+                // 对于雪花、UUID和ULID策略，移除空的ID字段，让ODM层生成的ID生效
+                if let Some(DataValue::String(s)) = mapped_data.get("_id") {
+                    if s.is_empty() {
+                        mapped_data.remove("_id");
+                    }
+                }
+            },
+            IdStrategy::Custom(_) => {
+                // 自定义策略保留ID字段
+            }
+        }
+    } else {
+        // 没有ID字段，检查策略是否需要ID
+        match id_strategy {
+            IdStrategy::Snowflake { .. } => {
+                // 雪花策略需要ID字段
+                return Err(QuickDbError::ValidationError {
+                    field: "_id".to_string(),
+                    message: format!("使用{:?}策略时必须提供ID字段", id_strategy),
+                });
+            },
+            IdStrategy::Uuid => {
+                // MongoDB的UUID策略不要求提供ID字段，可以自动生成字符串UUID
+                // 符合我们的设计：MongoDB将UUID作为字符串处理
+            },
+            _ => {} // 其他策略不需要ID字段
+        }
+    }
+
+    let mut doc = Document::new();
+    for (key, value) in &mapped_data {
+        // 特殊处理_id字段，根据ID策略决定BSON类型
+        if key == "_id" {
+            doc.insert(key, id_value_to_bson(adapter, value, id_strategy));
+        } else {
+            doc.insert(key, data_value_to_bson(adapter, value));
+        }
+    }
+
+    let preset_id = match mapped_data.get("_id") {
+        Some(DataValue::String(id)) if !id.is_empty() => Some(id.clone()),
+        _ => None,
+    };
+    Ok((doc, preset_id))
+}
+
+/// 把MongoDB生成的ID转换为字符串，ObjectId使用十六进制形式
+pub(crate) fn inserted_id_to_string(id: &Bson) -> String {
+    match id {
+        Bson::ObjectId(oid) => oid.to_hex(),
+        other => other.to_string(),
+    }
+}
+
+/// 为省略或为空的字段填充模型定义的默认值
+///
+/// MongoDB 没有列默认值，SQL数据库由建表时的 `DEFAULT` 子句完成同样的工作
+pub(crate) fn apply_field_defaults(table: &str, data: &mut HashMap<String, DataValue>) {
+    let Some(model_meta) = crate::manager::get_model(table) else {
+        return;
+    };
+    for (name, field_definition) in &model_meta.fields {
+        if name == "id" || name == "_id" || !matches!(data.get(name), None | Some(DataValue::Null)) {
+            continue;
+        }
+        if let Some(value) = field_definition.default_for_insert() {
+            data.insert(name.clone(), value);
+        }
+    }
+}
+
+/// 把JSON合并补丁展开为点号路径的 `$set` / `$unset` 条目
+///
+/// 嵌套对象逐层展开，只触及补丁里出现的键；值为 null 的键写入 `$unset`。
+/// 键中含有 `.` 或以 `$` 开头时无法安全表示为路径，直接拒绝
+pub(crate) fn flatten_json_merge_patch(
+    prefix: &str,
+    patch: &serde_json::Map<String, serde_json::Value>,
+    set_doc: &mut Document,
+    unset_doc: &mut Document,
+) -> QuickDbResult<()> {
+    for (key, value) in patch {
+        if key.is_empty() || key.contains('.') || key.starts_with('$') {
+            return Err(QuickDbError::ValidationError {
+                field: prefix.to_string(),
+                message: format!("JSON合并补丁的键 '{}' 不能为空、包含 '.' 或以 '$' 开头", key),
+            });
+        }
+        let path = format!("{}.{}", prefix, key);
+        match value {
+            serde_json::Value::Null => {
+                unset_doc.insert(path, "");
+            }
+            serde_json::Value::Object(nested) => {
+                flatten_json_merge_patch(&path, nested, set_doc, unset_doc)?;
+            }
+            other => {
+                let bson_value = mongodb::bson::to_bson(other).map_err(|e| QuickDbError::SerializationError {
+                    message: format!("JSON合并补丁转换为BSON失败: {}", e),
+                })?;
+                set_doc.insert(path, bson_value);
+            }
+        }
+    }
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_field_mapping_follows_config() {
+        let document = doc! { "_id": "u1", "name": "alice" };
+
+        let data = document_to_data_map(&MongoAdapter::new(), &document).expect("转换文档失败");
+        assert_eq!(data.get("id"), Some(&DataValue::String("u1".to_string())));
+        assert!(!data.contains_key("_id"));
+
+        let adapter = MongoAdapter::new().with_id_field(MongoIdField::UnderscoreId);
+        let data = document_to_data_map(&adapter, &document).expect("转换文档失败");
+        assert_eq!(data.get("_id"), Some(&DataValue::String("u1".to_string())));
+        assert!(!data.contains_key("id"));
+
+        // 写入和查询条件中的两种写法都映射到 _id
+        assert_eq!(map_field_name(&adapter, "id"), "_id");
+        assert_eq!(map_field_name(&adapter, "_id"), "_id");
+        let query = build_query_document(&adapter, &[QueryCondition::new("id", QueryOperator::Eq, "u1")])
+            .expect("构建查询失败");
+        assert_eq!(query, doc! { "_id": "u1" });
+    }
+
+    #[test]
+    fn test_json_merge_patch_flattens_to_dotted_paths() {
+        let patch = serde_json::json!({ "prefs": { "lang": "zh", "beta": null }, "tmp": null, "age": 3 });
+        let mut set_doc = Document::new();
+        let mut unset_doc = Document::new();
+        flatten_json_merge_patch("profile", patch.as_object().unwrap(), &mut set_doc, &mut unset_doc)
+            .expect("展开补丁失败");
+
+        assert_eq!(set_doc, doc! { "profile.age": 3i64, "profile.prefs.lang": "zh" });
+        assert_eq!(unset_doc, doc! { "profile.prefs.beta": "", "profile.tmp": "" });
+
+        let bad = serde_json::json!({ "a.b": 1 });
+        assert!(flatten_json_merge_patch("profile", bad.as_object().unwrap(), &mut set_doc, &mut unset_doc).is_err());
+    }
+
+    #[test]
+    fn test_null_operators_use_existence_semantics() {
+        let adapter = MongoAdapter::new();
+        let query = build_query_document(&adapter, &[
+            QueryCondition::new("last_login", QueryOperator::IsNull, DataValue::Null),
+            QueryCondition::new("email", QueryOperator::IsNotNull, DataValue::Null),
+        ]).expect("构建查询失败");
+
+        assert_eq!(query.get_document("last_login").unwrap(), &doc! { "$eq": Bson::Null });
+        assert_eq!(query.get_document("email").unwrap(), &doc! { "$ne": Bson::Null });
+
+        let query = build_query_document(&adapter, &[
+            QueryCondition::new("deleted_at", QueryOperator::Eq, DataValue::Null),
+            QueryCondition::new("archived_at", QueryOperator::Ne, DataValue::Null),
+        ]).expect("构建查询失败");
+        assert_eq!(query.get_document("deleted_at").unwrap(), &doc! { "$eq": Bson::Null });
+        assert_eq!(query.get_document("archived_at").unwrap(), &doc! { "$ne": Bson::Null });
+    }
+
+    #[test]
+    fn test_string_match_operators_escape_regex() {
+        let adapter = MongoAdapter::new();
+        let query = build_query_document(&adapter, &[
+            QueryCondition::new("name", QueryOperator::IContains, "a.b*"),
+            QueryCondition::new("code", QueryOperator::StartsWith, "50%_"),
+        ]).expect("构建查询失败");
+        assert_eq!(query.get_document("name").unwrap(), &doc! { "$regex": "a\\.b\\*", "$options": "i" });
+        assert_eq!(query.get_document("code").unwrap(), &doc! { "$regex": "^50%_", "$options": "i" });
+    }
+
+    #[test]
+    fn test_pipeline_from_json_accepts_extended_json() {
+        let pipeline = pipeline_from_json(&[
+            serde_json::json!({ "$match": { "_id": { "$oid": "507f1f77bcf86cd799439011" }, "qty": { "$gt": 5 } } }),
+            serde_json::json!({ "$unwind": "$items" }),
+        ]).expect("转换聚合管道失败");
+        assert_eq!(pipeline.len(), 2);
+        let matched = pipeline[0].get_document("$match").unwrap();
+        assert!(matches!(matched.get("_id"), Some(Bson::ObjectId(_))));
+        assert_eq!(pipeline[1], doc! { "$unwind": "$items" });
+
+        let err = pipeline_from_json(&[serde_json::json!("$match")]);
+        assert!(matches!(err, Err(QuickDbError::ValidationError { .. })), "{:?}", err);
+    }
+
+    #[test]
+    fn test_array_operators_map_to_all_and_size() {
+        let adapter = MongoAdapter::new();
+        let query = build_query_document(&adapter, &[
+            QueryCondition::new(
+                "tags",
+                QueryOperator::ArrayContainsAll,
+                DataValue::Array(vec![DataValue::String("rust".to_string()), DataValue::String("db".to_string())]),
+            ),
+        ]).expect("构建查询失败");
+        assert_eq!(query.get_document("tags").unwrap(), &doc! { "$all": ["rust", "db"] });
+
+        let query = build_query_document(&adapter, &[
+            QueryCondition::new("tags", QueryOperator::ArraySize, 3i64),
+        ]).expect("构建查询失败");
+        assert_eq!(query.get_document("tags").unwrap(), &doc! { "$size": 3i64 });
+
+        assert!(build_query_document(&adapter, &[
+            QueryCondition::new("tags", QueryOperator::ArraySize, -1i64),
+        ]).is_err());
+    }
+
+    #[test]
+    fn test_raw_sql_conditions_are_rejected() {
+        let adapter = MongoAdapter::new();
+        let err = build_query_document(&adapter, &[
+            QueryCondition::raw("age > $1", vec![DataValue::Int(1)]),
+        ]).unwrap_err();
+        assert!(matches!(err, QuickDbError::UnsupportedDatabase { .. }));
+    }
+
+    #[test]
+    fn test_logged_documents_follow_query_log_mode() {
+        // 日志作用域只读取日志相关配置，与数据库类型无关
+        let mut config = crate::types::DatabaseConfig::builder()
+            .db_type(DatabaseType::SQLite)
+            .connection(ConnectionConfig::SQLite { path: ":memory:".to_string(), create_if_missing: true })
+            .pool(PoolConfig::default())
+            .alias("default")
+            .id_strategy(IdStrategy::AutoIncrement)
+            .sensitive_fields(vec!["password".to_string()])
+            .log_queries(QueryLogMode::SqlWithRedactedParams)
+            .build()
+            .expect("构建配置失败");
+        let document = doc! { "username": "alice", "auth": { "password": "s3cret" }, "age": { "$gt": 18 } };
+
+        let scope = QueryLogScope::new(&config, None).unwrap();
+        assert_eq!(
+            render_logged_document(&scope, &document),
+            doc! { "username": "alice", "auth": { "password": "***" }, "age": { "$gt": 18 } }
+        );
+
+        config.log_queries = QueryLogMode::SqlOnly;
+        let scope = QueryLogScope::new(&config, None).unwrap();
+        assert_eq!(
+            render_logged_document(&scope, &document),
+            doc! { "username": "?", "auth": { "password": "?" }, "age": { "$gt": "?" } }
+        );
+    }
+
+    #[test]
+    fn test_null_only_matching_uses_bson_type() {
+        assert_eq!(null_match_document(MongoNullMatching::NullOnly, true), doc! { "$type": 10 });
+        assert_eq!(null_match_document(MongoNullMatching::NullOnly, false), doc! { "$not": { "$type": 10 } });
+
+        // 匹配语义按适配器（即别名）配置，默认适配器不受影响
+        let adapter = MongoAdapter::new().with_null_matching(MongoNullMatching::NullOnly);
+        let condition = [QueryCondition::new("last_login", QueryOperator::IsNull, DataValue::Null)];
+        let query = build_query_document(&adapter, &condition).expect("构建查询失败");
+        assert_eq!(query.get_document("last_login").unwrap(), &doc! { "$type": 10 });
+        let query = build_query_document(&MongoAdapter::new(), &condition).expect("构建查询失败");
+        assert_eq!(query.get_document("last_login").unwrap(), &doc! { "$eq": Bson::Null });
+    }
+
+    #[test]
+    fn test_select_exprs_map_to_aggregation_operators() {
+        let adapter = MongoAdapter::new();
+        let fields = build_add_fields_document(&adapter, &[
+            (Expr::col("price") * Expr::col("quantity"), "total".to_string()),
+            (Expr::func(ScalarFunction::Upper, vec![Expr::col("name")]), "upper_name".to_string()),
+            (Expr::col("id") + Expr::lit(1i64), "next_id".to_string()),
+        ]).expect("构建计算字段失败");
+
+        assert_eq!(fields.get_document("total").unwrap(), &doc! { "$multiply": ["$price", "$quantity"] });
+        assert_eq!(fields.get_document("upper_name").unwrap(), &doc! { "$toUpper": ["$name"] });
+        assert_eq!(
+            fields.get_document("next_id").unwrap(),
+            &doc! { "$add": ["$_id", { "$literal": 1i64 }] }
+        );
+        assert!(build_add_fields_document(&adapter, &[(Expr::col("a"), "$bad".to_string())]).is_err());
+    }
+
+    #[test]
+    fn test_aggregations_map_to_group_stage() {
+        let adapter = MongoAdapter::new();
+        let aggregations = vec![
+            Aggregation::new(AggFunc::Sum, "amount", "total"),
+            Aggregation::new(AggFunc::Count, "coupon", "with_coupon"),
+            Aggregation::count("orders"),
+        ];
+        let group_by = vec!["customer_id".to_string()];
+        let group = build_group_document(&adapter, &aggregations, &group_by).expect("构建分组文档失败");
+        assert_eq!(
+            group,
+            doc! {
+                "_id": { "g0": "$customer_id" },
+                "total": { "$sum": "$amount" },
+                "with_coupon": { "$sum": { "$cond": [{ "$gt": ["$coupon", null] }, 1, 0] } },
+                "orders": { "$sum": 1 },
+            }
+        );
+
+        let row = group_document_to_row(
+            &adapter,
+            &doc! { "_id": { "g0": "c1" }, "total": 30i64, "with_coupon": 1, "orders": 2 },
+            &aggregations,
+            &group_by,
+        ).unwrap();
+        let DataValue::Object(row) = row else { panic!("结果应为对象") };
+        assert_eq!(row.get("customer_id"), Some(&DataValue::String("c1".to_string())));
+        assert_eq!(row.get("total"), Some(&DataValue::Int(30)));
+        assert_eq!(row.get("orders"), Some(&DataValue::Int(2)));
+
+        let whole = build_group_document(&adapter, &[Aggregation::new(AggFunc::Max, "id", "last")], &[]).unwrap();
+        assert_eq!(whole, doc! { "_id": null, "last": { "$max": "$_id" } });
+        assert!(build_group_document(&adapter, &[Aggregation::count("_id")], &[]).is_err());
+    }
+}
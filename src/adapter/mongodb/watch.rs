@@ -0,0 +1,93 @@
+//! MongoDB变更订阅模块
+//!
+//! 基于 Change Stream 实现，要求MongoDB以副本集或分片集群方式部署
+
+use crate::adapter::MongoAdapter;
+use crate::pool::DatabaseConnection;
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::*;
+use futures::StreamExt;
+use mongodb::change_stream::event::{ChangeStreamEvent, OperationType};
+use mongodb::options::{ChangeStreamOptions, FullDocumentType};
+use mongodb::bson::Document;
+use rat_logger::debug;
+use std::sync::Arc;
+
+use super::utils as mongodb_utils;
+
+/// 订阅集合的数据变更
+pub(crate) async fn watch(
+    adapter: &MongoAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+) -> QuickDbResult<ChangeStream> {
+    let db = match connection {
+        DatabaseConnection::MongoDB(db) => db,
+        _ => return Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望MongoDB连接".to_string(),
+        }),
+    };
+
+    let collection = mongodb_utils::get_collection(adapter, db, table);
+    // 更新事件回查完整文档，保证事件中包含更新后的记录
+    let options = ChangeStreamOptions::builder()
+        .full_document(Some(FullDocumentType::UpdateLookup))
+        .build();
+
+    let change_stream = collection.watch(None, options).await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("打开MongoDB变更流失败: {}", e),
+        })?;
+
+    debug!("开始监听MongoDB集合变更: {}", table);
+
+    // 变更流为'static，文档转换使用独立的适配器实例
+    let converter = Arc::new(MongoAdapter::new().with_id_field(adapter.id_field));
+    let table = table.to_string();
+    let stream = change_stream.filter_map(move |event| {
+        let converter = converter.clone();
+        let table = table.clone();
+        async move {
+            match event {
+                Ok(event) => convert_event(&converter, &table, event),
+                Err(e) => Some(Err(QuickDbError::ConnectionError {
+                    message: format!("接收MongoDB变更事件失败: {}", e),
+                })),
+            }
+        }
+    });
+
+    Ok(ChangeStream::new(stream.boxed()))
+}
+
+/// 将MongoDB变更事件转换为通用变更事件，非数据变更事件（drop、rename等）会被跳过
+fn convert_event(
+    adapter: &MongoAdapter,
+    table: &str,
+    event: ChangeStreamEvent<Document>,
+) -> Option<QuickDbResult<ChangeEvent>> {
+    let op = match event.operation_type {
+        OperationType::Insert => ChangeOperation::Insert,
+        OperationType::Update | OperationType::Replace => ChangeOperation::Update,
+        OperationType::Delete => ChangeOperation::Delete,
+        _ => return None,
+    };
+
+    let id = match event.document_key.as_ref().and_then(|key| key.get("_id")) {
+        Some(id) => match mongodb_utils::bson_to_data_value(adapter, id) {
+            Ok(id) => id,
+            Err(e) => return Some(Err(e)),
+        },
+        None => DataValue::Null,
+    };
+
+    let document = match event.full_document.as_ref() {
+        Some(doc) => match mongodb_utils::document_to_data_value(adapter, doc) {
+            Ok(doc) => Some(doc),
+            Err(e) => return Some(Err(e)),
+        },
+        None => None,
+    };
+
+    Some(Ok(ChangeEvent { table: table.to_string(), op, id, document }))
+}
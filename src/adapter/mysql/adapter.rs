@@ -4,6 +4,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use dashmap::DashSet;
 use tokio::sync::Mutex;
 use rat_logger::{debug, info};
 
@@ -11,8 +12,14 @@ use rat_logger::{debug, info};
 pub struct MysqlAdapter {
     /// 表创建锁，防止重复创建表
     creation_locks: Arc<Mutex<HashMap<String, ()>>>,
+    /// 已确认存在的表，写入时跳过重复的存在性检查
+    known_tables: Arc<DashSet<String>>,
     /// 存储过程映射表，存储已创建的存储过程信息
     pub(crate) stored_procedures: Arc<Mutex<HashMap<String, crate::stored_procedure::StoredProcedureInfo>>>,
+    /// 日期时间列的小数秒位数，`None` 时使用数据库默认精度
+    pub(crate) datetime_precision: Option<u8>,
+    /// 是否对TEXT/VARCHAR列进行JSON内容探测
+    pub(crate) json_text_detection: bool,
 }
 
 impl MysqlAdapter {
@@ -20,10 +27,25 @@ impl MysqlAdapter {
     pub fn new() -> Self {
         Self {
             creation_locks: Arc::new(Mutex::new(HashMap::new())),
+            known_tables: Arc::new(DashSet::new()),
             stored_procedures: Arc::new(Mutex::new(HashMap::new())),
+            datetime_precision: None,
+            json_text_detection: false,
         }
     }
 
+    /// 设置建表时日期时间列的小数秒位数
+    pub fn with_datetime_precision(mut self, precision: Option<u8>) -> Self {
+        self.datetime_precision = precision;
+        self
+    }
+
+    /// 设置是否对TEXT/VARCHAR列进行JSON内容探测
+    pub fn with_json_text_detection(mut self, enabled: bool) -> Self {
+        self.json_text_detection = enabled;
+        self
+    }
+
     /// 获取表创建锁
     pub(crate) async fn acquire_table_lock(&self, table: &str) -> tokio::sync::MutexGuard<'_, HashMap<String, ()>> {
         let mut locks = self.creation_locks.lock().await;
@@ -34,6 +56,21 @@ impl MysqlAdapter {
         locks
     }
 
+    /// 表是否已确认存在
+    pub(crate) fn is_known_table(&self, table: &str) -> bool {
+        self.known_tables.contains(table)
+    }
+
+    /// 记录表已存在
+    pub(crate) fn mark_table_known(&self, table: &str) {
+        self.known_tables.insert(table.to_string());
+    }
+
+    /// 移除表的存在记录，表被删除后必须调用
+    pub(crate) fn forget_table(&self, table: &str) {
+        self.known_tables.remove(table);
+    }
+
     /// 释放表创建锁
     pub(crate) async fn release_table_lock(&self, table: &str, mut locks: tokio::sync::MutexGuard<'_, HashMap<String, ()>>) {
         locks.remove(table);
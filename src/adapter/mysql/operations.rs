@@ -6,7 +6,7 @@ use crate::adapter::query_builder::SqlQueryBuilder;
 use crate::pool::DatabaseConnection;
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
-use crate::model::{FieldType, FieldDefinition};
+use crate::model::{FieldType, FieldDefinition, IndexDefinition};
 use crate::manager;
 use async_trait::async_trait;
 use rat_logger::debug;
@@ -15,9 +15,14 @@ use sqlx::Row;
 
 use super::query as mysql_query;
 use super::schema as mysql_schema;
+use super::blob as mysql_blob;
 
 #[async_trait]
 impl DatabaseAdapter for MysqlAdapter {
+    fn capabilities(&self) -> Capabilities {
+        DatabaseType::MySQL.capabilities()
+    }
+
     async fn create(
         &self,
         connection: &DatabaseConnection,
@@ -26,32 +31,8 @@ impl DatabaseAdapter for MysqlAdapter {
         id_strategy: &IdStrategy,
     ) -> QuickDbResult<DataValue> {
         if let DatabaseConnection::MySQL(pool) = connection {
-            // 自动建表逻辑：检查表是否存在，如果不存在则创建
-            if !self.table_exists(connection, table).await? {
-                // 获取表创建锁，防止重复创建
-                let _lock = self.acquire_table_lock(table).await;
-                // 再次检查表是否存在（双重检查锁定模式）
-                if !self.table_exists(connection, table).await? {
-                    // 尝试从模型管理器获取预定义的元数据
-                    if let Some(model_meta) = manager::get_model(table) {
-                        debug!("表 {} 不存在，使用预定义模型元数据创建", table);
-
-                        // 使用模型元数据创建表
-                        self.create_table(connection, table, &model_meta.fields, id_strategy).await?;
-                        // 等待100ms确保数据库事务完全提交
-                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                        debug!("⏱️ 等待100ms确保表 '{}' 创建完成", table);
-                    } else {
-                        return Err(QuickDbError::ValidationError {
-                            field: "table_creation".to_string(),
-                            message: format!("表 '{}' 不存在，且没有预定义的模型元数据。请先定义模型并使用 define_model! 宏明确指定字段类型。", table),
-                        });
-                    }
-                } else {
-                    debug!("表 {} 已存在，跳过创建", table);
-                }
-                // 锁会在这里自动释放（当 _lock 超出作用域时）
-            }
+            // 自动建表逻辑：表不存在时按预定义的模型元数据创建
+            mysql_schema::ensure_table(self, connection, table, id_strategy).await?;
             
             let (sql, params) = SqlQueryBuilder::new()
                 .database_type(crate::types::DatabaseType::MySQL)
@@ -62,13 +43,23 @@ impl DatabaseAdapter for MysqlAdapter {
             debug!("生成的INSERT SQL: {}", sql);
             debug!("绑定参数: {:?}", params);
 
-            // 使用事务确保插入和获取ID在同一个连接中
-            let mut tx = pool.begin().await
-                .map_err(|e| QuickDbError::QueryError {
-                    message: format!("开始事务失败: {}", e),
-                })?;
+            // 使用事务确保插入和获取ID在同一个连接中；处于适配器事务作用域（如 seed）时加入该事务，由作用域统一提交
+            let mut scoped_tx = crate::adapter::transaction::current_mysql_transaction().await;
+            let mut own_tx = match scoped_tx {
+                Some(_) => None,
+                None => Some(pool.begin().await
+                    .map_err(|e| QuickDbError::QueryError {
+                        message: format!("开始事务失败: {}", e),
+                    })?),
+            };
+            let tx: &mut sqlx::MySqlConnection = match (&mut scoped_tx, &mut own_tx) {
+                (Some(scoped), _) => &mut **scoped,
+                (None, Some(own)) => &mut **own,
+                (None, None) => unreachable!("未加入事务作用域时必定开启了新事务"),
+            };
             
             let affected_rows = {
+                crate::types::query_log::log_sql(&sql, &params);
                 let mut query = sqlx::query(&sql);
                 // 绑定参数
                 for param in &params {
@@ -146,8 +137,11 @@ impl DatabaseAdapter for MysqlAdapter {
                 }
             };
 
-            // 提交事务
-            let commit_result = tx.commit().await;
+            // 提交事务（加入的作用域事务不在这里提交）
+            let commit_result = match own_tx {
+                Some(own) => own.commit().await,
+                None => Ok(()),
+            };
             match commit_result {
                 Ok(_) => debug!("✅ 事务提交成功"),
                 Err(e) => {
@@ -173,6 +167,114 @@ impl DatabaseAdapter for MysqlAdapter {
         }
     }
 
+    async fn create_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        data_list: &[HashMap<String, DataValue>],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            if data_list.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            // 自增ID只能通过 LAST_INSERT_ID() 逐条取回，多行插入在交错锁模式下无法保证ID连续，逐条写入
+            let has_id = |data: &HashMap<String, DataValue>| {
+                data.get("id").is_some_and(|id| !matches!(id, DataValue::Null))
+            };
+            if !data_list.iter().all(has_id) {
+                let mut results = Vec::with_capacity(data_list.len());
+                for data in data_list {
+                    results.push(self.create(connection, table, data, id_strategy).await?);
+                }
+                return Ok(results);
+            }
+
+            mysql_schema::ensure_table(self, connection, table, id_strategy).await?;
+
+            for (columns, chunk) in crate::adapter::insert_many_batches(DatabaseType::MySQL, data_list) {
+                let sql = crate::adapter::insert_many_sql(DatabaseType::MySQL, table, &columns, chunk.len(), None)?;
+                let params = crate::adapter::upsert_params(chunk, &columns);
+                debug!("执行MySQL批量插入: {}", sql);
+                self.execute_update(pool, &sql, &params).await?;
+            }
+            Ok(data_list.iter().map(|data| data["id"].clone()).collect())
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
+
+    async fn upsert_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        rows: &[HashMap<String, DataValue>],
+        conflict_fields: &[String],
+        _id_strategy: &IdStrategy,
+    ) -> QuickDbResult<UpsertResult> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            let columns = crate::adapter::upsert_columns(rows, conflict_fields)?;
+            let chunk_size = crate::adapter::upsert_chunk_size(DatabaseType::MySQL, columns.len());
+            for chunk in rows.chunks(chunk_size) {
+                let sql = crate::adapter::upsert_many_sql(DatabaseType::MySQL, table, &columns, conflict_fields, chunk.len())?;
+                let params = crate::adapter::upsert_params(chunk, &columns);
+                self.execute_update(pool, &sql, &params).await?;
+            }
+
+            // MySQL 对插入计1行、更新计2行、内容未变计0行，无法从影响行数还原插入与更新的数量
+            Ok(UpsertResult::undistinguished(rows.len() as u64))
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
+
+    async fn upsert(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        data: &HashMap<String, DataValue>,
+        conflict_fields: &[String],
+        _id_strategy: &IdStrategy,
+    ) -> QuickDbResult<UpsertOutcome> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            // ON DUPLICATE KEY UPDATE 对任一唯一索引生效，先确认冲突字段恰好对应一个唯一索引
+            let unique_indexes = mysql_schema::unique_index_columns(pool, table).await?;
+            let mut expected: Vec<&String> = conflict_fields.iter().collect();
+            expected.sort();
+            let matched = unique_indexes.iter().any(|columns| {
+                let mut columns: Vec<&String> = columns.iter().collect();
+                columns.sort();
+                columns == expected
+            });
+            if !matched {
+                return Err(crate::adapter::missing_unique_index_error(table, conflict_fields));
+            }
+
+            let rows = std::slice::from_ref(data);
+            let columns = crate::adapter::upsert_columns(rows, conflict_fields)?;
+            let sql = crate::adapter::upsert_many_sql(DatabaseType::MySQL, table, &columns, conflict_fields, 1)?;
+            let params = crate::adapter::upsert_params(rows, &columns);
+            let exists_sql = crate::adapter::upsert_exists_sql(DatabaseType::MySQL, table, conflict_fields, true)?;
+            let exists_params = crate::adapter::upsert_params(rows, conflict_fields);
+
+            // 内容未变的更新与插入同样计1行，在同一事务中先锁定并检查记录是否已存在
+            crate::adapter::transaction::run_in_transaction(connection, async {
+                let existing = self.execute_query(pool, &exists_sql, &exists_params).await?;
+                self.execute_update(pool, &sql, &params).await?;
+                Ok(if existing.is_empty() { UpsertOutcome::Inserted } else { UpsertOutcome::Updated })
+            }).await
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
+
     async fn find_by_id(
         &self,
         connection: &DatabaseConnection,
@@ -238,9 +340,11 @@ impl DatabaseAdapter for MysqlAdapter {
         if let DatabaseConnection::MySQL(pool) = connection {
             let mut builder = SqlQueryBuilder::new()
                 .database_type(crate::types::DatabaseType::MySQL)
-                .select(&["*"])
+                .select_fields(&options.fields)
+                .select_expr(&options.select_exprs)
                 .from(table)
-                .where_condition_groups(condition_groups);
+                .where_condition_groups(condition_groups)
+                .collation(options.collation.as_deref());
             
             // 添加排序
             for sort_field in &options.sort {
@@ -249,7 +353,7 @@ impl DatabaseAdapter for MysqlAdapter {
             
             // 添加分页
             if let Some(pagination) = &options.pagination {
-                builder = builder.limit(pagination.limit).offset(pagination.skip);
+                builder = builder.pagination(pagination);
             }
             
             let (sql, params) = builder.build()?;
@@ -361,6 +465,12 @@ impl DatabaseAdapter for MysqlAdapter {
                         set_clauses.push(format!("{} = {} * (1.0 - ?/100.0)", operation.field, operation.field));
                         params.push(operation.value.clone());
                     }
+                    crate::types::UpdateOperator::JsonMerge => {
+                        // JSON_MERGE_PATCH需要MySQL 5.7.22+，语义与RFC 7396一致
+                        let patch = operation.json_merge_patch()?;
+                        set_clauses.push(format!("{} = JSON_MERGE_PATCH(COALESCE({}, JSON_OBJECT()), CAST(? AS JSON))", operation.field, operation.field));
+                        params.push(DataValue::String(serde_json::Value::Object(patch).to_string()));
+                    }
                 }
             }
 
@@ -411,6 +521,28 @@ impl DatabaseAdapter for MysqlAdapter {
         mysql_query::delete_by_id(self, connection, table, id).await
     }
 
+    async fn read_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        offset: u64,
+        len: u64,
+        _gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<Option<Vec<u8>>> {
+        mysql_blob::read_chunk(connection, location, offset, len).await
+    }
+
+    async fn write_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        data: &[u8],
+        append: bool,
+        _gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<bool> {
+        mysql_blob::write_chunk(self, connection, location, data, append).await
+    }
+
     async fn count(
         &self,
         connection: &DatabaseConnection,
@@ -420,6 +552,28 @@ impl DatabaseAdapter for MysqlAdapter {
         mysql_query::count(self, connection, table, conditions).await
     }
 
+    async fn time_bucket(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        field: &str,
+        interval: Interval,
+        conditions: &[QueryCondition],
+    ) -> QuickDbResult<Vec<(DataValue, u64)>> {
+        mysql_query::time_bucket(self, connection, table, field, interval, conditions).await
+    }
+
+    async fn aggregate(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        aggregations: &[Aggregation],
+        conditions: &[QueryCondition],
+        group_by: &[String],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        mysql_query::aggregate(self, connection, table, aggregations, conditions, group_by).await
+    }
+
     async fn exists(
         &self,
         connection: &DatabaseConnection,
@@ -444,10 +598,9 @@ impl DatabaseAdapter for MysqlAdapter {
         connection: &DatabaseConnection,
         table: &str,
         index_name: &str,
-        fields: &[String],
-        unique: bool,
+        index: &IndexDefinition,
     ) -> QuickDbResult<()> {
-        mysql_schema::create_index(self, connection, table, index_name, fields, unique).await
+        mysql_schema::create_index(self, connection, table, index_name, index).await
     }
 
     async fn table_exists(
@@ -458,14 +611,52 @@ impl DatabaseAdapter for MysqlAdapter {
         mysql_schema::table_exists(self, connection, table).await
     }
 
+    async fn get_table_columns(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        mysql_schema::get_table_columns(self, connection, table).await
+    }
+
+    async fn list_index_names(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        mysql_schema::list_index_names(self, connection, table).await
+    }
+
+    async fn add_column(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        column: &str,
+        field_definition: &FieldDefinition,
+    ) -> QuickDbResult<()> {
+        mysql_schema::add_column(self, connection, table, column, field_definition).await
+    }
+
     async fn drop_table(
         &self,
         connection: &DatabaseConnection,
         table: &str,
     ) -> QuickDbResult<()> {
+        self.forget_table(table);
         mysql_schema::drop_table(self, connection, table).await
     }
 
+    async fn rename_table(
+        &self,
+        connection: &DatabaseConnection,
+        from: &str,
+        to: &str,
+    ) -> QuickDbResult<()> {
+        self.forget_table(from);
+        self.forget_table(to);
+        mysql_schema::rename_table(self, connection, from, to).await
+    }
+
     async fn get_server_version(
         &self,
         connection: &DatabaseConnection,
@@ -563,6 +754,7 @@ impl DatabaseAdapter for MysqlAdapter {
 
         debug!("执行存储过程查询SQL: {}", final_sql);
 
+        crate::types::query_log::log_sql(&final_sql, &[]);
         let rows = sqlx::query(&final_sql).fetch_all(pool).await
             .map_err(|e| QuickDbError::QueryError {
                 message: format!("执行存储过程查询失败: {}", e),
@@ -587,6 +779,69 @@ impl DatabaseAdapter for MysqlAdapter {
         debug!("存储过程 {} 执行完成，返回 {} 条记录", procedure_name, result.len());
         Ok(result)
     }
+
+    async fn execute_batch(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+    ) -> QuickDbResult<u64> {
+        let DatabaseConnection::MySQL(pool) = connection else {
+            return Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            });
+        };
+        // 只用于校验和统计语句数，脚本整体交给驱动的多语句支持执行
+        let statements = crate::adapter::split_sql_statements(DatabaseType::MySQL, sql)?;
+        if statements.is_empty() {
+            return Ok(0);
+        }
+
+        // MySQL 的DDL会隐式提交，事务只保证脚本中DML语句的原子性
+        let mut tx = pool.begin().await.map_err(|e| QuickDbError::TransactionError {
+            message: format!("开启事务失败: {}", e),
+        })?;
+        debug!("执行MySQL SQL脚本: {} 条语句", statements.len());
+        crate::types::query_log::log_sql(sql, &[]);
+        sqlx::Executor::execute(&mut *tx, sql).await.map_err(|e| QuickDbError::QueryError {
+            message: format!("执行SQL脚本失败: {}", e),
+        })?;
+        tx.commit().await.map_err(|e| QuickDbError::TransactionError {
+            message: format!("提交事务失败: {}", e),
+        })?;
+        Ok(statements.len() as u64)
+    }
+
+    async fn query_raw(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+        params: &[DataValue],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            debug!("执行MySQL原始查询SQL: {}", sql);
+            self.execute_query(pool, sql, params).await
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
+
+    async fn execute_raw_update(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+        params: &[DataValue],
+    ) -> QuickDbResult<u64> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            debug!("执行MySQL原始写入SQL: {}", sql);
+            self.execute_update(pool, sql, params).await
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
 }
 
 impl MysqlAdapter {
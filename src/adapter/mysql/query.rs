@@ -92,13 +92,77 @@ pub(crate) async fn delete(
         }
     }
 
+    /// MySQL按时间段计数操作
+    pub(crate) async fn time_bucket(
+    adapter: &MysqlAdapter,
+        connection: &DatabaseConnection,
+        table: &str,
+        field: &str,
+        interval: Interval,
+        conditions: &[QueryCondition],
+    ) -> QuickDbResult<Vec<(DataValue, u64)>> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            let (sql, params) = crate::adapter::query_builder::time_bucket_sql(DatabaseType::MySQL, table, field, interval, conditions)?;
+            let rows = adapter.execute_query(pool, &sql, &params).await?;
+            crate::adapter::query_builder::time_bucket_rows(rows)
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
+
+    /// MySQL聚合操作
+    pub(crate) async fn aggregate(
+    adapter: &MysqlAdapter,
+        connection: &DatabaseConnection,
+        table: &str,
+        aggregations: &[Aggregation],
+        conditions: &[QueryCondition],
+        group_by: &[String],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            let (sql, params) = crate::adapter::query_builder::aggregate_sql(DatabaseType::MySQL, table, aggregations, conditions, group_by)?;
+            adapter.execute_query(pool, &sql, &params).await
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
+
     pub(crate) async fn exists(
     adapter: &MysqlAdapter,
         connection: &DatabaseConnection,
         table: &str,
         conditions: &[QueryCondition],
     ) -> QuickDbResult<bool> {
-        let count = count(adapter, connection, table, conditions).await?;
-        Ok(count > 0)
+        if let DatabaseConnection::MySQL(pool) = connection {
+            // 使用 EXISTS + LIMIT 1，命中第一条记录即返回，避免全表计数
+            let (inner_sql, params) = SqlQueryBuilder::new()
+                .database_type(crate::types::DatabaseType::MySQL)
+                .select(&["1"])
+                .from(table)
+                .where_conditions(conditions)
+                .limit(1)
+                .build()?;
+            let sql = format!("SELECT EXISTS({}) AS found", inner_sql);
+
+            let results = adapter.execute_query(pool, &sql, &params).await?;
+
+            if let Some(DataValue::Object(map)) = results.first() {
+                match map.get("found") {
+                    Some(DataValue::Int(found)) => return Ok(*found != 0),
+                    Some(DataValue::Bool(found)) => return Ok(*found),
+                    _ => {}
+                }
+            }
+
+            Ok(false)
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
     }
 
@@ -1,13 +1,71 @@
   //! MySQL表和索引管理操作
 
 use crate::adapter::MysqlAdapter;
+use crate::adapter::query_builder::rename_table_sql;
 use crate::pool::DatabaseConnection;
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
-use crate::model::{FieldType, FieldDefinition};
-use rat_logger::debug;
+use crate::model::{FieldType, FieldDefinition, IndexDefinition};
+use rat_logger::{debug, info};
 use std::collections::HashMap;
 
+/// 目标数据库不存在时创建
+///
+/// `server_url` 不指定默认库，需要 `CREATE` 权限
+pub(crate) async fn create_database_if_missing(server_url: &str, database: &str) -> QuickDbResult<()> {
+    use sqlx::Connection;
+
+    let mut conn = sqlx::mysql::MySqlConnection::connect(server_url).await
+        .map_err(|e| QuickDbError::ConnectionError {
+            message: format!("连接MySQL服务器失败: {}", e),
+        })?;
+
+    // CREATE DATABASE 不支持参数绑定，库名按标识符转义
+    let sql = format!("CREATE DATABASE IF NOT EXISTS `{}`", database.replace('`', "``"));
+    let result = sqlx::query(&sql).execute(&mut conn).await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("创建MySQL数据库 {} 失败（需要CREATE权限）: {}", database, e),
+        })?;
+    // 库已存在时 MySQL 只产生警告，影响行数为 0
+    if result.rows_affected() > 0 {
+        info!("已创建MySQL数据库: {}", database);
+    }
+    Ok(())
+}
+
+/// 将字段类型映射为MySQL列类型，`datetime_precision` 为日期时间列的小数秒位数
+pub(crate) fn column_type(field_type: &FieldType, datetime_precision: Option<u8>) -> String {
+    match field_type {
+        FieldType::String { max_length, .. } => {
+            if let Some(max_len) = max_length {
+                format!("VARCHAR({})", max_len)
+            } else {
+                // 对于没有指定长度的字符串字段，使用合理的默认长度
+                "VARCHAR(1000)".to_string()
+            }
+        },
+        FieldType::Integer { .. } => "INT".to_string(),
+        FieldType::BigInteger => "BIGINT".to_string(),
+        FieldType::Float { .. } => "FLOAT".to_string(),
+        FieldType::Double => "DOUBLE".to_string(),
+        FieldType::Text => "TEXT".to_string(),
+        FieldType::Boolean => "BOOLEAN".to_string(),
+        FieldType::DateTime => match datetime_precision {
+            Some(precision) => format!("DATETIME({})", precision),
+            None => "DATETIME".to_string(),
+        },
+        FieldType::Date => "DATE".to_string(),
+        FieldType::Time => "TIME".to_string(),
+        FieldType::Uuid => "VARCHAR(36)".to_string(),
+        FieldType::Json => "JSON".to_string(),
+        FieldType::Binary => "BLOB".to_string(),
+        FieldType::Decimal { precision, scale } => format!("DECIMAL({},{})", precision, scale),
+        FieldType::Array { .. } => "JSON".to_string(),
+        FieldType::Object { .. } => "JSON".to_string(),
+        FieldType::Reference { .. } => "VARCHAR(255)".to_string(),
+    }
+}
+
 /// MySQL创建表操作
 pub(crate) async fn create_table(
     adapter: &MysqlAdapter,
@@ -23,7 +81,8 @@ pub(crate) async fn create_table(
             let id_definition = match id_strategy {
                 IdStrategy::AutoIncrement => "id BIGINT AUTO_INCREMENT PRIMARY KEY".to_string(),
                 IdStrategy::ObjectId => "id VARCHAR(255) PRIMARY KEY".to_string(), // ObjectId存储为字符串
-                IdStrategy::Uuid => "id VARCHAR(36) PRIMARY KEY".to_string(),
+                IdStrategy::Uuid | IdStrategy::UuidV7 => "id VARCHAR(36) PRIMARY KEY".to_string(),
+                IdStrategy::Ulid => "id CHAR(26) PRIMARY KEY".to_string(),
                 IdStrategy::Snowflake { .. } => "id BIGINT PRIMARY KEY".to_string(),
                 IdStrategy::Custom(_) => "id VARCHAR(255) PRIMARY KEY".to_string(), // 自定义ID使用字符串
             };
@@ -36,32 +95,7 @@ pub(crate) async fn create_table(
                 }
 
                 // 非id字段的正常处理
-                let sql_type = match &field_definition.field_type {
-                    FieldType::String { max_length, .. } => {
-                        if let Some(max_len) = max_length {
-                            format!("VARCHAR({})", max_len)
-                        } else {
-                            // 对于没有指定长度的字符串字段，使用合理的默认长度
-                            "VARCHAR(1000)".to_string()
-                        }
-                    },
-                    FieldType::Integer { .. } => "INT".to_string(),
-                    FieldType::BigInteger => "BIGINT".to_string(),
-                    FieldType::Float { .. } => "FLOAT".to_string(),
-                    FieldType::Double => "DOUBLE".to_string(),
-                    FieldType::Text => "TEXT".to_string(),
-                    FieldType::Boolean => "BOOLEAN".to_string(),
-                    FieldType::DateTime => "DATETIME".to_string(),
-                    FieldType::Date => "DATE".to_string(),
-                    FieldType::Time => "TIME".to_string(),
-                    FieldType::Uuid => "VARCHAR(36)".to_string(),
-                    FieldType::Json => "JSON".to_string(),
-                    FieldType::Binary => "BLOB".to_string(),
-                    FieldType::Decimal { precision, scale } => format!("DECIMAL({},{})", precision, scale),
-                    FieldType::Array { .. } => "JSON".to_string(),
-                    FieldType::Object { .. } => "JSON".to_string(),
-                    FieldType::Reference { .. } => "VARCHAR(255)".to_string(),
-                };
+                let sql_type = column_type(&field_definition.field_type, adapter.datetime_precision);
 
                 // 添加NULL或NOT NULL约束
                 let null_constraint = if field_definition.required {
@@ -69,7 +103,9 @@ pub(crate) async fn create_table(
                 } else {
                     "NULL"
                 };
-                field_definitions.push(format!("{} {} {}", name, sql_type, null_constraint));
+                let collate = crate::adapter::query_builder::column_collate_clause(DatabaseType::MySQL, field_definition)?;
+                let default = crate::adapter::query_builder::column_default_clause(DatabaseType::MySQL, field_definition)?;
+                field_definitions.push(format!("{} {}{}{} {}", name, sql_type, collate, default, null_constraint));
             }
             
             let sql = format!(
@@ -94,17 +130,26 @@ pub(crate) async fn create_index(
         connection: &DatabaseConnection,
         table: &str,
         index_name: &str,
-        fields: &[String],
-        unique: bool,
+        index: &IndexDefinition,
     ) -> QuickDbResult<()> {
         if let DatabaseConnection::MySQL(pool) = connection {
-            let unique_clause = if unique { "UNIQUE " } else { "" };
+            if !index.partial_filter.is_empty() {
+                return Err(QuickDbError::ValidationError {
+                    field: "partial_filter".to_string(),
+                    message: format!("MySQL不支持部分索引: {}", index_name),
+                });
+            }
+            if index.sparse {
+                debug!("MySQL不支持稀疏索引，忽略sparse选项: {}", index_name);
+            }
+
+            let unique_clause = if index.unique { "UNIQUE " } else { "" };
             let sql = format!(
                 "CREATE {}INDEX {} ON {} ({})",
                 unique_clause,
                 index_name,
                 table,
-                fields.join(", ")
+                crate::adapter::query_builder::index_columns_clause(DatabaseType::MySQL, index)?
             );
             
             adapter.execute_update(pool, &sql, &[]).await?;
@@ -117,6 +162,44 @@ pub(crate) async fn create_index(
         }
     }
 
+/// 写入前确保表存在，不存在时使用预定义的模型元数据创建
+///
+/// 已确认存在的表直接跳过，避免每次写入都查询元数据；没有模型元数据时返回错误
+pub(crate) async fn ensure_table(
+    adapter: &MysqlAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    id_strategy: &IdStrategy,
+) -> QuickDbResult<()> {
+    if !adapter.is_known_table(table) && !table_exists(adapter, connection, table).await? {
+        // 获取表创建锁，防止重复创建
+        let _lock = adapter.acquire_table_lock(table).await;
+        // 再次检查表是否存在（双重检查锁定模式）
+        if !table_exists(adapter, connection, table).await? {
+            // 尝试从模型管理器获取预定义的元数据
+            if let Some(model_meta) = crate::manager::get_model(table) {
+                debug!("表 {} 不存在，使用预定义模型元数据创建", table);
+
+                // 使用模型元数据创建表
+                create_table(adapter, connection, table, &model_meta.fields, id_strategy).await?;
+                // 等待100ms确保数据库事务完全提交
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                debug!("⏱️ 等待100ms确保表 '{}' 创建完成", table);
+            } else {
+                return Err(QuickDbError::ValidationError {
+                    field: "table_creation".to_string(),
+                    message: format!("表 '{}' 不存在，且没有预定义的模型元数据。请先定义模型并使用 define_model! 宏明确指定字段类型。", table),
+                });
+            }
+        } else {
+            debug!("表 {} 已存在，跳过创建", table);
+        }
+        // 锁会在这里自动释放（当 _lock 超出作用域时）
+    }
+    adapter.mark_table_known(table);
+    Ok(())
+}
+
     /// MySQL表存在检查操作
 pub(crate) async fn table_exists(
     adapter: &MysqlAdapter,
@@ -136,6 +219,101 @@ pub(crate) async fn table_exists(
         }
     }
 
+    /// MySQL获取表列名操作
+pub(crate) async fn get_table_columns(
+    adapter: &MysqlAdapter,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            let sql = "SELECT CAST(COLUMN_NAME AS CHAR) FROM INFORMATION_SCHEMA.COLUMNS WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?";
+            sqlx::query_scalar::<_, String>(sql)
+                .bind(table)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("获取MySQL表列信息失败: {}", e),
+                })
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
+
+    /// MySQL获取表索引名操作
+pub(crate) async fn list_index_names(
+    adapter: &MysqlAdapter,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            let sql = "SELECT DISTINCT CAST(INDEX_NAME AS CHAR) FROM INFORMATION_SCHEMA.STATISTICS WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?";
+            sqlx::query_scalar::<_, String>(sql)
+                .bind(table)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("获取MySQL索引信息失败: {}", e),
+                })
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
+
+    /// MySQL获取表上各唯一索引（含主键）的列名
+pub(crate) async fn unique_index_columns(
+    pool: &sqlx::MySqlPool,
+    table: &str,
+) -> QuickDbResult<Vec<Vec<String>>> {
+    let sql = "SELECT CAST(INDEX_NAME AS CHAR), CAST(COLUMN_NAME AS CHAR) FROM INFORMATION_SCHEMA.STATISTICS \
+               WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? AND NON_UNIQUE = 0 ORDER BY INDEX_NAME, SEQ_IN_INDEX";
+    let rows = sqlx::query_as::<_, (String, String)>(sql)
+        .bind(table)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("获取MySQL唯一索引信息失败: {}", e),
+        })?;
+
+    let mut indexes: Vec<(String, Vec<String>)> = Vec::new();
+    for (index_name, column) in rows {
+        match indexes.last_mut() {
+            Some((name, columns)) if *name == index_name => columns.push(column),
+            _ => indexes.push((index_name, vec![column])),
+        }
+    }
+    Ok(indexes.into_iter().map(|(_, columns)| columns).collect())
+}
+
+    /// MySQL添加列操作，新增列总是允许NULL以兼容已有数据
+pub(crate) async fn add_column(
+    adapter: &MysqlAdapter,
+        connection: &DatabaseConnection,
+        table: &str,
+        column: &str,
+        field_definition: &FieldDefinition,
+    ) -> QuickDbResult<()> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            let collate = crate::adapter::query_builder::column_collate_clause(DatabaseType::MySQL, field_definition)?;
+            let sql = format!(
+                "ALTER TABLE {} ADD COLUMN {} {}{} NULL",
+                table, column, column_type(&field_definition.field_type, adapter.datetime_precision), collate
+            );
+            debug!("执行MySQL添加列: {}", sql);
+
+            adapter.execute_update(pool, &sql, &[]).await?;
+
+            Ok(())
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
+
     /// MySQL删除表操作
 pub(crate) async fn drop_table(
     adapter: &MysqlAdapter,
@@ -158,6 +336,29 @@ pub(crate) async fn drop_table(
         }
     }
 
+    /// MySQL重命名表操作
+pub(crate) async fn rename_table(
+    adapter: &MysqlAdapter,
+        connection: &DatabaseConnection,
+        from: &str,
+        to: &str,
+    ) -> QuickDbResult<()> {
+        if let DatabaseConnection::MySQL(pool) = connection {
+            let sql = rename_table_sql(DatabaseType::MySQL, from, to)?;
+
+            debug!("执行MySQL重命名表SQL: {}", sql);
+
+            adapter.execute_update(pool, &sql, &[]).await?;
+
+            debug!("成功重命名MySQL表: {} -> {}", from, to);
+            Ok(())
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望MySQL连接".to_string(),
+            })
+        }
+    }
+
     /// MySQL获取服务器版本操作
 pub(crate) async fn get_server_version(
     adapter: &MysqlAdapter,
@@ -337,6 +337,18 @@ impl MysqlAdapter {
                         }
                     }
                 },
+                "DECIMAL" => {
+                    // DECIMAL按精确的十进制字符串返回，避免SUM/AVG等聚合结果的浮点舍入
+                    debug!("准备读取十进制字段: {}", column_name);
+                    match row.try_get::<Option<sqlx::types::Decimal>, _>(column_name) {
+                        Ok(Some(d)) => DataValue::String(d.to_string()),
+                        Ok(None) => DataValue::Null,
+                        Err(e) => {
+                            error!("读取十进制字段 {} 时发生错误: {}", column_name, e);
+                            DataValue::Null
+                        }
+                    }
+                },
                 "BOOLEAN" | "BOOL" => {
                     debug!("准备读取布尔字段: {}", column_name);
                     match Self::safe_read_bool(row, column_name) {
@@ -364,8 +376,24 @@ impl MysqlAdapter {
                         DataValue::Null
                     }
                 },
-                "JSON" | "LONGTEXT" | "TEXT" | "VARCHAR" => {
-                    // 简化处理：所有文本类型都作为字符串读取
+                "JSON" => {
+                    // 原生JSON列始终按JSON解析
+                    debug!("读取JSON字段: {}", column_name);
+                    Self::safe_read_json(row, column_name).unwrap_or_else(|e| {
+                        error!("无法读取JSON字段 {}: {}", column_name, e);
+                        DataValue::Null
+                    })
+                },
+                "LONGTEXT" | "TEXT" | "VARCHAR" if self.json_text_detection => {
+                    // 已开启文本列JSON内容探测：形如JSON的文本按JSON解析
+                    debug!("按JSON探测读取文本字段: {} (类型: {})", column_name, column_type);
+                    Self::safe_read_json(row, column_name).unwrap_or_else(|e| {
+                        error!("无法读取文本字段 {}: {}", column_name, e);
+                        DataValue::Null
+                    })
+                },
+                "LONGTEXT" | "TEXT" | "VARCHAR" => {
+                    // 文本列默认作为字符串读取，是否解析为JSON由模型元数据决定
                     debug!("读取文本字段: {} (类型: {})", column_name, column_type);
                     if let Ok(value) = row.try_get::<Option<String>, _>(column_name) {
                         let result = match value {
@@ -449,6 +477,7 @@ impl MysqlAdapter {
         sql: &str,
         params: &[DataValue],
     ) -> QuickDbResult<Vec<DataValue>> {
+        crate::types::query_log::log_sql(sql, params);
         let mut query = sqlx::query(sql);
         
         // 绑定参数
@@ -489,7 +518,11 @@ impl MysqlAdapter {
             };
         }
 
-        let rows = query.fetch_all(pool).await
+        // 处于适配器事务作用域（如显式事务）时在该事务中执行，可读到事务内未提交的写入
+        let rows = match crate::adapter::transaction::current_mysql_transaction().await {
+            Some(mut tx) => query.fetch_all(&mut **tx).await,
+            None => query.fetch_all(pool).await,
+        }
             .map_err(|e| QuickDbError::QueryError {
                 message: format!("执行MySQL查询失败: {}", e),
             })?;
@@ -530,6 +563,7 @@ impl MysqlAdapter {
         sql: &str,
         params: &[DataValue],
     ) -> QuickDbResult<u64> {
+        crate::types::query_log::log_sql(sql, params);
         let mut query = sqlx::query(sql);
         
         // 绑定参数
@@ -570,7 +604,11 @@ impl MysqlAdapter {
             };
         }
 
-        let result = query.execute(pool).await
+        // 处于适配器事务作用域（如试运行）时在该事务中执行
+        let result = match crate::adapter::transaction::current_mysql_transaction().await {
+            Some(mut tx) => query.execute(&mut **tx).await,
+            None => query.execute(pool).await,
+        }
             .map_err(|e| QuickDbError::QueryError {
                 message: format!("执行MySQL更新失败: {}", e),
             })?;
@@ -13,6 +13,8 @@ pub struct PostgresAdapter {
     creation_locks: Arc<Mutex<HashMap<String, ()>>>,
     /// 存储过程映射表，存储已创建的存储过程信息
     pub(crate) stored_procedures: Arc<Mutex<HashMap<String, crate::stored_procedure::StoredProcedureInfo>>>,
+    /// 日期时间列的小数秒位数，`None` 时使用数据库默认精度
+    pub(crate) datetime_precision: Option<u8>,
 }
 
 impl PostgresAdapter {
@@ -21,9 +23,16 @@ impl PostgresAdapter {
         Self {
             creation_locks: Arc::new(Mutex::new(HashMap::new())),
             stored_procedures: Arc::new(Mutex::new(HashMap::new())),
+            datetime_precision: None,
         }
     }
 
+    /// 设置建表时日期时间列的小数秒位数
+    pub fn with_datetime_precision(mut self, precision: Option<u8>) -> Self {
+        self.datetime_precision = precision;
+        self
+    }
+
     /// 获取表创建锁
     pub(crate) async fn acquire_table_lock(&self, table: &str) -> tokio::sync::MutexGuard<'_, HashMap<String, ()>> {
         let mut locks = self.creation_locks.lock().await;
@@ -0,0 +1,74 @@
+//! PostgreSQL二进制列分块读写
+//!
+//! 对 BYTEA 列按片段读取（`substring`）和追加写入（`||`），不使用大对象（large object）接口
+
+use crate::adapter::postgres::PostgresAdapter;
+use crate::adapter::query_builder::{blob_chunk_select_sql, blob_chunk_update_sql};
+use crate::pool::DatabaseConnection;
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::*;
+use sqlx::Row;
+
+/// 读取二进制列的一段数据，记录不存在时返回 `None`
+pub(crate) async fn read_chunk(
+    connection: &DatabaseConnection,
+    location: &BlobLocation,
+    offset: u64,
+    len: u64,
+) -> QuickDbResult<Option<Vec<u8>>> {
+    if let DatabaseConnection::PostgreSQL(pool) = connection {
+        let sql = blob_chunk_select_sql(DatabaseType::PostgreSQL, &location.table, &location.column)?;
+        let params = [DataValue::Int(offset as i64 + 1), DataValue::Int(len as i64), location.id.clone()];
+        crate::types::query_log::log_sql(&sql, &params);
+
+        let query = sqlx::query(&sql)
+            .bind(offset as i64 + 1)
+            .bind(len as i64);
+        let query = match &location.id {
+            DataValue::Int(i) => query.bind(*i),
+            DataValue::Uuid(uuid) => query.bind(*uuid),
+            DataValue::String(s) => match s.parse::<uuid::Uuid>() {
+                Ok(uuid) => query.bind(uuid),
+                Err(_) => query.bind(s.clone()),
+            },
+            other => query.bind(other.to_string()),
+        };
+        let row = query.fetch_optional(pool)
+            .await
+            .map_err(|e| QuickDbError::QueryError {
+                message: format!("PostgreSQL读取二进制数据失败: {}", e),
+            })?;
+        match row {
+            Some(row) => {
+                let chunk: Option<Vec<u8>> = row.try_get(0).map_err(|e| QuickDbError::QueryError {
+                    message: format!("PostgreSQL读取二进制数据失败: {}", e),
+                })?;
+                Ok(Some(chunk.unwrap_or_default()))
+            }
+            None => Ok(None),
+        }
+    } else {
+        Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+        })
+    }
+}
+
+/// 写入二进制列的一段数据，返回记录是否存在
+pub(crate) async fn write_chunk(
+    adapter: &PostgresAdapter,
+    connection: &DatabaseConnection,
+    location: &BlobLocation,
+    data: &[u8],
+    append: bool,
+) -> QuickDbResult<bool> {
+    if let DatabaseConnection::PostgreSQL(pool) = connection {
+        let sql = blob_chunk_update_sql(DatabaseType::PostgreSQL, &location.table, &location.column, append)?;
+        let params = [DataValue::Bytes(data.to_vec()), location.id.clone()];
+        Ok(super::utils::execute_update(adapter, pool, &sql, &params).await? > 0)
+    } else {
+        Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+        })
+    }
+}
@@ -8,6 +8,8 @@ pub mod utils;
 pub mod operations;
 pub mod query;
 pub mod schema;
+pub mod blob;
+pub mod watch;
 
 // 重新导出主要的公共类型和结构体
 pub use adapter::PostgresAdapter;
\ No newline at end of file
@@ -7,7 +7,7 @@ use crate::adapter::postgres::utils::row_to_data_map;
 use crate::pool::DatabaseConnection;
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
-use crate::model::{FieldType, FieldDefinition};
+use crate::model::{FieldType, FieldDefinition, IndexDefinition};
 use crate::manager;
 use async_trait::async_trait;
 use rat_logger::debug;
@@ -16,9 +16,14 @@ use std::collections::HashMap;
 
 use super::query as postgres_query;
 use super::schema as postgres_schema;
+use super::blob as postgres_blob;
 
 #[async_trait]
 impl DatabaseAdapter for PostgresAdapter {
+    fn capabilities(&self) -> Capabilities {
+        DatabaseType::PostgreSQL.capabilities()
+    }
+
     async fn create(
         &self,
         connection: &DatabaseConnection,
@@ -27,91 +32,12 @@ impl DatabaseAdapter for PostgresAdapter {
         id_strategy: &IdStrategy,
     ) -> QuickDbResult<DataValue> {
         if let DatabaseConnection::PostgreSQL(pool) = connection {
-            // 自动建表逻辑：检查表是否存在，如果不存在则创建
-            if !postgres_schema::table_exists(self, connection, table).await? {
-                // 获取表创建锁，防止重复创建
-                let _lock = self.acquire_table_lock(table).await;
-
-                // 再次检查表是否存在（双重检查锁定模式）
-                if !postgres_schema::table_exists(self, connection, table).await? {
-                    // 尝试从模型管理器获取预定义的元数据
-                    if let Some(model_meta) = crate::manager::get_model(table) {
-                        debug!("表 {} 不存在，使用预定义模型元数据创建", table);
-
-                        // 使用模型元数据创建表
-                        postgres_schema::create_table(self, connection, table, &model_meta.fields, id_strategy).await?;
-
-                        // 等待100ms确保数据库事务完全提交
-                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                        debug!("⏱️ 等待100ms确保表 '{}' 创建完成", table);
-                    } else {
-                        return Err(QuickDbError::ValidationError {
-                            field: "table_creation".to_string(),
-                            message: format!("表 '{}' 不存在，且没有预定义的模型元数据。请先定义模型并使用 define_model! 宏明确指定字段类型。", table),
-                        });
-                    }
-                } else {
-                    debug!("表 {} 已存在，跳过创建", table);
-                }
+            // 自动建表逻辑：表不存在时按预定义的模型元数据创建
+            postgres_schema::ensure_table(self, connection, table, id_strategy).await?;
 
-                // 锁会在这里自动释放（当 _lock 超出作用域时）
-            }
+            let has_auto_increment_id = postgres_schema::has_serial_id(pool, table).await?;
+            let insert_data = prepare_insert_data(data, has_auto_increment_id, id_strategy);
 
-            // 表已存在，检查是否有SERIAL类型的id字段
-            let mut has_auto_increment_id = false;
-            let check_serial_sql = "SELECT column_default FROM information_schema.columns WHERE table_name = $1 AND column_name = 'id'";
-            let rows = sqlx::query(check_serial_sql)
-                .bind(table)
-                .fetch_all(pool)
-                .await
-                .map_err(|e| QuickDbError::QueryError {
-                    message: format!("检查表结构失败: {}", e),
-                })?;
-
-            if let Some(row) = rows.first() {
-                if let Ok(Some(default_value)) = row.try_get::<Option<String>, _>("column_default") {
-                    has_auto_increment_id = default_value.starts_with("nextval");
-                }
-            }
-            
-            // 准备插入数据
-            // 如果数据中没有id字段，说明期望使用自增ID，不需要在INSERT中包含id字段
-            // 如果数据中有id字段但表使用SERIAL自增，也要移除id字段让PostgreSQL自动生成
-            let mut insert_data = data.clone();
-            let data_has_id = insert_data.contains_key("id");
-            
-            if !data_has_id || (data_has_id && has_auto_increment_id) {
-                insert_data.remove("id");
-                debug!("使用PostgreSQL SERIAL自增，不在INSERT中包含id字段");
-            } else if data_has_id {
-                // 如果有ID字段且指定了ID策略，可能需要转换数据类型
-                match id_strategy {
-                    IdStrategy::Snowflake { .. } => {
-                        // 雪花ID需要转换为整数
-                        if let Some(id_value) = insert_data.get("id").cloned() {
-                            if let DataValue::String(s) = id_value {
-                                if let Ok(num) = s.parse::<i64>() {
-                                    insert_data.insert("id".to_string(), DataValue::Int(num));
-                                    debug!("将雪花ID从字符串转换为整数: {} -> {}", s, num);
-                                }
-                            }
-                        }
-                    },
-                    IdStrategy::Uuid => {
-                        // UUID需要转换为UUID类型
-                        if let Some(id_value) = insert_data.get("id").cloned() {
-                            if let DataValue::String(s) = id_value {
-                                if let Ok(uuid) = s.parse::<uuid::Uuid>() {
-                                    insert_data.insert("id".to_string(), DataValue::Uuid(uuid));
-                                    debug!("将UUID从字符串转换为UUID类型: {}", s);
-                                }
-                            }
-                        }
-                    },
-                    _ => {} // 其他策略不需要转换
-                }
-            }
-            
             let (sql, params) = SqlQueryBuilder::new()
                 .database_type(crate::types::DatabaseType::PostgreSQL)
                 .insert(insert_data)
@@ -138,6 +64,109 @@ impl DatabaseAdapter for PostgresAdapter {
         }
     }
 
+    async fn create_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        data_list: &[HashMap<String, DataValue>],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if let DatabaseConnection::PostgreSQL(pool) = connection {
+            if data_list.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            postgres_schema::ensure_table(self, connection, table, id_strategy).await?;
+
+            let has_auto_increment_id = postgres_schema::has_serial_id(pool, table).await?;
+            let insert_list: Vec<HashMap<String, DataValue>> = data_list.iter()
+                .map(|data| prepare_insert_data(data, has_auto_increment_id, id_strategy))
+                .collect();
+
+            let has_id = |data: &HashMap<String, DataValue>| {
+                data.get("id").is_some_and(|id| !matches!(id, DataValue::Null))
+            };
+            let mut results = Vec::with_capacity(insert_list.len());
+            for (columns, chunk) in crate::adapter::insert_many_batches(DatabaseType::PostgreSQL, &insert_list) {
+                // 记录都带有ID时直接返回，否则通过 RETURNING 取回数据库生成的自增ID
+                let returning = !chunk.iter().all(has_id);
+                let sql = crate::adapter::insert_many_sql(DatabaseType::PostgreSQL, table, &columns, chunk.len(), returning.then_some("id"))?;
+                let params = crate::adapter::upsert_params(chunk, &columns);
+                debug!("执行PostgreSQL批量插入: {}", sql);
+
+                if !returning {
+                    super::utils::execute_update(self, pool, &sql, &params).await?;
+                    results.extend(chunk.iter().map(|data| data["id"].clone()));
+                    continue;
+                }
+                let mut ids: Vec<DataValue> = super::utils::execute_query(self, pool, &sql, &params).await?
+                    .into_iter()
+                    .map(|row| match row {
+                        DataValue::Object(mut map) => map.remove("id").unwrap_or(DataValue::Null),
+                        other => other,
+                    })
+                    .collect();
+                // SERIAL按插入顺序递增，RETURNING 的行序不保证与插入顺序一致，排序后与输入一一对应
+                if !chunk.iter().any(has_id) {
+                    ids.sort_by_key(|id| match id {
+                        DataValue::Int(i) => *i,
+                        _ => i64::MAX,
+                    });
+                }
+                results.extend(ids);
+            }
+            Ok(results)
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+            })
+        }
+    }
+
+    async fn upsert_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        rows: &[HashMap<String, DataValue>],
+        conflict_fields: &[String],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<UpsertResult> {
+        if let DatabaseConnection::PostgreSQL(pool) = connection {
+            let columns = crate::adapter::upsert_columns(rows, conflict_fields)?;
+
+            // 与创建记录一致：雪花ID以整数写入
+            let rows: Vec<HashMap<String, DataValue>> = rows.iter()
+                .map(|row| {
+                    let mut row = row.clone();
+                    if let (IdStrategy::Snowflake { .. }, Some(DataValue::String(s))) = (id_strategy, row.get("id")) {
+                        if let Ok(num) = s.parse::<i64>() {
+                            row.insert("id".to_string(), DataValue::Int(num));
+                        }
+                    }
+                    row
+                })
+                .collect();
+
+            let mut inserted = 0u64;
+            let chunk_size = crate::adapter::upsert_chunk_size(DatabaseType::PostgreSQL, columns.len());
+            for chunk in rows.chunks(chunk_size) {
+                let sql = crate::adapter::upsert_many_sql(DatabaseType::PostgreSQL, table, &columns, conflict_fields, chunk.len())?;
+                let params = crate::adapter::upsert_params(chunk, &columns);
+                let results = super::utils::execute_query(self, pool, &sql, &params).await
+                    .map_err(|e| crate::adapter::upsert_conflict_error(table, conflict_fields, e))?;
+                inserted += results.iter()
+                    .filter(|row| matches!(row, DataValue::Object(map) if map.get("inserted") == Some(&DataValue::Bool(true))))
+                    .count() as u64;
+            }
+
+            Ok(UpsertResult::with_inserted(rows.len() as u64, inserted))
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+            })
+        }
+    }
+
     async fn find_by_id(
         &self,
         connection: &DatabaseConnection,
@@ -204,9 +233,11 @@ impl DatabaseAdapter for PostgresAdapter {
         if let DatabaseConnection::PostgreSQL(pool) = connection {
             let mut builder = SqlQueryBuilder::new()
                 .database_type(crate::types::DatabaseType::PostgreSQL)
-                .select(&["*"])
+                .select_fields(&options.fields)
+                .select_expr(&options.select_exprs)
                 .from(table)
-                .where_condition_groups(condition_groups);
+                .where_condition_groups(condition_groups)
+                .collation(options.collation.as_deref());
             
             // 添加排序
             if !options.sort.is_empty() {
@@ -217,7 +248,7 @@ impl DatabaseAdapter for PostgresAdapter {
             
             // 添加分页
             if let Some(pagination) = &options.pagination {
-                builder = builder.limit(pagination.limit).offset(pagination.skip);
+                builder = builder.pagination(pagination);
             }
             
             let (sql, params) = builder.build()?;
@@ -315,6 +346,11 @@ impl DatabaseAdapter for PostgresAdapter {
                         set_clauses.push(format!("{} = {} * (1.0 - ${}/100.0)", operation.field, operation.field, params.len() + 1));
                         params.push(operation.value.clone());
                     }
+                    crate::types::UpdateOperator::JsonMerge => {
+                        let patch = operation.json_merge_patch()?;
+                        let merged = crate::adapter::postgres::utils::build_jsonb_merge_expr(&operation.field, &patch, &mut params);
+                        set_clauses.push(format!("{} = {}", operation.field, merged));
+                    }
                 }
             }
 
@@ -365,6 +401,28 @@ impl DatabaseAdapter for PostgresAdapter {
         postgres_query::delete_by_id(self, connection, table, id).await
     }
 
+    async fn read_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        offset: u64,
+        len: u64,
+        _gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<Option<Vec<u8>>> {
+        postgres_blob::read_chunk(connection, location, offset, len).await
+    }
+
+    async fn write_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        data: &[u8],
+        append: bool,
+        _gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<bool> {
+        postgres_blob::write_chunk(self, connection, location, data, append).await
+    }
+
     async fn count(
         &self,
         connection: &DatabaseConnection,
@@ -374,6 +432,28 @@ impl DatabaseAdapter for PostgresAdapter {
         postgres_query::count(self, connection, table, conditions).await
     }
 
+    async fn time_bucket(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        field: &str,
+        interval: Interval,
+        conditions: &[QueryCondition],
+    ) -> QuickDbResult<Vec<(DataValue, u64)>> {
+        postgres_query::time_bucket(self, connection, table, field, interval, conditions).await
+    }
+
+    async fn aggregate(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        aggregations: &[Aggregation],
+        conditions: &[QueryCondition],
+        group_by: &[String],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        postgres_query::aggregate(self, connection, table, aggregations, conditions, group_by).await
+    }
+
     async fn exists(
         &self,
         connection: &DatabaseConnection,
@@ -397,9 +477,10 @@ impl DatabaseAdapter for PostgresAdapter {
             if !fields.contains_key("id") {
                 let id_definition = match id_strategy {
                     IdStrategy::AutoIncrement => "id SERIAL PRIMARY KEY".to_string(),
-                    IdStrategy::Uuid => "id UUID PRIMARY KEY".to_string(), // 使用原生UUID类型，返回时转换为字符串
+                    IdStrategy::Uuid | IdStrategy::UuidV7 => "id UUID PRIMARY KEY".to_string(), // 使用原生UUID类型，返回时转换为字符串
                     IdStrategy::Snowflake { .. } => "id BIGINT PRIMARY KEY".to_string(),
                     IdStrategy::ObjectId => "id TEXT PRIMARY KEY".to_string(),
+                    IdStrategy::Ulid => "id CHAR(26) PRIMARY KEY".to_string(),
                     IdStrategy::Custom(_) => "id TEXT PRIMARY KEY".to_string(), // 自定义策略使用TEXT
                 };
                 field_definitions.push(id_definition);
@@ -422,7 +503,7 @@ impl DatabaseAdapter for PostgresAdapter {
                     FieldType::Boolean => "BOOLEAN".to_string(),
                     FieldType::DateTime => {
                         debug!("🔍 字段 {} 类型为 DateTime，required: {}", name, field_definition.required);
-                        "TIMESTAMPTZ".to_string()
+                        super::schema::column_type(&FieldType::DateTime, self.datetime_precision)
                     },
                     FieldType::Date => "DATE".to_string(),
                     FieldType::Time => "TIME".to_string(),
@@ -439,9 +520,10 @@ impl DatabaseAdapter for PostgresAdapter {
                 if name == "id" {
                     let id_definition = match id_strategy {
                         IdStrategy::AutoIncrement => "id SERIAL PRIMARY KEY".to_string(),
-                        IdStrategy::Uuid => "id UUID PRIMARY KEY".to_string(), // 使用原生UUID类型
+                        IdStrategy::Uuid | IdStrategy::UuidV7 => "id UUID PRIMARY KEY".to_string(), // 使用原生UUID类型
                         IdStrategy::Snowflake { .. } => "id BIGINT PRIMARY KEY".to_string(),
                         IdStrategy::ObjectId => "id TEXT PRIMARY KEY".to_string(),
+                        IdStrategy::Ulid => "id CHAR(26) PRIMARY KEY".to_string(),
                         IdStrategy::Custom(_) => "id TEXT PRIMARY KEY".to_string(), // 自定义策略使用TEXT
                     };
                     field_definitions.push(id_definition);
@@ -453,7 +535,9 @@ impl DatabaseAdapter for PostgresAdapter {
                         "NULL"
                     };
                     debug!("🔍 字段 {} 定义: {} {}", name, sql_type, null_constraint);
-                    field_definitions.push(format!("{} {} {}", name, sql_type, null_constraint));
+                    let collate = crate::adapter::query_builder::column_collate_clause(DatabaseType::PostgreSQL, field_definition)?;
+                    let default = crate::adapter::query_builder::column_default_clause(DatabaseType::PostgreSQL, field_definition)?;
+                    field_definitions.push(format!("{} {}{}{} {}", name, sql_type, collate, default, null_constraint));
                 }
             }
             
@@ -481,29 +565,9 @@ impl DatabaseAdapter for PostgresAdapter {
         connection: &DatabaseConnection,
         table: &str,
         index_name: &str,
-        fields: &[String],
-        unique: bool,
+        index: &IndexDefinition,
     ) -> QuickDbResult<()> {
-        if let DatabaseConnection::PostgreSQL(pool) = connection {
-            let unique_clause = if unique { "UNIQUE " } else { "" };
-            let sql = format!(
-                "CREATE {}INDEX IF NOT EXISTS {} ON {} ({})",
-                unique_clause,
-                index_name,
-                table,
-                fields.join(", ")
-            );
-            
-            debug!("执行PostgreSQL索引创建: {}", sql);
-            
-            super::utils::execute_update(self, pool, &sql, &[]).await?;
-            
-            Ok(())
-        } else {
-            Err(QuickDbError::ConnectionError {
-                message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
-            })
-        }
+        super::schema::create_index(self, connection, table, index_name, index).await
     }
 
     async fn table_exists(
@@ -532,6 +596,32 @@ impl DatabaseAdapter for PostgresAdapter {
         }
     }
 
+    async fn get_table_columns(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        super::schema::get_table_columns(self, connection, table).await
+    }
+
+    async fn list_index_names(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        super::schema::list_index_names(self, connection, table).await
+    }
+
+    async fn add_column(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        column: &str,
+        field_definition: &FieldDefinition,
+    ) -> QuickDbResult<()> {
+        super::schema::add_column(self, connection, table, column, field_definition).await
+    }
+
     async fn drop_table(
         &self,
         connection: &DatabaseConnection,
@@ -571,6 +661,15 @@ impl DatabaseAdapter for PostgresAdapter {
         }
     }
 
+    async fn rename_table(
+        &self,
+        connection: &DatabaseConnection,
+        from: &str,
+        to: &str,
+    ) -> QuickDbResult<()> {
+        postgres_schema::rename_table(self, connection, from, to).await
+    }
+
     async fn get_server_version(
         &self,
         connection: &DatabaseConnection,
@@ -691,6 +790,7 @@ impl DatabaseAdapter for PostgresAdapter {
 
         debug!("执行存储过程查询SQL: {}", final_sql);
 
+        crate::types::query_log::log_sql(&final_sql, &[]);
         let rows = sqlx::query(&final_sql).fetch_all(pool).await
             .map_err(|e| QuickDbError::QueryError {
                 message: format!("执行存储过程查询失败: {}", e),
@@ -715,6 +815,84 @@ impl DatabaseAdapter for PostgresAdapter {
         debug!("存储过程 {} 执行完成，返回 {} 条记录", procedure_name, result.len());
         Ok(result)
     }
+
+    async fn watch(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<ChangeStream> {
+        if let DatabaseConnection::PostgreSQL(pool) = connection {
+            super::watch::watch(pool, table).await
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+            })
+        }
+    }
+
+    async fn execute_batch(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+    ) -> QuickDbResult<u64> {
+        let DatabaseConnection::PostgreSQL(pool) = connection else {
+            return Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+            });
+        };
+        let statements = crate::adapter::split_sql_statements(DatabaseType::PostgreSQL, sql)?;
+        if statements.is_empty() {
+            return Ok(0);
+        }
+
+        // 逐条执行以便定位出错的语句，事务未提交即被丢弃时自动回滚
+        let mut tx = pool.begin().await.map_err(|e| QuickDbError::TransactionError {
+            message: format!("开启事务失败: {}", e),
+        })?;
+        for (index, statement) in statements.iter().enumerate() {
+            debug!("执行PostgreSQL SQL脚本第 {} 条语句: {}", index + 1, statement);
+            crate::types::query_log::log_sql(statement, &[]);
+            sqlx::query(statement).execute(&mut *tx).await.map_err(|e| QuickDbError::QueryError {
+                message: format!("SQL脚本第 {} 条语句执行失败: {}", index + 1, e),
+            })?;
+        }
+        tx.commit().await.map_err(|e| QuickDbError::TransactionError {
+            message: format!("提交事务失败: {}", e),
+        })?;
+        Ok(statements.len() as u64)
+    }
+
+    async fn query_raw(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+        params: &[DataValue],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if let DatabaseConnection::PostgreSQL(pool) = connection {
+            debug!("执行PostgreSQL原始查询SQL: {}", sql);
+            super::utils::execute_query(self, pool, sql, params).await
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+            })
+        }
+    }
+
+    async fn execute_raw_update(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+        params: &[DataValue],
+    ) -> QuickDbResult<u64> {
+        if let DatabaseConnection::PostgreSQL(pool) = connection {
+            debug!("执行PostgreSQL原始写入SQL: {}", sql);
+            super::utils::execute_update(self, pool, sql, params).await
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+            })
+        }
+    }
 }
 
 impl PostgresAdapter {
@@ -826,3 +1004,50 @@ impl PostgresAdapter {
         Ok(final_sql)
     }
 }
+
+/// 准备插入数据
+///
+/// 数据中没有id字段时使用自增ID，不在INSERT中包含id字段；表使用SERIAL自增时同样移除id字段，
+/// 其他情况按ID策略转换id的类型
+fn prepare_insert_data(
+    data: &HashMap<String, DataValue>,
+    has_auto_increment_id: bool,
+    id_strategy: &IdStrategy,
+) -> HashMap<String, DataValue> {
+    let mut insert_data = data.clone();
+    let data_has_id = insert_data.contains_key("id");
+
+    if !data_has_id || (data_has_id && has_auto_increment_id) {
+        insert_data.remove("id");
+        debug!("使用PostgreSQL SERIAL自增，不在INSERT中包含id字段");
+    } else if data_has_id {
+        // 如果有ID字段且指定了ID策略，可能需要转换数据类型
+        match id_strategy {
+            IdStrategy::Snowflake { .. } => {
+                // 雪花ID需要转换为整数
+                if let Some(id_value) = insert_data.get("id").cloned() {
+                    if let DataValue::String(s) = id_value {
+                        if let Ok(num) = s.parse::<i64>() {
+                            insert_data.insert("id".to_string(), DataValue::Int(num));
+                            debug!("将雪花ID从字符串转换为整数: {} -> {}", s, num);
+                        }
+                    }
+                }
+            },
+            IdStrategy::Uuid | IdStrategy::UuidV7 => {
+                // UUID需要转换为UUID类型
+                if let Some(id_value) = insert_data.get("id").cloned() {
+                    if let DataValue::String(s) = id_value {
+                        if let Ok(uuid) = s.parse::<uuid::Uuid>() {
+                            insert_data.insert("id".to_string(), DataValue::Uuid(uuid));
+                            debug!("将UUID从字符串转换为UUID类型: {}", s);
+                        }
+                    }
+                }
+            },
+            _ => {} // 其他策略不需要转换
+        }
+    }
+
+    insert_data
+}
@@ -84,6 +84,51 @@ pub(crate) async fn count(
     }
 }
 
+/// PostgreSQL按时间段计数操作
+pub(crate) async fn time_bucket(
+    adapter: &PostgresAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    field: &str,
+    interval: Interval,
+    conditions: &[QueryCondition],
+) -> QuickDbResult<Vec<(DataValue, u64)>> {
+    if let DatabaseConnection::PostgreSQL(pool) = connection {
+        let (sql, params) = crate::adapter::query_builder::time_bucket_sql(DatabaseType::PostgreSQL, table, field, interval, conditions)?;
+
+        debug!("执行PostgreSQL按时间段计数: {}", sql);
+
+        let rows = super::utils::execute_query(adapter, pool, &sql, &params).await?;
+        crate::adapter::query_builder::time_bucket_rows(rows)
+    } else {
+        Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+        })
+    }
+}
+
+/// PostgreSQL聚合操作
+pub(crate) async fn aggregate(
+    adapter: &PostgresAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    aggregations: &[Aggregation],
+    conditions: &[QueryCondition],
+    group_by: &[String],
+) -> QuickDbResult<Vec<DataValue>> {
+    if let DatabaseConnection::PostgreSQL(pool) = connection {
+        let (sql, params) = crate::adapter::query_builder::aggregate_sql(DatabaseType::PostgreSQL, table, aggregations, conditions, group_by)?;
+
+        debug!("执行PostgreSQL聚合查询: {}", sql);
+
+        super::utils::execute_query(adapter, pool, &sql, &params).await
+    } else {
+        Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+        })
+    }
+}
+
 /// PostgreSQL存在检查操作
 pub(crate) async fn exists(
     adapter: &PostgresAdapter,
@@ -91,6 +136,32 @@ pub(crate) async fn exists(
     table: &str,
     conditions: &[QueryCondition],
 ) -> QuickDbResult<bool> {
-    let count = count(adapter, connection, table, conditions).await?;
-    Ok(count > 0)
+    if let DatabaseConnection::PostgreSQL(pool) = connection {
+        // 使用 EXISTS + LIMIT 1，命中第一条记录即返回，避免全表计数
+        let (inner_sql, params) = SqlQueryBuilder::new()
+            .database_type(crate::types::DatabaseType::PostgreSQL)
+            .select(&["1"])
+            .from(table)
+            .where_conditions(conditions)
+            .limit(1)
+            .build()?;
+        let sql = format!("SELECT EXISTS({}) AS found", inner_sql);
+
+        debug!("执行PostgreSQL存在检查: {}", sql);
+
+        let results = super::utils::execute_query(adapter, pool, &sql, &params).await?;
+        if let Some(DataValue::Object(obj)) = results.first() {
+            match obj.get("found") {
+                Some(DataValue::Bool(found)) => return Ok(*found),
+                Some(DataValue::Int(found)) => return Ok(*found != 0),
+                _ => {}
+            }
+        }
+
+        Ok(false)
+    } else {
+        Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+        })
+    }
 }
\ No newline at end of file
@@ -1,14 +1,86 @@
 //! PostgreSQL表和索引管理操作
 
 use crate::adapter::postgres::PostgresAdapter;
+use crate::adapter::query_builder::rename_table_sql;
 use crate::pool::DatabaseConnection;
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
-use crate::model::{FieldType, FieldDefinition};
-use rat_logger::debug;
+use crate::model::{FieldType, FieldDefinition, IndexDefinition};
+use rat_logger::{debug, info};
 use sqlx::Row;
 use std::collections::HashMap;
 
+/// 目标数据库不存在时创建
+///
+/// `maintenance_url` 指向服务器的维护库（`postgres`），需要 `CREATEDB` 权限；
+/// 并发创建时另一方已建好的库视为成功
+pub(crate) async fn create_database_if_missing(maintenance_url: &str, database: &str) -> QuickDbResult<()> {
+    use sqlx::Connection;
+
+    let mut conn = sqlx::postgres::PgConnection::connect(maintenance_url).await
+        .map_err(|e| QuickDbError::ConnectionError {
+            message: format!("连接PostgreSQL维护库失败: {}", e),
+        })?;
+
+    let exists = sqlx::query("SELECT 1 FROM pg_database WHERE datname = $1")
+        .bind(database)
+        .fetch_optional(&mut conn)
+        .await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("检查PostgreSQL数据库是否存在失败: {}", e),
+        })?
+        .is_some();
+    if exists {
+        return Ok(());
+    }
+
+    // CREATE DATABASE 不支持参数绑定，库名按标识符转义
+    let sql = format!("CREATE DATABASE \"{}\"", database.replace('"', "\"\""));
+    match sqlx::query(&sql).execute(&mut conn).await {
+        Ok(_) => {
+            info!("已创建PostgreSQL数据库: {}", database);
+            Ok(())
+        }
+        // 42P04: duplicate_database
+        Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("42P04") => Ok(()),
+        Err(e) => Err(QuickDbError::QueryError {
+            message: format!("创建PostgreSQL数据库 {} 失败（需要CREATEDB权限）: {}", database, e),
+        }),
+    }
+}
+
+/// 将字段类型映射为PostgreSQL列类型，`datetime_precision` 为日期时间列的小数秒位数
+pub(crate) fn column_type(field_type: &FieldType, datetime_precision: Option<u8>) -> String {
+    match field_type {
+        FieldType::String { max_length, .. } => {
+            if let Some(max_len) = max_length {
+                format!("VARCHAR({})", max_len)
+            } else {
+                "TEXT".to_string()
+            }
+        },
+        FieldType::Integer { .. } => "INTEGER".to_string(),
+        FieldType::BigInteger => "BIGINT".to_string(),
+        FieldType::Float { .. } => "REAL".to_string(),
+        FieldType::Double => "DOUBLE PRECISION".to_string(),
+        FieldType::Text => "TEXT".to_string(),
+        FieldType::Boolean => "BOOLEAN".to_string(),
+        FieldType::DateTime => match datetime_precision {
+            Some(precision) => format!("TIMESTAMPTZ({})", precision),
+            None => "TIMESTAMPTZ".to_string(),
+        },
+        FieldType::Date => "DATE".to_string(),
+        FieldType::Time => "TIME".to_string(),
+        FieldType::Uuid => "UUID".to_string(),
+        FieldType::Json => "JSONB".to_string(),
+        FieldType::Binary => "BYTEA".to_string(),
+        FieldType::Decimal { precision, scale } => format!("DECIMAL({},{})", precision, scale),
+        FieldType::Array { item_type: _, max_items: _, min_items: _ } => "JSONB".to_string(),
+        FieldType::Object { .. } => "JSONB".to_string(),
+        FieldType::Reference { target_collection: _ } => "TEXT".to_string(),
+    }
+}
+
 /// PostgreSQL创建表操作
 pub(crate) async fn create_table(
     adapter: &PostgresAdapter,
@@ -24,51 +96,26 @@ pub(crate) async fn create_table(
         if !fields.contains_key("id") {
             let id_definition = match id_strategy {
                 IdStrategy::AutoIncrement => "id SERIAL PRIMARY KEY".to_string(),
-                IdStrategy::Uuid => "id UUID PRIMARY KEY".to_string(), // 使用原生UUID类型，返回时转换为字符串
+                IdStrategy::Uuid | IdStrategy::UuidV7 => "id UUID PRIMARY KEY".to_string(), // 使用原生UUID类型，返回时转换为字符串
                 IdStrategy::Snowflake { .. } => "id BIGINT PRIMARY KEY".to_string(),
                 IdStrategy::ObjectId => "id TEXT PRIMARY KEY".to_string(),
+                IdStrategy::Ulid => "id CHAR(26) PRIMARY KEY".to_string(),
                 IdStrategy::Custom(_) => "id TEXT PRIMARY KEY".to_string(), // 自定义策略使用TEXT
             };
             field_definitions.push(id_definition);
         }
 
         for (name, field_definition) in fields {
-            let sql_type = match &field_definition.field_type {
-                FieldType::String { max_length, .. } => {
-                    if let Some(max_len) = max_length {
-                        format!("VARCHAR({})", max_len)
-                    } else {
-                        "TEXT".to_string()
-                    }
-                },
-                FieldType::Integer { .. } => "INTEGER".to_string(),
-                FieldType::BigInteger => "BIGINT".to_string(),
-                FieldType::Float { .. } => "REAL".to_string(),
-                FieldType::Double => "DOUBLE PRECISION".to_string(),
-                FieldType::Text => "TEXT".to_string(),
-                FieldType::Boolean => "BOOLEAN".to_string(),
-                FieldType::DateTime => {
-                    debug!("🔍 字段 {} 类型为 DateTime，required: {}", name, field_definition.required);
-                    "TIMESTAMPTZ".to_string()
-                },
-                FieldType::Date => "DATE".to_string(),
-                FieldType::Time => "TIME".to_string(),
-                FieldType::Uuid => "UUID".to_string(),
-                FieldType::Json => "JSONB".to_string(),
-                FieldType::Binary => "BYTEA".to_string(),
-                FieldType::Decimal { precision, scale } => format!("DECIMAL({},{})", precision, scale),
-                FieldType::Array { item_type: _, max_items: _, min_items: _ } => "JSONB".to_string(),
-                FieldType::Object { .. } => "JSONB".to_string(),
-                FieldType::Reference { target_collection: _ } => "TEXT".to_string(),
-            };
+            let sql_type = column_type(&field_definition.field_type, adapter.datetime_precision);
 
             // 如果是id字段，根据ID策略创建正确的字段类型
             if name == "id" {
                 let id_definition = match id_strategy {
                     IdStrategy::AutoIncrement => "id SERIAL PRIMARY KEY".to_string(),
-                    IdStrategy::Uuid => "id UUID PRIMARY KEY".to_string(), // 使用原生UUID类型
+                    IdStrategy::Uuid | IdStrategy::UuidV7 => "id UUID PRIMARY KEY".to_string(), // 使用原生UUID类型
                     IdStrategy::Snowflake { .. } => "id BIGINT PRIMARY KEY".to_string(),
                     IdStrategy::ObjectId => "id TEXT PRIMARY KEY".to_string(),
+                    IdStrategy::Ulid => "id CHAR(26) PRIMARY KEY".to_string(),
                     IdStrategy::Custom(_) => "id TEXT PRIMARY KEY".to_string(), // 自定义策略使用TEXT
                 };
                 field_definitions.push(id_definition);
@@ -80,7 +127,9 @@ pub(crate) async fn create_table(
                     "NULL"
                 };
                 debug!("🔍 字段 {} 定义: {} {}", name, sql_type, null_constraint);
-                field_definitions.push(format!("{} {} {}", name, sql_type, null_constraint));
+                let collate = crate::adapter::query_builder::column_collate_clause(DatabaseType::PostgreSQL, field_definition)?;
+                let default = crate::adapter::query_builder::column_default_clause(DatabaseType::PostgreSQL, field_definition)?;
+                field_definitions.push(format!("{} {}{}{} {}", name, sql_type, collate, default, null_constraint));
             }
         }
 
@@ -109,17 +158,21 @@ pub(crate) async fn create_index(
     connection: &DatabaseConnection,
     table: &str,
     index_name: &str,
-    fields: &[String],
-    unique: bool,
+    index: &IndexDefinition,
 ) -> QuickDbResult<()> {
     if let DatabaseConnection::PostgreSQL(pool) = connection {
-        let unique_clause = if unique { "UNIQUE " } else { "" };
+        if index.sparse {
+            debug!("PostgreSQL不支持稀疏索引，忽略sparse选项: {}", index_name);
+        }
+
+        let unique_clause = if index.unique { "UNIQUE " } else { "" };
         let sql = format!(
-            "CREATE {}INDEX IF NOT EXISTS {} ON {} ({})",
+            "CREATE {}INDEX IF NOT EXISTS {} ON {} ({}){}",
             unique_clause,
             index_name,
             table,
-            fields.join(", ")
+            crate::adapter::query_builder::index_columns_clause(DatabaseType::PostgreSQL, index)?,
+            crate::adapter::query_builder::index_predicate_clause(DatabaseType::PostgreSQL, &index.partial_filter)?
         );
 
         debug!("执行PostgreSQL索引创建: {}", sql);
@@ -134,6 +187,60 @@ pub(crate) async fn create_index(
     }
 }
 
+/// 写入前确保表存在，不存在时使用预定义的模型元数据创建，没有模型元数据时返回错误
+pub(crate) async fn ensure_table(
+    adapter: &PostgresAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    id_strategy: &IdStrategy,
+) -> QuickDbResult<()> {
+    if !table_exists(adapter, connection, table).await? {
+        // 获取表创建锁，防止重复创建
+        let _lock = adapter.acquire_table_lock(table).await;
+
+        // 再次检查表是否存在（双重检查锁定模式）
+        if !table_exists(adapter, connection, table).await? {
+            // 尝试从模型管理器获取预定义的元数据
+            if let Some(model_meta) = crate::manager::get_model(table) {
+                debug!("表 {} 不存在，使用预定义模型元数据创建", table);
+
+                // 使用模型元数据创建表
+                create_table(adapter, connection, table, &model_meta.fields, id_strategy).await?;
+
+                // 等待100ms确保数据库事务完全提交
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                debug!("⏱️ 等待100ms确保表 '{}' 创建完成", table);
+            } else {
+                return Err(QuickDbError::ValidationError {
+                    field: "table_creation".to_string(),
+                    message: format!("表 '{}' 不存在，且没有预定义的模型元数据。请先定义模型并使用 define_model! 宏明确指定字段类型。", table),
+                });
+            }
+        } else {
+            debug!("表 {} 已存在，跳过创建", table);
+        }
+
+        // 锁会在这里自动释放（当 _lock 超出作用域时）
+    }
+    Ok(())
+}
+
+/// 检查表的id字段是否为SERIAL自增列
+pub(crate) async fn has_serial_id(pool: &sqlx::PgPool, table: &str) -> QuickDbResult<bool> {
+    let check_serial_sql = "SELECT column_default FROM information_schema.columns WHERE table_name = $1 AND column_name = 'id'";
+    let rows = sqlx::query(check_serial_sql)
+        .bind(table)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("检查表结构失败: {}", e),
+        })?;
+
+    Ok(rows.first()
+        .and_then(|row| row.try_get::<Option<String>, _>("column_default").ok().flatten())
+        .is_some_and(|default_value| default_value.starts_with("nextval")))
+}
+
 /// PostgreSQL表存在检查操作
 pub(crate) async fn table_exists(
     adapter: &PostgresAdapter,
@@ -161,6 +268,76 @@ pub(crate) async fn table_exists(
     }
 }
 
+/// PostgreSQL获取表列名操作
+pub(crate) async fn get_table_columns(
+    adapter: &PostgresAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+) -> QuickDbResult<Vec<String>> {
+    if let DatabaseConnection::PostgreSQL(pool) = connection {
+        let sql = "SELECT column_name::text FROM information_schema.columns WHERE table_schema = 'public' AND table_name = $1";
+        sqlx::query_scalar::<_, String>(sql)
+            .bind(table)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| QuickDbError::QueryError {
+                message: format!("获取PostgreSQL表列信息失败: {}", e),
+            })
+    } else {
+        Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+        })
+    }
+}
+
+/// PostgreSQL获取表索引名操作
+pub(crate) async fn list_index_names(
+    adapter: &PostgresAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+) -> QuickDbResult<Vec<String>> {
+    if let DatabaseConnection::PostgreSQL(pool) = connection {
+        let sql = "SELECT indexname::text FROM pg_indexes WHERE schemaname = 'public' AND tablename = $1";
+        sqlx::query_scalar::<_, String>(sql)
+            .bind(table)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| QuickDbError::QueryError {
+                message: format!("获取PostgreSQL索引信息失败: {}", e),
+            })
+    } else {
+        Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+        })
+    }
+}
+
+/// PostgreSQL添加列操作，新增列总是允许NULL以兼容已有数据
+pub(crate) async fn add_column(
+    adapter: &PostgresAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    column: &str,
+    field_definition: &FieldDefinition,
+) -> QuickDbResult<()> {
+    if let DatabaseConnection::PostgreSQL(pool) = connection {
+        let collate = crate::adapter::query_builder::column_collate_clause(DatabaseType::PostgreSQL, field_definition)?;
+        let sql = format!(
+            "ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} {}{} NULL",
+            table, column, column_type(&field_definition.field_type, adapter.datetime_precision), collate
+        );
+        debug!("执行PostgreSQL添加列: {}", sql);
+
+        super::utils::execute_update(adapter, pool, &sql, &[]).await?;
+
+        Ok(())
+    } else {
+        Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+        })
+    }
+}
+
 /// PostgreSQL删除表操作
 pub(crate) async fn drop_table(
     adapter: &PostgresAdapter,
@@ -201,6 +378,36 @@ pub(crate) async fn drop_table(
     }
 }
 
+/// PostgreSQL重命名表操作
+///
+/// 外键、索引和序列随表一起保留，引用该表的视图会自动指向新名称
+pub(crate) async fn rename_table(
+    _adapter: &PostgresAdapter,
+    connection: &DatabaseConnection,
+    from: &str,
+    to: &str,
+) -> QuickDbResult<()> {
+    if let DatabaseConnection::PostgreSQL(pool) = connection {
+        let sql = rename_table_sql(DatabaseType::PostgreSQL, from, to)?;
+
+        debug!("执行PostgreSQL重命名表SQL: {}", sql);
+
+        sqlx::query(&sql)
+            .execute(pool)
+            .await
+            .map_err(|e| QuickDbError::QueryError {
+                message: format!("重命名PostgreSQL表失败: {}", e),
+            })?;
+
+        debug!("成功重命名PostgreSQL表: {} -> {}", from, to);
+        Ok(())
+    } else {
+        Err(QuickDbError::ConnectionError {
+            message: "连接类型不匹配，期望PostgreSQL连接".to_string(),
+        })
+    }
+}
+
 /// PostgreSQL获取服务器版本操作
 pub(crate) async fn get_server_version(
     adapter: &PostgresAdapter,
@@ -58,6 +58,17 @@ pub(crate) fn row_to_data_map(
                     DataValue::Null
                 }
             },
+            "NUMERIC" => {
+                // NUMERIC按精确的十进制字符串返回，避免SUM/AVG等聚合结果的浮点舍入
+                if let Ok(val) = row.try_get::<Option<sqlx::types::Decimal>, _>(column_name) {
+                    match val {
+                        Some(d) => DataValue::String(d.to_string()),
+                        None => DataValue::Null,
+                    }
+                } else {
+                    DataValue::Null
+                }
+            },
             "BOOL" => {
                 if let Ok(val) = row.try_get::<Option<bool>, _>(column_name) {
                     match val {
@@ -199,6 +210,7 @@ pub(crate) async fn execute_query(
     sql: &str,
     params: &[DataValue],
 ) -> QuickDbResult<Vec<DataValue>> {
+    crate::types::query_log::log_sql(sql, params);
     let mut query = sqlx::query(sql);
 
     // 绑定参数
@@ -232,11 +244,35 @@ pub(crate) async fn execute_query(
         };
     }
 
-    let rows = query.fetch_all(pool)
-        .await
-        .map_err(|e| QuickDbError::QueryError {
-            message: format!("执行PostgreSQL查询失败: {}", e),
-        })?;
+    // 处于适配器事务作用域（如 seed）时在该事务中执行
+    if let Some(mut tx) = crate::adapter::transaction::current_postgres_transaction().await {
+        let rows = query.fetch_all(&mut **tx)
+            .await
+            .map_err(|e| QuickDbError::QueryError {
+                message: format!("执行PostgreSQL查询失败: {}", e),
+            })?;
+        return rows.iter()
+            .map(|row| row_to_data_map(adapter, row).map(DataValue::Object))
+            .collect();
+    }
+
+    let rows = match crate::types::current_session_context() {
+        Some(context) => {
+            let mut tx = begin_with_session_context(pool, &context).await?;
+            let rows = query.fetch_all(&mut *tx)
+                .await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("执行PostgreSQL查询失败: {}", e),
+                })?;
+            commit_session_transaction(tx).await?;
+            rows
+        },
+        None => query.fetch_all(pool)
+            .await
+            .map_err(|e| QuickDbError::QueryError {
+                message: format!("执行PostgreSQL查询失败: {}", e),
+            })?,
+    };
 
     let mut results = Vec::new();
     for row in rows {
@@ -255,6 +291,7 @@ pub(crate) async fn execute_update(
     params: &[DataValue],
 ) -> QuickDbResult<u64> {
     rat_logger::debug!("🔍 PostgreSQL execute_update: SQL={}", sql);
+    crate::types::query_log::log_sql(sql, params);
     let mut query = sqlx::query(sql);
 
     // 绑定参数
@@ -287,11 +324,127 @@ pub(crate) async fn execute_update(
         };
     }
 
-    let result = query.execute(pool)
+    // 处于适配器事务作用域（如试运行）时在该事务中执行
+    if let Some(mut tx) = crate::adapter::transaction::current_postgres_transaction().await {
+        let result = query.execute(&mut **tx)
+            .await
+            .map_err(|e| QuickDbError::QueryError {
+                message: format!("执行PostgreSQL更新失败: {}", e),
+            })?;
+        return Ok(result.rows_affected());
+    }
+
+    let result = match crate::types::current_session_context() {
+        Some(context) => {
+            let mut tx = begin_with_session_context(pool, &context).await?;
+            let result = query.execute(&mut *tx)
+                .await
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("执行PostgreSQL更新失败: {}", e),
+                })?;
+            commit_session_transaction(tx).await?;
+            result
+        },
+        None => query.execute(pool)
+            .await
+            .map_err(|e| QuickDbError::QueryError {
+                message: format!("执行PostgreSQL更新失败: {}", e),
+            })?,
+    };
+
+    Ok(result.rows_affected())
+}
+/// 开启事务并应用会话上下文中的设置
+///
+/// 使用 `set_config(key, value, true)`（等价于 `SET LOCAL`），设置在事务结束时自动复位
+async fn begin_with_session_context<'a>(
+    pool: &'a sqlx::Pool<sqlx::Postgres>,
+    context: &crate::types::SessionContext,
+) -> QuickDbResult<sqlx::Transaction<'a, sqlx::Postgres>> {
+    let mut tx = pool.begin()
         .await
         .map_err(|e| QuickDbError::QueryError {
-            message: format!("执行PostgreSQL更新失败: {}", e),
+            message: format!("开启PostgreSQL事务失败: {}", e),
         })?;
 
-    Ok(result.rows_affected())
-}
\ No newline at end of file
+    for (key, value) in context.settings() {
+        debug!("应用PostgreSQL会话设置: {} = {}", key, value);
+        sqlx::query("SELECT set_config($1, $2, true)")
+            .bind(key)
+            .bind(value)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| QuickDbError::QueryError {
+                message: format!("应用PostgreSQL会话设置 '{}' 失败: {}", key, e),
+            })?;
+    }
+
+    Ok(tx)
+}
+
+/// 提交携带会话设置的事务
+async fn commit_session_transaction(tx: sqlx::Transaction<'_, sqlx::Postgres>) -> QuickDbResult<()> {
+    tx.commit()
+        .await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("提交PostgreSQL事务失败: {}", e),
+        })
+}
+
+/// 构建JSON合并补丁（RFC 7396）的JSONB表达式
+///
+/// 原值不是对象时按空对象处理；值为 null 的键用 `-` 删除，嵌套对象递归合并，
+/// 其他值整体替换。键和值都以参数绑定，编号接在 `params` 已有参数之后
+pub(crate) fn build_jsonb_merge_expr(
+    target: &str,
+    patch: &serde_json::Map<String, Value>,
+    params: &mut Vec<DataValue>,
+) -> String {
+    let mut expr = format!("(CASE WHEN jsonb_typeof({}) = 'object' THEN {} ELSE '{{}}'::jsonb END)", target, target);
+    let mut pairs = Vec::new();
+
+    for (key, value) in patch {
+        params.push(DataValue::String(key.clone()));
+        let key_param = params.len();
+        match value {
+            Value::Null => {
+                expr = format!("({} - ${}::text)", expr, key_param);
+            }
+            Value::Object(nested) => {
+                let nested_target = format!("({} -> ${}::text)", target, key_param);
+                let nested_expr = build_jsonb_merge_expr(&nested_target, nested, params);
+                pairs.push(format!("${}::text, {}", key_param, nested_expr));
+            }
+            other => {
+                params.push(DataValue::Json(other.clone()));
+                pairs.push(format!("${}::text, ${}", key_param, params.len()));
+            }
+        }
+    }
+
+    if !pairs.is_empty() {
+        expr = format!("({} || jsonb_build_object({}))", expr, pairs.join(", "));
+    }
+    expr
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsonb_merge_expr_binds_keys_and_recurses() {
+        let patch = serde_json::json!({ "prefs": { "lang": "zh" }, "tmp": null });
+        let mut params = vec![DataValue::Int(1)];
+        let expr = build_jsonb_merge_expr("profile", patch.as_object().unwrap(), &mut params);
+
+        assert_eq!(
+            expr,
+            "(((CASE WHEN jsonb_typeof(profile) = 'object' THEN profile ELSE '{}'::jsonb END) - $5::text) \
+             || jsonb_build_object($2::text, \
+             ((CASE WHEN jsonb_typeof((profile -> $2::text)) = 'object' THEN (profile -> $2::text) ELSE '{}'::jsonb END) \
+             || jsonb_build_object($3::text, $4))))"
+        );
+        assert_eq!(params.len(), 5);
+    }
+}
@@ -0,0 +1,146 @@
+//! PostgreSQL变更订阅模块
+//!
+//! 基于 LISTEN/NOTIFY 实现，需要目标表已安装 `postgres_change_trigger_sql` 生成的触发器。
+//! 通知只携带操作类型和主键，插入和更新事件收到后再按主键查询当前记录
+
+use crate::adapter::postgres_utils::postgres_change_channel;
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::*;
+use futures::StreamExt;
+use rat_logger::{debug, warn};
+use sqlx::postgres::PgListener;
+
+/// 订阅表的数据变更
+pub(crate) async fn watch(
+    pool: &sqlx::Pool<sqlx::Postgres>,
+    table: &str,
+) -> QuickDbResult<ChangeStream> {
+    let channel = postgres_change_channel(table);
+
+    let mut listener = PgListener::connect_with(pool).await
+        .map_err(|e| QuickDbError::ConnectionError {
+            message: format!("创建PostgreSQL监听连接失败: {}", e),
+        })?;
+    listener.listen(&channel).await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("监听PostgreSQL频道 {} 失败: {}", channel, e),
+        })?;
+
+    debug!("开始监听PostgreSQL变更频道: {}", channel);
+
+    let lookup_sql = document_lookup_sql(table)?;
+    let table = table.to_string();
+    let pool = pool.clone();
+    let stream = listener.into_stream().filter_map(move |notification| {
+        let table = table.clone();
+        let lookup_sql = lookup_sql.clone();
+        let pool = pool.clone();
+        async move {
+            let notification = match notification {
+                Ok(notification) => notification,
+                Err(e) => return Some(Err(QuickDbError::ConnectionError {
+                    message: format!("接收PostgreSQL通知失败: {}", e),
+                })),
+            };
+            Some(match parse_notification_payload(&table, notification.payload())? {
+                Ok(event) => with_current_document(&pool, &lookup_sql, notification.payload(), event).await,
+                Err(e) => Err(e),
+            })
+        }
+    });
+
+    Ok(ChangeStream::new(stream.boxed()))
+}
+
+/// 解析触发器发送的通知载荷，无法识别的操作类型会被跳过
+fn parse_notification_payload(table: &str, payload: &str) -> Option<QuickDbResult<ChangeEvent>> {
+    let json: serde_json::Value = match serde_json::from_str(payload) {
+        Ok(json) => json,
+        Err(e) => {
+            return Some(Err(QuickDbError::SerializationError {
+                message: format!("解析PostgreSQL通知载荷失败: {}", e),
+            }));
+        }
+    };
+
+    let op = match json.get("op").and_then(|v| v.as_str()).and_then(ChangeOperation::from_str) {
+        Some(op) => op,
+        None => {
+            warn!("忽略无法识别的PostgreSQL变更通知: {}", payload);
+            return None;
+        }
+    };
+
+    let id = json.get("id")
+        .cloned()
+        .map(crate::types::data_value::json_value_to_data_value)
+        .unwrap_or(DataValue::Null);
+
+    Some(Ok(ChangeEvent { table: table.to_string(), op, id, document: None }))
+}
+
+/// 按通知载荷中的主键查询当前记录的SQL
+///
+/// 通过 `json_populate_record` 把载荷中的ID转换为主键列的类型，查询可以使用主键索引
+fn document_lookup_sql(table: &str) -> QuickDbResult<String> {
+    let validator = crate::security::DatabaseSecurityValidator::new(DatabaseType::PostgreSQL);
+    let safe_table = validator.get_safe_table_identifier(table)?;
+    Ok(format!(
+        "SELECT row_to_json(t)::text FROM {table} t WHERE t.id = (json_populate_record(NULL::{table}, $1::json)).id",
+        table = safe_table,
+    ))
+}
+
+/// 为插入和更新事件填充变更后的记录
+///
+/// 与 MongoDB 的 `UpdateLookup` 一致，读取的是收到通知时的当前记录；记录已被删除时 `document` 为 `None`
+async fn with_current_document(
+    pool: &sqlx::Pool<sqlx::Postgres>,
+    lookup_sql: &str,
+    payload: &str,
+    mut event: ChangeEvent,
+) -> QuickDbResult<ChangeEvent> {
+    if event.op == ChangeOperation::Delete {
+        return Ok(event);
+    }
+    let row: Option<String> = sqlx::query_scalar(lookup_sql)
+        .bind(payload)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("查询变更记录失败: {}", e),
+        })?;
+    event.document = match row {
+        Some(text) => {
+            let json: serde_json::Value = serde_json::from_str(&text)
+                .map_err(|e| QuickDbError::SerializationError {
+                    message: format!("解析变更记录失败: {}", e),
+                })?;
+            Some(crate::types::data_value::json_value_to_data_value(json))
+        }
+        None => None,
+    };
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_payload_carries_only_op_and_id() {
+        let event = parse_notification_payload("users", r#"{"op": "UPDATE", "id": 7}"#)
+            .expect("应识别操作类型")
+            .expect("应解析载荷");
+        assert_eq!(event.op, ChangeOperation::Update);
+        assert_eq!(event.id, DataValue::Int(7));
+        assert!(event.document.is_none());
+        assert!(parse_notification_payload("users", r#"{"op": "TRUNCATE"}"#).is_none());
+
+        assert_eq!(
+            document_lookup_sql("users").unwrap(),
+            "SELECT row_to_json(t)::text FROM \"users\" t WHERE t.id = (json_populate_record(NULL::\"users\", $1::json)).id"
+        );
+        assert!(document_lookup_sql("users; DROP TABLE x").is_err());
+    }
+}
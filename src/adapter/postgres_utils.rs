@@ -325,11 +325,57 @@ pub fn convert_to_jsonb_value(value: &DataValue) -> QuickDbResult<DataValue> {
     }
 }
 
+/// 获取表变更通知使用的PostgreSQL NOTIFY频道名
+pub fn postgres_change_channel(table: &str) -> String {
+    format!("rat_quickdb_{}", table)
+}
+
+/// 生成PostgreSQL变更通知触发器SQL
+///
+/// `watch` 基于 LISTEN/NOTIFY 实现，需要先在目标表上安装此触发器。
+/// 触发器在每次 INSERT/UPDATE/DELETE 后向 [`postgres_change_channel`] 频道发送JSON载荷
+/// `{"op": "INSERT", "id": ...}`。NOTIFY 载荷上限为8000字节，因此只发送操作类型和主键，
+/// 插入和更新事件的记录由 `watch` 收到通知后按ID查询
+pub fn postgres_change_trigger_sql(table: &str) -> QuickDbResult<String> {
+    let validator = crate::security::DatabaseSecurityValidator::new(crate::types::DatabaseType::PostgreSQL);
+    let safe_table = validator.get_safe_table_identifier(table)?;
+    let trigger_name = format!("\"rat_quickdb_change_{}\"", table);
+
+    Ok(format!(
+        r#"CREATE OR REPLACE FUNCTION rat_quickdb_notify_change() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM pg_notify('rat_quickdb_' || TG_TABLE_NAME, json_build_object('op', TG_OP, 'id', OLD.id)::text);
+        RETURN OLD;
+    ELSE
+        PERFORM pg_notify('rat_quickdb_' || TG_TABLE_NAME, json_build_object('op', TG_OP, 'id', NEW.id)::text);
+        RETURN NEW;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+DROP TRIGGER IF EXISTS {trigger} ON {table};
+CREATE TRIGGER {trigger} AFTER INSERT OR UPDATE OR DELETE ON {table} FOR EACH ROW EXECUTE FUNCTION rat_quickdb_notify_change();"#,
+        trigger = trigger_name,
+        table = safe_table,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_change_trigger_sql() {
+        let sql = postgres_change_trigger_sql("users").unwrap();
+        assert!(sql.contains("ON \"users\""));
+        assert!(sql.contains("CREATE TRIGGER \"rat_quickdb_change_users\""));
+        // 载荷只含操作类型和主键，不受记录大小影响
+        assert!(!sql.contains("row_to_json"));
+        assert_eq!(postgres_change_channel("users"), "rat_quickdb_users");
+        assert!(postgres_change_trigger_sql("users; DROP TABLE x").is_err());
+    }
+
     #[test]
     fn test_json_query_condition_string_search() {
         let (sql, param) = build_json_query_condition("profile", &DataValue::String("Rust".to_string()), "$1").unwrap();
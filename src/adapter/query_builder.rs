@@ -12,6 +12,8 @@ pub struct SqlQueryBuilder {
     query_type: QueryType,
     table: String,
     fields: Vec<String>,
+    projection: Vec<String>,
+    select_exprs: Vec<(Expr, String)>,
     conditions: Vec<QueryCondition>,
     condition_groups: Vec<QueryConditionGroup>,
     joins: Vec<JoinClause>,
@@ -24,6 +26,7 @@ pub struct SqlQueryBuilder {
     returning_fields: Vec<String>,
     db_type: DatabaseType,
     security_validator: DatabaseSecurityValidator,
+    collation: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +66,8 @@ impl SqlQueryBuilder {
             query_type: QueryType::Select,
             table: String::new(),
             fields: Vec::new(),
+            projection: Vec::new(),
+            select_exprs: Vec::new(),
             conditions: Vec::new(),
             condition_groups: Vec::new(),
             joins: Vec::new(),
@@ -75,6 +80,7 @@ impl SqlQueryBuilder {
             returning_fields: Vec::new(),
             db_type,
             security_validator: DatabaseSecurityValidator::new(db_type),
+            collation: None,
         }
     }
 
@@ -92,6 +98,19 @@ impl SqlQueryBuilder {
         self
     }
 
+    /// 按字段名设置SELECT列表，字段名在构建时校验并加引号；为空时选择所有字段
+    pub fn select_fields<S: AsRef<str>>(mut self, fields: &[S]) -> Self {
+        self.query_type = QueryType::Select;
+        self.projection = fields.iter().map(|f| f.as_ref().to_string()).collect();
+        self
+    }
+
+    /// 在SELECT列表末尾追加计算字段（表达式, 结果字段名）
+    pub fn select_expr<S: AsRef<str>>(mut self, exprs: &[(Expr, S)]) -> Self {
+        self.select_exprs.extend(exprs.iter().map(|(expr, alias)| (expr.clone(), alias.as_ref().to_string())));
+        self
+    }
+
     /// 设置查询类型为INSERT
     pub fn insert(mut self, values: HashMap<String, DataValue>) -> Self {
         self.query_type = QueryType::Insert;
@@ -182,6 +201,21 @@ impl SqlQueryBuilder {
         self
     }
 
+    /// 按分页配置设置LIMIT和OFFSET
+    ///
+    /// `limit` 为 0 表示不限制返回数量（与 MongoDB 一致），`skip` 为 0 时不输出 OFFSET
+    pub fn pagination(mut self, pagination: &PaginationConfig) -> Self {
+        self.limit = (pagination.limit > 0).then_some(pagination.limit);
+        self.offset = (pagination.skip > 0).then_some(pagination.skip);
+        self
+    }
+
+    /// 设置排序规则（COLLATE），作用于字符串比较条件和字符串字段的ORDER BY
+    pub fn collation(mut self, collation: Option<&str>) -> Self {
+        self.collation = collation.map(|c| c.to_string());
+        self
+    }
+
     /// 设置RETURNING子句（用于INSERT/UPDATE/DELETE）
     pub fn returning(mut self, fields: &[&str]) -> Self {
         self.returning_fields = fields.iter().map(|s| s.to_string()).collect();
@@ -190,6 +224,10 @@ impl SqlQueryBuilder {
 
     /// 构建SQL查询语句
     pub fn build(&self) -> QuickDbResult<(String, Vec<DataValue>)> {
+        if let Some(collation) = &self.collation {
+            validate_collation_name(collation)?;
+        }
+
         let result = match self.query_type {
             QueryType::Select => self.build_select(),
             QueryType::Insert => self.build_insert(),
@@ -208,11 +246,20 @@ impl SqlQueryBuilder {
             });
         }
 
-        let fields = if self.fields.is_empty() {
+        let mut fields = if !self.projection.is_empty() {
+            self.projection.iter()
+                .map(|field| self.security_validator.get_safe_field_identifier(field))
+                .collect::<QuickDbResult<Vec<_>>>()?
+                .join(", ")
+        } else if self.fields.is_empty() {
             "*".to_string()
         } else {
             self.fields.join(", ")
         };
+        for (expr, alias) in &self.select_exprs {
+            let safe_alias = self.security_validator.get_safe_field_identifier(alias)?;
+            fields.push_str(&format!(", {} AS {}", render_expr(self.db_type, &self.security_validator, expr)?, safe_alias));
+        }
 
         let mut sql = format!("SELECT {} FROM {}", fields, self.table);
         let mut params = Vec::new();
@@ -253,24 +300,32 @@ impl SqlQueryBuilder {
 
         // 添加ORDER BY
         if !self.order_by.is_empty() {
-            let order_clauses: Vec<String> = self.order_by
+            let order_clauses = self.order_by
                 .iter()
                 .map(|o| {
                     let direction = match o.direction {
                         SortDirection::Asc => "ASC",
                         SortDirection::Desc => "DESC",
                     };
-                    format!("{} {}", o.field, direction)
+                    Ok(format!("{} {}", self.order_key(&o.field)?, direction))
                 })
-                .collect();
+                .collect::<QuickDbResult<Vec<String>>>()?;
             sql.push_str(&format!(" ORDER BY {}", order_clauses.join(", ")));
         }
 
         // 添加LIMIT和OFFSET
-        if let Some(limit) = self.limit {
-            sql.push_str(&format!(" LIMIT {}", limit));
+        // SQLite和MySQL不接受没有LIMIT的OFFSET，只有偏移量时补上表示"不限制"的LIMIT
+        let offset = self.offset.filter(|offset| *offset > 0);
+        match (self.limit, offset) {
+            (Some(limit), _) => sql.push_str(&format!(" LIMIT {}", limit)),
+            (None, Some(_)) => match self.db_type {
+                DatabaseType::SQLite => sql.push_str(" LIMIT -1"),
+                DatabaseType::MySQL => sql.push_str(&format!(" LIMIT {}", u64::MAX)),
+                DatabaseType::PostgreSQL | DatabaseType::MongoDB => {},
+            },
+            (None, None) => {},
         }
-        if let Some(offset) = self.offset {
+        if let Some(offset) = offset {
             sql.push_str(&format!(" OFFSET {}", offset));
         }
 
@@ -305,7 +360,11 @@ impl SqlQueryBuilder {
         }
 
         let columns: Vec<String> = non_null_values.keys().cloned().collect();
-        let placeholders: Vec<String> = self.generate_placeholders(columns.len());
+        let placeholders: Vec<String> = self.generate_placeholders(columns.len())
+            .into_iter()
+            .zip(&columns)
+            .map(|(placeholder, column)| self.typed_placeholder(placeholder, column, &non_null_values[column]))
+            .collect();
         let params: Vec<DataValue> = columns.iter().map(|k| non_null_values[k].clone()).collect();
 
         let mut sql = format!(
@@ -351,8 +410,8 @@ impl SqlQueryBuilder {
         }
 
         let mut param_index = 1;
-        let set_clauses: Vec<String> = non_null_values.keys().map(|k| {
-            let placeholder = self.get_placeholder(param_index);
+        let set_clauses: Vec<String> = non_null_values.iter().map(|(k, v)| {
+            let placeholder = self.typed_placeholder(self.get_placeholder(param_index), k, v);
             param_index += 1;
             format!("{} = {}", k, placeholder)
         }).collect();
@@ -461,11 +520,13 @@ impl SqlQueryBuilder {
                 }
 
                 let logical_op = match operator {
-                    LogicalOperator::And => " AND ",
+                    LogicalOperator::And | LogicalOperator::Not => " AND ",
                     LogicalOperator::Or => " OR ",
                 };
 
-                let combined_clause = if clauses.len() == 1 {
+                let combined_clause = if matches!(operator, LogicalOperator::Not) {
+                    format!("NOT ({})", clauses.join(logical_op))
+                } else if clauses.len() == 1 {
                     clauses[0].clone()
                 } else {
                     format!("({})", clauses.join(logical_op))
@@ -481,8 +542,16 @@ impl SqlQueryBuilder {
         let placeholder = self.get_placeholder(param_index);
         let mut new_index = param_index;
 
-        let safe_field = self.security_validator.get_safe_field_identifier(&condition.field)?;
-        let (clause, params) = match condition.operator {
+        // 原始条件的 field 是SQL片段而不是字段名，不经过字段校验
+        if matches!(condition.operator, QueryOperator::Raw) {
+            let (clause, params) = raw_condition_clause(self.db_type, condition, &mut new_index)?;
+            return Ok((clause, params, new_index));
+        }
+
+        let safe_field = self.condition_field_expr(condition)?;
+        let compare_field = self.collated_field(&safe_field, condition);
+        let value_placeholder = self.typed_placeholder(placeholder.clone(), &condition.field, &condition.value);
+        let (clause, params) = match null_aware_operator(condition) {
             QueryOperator::Eq => {
                 new_index += 1;
                 let value = if matches!(self.db_type, DatabaseType::PostgreSQL) {
@@ -490,27 +559,27 @@ impl SqlQueryBuilder {
                 } else {
                     condition.value.clone()
                 };
-                (format!("{} = {}", safe_field, placeholder), vec![value])
+                (format!("{} = {}", compare_field, value_placeholder), vec![value])
             }
             QueryOperator::Ne => {
                 new_index += 1;
-                (format!("{} != {}", safe_field, placeholder), vec![condition.value.clone()])
+                (format!("{} != {}", compare_field, value_placeholder), vec![condition.value.clone()])
             }
             QueryOperator::Gt => {
                 new_index += 1;
-                (format!("{} > {}", safe_field, placeholder), vec![condition.value.clone()])
+                (format!("{} > {}", compare_field, value_placeholder), vec![condition.value.clone()])
             }
             QueryOperator::Gte => {
                 new_index += 1;
-                (format!("{} >= {}", safe_field, placeholder), vec![condition.value.clone()])
+                (format!("{} >= {}", compare_field, value_placeholder), vec![condition.value.clone()])
             }
             QueryOperator::Lt => {
                 new_index += 1;
-                (format!("{} < {}", safe_field, placeholder), vec![condition.value.clone()])
+                (format!("{} < {}", compare_field, value_placeholder), vec![condition.value.clone()])
             }
             QueryOperator::Lte => {
                 new_index += 1;
-                (format!("{} <= {}", safe_field, placeholder), vec![condition.value.clone()])
+                (format!("{} <= {}", compare_field, value_placeholder), vec![condition.value.clone()])
             }
             QueryOperator::Contains => {
                 new_index += 1;
@@ -530,8 +599,8 @@ impl SqlQueryBuilder {
                     }
                 }
 
-                // 对于PostgreSQL数据库，必须根据字段类型确定操作符
-                if matches!(self.db_type, DatabaseType::PostgreSQL) {
+                // 对于PostgreSQL数据库，必须根据字段类型确定操作符（JSON子字段路径已提取为文本，直接使用LIKE）
+                if matches!(self.db_type, DatabaseType::PostgreSQL) && !self.is_json_path(&condition.field) {
                     // 检查字段类型
                     if let Some(field_type) = self.get_field_type(&self.table, &condition.field) {
                         if matches!(field_type, crate::model::FieldType::Json) {
@@ -550,12 +619,8 @@ impl SqlQueryBuilder {
                             }
                         } else {
                             // 非JSON字段使用LIKE查询
-                            let value = if let DataValue::String(s) = &condition.value {
-                                DataValue::String(format!("%{}%", s))
-                            } else {
-                                condition.value.clone()
-                            };
-                            (format!("{} LIKE {}", safe_field, placeholder), vec![value])
+                            let (clause, value) = self.like_clause(&compare_field, &placeholder, condition);
+                            (clause, vec![value])
                         }
                     } else {
                         // 无法确定字段类型，直接报错
@@ -566,59 +631,18 @@ impl SqlQueryBuilder {
                     }
                 } else {
                     // 其他数据库继续使用LIKE操作符
-                    let value = if let DataValue::String(s) = &condition.value {
-                        DataValue::String(format!("%{}%", s))
-                    } else {
-                        condition.value.clone()
-                    };
-                    (format!("{} LIKE {}", safe_field, placeholder), vec![value])
+                    let (clause, value) = self.like_clause(&compare_field, &placeholder, condition);
+                    (clause, vec![value])
                 }
             }
-            QueryOperator::StartsWith => {
+            QueryOperator::IContains | QueryOperator::StartsWith | QueryOperator::EndsWith => {
                 new_index += 1;
-                let value = if let DataValue::String(s) = &condition.value {
-                    DataValue::String(format!("{}%", s))
-                } else {
-                    condition.value.clone()
-                };
-                (format!("{} LIKE {}", safe_field, placeholder), vec![value])
+                let (clause, value) = self.like_clause(&compare_field, &placeholder, condition);
+                (clause, vec![value])
             }
-            QueryOperator::EndsWith => {
-                new_index += 1;
-                let value = if let DataValue::String(s) = &condition.value {
-                    DataValue::String(format!("%{}", s))
-                } else {
-                    condition.value.clone()
-                };
-                (format!("{} LIKE {}", safe_field, placeholder), vec![value])
-            }
-            QueryOperator::In => {
-                if let DataValue::Array(values) = &condition.value {
-                    let mut placeholders = Vec::new();
-                    for _ in 0..values.len() {
-                        placeholders.push(self.get_placeholder(new_index));
-                        new_index += 1;
-                    }
-                    (format!("{} IN ({})", safe_field, placeholders.join(", ")), values.clone())
-                } else {
-                    return Err(QuickDbError::QueryError {
-                        message: "IN 操作符需要数组类型的值".to_string(),
-                    });
-                }
-            }
-            QueryOperator::NotIn => {
-                if let DataValue::Array(values) = &condition.value {
-                    let mut placeholders = Vec::new();
-                    for _ in 0..values.len() {
-                        placeholders.push(self.get_placeholder(new_index));
-                        new_index += 1;
-                    }
-                    (format!("{} NOT IN ({})", safe_field, placeholders.join(", ")), values.clone())
-                } else {
-                    return Err(QuickDbError::QueryError {
-                        message: "NOT IN 操作符需要数组类型的值".to_string(),
-                    });
-                }
+            QueryOperator::In | QueryOperator::NotIn => {
+                let clause = self.in_list_clause(&safe_field, condition, &mut new_index)?;
+                (clause, in_list_values(condition))
             }
             QueryOperator::Regex => {
                 new_index += 1;
@@ -633,11 +657,36 @@ impl SqlQueryBuilder {
             QueryOperator::IsNotNull => {
                 (format!("{} IS NOT NULL", safe_field), vec![])
             }
+            QueryOperator::ArrayContainsAll | QueryOperator::ArraySize => {
+                self.array_condition_clause(&safe_field, condition, &mut new_index)?
+            }
+            QueryOperator::Raw => unreachable!("原始条件已在前面处理"),
         };
 
         Ok((clause, params, new_index))
     }
 
+    /// 生成 `IN` / `NOT IN` 子句，每个数组元素一个占位符，`param_index` 按元素数推进
+    ///
+    /// 空数组时 `IN` 不匹配任何记录、`NOT IN` 匹配所有记录，避免生成 `IN ()` 这样的非法SQL
+    fn in_list_clause(&self, safe_field: &str, condition: &QueryCondition, param_index: &mut usize) -> QuickDbResult<String> {
+        let negated = matches!(condition.operator, QueryOperator::NotIn);
+        let DataValue::Array(values) = &condition.value else {
+            return Err(QuickDbError::QueryError {
+                message: format!("{} 操作符需要数组类型的值", if negated { "NOT IN" } else { "IN" }),
+            });
+        };
+        if values.is_empty() {
+            return Ok(if negated { "1 = 1" } else { "1 = 0" }.to_string());
+        }
+
+        let placeholders: Vec<String> = (0..values.len())
+            .map(|offset| self.get_placeholder(*param_index + offset))
+            .collect();
+        *param_index += values.len();
+        Ok(format!("{} {} ({})", safe_field, if negated { "NOT IN" } else { "IN" }, placeholders.join(", ")))
+    }
+
     /// 构建WHERE子句，从指定的参数索引开始
     pub(crate) fn build_where_clause_with_offset(&self, conditions: &[QueryCondition], start_index: usize) -> QuickDbResult<(String, Vec<DataValue>)> {
         if conditions.is_empty() {
@@ -649,42 +698,52 @@ impl SqlQueryBuilder {
         let mut param_index = start_index;
 
         for condition in conditions {
+            // 原始条件的 field 是SQL片段而不是字段名，不经过字段校验
+            if matches!(condition.operator, QueryOperator::Raw) {
+                let (clause, raw_params) = raw_condition_clause(self.db_type, condition, &mut param_index)?;
+                clauses.push(clause);
+                params.extend(raw_params);
+                continue;
+            }
+
             let placeholder = self.get_placeholder(param_index);
-            let safe_field = self.security_validator.get_safe_field_identifier(&condition.field)?;
+            let safe_field = self.condition_field_expr(condition)?;
+            let compare_field = self.collated_field(&safe_field, condition);
+            let value_placeholder = self.typed_placeholder(placeholder.clone(), &condition.field, &condition.value);
 
-            match condition.operator {
+            match null_aware_operator(condition) {
                 QueryOperator::Eq => {
                     let value = if matches!(self.db_type, DatabaseType::PostgreSQL) {
                         self.convert_uuid_value_for_postgres(&self.table, &condition.field, &condition.value)?
                     } else {
                         condition.value.clone()
                     };
-                    clauses.push(format!("{} = {}", safe_field, placeholder));
+                    clauses.push(format!("{} = {}", compare_field, value_placeholder));
                     params.push(value);
                     param_index += 1;
                 }
                 QueryOperator::Ne => {
-                    clauses.push(format!("{} != {}", safe_field, placeholder));
+                    clauses.push(format!("{} != {}", compare_field, value_placeholder));
                     params.push(condition.value.clone());
                     param_index += 1;
                 }
                 QueryOperator::Gt => {
-                    clauses.push(format!("{} > {}", safe_field, placeholder));
+                    clauses.push(format!("{} > {}", compare_field, value_placeholder));
                     params.push(condition.value.clone());
                     param_index += 1;
                 }
                 QueryOperator::Gte => {
-                    clauses.push(format!("{} >= {}", safe_field, placeholder));
+                    clauses.push(format!("{} >= {}", compare_field, value_placeholder));
                     params.push(condition.value.clone());
                     param_index += 1;
                 }
                 QueryOperator::Lt => {
-                    clauses.push(format!("{} < {}", safe_field, placeholder));
+                    clauses.push(format!("{} < {}", compare_field, value_placeholder));
                     params.push(condition.value.clone());
                     param_index += 1;
                 }
                 QueryOperator::Lte => {
-                    clauses.push(format!("{} <= {}", safe_field, placeholder));
+                    clauses.push(format!("{} <= {}", compare_field, value_placeholder));
                     params.push(condition.value.clone());
                     param_index += 1;
                 }
@@ -704,8 +763,8 @@ impl SqlQueryBuilder {
                         }
                     }
 
-                    // 对于PostgreSQL数据库，必须根据字段类型确定操作符
-                    if matches!(self.db_type, DatabaseType::PostgreSQL) {
+                    // 对于PostgreSQL数据库，必须根据字段类型确定操作符（JSON子字段路径已提取为文本，直接使用LIKE）
+                    if matches!(self.db_type, DatabaseType::PostgreSQL) && !self.is_json_path(&condition.field) {
                         // 检查字段类型
                         if let Some(field_type) = self.get_field_type(&self.table, &condition.field) {
                             if matches!(field_type, crate::model::FieldType::Json) {
@@ -725,12 +784,9 @@ impl SqlQueryBuilder {
                                 }
                             } else {
                                 // 非JSON字段使用LIKE查询
-                                if let DataValue::String(s) = &condition.value {
-                                    params.push(DataValue::String(format!("%{}%", s)));
-                                } else {
-                                    params.push(condition.value.clone());
-                                }
-                                clauses.push(format!("{} LIKE {}", condition.field, placeholder));
+                                let (clause, value) = self.like_clause(&compare_field, &placeholder, condition);
+                                clauses.push(clause);
+                                params.push(value);
                             }
                         } else {
                             // 无法确定字段类型，直接报错
@@ -741,88 +797,101 @@ impl SqlQueryBuilder {
                         }
                     } else {
                         // 其他数据库继续使用LIKE操作符
-                        if let DataValue::String(s) = &condition.value {
-                            params.push(DataValue::String(format!("%{}%", s)));
-                        } else {
-                            params.push(condition.value.clone());
-                        }
-                        clauses.push(format!("{} LIKE {}", condition.field, placeholder));
-                    }
-                    param_index += 1;
-                }
-                QueryOperator::StartsWith => {
-                    clauses.push(format!("{} LIKE {}", condition.field, placeholder));
-                    if let DataValue::String(s) = &condition.value {
-                        params.push(DataValue::String(format!("{}%", s)));
-                    } else {
-                        params.push(condition.value.clone());
+                        let (clause, value) = self.like_clause(&compare_field, &placeholder, condition);
+                        clauses.push(clause);
+                        params.push(value);
                     }
                     param_index += 1;
                 }
-                QueryOperator::EndsWith => {
-                    clauses.push(format!("{} LIKE {}", condition.field, placeholder));
-                    if let DataValue::String(s) = &condition.value {
-                        params.push(DataValue::String(format!("%{}", s)));
-                    } else {
-                        params.push(condition.value.clone());
-                    }
+                QueryOperator::IContains | QueryOperator::StartsWith | QueryOperator::EndsWith => {
+                    let (clause, value) = self.like_clause(&compare_field, &placeholder, condition);
+                    clauses.push(clause);
+                    params.push(value);
                     param_index += 1;
                 }
-                QueryOperator::In => {
-                    if let DataValue::Array(values) = &condition.value {
-                        let mut placeholders = Vec::new();
-                        for _ in 0..values.len() {
-                            placeholders.push(self.get_placeholder(param_index));
-                            param_index += 1;
-                        }
-                        clauses.push(format!("{} IN ({})", condition.field, placeholders.join(", ")));
-                        params.extend(values.clone());
-                    } else {
-                        return Err(QuickDbError::QueryError {
-                            message: "IN 操作符需要数组类型的值".to_string(),
-                        });
-                    }
-                }
-                QueryOperator::NotIn => {
-                    if let DataValue::Array(values) = &condition.value {
-                        let mut placeholders = Vec::new();
-                        for _ in 0..values.len() {
-                            placeholders.push(self.get_placeholder(param_index));
-                            param_index += 1;
-                        }
-                        clauses.push(format!("{} NOT IN ({})", condition.field, placeholders.join(", ")));
-                        params.extend(values.clone());
-                    } else {
-                        return Err(QuickDbError::QueryError {
-                            message: "NOT IN 操作符需要数组类型的值".to_string(),
-                        });
-                    }
+                QueryOperator::In | QueryOperator::NotIn => {
+                    clauses.push(self.in_list_clause(&safe_field, condition, &mut param_index)?);
+                    params.extend(in_list_values(condition));
                 }
                 QueryOperator::Regex => {
                     // 不同数据库的正则表达式语法不同，这里使用通用的LIKE
-                    clauses.push(format!("{} REGEXP {}", condition.field, placeholder));
+                    clauses.push(format!("{} REGEXP {}", safe_field, placeholder));
                     params.push(condition.value.clone());
                     param_index += 1;
                 }
                 QueryOperator::Exists => {
                     // 检查字段是否存在（主要用于NoSQL数据库）
-                    clauses.push(format!("{} IS NOT NULL", condition.field));
+                    clauses.push(format!("{} IS NOT NULL", safe_field));
                     // Exists操作符不需要参数值
                 }
                 QueryOperator::IsNull => {
-                    clauses.push(format!("{} IS NULL", condition.field));
+                    clauses.push(format!("{} IS NULL", safe_field));
                     // IsNull操作符不需要参数值
                 }
                 QueryOperator::IsNotNull => {
-                    clauses.push(format!("{} IS NOT NULL", condition.field));
+                    clauses.push(format!("{} IS NOT NULL", safe_field));
                     // IsNotNull操作符不需要参数值
                 }
+                QueryOperator::ArrayContainsAll | QueryOperator::ArraySize => {
+                    let (clause, array_params) = self.array_condition_clause(&safe_field, condition, &mut param_index)?;
+                    clauses.push(clause);
+                    params.extend(array_params);
+                }
+                QueryOperator::Raw => unreachable!("原始条件已在前面处理"),
             }
         }
 
         Ok((clauses.join(" AND "), params))
     }
 
+    /// 构建数组条件（`ArrayContainsAll` / `ArraySize`）的子句，按使用的参数数推进占位符索引
+    ///
+    /// 数组字段在 SQL 数据库中以 JSON 存储：PostgreSQL 为 JSONB 列，MySQL 为 JSON 列，SQLite 为 JSON 文本
+    fn array_condition_clause(&self, field: &str, condition: &QueryCondition, param_index: &mut usize) -> QuickDbResult<(String, Vec<DataValue>)> {
+        if matches!(condition.operator, QueryOperator::ArraySize) {
+            let size = condition.array_size()?;
+            let placeholder = self.get_placeholder(*param_index);
+            *param_index += 1;
+            let length_expr = match self.db_type {
+                DatabaseType::PostgreSQL => format!("jsonb_array_length({})", field),
+                DatabaseType::MySQL => format!("JSON_LENGTH({})", field),
+                _ => format!("json_array_length({})", field),
+            };
+            return Ok((format!("{} = {}", length_expr, placeholder), vec![DataValue::Int(size)]));
+        }
+
+        let elements = condition.array_elements()?;
+        if elements.is_empty() {
+            return Ok(("1 = 0".to_string(), vec![]));
+        }
+
+        let placeholder = self.get_placeholder(*param_index);
+        match self.db_type {
+            // JSONB 包含判断一次完成：数组参数绑定为 JSONB
+            DatabaseType::PostgreSQL => {
+                *param_index += 1;
+                Ok((format!("{} @> {}", field, placeholder), vec![condition.value.clone()]))
+            }
+            // 候选值为数组时，JSON_CONTAINS 要求目标包含其中每个元素
+            DatabaseType::MySQL => {
+                *param_index += 1;
+                Ok((format!("JSON_CONTAINS({}, {})", field, placeholder), vec![condition.value.clone()]))
+            }
+            _ => {
+                let mut clauses = Vec::with_capacity(elements.len());
+                for _ in elements {
+                    clauses.push(format!(
+                        "EXISTS (SELECT 1 FROM json_each({}) WHERE json_each.value = {})",
+                        field,
+                        self.get_placeholder(*param_index)
+                    ));
+                    *param_index += 1;
+                }
+                Ok((format!("({})", clauses.join(" AND ")), elements.to_vec()))
+            }
+        }
+    }
+
     /// 生成占位符
     fn generate_placeholders(&self, count: usize) -> Vec<String> {
         match self.db_type {
@@ -848,6 +917,21 @@ impl SqlQueryBuilder {
         }
     }
 
+    /// 为写入或比较字段值的占位符附加类型转换
+    ///
+    /// DECIMAL 字段的值以精确的十进制字符串传递，PostgreSQL 按 TEXT 绑定后不能直接写入或比较 NUMERIC 列，
+    /// 需转换为 `::numeric`；其他数据库和字段原样返回
+    fn typed_placeholder(&self, placeholder: String, field: &str, value: &DataValue) -> String {
+        if self.db_type == DatabaseType::PostgreSQL
+            && matches!(value, DataValue::String(_))
+            && matches!(self.get_field_type(&self.table, field), Some(crate::model::FieldType::Decimal { .. }))
+        {
+            format!("{}::numeric", placeholder)
+        } else {
+            placeholder
+        }
+    }
+
     /// 获取字段类型信息
     ///
     /// 通过查询模型元数据来确定指定字段的类型
@@ -868,6 +952,29 @@ impl SqlQueryBuilder {
         }
     }
 
+    /// 字段名是否为内嵌对象（Object/Json 字段）的子字段路径，如 `address.city`
+    fn is_json_path(&self, field_name: &str) -> bool {
+        field_name.split_once('.').is_some_and(|(column, _)| {
+            matches!(
+                self.get_field_type(&self.table, column),
+                Some(crate::model::FieldType::Object { .. }) | Some(crate::model::FieldType::Json)
+            )
+        })
+    }
+
+    /// 条件字段对应的SQL表达式
+    ///
+    /// 内嵌对象的子字段路径生成 JSON 提取表达式，其余字段返回带引号的列名
+    fn condition_field_expr(&self, condition: &QueryCondition) -> QuickDbResult<String> {
+        match condition.field.split_once('.') {
+            Some((column, path)) if self.is_json_path(&condition.field) => {
+                let safe_column = self.security_validator.get_safe_field_identifier(column)?;
+                json_path_expr(self.db_type, &safe_column, path, &condition.value)
+            }
+            _ => self.security_validator.get_safe_field_identifier(&condition.field),
+        }
+    }
+
     /// PostgreSQL UUID字段值转换
     ///
     /// 仅在PostgreSQL数据库且字段类型为UUID时使用
@@ -905,10 +1012,1954 @@ impl SqlQueryBuilder {
         // 非UUID字段或非字符串值，直接返回原值
         Ok(value.clone())
     }
+
+    /// 排序项对应的SQL表达式
+    ///
+    /// 排序字段可以是表字段，也可以是查询结果中的别名（计算字段或 `SUM(x) AS total` 形式的聚合列）。
+    /// SQLite 和 MySQL 直接引用别名；PostgreSQL 的输出列别名只能单独作为排序项，
+    /// 附加 COLLATE 时会报错，因此计算字段改为重复其表达式
+    fn order_key(&self, field: &str) -> QuickDbResult<String> {
+        if let Some((expr, alias)) = self.select_exprs.iter().find(|(_, alias)| alias == field) {
+            let key = match self.db_type {
+                DatabaseType::PostgreSQL => render_expr(self.db_type, &self.security_validator, expr)?,
+                _ => self.security_validator.get_safe_field_identifier(alias)?,
+            };
+            return Ok(match self.collate_clause() {
+                Some(collate) if self.is_string_expr(expr) => format!("{}{}", key, collate),
+                _ => key,
+            });
+        }
+        if self.fields.iter().any(|f| select_list_alias(f) == Some(field)) {
+            return self.security_validator.get_safe_field_identifier(field);
+        }
+
+        validate_sort_field(&self.table, field)?;
+        // 带表名前缀的列逐段校验并加引号，避免未校验的片段拼入 ORDER BY
+        let key = field
+            .split('.')
+            .map(|segment| self.security_validator.get_safe_field_identifier(segment))
+            .collect::<QuickDbResult<Vec<_>>>()?
+            .join(".");
+        // 排序规则只对字符串字段生效，其他类型加 COLLATE 会报错
+        let is_string_field = matches!(
+            self.get_field_type(&self.table, field),
+            Some(crate::model::FieldType::String { .. })
+        );
+        Ok(match self.collate_clause() {
+            Some(collate) if is_string_field => format!("{}{}", key, collate),
+            _ => key,
+        })
+    }
+
+    /// 计算表达式的结果是否为字符串
+    fn is_string_expr(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Column(name) => matches!(
+                self.get_field_type(&self.table, name),
+                Some(crate::model::FieldType::String { .. })
+            ),
+            Expr::Literal(value) => matches!(value, DataValue::String(_)),
+            Expr::Binary { .. } => false,
+            Expr::Function { function, args } => match function {
+                ScalarFunction::Lower | ScalarFunction::Upper | ScalarFunction::Concat => true,
+                ScalarFunction::Coalesce => args.first().is_some_and(|arg| self.is_string_expr(arg)),
+                ScalarFunction::Length | ScalarFunction::Abs | ScalarFunction::Round => false,
+            },
+        }
+    }
+
+    /// 当前排序规则对应的 COLLATE 子句（带前导空格）
+    fn collate_clause(&self) -> Option<String> {
+        self.collation.as_deref().map(|c| collate_clause(self.db_type, c))
+    }
+
+    /// 生成包含、前缀、后缀匹配的 LIKE 条件，返回子句和绑定值
+    ///
+    /// 字符串值中的 `%`、`_` 和转义字符先按字面转义再拼接通配符；
+    /// `IContains` 在 PostgreSQL 使用 `ILIKE`，其他数据库两侧取 `LOWER()` 比较
+    fn like_clause(&self, compare_field: &str, placeholder: &str, condition: &QueryCondition) -> (String, DataValue) {
+        let value = like_pattern(&condition.operator, &condition.value);
+        let clause = match (&condition.operator, self.db_type) {
+            (QueryOperator::IContains, DatabaseType::PostgreSQL) => {
+                format!("{} ILIKE {} ESCAPE '{}'", compare_field, placeholder, LIKE_ESCAPE_CHAR)
+            }
+            (QueryOperator::IContains, _) => {
+                format!("LOWER({}) LIKE LOWER({}) ESCAPE '{}'", compare_field, placeholder, LIKE_ESCAPE_CHAR)
+            }
+            _ => format!("{} LIKE {} ESCAPE '{}'", compare_field, placeholder, LIKE_ESCAPE_CHAR),
+        };
+        (clause, value)
+    }
+
+    /// 为字符串比较条件的字段附加排序规则，其他条件原样返回
+    fn collated_field(&self, field: &str, condition: &QueryCondition) -> String {
+        let is_string_comparison = matches!(condition.value, DataValue::String(_))
+            && matches!(
+                condition.operator,
+                QueryOperator::Eq
+                    | QueryOperator::Ne
+                    | QueryOperator::Gt
+                    | QueryOperator::Gte
+                    | QueryOperator::Lt
+                    | QueryOperator::Lte
+                    | QueryOperator::Contains
+                    | QueryOperator::IContains
+                    | QueryOperator::StartsWith
+                    | QueryOperator::EndsWith
+            );
+
+        match self.collate_clause() {
+            Some(collate) if is_string_comparison => format!("{}{}", field, collate),
+            _ => field.to_string(),
+        }
+    }
+}
+
+/// 生成从 JSON 列中提取子字段的表达式
+///
+/// `path` 为点分隔的子字段路径（纯数字段表示数组下标）。提取结果按比较值的类型处理：
+/// SQLite 的 `json_extract` 本身返回原生类型；MySQL 对字符串比较去掉 JSON 引号；
+/// PostgreSQL 的 `#>>` 返回文本，数值和布尔比较需要显式转换
+pub(crate) fn json_path_expr(db_type: DatabaseType, safe_column: &str, path: &str, value: &DataValue) -> QuickDbResult<String> {
+    let segments: Vec<&str> = path.split('.').collect();
+    for segment in &segments {
+        if segment.is_empty() || !segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(QuickDbError::ValidationError {
+                field: path.to_string(),
+                message: format!("JSON子字段路径包含非法片段: '{}'", segment),
+            });
+        }
+    }
+
+    // 数组条件（IN/NOT IN）按首个元素判断比较类型
+    let sample = match value {
+        DataValue::Array(values) => values.first().unwrap_or(value),
+        other => other,
+    };
+
+    match db_type {
+        DatabaseType::SQLite | DatabaseType::MySQL => {
+            let json_path = segments.iter().fold("$".to_string(), |acc, segment| {
+                if segment.chars().all(|c| c.is_ascii_digit()) {
+                    format!("{}[{}]", acc, segment)
+                } else {
+                    format!("{}.{}", acc, segment)
+                }
+            });
+            if matches!(db_type, DatabaseType::SQLite) {
+                Ok(format!("json_extract({}, '{}')", safe_column, json_path))
+            } else if matches!(sample, DataValue::Int(_) | DataValue::Float(_) | DataValue::Bool(_)) {
+                Ok(format!("JSON_EXTRACT({}, '{}')", safe_column, json_path))
+            } else {
+                Ok(format!("JSON_UNQUOTE(JSON_EXTRACT({}, '{}'))", safe_column, json_path))
+            }
+        }
+        DatabaseType::PostgreSQL => {
+            let extracted = format!("({} #>> '{{{}}}')", safe_column, segments.join(","));
+            Ok(match sample {
+                DataValue::Int(_) | DataValue::Float(_) => format!("{}::numeric", extracted),
+                DataValue::Bool(_) => format!("{}::boolean", extracted),
+                _ => extracted,
+            })
+        }
+        DatabaseType::MongoDB => Ok(format!("{}.{}", safe_column, path)),
+    }
+}
+
+/// 选择列表中 `表达式 AS 别名` 形式的列别名，去掉别名两侧的引号
+fn select_list_alias(field: &str) -> Option<&str> {
+    let upper = field.to_ascii_uppercase();
+    let pos = upper.rfind(" AS ")?;
+    let alias = field[pos + 4..].trim().trim_matches(|c| c == '"' || c == '`');
+    (!alias.is_empty()).then_some(alias)
+}
+
+/// 校验排序字段是否为模型中定义的字段
+///
+/// 查询结果中的别名由调用方先行匹配；模型未注册、`_id` 或带点的路径（内嵌字段、带表名前缀的列）不做模型校验，
+/// SQL 数据库在生成 ORDER BY 时仍会逐段校验标识符
+pub(crate) fn validate_sort_field(table: &str, field: &str) -> QuickDbResult<()> {
+    if field == "id" || field == "_id" || field.contains('.') {
+        return Ok(());
+    }
+    match crate::manager::get_model(table) {
+        Some(model_meta) if !model_meta.fields.contains_key(field) => Err(QuickDbError::ValidationError {
+            field: field.to_string(),
+            message: format!("排序字段 {} 既不是表 {} 的字段，也不是查询结果中的别名", field, table),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// 生成 COLLATE 子句（带前导空格）
+///
+/// PostgreSQL 的排序规则名区分大小写且可能包含连字符（如 `en-US-x-icu`），需要加双引号
+pub(crate) fn collate_clause(db_type: DatabaseType, collation: &str) -> String {
+    match db_type {
+        DatabaseType::PostgreSQL => format!(" COLLATE \"{}\"", collation),
+        _ => format!(" COLLATE {}", collation),
+    }
+}
+
+/// MySQL 不区分大小写唯一字段未设置排序规则时使用的排序规则
+const MYSQL_CASE_INSENSITIVE_COLLATION: &str = "utf8mb4_unicode_ci";
+
+/// 生成列定义中的 COLLATE 子句，仅字符串/文本字段且设置了排序规则时生效
+///
+/// MySQL 的索引无法单独指定排序规则，不区分大小写的唯一字段未设置排序规则时
+/// 使用 `utf8mb4_unicode_ci`，使唯一索引按不区分大小写比较
+pub(crate) fn column_collate_clause(
+    db_type: DatabaseType,
+    field_definition: &crate::model::FieldDefinition,
+) -> QuickDbResult<String> {
+    match (&field_definition.field_type, &field_definition.collation) {
+        (crate::model::FieldType::String { .. } | crate::model::FieldType::Text, Some(collation)) => {
+            validate_collation_name(collation)?;
+            Ok(collate_clause(db_type, collation))
+        }
+        (crate::model::FieldType::String { .. } | crate::model::FieldType::Text, None)
+            if db_type == DatabaseType::MySQL && field_definition.unique_case_insensitive =>
+        {
+            Ok(collate_clause(db_type, MYSQL_CASE_INSENSITIVE_COLLATION))
+        }
+        _ => Ok(String::new()),
+    }
+}
+
+/// 生成列定义中的 DEFAULT 子句（带前导空格），未设置默认值时返回空字符串
+///
+/// DDL不支持参数绑定，默认值以字面量形式写入；MySQL 的 TEXT/JSON/BLOB 列只接受
+/// 表达式默认值（8.0.13+），默认值外加括号
+pub(crate) fn column_default_clause(
+    db_type: DatabaseType,
+    field_definition: &crate::model::FieldDefinition,
+) -> QuickDbResult<String> {
+    use crate::model::{DefaultExpr, FieldType};
+
+    if let Some(expr) = &field_definition.default_expr {
+        return match (expr, &field_definition.field_type) {
+            (DefaultExpr::CurrentTimestamp, FieldType::DateTime) => Ok(" DEFAULT CURRENT_TIMESTAMP".to_string()),
+            _ => Err(QuickDbError::ValidationError {
+                field: "default_expr".to_string(),
+                message: format!("默认值表达式 {:?} 不适用于字段类型 {:?}", expr, field_definition.field_type),
+            }),
+        };
+    }
+
+    let value = match &field_definition.default {
+        None | Some(DataValue::Null) => return Ok(String::new()),
+        Some(value) => value,
+    };
+    let literal = default_literal(db_type, value).ok_or_else(|| QuickDbError::ValidationError {
+        field: "default".to_string(),
+        message: format!("列默认值不支持该类型的值: {}", value.type_name()),
+    })?;
+    let expression_only = db_type == DatabaseType::MySQL
+        && matches!(
+            field_definition.field_type,
+            FieldType::Text | FieldType::Json | FieldType::Binary | FieldType::Array { .. } | FieldType::Object { .. }
+        );
+    if expression_only {
+        Ok(format!(" DEFAULT ({})", literal))
+    } else {
+        Ok(format!(" DEFAULT {}", literal))
+    }
+}
+
+/// 将列默认值渲染为SQL字面量
+///
+/// 日期时间按各数据库适配器写入时的格式，JSON类值写为JSON文本，二进制写为十六进制字面量；
+/// MySQL 默认把反斜杠当作转义符，字符串中的反斜杠需要转义
+fn default_literal(db_type: DatabaseType, value: &DataValue) -> Option<String> {
+    match value {
+        DataValue::DateTime(dt) => {
+            let text = match db_type {
+                DatabaseType::MySQL => dt.format("%Y-%m-%d %H:%M:%S%.6f").to_string(),
+                _ => dt.to_rfc3339(),
+            };
+            default_literal(db_type, &DataValue::String(text))
+        }
+        DataValue::Uuid(uuid) => default_literal(db_type, &DataValue::String(uuid.to_string())),
+        DataValue::Json(_) | DataValue::Array(_) | DataValue::Object(_) => {
+            default_literal(db_type, &DataValue::String(value.to_json_value().to_string()))
+        }
+        DataValue::Bytes(bytes) => {
+            let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            Some(match db_type {
+                DatabaseType::PostgreSQL => format!("'\\x{}'::bytea", hex),
+                _ => format!("X'{}'", hex),
+            })
+        }
+        other => sql_literal(db_type, other),
+    }
+}
+
+/// 验证排序规则名，排序规则会直接拼接进SQL，只允许字母、数字、下划线、连字符和点
+pub(crate) fn validate_collation_name(collation: &str) -> QuickDbResult<()> {
+    let valid = !collation.is_empty()
+        && collation
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(QuickDbError::ValidationError {
+            field: "collation".to_string(),
+            message: format!("无效的排序规则名: {}", collation),
+        })
+    }
+}
+
+/// `IN` / `NOT IN` 条件绑定的参数，与 [`SqlQueryBuilder::in_list_clause`] 的占位符一一对应
+fn in_list_values(condition: &QueryCondition) -> Vec<DataValue> {
+    match &condition.value {
+        DataValue::Array(values) => values.clone(),
+        _ => Vec::new(),
+    }
+}
+
+/// 获取考虑空值语义的操作符
+///
+/// SQL中 `= NULL` / `!= NULL` 永远不成立，Eq/Ne 搭配 Null 值时分别改写为 IsNull/IsNotNull
+pub(crate) fn null_aware_operator(condition: &QueryCondition) -> QueryOperator {
+    match (&condition.operator, &condition.value) {
+        (QueryOperator::Eq, DataValue::Null) => QueryOperator::IsNull,
+        (QueryOperator::Ne, DataValue::Null) => QueryOperator::IsNotNull,
+        (operator, _) => operator.clone(),
+    }
+}
+
+/// 改写原始条件片段中的参数引用，返回加括号的子句和按占位符顺序排列的参数，并按使用的参数数推进占位符索引
+///
+/// PostgreSQL 把 `$n` 改为整条语句中的 `$(起始索引 + n - 1)`，参数按原顺序追加；
+/// SQLite 和 MySQL 的 `?` 只能按出现顺序绑定，因此按引用顺序展开参数，同一参数被引用多次时重复绑定。
+/// 单引号字符串和带引号的标识符中的内容保持原样
+pub(crate) fn raw_condition_clause(
+    db_type: DatabaseType,
+    condition: &QueryCondition,
+    param_index: &mut usize,
+) -> QuickDbResult<(String, Vec<DataValue>)> {
+    let invalid = |message: String| QuickDbError::ValidationError {
+        field: "raw_condition".to_string(),
+        message,
+    };
+    if db_type == DatabaseType::MongoDB {
+        return Err(QuickDbError::UnsupportedDatabase {
+            db_type: "MongoDB 不支持原始SQL条件".to_string(),
+        });
+    }
+    let fragment = condition.field.trim();
+    if fragment.is_empty() {
+        return Err(invalid("原始条件片段不能为空".to_string()));
+    }
+    let DataValue::Array(params) = &condition.value else {
+        return Err(invalid("原始条件的参数必须是数组".to_string()));
+    };
+
+    let mut clause = String::with_capacity(fragment.len() + 2);
+    let mut bound = Vec::new();
+    let mut referenced = vec![false; params.len()];
+    let mut quote: Option<char> = None;
+    let mut chars = fragment.chars().peekable();
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            clause.push(c);
+            if c == '\\' && q == '\'' && db_type == DatabaseType::MySQL {
+                // MySQL 字符串中的反斜杠转义下一个字符
+                if let Some(escaped) = chars.next() {
+                    clause.push(escaped);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' | '`' => {
+                quote = Some(c);
+                clause.push(c);
+            }
+            '?' if db_type != DatabaseType::PostgreSQL => {
+                return Err(invalid("原始条件片段请用 $1、$2 ... 引用参数，不能使用 ?".to_string()));
+            }
+            '$' if chars.peek().is_some_and(|next| next.is_ascii_digit()) => {
+                let mut digits = String::new();
+                while let Some(digit) = chars.peek().filter(|next| next.is_ascii_digit()) {
+                    digits.push(*digit);
+                    chars.next();
+                }
+                let position = digits.parse::<usize>().unwrap_or(0);
+                if position == 0 || position > params.len() {
+                    return Err(invalid(format!("原始条件片段引用了 ${}，但只提供了 {} 个参数", digits, params.len())));
+                }
+                referenced[position - 1] = true;
+                if db_type == DatabaseType::PostgreSQL {
+                    clause.push_str(&format!("${}", *param_index + position - 1));
+                } else {
+                    clause.push('?');
+                    bound.push(params[position - 1].clone());
+                }
+            }
+            _ => clause.push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(invalid("原始条件片段中的引号未闭合".to_string()));
+    }
+    if let Some(unused) = referenced.iter().position(|used| !used) {
+        return Err(invalid(format!("原始条件的参数 ${} 未在片段中引用", unused + 1)));
+    }
+
+    if db_type == DatabaseType::PostgreSQL {
+        bound = params.clone();
+    }
+    *param_index += bound.len();
+    Ok((format!("({})", clause), bound))
+}
+
+/// 把包含多条语句的SQL脚本按 `;` 拆分为单条语句
+///
+/// 字符串、带引号的标识符、`--` 行注释和 `/* */` 块注释中的分号不会拆分；
+/// PostgreSQL 额外识别 `$tag$ ... $tag$` 美元符号引用（函数体），MySQL 额外识别 `#` 行注释和字符串中的反斜杠转义，
+/// SQLite 的 `CREATE TRIGGER ... BEGIN ... END` 作为一条语句。只有空白和注释的片段会被丢弃
+pub(crate) fn split_sql_statements(db_type: DatabaseType, sql: &str) -> QuickDbResult<Vec<String>> {
+    let invalid = |message: &str| QuickDbError::ValidationError {
+        field: "sql".to_string(),
+        message: message.to_string(),
+    };
+    if db_type == DatabaseType::MongoDB {
+        return Err(QuickDbError::UnsupportedDatabase {
+            db_type: "MongoDB 不支持SQL脚本".to_string(),
+        });
+    }
+
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    // 当前语句中注释和空白之外的内容，用于丢弃空语句和识别触发器
+    let mut content = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\'' | '"' | '`' => {
+                let start = i;
+                i += 1;
+                loop {
+                    let Some(&q) = chars.get(i) else {
+                        return Err(invalid("SQL脚本中的引号未闭合"));
+                    };
+                    if q == '\\' && db_type == DatabaseType::MySQL && c != '`' {
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    if q == c {
+                        // 连续两个引号表示转义
+                        if chars.get(i) == Some(&c) {
+                            i += 1;
+                            continue;
+                        }
+                        break;
+                    }
+                }
+                let quoted: String = chars[start..i].iter().collect();
+                current.push_str(&quoted);
+                content.push_str(&quoted);
+                continue;
+            }
+            '[' if db_type == DatabaseType::SQLite => {
+                let Some(end) = chars[i..].iter().position(|&ch| ch == ']') else {
+                    return Err(invalid("SQL脚本中的方括号标识符未闭合"));
+                };
+                let quoted: String = chars[i..=i + end].iter().collect();
+                current.push_str(&quoted);
+                content.push_str(&quoted);
+                i += end + 1;
+                continue;
+            }
+            '-' if next == Some('-') => {
+                let end = chars[i..].iter().position(|&ch| ch == '\n').map_or(chars.len(), |p| i + p);
+                current.extend(&chars[i..end]);
+                i = end;
+                continue;
+            }
+            '#' if db_type == DatabaseType::MySQL => {
+                let end = chars[i..].iter().position(|&ch| ch == '\n').map_or(chars.len(), |p| i + p);
+                current.extend(&chars[i..end]);
+                i = end;
+                continue;
+            }
+            '/' if next == Some('*') => {
+                // PostgreSQL 的块注释可以嵌套
+                let start = i;
+                let mut depth = 0;
+                loop {
+                    match (chars.get(i), chars.get(i + 1)) {
+                        (Some('/'), Some('*')) if depth == 0 || db_type == DatabaseType::PostgreSQL => {
+                            depth += 1;
+                            i += 2;
+                        }
+                        (Some('*'), Some('/')) => {
+                            depth -= 1;
+                            i += 2;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        (Some(_), _) => i += 1,
+                        (None, _) => return Err(invalid("SQL脚本中的块注释未闭合")),
+                    }
+                }
+                current.extend(&chars[start..i]);
+                continue;
+            }
+            '$' if db_type == DatabaseType::PostgreSQL && !is_identifier_char(current.chars().last()) => {
+                let tag_len = chars[i + 1..].iter().take_while(|ch| ch.is_alphanumeric() || **ch == '_').count();
+                let tag_starts_with_digit = chars.get(i + 1).is_some_and(|ch| ch.is_ascii_digit());
+                if chars.get(i + 1 + tag_len) == Some(&'$') && !tag_starts_with_digit {
+                    let tag: String = chars[i..i + tag_len + 2].iter().collect();
+                    let body_start = i + tag.chars().count();
+                    let rest: String = chars[body_start..].iter().collect();
+                    let Some(end) = rest.find(&tag) else {
+                        return Err(invalid("SQL脚本中的美元符号引用未闭合"));
+                    };
+                    let end = body_start + rest[..end].chars().count() + tag.chars().count();
+                    let quoted: String = chars[i..end].iter().collect();
+                    current.push_str(&quoted);
+                    content.push_str(&quoted);
+                    i = end;
+                    continue;
+                }
+            }
+            ';' if !(db_type == DatabaseType::SQLite && is_open_trigger(&content)) => {
+                if !content.trim().is_empty() {
+                    statements.push(current.trim().to_string());
+                }
+                current.clear();
+                content.clear();
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+        content.push(c);
+        i += 1;
+    }
+    if !content.trim().is_empty() {
+        statements.push(current.trim().to_string());
+    }
+    Ok(statements)
+}
+
+/// 字符是否可以出现在标识符中（用于区分 `$tag$` 和标识符中的 `$`）
+fn is_identifier_char(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// 语句是否为尚未以 `END` 结束的 SQLite 触发器定义，触发器体中的分号不拆分语句
+fn is_open_trigger(content: &str) -> bool {
+    let words: Vec<String> = content
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+        .take(3)
+        .map(|w| w.to_ascii_uppercase())
+        .collect();
+    let is_trigger = match words.as_slice() {
+        [create, trigger, ..] if create == "CREATE" && trigger == "TRIGGER" => true,
+        [create, temp, trigger] => create == "CREATE" && (temp == "TEMP" || temp == "TEMPORARY") && trigger == "TRIGGER",
+        _ => false,
+    };
+    let ends_with_end = content
+        .trim_end()
+        .rsplit(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .next()
+        .is_some_and(|w| w.eq_ignore_ascii_case("END"));
+    is_trigger && !ends_with_end
+}
+
+/// 生成索引列列表，按索引定义附加 DESC
+///
+/// 不区分大小写的索引在 PostgreSQL 中索引 `lower(字段)`，在 SQLite 中附加 `COLLATE NOCASE`；
+/// MySQL 的索引跟随列的排序规则，列保持原样
+pub(crate) fn index_columns_clause(
+    db_type: DatabaseType,
+    index: &crate::model::IndexDefinition,
+) -> QuickDbResult<String> {
+    index.validate()?;
+    let columns: Vec<String> = index.fields.iter().enumerate()
+        .map(|(i, field)| {
+            let column = match (index.case_insensitive, db_type) {
+                (true, DatabaseType::PostgreSQL) => format!("lower({})", field),
+                (true, DatabaseType::SQLite) => format!("{} COLLATE NOCASE", field),
+                _ => field.clone(),
+            };
+            match index.direction_at(i) {
+                SortDirection::Asc => column,
+                SortDirection::Desc => format!("{} DESC", column),
+            }
+        })
+        .collect();
+    Ok(columns.join(", "))
+}
+
+/// 生成部分索引的 WHERE 子句（带前导空格），无条件时返回空字符串
+///
+/// 索引DDL不支持参数绑定，条件值以字面量形式写入，仅支持比较、IN 和空值判断
+pub(crate) fn index_predicate_clause(
+    db_type: DatabaseType,
+    conditions: &[QueryCondition],
+) -> QuickDbResult<String> {
+    if conditions.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut predicates = Vec::with_capacity(conditions.len());
+    for condition in conditions {
+        let field = &condition.field;
+        let predicate = match null_aware_operator(condition) {
+            QueryOperator::IsNull => format!("{} IS NULL", field),
+            QueryOperator::IsNotNull => format!("{} IS NOT NULL", field),
+            QueryOperator::In | QueryOperator::NotIn => {
+                let values = match &condition.value {
+                    DataValue::Array(values) => values.iter()
+                        .map(|v| index_literal(db_type, field, v))
+                        .collect::<QuickDbResult<Vec<_>>>()?,
+                    other => vec![index_literal(db_type, field, other)?],
+                };
+                let keyword = if matches!(condition.operator, QueryOperator::In) { "IN" } else { "NOT IN" };
+                format!("{} {} ({})", field, keyword, values.join(", "))
+            }
+            ref op => {
+                let symbol = match op {
+                    QueryOperator::Eq => "=",
+                    QueryOperator::Ne => "<>",
+                    QueryOperator::Gt => ">",
+                    QueryOperator::Gte => ">=",
+                    QueryOperator::Lt => "<",
+                    QueryOperator::Lte => "<=",
+                    _ => {
+                        return Err(QuickDbError::ValidationError {
+                            field: field.clone(),
+                            message: format!("部分索引条件不支持操作符 {:?}", op),
+                        });
+                    }
+                };
+                format!("{} {} {}", field, symbol, index_literal(db_type, field, &condition.value)?)
+            }
+        };
+        predicates.push(predicate);
+    }
+
+    Ok(format!(" WHERE {}", predicates.join(" AND ")))
+}
+
+/// 将值转换为索引条件中的SQL字面量
+fn index_literal(db_type: DatabaseType, field: &str, value: &DataValue) -> QuickDbResult<String> {
+    sql_literal(db_type, value).ok_or_else(|| QuickDbError::ValidationError {
+        field: field.to_string(),
+        message: format!("部分索引条件不支持该类型的值: {}", value.type_name()),
+    })
+}
+
+/// 将标量值渲染为内联SQL字面量，字符串中的单引号会被转义；不支持的类型返回 `None`
+///
+/// MySQL 默认把反斜杠当作转义字符，`\'` 会转义掉结束引号，因此还需要转义反斜杠
+fn sql_literal(db_type: DatabaseType, value: &DataValue) -> Option<String> {
+    match value {
+        DataValue::Null => Some("NULL".to_string()),
+        DataValue::Bool(b) => Some(match db_type {
+            DatabaseType::PostgreSQL => if *b { "TRUE" } else { "FALSE" }.to_string(),
+            _ => if *b { "1" } else { "0" }.to_string(),
+        }),
+        DataValue::Int(i) => Some(i.to_string()),
+        DataValue::Float(f) if f.is_finite() => Some(format!("{:?}", f)),
+        DataValue::String(s) if db_type == DatabaseType::MySQL => {
+            Some(format!("'{}'", s.replace('\\', "\\\\").replace('\'', "''")))
+        }
+        DataValue::String(s) => Some(format!("'{}'", s.replace('\'', "''"))),
+        _ => None,
+    }
+}
+
+/// 将计算表达式渲染为SQL
+///
+/// 字段名经过安全校验并加引号，字面量内联渲染（不占用参数占位符，避免与WHERE参数编号冲突）
+pub(crate) fn render_expr(db_type: DatabaseType, validator: &DatabaseSecurityValidator, expr: &Expr) -> QuickDbResult<String> {
+    match expr {
+        Expr::Column(name) => validator.get_safe_field_identifier(name),
+        Expr::Literal(value) => sql_literal(db_type, value).ok_or_else(|| QuickDbError::ValidationError {
+            field: "select_expr".to_string(),
+            message: format!("计算表达式不支持该类型的字面量: {}", value.type_name()),
+        }),
+        Expr::Binary { left, op, right } => {
+            let op = match op {
+                BinaryOperator::Add => "+",
+                BinaryOperator::Sub => "-",
+                BinaryOperator::Mul => "*",
+                BinaryOperator::Div => "/",
+                BinaryOperator::Mod => "%",
+            };
+            Ok(format!(
+                "({} {} {})",
+                render_expr(db_type, validator, left)?,
+                op,
+                render_expr(db_type, validator, right)?
+            ))
+        }
+        Expr::Function { function, args } => {
+            expr.validate()?;
+            let rendered = args
+                .iter()
+                .map(|arg| render_expr(db_type, validator, arg))
+                .collect::<QuickDbResult<Vec<_>>>()?;
+            let name = match (function, db_type) {
+                // SQLite 没有 CONCAT，使用 || 拼接
+                (ScalarFunction::Concat, DatabaseType::SQLite) => {
+                    return Ok(format!("({})", rendered.join(" || ")));
+                }
+                (ScalarFunction::Concat, _) => "CONCAT",
+                // MySQL 的 LENGTH 按字节计算
+                (ScalarFunction::Length, DatabaseType::MySQL) => "CHAR_LENGTH",
+                // PostgreSQL 只有 round(numeric, int)，浮点数需先转换
+                (ScalarFunction::Round, DatabaseType::PostgreSQL) if rendered.len() == 2 => {
+                    return Ok(format!("ROUND(CAST({} AS NUMERIC), {})", rendered[0], rendered[1]));
+                }
+                (ScalarFunction::Length, _) => "LENGTH",
+                (ScalarFunction::Lower, _) => "LOWER",
+                (ScalarFunction::Upper, _) => "UPPER",
+                (ScalarFunction::Abs, _) => "ABS",
+                (ScalarFunction::Round, _) => "ROUND",
+                (ScalarFunction::Coalesce, _) => "COALESCE",
+            };
+            Ok(format!("{}({})", name, rendered.join(", ")))
+        }
+    }
 }
 
 impl Default for SqlQueryBuilder {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// 生成读取二进制列片段的查询语句
+///
+/// 参数依次为起始位置（从1开始）、长度和记录ID；结果只有一列，列为 NULL 时结果为 NULL
+pub(crate) fn blob_chunk_select_sql(db_type: DatabaseType, table: &str, column: &str) -> QuickDbResult<String> {
+    let validator = DatabaseSecurityValidator::new(db_type);
+    let table = validator.get_safe_table_identifier(table)?;
+    let column = validator.get_safe_field_identifier(column)?;
+    let id = validator.get_safe_field_identifier("id")?;
+    match db_type {
+        DatabaseType::SQLite => Ok(format!("SELECT substr({}, ?, ?) FROM {} WHERE {} = ?", column, table, id)),
+        DatabaseType::PostgreSQL => Ok(format!(
+            "SELECT substring({} from $1::integer for $2::integer) FROM {} WHERE {} = $3",
+            column, table, id
+        )),
+        DatabaseType::MySQL => Ok(format!("SELECT SUBSTRING({}, ?, ?) FROM {} WHERE {} = ?", column, table, id)),
+        DatabaseType::MongoDB => Err(QuickDbError::UnsupportedDatabase {
+            db_type: "MongoDB 不使用SQL读取二进制片段".to_string(),
+        }),
+    }
+}
+
+/// 生成写入二进制列片段的更新语句
+///
+/// 参数依次为数据和记录ID；`append` 为 `true` 时把数据追加到现有值之后（NULL 视为空），否则覆盖
+pub(crate) fn blob_chunk_update_sql(db_type: DatabaseType, table: &str, column: &str, append: bool) -> QuickDbResult<String> {
+    let validator = DatabaseSecurityValidator::new(db_type);
+    let table = validator.get_safe_table_identifier(table)?;
+    let column = validator.get_safe_field_identifier(column)?;
+    let id = validator.get_safe_field_identifier("id")?;
+    let value = match (db_type, append) {
+        (DatabaseType::PostgreSQL, false) => "$1".to_string(),
+        (DatabaseType::PostgreSQL, true) => format!("COALESCE({}, ''::bytea) || $1", column),
+        (DatabaseType::SQLite, true) => format!("CAST(COALESCE({}, X'') || ? AS BLOB)", column),
+        (DatabaseType::MySQL, true) => format!("CONCAT(COALESCE({}, ''), ?)", column),
+        (DatabaseType::MongoDB, _) => {
+            return Err(QuickDbError::UnsupportedDatabase {
+                db_type: "MongoDB 不使用SQL写入二进制片段".to_string(),
+            })
+        }
+        _ => "?".to_string(),
+    };
+    let id_placeholder = if db_type == DatabaseType::PostgreSQL { "$2" } else { "?" };
+    Ok(format!("UPDATE {} SET {} = {} WHERE {} = {}", table, column, value, id, id_placeholder))
+}
+
+/// 生成按时间段计数的查询语句
+///
+/// 时间字段按UTC截断到 `interval` 的起点，结果列 `bucket` 为 `YYYY-MM-DDTHH:MM:SSZ` 格式的字符串，
+/// `count` 为该时间段的记录数，按时间段升序排列；时间字段为空的记录不计入
+pub(crate) fn time_bucket_sql(
+    db_type: DatabaseType,
+    table: &str,
+    field: &str,
+    interval: Interval,
+    conditions: &[QueryCondition],
+) -> QuickDbResult<(String, Vec<DataValue>)> {
+    let column = DatabaseSecurityValidator::new(db_type).get_safe_field_identifier(field)?;
+    let bucket = match db_type {
+        // SQLite 以 RFC 3339 文本存储时间，strftime 会先换算到UTC；'weekday 0' 前进到周日，再退6天即为周一
+        DatabaseType::SQLite => match interval {
+            Interval::Minute => format!("strftime('%Y-%m-%dT%H:%M:00Z', {})", column),
+            Interval::Hour => format!("strftime('%Y-%m-%dT%H:00:00Z', {})", column),
+            Interval::Day => format!("strftime('%Y-%m-%dT00:00:00Z', {})", column),
+            Interval::Week => format!("strftime('%Y-%m-%dT00:00:00Z', {}, 'weekday 0', '-6 days')", column),
+            Interval::Month => format!("strftime('%Y-%m-01T00:00:00Z', {})", column),
+        },
+        DatabaseType::PostgreSQL => {
+            let unit = match interval {
+                Interval::Minute => "minute",
+                Interval::Hour => "hour",
+                Interval::Day => "day",
+                Interval::Week => "week",
+                Interval::Month => "month",
+            };
+            format!(
+                "to_char(date_trunc('{}', {} AT TIME ZONE 'UTC'), 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"')",
+                unit, column
+            )
+        }
+        // MySQL 的 DATETIME 列存储写入的UTC时间，不做时区换算
+        DatabaseType::MySQL => match interval {
+            Interval::Minute => format!("DATE_FORMAT({}, '%Y-%m-%dT%H:%i:00Z')", column),
+            Interval::Hour => format!("DATE_FORMAT({}, '%Y-%m-%dT%H:00:00Z')", column),
+            Interval::Day => format!("DATE_FORMAT({}, '%Y-%m-%dT00:00:00Z')", column),
+            Interval::Week => format!(
+                "DATE_FORMAT(DATE_SUB({0}, INTERVAL WEEKDAY({0}) DAY), '%Y-%m-%dT00:00:00Z')",
+                column
+            ),
+            Interval::Month => format!("DATE_FORMAT({}, '%Y-%m-01T00:00:00Z')", column),
+        },
+        DatabaseType::MongoDB => return Err(QuickDbError::UnsupportedDatabase {
+            db_type: "MongoDB 不使用SQL按时间段统计".to_string(),
+        }),
+    };
+
+    let mut conditions = conditions.to_vec();
+    conditions.push(QueryCondition {
+        field: field.to_string(),
+        operator: QueryOperator::IsNotNull,
+        value: DataValue::Null,
+    });
+    let bucket_column = format!("{} AS bucket", bucket);
+    let (sql, params) = SqlQueryBuilder::new()
+        .database_type(db_type)
+        .select(&[bucket_column.as_str(), "COUNT(*) AS count"])
+        .from(table)
+        .where_conditions(&conditions)
+        .group_by(&["bucket"])
+        .build()?;
+    Ok((format!("{} ORDER BY bucket", sql), params))
+}
+
+/// 生成聚合查询语句
+///
+/// 结果列依次为分组字段和各聚合项的结果字段，存在分组字段时按分组字段升序排列
+pub(crate) fn aggregate_sql(
+    db_type: DatabaseType,
+    table: &str,
+    aggregations: &[Aggregation],
+    conditions: &[QueryCondition],
+    group_by: &[String],
+) -> QuickDbResult<(String, Vec<DataValue>)> {
+    Aggregation::validate(aggregations, group_by)?;
+    let validator = DatabaseSecurityValidator::new(db_type);
+    let group_columns = group_by.iter()
+        .map(|field| validator.get_safe_field_identifier(field))
+        .collect::<QuickDbResult<Vec<_>>>()?;
+
+    let mut columns = group_columns.clone();
+    for aggregation in aggregations {
+        let func = match aggregation.func {
+            AggFunc::Count => "COUNT",
+            AggFunc::Sum => "SUM",
+            AggFunc::Avg => "AVG",
+            AggFunc::Min => "MIN",
+            AggFunc::Max => "MAX",
+        };
+        let argument = if aggregation.field == "*" {
+            "*".to_string()
+        } else {
+            validator.get_safe_field_identifier(&aggregation.field)?
+        };
+        let alias = validator.get_safe_field_identifier(&aggregation.alias)?;
+        columns.push(format!("{}({}) AS {}", func, argument, alias));
+    }
+
+    let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+    let group_columns: Vec<&str> = group_columns.iter().map(String::as_str).collect();
+    let (sql, params) = SqlQueryBuilder::new()
+        .database_type(db_type)
+        .select(&columns)
+        .from(table)
+        .where_conditions(conditions)
+        .group_by(&group_columns)
+        .build()?;
+    if group_columns.is_empty() {
+        Ok((sql, params))
+    } else {
+        Ok((format!("{} ORDER BY {}", sql, group_columns.join(", ")), params))
+    }
+}
+
+/// 解析按时间段计数的查询结果，时间段为 `DataValue::DateTime`
+pub(crate) fn time_bucket_rows(rows: Vec<DataValue>) -> QuickDbResult<Vec<(DataValue, u64)>> {
+    rows.into_iter()
+        .map(|row| {
+            let DataValue::Object(mut row) = row else {
+                return Err(QuickDbError::QueryError {
+                    message: "按时间段统计的结果行格式错误".to_string(),
+                });
+            };
+            let bucket = match row.remove("bucket") {
+                Some(DataValue::DateTime(dt)) => dt,
+                Some(DataValue::String(s)) => chrono::DateTime::parse_from_rfc3339(&s)
+                    .map(|dt| dt.with_timezone(&chrono::Utc))
+                    .map_err(|e| QuickDbError::QueryError {
+                        message: format!("无法解析时间段 {}: {}", s, e),
+                    })?,
+                other => return Err(QuickDbError::QueryError {
+                    message: format!("按时间段统计的时间段格式错误: {:?}", other),
+                }),
+            };
+            let count = match row.remove("count") {
+                Some(DataValue::Int(count)) => count.max(0) as u64,
+                other => return Err(QuickDbError::QueryError {
+                    message: format!("按时间段统计的计数格式错误: {:?}", other),
+                }),
+            };
+            Ok((DataValue::DateTime(bucket), count))
+        })
+        .collect()
+}
+
+/// 生成重命名表的语句，三种SQL数据库都支持 `ALTER TABLE ... RENAME TO ...`
+pub(crate) fn rename_table_sql(db_type: DatabaseType, from: &str, to: &str) -> QuickDbResult<String> {
+    if db_type == DatabaseType::MongoDB {
+        return Err(QuickDbError::UnsupportedDatabase {
+            db_type: "MongoDB 使用 renameCollection 重命名集合".to_string(),
+        });
+    }
+    let validator = DatabaseSecurityValidator::new(db_type);
+    let from = validator.get_safe_table_identifier(from)?;
+    let to = validator.get_safe_table_identifier(to)?;
+    Ok(format!("ALTER TABLE {} RENAME TO {}", from, to))
+}
+
+/// 整理批量插入或更新的列名
+///
+/// 所有记录的字段集合必须一致，冲突字段必须是记录中的字段；返回排序后的列名，保证生成的语句稳定
+pub(crate) fn upsert_columns(rows: &[HashMap<String, DataValue>], conflict_fields: &[String]) -> QuickDbResult<Vec<String>> {
+    let first = rows.first().ok_or_else(|| QuickDbError::ValidationError {
+        field: "rows".to_string(),
+        message: "批量插入或更新的记录不能为空".to_string(),
+    })?;
+    let mut columns: Vec<String> = first.keys().cloned().collect();
+    columns.sort();
+
+    if let Some((index, _)) = rows.iter().enumerate()
+        .find(|(_, row)| row.len() != columns.len() || columns.iter().any(|column| !row.contains_key(column)))
+    {
+        return Err(QuickDbError::ValidationError {
+            field: "rows".to_string(),
+            message: format!("第 {} 条记录的字段与第一条记录不一致，批量插入或更新要求所有记录字段相同", index + 1),
+        });
+    }
+    if let Some(field) = conflict_fields.iter().find(|field| !columns.contains(field)) {
+        return Err(QuickDbError::ValidationError {
+            field: field.clone(),
+            message: format!("冲突字段 '{}' 不在记录中", field),
+        });
+    }
+    Ok(columns)
+}
+
+/// 单条批量插入或更新语句最多容纳的记录数，按各数据库绑定参数数量的上限计算
+pub(crate) fn upsert_chunk_size(db_type: DatabaseType, column_count: usize) -> usize {
+    let max_params = match db_type {
+        DatabaseType::SQLite => 32766,
+        _ => 65535,
+    };
+    (max_params / column_count.max(1)).max(1)
+}
+
+/// 生成多行插入或更新语句
+///
+/// 冲突时更新除冲突字段和ID以外的所有列；没有可更新的列时把第一个冲突字段写回原值，
+/// 使已存在的记录仍计入影响行数（PostgreSQL 也仍会返回该行）。
+/// PostgreSQL 附加 `RETURNING (xmax = 0)`，逐行标记记录是否为新插入；
+/// MySQL 的 `ON DUPLICATE KEY UPDATE` 对表上任一唯一索引生效，不使用冲突字段
+pub(crate) fn upsert_many_sql(
+    db_type: DatabaseType,
+    table: &str,
+    columns: &[String],
+    conflict_fields: &[String],
+    row_count: usize,
+) -> QuickDbResult<String> {
+    if db_type == DatabaseType::MongoDB {
+        return Err(QuickDbError::UnsupportedDatabase {
+            db_type: "MongoDB 使用 update 命令批量执行 upsert".to_string(),
+        });
+    }
+    let validator = DatabaseSecurityValidator::new(db_type);
+    let safe_table = validator.get_safe_table_identifier(table)?;
+    let safe_columns = columns.iter()
+        .map(|column| validator.get_safe_field_identifier(column))
+        .collect::<QuickDbResult<Vec<_>>>()?;
+    let safe_conflicts = conflict_fields.iter()
+        .map(|field| validator.get_safe_field_identifier(field))
+        .collect::<QuickDbResult<Vec<_>>>()?;
+
+    let mut update_columns: Vec<&String> = columns.iter().zip(&safe_columns)
+        .filter(|(column, _)| !conflict_fields.contains(column) && column.as_str() != "id")
+        .map(|(_, safe)| safe)
+        .collect();
+    if update_columns.is_empty() {
+        update_columns.push(&safe_conflicts[0]);
+    }
+
+    let mut sql = format!(
+        "INSERT INTO {} ({}) VALUES {}",
+        safe_table,
+        safe_columns.join(", "),
+        values_placeholders(db_type, columns.len(), row_count)
+    );
+    if db_type == DatabaseType::MySQL {
+        let assignments: Vec<String> = update_columns.iter()
+            .map(|column| format!("{} = VALUES({})", column, column))
+            .collect();
+        sql.push_str(&format!(" ON DUPLICATE KEY UPDATE {}", assignments.join(", ")));
+    } else {
+        let assignments: Vec<String> = update_columns.iter()
+            .map(|column| format!("{} = excluded.{}", column, column))
+            .collect();
+        sql.push_str(&format!(" ON CONFLICT ({}) DO UPDATE SET {}", safe_conflicts.join(", "), assignments.join(", ")));
+    }
+    if db_type == DatabaseType::PostgreSQL {
+        sql.push_str(" RETURNING (xmax = 0) AS \"inserted\"");
+    }
+    Ok(sql)
+}
+
+/// LIKE 模式使用的转义字符
+///
+/// 不使用反斜杠：MySQL 字符串字面量中的反斜杠本身需要转义，且受 `NO_BACKSLASH_ESCAPES` 影响
+const LIKE_ESCAPE_CHAR: char = '!';
+
+/// 转义 LIKE 元字符（`%`、`_` 和转义字符本身），使值按字面匹配
+pub(crate) fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_') || c == LIKE_ESCAPE_CHAR {
+            escaped.push(LIKE_ESCAPE_CHAR);
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 包含、前缀、后缀匹配条件实际绑定的 LIKE 模式，非字符串值原样返回
+pub(crate) fn like_pattern(operator: &QueryOperator, value: &DataValue) -> DataValue {
+    match value {
+        DataValue::String(s) => {
+            let escaped = escape_like(s);
+            DataValue::String(match operator {
+                QueryOperator::StartsWith => format!("{}%", escaped),
+                QueryOperator::EndsWith => format!("%{}", escaped),
+                _ => format!("%{}%", escaped),
+            })
+        }
+        other => other.clone(),
+    }
+}
+
+/// 生成检查冲突字段上是否已有记录的查询，参数按 `conflict_fields` 的顺序绑定
+///
+/// `lock` 为真时附加 `FOR UPDATE`，在事务中锁住该键（不存在时锁住间隙），防止并发插入
+pub(crate) fn upsert_exists_sql(
+    db_type: DatabaseType,
+    table: &str,
+    conflict_fields: &[String],
+    lock: bool,
+) -> QuickDbResult<String> {
+    let validator = DatabaseSecurityValidator::new(db_type);
+    let safe_table = validator.get_safe_table_identifier(table)?;
+    let predicates = conflict_fields.iter().enumerate()
+        .map(|(index, field)| {
+            let placeholder = match db_type {
+                DatabaseType::PostgreSQL => format!("${}", index + 1),
+                _ => "?".to_string(),
+            };
+            Ok(format!("{} = {}", validator.get_safe_field_identifier(field)?, placeholder))
+        })
+        .collect::<QuickDbResult<Vec<_>>>()?;
+
+    let mut sql = format!("SELECT 1 FROM {} WHERE {} LIMIT 1", safe_table, predicates.join(" AND "));
+    if lock {
+        sql.push_str(" FOR UPDATE");
+    }
+    Ok(sql)
+}
+
+/// 把冲突列上缺少唯一约束的数据库错误转换为说明原因的验证错误，其他错误原样返回
+pub(crate) fn upsert_conflict_error(table: &str, conflict_fields: &[String], error: QuickDbError) -> QuickDbError {
+    let message = error.to_string();
+    // SQLite: "ON CONFLICT clause does not match any PRIMARY KEY or UNIQUE constraint"
+    // PostgreSQL: "there is no unique or exclusion constraint matching the ON CONFLICT specification"
+    if message.contains("does not match any PRIMARY KEY or UNIQUE constraint")
+        || message.contains("no unique or exclusion constraint matching the ON CONFLICT")
+    {
+        return missing_unique_index_error(table, conflict_fields);
+    }
+    error
+}
+
+/// 冲突字段上没有唯一索引或主键的错误
+pub(crate) fn missing_unique_index_error(table: &str, conflict_fields: &[String]) -> QuickDbError {
+    QuickDbError::ValidationError {
+        field: "conflict_fields".to_string(),
+        message: format!(
+            "表 {} 的冲突字段 ({}) 上没有唯一索引或主键，无法判断记录是否已存在",
+            table,
+            conflict_fields.join(", ")
+        ),
+    }
+}
+
+/// 生成多行 `VALUES` 的占位符列表，如 `(?, ?), (?, ?)`
+fn values_placeholders(db_type: DatabaseType, column_count: usize, row_count: usize) -> String {
+    let mut index = 0;
+    let rows: Vec<String> = (0..row_count)
+        .map(|_| {
+            let placeholders: Vec<String> = (0..column_count)
+                .map(|_| {
+                    index += 1;
+                    match db_type {
+                        DatabaseType::PostgreSQL => format!("${}", index),
+                        _ => "?".to_string(),
+                    }
+                })
+                .collect();
+            format!("({})", placeholders.join(", "))
+        })
+        .collect();
+    rows.join(", ")
+}
+
+/// 单条多行插入语句最多容纳的记录数
+const INSERT_MANY_MAX_ROWS: usize = 1000;
+
+/// 单条插入写入的列：与单条插入一样省略值为NULL的字段，让数据库使用列默认值
+fn insert_columns(row: &HashMap<String, DataValue>) -> Vec<String> {
+    let mut columns: Vec<String> = row.iter()
+        .filter(|(_, value)| !matches!(value, DataValue::Null))
+        .map(|(column, _)| column.clone())
+        .collect();
+    columns.sort();
+    columns
+}
+
+/// 把多行插入的记录拆分为若干批，每批对应一条多行插入语句
+///
+/// 相邻且写入列相同的记录合为一批，批内记录数不超过 1000，也不超过数据库绑定参数数量的上限；
+/// 返回每批的列名和记录，顺序与输入一致
+pub(crate) fn insert_many_batches(
+    db_type: DatabaseType,
+    rows: &[HashMap<String, DataValue>],
+) -> Vec<(Vec<String>, &[HashMap<String, DataValue>])> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    while start < rows.len() {
+        let columns = insert_columns(&rows[start]);
+        let max_rows = upsert_chunk_size(db_type, columns.len()).min(INSERT_MANY_MAX_ROWS);
+        let mut end = start + 1;
+        while end < rows.len() && end - start < max_rows && insert_columns(&rows[end]) == columns {
+            end += 1;
+        }
+        batches.push((columns, &rows[start..end]));
+        start = end;
+    }
+    batches
+}
+
+/// 生成多行插入语句，`returning` 为列名时附加 `RETURNING` 子句（PostgreSQL、SQLite）
+pub(crate) fn insert_many_sql(
+    db_type: DatabaseType,
+    table: &str,
+    columns: &[String],
+    row_count: usize,
+    returning: Option<&str>,
+) -> QuickDbResult<String> {
+    if db_type == DatabaseType::MongoDB {
+        return Err(QuickDbError::UnsupportedDatabase {
+            db_type: "MongoDB 使用 insert_many 批量插入".to_string(),
+        });
+    }
+    if columns.is_empty() {
+        return Err(QuickDbError::QueryError {
+            message: "所有插入值都是 NULL，无法插入".to_string(),
+        });
+    }
+    let validator = DatabaseSecurityValidator::new(db_type);
+    let safe_table = validator.get_safe_table_identifier(table)?;
+    let safe_columns = columns.iter()
+        .map(|column| validator.get_safe_field_identifier(column))
+        .collect::<QuickDbResult<Vec<_>>>()?;
+
+    let mut sql = format!(
+        "INSERT INTO {} ({}) VALUES {}",
+        safe_table,
+        safe_columns.join(", "),
+        values_placeholders(db_type, columns.len(), row_count)
+    );
+    if let Some(column) = returning {
+        sql.push_str(&format!(" RETURNING {}", validator.get_safe_field_identifier(column)?));
+    }
+    Ok(sql)
+}
+
+/// 按列顺序展开批量插入或更新的参数
+pub(crate) fn upsert_params(rows: &[HashMap<String, DataValue>], columns: &[String]) -> Vec<DataValue> {
+    rows.iter()
+        .flat_map(|row| columns.iter().map(|column| row.get(column).cloned().unwrap_or(DataValue::Null)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_select(db_type: DatabaseType, conditions: &[QueryCondition]) -> (String, Vec<DataValue>) {
+        SqlQueryBuilder::new()
+            .database_type(db_type)
+            .select(&["*"])
+            .from("users")
+            .where_conditions(conditions)
+            .build()
+            .expect("构建查询失败")
+    }
+
+    fn build_paginated(db_type: DatabaseType, limit: Option<u64>, offset: Option<u64>) -> String {
+        let mut builder = SqlQueryBuilder::new()
+            .database_type(db_type)
+            .select(&["*"])
+            .from("users");
+        if let Some(limit) = limit {
+            builder = builder.limit(limit);
+        }
+        if let Some(offset) = offset {
+            builder = builder.offset(offset);
+        }
+        builder.build().expect("构建查询失败").0
+    }
+
+    #[test]
+    fn test_limit_and_offset_combinations() {
+        for db_type in [DatabaseType::SQLite, DatabaseType::MySQL, DatabaseType::PostgreSQL] {
+            assert!(build_paginated(db_type, Some(10), Some(0)).ends_with("users LIMIT 10"));
+            assert!(build_paginated(db_type, Some(10), None).ends_with("users LIMIT 10"));
+            assert!(build_paginated(db_type, Some(5), Some(100000)).ends_with("users LIMIT 5 OFFSET 100000"));
+            assert!(build_paginated(db_type, None, None).ends_with("users"));
+            assert!(build_paginated(db_type, None, Some(0)).ends_with("users"));
+        }
+
+        assert!(build_paginated(DatabaseType::SQLite, None, Some(20)).ends_with("users LIMIT -1 OFFSET 20"));
+        assert!(build_paginated(DatabaseType::MySQL, None, Some(20))
+            .ends_with(&format!("users LIMIT {} OFFSET 20", u64::MAX)));
+        assert!(build_paginated(DatabaseType::PostgreSQL, None, Some(20)).ends_with("users OFFSET 20"));
+    }
+
+    #[test]
+    fn test_select_fields_quotes_and_validates_names() {
+        let build = |db_type, fields: &[&str]| SqlQueryBuilder::new()
+            .database_type(db_type)
+            .select_fields(fields)
+            .from("users")
+            .build();
+
+        assert!(build(DatabaseType::SQLite, &[]).unwrap().0.starts_with("SELECT * FROM users"));
+        assert!(build(DatabaseType::PostgreSQL, &["id", "name"]).unwrap().0.starts_with("SELECT \"id\", \"name\" FROM users"));
+        assert!(build(DatabaseType::MySQL, &["id", "name"]).unwrap().0.starts_with("SELECT `id`, `name` FROM users"));
+        assert!(build(DatabaseType::SQLite, &["name; DROP TABLE users"]).is_err());
+    }
+
+    #[test]
+    fn test_pagination_config_zero_limit_means_unlimited() {
+        let sql = SqlQueryBuilder::new()
+            .database_type(DatabaseType::SQLite)
+            .select(&["*"])
+            .from("users")
+            .pagination(&PaginationConfig { skip: 30, limit: 0 })
+            .build()
+            .expect("构建查询失败")
+            .0;
+        assert!(sql.ends_with("users LIMIT -1 OFFSET 30"));
+    }
+
+    #[test]
+    fn test_is_null_operators_render_without_params() {
+        let (sql, params) = build_select(DatabaseType::SQLite, &[
+            QueryCondition::new("last_login", QueryOperator::IsNull, DataValue::Null),
+            QueryCondition::new("email", QueryOperator::IsNotNull, DataValue::Null),
+        ]);
+        assert!(sql.contains("\"last_login\" IS NULL"), "{}", sql);
+        assert!(sql.contains("\"email\" IS NOT NULL"), "{}", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_like_operators_escape_metacharacters() {
+        let (sql, params) = build_select(DatabaseType::SQLite, &[
+            QueryCondition::new("name", QueryOperator::Contains, "50%_off"),
+            QueryCondition::new("path", QueryOperator::StartsWith, "C:\\tmp!"),
+            QueryCondition::new("code", QueryOperator::EndsWith, "_x"),
+        ]);
+        assert!(sql.contains("\"name\" LIKE ? ESCAPE '!'"), "{}", sql);
+        assert_eq!(params, vec![
+            DataValue::String("%50!%!_off%".to_string()),
+            DataValue::String("C:\\tmp!!%".to_string()),
+            DataValue::String("%!_x".to_string()),
+        ]);
+
+        let icontains = || QueryCondition::new("name", QueryOperator::IContains, "Ab%");
+        let (sql, params) = build_select(DatabaseType::PostgreSQL, &[icontains()]);
+        assert!(sql.contains("\"name\" ILIKE $1 ESCAPE '!'"), "{}", sql);
+        assert_eq!(params, vec![DataValue::String("%Ab!%%".to_string())]);
+        let (sql, _) = build_select(DatabaseType::MySQL, &[icontains()]);
+        assert!(sql.contains("LOWER(`name`) LIKE LOWER(?) ESCAPE '!'"), "{}", sql);
+    }
+
+    #[test]
+    fn test_decimal_strings_cast_to_numeric_on_postgres() {
+        crate::manager::register_model(
+            crate::model::ModelMeta::new("qb_decimal_prices")
+                .field("amount", crate::model::FieldDefinition::new(crate::model::FieldType::Decimal { precision: 20, scale: 2 })),
+        ).unwrap();
+        let amount = || DataValue::String("12345678901234567.89".to_string());
+        let build = |db_type: DatabaseType, builder: SqlQueryBuilder| builder
+            .database_type(db_type)
+            .from("qb_decimal_prices")
+            .build()
+            .expect("构建查询失败");
+
+        let (sql, params) = build(DatabaseType::PostgreSQL, SqlQueryBuilder::new()
+            .insert(HashMap::from([("amount".to_string(), amount())])));
+        assert!(sql.ends_with("VALUES ($1::numeric)"), "{}", sql);
+        assert_eq!(params, vec![amount()]);
+        let (sql, _) = build(DatabaseType::PostgreSQL, SqlQueryBuilder::new()
+            .update(HashMap::from([("amount".to_string(), amount())]))
+            .where_conditions(&[QueryCondition::new("amount", QueryOperator::Gte, amount())]));
+        assert!(sql.contains("SET amount = $1::numeric WHERE \"amount\" >= $2::numeric"), "{}", sql);
+
+        // 数值参数和其他数据库不转换
+        let (sql, _) = build(DatabaseType::PostgreSQL, SqlQueryBuilder::new()
+            .select(&["*"])
+            .where_conditions(&[QueryCondition::new("amount", QueryOperator::Eq, DataValue::Float(1.5))]));
+        assert!(sql.ends_with("\"amount\" = $1"), "{}", sql);
+        let (sql, _) = build(DatabaseType::MySQL, SqlQueryBuilder::new()
+            .insert(HashMap::from([("amount".to_string(), amount())])));
+        assert!(sql.ends_with("VALUES (?)"), "{}", sql);
+    }
+
+    #[test]
+    fn test_array_operators_per_database() {
+        let tags = || QueryCondition::new(
+            "tags",
+            QueryOperator::ArrayContainsAll,
+            DataValue::Array(vec![DataValue::String("rust".to_string()), DataValue::String("db".to_string())]),
+        );
+        let size = || QueryCondition::new("tags", QueryOperator::ArraySize, 2i64);
+
+        let (sql, params) = build_select(DatabaseType::SQLite, &[tags(), size()]);
+        assert!(sql.contains(
+            "(EXISTS (SELECT 1 FROM json_each(\"tags\") WHERE json_each.value = ?) AND \
+             EXISTS (SELECT 1 FROM json_each(\"tags\") WHERE json_each.value = ?))"
+        ), "{}", sql);
+        assert!(sql.contains("json_array_length(\"tags\") = ?"), "{}", sql);
+        assert_eq!(params, vec![
+            DataValue::String("rust".to_string()),
+            DataValue::String("db".to_string()),
+            DataValue::Int(2),
+        ]);
+
+        let (sql, params) = build_select(DatabaseType::MySQL, &[tags(), size()]);
+        assert!(sql.contains("JSON_CONTAINS(`tags`, ?) AND JSON_LENGTH(`tags`) = ?"), "{}", sql);
+        assert_eq!(params.len(), 2);
+
+        // 条件组路径同样按参数数推进占位符
+        let (sql, params) = SqlQueryBuilder::new()
+            .database_type(DatabaseType::PostgreSQL)
+            .select(&["*"])
+            .from("users")
+            .where_condition_groups(&[QueryConditionGroup::and(vec![tags().into(), size().into()])])
+            .build()
+            .expect("构建查询失败");
+        assert!(sql.contains("\"tags\" @> $1 AND jsonb_array_length(\"tags\") = $2"), "{}", sql);
+        assert_eq!(params, vec![tags().value, DataValue::Int(2)]);
+    }
+
+    #[test]
+    fn test_in_operators_bind_one_param_per_element() {
+        let ids = || DataValue::Array(vec![DataValue::Int(1), DataValue::Int(2), DataValue::Int(3)]);
+
+        let (sql, params) = build_select(DatabaseType::SQLite, &[
+            QueryCondition::new("id", QueryOperator::In, ids()),
+            QueryCondition::new("age", QueryOperator::Gt, 18i64),
+        ]);
+        assert!(sql.contains("\"id\" IN (?, ?, ?) AND \"age\" > ?"), "{}", sql);
+        assert_eq!(params.len(), 4);
+
+        let (sql, params) = build_select(DatabaseType::MySQL, &[QueryCondition::new("id", QueryOperator::NotIn, ids())]);
+        assert!(sql.contains("`id` NOT IN (?, ?, ?)"), "{}", sql);
+        assert_eq!(params, match ids() { DataValue::Array(values) => values, _ => unreachable!() });
+
+        // PostgreSQL占位符按元素数推进，条件组路径同样如此
+        let (sql, params) = SqlQueryBuilder::new()
+            .database_type(DatabaseType::PostgreSQL)
+            .select(&["*"])
+            .from("users")
+            .where_condition_groups(&[QueryConditionGroup::and(vec![
+                QueryCondition::new("id", QueryOperator::In, ids()).into(),
+                QueryCondition::new("age", QueryOperator::Gt, 18i64).into(),
+            ])])
+            .build()
+            .expect("构建查询失败");
+        assert!(sql.contains("\"id\" IN ($1, $2, $3) AND \"age\" > $4"), "{}", sql);
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn test_in_operators_with_empty_array() {
+        let empty = || DataValue::Array(vec![]);
+        for db_type in [DatabaseType::SQLite, DatabaseType::PostgreSQL, DatabaseType::MySQL] {
+            let (sql, params) = build_select(db_type, &[QueryCondition::new("id", QueryOperator::In, empty())]);
+            assert!(sql.contains("WHERE 1 = 0"), "{}", sql);
+            assert!(params.is_empty());
+
+            let (sql, params) = build_select(db_type, &[
+                QueryCondition::new("id", QueryOperator::NotIn, empty()),
+                QueryCondition::new("age", QueryOperator::Gt, 18i64),
+            ]);
+            assert!(sql.contains("WHERE 1 = 1 AND "), "{}", sql);
+            assert_eq!(params, vec![DataValue::Int(18)]);
+        }
+
+        let err = SqlQueryBuilder::new()
+            .database_type(DatabaseType::SQLite)
+            .select(&["*"])
+            .from("users")
+            .where_conditions(&[QueryCondition::new("id", QueryOperator::In, 1i64)])
+            .build();
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_array_operators_validate_values() {
+        let (sql, params) = build_select(DatabaseType::SQLite, &[
+            QueryCondition::new("tags", QueryOperator::ArrayContainsAll, DataValue::Array(vec![])),
+        ]);
+        assert!(sql.contains("1 = 0"), "{}", sql);
+        assert!(params.is_empty());
+
+        for value in [DataValue::Int(-1), DataValue::String("2".to_string())] {
+            let result = SqlQueryBuilder::new()
+                .database_type(DatabaseType::SQLite)
+                .select(&["*"])
+                .from("users")
+                .where_conditions(&[QueryCondition::new("tags", QueryOperator::ArraySize, value)])
+                .build();
+            assert!(matches!(result, Err(QuickDbError::ValidationError { .. })));
+        }
+        let result = SqlQueryBuilder::new()
+            .database_type(DatabaseType::MySQL)
+            .select(&["*"])
+            .from("users")
+            .where_conditions(&[QueryCondition::new("tags", QueryOperator::ArrayContainsAll, "rust")])
+            .build();
+        assert!(matches!(result, Err(QuickDbError::ValidationError { .. })));
+    }
+
+    #[test]
+    fn test_eq_and_ne_null_rewritten_to_is_null() {
+        let (sql, params) = build_select(DatabaseType::PostgreSQL, &[
+            QueryCondition::new("deleted_at", QueryOperator::Eq, DataValue::Null),
+            QueryCondition::new("name", QueryOperator::Ne, DataValue::Null),
+            QueryCondition::new("age", QueryOperator::Gte, 18i64),
+        ]);
+        assert!(sql.contains("\"deleted_at\" IS NULL"), "{}", sql);
+        assert!(sql.contains("\"name\" IS NOT NULL"), "{}", sql);
+        assert!(sql.contains("\"age\" >= $1"), "{}", sql);
+        assert_eq!(params, vec![DataValue::Int(18)]);
+    }
+
+    #[test]
+    fn test_json_path_expr_per_database() {
+        let city = DataValue::String("北京".to_string());
+        let zip = DataValue::Int(100000);
+
+        assert_eq!(
+            json_path_expr(DatabaseType::SQLite, "\"address\"", "city", &city).unwrap(),
+            "json_extract(\"address\", '$.city')"
+        );
+        assert_eq!(
+            json_path_expr(DatabaseType::MySQL, "`address`", "geo.lat", &DataValue::Float(39.9)).unwrap(),
+            "JSON_EXTRACT(`address`, '$.geo.lat')"
+        );
+        assert_eq!(
+            json_path_expr(DatabaseType::MySQL, "`address`", "lines.0", &city).unwrap(),
+            "JSON_UNQUOTE(JSON_EXTRACT(`address`, '$.lines[0]'))"
+        );
+        assert_eq!(
+            json_path_expr(DatabaseType::PostgreSQL, "\"address\"", "city", &city).unwrap(),
+            "(\"address\" #>> '{city}')"
+        );
+        assert_eq!(
+            json_path_expr(DatabaseType::PostgreSQL, "\"address\"", "geo.zip", &DataValue::Array(vec![zip])).unwrap(),
+            "(\"address\" #>> '{geo,zip}')::numeric"
+        );
+        assert!(json_path_expr(DatabaseType::SQLite, "\"address\"", "city') OR 1=1 --", &city).is_err());
+        assert!(json_path_expr(DatabaseType::SQLite, "\"address\"", "geo..lat", &city).is_err());
+    }
+
+    #[test]
+    fn test_select_expr_renders_per_database() {
+        let exprs = [
+            (Expr::col("price") * Expr::col("quantity"), "total"),
+            (Expr::func(ScalarFunction::Concat, vec![Expr::col("first"), Expr::lit(" "), Expr::col("last")]), "full_name"),
+            (Expr::func(ScalarFunction::Round, vec![Expr::col("score"), Expr::lit(2i64)]), "score_2"),
+        ];
+        let build = |db_type| {
+            SqlQueryBuilder::new()
+                .database_type(db_type)
+                .select(&["*"])
+                .select_expr(&exprs)
+                .from("orders")
+                .where_condition(QueryCondition::new("status", QueryOperator::Eq, "paid"))
+                .build()
+                .expect("构建查询失败")
+        };
+
+        let (sql, params) = build(DatabaseType::SQLite);
+        assert!(sql.starts_with("SELECT *, (\"price\" * \"quantity\") AS \"total\", (\"first\" || ' ' || \"last\") AS \"full_name\", ROUND(\"score\", 2) AS \"score_2\" FROM"), "{}", sql);
+        assert_eq!(params, vec![DataValue::String("paid".to_string())]);
+
+        let (sql, _) = build(DatabaseType::MySQL);
+        assert!(sql.contains("CONCAT(`first`, ' ', `last`) AS `full_name`"), "{}", sql);
+
+        let (sql, _) = build(DatabaseType::PostgreSQL);
+        assert!(sql.contains("ROUND(CAST(\"score\" AS NUMERIC), 2) AS \"score_2\""), "{}", sql);
+        assert!(sql.contains("\"status\" = $1"), "{}", sql);
+
+        let injected = SqlQueryBuilder::new()
+            .select(&["*"])
+            .select_expr(&[(Expr::lit("x'; DROP TABLE orders; --"), "v")])
+            .from("orders")
+            .build()
+            .unwrap()
+            .0;
+        assert!(injected.contains("'x''; DROP TABLE orders; --' AS \"v\""), "{}", injected);
+        // MySQL 中反斜杠是转义字符，必须一并转义，否则 \' 会提前结束字符串
+        let injected = SqlQueryBuilder::new()
+            .database_type(DatabaseType::MySQL)
+            .select(&["*"])
+            .select_expr(&[(Expr::lit("x\\'; DROP TABLE orders; --"), "v")])
+            .from("orders")
+            .build()
+            .unwrap()
+            .0;
+        assert!(injected.contains("'x\\\\''; DROP TABLE orders; --' AS `v`"), "{}", injected);
+        assert!(SqlQueryBuilder::new()
+            .select(&["*"])
+            .select_expr(&[(Expr::func(ScalarFunction::Abs, vec![]), "v")])
+            .from("orders")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_order_by_select_alias_per_database() {
+        let build = |db_type, collation: Option<&str>| {
+            SqlQueryBuilder::new()
+                .database_type(db_type)
+                .select(&["*"])
+                .select_expr(&[
+                    (Expr::col("price") * Expr::col("quantity"), "total"),
+                    (Expr::func(ScalarFunction::Lower, vec![Expr::col("name")]), "lname"),
+                ])
+                .from("orders")
+                .order_by("total", SortDirection::Desc)
+                .order_by("lname", SortDirection::Asc)
+                .collation(collation)
+                .build()
+                .expect("构建查询失败")
+                .0
+        };
+
+        assert!(build(DatabaseType::SQLite, None).ends_with("ORDER BY \"total\" DESC, \"lname\" ASC"));
+        assert!(build(DatabaseType::MySQL, Some("utf8mb4_bin")).ends_with("ORDER BY `total` DESC, `lname` COLLATE utf8mb4_bin ASC"));
+        // PostgreSQL 重复表达式，别名不能与 COLLATE 组合
+        let sql = build(DatabaseType::PostgreSQL, Some("C"));
+        assert!(sql.ends_with("ORDER BY (\"price\" * \"quantity\") DESC, LOWER(\"name\") COLLATE \"C\" ASC"), "{}", sql);
+
+        // 选择列表中的聚合列别名
+        let (sql, _) = SqlQueryBuilder::new()
+            .database_type(DatabaseType::PostgreSQL)
+            .select(&["category", "SUM(amount) AS total_sales"])
+            .from("sales")
+            .group_by(&["category"])
+            .order_by("total_sales", SortDirection::Desc)
+            .build()
+            .unwrap();
+        assert!(sql.ends_with("GROUP BY category ORDER BY \"total_sales\" DESC"), "{}", sql);
+        assert_eq!(select_list_alias("count(*) as `n`"), Some("n"));
+        assert_eq!(select_list_alias("category"), None);
+    }
+
+    #[test]
+    fn test_order_by_quotes_and_validates_dotted_fields() {
+        let build = |db_type, field: &str| {
+            SqlQueryBuilder::new()
+                .database_type(db_type)
+                .select(&["*"])
+                .from("unregistered_orders")
+                .order_by(field, SortDirection::Asc)
+                .build()
+                .map(|(sql, _)| sql)
+        };
+
+        assert!(build(DatabaseType::SQLite, "created_at").unwrap().ends_with("ORDER BY \"created_at\" ASC"));
+        assert!(build(DatabaseType::MySQL, "o.created_at").unwrap().ends_with("ORDER BY `o`.`created_at` ASC"));
+        assert!(build(DatabaseType::PostgreSQL, "o.id").unwrap().ends_with("ORDER BY \"o\".\"id\" ASC"));
+        // 带点的路径不再原样拼入 SQL
+        assert!(build(DatabaseType::SQLite, "o.id; DROP TABLE users").is_err());
+        assert!(build(DatabaseType::PostgreSQL, "o.(SELECT 1)").is_err());
+        assert!(build(DatabaseType::MySQL, "o.").is_err());
+    }
+
+    #[test]
+    fn test_case_insensitive_index_columns_per_database() {
+        let index = crate::model::IndexDefinition::new(vec!["email".to_string(), "username".to_string()], true)
+            .directions(vec![SortDirection::Asc, SortDirection::Desc])
+            .case_insensitive(true);
+        assert_eq!(index_columns_clause(DatabaseType::PostgreSQL, &index).unwrap(), "lower(email), lower(username) DESC");
+        assert_eq!(index_columns_clause(DatabaseType::SQLite, &index).unwrap(), "email COLLATE NOCASE, username COLLATE NOCASE DESC");
+        assert_eq!(index_columns_clause(DatabaseType::MySQL, &index).unwrap(), "email, username DESC");
+
+        let email = crate::model::string_field(None, None, None).unique_case_insensitive();
+        assert_eq!(column_collate_clause(DatabaseType::MySQL, &email).unwrap(), " COLLATE utf8mb4_unicode_ci");
+        assert_eq!(column_collate_clause(DatabaseType::PostgreSQL, &email).unwrap(), "");
+    }
+
+    #[test]
+    fn test_column_default_clause_per_database() {
+        use crate::model::{boolean_field, datetime_field, json_field, string_field, DefaultExpr};
+
+        let status = string_field(None, None, None).default_value(DataValue::String("it's \\new".to_string()));
+        assert_eq!(column_default_clause(DatabaseType::SQLite, &status).unwrap(), " DEFAULT 'it''s \\new'");
+        assert_eq!(column_default_clause(DatabaseType::MySQL, &status).unwrap(), " DEFAULT 'it''s \\\\new'");
+
+        let active = boolean_field().default_value(DataValue::Bool(true));
+        assert_eq!(column_default_clause(DatabaseType::PostgreSQL, &active).unwrap(), " DEFAULT TRUE");
+        assert_eq!(column_default_clause(DatabaseType::SQLite, &active).unwrap(), " DEFAULT 1");
+
+        let settings = json_field().default_value(DataValue::Json(serde_json::json!({ "a": 1 })));
+        assert_eq!(column_default_clause(DatabaseType::MySQL, &settings).unwrap(), " DEFAULT ('{\"a\":1}')");
+        assert_eq!(column_default_clause(DatabaseType::PostgreSQL, &settings).unwrap(), " DEFAULT '{\"a\":1}'");
+
+        let created_at = datetime_field().default_expr(DefaultExpr::CurrentTimestamp);
+        assert_eq!(column_default_clause(DatabaseType::MySQL, &created_at).unwrap(), " DEFAULT CURRENT_TIMESTAMP");
+        assert!(column_default_clause(DatabaseType::SQLite, &status.clone().default_expr(DefaultExpr::CurrentTimestamp)).is_err());
+        assert_eq!(column_default_clause(DatabaseType::SQLite, &string_field(None, None, None)).unwrap(), "");
+    }
+
+    #[test]
+    fn test_blob_chunk_sql_per_database() {
+        assert_eq!(
+            blob_chunk_select_sql(DatabaseType::SQLite, "files", "content").unwrap(),
+            "SELECT substr(\"content\", ?, ?) FROM \"files\" WHERE \"id\" = ?"
+        );
+        assert_eq!(
+            blob_chunk_select_sql(DatabaseType::PostgreSQL, "files", "content").unwrap(),
+            "SELECT substring(\"content\" from $1::integer for $2::integer) FROM \"files\" WHERE \"id\" = $3"
+        );
+        assert_eq!(
+            blob_chunk_update_sql(DatabaseType::PostgreSQL, "files", "content", true).unwrap(),
+            "UPDATE \"files\" SET \"content\" = COALESCE(\"content\", ''::bytea) || $1 WHERE \"id\" = $2"
+        );
+        assert_eq!(
+            blob_chunk_update_sql(DatabaseType::MySQL, "files", "content", false).unwrap(),
+            "UPDATE `files` SET `content` = ? WHERE `id` = ?"
+        );
+        assert!(blob_chunk_select_sql(DatabaseType::MySQL, "files", "content; DROP TABLE files").is_err());
+    }
+
+    #[test]
+    fn test_rename_table_sql_quotes_identifiers() {
+        assert_eq!(
+            rename_table_sql(DatabaseType::PostgreSQL, "users", "users_old").unwrap(),
+            "ALTER TABLE \"users\" RENAME TO \"users_old\""
+        );
+        assert_eq!(
+            rename_table_sql(DatabaseType::MySQL, "users", "users_old").unwrap(),
+            "ALTER TABLE `users` RENAME TO `users_old`"
+        );
+        assert!(rename_table_sql(DatabaseType::SQLite, "users", "x; DROP TABLE users").is_err());
+        assert!(rename_table_sql(DatabaseType::MongoDB, "users", "users_old").is_err());
+    }
+
+    #[test]
+    fn test_time_bucket_sql_per_database() {
+        let active = vec![QueryCondition {
+            field: "status".to_string(),
+            operator: QueryOperator::Eq,
+            value: DataValue::String("active".to_string()),
+        }];
+        let (sql, params) = time_bucket_sql(DatabaseType::PostgreSQL, "events", "created_at", Interval::Week, &active).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT to_char(date_trunc('week', \"created_at\" AT TIME ZONE 'UTC'), 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"') AS bucket, \
+             COUNT(*) AS count FROM events WHERE \"status\" = $1 AND \"created_at\" IS NOT NULL GROUP BY bucket ORDER BY bucket"
+        );
+        assert_eq!(params, vec![DataValue::String("active".to_string())]);
+
+        let (sql, _) = time_bucket_sql(DatabaseType::MySQL, "events", "created_at", Interval::Hour, &[]).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT DATE_FORMAT(`created_at`, '%Y-%m-%dT%H:00:00Z') AS bucket, COUNT(*) AS count \
+             FROM events WHERE `created_at` IS NOT NULL GROUP BY bucket ORDER BY bucket"
+        );
+
+        let (sql, _) = time_bucket_sql(DatabaseType::SQLite, "events", "created_at", Interval::Month, &[]).unwrap();
+        assert!(sql.starts_with("SELECT strftime('%Y-%m-01T00:00:00Z', \"created_at\") AS bucket"), "{}", sql);
+
+        assert!(time_bucket_sql(DatabaseType::SQLite, "events", "created_at; DROP TABLE events", Interval::Day, &[]).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_sql_per_database() {
+        let paid = vec![QueryCondition {
+            field: "status".to_string(),
+            operator: QueryOperator::Eq,
+            value: DataValue::String("paid".to_string()),
+        }];
+        let aggregations = vec![
+            Aggregation::new(AggFunc::Sum, "amount", "total"),
+            Aggregation::count("orders"),
+        ];
+        let group_by = vec!["customer_id".to_string()];
+        let (sql, params) = aggregate_sql(DatabaseType::PostgreSQL, "orders", &aggregations, &paid, &group_by).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT \"customer_id\", SUM(\"amount\") AS \"total\", COUNT(*) AS \"orders\" FROM orders \
+             WHERE \"status\" = $1 GROUP BY \"customer_id\" ORDER BY \"customer_id\""
+        );
+        assert_eq!(params, vec![DataValue::String("paid".to_string())]);
+
+        let (sql, _) = aggregate_sql(
+            DatabaseType::MySQL,
+            "orders",
+            &[Aggregation::new(AggFunc::Max, "amount", "largest")],
+            &[],
+            &[],
+        ).unwrap();
+        assert_eq!(sql, "SELECT MAX(`amount`) AS `largest` FROM orders");
+
+        assert!(aggregate_sql(DatabaseType::SQLite, "orders", &[], &[], &[]).is_err());
+        assert!(aggregate_sql(DatabaseType::SQLite, "orders", &[Aggregation::new(AggFunc::Sum, "*", "total")], &[], &[]).is_err());
+        assert!(aggregate_sql(DatabaseType::SQLite, "orders", &[Aggregation::count("customer_id")], &[], &group_by).is_err());
+        assert!(aggregate_sql(DatabaseType::SQLite, "orders", &[Aggregation::count("n; DROP TABLE orders")], &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_raw_condition_renumbers_placeholders() {
+        let conditions = [
+            QueryCondition::new("status", QueryOperator::Eq, "active"),
+            QueryCondition::raw("age = ANY($1) AND name <> '$2' AND score > $2", vec![
+                DataValue::Array(vec![DataValue::Int(18), DataValue::Int(30)]),
+                DataValue::Int(5),
+            ]),
+            QueryCondition::new("city", QueryOperator::Eq, "x"),
+        ];
+        let (sql, params) = build_select(DatabaseType::PostgreSQL, &conditions);
+        assert!(sql.ends_with("(age = ANY($2) AND name <> '$2' AND score > $3) AND \"city\" = $4"), "{}", sql);
+        assert_eq!(params.len(), 4);
+        assert_eq!(params[2], DataValue::Int(5));
+
+        // ? 占位符按引用顺序展开参数，重复引用时重复绑定
+        let repeated = [QueryCondition::raw("lo <= $1 AND hi >= $1 AND tag = $2", vec![DataValue::Int(7), DataValue::String("a".to_string())])];
+        let (sql, params) = build_select(DatabaseType::SQLite, &repeated);
+        assert!(sql.ends_with("WHERE (lo <= ? AND hi >= ? AND tag = ?)"), "{}", sql);
+        assert_eq!(params, vec![DataValue::Int(7), DataValue::Int(7), DataValue::String("a".to_string())]);
+
+        // 条件组中同样按整体参数列表编号
+        let (where_clause, _) = SqlQueryBuilder::new()
+            .database_type(DatabaseType::PostgreSQL)
+            .build_where_clause_from_groups(&[QueryConditionGroup::or(vec![
+                QueryConditionGroup::cond("a", QueryOperator::Eq, 1i64),
+                QueryConditionGroup::Single(QueryCondition::raw("b % $1 = 0", vec![DataValue::Int(2)])),
+            ])])
+            .unwrap();
+        assert_eq!(where_clause, "(\"a\" = $1 OR (b % $2 = 0))");
+    }
+
+    #[test]
+    fn test_raw_condition_rejects_invalid_fragments() {
+        let build = |db_type: DatabaseType, condition: QueryCondition| {
+            SqlQueryBuilder::new()
+                .database_type(db_type)
+                .select(&["*"])
+                .from("users")
+                .where_conditions(&[condition])
+                .build()
+        };
+        assert!(build(DatabaseType::SQLite, QueryCondition::raw("age > ?", vec![DataValue::Int(1)])).is_err());
+        assert!(build(DatabaseType::PostgreSQL, QueryCondition::raw("age > $2", vec![DataValue::Int(1)])).is_err());
+        assert!(build(DatabaseType::PostgreSQL, QueryCondition::raw("age > 1", vec![DataValue::Int(1)])).is_err());
+        assert!(build(DatabaseType::MySQL, QueryCondition::raw("name = 'x", vec![])).is_err());
+        assert!(build(DatabaseType::MySQL, QueryCondition::raw(" ", vec![])).is_err());
+        // PostgreSQL 的 JSONB ? 操作符不受影响
+        assert!(build(DatabaseType::PostgreSQL, QueryCondition::raw("tags ? $1", vec![DataValue::String("a".to_string())])).is_ok());
+    }
+
+    #[test]
+    fn test_split_sql_statements_respects_quotes_and_comments() {
+        let script = "CREATE TABLE a (note TEXT DEFAULT 'x;y'); -- 注释; 不拆分\n\
+            /* 块注释; */ INSERT INTO a VALUES ('it''s;'); ;\n\
+            INSERT INTO \"we;ird\" VALUES (1)";
+        assert_eq!(
+            split_sql_statements(DatabaseType::SQLite, script).unwrap(),
+            vec![
+                "CREATE TABLE a (note TEXT DEFAULT 'x;y')",
+                "-- 注释; 不拆分\n/* 块注释; */ INSERT INTO a VALUES ('it''s;')",
+                "INSERT INTO \"we;ird\" VALUES (1)",
+            ]
+        );
+
+        // SQLite 触发器体中的分号不拆分
+        let trigger = "CREATE TRIGGER t AFTER INSERT ON a BEGIN UPDATE a SET n = 1; DELETE FROM b; END; SELECT 1;";
+        assert_eq!(split_sql_statements(DatabaseType::SQLite, trigger).unwrap().len(), 2);
+
+        // PostgreSQL 美元符号引用的函数体
+        let function = "CREATE FUNCTION f() RETURNS int AS $body$ BEGIN RETURN 1; END; $body$ LANGUAGE plpgsql; SELECT $1, $$a;b$$";
+        let statements = split_sql_statements(DatabaseType::PostgreSQL, function).unwrap();
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("$body$ LANGUAGE plpgsql"), "{:?}", statements);
+
+        // MySQL 的反斜杠转义和 # 注释
+        let mysql = "INSERT INTO a VALUES ('a\\';b'); # x;y\nSELECT 1";
+        assert_eq!(split_sql_statements(DatabaseType::MySQL, mysql).unwrap().len(), 2);
+
+        assert!(split_sql_statements(DatabaseType::SQLite, "SELECT 'a;").is_err());
+        assert!(split_sql_statements(DatabaseType::PostgreSQL, "SELECT 1 /* a;").is_err());
+        assert!(split_sql_statements(DatabaseType::SQLite, " ; -- 空\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_upsert_many_sql_per_database() {
+        let columns = vec!["email".to_string(), "id".to_string(), "name".to_string()];
+        let conflict = vec!["email".to_string()];
+
+        assert_eq!(
+            upsert_many_sql(DatabaseType::PostgreSQL, "users", &columns, &conflict, 2).unwrap(),
+            "INSERT INTO \"users\" (\"email\", \"id\", \"name\") VALUES ($1, $2, $3), ($4, $5, $6) \
+             ON CONFLICT (\"email\") DO UPDATE SET \"name\" = excluded.\"name\" RETURNING (xmax = 0) AS \"inserted\""
+        );
+        assert_eq!(
+            upsert_many_sql(DatabaseType::MySQL, "users", &columns, &conflict, 1).unwrap(),
+            "INSERT INTO `users` (`email`, `id`, `name`) VALUES (?, ?, ?) ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)"
+        );
+
+        // 只有冲突字段和ID时把冲突字段写回原值
+        let key_only = vec!["email".to_string(), "id".to_string()];
+        assert_eq!(
+            upsert_many_sql(DatabaseType::SQLite, "users", &key_only, &conflict, 1).unwrap(),
+            "INSERT INTO \"users\" (\"email\", \"id\") VALUES (?, ?) ON CONFLICT (\"email\") DO UPDATE SET \"email\" = excluded.\"email\""
+        );
+        assert!(upsert_many_sql(DatabaseType::MongoDB, "users", &columns, &conflict, 1).is_err());
+    }
+
+    #[test]
+    fn test_upsert_exists_sql_per_database() {
+        let conflict = vec!["email".to_string(), "tenant".to_string()];
+        assert_eq!(
+            upsert_exists_sql(DatabaseType::PostgreSQL, "users", &conflict, false).unwrap(),
+            "SELECT 1 FROM \"users\" WHERE \"email\" = $1 AND \"tenant\" = $2 LIMIT 1"
+        );
+        assert_eq!(
+            upsert_exists_sql(DatabaseType::MySQL, "users", &conflict, true).unwrap(),
+            "SELECT 1 FROM `users` WHERE `email` = ? AND `tenant` = ? LIMIT 1 FOR UPDATE"
+        );
+
+        let missing = QuickDbError::QueryError {
+            message: "ON CONFLICT clause does not match any PRIMARY KEY or UNIQUE constraint".to_string(),
+        };
+        assert!(matches!(
+            upsert_conflict_error("users", &conflict, missing),
+            QuickDbError::ValidationError { ref field, .. } if field == "conflict_fields"
+        ));
+        let other = QuickDbError::QueryError { message: "database is locked".to_string() };
+        assert!(matches!(upsert_conflict_error("users", &conflict, other), QuickDbError::QueryError { .. }));
+    }
+
+    #[test]
+    fn test_upsert_columns_require_uniform_rows() {
+        let row = |fields: &[&str]| -> HashMap<String, DataValue> {
+            fields.iter().map(|f| (f.to_string(), DataValue::Int(1))).collect()
+        };
+        let conflict = vec!["email".to_string()];
+
+        assert_eq!(
+            upsert_columns(&[row(&["name", "email"]), row(&["email", "name"])], &conflict).unwrap(),
+            vec!["email".to_string(), "name".to_string()]
+        );
+        assert!(upsert_columns(&[row(&["name", "email"]), row(&["email"])], &conflict).is_err());
+        assert!(upsert_columns(&[row(&["name"])], &conflict).is_err());
+        assert!(upsert_columns(&[], &conflict).is_err());
+    }
+
+    #[test]
+    fn test_insert_many_batches_group_rows_with_same_columns() {
+        let row = |name: DataValue, age: DataValue| -> HashMap<String, DataValue> {
+            HashMap::from([("name".to_string(), name), ("age".to_string(), age)])
+        };
+        let rows = vec![
+            row(DataValue::String("a".to_string()), DataValue::Int(1)),
+            row(DataValue::String("b".to_string()), DataValue::Int(2)),
+            row(DataValue::String("c".to_string()), DataValue::Null),
+            row(DataValue::String("d".to_string()), DataValue::Int(4)),
+        ];
+        let batches = insert_many_batches(DatabaseType::SQLite, &rows);
+        let shapes: Vec<(Vec<String>, usize)> = batches.iter()
+            .map(|(columns, batch)| (columns.clone(), batch.len()))
+            .collect();
+        assert_eq!(shapes, vec![
+            (vec!["age".to_string(), "name".to_string()], 2),
+            (vec!["name".to_string()], 1),
+            (vec!["age".to_string(), "name".to_string()], 1),
+        ]);
+
+        let many = vec![row(DataValue::String("x".to_string()), DataValue::Int(1)); 2500];
+        let sizes: Vec<usize> = insert_many_batches(DatabaseType::PostgreSQL, &many).iter().map(|(_, batch)| batch.len()).collect();
+        assert_eq!(sizes, vec![1000, 1000, 500]);
+    }
+
+    #[test]
+    fn test_insert_many_sql_per_database() {
+        let columns = vec!["age".to_string(), "name".to_string()];
+        assert_eq!(
+            insert_many_sql(DatabaseType::PostgreSQL, "users", &columns, 2, Some("id")).unwrap(),
+            "INSERT INTO \"users\" (\"age\", \"name\") VALUES ($1, $2), ($3, $4) RETURNING \"id\""
+        );
+        assert_eq!(
+            insert_many_sql(DatabaseType::MySQL, "users", &columns, 1, None).unwrap(),
+            "INSERT INTO `users` (`age`, `name`) VALUES (?, ?)"
+        );
+        assert!(insert_many_sql(DatabaseType::SQLite, "users", &[], 1, None).is_err());
+        assert!(insert_many_sql(DatabaseType::MongoDB, "users", &columns, 1, None).is_err());
+    }
+}
@@ -4,15 +4,23 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use dashmap::DashSet;
 use tokio::sync::Mutex;
 use rat_logger::{debug, info};
 
+/// 写语句遇到数据库忙时默认的重试次数
+pub const DEFAULT_BUSY_RETRY_COUNT: u32 = 5;
+
 /// SQLite适配器
 pub struct SqliteAdapter {
     /// 表创建锁，防止重复创建表
     creation_locks: Arc<Mutex<HashMap<String, ()>>>,
+    /// 已确认存在的表，写入时跳过重复的存在性检查
+    known_tables: Arc<DashSet<String>>,
     /// 存储过程映射表，存储已创建的存储过程信息
     pub(crate) stored_procedures: Arc<Mutex<HashMap<String, crate::stored_procedure::StoredProcedureInfo>>>,
+    /// 写语句遇到数据库忙时的最大重试次数
+    pub(crate) busy_retry_count: u32,
 }
 
 impl SqliteAdapter {
@@ -20,10 +28,18 @@ impl SqliteAdapter {
     pub fn new() -> Self {
         Self {
             creation_locks: Arc::new(Mutex::new(HashMap::new())),
+            known_tables: Arc::new(DashSet::new()),
             stored_procedures: Arc::new(Mutex::new(HashMap::new())),
+            busy_retry_count: DEFAULT_BUSY_RETRY_COUNT,
         }
     }
 
+    /// 设置写语句遇到数据库忙时的最大重试次数，为 0 时不重试
+    pub fn with_busy_retry_count(mut self, count: u32) -> Self {
+        self.busy_retry_count = count;
+        self
+    }
+
     /// 获取表创建锁
     pub(crate) async fn acquire_table_lock(&self, table: &str) -> tokio::sync::MutexGuard<'_, HashMap<String, ()>> {
         let mut locks = self.creation_locks.lock().await;
@@ -34,6 +50,21 @@ impl SqliteAdapter {
         locks
     }
 
+    /// 表是否已确认存在
+    pub(crate) fn is_known_table(&self, table: &str) -> bool {
+        self.known_tables.contains(table)
+    }
+
+    /// 记录表已存在
+    pub(crate) fn mark_table_known(&self, table: &str) {
+        self.known_tables.insert(table.to_string());
+    }
+
+    /// 移除表的存在记录，表被删除后必须调用
+    pub(crate) fn forget_table(&self, table: &str) {
+        self.known_tables.remove(table);
+    }
+
     /// 释放表创建锁
     pub(crate) async fn release_table_lock(&self, table: &str, mut locks: tokio::sync::MutexGuard<'_, HashMap<String, ()>>) {
         locks.remove(table);
@@ -0,0 +1,61 @@
+//! SQLite二进制列分块读写
+
+use crate::adapter::SqliteAdapter;
+use crate::adapter::query_builder::{blob_chunk_select_sql, blob_chunk_update_sql};
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::*;
+use crate::pool::DatabaseConnection;
+use sqlx::Row;
+
+/// 读取二进制列的一段数据，记录不存在时返回 `None`
+pub(crate) async fn read_chunk(
+    connection: &DatabaseConnection,
+    location: &BlobLocation,
+    offset: u64,
+    len: u64,
+) -> QuickDbResult<Option<Vec<u8>>> {
+    let pool = match connection {
+        DatabaseConnection::SQLite(pool) => pool,
+        _ => return Err(QuickDbError::ConnectionError {
+            message: "Invalid connection type for SQLite".to_string(),
+        }),
+    };
+    let sql = blob_chunk_select_sql(DatabaseType::SQLite, &location.table, &location.column)?;
+    let params = [DataValue::Int(offset as i64 + 1), DataValue::Int(len as i64), location.id.clone()];
+    crate::types::query_log::log_sql(&sql, &params);
+
+    let row = SqliteAdapter::bind_params(sqlx::query(&sql), &params)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("SQLite读取二进制数据失败: {}", e),
+        })?;
+    match row {
+        Some(row) => {
+            let chunk: Option<Vec<u8>> = row.try_get(0).map_err(|e| QuickDbError::QueryError {
+                message: format!("SQLite读取二进制数据失败: {}", e),
+            })?;
+            Ok(Some(chunk.unwrap_or_default()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// 写入二进制列的一段数据，返回记录是否存在
+pub(crate) async fn write_chunk(
+    adapter: &SqliteAdapter,
+    connection: &DatabaseConnection,
+    location: &BlobLocation,
+    data: &[u8],
+    append: bool,
+) -> QuickDbResult<bool> {
+    let pool = match connection {
+        DatabaseConnection::SQLite(pool) => pool,
+        _ => return Err(QuickDbError::ConnectionError {
+            message: "Invalid connection type for SQLite".to_string(),
+        }),
+    };
+    let sql = blob_chunk_update_sql(DatabaseType::SQLite, &location.table, &location.column, append)?;
+    let params = [DataValue::Bytes(data.to_vec()), location.id.clone()];
+    Ok(adapter.execute_update(pool, &sql, &params).await? > 0)
+}
@@ -8,6 +8,7 @@ pub mod utils;
 pub mod operations;
 pub mod query;
 pub mod schema;
+pub mod blob;
 
 // 重新导出主要的公共类型和结构体
-pub use adapter::SqliteAdapter;
\ No newline at end of file
+pub use adapter::{SqliteAdapter, DEFAULT_BUSY_RETRY_COUNT};
\ No newline at end of file
@@ -2,7 +2,7 @@
 use crate::adapter::{DatabaseAdapter, SqlQueryBuilder};
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
-use crate::model::{FieldDefinition, FieldType};
+use crate::model::{FieldDefinition, FieldType, IndexDefinition};
 use crate::pool::DatabaseConnection;
 use std::collections::HashMap;
 use async_trait::async_trait;
@@ -12,9 +12,14 @@ use sqlx::{sqlite::SqliteRow, Row, Column};
 use super::adapter::SqliteAdapter;
 use super::query as sqlite_query;
 use super::schema as sqlite_schema;
+use super::blob as sqlite_blob;
 
 #[async_trait]
 impl DatabaseAdapter for SqliteAdapter {
+    fn capabilities(&self) -> Capabilities {
+        DatabaseType::SQLite.capabilities()
+    }
+
     async fn create(
         &self,
         connection: &DatabaseConnection,
@@ -29,32 +34,8 @@ impl DatabaseAdapter for SqliteAdapter {
             }),
         };
         
-        // 自动建表逻辑：检查表是否存在，如果不存在则创建
-            if !self.table_exists(connection, table).await? {
-                // 获取表创建锁，防止重复创建
-                let _lock = self.acquire_table_lock(table).await;
-                // 再次检查表是否存在（双重检查锁定模式）
-                if !self.table_exists(connection, table).await? {
-                    // 尝试从模型管理器获取预定义的元数据
-                    if let Some(model_meta) = crate::manager::get_model(table) {
-                        debug!("表 {} 不存在，使用预定义模型元数据创建", table);
-
-                        // 使用模型元数据创建表
-                        self.create_table(connection, table, &model_meta.fields, id_strategy).await?;
-                        // 等待100ms确保数据库事务完全提交
-                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                        debug!("⏱️ 等待100ms确保表 '{}' 创建完成", table);
-                    } else {
-                        return Err(QuickDbError::ValidationError {
-                            field: "table_creation".to_string(),
-                            message: format!("表 '{}' 不存在，且没有预定义的模型元数据。请先定义模型并使用 define_model! 宏明确指定字段类型。", table),
-                        });
-                    }
-                } else {
-                    debug!("表 {} 已存在，跳过创建", table);
-                }
-                // 锁会在这里自动释放（当 _lock 超出作用域时）
-            }
+        // 自动建表逻辑：表不存在时按预定义的模型元数据创建
+        sqlite_schema::ensure_table(self, connection, table, id_strategy).await?;
             
             let (sql, params) = SqlQueryBuilder::new()
                 .insert(data.clone())
@@ -62,30 +43,14 @@ impl DatabaseAdapter for SqliteAdapter {
                 .build()?;
             
             // 构建参数化查询，使用正确的参数顺序
-            let mut query = sqlx::query(&sql);
-            for param in &params {
-                match param {
-                    DataValue::String(s) => { query = query.bind(s); },
-                    DataValue::Int(i) => { query = query.bind(i); },
-                    DataValue::Float(f) => { query = query.bind(f); },
-                    DataValue::Bool(b) => { query = query.bind(b); },
-                    DataValue::Bytes(bytes) => { query = query.bind(bytes); },
-                    DataValue::DateTime(dt) => { query = query.bind(dt.to_rfc3339()); },
-                    DataValue::Uuid(uuid) => { query = query.bind(uuid.to_string()); },
-                    DataValue::Json(json) => { query = query.bind(json.to_string()); },
-                    DataValue::Array(_) => {
-                        let json = param.to_json_value().to_string();
-                        query = query.bind(json);
-                    },
-                    DataValue::Object(_) => {
-                        let json = param.to_json_value().to_string();
-                        query = query.bind(json);
-                    },
-                    DataValue::Null => { query = query.bind(Option::<String>::None); },
-                }
-            }
+            crate::types::query_log::log_sql(&sql, &params);
+            let build_query = || Self::bind_insert_params(sqlx::query(&sql), &params);
             
-            let result = query.execute(pool).await
+            // 处于适配器事务作用域（如 seed）时在该事务中执行
+            let result = match crate::adapter::transaction::current_sqlite_transaction().await {
+                Some(mut tx) => build_query().execute(&mut **tx).await,
+                None => self.retry_on_busy(|| build_query().execute(pool)).await,
+            }
                 .map_err(|e| QuickDbError::QueryError {
                     message: format!("执行SQLite插入失败: {}", e),
                 })?;
@@ -111,6 +76,126 @@ impl DatabaseAdapter for SqliteAdapter {
             }
     }
 
+    async fn create_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        data_list: &[HashMap<String, DataValue>],
+        id_strategy: &IdStrategy,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let pool = match connection {
+            DatabaseConnection::SQLite(pool) => pool,
+            _ => return Err(QuickDbError::ConnectionError {
+                message: "Invalid connection type for SQLite".to_string(),
+            }),
+        };
+        if data_list.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        sqlite_schema::ensure_table(self, connection, table, id_strategy).await?;
+
+        let has_id = |data: &HashMap<String, DataValue>| {
+            data.get("id").is_some_and(|id| !matches!(id, DataValue::Null))
+        };
+        let mut results = Vec::with_capacity(data_list.len());
+        for (columns, chunk) in crate::adapter::insert_many_batches(DatabaseType::SQLite, data_list) {
+            // 记录都带有ID时直接返回，否则与单条插入返回 last_insert_rowid 一样通过 RETURNING 取回 rowid
+            let returning = !chunk.iter().all(has_id);
+            let sql = crate::adapter::insert_many_sql(DatabaseType::SQLite, table, &columns, chunk.len(), returning.then_some("rowid"))?;
+            let params = crate::adapter::upsert_params(chunk, &columns);
+            crate::types::query_log::log_sql(&sql, &params);
+            let build_query = || Self::bind_insert_params(sqlx::query(&sql), &params);
+
+            let rows = match crate::adapter::transaction::current_sqlite_transaction().await {
+                Some(mut tx) => build_query().fetch_all(&mut **tx).await,
+                None => self.retry_on_busy(|| build_query().fetch_all(pool)).await,
+            }
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("执行SQLite批量插入失败: {}", e),
+                })?;
+
+            if !returning {
+                results.extend(chunk.iter().map(|data| data["id"].clone()));
+                continue;
+            }
+            let mut ids = rows.iter()
+                .map(|row| row.try_get::<i64, _>(0).map(DataValue::Int))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| QuickDbError::QueryError {
+                    message: format!("解析SQLite批量插入返回的ID失败: {}", e),
+                })?;
+            // rowid按插入顺序递增，RETURNING 的行序不保证与插入顺序一致，排序后与输入一一对应
+            if !chunk.iter().any(has_id) {
+                ids.sort_by_key(|id| match id {
+                    DataValue::Int(i) => *i,
+                    _ => i64::MAX,
+                });
+            }
+            results.extend(ids);
+        }
+        Ok(results)
+    }
+
+    async fn upsert_many(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        rows: &[HashMap<String, DataValue>],
+        conflict_fields: &[String],
+        _id_strategy: &IdStrategy,
+    ) -> QuickDbResult<UpsertResult> {
+        let pool = match connection {
+            DatabaseConnection::SQLite(pool) => pool,
+            _ => return Err(QuickDbError::ConnectionError {
+                message: "Invalid connection type for SQLite".to_string(),
+            }),
+        };
+
+        let columns = crate::adapter::upsert_columns(rows, conflict_fields)?;
+        let chunk_size = crate::adapter::upsert_chunk_size(DatabaseType::SQLite, columns.len());
+        for chunk in rows.chunks(chunk_size) {
+            let sql = crate::adapter::upsert_many_sql(DatabaseType::SQLite, table, &columns, conflict_fields, chunk.len())?;
+            let params = crate::adapter::upsert_params(chunk, &columns);
+            self.execute_update(pool, &sql, &params).await
+                .map_err(|e| crate::adapter::upsert_conflict_error(table, conflict_fields, e))?;
+        }
+
+        // SQLite 的影响行数不区分插入与更新
+        Ok(UpsertResult::undistinguished(rows.len() as u64))
+    }
+
+    async fn upsert(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        data: &HashMap<String, DataValue>,
+        conflict_fields: &[String],
+        _id_strategy: &IdStrategy,
+    ) -> QuickDbResult<UpsertOutcome> {
+        let pool = match connection {
+            DatabaseConnection::SQLite(pool) => pool,
+            _ => return Err(QuickDbError::ConnectionError {
+                message: "Invalid connection type for SQLite".to_string(),
+            }),
+        };
+
+        let rows = std::slice::from_ref(data);
+        let columns = crate::adapter::upsert_columns(rows, conflict_fields)?;
+        let sql = crate::adapter::upsert_many_sql(DatabaseType::SQLite, table, &columns, conflict_fields, 1)?;
+        let params = crate::adapter::upsert_params(rows, &columns);
+        let exists_sql = crate::adapter::upsert_exists_sql(DatabaseType::SQLite, table, conflict_fields, false)?;
+        let exists_params = crate::adapter::upsert_params(rows, conflict_fields);
+
+        // 影响行数不区分插入与更新，在同一事务中先检查记录是否已存在
+        crate::adapter::transaction::run_in_transaction(connection, async {
+            let existing = self.execute_query(pool, &exists_sql, &exists_params).await?;
+            self.execute_update(pool, &sql, &params).await
+                .map_err(|e| crate::adapter::upsert_conflict_error(table, conflict_fields, e))?;
+            Ok(if existing.is_empty() { UpsertOutcome::Inserted } else { UpsertOutcome::Updated })
+        }).await
+    }
+
     async fn find_by_id(
         &self,
         connection: &DatabaseConnection,
@@ -126,6 +211,7 @@ impl DatabaseAdapter for SqliteAdapter {
         {
             let sql = format!("SELECT * FROM {} WHERE id = ? LIMIT 1", table);
             
+            crate::types::query_log::log_sql(&sql, std::slice::from_ref(id));
             let mut query = sqlx::query(&sql);
             match id {
                 DataValue::String(s) => { query = query.bind(s); },
@@ -186,17 +272,29 @@ impl DatabaseAdapter for SqliteAdapter {
             }),
         };
         {
-            let (sql, params) = SqlQueryBuilder::new()
+            let mut builder = SqlQueryBuilder::new()
                 .database_type(DatabaseType::SQLite)
-                .select(&["*"])
+                .select_fields(&options.fields)
+                .select_expr(&options.select_exprs)
                 .from(table)
                 .where_condition_groups(condition_groups)
-                .limit(options.pagination.as_ref().map(|p| p.limit).unwrap_or(1000))
-                .offset(options.pagination.as_ref().map(|p| p.skip).unwrap_or(0))
-                .build()?;
+                .collation(options.collation.as_deref());
+
+            // 添加排序
+            for sort_field in &options.sort {
+                builder = builder.order_by(&sort_field.field, sort_field.direction.clone());
+            }
+
+            builder = match &options.pagination {
+                Some(pagination) => builder.pagination(pagination),
+                None => builder.limit(1000),
+            };
+
+            let (sql, params) = builder.build()?;
 
             debug!("执行SQLite条件组合查询: {}", sql);
 
+            crate::types::query_log::log_sql(&sql, &params);
             let mut query = sqlx::query(&sql);
             for param in &params {
                 match param {
@@ -209,7 +307,11 @@ impl DatabaseAdapter for SqliteAdapter {
                 }
             }
 
-            let rows = query.fetch_all(pool).await
+            // 处于适配器事务作用域（如显式事务）时在该事务中执行，可读到事务内未提交的写入
+            let rows = match crate::adapter::transaction::current_sqlite_transaction().await {
+                Some(mut tx) => query.fetch_all(&mut **tx).await,
+                None => query.fetch_all(pool).await,
+            }
                 .map_err(|e| QuickDbError::QueryError {
                     message: format!("执行SQLite条件组合查询失败: {}", e),
                 })?;
@@ -244,18 +346,26 @@ impl DatabaseAdapter for SqliteAdapter {
                 .where_conditions(conditions)
                 .build()?;
             
-            let mut query = sqlx::query(&sql);
-            for param in &params {
-                match param {
-                    DataValue::String(s) => { query = query.bind(s); },
-                    DataValue::Int(i) => { query = query.bind(i); },
-                    DataValue::Float(f) => { query = query.bind(f); },
-                    DataValue::Bool(b) => { query = query.bind(b); },
-                    _ => { query = query.bind(param.to_string()); },
+            crate::types::query_log::log_sql(&sql, &params);
+            let build_query = || {
+                let mut query = sqlx::query(&sql);
+                for param in &params {
+                    match param {
+                        DataValue::String(s) => { query = query.bind(s); },
+                        DataValue::Int(i) => { query = query.bind(i); },
+                        DataValue::Float(f) => { query = query.bind(f); },
+                        DataValue::Bool(b) => { query = query.bind(b); },
+                        _ => { query = query.bind(param.to_string()); },
+                    }
                 }
-            }
+                query
+            };
             
-            let result = query.execute(pool).await
+            // 处于适配器事务作用域（如试运行）时在该事务中执行
+            let result = match crate::adapter::transaction::current_sqlite_transaction().await {
+                Some(mut tx) => build_query().execute(&mut **tx).await,
+                None => self.retry_on_busy(|| build_query().execute(pool)).await,
+            }
                 .map_err(|e| QuickDbError::QueryError {
                     message: format!("执行SQLite更新失败: {}", e),
                 })?;
@@ -328,6 +438,12 @@ impl DatabaseAdapter for SqliteAdapter {
                     set_clauses.push(format!("{} = {} * (1.0 - ?/100.0)", operation.field, operation.field));
                     params.push(operation.value.clone());
                 }
+                crate::types::UpdateOperator::JsonMerge => {
+                    // JSON1扩展的json_patch实现RFC 7396合并语义
+                    let patch = operation.json_merge_patch()?;
+                    set_clauses.push(format!("{} = json_patch(COALESCE({}, '{{}}'), ?)", operation.field, operation.field));
+                    params.push(DataValue::String(serde_json::Value::Object(patch).to_string()));
+                }
             }
         }
 
@@ -373,6 +489,28 @@ impl DatabaseAdapter for SqliteAdapter {
         sqlite_query::delete_by_id(self, connection, table, id).await
     }
 
+    async fn read_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        offset: u64,
+        len: u64,
+        _gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<Option<Vec<u8>>> {
+        sqlite_blob::read_chunk(connection, location, offset, len).await
+    }
+
+    async fn write_blob_chunk(
+        &self,
+        connection: &DatabaseConnection,
+        location: &BlobLocation,
+        data: &[u8],
+        append: bool,
+        _gridfs_bucket: Option<&str>,
+    ) -> QuickDbResult<bool> {
+        sqlite_blob::write_chunk(self, connection, location, data, append).await
+    }
+
     async fn count(
         &self,
         connection: &DatabaseConnection,
@@ -382,6 +520,28 @@ impl DatabaseAdapter for SqliteAdapter {
         sqlite_query::count(self, connection, table, conditions).await
     }
 
+    async fn time_bucket(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        field: &str,
+        interval: Interval,
+        conditions: &[QueryCondition],
+    ) -> QuickDbResult<Vec<(DataValue, u64)>> {
+        sqlite_query::time_bucket(self, connection, table, field, interval, conditions).await
+    }
+
+    async fn aggregate(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        aggregations: &[Aggregation],
+        conditions: &[QueryCondition],
+        group_by: &[String],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        sqlite_query::aggregate(self, connection, table, aggregations, conditions, group_by).await
+    }
+
     async fn exists(
         &self,
         connection: &DatabaseConnection,
@@ -406,10 +566,9 @@ impl DatabaseAdapter for SqliteAdapter {
         connection: &DatabaseConnection,
         table: &str,
         index_name: &str,
-        fields: &[String],
-        unique: bool,
+        index: &IndexDefinition,
     ) -> QuickDbResult<()> {
-        sqlite_schema::create_index(self, connection, table, index_name, fields, unique).await
+        sqlite_schema::create_index(self, connection, table, index_name, index).await
     }
 
     async fn table_exists(
@@ -420,14 +579,52 @@ impl DatabaseAdapter for SqliteAdapter {
         sqlite_schema::table_exists(self, connection, table).await
     }
 
+    async fn get_table_columns(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        sqlite_schema::get_table_columns(self, connection, table).await
+    }
+
+    async fn list_index_names(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+    ) -> QuickDbResult<Vec<String>> {
+        sqlite_schema::list_index_names(self, connection, table).await
+    }
+
+    async fn add_column(
+        &self,
+        connection: &DatabaseConnection,
+        table: &str,
+        column: &str,
+        field_definition: &FieldDefinition,
+    ) -> QuickDbResult<()> {
+        sqlite_schema::add_column(self, connection, table, column, field_definition).await
+    }
+
     async fn drop_table(
         &self,
         connection: &DatabaseConnection,
         table: &str,
     ) -> QuickDbResult<()> {
+        self.forget_table(table);
         sqlite_schema::drop_table(self, connection, table).await
     }
 
+    async fn rename_table(
+        &self,
+        connection: &DatabaseConnection,
+        from: &str,
+        to: &str,
+    ) -> QuickDbResult<()> {
+        self.forget_table(from);
+        self.forget_table(to);
+        sqlite_schema::rename_table(self, connection, from, to).await
+    }
+
     async fn get_server_version(
         &self,
         connection: &DatabaseConnection,
@@ -531,6 +728,7 @@ impl DatabaseAdapter for SqliteAdapter {
 
         debug!("执行存储过程查询SQL: {}", final_sql);
 
+        crate::types::query_log::log_sql(&final_sql, &[]);
         let rows = sqlx::query(&final_sql).fetch_all(pool).await
             .map_err(|e| QuickDbError::QueryError {
                 message: format!("执行存储过程查询失败: {}", e),
@@ -555,6 +753,70 @@ impl DatabaseAdapter for SqliteAdapter {
         debug!("存储过程 {} 执行完成，返回 {} 条记录", procedure_name, result.len());
         Ok(result)
     }
+
+    async fn execute_batch(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+    ) -> QuickDbResult<u64> {
+        let DatabaseConnection::SQLite(pool) = connection else {
+            return Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望SQLite连接".to_string(),
+            });
+        };
+        let statements = crate::adapter::split_sql_statements(DatabaseType::SQLite, sql)?;
+        if statements.is_empty() {
+            return Ok(0);
+        }
+
+        // 逐条执行以便定位出错的语句，事务未提交即被丢弃时自动回滚
+        let mut tx = pool.begin().await.map_err(|e| QuickDbError::TransactionError {
+            message: format!("开启事务失败: {}", e),
+        })?;
+        for (index, statement) in statements.iter().enumerate() {
+            debug!("执行SQLite SQL脚本第 {} 条语句: {}", index + 1, statement);
+            crate::types::query_log::log_sql(statement, &[]);
+            sqlx::query(statement).execute(&mut *tx).await.map_err(|e| QuickDbError::QueryError {
+                message: format!("SQL脚本第 {} 条语句执行失败: {}", index + 1, e),
+            })?;
+        }
+        tx.commit().await.map_err(|e| QuickDbError::TransactionError {
+            message: format!("提交事务失败: {}", e),
+        })?;
+        Ok(statements.len() as u64)
+    }
+
+    async fn query_raw(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+        params: &[DataValue],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if let DatabaseConnection::SQLite(pool) = connection {
+            debug!("执行SQLite原始查询SQL: {}", sql);
+            self.execute_query(pool, sql, params).await
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望SQLite连接".to_string(),
+            })
+        }
+    }
+
+    async fn execute_raw_update(
+        &self,
+        connection: &DatabaseConnection,
+        sql: &str,
+        params: &[DataValue],
+    ) -> QuickDbResult<u64> {
+        if let DatabaseConnection::SQLite(pool) = connection {
+            debug!("执行SQLite原始写入SQL: {}", sql);
+            self.execute_update(pool, sql, params).await
+        } else {
+            Err(QuickDbError::ConnectionError {
+                message: "连接类型不匹配，期望SQLite连接".to_string(),
+            })
+        }
+    }
 }
 
 impl SqliteAdapter {
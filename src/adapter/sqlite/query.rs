@@ -26,18 +26,26 @@ pub(crate) async fn delete(
             .where_conditions(conditions)
             .build()?;
 
-        let mut query = sqlx::query(&sql);
-        for param in &params {
-            match param {
-                DataValue::String(s) => { query = query.bind(s); },
-                DataValue::Int(i) => { query = query.bind(i); },
-                DataValue::Float(f) => { query = query.bind(f); },
-                DataValue::Bool(b) => { query = query.bind(b); },
-                _ => { query = query.bind(param.to_string()); },
+        crate::types::query_log::log_sql(&sql, &params);
+        let build_query = || {
+            let mut query = sqlx::query(&sql);
+            for param in &params {
+                match param {
+                    DataValue::String(s) => { query = query.bind(s); },
+                    DataValue::Int(i) => { query = query.bind(i); },
+                    DataValue::Float(f) => { query = query.bind(f); },
+                    DataValue::Bool(b) => { query = query.bind(b); },
+                    _ => { query = query.bind(param.to_string()); },
+                }
             }
-        }
+            query
+        };
 
-        let result = query.execute(pool).await
+        // 处于适配器事务作用域（如试运行）时在该事务中执行
+        let result = match crate::adapter::transaction::current_sqlite_transaction().await {
+            Some(mut tx) => build_query().execute(&mut **tx).await,
+            None => adapter.retry_on_busy(|| build_query().execute(pool)).await,
+        }
             .map_err(|e| QuickDbError::QueryError {
                 message: format!("执行SQLite删除失败: {}", e),
             })?;
@@ -83,6 +91,7 @@ pub(crate) async fn count(
             .where_conditions(conditions)
             .build()?;
 
+        crate::types::query_log::log_sql(&sql, &params);
         let mut query = sqlx::query(&sql);
         for param in &params {
             match param {
@@ -108,6 +117,47 @@ pub(crate) async fn count(
     }
 }
 
+/// SQLite按时间段计数操作
+pub(crate) async fn time_bucket(
+    adapter: &SqliteAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    field: &str,
+    interval: Interval,
+    conditions: &[QueryCondition],
+) -> QuickDbResult<Vec<(DataValue, u64)>> {
+    let pool = match connection {
+        DatabaseConnection::SQLite(pool) => pool,
+        _ => return Err(QuickDbError::ConnectionError {
+            message: "Invalid connection type for SQLite".to_string(),
+        }),
+    };
+
+    let (sql, params) = crate::adapter::query_builder::time_bucket_sql(DatabaseType::SQLite, table, field, interval, conditions)?;
+    let rows = adapter.execute_query(pool, &sql, &params).await?;
+    crate::adapter::query_builder::time_bucket_rows(rows)
+}
+
+/// SQLite聚合操作
+pub(crate) async fn aggregate(
+    adapter: &SqliteAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    aggregations: &[Aggregation],
+    conditions: &[QueryCondition],
+    group_by: &[String],
+) -> QuickDbResult<Vec<DataValue>> {
+    let pool = match connection {
+        DatabaseConnection::SQLite(pool) => pool,
+        _ => return Err(QuickDbError::ConnectionError {
+            message: "Invalid connection type for SQLite".to_string(),
+        }),
+    };
+
+    let (sql, params) = crate::adapter::query_builder::aggregate_sql(DatabaseType::SQLite, table, aggregations, conditions, group_by)?;
+    adapter.execute_query(pool, &sql, &params).await
+}
+
 /// SQLite存在性检查操作
 pub(crate) async fn exists(
     adapter: &SqliteAdapter,
@@ -115,6 +165,43 @@ pub(crate) async fn exists(
     table: &str,
     conditions: &[QueryCondition],
 ) -> QuickDbResult<bool> {
-    let count = count(adapter, connection, table, conditions).await?;
-    Ok(count > 0)
+    let pool = match connection {
+        DatabaseConnection::SQLite(pool) => pool,
+        _ => return Err(QuickDbError::ConnectionError {
+            message: "Invalid connection type for SQLite".to_string(),
+        }),
+    };
+
+    // 使用 EXISTS + LIMIT 1，命中第一条记录即返回，避免全表计数
+    let (inner_sql, params) = SqlQueryBuilder::new()
+        .select(&["1"])
+        .from(table)
+        .where_conditions(conditions)
+        .limit(1)
+        .build()?;
+    let sql = format!("SELECT EXISTS({}) AS found", inner_sql);
+
+    crate::types::query_log::log_sql(&sql, &params);
+    let mut query = sqlx::query(&sql);
+    for param in &params {
+        match param {
+            DataValue::String(s) => { query = query.bind(s); },
+            DataValue::Int(i) => { query = query.bind(i); },
+            DataValue::Float(f) => { query = query.bind(f); },
+            DataValue::Bool(b) => { query = query.bind(b); },
+            _ => { query = query.bind(param.to_string()); },
+        }
+    }
+
+    let row = query.fetch_one(pool).await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("执行SQLite存在性检查失败: {}", e),
+        })?;
+
+    let found: i64 = row.try_get("found")
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("获取存在性检查结果失败: {}", e),
+        })?;
+
+    Ok(found != 0)
 }
\ No newline at end of file
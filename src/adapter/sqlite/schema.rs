@@ -1,14 +1,44 @@
 use crate::adapter::SqliteAdapter;
 use crate::adapter::{DatabaseAdapter, SqlQueryBuilder};
+use crate::adapter::query_builder::rename_table_sql;
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
-use crate::model::{FieldDefinition, FieldType};
+use crate::model::{FieldDefinition, FieldType, IndexDefinition};
 use crate::pool::DatabaseConnection;
 use async_trait::async_trait;
 use rat_logger::debug;
 use sqlx::{sqlite::SqliteRow, Row, Column};
 use std::collections::HashMap;
 
+/// 将字段类型映射为SQLite列类型
+pub(crate) fn column_type(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::String { max_length, .. } => {
+            if let Some(max_len) = max_length {
+                format!("VARCHAR({})", max_len)
+            } else {
+                "TEXT".to_string()
+            }
+        },
+        FieldType::Integer { .. } => "INTEGER".to_string(),
+        FieldType::BigInteger => "INTEGER".to_string(), // SQLite只有INTEGER类型
+        FieldType::Float { .. } => "REAL".to_string(),
+        FieldType::Double => "REAL".to_string(), // SQLite只有REAL类型
+        FieldType::Text => "TEXT".to_string(),
+        FieldType::Boolean => "INTEGER".to_string(),
+        FieldType::DateTime => "TEXT".to_string(),
+        FieldType::Date => "TEXT".to_string(),
+        FieldType::Time => "TEXT".to_string(),
+        FieldType::Json => "TEXT".to_string(),
+        FieldType::Uuid => "TEXT".to_string(),
+        FieldType::Binary => "BLOB".to_string(),
+        FieldType::Decimal { precision: _, scale: _ } => "REAL".to_string(), // SQLite没有DECIMAL，使用REAL
+        FieldType::Array { .. } => "TEXT".to_string(), // 存储为JSON
+        FieldType::Object { .. } => "TEXT".to_string(), // 存储为JSON
+        FieldType::Reference { .. } => "TEXT".to_string(), // 存储引用ID
+    }
+}
+
 /// SQLite创建表操作
 pub(crate) async fn create_table(
     adapter: &SqliteAdapter,
@@ -38,31 +68,7 @@ pub(crate) async fn create_table(
                 sql.push_str(", ");
             }
 
-            let sql_type = match &field_definition.field_type {
-                FieldType::String { max_length, .. } => {
-                    if let Some(max_len) = max_length {
-                        format!("VARCHAR({})", max_len)
-                    } else {
-                        "TEXT".to_string()
-                    }
-                },
-                FieldType::Integer { .. } => "INTEGER".to_string(),
-                FieldType::BigInteger => "INTEGER".to_string(), // SQLite只有INTEGER类型
-                FieldType::Float { .. } => "REAL".to_string(),
-                FieldType::Double => "REAL".to_string(), // SQLite只有REAL类型
-                FieldType::Text => "TEXT".to_string(),
-                FieldType::Boolean => "INTEGER".to_string(),
-                FieldType::DateTime => "TEXT".to_string(),
-                FieldType::Date => "TEXT".to_string(),
-                FieldType::Time => "TEXT".to_string(),
-                FieldType::Json => "TEXT".to_string(),
-                FieldType::Uuid => "TEXT".to_string(),
-                FieldType::Binary => "BLOB".to_string(),
-                FieldType::Decimal { precision: _, scale: _ } => "REAL".to_string(), // SQLite没有DECIMAL，使用REAL
-                FieldType::Array { .. } => "TEXT".to_string(), // 存储为JSON
-                FieldType::Object { .. } => "TEXT".to_string(), // 存储为JSON
-                FieldType::Reference { .. } => "TEXT".to_string(), // 存储引用ID
-            };
+            let sql_type = column_type(&field_definition.field_type);
 
             // 如果是id字段，添加主键约束
             // 添加NULL或NOT NULL约束
@@ -72,10 +78,15 @@ pub(crate) async fn create_table(
                 ""
             };
 
-            if field_name == "id" {
+            // 自增策略下id列必须是INTEGER PRIMARY KEY，否则SQLite不会生成ID
+            if field_name == "id" && matches!(id_strategy, IdStrategy::AutoIncrement) {
+                sql.push_str("id INTEGER PRIMARY KEY AUTOINCREMENT");
+            } else if field_name == "id" {
                 sql.push_str(&format!("{} {} PRIMARY KEY", field_name, sql_type));
             } else {
-                sql.push_str(&format!("{} {} {}", field_name, sql_type, null_constraint));
+                let collate = crate::adapter::query_builder::column_collate_clause(DatabaseType::SQLite, field_definition)?;
+                let default = crate::adapter::query_builder::column_default_clause(DatabaseType::SQLite, field_definition)?;
+                sql.push_str(&format!("{} {}{}{} {}", field_name, sql_type, collate, default, null_constraint));
             }
             has_fields = true;
         }
@@ -97,8 +108,7 @@ pub(crate) async fn create_index(
     connection: &DatabaseConnection,
     table: &str,
     index_name: &str,
-    fields: &[String],
-    unique: bool,
+    index: &IndexDefinition,
 ) -> QuickDbResult<()> {
     let pool = match connection {
         DatabaseConnection::SQLite(pool) => pool,
@@ -107,11 +117,16 @@ pub(crate) async fn create_index(
         }),
     };
     {
-        let unique_keyword = if unique { "UNIQUE " } else { "" };
-        let fields_str = fields.join(", ");
+        if index.sparse {
+            debug!("SQLite不支持稀疏索引，忽略sparse选项: {}", index_name);
+        }
+
+        let unique_keyword = if index.unique { "UNIQUE " } else { "" };
+        let fields_str = crate::adapter::query_builder::index_columns_clause(DatabaseType::SQLite, index)?;
+        let predicate = crate::adapter::query_builder::index_predicate_clause(DatabaseType::SQLite, &index.partial_filter)?;
         let sql = format!(
-            "CREATE {}INDEX IF NOT EXISTS {} ON {} ({})",
-            unique_keyword, index_name, table, fields_str
+            "CREATE {}INDEX IF NOT EXISTS {} ON {} ({}){}",
+            unique_keyword, index_name, table, fields_str, predicate
         );
 
         sqlx::query(&sql).execute(pool).await
@@ -123,6 +138,44 @@ pub(crate) async fn create_index(
     }
 }
 
+/// 写入前确保表存在，不存在时使用预定义的模型元数据创建
+///
+/// 已确认存在的表直接跳过，避免每次写入都查询元数据；没有模型元数据时返回错误
+pub(crate) async fn ensure_table(
+    adapter: &SqliteAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    id_strategy: &IdStrategy,
+) -> QuickDbResult<()> {
+    if !adapter.is_known_table(table) && !adapter.table_exists(connection, table).await? {
+        // 获取表创建锁，防止重复创建
+        let _lock = adapter.acquire_table_lock(table).await;
+        // 再次检查表是否存在（双重检查锁定模式）
+        if !adapter.table_exists(connection, table).await? {
+            // 尝试从模型管理器获取预定义的元数据
+            if let Some(model_meta) = crate::manager::get_model(table) {
+                debug!("表 {} 不存在，使用预定义模型元数据创建", table);
+
+                // 使用模型元数据创建表
+                adapter.create_table(connection, table, &model_meta.fields, id_strategy).await?;
+                // 等待100ms确保数据库事务完全提交
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                debug!("⏱️ 等待100ms确保表 '{}' 创建完成", table);
+            } else {
+                return Err(QuickDbError::ValidationError {
+                    field: "table_creation".to_string(),
+                    message: format!("表 '{}' 不存在，且没有预定义的模型元数据。请先定义模型并使用 define_model! 宏明确指定字段类型。", table),
+                });
+            }
+        } else {
+            debug!("表 {} 已存在，跳过创建", table);
+        }
+        // 锁会在这里自动释放（当 _lock 超出作用域时）
+    }
+    adapter.mark_table_known(table);
+    Ok(())
+}
+
 /// SQLite表存在检查操作
 pub(crate) async fn table_exists(
     adapter: &SqliteAdapter,
@@ -149,6 +202,79 @@ pub(crate) async fn table_exists(
     }
 }
 
+/// SQLite获取表列名操作
+pub(crate) async fn get_table_columns(
+    adapter: &SqliteAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+) -> QuickDbResult<Vec<String>> {
+    let pool = match connection {
+        DatabaseConnection::SQLite(pool) => pool,
+        _ => return Err(QuickDbError::ConnectionError {
+            message: "Invalid connection type for SQLite".to_string(),
+        }),
+    };
+    let sql = "SELECT name FROM pragma_table_info(?)";
+    sqlx::query_scalar::<_, String>(sql)
+        .bind(table)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("获取SQLite表列信息失败: {}", e),
+        })
+}
+
+/// SQLite获取表索引名操作
+pub(crate) async fn list_index_names(
+    adapter: &SqliteAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+) -> QuickDbResult<Vec<String>> {
+    let pool = match connection {
+        DatabaseConnection::SQLite(pool) => pool,
+        _ => return Err(QuickDbError::ConnectionError {
+            message: "Invalid connection type for SQLite".to_string(),
+        }),
+    };
+    let sql = "SELECT name FROM sqlite_master WHERE type='index' AND tbl_name=?";
+    sqlx::query_scalar::<_, String>(sql)
+        .bind(table)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("获取SQLite索引信息失败: {}", e),
+        })
+}
+
+/// SQLite添加列操作，新增列总是允许NULL以兼容已有数据
+pub(crate) async fn add_column(
+    adapter: &SqliteAdapter,
+    connection: &DatabaseConnection,
+    table: &str,
+    column: &str,
+    field_definition: &FieldDefinition,
+) -> QuickDbResult<()> {
+    let pool = match connection {
+        DatabaseConnection::SQLite(pool) => pool,
+        _ => return Err(QuickDbError::ConnectionError {
+            message: "Invalid connection type for SQLite".to_string(),
+        }),
+    };
+    let collate = crate::adapter::query_builder::column_collate_clause(DatabaseType::SQLite, field_definition)?;
+    let sql = format!(
+        "ALTER TABLE {} ADD COLUMN {} {}{}",
+        table, column, column_type(&field_definition.field_type), collate
+    );
+    debug!("执行SQLite添加列: {}", sql);
+
+    sqlx::query(&sql).execute(pool).await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("SQLite添加列失败: {}", e),
+        })?;
+
+    Ok(())
+}
+
 /// SQLite删除表操作
 pub(crate) async fn drop_table(
     adapter: &SqliteAdapter,
@@ -177,6 +303,38 @@ pub(crate) async fn drop_table(
     Ok(())
 }
 
+/// SQLite重命名表操作
+///
+/// 其他表中指向该表的外键会随之更新（需 SQLite 3.26+ 且未开启 `legacy_alter_table`），
+/// 但引用该表的视图和触发器不会改写
+pub(crate) async fn rename_table(
+    _adapter: &SqliteAdapter,
+    connection: &DatabaseConnection,
+    from: &str,
+    to: &str,
+) -> QuickDbResult<()> {
+    let pool = match connection {
+        DatabaseConnection::SQLite(pool) => pool,
+        _ => return Err(QuickDbError::ConnectionError {
+            message: "Invalid connection type for SQLite".to_string(),
+        }),
+    };
+
+    let sql = rename_table_sql(DatabaseType::SQLite, from, to)?;
+
+    debug!("执行SQLite重命名表SQL: {}", sql);
+
+    sqlx::query(&sql)
+        .execute(pool)
+        .await
+        .map_err(|e| QuickDbError::QueryError {
+            message: format!("重命名SQLite表失败: {}", e),
+        })?;
+
+    debug!("成功重命名SQLite表: {} -> {}", from, to);
+    Ok(())
+}
+
 /// SQLite获取服务器版本操作
 pub(crate) async fn get_server_version(
     adapter: &SqliteAdapter,
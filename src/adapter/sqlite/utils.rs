@@ -6,11 +6,57 @@ use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
 use crate::pool::DatabaseConnection;
 use std::collections::HashMap;
-use rat_logger::debug;
+use std::future::Future;
+use std::time::Duration;
+use rat_logger::{debug, warn};
 use sqlx::{Row, sqlite::SqliteRow, Column};
 
+/// 数据库忙重试的首次等待时间（毫秒），之后每次翻倍
+const BUSY_INITIAL_BACKOFF_MS: u64 = 5;
+/// 数据库忙重试的单次等待上限（毫秒）
+const BUSY_MAX_BACKOFF_MS: u64 = 500;
+
+/// 是否为 SQLITE_BUSY / SQLITE_LOCKED（含扩展错误码，如 SQLITE_BUSY_SNAPSHOT）
+fn is_busy_error(error: &sqlx::Error) -> bool {
+    error.as_database_error()
+        .and_then(|e| e.code())
+        .and_then(|code| code.parse::<i32>().ok())
+        .map(|code| matches!(code & 0xff, 5 | 6))
+        .unwrap_or(false)
+}
+
+/// 第 `attempt` 次重试（从0开始）前的等待时间，按共用的退避规则随机取值，
+/// 避免同时被锁住的写入者同时重试再次冲突
+fn busy_backoff(attempt: u32) -> Duration {
+    crate::transaction::retry::jittered_backoff(BUSY_INITIAL_BACKOFF_MS, BUSY_MAX_BACKOFF_MS, attempt, rand::random())
+}
+
 
 impl SqliteAdapter {
+    /// 执行写语句，遇到数据库忙时按带抖动的指数退避重试，最多重试 `busy_retry_count` 次
+    ///
+    /// 返回忙错误的语句没有产生任何修改，重新执行是安全的。闭包每次调用都要重新构建语句；
+    /// 事务中的语句不经过这里，由事务整体重试处理
+    pub(crate) async fn retry_on_busy<T, F, Fut>(&self, mut statement: F) -> Result<T, sqlx::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match statement().await {
+                Err(e) if is_busy_error(&e) && attempt < self.busy_retry_count => {
+                    let delay = busy_backoff(attempt);
+                    attempt += 1;
+                    warn!("SQLite数据库忙，{}ms 后进行第 {}/{} 次重试: {}",
+                          delay.as_millis(), attempt, self.busy_retry_count, e);
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// 将sqlx的行转换为DataValue映射
     pub(crate) fn row_to_data_map(&self, row: &SqliteRow) -> QuickDbResult<HashMap<String, DataValue>> {
         let mut map = HashMap::new();
@@ -74,8 +120,27 @@ impl SqliteAdapter {
         sql: &str,
         params: &[DataValue],
     ) -> QuickDbResult<u64> {
-        let mut query = sqlx::query(sql);
+        crate::types::query_log::log_sql(sql, params);
+
+        debug!("执行SQLite更新SQL: {}", sql);
+
+        // 处于适配器事务作用域（如试运行）时在该事务中执行
+        let result = match crate::adapter::transaction::current_sqlite_transaction().await {
+            Some(mut tx) => Self::bind_params(sqlx::query(sql), params).execute(&mut **tx).await,
+            None => self.retry_on_busy(|| Self::bind_params(sqlx::query(sql), params).execute(pool)).await,
+        }
+            .map_err(|e| QuickDbError::QueryError {
+                message: format!("SQLite更新失败: {}", e),
+            })?;
+
+        Ok(result.rows_affected())
+    }
 
+    /// 按顺序绑定查询参数
+    pub(crate) fn bind_params<'q>(
+        mut query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+        params: &'q [DataValue],
+    ) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
         // 绑定参数
         for param in params {
             query = match param {
@@ -95,16 +160,100 @@ impl SqliteAdapter {
                 DataValue::Object(obj) => query.bind(serde_json::to_string(obj).unwrap_or_default()),
             };
         }
+        query
+    }
 
-        debug!("执行SQLite更新SQL: {}", sql);
+    /// 按顺序绑定插入语句的参数
+    ///
+    /// 时间按RFC3339字符串写入，数组和对象写入不带类型标签的JSON，单条与批量插入共用
+    pub(crate) fn bind_insert_params<'q>(
+        mut query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+        params: &'q [DataValue],
+    ) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+        for param in params {
+            query = match param {
+                DataValue::String(s) => query.bind(s),
+                DataValue::Int(i) => query.bind(i),
+                DataValue::Float(f) => query.bind(f),
+                DataValue::Bool(b) => query.bind(b),
+                DataValue::Bytes(bytes) => query.bind(bytes),
+                DataValue::DateTime(dt) => query.bind(dt.to_rfc3339()),
+                DataValue::Uuid(uuid) => query.bind(uuid.to_string()),
+                DataValue::Json(json) => query.bind(json.to_string()),
+                DataValue::Array(_) | DataValue::Object(_) => query.bind(param.to_json_value().to_string()),
+                DataValue::Null => query.bind(Option::<String>::None),
+            };
+        }
+        query
+    }
+
+    /// 执行查询并返回结果，每行为一个 `DataValue::Object`
+    pub(crate) async fn execute_query(
+        &self,
+        pool: &sqlx::Pool<sqlx::Sqlite>,
+        sql: &str,
+        params: &[DataValue],
+    ) -> QuickDbResult<Vec<DataValue>> {
+        crate::types::query_log::log_sql(sql, params);
+        let query = Self::bind_params(sqlx::query(sql), params);
+
+        debug!("执行SQLite查询SQL: {}", sql);
 
-        let result = query.execute(pool)
-            .await
+        // 处于适配器事务作用域（如显式事务）时在该事务中执行，可读到事务内未提交的写入
+        let rows = match crate::adapter::transaction::current_sqlite_transaction().await {
+            Some(mut tx) => query.fetch_all(&mut **tx).await,
+            None => query.fetch_all(pool).await,
+        }
             .map_err(|e| QuickDbError::QueryError {
-                message: format!("SQLite更新失败: {}", e),
+                message: format!("SQLite查询失败: {}", e),
             })?;
 
-        Ok(result.rows_affected())
+        rows.iter()
+            .map(|row| self.row_to_data_map(row).map(DataValue::Object))
+            .collect()
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqliteConnectOptions;
+    use sqlx::{ConnectOptions, Connection};
+
+    #[tokio::test]
+    async fn test_retry_on_busy_waits_for_lock_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = SqliteConnectOptions::new()
+            .filename(dir.path().join("busy.db"))
+            .create_if_missing(true)
+            .busy_timeout(Duration::ZERO);
+        let pool = sqlx::SqlitePool::connect_with(options.clone()).await.unwrap();
+        sqlx::query("CREATE TABLE t (v INTEGER)").execute(&pool).await.unwrap();
+
+        // 另一个连接持有写锁，50ms 后释放
+        let mut holder = options.connect().await.unwrap();
+        sqlx::query("BEGIN EXCLUSIVE").execute(&mut holder).await.unwrap();
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            sqlx::query("COMMIT").execute(&mut holder).await.unwrap();
+            holder.close().await.unwrap();
+        });
+
+        let insert = || sqlx::query("INSERT INTO t (v) VALUES (1)").execute(&pool);
+        let error = SqliteAdapter::new().with_busy_retry_count(0).retry_on_busy(insert).await.unwrap_err();
+        assert!(is_busy_error(&error));
+
+        let result = SqliteAdapter::new().with_busy_retry_count(20).retry_on_busy(insert).await.unwrap();
+        assert_eq!(result.rows_affected(), 1);
+        release.await.unwrap();
+    }
+
+    #[test]
+    fn test_busy_backoff_is_bounded() {
+        for attempt in 0..40 {
+            let delay = busy_backoff(attempt).as_millis() as u64;
+            assert!(delay <= BUSY_MAX_BACKOFF_MS);
+        }
+        assert!(busy_backoff(0).as_millis() as u64 <= BUSY_INITIAL_BACKOFF_MS);
+    }
+}
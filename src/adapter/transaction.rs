@@ -0,0 +1,289 @@
+//! 适配器事务作用域
+//!
+//! 让多次适配器调用共享同一个数据库事务：[`AdapterTransaction::begin`] 从连接池取出一个连接开启事务，
+//! 在 [`AdapterTransaction::scope`] 内执行的插入、更新和删除语句都会加入该事务，结束后由调用方统一提交或回滚。
+//! 用于需要整体成功、失败时整体回滚的多表写入（如 `seed`）、总是回滚的试运行，以及显式事务句柄。
+//! MongoDB 的事务基于客户端会话，只由显式事务句柄使用，需要副本集或分片集群部署。
+//!
+//! 建表等DDL仍在连接池上执行，调用方应在开启事务前确保表已存在，
+//! 避免 SQLite 在同一数据库上互相等待写锁。
+
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::pool::DatabaseConnection;
+use crate::transaction::{IsolationLevel, SavepointAction};
+
+tokio::task_local! {
+    static ACTIVE_TRANSACTION: AdapterTransaction;
+}
+
+/// 跨适配器调用共享的数据库事务
+#[derive(Clone)]
+pub(crate) enum AdapterTransaction {
+    #[cfg(feature = "sqlite-support")]
+    SQLite(Arc<Mutex<sqlx::Transaction<'static, sqlx::Sqlite>>>),
+    #[cfg(feature = "postgres-support")]
+    PostgreSQL(Arc<Mutex<sqlx::Transaction<'static, sqlx::Postgres>>>),
+    #[cfg(feature = "mysql-support")]
+    MySQL(Arc<Mutex<sqlx::Transaction<'static, sqlx::MySql>>>),
+    #[cfg(feature = "mongodb-support")]
+    MongoDB(Arc<Mutex<mongodb::ClientSession>>),
+}
+
+impl AdapterTransaction {
+    /// 检查连接是否支持跨表事务写入（`seed`、试运行），不支持时返回错误
+    pub(crate) fn ensure_supported(connection: &DatabaseConnection) -> QuickDbResult<()> {
+        match connection {
+            #[cfg(feature = "sqlite-support")]
+            DatabaseConnection::SQLite(_) => Ok(()),
+            #[cfg(feature = "postgres-support")]
+            DatabaseConnection::PostgreSQL(_) => Ok(()),
+            #[cfg(feature = "mysql-support")]
+            DatabaseConnection::MySQL(_) => Ok(()),
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// 以指定隔离级别在连接上开启事务，MongoDB需通过 [`AdapterTransaction::begin_mongo`] 开启
+    ///
+    /// MySQL 的 `SET TRANSACTION` 只作用于同一连接上的下一个事务，在 `BEGIN` 之前执行；
+    /// PostgreSQL 在 `BEGIN` 之后、第一条查询之前执行；SQLite 的事务始终串行化，只做校验
+    pub(crate) async fn begin(connection: &DatabaseConnection, isolation: IsolationLevel) -> QuickDbResult<Self> {
+        match connection {
+            #[cfg(feature = "sqlite-support")]
+            DatabaseConnection::SQLite(pool) => {
+                isolation.set_transaction_sql(&crate::types::DatabaseType::SQLite)?;
+                Ok(Self::SQLite(Arc::new(Mutex::new(pool.begin().await.map_err(|e| transaction_error("开启", e))?))))
+            }
+            #[cfg(feature = "postgres-support")]
+            DatabaseConnection::PostgreSQL(pool) => {
+                let mut tx = pool.begin().await.map_err(|e| transaction_error("开启", e))?;
+                if let Some(sql) = isolation.set_transaction_sql(&crate::types::DatabaseType::PostgreSQL)? {
+                    sqlx::query(&sql).execute(&mut *tx).await.map_err(isolation_error)?;
+                }
+                Ok(Self::PostgreSQL(Arc::new(Mutex::new(tx))))
+            }
+            #[cfg(feature = "mysql-support")]
+            DatabaseConnection::MySQL(pool) => {
+                let tx = match isolation.set_transaction_sql(&crate::types::DatabaseType::MySQL)? {
+                    Some(sql) => {
+                        let mut conn = pool.acquire().await.map_err(|e| transaction_error("开启", e))?;
+                        sqlx::query(&sql).execute(&mut *conn).await.map_err(isolation_error)?;
+                        sqlx::Transaction::begin(conn, None).await
+                    }
+                    None => pool.begin().await,
+                }
+                    .map_err(|e| transaction_error("开启", e))?;
+                Ok(Self::MySQL(Arc::new(Mutex::new(tx))))
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// 以指定隔离级别在MongoDB客户端上开启会话事务，隔离级别映射为事务的读关注和写关注
+    ///
+    /// 事务中的操作必须使用同一客户端创建的数据库句柄
+    #[cfg(feature = "mongodb-support")]
+    pub(crate) async fn begin_mongo(client: &mongodb::Client, isolation: IsolationLevel) -> QuickDbResult<Self> {
+        let options = isolation.mongodb_concerns()?.map(|(read_concern, write_concern)| {
+            mongodb::options::TransactionOptions::builder()
+                .read_concern(read_concern)
+                .write_concern(write_concern)
+                .build()
+        });
+        let mut session = client.start_session(None).await
+            .map_err(|e| transaction_error("开启", e))?;
+        session.start_transaction(options).await
+            .map_err(|e| transaction_error("开启", e))?;
+        Ok(Self::MongoDB(Arc::new(Mutex::new(session))))
+    }
+
+    /// 在事务中执行保存点操作，MongoDB 事务没有保存点，返回不支持的错误
+    pub(crate) async fn savepoint(&self, action: SavepointAction, name: &str) -> QuickDbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite-support")]
+            Self::SQLite(tx) => {
+                let sql = action.sql(&crate::types::DatabaseType::SQLite, name)?;
+                sqlx::query(&sql).execute(&mut **tx.lock().await).await.map_err(savepoint_error)?;
+            }
+            #[cfg(feature = "postgres-support")]
+            Self::PostgreSQL(tx) => {
+                let sql = action.sql(&crate::types::DatabaseType::PostgreSQL, name)?;
+                sqlx::query(&sql).execute(&mut **tx.lock().await).await.map_err(savepoint_error)?;
+            }
+            #[cfg(feature = "mysql-support")]
+            Self::MySQL(tx) => {
+                let sql = action.sql(&crate::types::DatabaseType::MySQL, name)?;
+                sqlx::query(&sql).execute(&mut **tx.lock().await).await.map_err(savepoint_error)?;
+            }
+            #[cfg(feature = "mongodb-support")]
+            Self::MongoDB(_) => {
+                action.sql(&crate::types::DatabaseType::MongoDB, name)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 在事务作用域中执行一段异步代码
+    pub(crate) async fn scope<F: Future>(&self, future: F) -> F::Output {
+        ACTIVE_TRANSACTION.scope(self.clone(), future).await
+    }
+
+    /// 提交事务，作用域结束后调用
+    pub(crate) async fn commit(self) -> QuickDbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite-support")]
+            Self::SQLite(tx) => into_inner(tx)?.commit().await.map_err(|e| transaction_error("提交", e)),
+            #[cfg(feature = "postgres-support")]
+            Self::PostgreSQL(tx) => into_inner(tx)?.commit().await.map_err(|e| transaction_error("提交", e)),
+            #[cfg(feature = "mysql-support")]
+            Self::MySQL(tx) => into_inner(tx)?.commit().await.map_err(|e| transaction_error("提交", e)),
+            #[cfg(feature = "mongodb-support")]
+            Self::MongoDB(session) => into_inner(session)?.commit_transaction().await.map_err(|e| transaction_error("提交", e)),
+        }
+    }
+
+    /// 回滚事务，作用域结束后调用
+    pub(crate) async fn rollback(self) -> QuickDbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite-support")]
+            Self::SQLite(tx) => into_inner(tx)?.rollback().await.map_err(|e| transaction_error("回滚", e)),
+            #[cfg(feature = "postgres-support")]
+            Self::PostgreSQL(tx) => into_inner(tx)?.rollback().await.map_err(|e| transaction_error("回滚", e)),
+            #[cfg(feature = "mysql-support")]
+            Self::MySQL(tx) => into_inner(tx)?.rollback().await.map_err(|e| transaction_error("回滚", e)),
+            #[cfg(feature = "mongodb-support")]
+            Self::MongoDB(session) => into_inner(session)?.abort_transaction().await.map_err(|e| transaction_error("回滚", e)),
+        }
+    }
+}
+
+/// 在事务中执行一段异步代码
+///
+/// 已处于事务作用域时直接加入当前事务，由外层统一提交；否则开启新事务，成功时提交、失败时回滚
+pub(crate) async fn run_in_transaction<T, F: Future<Output = QuickDbResult<T>>>(
+    connection: &DatabaseConnection,
+    future: F,
+) -> QuickDbResult<T> {
+    if in_transaction() {
+        return future.await;
+    }
+
+    let transaction = AdapterTransaction::begin(connection, IsolationLevel::BackendDefault).await?;
+    match transaction.scope(future).await {
+        Ok(value) => {
+            transaction.commit().await?;
+            Ok(value)
+        }
+        Err(e) => {
+            if let Err(rollback_error) = transaction.rollback().await {
+                rat_logger::error!("回滚事务失败: {}", rollback_error);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// 当前是否处于适配器事务作用域中
+pub(crate) fn in_transaction() -> bool {
+    ACTIVE_TRANSACTION.try_with(|_| ()).is_ok()
+}
+
+/// 不支持适配器事务的错误
+fn unsupported() -> QuickDbError {
+    QuickDbError::UnsupportedDatabase {
+        db_type: "当前数据库不支持跨表事务写入".to_string(),
+    }
+}
+
+/// 构造事务操作失败的错误
+fn transaction_error(action: &str, e: impl std::fmt::Display) -> QuickDbError {
+    QuickDbError::TransactionError {
+        message: format!("{}事务失败: {}", action, e),
+    }
+}
+
+/// 构造保存点语句执行失败的错误
+fn savepoint_error(e: sqlx::Error) -> QuickDbError {
+    QuickDbError::TransactionError {
+        message: format!("执行保存点语句失败: {}", e),
+    }
+}
+
+/// 构造设置事务隔离级别失败的错误
+#[cfg(any(feature = "postgres-support", feature = "mysql-support"))]
+fn isolation_error(e: impl std::fmt::Display) -> QuickDbError {
+    QuickDbError::TransactionError {
+        message: format!("设置事务隔离级别失败: {}", e),
+    }
+}
+
+/// 取回作用域结束后不再共享的事务
+fn into_inner<T>(tx: Arc<Mutex<T>>) -> QuickDbResult<T> {
+    Arc::try_unwrap(tx)
+        .map(Mutex::into_inner)
+        .map_err(|_| QuickDbError::TransactionError {
+            message: "事务仍在作用域中使用，无法结束".to_string(),
+        })
+}
+
+/// 当前作用域中的 SQLite 事务
+#[cfg(feature = "sqlite-support")]
+pub(crate) async fn current_sqlite_transaction() -> Option<OwnedMutexGuard<sqlx::Transaction<'static, sqlx::Sqlite>>> {
+    let tx = ACTIVE_TRANSACTION
+        .try_with(|active| match active {
+            AdapterTransaction::SQLite(tx) => Some(tx.clone()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        })
+        .ok()
+        .flatten()?;
+    Some(tx.lock_owned().await)
+}
+
+/// 当前作用域中的 PostgreSQL 事务
+#[cfg(feature = "postgres-support")]
+pub(crate) async fn current_postgres_transaction() -> Option<OwnedMutexGuard<sqlx::Transaction<'static, sqlx::Postgres>>> {
+    let tx = ACTIVE_TRANSACTION
+        .try_with(|active| match active {
+            AdapterTransaction::PostgreSQL(tx) => Some(tx.clone()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        })
+        .ok()
+        .flatten()?;
+    Some(tx.lock_owned().await)
+}
+
+/// 当前作用域中的 MySQL 事务
+#[cfg(feature = "mysql-support")]
+pub(crate) async fn current_mysql_transaction() -> Option<OwnedMutexGuard<sqlx::Transaction<'static, sqlx::MySql>>> {
+    let tx = ACTIVE_TRANSACTION
+        .try_with(|active| match active {
+            AdapterTransaction::MySQL(tx) => Some(tx.clone()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        })
+        .ok()
+        .flatten()?;
+    Some(tx.lock_owned().await)
+}
+
+/// 当前作用域中的 MongoDB 事务会话
+#[cfg(feature = "mongodb-support")]
+pub(crate) async fn current_mongo_session() -> Option<OwnedMutexGuard<mongodb::ClientSession>> {
+    let session = ACTIVE_TRANSACTION
+        .try_with(|active| match active {
+            AdapterTransaction::MongoDB(session) => Some(session.clone()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        })
+        .ok()
+        .flatten()?;
+    Some(session.lock_owned().await)
+}
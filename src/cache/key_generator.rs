@@ -2,7 +2,7 @@
 //!
 //! 提供各种缓存键的生成策略和实现
 
-use crate::types::{IdType, QueryCondition, QueryConditionGroup, QueryOptions, SortDirection, CacheConfig, DataValue};
+use crate::types::{IdType, QueryCondition, QueryConditionGroup, QueryOptions, SortDirection, CacheConfig, DataValue, LogicalOperator};
 use rat_logger::debug;
 use std::vec::Vec;
 
@@ -38,6 +38,22 @@ impl CacheManager {
         key
     }
 
+    /// 生成 `find` 查询的缓存键，与缓存适配器的查询路径使用同一套规则
+    pub fn generate_find_cache_key(&self, table: &str, conditions: &[QueryCondition], options: &QueryOptions) -> String {
+        self.generate_condition_groups_cache_key(table, &Self::find_condition_groups(conditions), options)
+    }
+
+    /// 把简单条件列表转换为条件组合（AND逻辑），`find` 统一按条件组合查询和缓存
+    pub(crate) fn find_condition_groups(conditions: &[QueryCondition]) -> Vec<QueryConditionGroup> {
+        if conditions.is_empty() {
+            return vec![];
+        }
+        vec![QueryConditionGroup::Group {
+            operator: LogicalOperator::And,
+            conditions: conditions.iter().map(|c| QueryConditionGroup::Single(c.clone())).collect(),
+        }]
+    }
+
     /// 构建查询签名 - 高效版本，避免JSON序列化
     fn build_query_signature(&self, options: &QueryOptions) -> String {
         let mut parts = Vec::new();
@@ -56,10 +72,27 @@ impl CacheManager {
             parts.push(format!("s{}", sort_str));
         }
         
-        // 投影信息
+        // 投影信息：缓存的是投影后的记录，不同字段集必须使用不同的键；
+        // 结果中字段无序，字段集相同但顺序不同的查询共用同一缓存
         if !options.fields.is_empty() {
-            let proj_str = options.fields.join(",");
-            parts.push(format!("f{}", proj_str));
+            let mut fields: Vec<&str> = options.fields.iter().map(String::as_str).collect();
+            fields.sort_unstable();
+            fields.dedup();
+            parts.push(format!("f{}", fields.join(",")));
+        }
+
+        // 排序规则
+        if let Some(collation) = &options.collation {
+            parts.push(format!("c{}", collation));
+        }
+
+        // 计算字段
+        if !options.select_exprs.is_empty() {
+            let expr_str = options.select_exprs.iter()
+                .map(|(expr, alias)| format!("{}={:?}", alias, expr))
+                .collect::<Vec<_>>()
+                .join(",");
+            parts.push(format!("e{}", expr_str));
         }
         
         // 连接部分生成最终签名
@@ -89,7 +122,8 @@ impl CacheManager {
                      DataValue::Int(n) => n.to_string(),
                      DataValue::Float(f) => f.to_string(),
                      DataValue::Bool(b) => b.to_string(),
-                     _ => "val".to_string(),
+                     // 数组等复合值也要参与签名，否则不同的 IN 列表或原始条件参数会命中同一缓存
+                     other => format!("{:?}", other),
                  }
             ));
         }
@@ -102,54 +136,36 @@ impl CacheManager {
             return "no_groups".to_string();
         }
         
-        let mut signature = String::new();
-        for (i, group) in condition_groups.iter().enumerate() {
-            if i > 0 {
-                signature.push('_');
-            }
-            match group {
-                QueryConditionGroup::Single(condition) => {
-                    signature.push_str(&format!("s{}{:?}{}", 
-                        condition.field, 
-                        condition.operator, 
-                        match &condition.value {
-                             DataValue::String(s) => s.clone(),  // 修复：不截断字符串，使用完整值
-                             DataValue::Int(n) => n.to_string(),
-                             DataValue::Float(f) => f.to_string(),
-                             DataValue::Bool(b) => b.to_string(),
-                             _ => "val".to_string(),
-                         }
-                    ));
-                },
-                QueryConditionGroup::Group { conditions, operator } => {
-                    signature.push_str(&format!("g{:?}_", operator));
-                    for (j, condition) in conditions.iter().enumerate() {
-                        if j > 0 {
-                            signature.push('|');
-                        }
-                        match condition {
-                            QueryConditionGroup::Single(cond) => {
-                                signature.push_str(&format!("{}{:?}{}", 
-                                    cond.field, 
-                                    cond.operator, 
-                                    match &cond.value {
-                                        DataValue::String(s) => s.clone(),
-                                        DataValue::Int(n) => n.to_string(),
-                                        DataValue::Float(f) => f.to_string(),
-                                        DataValue::Bool(b) => b.to_string(),
-                                        _ => "val".to_string(),
-                                    }
-                                ));
-                            },
-                            QueryConditionGroup::Group { .. } => {
-                                // 递归处理嵌套组合（简化处理）
-                                signature.push_str("nested");
-                            }
-                        }
-                    }
-                }
+        condition_groups.iter()
+            .map(|group| self.build_group_signature(group))
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+
+    /// 递归构建单个条件组合的签名，嵌套组合完整展开，避免不同条件树生成相同的键
+    fn build_group_signature(&self, group: &QueryConditionGroup) -> String {
+        match group {
+            QueryConditionGroup::Single(condition) => {
+                format!("s{}{:?}{}", 
+                    condition.field, 
+                    condition.operator, 
+                    match &condition.value {
+                         DataValue::String(s) => s.clone(),  // 修复：不截断字符串，使用完整值
+                         DataValue::Int(n) => n.to_string(),
+                         DataValue::Float(f) => f.to_string(),
+                         DataValue::Bool(b) => b.to_string(),
+                         // 数组等复合值也要参与签名，否则不同的 IN 列表或原始条件参数会命中同一缓存
+                     other => format!("{:?}", other),
+                     }
+                )
+            },
+            QueryConditionGroup::Group { conditions, operator } => {
+                let children = conditions.iter()
+                    .map(|condition| self.build_group_signature(condition))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                format!("g{:?}({})", operator, children)
             }
         }
-        signature
     }
 }
@@ -10,7 +10,9 @@ pub mod record_cache;
 pub mod query_cache;
 pub mod operations;
 pub mod cache_manager;
+pub mod warmup;
 
 // 重新导出主要的公共类型和结构体
 pub use stats::{CachePerformanceStats, CacheStats};
-pub use cache_manager::CacheManager;
\ No newline at end of file
+pub use cache_manager::CacheManager;
+pub use warmup::CacheWarmSpec;
\ No newline at end of file
@@ -0,0 +1,111 @@
+//! 查询缓存预热模块
+//!
+//! 在启动或定时任务中预先执行已知的热点查询并写入缓存，使部署后的首个真实请求即可命中缓存
+
+use crate::types::{QueryCondition, QueryOptions};
+use anyhow::{anyhow, Result};
+use rat_logger::{info, debug, warn};
+
+// 从 cache_manager.rs 中引入 CacheManager
+use super::cache_manager::CacheManager;
+
+/// 一条待预热的查询
+#[derive(Debug, Clone)]
+pub struct CacheWarmSpec {
+    /// 数据库别名，必须是该缓存管理器所属的别名
+    pub alias: String,
+    /// 表名
+    pub table: String,
+    /// 查询条件，与实际调用 `find` 时传入的条件一致才能命中
+    pub conditions: Vec<QueryCondition>,
+    /// 查询选项（分页、排序、投影等），同样需要与实际调用一致
+    pub options: QueryOptions,
+}
+
+impl CacheWarmSpec {
+    /// 创建预热查询，使用默认查询选项
+    pub fn new(alias: &str, table: &str, conditions: Vec<QueryCondition>) -> Self {
+        Self {
+            alias: alias.to_string(),
+            table: table.to_string(),
+            conditions,
+            options: QueryOptions::default(),
+        }
+    }
+
+    /// 设置查询选项
+    pub fn with_options(mut self, options: QueryOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl CacheManager {
+    /// 预热单条查询 - 执行查询并写入缓存，不返回查询数据
+    ///
+    /// 查询经由正常的 `find` 路径执行，缓存键与实际查询完全相同。
+    /// 返回是否新写入了缓存条目：已缓存、结果过大（超过1000条）或在会话上下文中执行时返回 `false`
+    ///
+    /// # 参数
+    /// * `alias` - 数据库别名，必须是该缓存管理器所属的别名
+    /// * `table` - 表名
+    /// * `conditions` - 查询条件
+    /// * `options` - 查询选项
+    pub async fn warm(
+        &self,
+        alias: &str,
+        table: &str,
+        conditions: &[QueryCondition],
+        options: &QueryOptions,
+    ) -> Result<bool> {
+        if !self.config.enabled {
+            return Ok(false);
+        }
+
+        // 查询会写入别名对应的缓存，别名不属于当前缓存管理器时预热结果无法在此命中
+        let owner = crate::manager::get_cache_manager(alias).map_err(|e| anyhow!("{}", e))?;
+        if !std::ptr::eq(owner.as_ref(), self) {
+            return Err(anyhow!("数据库 {} 不使用此缓存管理器", alias));
+        }
+
+        let key = self.generate_find_cache_key(table, conditions, options);
+        if self.is_key_cached(&key).await {
+            debug!("查询已缓存，跳过预热: table={}, key={}", table, key);
+            return Ok(false);
+        }
+
+        crate::odm::find(table, conditions.to_vec(), Some(options.clone()), Some(alias))
+            .await
+            .map_err(|e| anyhow!("预热查询失败: table={}, error={}", table, e))?;
+
+        let warmed = self.is_key_cached(&key).await;
+        debug!("查询缓存预热: table={}, key={}, warmed={}", table, key, warmed);
+        Ok(warmed)
+    }
+
+    /// 批量预热查询，返回新写入的缓存条目数
+    ///
+    /// 单条查询失败时记录警告并继续预热其余查询
+    pub async fn warm_all(&self, specs: &[CacheWarmSpec]) -> Result<usize> {
+        if !self.config.enabled || specs.is_empty() {
+            return Ok(0);
+        }
+
+        let mut warmed_count = 0;
+        for spec in specs {
+            match self.warm(&spec.alias, &spec.table, &spec.conditions, &spec.options).await {
+                Ok(true) => warmed_count += 1,
+                Ok(false) => {}
+                Err(e) => warn!("查询缓存预热失败: alias={}, table={}, error={}", spec.alias, spec.table, e),
+            }
+        }
+
+        info!("查询缓存预热完成: total={}, warmed_count={}", specs.len(), warmed_count);
+        Ok(warmed_count)
+    }
+
+    /// 检查缓存键是否存在，不计入命中统计
+    async fn is_key_cached(&self, key: &str) -> bool {
+        matches!(self.cache.get(key).await, Ok(Some(_)))
+    }
+}
@@ -6,6 +6,7 @@ use crate::types::*;
 use crate::error::QuickDbError;
 use rat_logger::info;
 use std::path::PathBuf;
+use std::collections::HashMap;
 
 /// 数据库配置构建器
 ///
@@ -20,6 +21,35 @@ pub struct DatabaseConfigBuilder {
     cache: Option<CacheConfig>,
     /// ID 生成策略
     id_strategy: Option<IdStrategy>,
+    /// 查询不存在的表时是否返回空结果
+    missing_table_returns_empty: bool,
+    /// 写后缓冲配置
+    write_behind: Option<WriteBehindConfig>,
+    /// 查询日志模式
+    log_queries: QueryLogMode,
+    /// 查询日志脱敏字段
+    sensitive_fields: Vec<String>,
+    /// 是否为只读别名
+    read_only: bool,
+    dry_run: bool,
+    /// N+1 查询检测配置
+    detect_n_plus_one: Option<NPlusOneConfig>,
+    /// MongoDB GridFS 存储桶名称
+    gridfs_bucket: Option<String>,
+    /// SQLite 数据库忙时的重试次数
+    busy_retry_count: Option<u32>,
+    /// MongoDB 主键在查询结果中的字段名
+    mongo_id_field: Option<MongoIdField>,
+    /// MongoDB 空值条件的匹配语义
+    mongo_null_matching: Option<MongoNullMatching>,
+    /// 连接时上报的应用名称
+    application_name: Option<String>,
+    /// 日期时间的小数秒位数
+    datetime_precision: Option<u8>,
+    /// 是否对 MySQL 文本列进行JSON内容探测
+    mysql_json_text_detection: bool,
+    /// 按工作负载命名的连接池配置
+    workloads: HashMap<String, PoolConfig>,
 }
 impl DatabaseConfig {
     /// 创建数据库配置构建器
@@ -38,6 +68,21 @@ impl DatabaseConfigBuilder {
             alias: None,
             cache: None,
             id_strategy: None,
+            missing_table_returns_empty: false,
+            write_behind: None,
+            log_queries: QueryLogMode::Off,
+            sensitive_fields: Vec::new(),
+            read_only: false,
+            dry_run: false,
+            detect_n_plus_one: None,
+            gridfs_bucket: None,
+            busy_retry_count: None,
+            mongo_id_field: None,
+            mongo_null_matching: None,
+            application_name: None,
+            datetime_precision: None,
+            mysql_json_text_detection: false,
+            workloads: HashMap::new(),
         }
     }
 
@@ -91,6 +136,180 @@ impl DatabaseConfigBuilder {
         self
     }
 
+    /// 设置查询不存在的表时是否返回空结果
+    ///
+    /// 开启后 SQL 数据库的行为与 MongoDB 一致，但会掩盖表名拼写错误，默认关闭
+    ///
+    /// # 参数
+    ///
+    /// * `enabled` - 是否返回空结果
+    pub fn missing_table_returns_empty(mut self, enabled: bool) -> Self {
+        self.missing_table_returns_empty = enabled;
+        self
+    }
+
+    /// 开启写后缓冲，单条创建先入内存缓冲区再批量写入
+    ///
+    /// 以持久性换取写入吞吐，进程崩溃时未刷新的数据会丢失，详见 [`WriteBehindConfig`]
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 写后缓冲配置
+    pub fn write_behind(mut self, config: WriteBehindConfig) -> Self {
+        self.write_behind = Some(config);
+        self
+    }
+
+    /// 设置查询日志模式
+    ///
+    /// # 参数
+    ///
+    /// * `mode` - 查询日志模式
+    pub fn log_queries(mut self, mode: QueryLogMode) -> Self {
+        self.log_queries = mode;
+        self
+    }
+
+    /// 设置查询日志中需要脱敏的字段名，配合 `QueryLogMode::SqlWithRedactedParams` 使用
+    ///
+    /// # 参数
+    ///
+    /// * `fields` - 字段名列表（不区分大小写）
+    pub fn sensitive_fields(mut self, fields: Vec<String>) -> Self {
+        self.sensitive_fields = fields;
+        self
+    }
+
+    /// 开启 N+1 查询检测，同一查询在短时间内重复执行过多时输出警告
+    ///
+    /// 用于开发环境发现循环中逐条查询的写法，详见 [`NPlusOneConfig`]
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 检测阈值与统计窗口
+    pub fn detect_n_plus_one(mut self, config: NPlusOneConfig) -> Self {
+        self.detect_n_plus_one = Some(config);
+        self
+    }
+
+    /// 设置 MongoDB 二进制流使用的 GridFS 存储桶
+    ///
+    /// 只对 MongoDB 有效，配置后可以用 `read_blob`/`write_blob` 流式读写大文件
+    ///
+    /// # 参数
+    ///
+    /// * `bucket` - 存储桶名称，如 `"fs"`
+    pub fn gridfs_bucket<S: Into<String>>(mut self, bucket: S) -> Self {
+        self.gridfs_bucket = Some(bucket.into());
+        self
+    }
+
+    /// 设置 SQLite 写语句遇到数据库忙时的重试次数
+    ///
+    /// 多个连接或进程同时写入同一个数据库文件时，SQLite 会返回 `database is locked`；
+    /// 语句失败时没有产生修改，可以安全地退避后重试。为 0 时关闭重试
+    ///
+    /// # 参数
+    ///
+    /// * `count` - 最大重试次数（不含首次执行）
+    pub fn busy_retry_count(mut self, count: u32) -> Self {
+        self.busy_retry_count = Some(count);
+        self
+    }
+
+    /// 设置 MongoDB 主键 `_id` 在查询结果中使用的字段名
+    ///
+    /// 默认映射为 `id`；ID字段命名为 `_id` 的模型使用 `MongoIdField::UnderscoreId`
+    pub fn mongo_id_field(mut self, field: MongoIdField) -> Self {
+        self.mongo_id_field = Some(field);
+        self
+    }
+
+    /// 设置 MongoDB 空值条件的匹配语义
+    ///
+    /// 默认 `IsNull` 同时匹配 null 和缺失字段；需要与 SQL 的 `IS NULL` 一致时使用
+    /// `MongoNullMatching::NullOnly`，只匹配显式存储的 null
+    pub fn mongo_null_matching(mut self, mode: MongoNullMatching) -> Self {
+        self.mongo_null_matching = Some(mode);
+        self
+    }
+
+    /// 设置连接时上报给数据库的应用名称
+    ///
+    /// 多个服务共用一个数据库时，DBA 可以据此在 `pg_stat_activity` 等进程列表中区分负载来源。
+    /// 只适用于 PostgreSQL 和 MongoDB，未设置时使用 `rat_quickdb-{别名}`
+    pub fn application_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.application_name = Some(name.into());
+        self
+    }
+
+    /// 设置日期时间的小数秒位数
+    ///
+    /// 写入和查询中的日期时间截断到该精度，MySQL/PostgreSQL 建表时使用相同精度的列类型，
+    /// 避免"写入的时间戳按原值精确查询却查不到"
+    ///
+    /// # 参数
+    ///
+    /// * `precision` - 小数秒位数，0~6，例如 6 表示微秒
+    pub fn datetime_precision(mut self, precision: u8) -> Self {
+        self.datetime_precision = Some(precision);
+        self
+    }
+
+    /// 设置是否对 MySQL 的 TEXT/VARCHAR 列进行JSON内容探测（仅 MySQL 有效）
+    ///
+    /// 默认关闭，文本列是否解析为JSON由模型元数据决定；访问未注册模型的表时可开启，
+    /// 代价是内容恰好形如JSON的普通字符串也会被解析
+    ///
+    /// # 参数
+    ///
+    /// * `enabled` - 是否开启探测
+    pub fn mysql_json_text_detection(mut self, enabled: bool) -> Self {
+        self.mysql_json_text_detection = enabled;
+        self
+    }
+
+    /// 添加按工作负载命名的连接池
+    ///
+    /// 例如为报表查询配置连接数少、超时长的 `analytics` 连接池，使其无法占满处理在线请求的主连接池。
+    /// 查询选项通过 `with_workload("analytics")` 使用该连接池，同名工作负载会覆盖之前的配置
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 工作负载名称
+    /// * `pool` - 该工作负载的连接池配置
+    pub fn workload<S: Into<String>>(mut self, name: S, pool: PoolConfig) -> Self {
+        self.workloads.insert(name.into(), pool);
+        self
+    }
+
+    /// 设置是否为只读别名
+    ///
+    /// 用于报表副本等只允许读取的数据库：写操作及建表、删表等DDL会直接返回
+    /// `QuickDbError::ReadOnly`，不会发送到数据库
+    ///
+    /// # 参数
+    ///
+    /// * `read_only` - 是否只读
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// 设置是否为试运行别名
+    ///
+    /// 用于在提交前验证数据迁移脚本：插入、更新和删除照常校验并执行，返回预期的影响行数，
+    /// 但所在事务总是回滚，不会修改数据；试运行不会自动建表，其他写操作和DDL直接返回错误。
+    /// MongoDB 在总是中止的会话事务中执行，需要副本集或分片集群部署
+    ///
+    /// # 参数
+    ///
+    /// * `dry_run` - 是否试运行
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
     /// 设置缓存配置
     ///
     /// # 参数
@@ -157,6 +376,40 @@ impl DatabaseConfigBuilder {
         // 验证配置的一致性
         Self::validate_config(&db_type, &connection)?;
 
+        if let Some(detection) = &self.detect_n_plus_one {
+            if detection.threshold < 2 {
+                return Err(crate::quick_error!(config, "N+1查询检测阈值必须至少为2"));
+            }
+            if detection.window_ms == 0 {
+                return Err(crate::quick_error!(config, "N+1查询检测窗口必须大于0"));
+            }
+        }
+
+        if self.gridfs_bucket.is_some() && db_type != DatabaseType::MongoDB {
+            return Err(crate::quick_error!(config, "gridfs_bucket 只适用于 MongoDB"));
+        }
+
+        if self.busy_retry_count.is_some() && db_type != DatabaseType::SQLite {
+            return Err(crate::quick_error!(config, "busy_retry_count 只适用于 SQLite"));
+        }
+
+
+        if self.mongo_id_field.is_some() && db_type != DatabaseType::MongoDB {
+            return Err(crate::quick_error!(config, "mongo_id_field 只适用于 MongoDB"));
+        }
+
+        if self.datetime_precision.is_some_and(|precision| precision > 6) {
+            return Err(crate::quick_error!(config, "datetime_precision 必须在0到6之间"));
+        }
+
+        if self.application_name.is_some() && !matches!(db_type, DatabaseType::PostgreSQL | DatabaseType::MongoDB) {
+            return Err(crate::quick_error!(config, "application_name 只适用于 PostgreSQL 和 MongoDB"));
+        }
+
+        if self.workloads.keys().any(|name| name.trim().is_empty()) {
+            return Err(crate::quick_error!(config, "工作负载名称不能为空"));
+        }
+
         info!("创建数据库配置: 别名={}, 类型={:?}", alias, db_type);
 
         Ok(DatabaseConfig {
@@ -166,6 +419,21 @@ impl DatabaseConfigBuilder {
             alias,
             cache: self.cache,
             id_strategy,
+            missing_table_returns_empty: self.missing_table_returns_empty,
+            write_behind: self.write_behind,
+            log_queries: self.log_queries,
+            sensitive_fields: self.sensitive_fields,
+            read_only: self.read_only,
+            dry_run: self.dry_run,
+            detect_n_plus_one: self.detect_n_plus_one,
+            gridfs_bucket: self.gridfs_bucket,
+            busy_retry_count: self.busy_retry_count,
+            mongo_id_field: self.mongo_id_field,
+            mongo_null_matching: self.mongo_null_matching,
+            application_name: self.application_name,
+            datetime_precision: self.datetime_precision,
+            mysql_json_text_detection: self.mysql_json_text_detection,
+            workloads: self.workloads,
         })
     }
 
@@ -17,10 +17,18 @@ pub struct PoolConfigBuilder {
     connection_timeout: Option<u64>,
     idle_timeout: Option<u64>,
     max_lifetime: Option<u64>,
+    max_lifetime_jitter: Option<u64>,
     max_retries: Option<u32>,
     retry_interval_ms: Option<u64>,
+    retry_attempts: Option<u32>,
+    retry_base_delay_ms: Option<u64>,
+    retry_max_delay_ms: Option<u64>,
     keepalive_interval_sec: Option<u64>,
     health_check_timeout_sec: Option<u64>,
+    test_before_acquire: Option<bool>,
+    sampling: Option<PoolSamplingConfig>,
+    reserved_interactive_connections: Option<u32>,
+    max_batch_operations: Option<u32>,
 }
 impl PoolConfig {
     /// 创建连接池配置构建器
@@ -38,10 +46,18 @@ impl PoolConfigBuilder {
             connection_timeout: None,
             idle_timeout: None,
             max_lifetime: None,
+            max_lifetime_jitter: None,
             max_retries: None,
             retry_interval_ms: None,
+            retry_attempts: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
             keepalive_interval_sec: None,
             health_check_timeout_sec: None,
+            test_before_acquire: None,
+            sampling: None,
+            reserved_interactive_connections: None,
+            max_batch_operations: None,
         }
     }
 
@@ -115,6 +131,36 @@ impl PoolConfigBuilder {
         self
     }
 
+    /// 设置操作遇到瞬时错误（连接中断、超时）时的重试次数，默认 `0` 不重试
+    ///
+    /// # 参数
+    ///
+    /// * `attempts` - 重试次数
+    pub fn retry_attempts(mut self, attempts: u32) -> Self {
+        self.retry_attempts = Some(attempts);
+        self
+    }
+
+    /// 设置重试退避的基础等待时间（毫秒），默认 100
+    ///
+    /// # 参数
+    ///
+    /// * `delay` - 首次重试前的基础等待时间（毫秒），之后每次翻倍
+    pub fn retry_base_delay_ms(mut self, delay: u64) -> Self {
+        self.retry_base_delay_ms = Some(delay);
+        self
+    }
+
+    /// 设置重试退避的等待时间上限（毫秒），默认 5000
+    ///
+    /// # 参数
+    ///
+    /// * `delay` - 单次重试等待时间上限（毫秒）
+    pub fn retry_max_delay_ms(mut self, delay: u64) -> Self {
+        self.retry_max_delay_ms = Some(delay);
+        self
+    }
+
     /// 设置保活检测间隔（秒）
     ///
     /// # 参数
@@ -135,6 +181,59 @@ impl PoolConfigBuilder {
         self
     }
 
+    /// 设置获取连接前是否检测连接存活（可选，默认开启）
+    ///
+    /// # 参数
+    ///
+    /// * `enabled` - 是否在获取连接前检测
+    pub fn test_before_acquire(mut self, enabled: bool) -> Self {
+        self.test_before_acquire = Some(enabled);
+        self
+    }
+
+    /// 启用连接池使用情况采样（可选，默认关闭）
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 采样间隔和保留样本数
+    pub fn sampling(mut self, config: PoolSamplingConfig) -> Self {
+        self.sampling = Some(config);
+        self
+    }
+
+    /// 设置为交互操作保留的连接数（可选，默认0即不区分操作类别）
+    ///
+    /// # 参数
+    ///
+    /// * `connections` - 保留连接数，必须小于最大连接数
+    pub fn reserved_interactive_connections(mut self, connections: u32) -> Self {
+        self.reserved_interactive_connections = Some(connections);
+        self
+    }
+
+    /// 设置连接生存时间的随机抖动范围（可选，默认0即不抖动）
+    ///
+    /// 每个连接在 `max_lifetime - jitter` 到 `max_lifetime` 之间随机退役，
+    /// 分散同时创建的连接的重连时间
+    ///
+    /// # 参数
+    ///
+    /// * `jitter` - 抖动范围（秒），不能大于连接最大生存时间
+    pub fn max_lifetime_jitter(mut self, jitter: u64) -> Self {
+        self.max_lifetime_jitter = Some(jitter);
+        self
+    }
+
+    /// 设置同时进行的批量操作上限（可选，默认0即不限制）
+    ///
+    /// # 参数
+    ///
+    /// * `limit` - 批量操作上限
+    pub fn max_batch_operations(mut self, limit: u32) -> Self {
+        self.max_batch_operations = Some(limit);
+        self
+    }
+
     /// 构建连接池配置
     /// 
     /// # 错误
@@ -194,6 +293,28 @@ impl PoolConfigBuilder {
             return Err(crate::quick_error!(config, "连接最大生存时间不能为零"));
         }
 
+        let max_lifetime_jitter = self.max_lifetime_jitter.unwrap_or(0);
+        if max_lifetime_jitter > max_lifetime {
+            return Err(crate::quick_error!(config, "连接生存时间抖动范围不能大于连接最大生存时间"));
+        }
+
+        let retry_base_delay_ms = self.retry_base_delay_ms.unwrap_or(100);
+        let retry_max_delay_ms = self.retry_max_delay_ms.unwrap_or(5000);
+        if retry_base_delay_ms > retry_max_delay_ms {
+            return Err(crate::quick_error!(config, "重试基础等待时间不能大于重试等待时间上限"));
+        }
+
+        if let Some(sampling) = &self.sampling {
+            if sampling.interval_ms == 0 || sampling.capacity == 0 {
+                return Err(crate::quick_error!(config, "采样间隔和保留样本数不能为零"));
+            }
+        }
+
+        let reserved_interactive_connections = self.reserved_interactive_connections.unwrap_or(0);
+        if reserved_interactive_connections > 0 && reserved_interactive_connections >= max_connections {
+            return Err(crate::quick_error!(config, "交互保留连接数必须小于最大连接数，批量通道至少需要一个连接"));
+        }
+
         info!("创建连接池配置: 最小连接数={}, 最大连接数={}, 连接超时={}s", 
               min_connections, max_connections, connection_timeout);
 
@@ -203,10 +324,18 @@ impl PoolConfigBuilder {
             connection_timeout: connection_timeout * 1000,  // 转换为毫秒
             idle_timeout,
             max_lifetime,
+            max_lifetime_jitter,
             max_retries,
             retry_interval_ms,
+            retry_attempts: self.retry_attempts.unwrap_or(0),
+            retry_base_delay_ms,
+            retry_max_delay_ms,
             keepalive_interval_sec,
             health_check_timeout_sec,
+            test_before_acquire: self.test_before_acquire.unwrap_or(true),
+            sampling: self.sampling,
+            reserved_interactive_connections,
+            max_batch_operations: self.max_batch_operations.unwrap_or(0),
         })
     }
 }
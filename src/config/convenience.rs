@@ -72,6 +72,7 @@ pub fn postgres_config<S: Into<String>>(
             password: password.into(),
             ssl_mode: Some("prefer".to_string()),
             tls_config: None,
+            create_database_if_missing: false,
         })
         .pool(pool_config)
         .alias(alias)
@@ -115,6 +116,7 @@ pub fn mysql_config<S: Into<String>>(
             password: password.into(),
             ssl_opts: None,
             tls_config: None,
+            create_database_if_missing: false,
         })
         .pool(pool_config)
         .alias(alias)
@@ -1,6 +1,7 @@
 //! 错误处理模块
 //!
-//! 提供统一的错误类型定义和中文错误信息
+//! 提供统一的错误类型定义和多语言错误信息。`Display` 按 [`crate::i18n`] 的当前语言渲染，
+//! 程序中判断错误类别应使用 [`QuickDbError::kind`]
 
 use thiserror::Error;
 
@@ -8,62 +9,314 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum QuickDbError {
     /// 数据库连接错误
-    #[error("数据库连接失败: {message}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     ConnectionError { message: String },
 
     /// 连接池错误
-    #[error("连接池操作失败: {message}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     PoolError { message: String },
 
     /// 查询执行错误
-    #[error("查询执行失败: {message}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     QueryError { message: String },
 
     /// 序列化/反序列化错误
-    #[error("数据序列化失败: {message}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     SerializationError { message: String },
 
     /// 模型验证错误
-    #[error("模型验证失败: {field} - {message}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     ValidationError { field: String, message: String },
 
     /// 配置错误
-    #[error("配置错误: {message}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     ConfigError { message: String },
 
     /// 数据库别名未找到
-    #[error("数据库别名 '{alias}' 未找到")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     AliasNotFound { alias: String },
 
+    /// 只读别名上的写操作
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
+    ReadOnly { alias: String },
+
+    /// 连接池已饱和，快速失败而不排队等待
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
+    PoolTimeout { alias: String },
+
     /// 不支持的数据库类型
-    #[error("不支持的数据库类型: {db_type}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     UnsupportedDatabase { db_type: String },
 
     /// 事务操作错误（虽然不支持事务，但保留用于未来扩展）
-    #[error("事务操作失败: {message}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     TransactionError { message: String },
 
     /// 任务执行错误
-    #[error("任务执行失败: {0}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     TaskExecutionError(String),
 
     /// 缓存操作错误
-    #[error("缓存操作失败: {message}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     CacheError { message: String },
 
     /// IO 错误
-    #[error("IO 操作失败: {0}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     IoError(#[from] std::io::Error),
 
     /// JSON 序列化错误
-    #[error("JSON 处理失败: {0}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     JsonError(#[from] serde_json::Error),
 
     /// 通用错误
-    #[error("操作失败: {0}")]
+    #[error("{}", self.localized_message(&crate::i18n::current_language()))]
     Other(#[from] anyhow::Error),
 }
 
+/// 错误类别，供应用代码按类别分支处理，不依赖可能变化的本地化错误文本
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// 目标不存在（别名、表/集合、存储过程等）
+    NotFound,
+    /// 违反唯一约束
+    UniqueViolation,
+    /// 操作超时
+    Timeout,
+    /// 连接或连接池错误
+    Connection,
+    /// 死锁或序列化失败，可整体重试事务
+    Conflict,
+    /// 模型验证失败
+    Validation,
+    /// 配置错误或不支持的数据库类型
+    Config,
+    /// 只读别名上的写操作
+    ReadOnly,
+    /// 查询执行错误
+    Query,
+    /// 序列化/反序列化错误
+    Serialization,
+    /// 事务操作错误
+    Transaction,
+    /// 缓存操作错误
+    Cache,
+    /// IO 错误
+    Io,
+    /// 其他错误
+    Other,
+}
+
+/// 死锁与序列化失败的特征（SQLSTATE、MySQL错误码及各数据库的错误文本）
+///
+/// 底层驱动错误在转换时被格式化为消息文本，因此按文本识别
+const RETRYABLE_CONFLICT_PATTERNS: &[&str] = &[
+    "(40001)",
+    "40P01",
+    "1213 (",
+    "deadlock",
+    "could not serialize access",
+    "serialization failure",
+];
+
+/// 唯一约束冲突的特征（SQLite、MySQL 1062、PostgreSQL 23505、MongoDB E11000）
+const UNIQUE_VIOLATION_PATTERNS: &[&str] = &[
+    "unique constraint failed",
+    "duplicate entry",
+    "1062 (",
+    "23505",
+    "duplicate key value violates unique constraint",
+    "e11000",
+];
+
+/// 超时的特征
+const TIMEOUT_PATTERNS: &[&str] = &[
+    "超时",
+    "timed out",
+    "timeout",
+];
+
+/// 连接中断的特征（驱动把网络错误包装在查询错误中返回），此时服务端可能已经执行了语句
+const CONNECTION_LOST_PATTERNS: &[&str] = &[
+    "error communicating with database",
+    "connection reset",
+    "broken pipe",
+    "unexpected eof",
+];
+
+/// 请求发出之前就失败的连接特征（无法建立连接、等待可用连接超时、找不到可用服务器），此时操作一定没有执行
+const CONNECT_FAILURE_PATTERNS: &[&str] = &[
+    "connection refused",
+    "pool timed out while waiting for an open connection",
+    "server selection timeout",
+];
+
+/// 表、集合或其他对象不存在的特征
+const NOT_FOUND_PATTERNS: &[&str] = &[
+    "不存在",
+    "no such table",
+    "ns not found",
+];
+
+impl QuickDbError {
+    /// 按指定语言渲染错误消息（如 "zh-CN"、"en-US"、"ja-JP"）
+    ///
+    /// 只翻译错误类别前缀，`message` 等字段中来自驱动或调用方的内容保持原样
+    pub fn localized_message(&self, lang: &str) -> String {
+        let (key, args): (&str, Vec<(&str, String)>) = match self {
+            QuickDbError::ConnectionError { message } => ("error.connection", vec![("message", message.clone())]),
+            QuickDbError::PoolError { message } => ("error.pool", vec![("message", message.clone())]),
+            QuickDbError::QueryError { message } => ("error.query", vec![("message", message.clone())]),
+            QuickDbError::SerializationError { message } => ("error.serialization", vec![("message", message.clone())]),
+            QuickDbError::ValidationError { field, message } => {
+                ("error.validation", vec![("field", field.clone()), ("message", message.clone())])
+            }
+            QuickDbError::ConfigError { message } => ("error.config", vec![("message", message.clone())]),
+            QuickDbError::AliasNotFound { alias } => ("error.alias_not_found", vec![("alias", alias.clone())]),
+            QuickDbError::ReadOnly { alias } => ("error.read_only", vec![("alias", alias.clone())]),
+            QuickDbError::PoolTimeout { alias } => ("error.pool_timeout", vec![("alias", alias.clone())]),
+            QuickDbError::UnsupportedDatabase { db_type } => ("error.unsupported_database", vec![("db_type", db_type.clone())]),
+            QuickDbError::TransactionError { message } => ("error.transaction", vec![("message", message.clone())]),
+            QuickDbError::TaskExecutionError(message) => ("error.task_execution", vec![("message", message.clone())]),
+            QuickDbError::CacheError { message } => ("error.cache", vec![("message", message.clone())]),
+            QuickDbError::IoError(e) => ("error.io", vec![("message", e.to_string())]),
+            QuickDbError::JsonError(e) => ("error.json", vec![("message", e.to_string())]),
+            QuickDbError::Other(e) => ("error.other", vec![("message", e.to_string())]),
+        };
+        let args: Vec<(&str, &str)> = args.iter().map(|(name, value)| (*name, value.as_str())).collect();
+        crate::i18n::tf_with_lang(key, lang, &args)
+    }
+
+    /// 可能携带底层驱动错误文本的消息，小写化后用于特征匹配
+    fn driver_message(&self) -> Option<String> {
+        match self {
+            QuickDbError::QueryError { message }
+            | QuickDbError::TransactionError { message }
+            | QuickDbError::ConnectionError { message }
+            | QuickDbError::PoolError { message }
+            | QuickDbError::TaskExecutionError(message) => Some(message.to_lowercase()),
+            QuickDbError::Other(e) => Some(e.to_string().to_lowercase()),
+            _ => None,
+        }
+    }
+
+    fn message_matches(&self, patterns: &[&str]) -> bool {
+        self.driver_message()
+            .map(|message| patterns.iter().any(|pattern| message.contains(&pattern.to_lowercase())))
+            .unwrap_or(false)
+    }
+
+    /// 是否为可整体重试事务的冲突错误（死锁或序列化失败）
+    pub fn is_retryable_conflict(&self) -> bool {
+        self.message_matches(RETRYABLE_CONFLICT_PATTERNS)
+    }
+
+    /// 是否为查询的表不存在引起的错误
+    ///
+    /// 依据各数据库驱动的错误信息识别：SQLite `no such table`、MySQL `Table '...' doesn't exist`、
+    /// PostgreSQL `relation "..." does not exist`
+    pub fn is_missing_table(&self) -> bool {
+        let message = match self {
+            QuickDbError::QueryError { message } | QuickDbError::ConnectionError { message } => message,
+            _ => return false,
+        };
+        message.contains("no such table")
+            || (message.contains("Table '") && message.contains("doesn't exist"))
+            || (message.contains("relation \"") && message.contains("does not exist"))
+    }
+
+    /// 是否为目标不存在的错误（别名、表/集合、存储过程等）
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, QuickDbError::AliasNotFound { .. })
+            || self.is_missing_table()
+            || self.message_matches(NOT_FOUND_PATTERNS)
+    }
+
+    /// 是否为唯一约束冲突
+    pub fn is_unique_violation(&self) -> bool {
+        self.message_matches(UNIQUE_VIOLATION_PATTERNS)
+    }
+
+    /// 是否为超时错误
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            QuickDbError::PoolTimeout { .. } => true,
+            QuickDbError::IoError(e) => e.kind() == std::io::ErrorKind::TimedOut,
+            _ => self.message_matches(TIMEOUT_PATTERNS),
+        }
+    }
+
+    /// 是否为连接或连接池错误
+    pub fn is_connection_error(&self) -> bool {
+        matches!(self, QuickDbError::ConnectionError { .. } | QuickDbError::PoolError { .. })
+    }
+
+    /// 是否为瞬时错误，只读操作重新执行可能成功
+    ///
+    /// 包括连接中断、无法建立连接和超时。连接类型不匹配等配置问题以及唯一约束冲突等由数据本身引起的错误
+    /// 重试也不会成功，不在此列。连接中断和超时发生时服务端可能已经执行了语句，写操作只能在
+    /// [`is_connect_failure`](Self::is_connect_failure) 时重试
+    pub fn is_retryable(&self) -> bool {
+        if self.is_unique_violation() {
+            return false;
+        }
+        self.is_timeout() || self.is_connect_failure() || self.message_matches(CONNECTION_LOST_PATTERNS)
+    }
+
+    /// 是否为请求发出之前的连接失败（无法建立连接、等待可用连接超时），此时操作一定没有执行，写操作也可以安全重试
+    pub fn is_connect_failure(&self) -> bool {
+        self.message_matches(CONNECT_FAILURE_PATTERNS)
+    }
+
+    /// 出错的字段名（如果能确定）
+    ///
+    /// 验证错误返回被验证的字段；SQLite 唯一约束冲突从 `表.字段` 中提取字段名
+    pub fn field_name(&self) -> Option<&str> {
+        match self {
+            QuickDbError::ValidationError { field, .. } => Some(field.as_str()),
+            QuickDbError::QueryError { message } => {
+                let rest = &message[message.find("UNIQUE constraint failed: ")? + "UNIQUE constraint failed: ".len()..];
+                let column = rest.split([',', ' ', '\n', '"', ')']).next()?;
+                column.rsplit('.').next().filter(|name| !name.is_empty())
+            }
+            _ => None,
+        }
+    }
+
+    /// 错误类别
+    ///
+    /// 唯一约束冲突、不存在、超时和事务冲突优先于变体本身判断，
+    /// 例如以 `QueryError` 返回的唯一约束冲突归为 [`ErrorKind::UniqueViolation`]
+    pub fn kind(&self) -> ErrorKind {
+        if self.is_unique_violation() {
+            return ErrorKind::UniqueViolation;
+        }
+        if self.is_not_found() {
+            return ErrorKind::NotFound;
+        }
+        if self.is_timeout() {
+            return ErrorKind::Timeout;
+        }
+        if self.is_retryable_conflict() {
+            return ErrorKind::Conflict;
+        }
+        match self {
+            QuickDbError::ConnectionError { .. } | QuickDbError::PoolError { .. } => ErrorKind::Connection,
+            QuickDbError::QueryError { .. } => ErrorKind::Query,
+            QuickDbError::SerializationError { .. } | QuickDbError::JsonError(_) => ErrorKind::Serialization,
+            QuickDbError::ValidationError { .. } => ErrorKind::Validation,
+            QuickDbError::ConfigError { .. } | QuickDbError::UnsupportedDatabase { .. } => ErrorKind::Config,
+            QuickDbError::AliasNotFound { .. } => ErrorKind::NotFound,
+            QuickDbError::ReadOnly { .. } => ErrorKind::ReadOnly,
+            QuickDbError::PoolTimeout { .. } => ErrorKind::Timeout,
+            QuickDbError::TransactionError { .. } => ErrorKind::Transaction,
+            QuickDbError::CacheError { .. } => ErrorKind::Cache,
+            QuickDbError::IoError(_) => ErrorKind::Io,
+            QuickDbError::TaskExecutionError(_) | QuickDbError::Other(_) => ErrorKind::Other,
+        }
+    }
+}
+
 /// QuickDB 结果类型别名
 pub type QuickDbResult<T> = Result<T, QuickDbError>;
 
@@ -177,6 +430,7 @@ mod tests {
 
     #[test]
     fn test_error_creation() {
+        crate::i18n::set_language("zh-CN");
         let err = ErrorBuilder::connection_error("测试连接失败");
         assert!(matches!(err, QuickDbError::ConnectionError { .. }));
         assert_eq!(err.to_string(), "数据库连接失败: 测试连接失败");
@@ -184,8 +438,64 @@ mod tests {
 
     #[test]
     fn test_error_macro() {
+        crate::i18n::set_language("zh-CN");
         let err = quick_error!(validation, "用户名", "不能为空");
         assert!(matches!(err, QuickDbError::ValidationError { .. }));
         assert_eq!(err.to_string(), "模型验证失败: 用户名 - 不能为空");
     }
+
+    #[test]
+    fn test_localized_message() {
+        let err = quick_error!(alias_not_found, "main");
+        assert_eq!(err.localized_message("zh-CN"), "数据库别名 'main' 未找到");
+        assert_eq!(err.localized_message("en-US"), "Database alias 'main' not found");
+        // 没有该语言的翻译时回退，类别保持不变
+        assert!(!err.localized_message("xx-XX").starts_with('['));
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_error_kind() {
+        let err = quick_error!(query, "SQLite插入失败: UNIQUE constraint failed: users.email");
+        assert!(err.is_unique_violation());
+        assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+        assert_eq!(err.field_name(), Some("email"));
+
+        let err = quick_error!(query, "MySQL插入失败: Duplicate entry 'a@b.c' for key 'users.email'");
+        assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+
+        let err = quick_error!(alias_not_found, "missing");
+        assert!(err.is_not_found());
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let err = quick_error!(connection, "等待连接池响应超时");
+        assert!(err.is_timeout());
+        assert!(err.is_connection_error());
+        assert_eq!(err.kind(), ErrorKind::Timeout);
+
+        let err = quick_error!(validation, "age", "必须为正数");
+        assert_eq!(err.field_name(), Some("age"));
+        assert_eq!(err.kind(), ErrorKind::Validation);
+        assert!(!err.is_not_found());
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let refused = quick_error!(query, "执行PostgreSQL查询失败: error communicating with database: Connection refused (os error 111)");
+        assert!(refused.is_retryable());
+        assert!(refused.is_connect_failure());
+        assert!(quick_error!(query, "执行MySQL查询失败: pool timed out while waiting for an open connection").is_connect_failure());
+
+        // 连接中断时语句可能已经执行，只读操作可以重试，写操作不能
+        let reset = quick_error!(query, "执行MySQL更新失败: error communicating with database: Connection reset by peer (os error 104)");
+        assert!(reset.is_retryable());
+        assert!(!reset.is_connect_failure());
+
+        // 连接类型不匹配不是瞬时错误
+        assert!(!quick_error!(connection, "连接类型不匹配，期望MySQL连接").is_retryable());
+
+        assert!(!quick_error!(query, "MySQL插入失败: Duplicate entry 'a@b.c' for key 'users.email'").is_retryable());
+        assert!(!quick_error!(query, "执行PostgreSQL查询失败: syntax error at or near \"SELEC\"").is_retryable());
+        assert!(!quick_error!(validation, "age", "必须为正数").is_retryable());
+    }
 }
\ No newline at end of file
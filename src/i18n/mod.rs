@@ -1,10 +1,22 @@
 //! 多语言错误消息模块
 //!
-//! 使用rat_embed_lang框架提供统一的错误消息多语言支持
+//! 使用rat_embed_lang框架提供统一的错误消息多语言支持。
+//! [`QuickDbError`](crate::QuickDbError) 的 `Display` 按当前语言渲染，语言来自
+//! `RAT_LANG`/`LANG` 环境变量（默认 zh-CN），也可以通过 [`set_language`] 在运行时切换；
+//! 程序判断错误类别应使用 [`QuickDbError::kind`](crate::QuickDbError::kind)，不要匹配消息文本
 
 use std::collections::HashMap;
+use std::sync::Once;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
 use rat_embed_lang::register_translations;
 
+/// 翻译只注册一次，重复调用 `init` 不会因重复 key 而 panic
+static INIT: Once = Once::new();
+
+/// 应用自定义的错误消息模板：key -> (语言 -> 模板)，优先于内置翻译
+static CUSTOM_MESSAGES: Lazy<DashMap<String, HashMap<String, String>>> = Lazy::new(DashMap::new);
+
 /// 错误消息翻译注册器
 pub struct ErrorMessageI18n;
 
@@ -62,6 +74,20 @@ impl ErrorMessageI18n {
         alias_not_found_errors.insert("ja-JP".to_string(), "データベースエイリアス '{alias}' が見つかりません".to_string());
         translations.insert("error.alias_not_found".to_string(), alias_not_found_errors);
 
+        // 只读别名拒绝写操作
+        let mut read_only_errors = HashMap::new();
+        read_only_errors.insert("zh-CN".to_string(), "数据库别名 '{alias}' 为只读，拒绝写操作".to_string());
+        read_only_errors.insert("en-US".to_string(), "Database alias '{alias}' is read-only, write rejected".to_string());
+        read_only_errors.insert("ja-JP".to_string(), "データベースエイリアス '{alias}' は読み取り専用のため、書き込みを拒否しました".to_string());
+        translations.insert("error.read_only".to_string(), read_only_errors);
+
+        // 连接池饱和时快速失败
+        let mut pool_timeout_errors = HashMap::new();
+        pool_timeout_errors.insert("zh-CN".to_string(), "数据库别名 '{alias}' 的连接池已饱和，没有可用连接".to_string());
+        pool_timeout_errors.insert("en-US".to_string(), "Connection pool for database alias '{alias}' is saturated, no connection available".to_string());
+        pool_timeout_errors.insert("ja-JP".to_string(), "データベースエイリアス '{alias}' のコネクションプールが飽和しており、利用可能な接続がありません".to_string());
+        translations.insert("error.pool_timeout".to_string(), pool_timeout_errors);
+
         // 不支持的数据库类型
         let mut unsupported_db_errors = HashMap::new();
         unsupported_db_errors.insert("zh-CN".to_string(), "不支持的数据库类型: {db_type}".to_string());
@@ -236,26 +262,114 @@ impl ErrorMessageI18n {
         serialize_failed.insert("ja-JP".to_string(), "シリアライズ失敗: {message}".to_string());
         translations.insert("error.serialize".to_string(), serialize_failed);
 
+        // 事务错误
+        let mut transaction_errors = HashMap::new();
+        transaction_errors.insert("zh-CN".to_string(), "事务操作失败: {message}".to_string());
+        transaction_errors.insert("en-US".to_string(), "Transaction operation failed: {message}".to_string());
+        transaction_errors.insert("ja-JP".to_string(), "トランザクション操作が失敗しました: {message}".to_string());
+        translations.insert("error.transaction".to_string(), transaction_errors);
+
+        // 任务执行错误
+        let mut task_execution_errors = HashMap::new();
+        task_execution_errors.insert("zh-CN".to_string(), "任务执行失败: {message}".to_string());
+        task_execution_errors.insert("en-US".to_string(), "Task execution failed: {message}".to_string());
+        task_execution_errors.insert("ja-JP".to_string(), "タスク実行が失敗しました: {message}".to_string());
+        translations.insert("error.task_execution".to_string(), task_execution_errors);
+
+        // IO 错误
+        let mut io_errors = HashMap::new();
+        io_errors.insert("zh-CN".to_string(), "IO 操作失败: {message}".to_string());
+        io_errors.insert("en-US".to_string(), "IO operation failed: {message}".to_string());
+        io_errors.insert("ja-JP".to_string(), "IO 操作が失敗しました: {message}".to_string());
+        translations.insert("error.io".to_string(), io_errors);
+
+        // JSON 处理错误
+        let mut json_errors = HashMap::new();
+        json_errors.insert("zh-CN".to_string(), "JSON 处理失败: {message}".to_string());
+        json_errors.insert("en-US".to_string(), "JSON processing failed: {message}".to_string());
+        json_errors.insert("ja-JP".to_string(), "JSON 処理が失敗しました: {message}".to_string());
+        translations.insert("error.json".to_string(), json_errors);
+
+        // 通用错误
+        let mut other_errors = HashMap::new();
+        other_errors.insert("zh-CN".to_string(), "操作失败: {message}".to_string());
+        other_errors.insert("en-US".to_string(), "Operation failed: {message}".to_string());
+        other_errors.insert("ja-JP".to_string(), "操作が失敗しました: {message}".to_string());
+        translations.insert("error.other".to_string(), other_errors);
+
         // 注册所有翻译
         register_translations(translations);
     }
 
     /// 初始化错误消息多语言支持
+    ///
+    /// 首次渲染错误消息时也会自动执行，重复调用无副作用
     pub fn init() {
-        Self::register_all_translations();
+        INIT.call_once(|| {
+            Self::register_all_translations();
+
+            // 从环境变量获取语言设置，默认为zh-CN
+            let lang = std::env::var("RAT_LANG")
+                .or_else(|_| std::env::var("LANG"))
+                .unwrap_or_else(|_| "zh-CN".to_string());
+
+            // 标准化语言代码
+            use rat_embed_lang::normalize_language_code;
+            let normalized_lang = normalize_language_code(&lang);
+            rat_embed_lang::set_language(&normalized_lang);
+        });
+    }
+}
 
-        // 从环境变量获取语言设置，默认为zh-CN
-        let lang = std::env::var("RAT_LANG")
-            .or_else(|_| std::env::var("LANG"))
-            .unwrap_or_else(|_| "zh-CN".to_string());
+/// 设置消息语言（如 "zh-CN"、"en-US"、"ja-JP"），缺少对应翻译时回退到英语
+pub fn set_language(lang: &str) {
+    ErrorMessageI18n::init();
+    rat_embed_lang::set_language(lang);
+}
 
-        // 标准化语言代码
-        use rat_embed_lang::normalize_language_code;
-        let normalized_lang = normalize_language_code(&lang);
-        set_language(&normalized_lang);
-    }
+/// 获取当前消息语言
+pub fn current_language() -> String {
+    ErrorMessageI18n::init();
+    rat_embed_lang::current_language()
+}
+
+/// 获取翻译文本
+pub fn t(key: &str) -> String {
+    tf(key, &[])
 }
 
+/// 获取参数化翻译文本，`{参数名}` 会被替换为对应参数值
+pub fn tf(key: &str, args: &[(&str, &str)]) -> String {
+    tf_with_lang(key, &current_language(), args)
+}
+
+/// 按指定语言获取参数化翻译文本
+///
+/// 依次查找自定义模板、内置翻译，该语言没有翻译时回退到当前语言的内置翻译或英语
+pub fn tf_with_lang(key: &str, lang: &str, args: &[(&str, &str)]) -> String {
+    ErrorMessageI18n::init();
+    let custom = CUSTOM_MESSAGES.get(key).and_then(|templates| templates.get(lang).cloned());
+    let template = match custom {
+        Some(template) => template,
+        None if rat_embed_lang::has_translation_for_lang(key, lang) => rat_embed_lang::t_with_lang(key, lang),
+        None => rat_embed_lang::t(key),
+    };
+
+    args.iter().fold(template, |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
 
-/// 重新导出rat_embed_lang的核心函数
-pub use rat_embed_lang::{t, tf, set_language, current_language};
\ No newline at end of file
+/// 注册自定义错误消息模板，可覆盖内置翻译或补充新的语言
+///
+/// # 参数
+///
+/// * `key` - 消息key，例如 "error.connection"
+/// * `lang` - 语言代码，例如 "de-DE"
+/// * `template` - 消息模板，使用 `{message}`、`{field}` 等占位符
+pub fn register_message(key: &str, lang: &str, template: &str) {
+    CUSTOM_MESSAGES
+        .entry(key.to_string())
+        .or_default()
+        .insert(lang.to_string(), template.to_string());
+}
\ No newline at end of file
@@ -1,15 +1,54 @@
 //! ID 生成器模块
 //! 
 //! 提供多种 ID 生成策略，包括 UUID、雪花算法、MongoDB ObjectId 等。
+//! 自定义策略通过 [`register_id_generator`] 注册实现了 [`IdGeneratorTrait`] 的生成器。
 
-use crate::types::{IdStrategy, IdType};
+use crate::error::QuickDbResult;
+use crate::types::{DataValue, IdStrategy, IdType};
+use std::collections::HashMap;
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 use rat_logger::{info, warn, error};
 
+/// 自定义 ID 生成器
+///
+/// 用于接入外部ID服务、ULID 等内置策略之外的方案。注册后通过 `IdStrategy::Custom(名称)` 使用，
+/// 生成的ID建议为字符串（SQL数据库中自定义策略的ID列为文本类型）
+#[async_trait]
+pub trait IdGeneratorTrait: Send + Sync {
+    /// 为指定表和数据库别名生成新的ID
+    async fn generate(&self, table: &str, alias: &str) -> QuickDbResult<DataValue>;
+}
+
+/// 已注册的自定义 ID 生成器
+static CUSTOM_ID_GENERATORS: Lazy<DashMap<String, Arc<dyn IdGeneratorTrait>>> = Lazy::new(DashMap::new);
+
+/// 注册自定义 ID 生成器，同名的生成器会被替换
+pub fn register_id_generator(name: &str, generator: Arc<dyn IdGeneratorTrait>) {
+    info!("注册自定义ID生成器: {}", name);
+    CUSTOM_ID_GENERATORS.insert(name.to_string(), generator);
+}
+
+/// 注销自定义 ID 生成器，返回被移除的生成器
+pub fn unregister_id_generator(name: &str) -> Option<Arc<dyn IdGeneratorTrait>> {
+    CUSTOM_ID_GENERATORS.remove(name).map(|(_, generator)| generator)
+}
+
+/// 调用已注册的自定义 ID 生成器
+async fn generate_custom(name: &str, table: &str, alias: &str) -> Result<DataValue> {
+    let generator = CUSTOM_ID_GENERATORS
+        .get(name)
+        .map(|entry| entry.value().clone())
+        .ok_or_else(|| anyhow!("未注册的自定义ID生成器: {}", name))?;
+    Ok(generator.generate(table, alias).await?)
+}
+
 /// ID 生成器
 #[derive(Clone, Debug)]
 pub struct IdGenerator {
@@ -49,6 +88,17 @@ impl IdGenerator {
                 let uuid = Uuid::new_v4();
                 Ok(IdType::String(uuid.to_string()))
             }
+            IdStrategy::UuidV7 => {
+                let uuid = Uuid::now_v7();
+                Ok(IdType::String(uuid.to_string()))
+            }
+            IdStrategy::Ulid => {
+                let millis = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_millis() as u64;
+                Ok(IdType::String(encode_ulid(millis, rand::random())))
+            }
             IdStrategy::Snowflake { .. } => {
                 if let Some(generator) = &self.snowflake_generator {
                     let id = generator.generate().await?;
@@ -74,24 +124,35 @@ impl IdGenerator {
                 Ok(IdType::String(object_id))
             }
             IdStrategy::Custom(generator_name) => {
-                // 这里可以根据自定义生成器名称来调用不同的生成逻辑
-                // 目前简单返回一个基于时间戳的 ID
-                let timestamp = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_nanos();
-                
-                let custom_id = format!("{}_{}", generator_name, timestamp);
-                Ok(IdType::String(custom_id))
+                // 没有表和别名上下文，传入空字符串
+                let value = generate_custom(generator_name, "", "").await?;
+                IdType::from_data_value(&value)
+                    .ok_or_else(|| anyhow!("自定义ID生成器 {} 返回了不支持的ID类型: {:?}", generator_name, value))
             }
         }
     }
 
+    /// 为指定表和数据库别名生成新的 ID
+    ///
+    /// 自定义策略会把表名和别名传给注册的生成器，其余策略与 [`IdGenerator::generate`] 相同
+    pub async fn generate_for(&self, table: &str, alias: &str) -> Result<DataValue> {
+        match &self.strategy {
+            IdStrategy::Custom(generator_name) => generate_custom(generator_name, table, alias).await,
+            _ => Ok(self.generate().await?.into()),
+        }
+    }
+
     /// 验证 ID 格式是否有效
     pub fn validate_id(&self, id: &IdType) -> bool {
         match (&self.strategy, id) {
             (IdStrategy::AutoIncrement, IdType::Number(n)) => *n > 0,
             (IdStrategy::Uuid, IdType::String(s)) => Uuid::parse_str(s).is_ok(),
+            (IdStrategy::UuidV7, IdType::String(s)) => {
+                Uuid::parse_str(s).map(|uuid| uuid.get_version_num() == 7).unwrap_or(false)
+            }
+            (IdStrategy::Ulid, IdType::String(s)) => {
+                s.len() == 26 && s.bytes().all(|b| ULID_ALPHABET.contains(&b))
+            }
             (IdStrategy::Snowflake { .. }, IdType::String(s)) => {
                 s.parse::<u64>().is_ok()
             }
@@ -112,6 +173,59 @@ impl IdGenerator {
     pub fn set_auto_increment_start(&self, start: u64) {
         self.auto_increment_counter.store(start, Ordering::SeqCst);
     }
+
+    /// 写入前按策略填充ID字段
+    ///
+    /// 所有数据库使用同一规则，ID统一写入 `id` 字段（MongoDB适配器会映射为 `_id`）：
+    ///
+    /// | 策略 | 生成方 | 行为 |
+    /// |------|--------|------|
+    /// | AutoIncrement | 数据库 | 移除传入的 `id`/`_id`，由数据库生成并在创建后读回 |
+    /// | Uuid / UuidV7 / Ulid / Snowflake / ObjectId / Custom | ODM | ID为空时由生成器填充，否则保留传入的值 |
+    ///
+    /// 缺失、Null、空字符串和非正整数都视为空ID。
+    /// ObjectId 以24位十六进制字符串生成，MongoDB适配器写入时会转换为原生ObjectId；
+    /// Custom 策略调用注册的生成器，未注册时返回错误
+    pub async fn populate_id(&self, table: &str, alias: &str, data: &mut HashMap<String, DataValue>) -> Result<()> {
+        // 同时移除 id 和 _id，避免两者同时存在时映射结果依赖HashMap的遍历顺序
+        let provided_id = [data.remove("id"), data.remove("_id")]
+            .into_iter()
+            .flatten()
+            .find(|value| !Self::is_empty_id(value));
+
+        if matches!(self.strategy, IdStrategy::AutoIncrement) {
+            return Ok(());
+        }
+
+        let id_value = match provided_id {
+            Some(value) => value,
+            None => self.generate_for(table, alias).await?,
+        };
+        data.insert("id".to_string(), id_value);
+        Ok(())
+    }
+
+    /// 判断ID值是否为空
+    fn is_empty_id(value: &DataValue) -> bool {
+        match value {
+            DataValue::Null => true,
+            DataValue::String(s) => s.is_empty(),
+            DataValue::Int(i) => *i <= 0,
+            _ => false,
+        }
+    }
+}
+
+/// ULID 使用的 Crockford Base32 字母表
+const ULID_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// 将48位毫秒时间戳和80位随机数编码为26位 ULID 字符串
+fn encode_ulid(millis: u64, random: u128) -> String {
+    let value = ((millis as u128 & 0xFFFF_FFFF_FFFF) << 80) | (random & ((1u128 << 80) - 1));
+    (0..26)
+        .rev()
+        .map(|i| ULID_ALPHABET[((value >> (i * 5)) & 0x1F) as usize] as char)
+        .collect()
 }
 
 /// 雪花算法生成器
@@ -320,6 +434,154 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_time_sortable_generators() {
+        for (strategy, len) in [(IdStrategy::UuidV7, 36), (IdStrategy::Ulid, 26)] {
+            let generator = IdGenerator::new(strategy.clone()).unwrap();
+            let first = generator.generate().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+            let second = generator.generate().await.unwrap();
+
+            match (&first, &second) {
+                (IdType::String(s1), IdType::String(s2)) => {
+                    assert_eq!(s1.len(), len, "{:?}", strategy);
+                    // 不同毫秒生成的ID按字典序递增
+                    assert!(s1 < s2, "{:?}: {} >= {}", strategy, s1, s2);
+                    assert!(generator.validate_id(&first));
+                    assert!(generator.validate_id(&second));
+                }
+                _ => panic!("Expected string IDs"),
+            }
+        }
+
+        // ULID 编码：时间戳在前，全零和全一的边界值
+        assert_eq!(encode_ulid(0, 0), "00000000000000000000000000");
+        assert_eq!(encode_ulid(0xFFFF_FFFF_FFFF, u128::MAX), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+    }
+
+    #[tokio::test]
+    async fn test_populate_id_database_generated() {
+        let generator = IdGenerator::new(IdStrategy::AutoIncrement).unwrap();
+
+        let mut data = HashMap::new();
+        data.insert("id".to_string(), DataValue::Int(42));
+        data.insert("_id".to_string(), DataValue::Null);
+        data.insert("name".to_string(), DataValue::String("alice".to_string()));
+        generator.populate_id("users", "default", &mut data).await.unwrap();
+
+        // 自增策略由数据库生成，传入的ID全部移除
+        assert!(!data.contains_key("id"));
+        assert!(!data.contains_key("_id"));
+        assert!(data.contains_key("name"));
+    }
+
+    /// 测试用的自定义生成器：按表名加序号生成
+    struct OrderIdGenerator {
+        counter: AtomicU64,
+    }
+
+    #[async_trait]
+    impl IdGeneratorTrait for OrderIdGenerator {
+        async fn generate(&self, table: &str, _alias: &str) -> QuickDbResult<DataValue> {
+            let n = self.counter.fetch_add(1, Ordering::SeqCst);
+            Ok(DataValue::String(format!("{}-{}", table, n)))
+        }
+    }
+
+    /// ULID 示例实现：48位毫秒时间戳 + 80位随机数，以 Crockford Base32 编码为26个字符
+    struct UlidGenerator;
+
+    #[async_trait]
+    impl IdGeneratorTrait for UlidGenerator {
+        async fn generate(&self, _table: &str, _alias: &str) -> QuickDbResult<DataValue> {
+            const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+            let millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+            let random: u128 = rand::random::<u128>() & ((1u128 << 80) - 1);
+            let value = ((millis & 0xFFFF_FFFF_FFFF) << 80) | random;
+            let ulid: String = (0..26)
+                .rev()
+                .map(|i| ALPHABET[((value >> (i * 5)) & 0x1F) as usize] as char)
+                .collect();
+            Ok(DataValue::String(ulid))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_generator_registry() {
+        register_id_generator("test_ulid", Arc::new(UlidGenerator));
+        let generator = IdGenerator::new(IdStrategy::Custom("test_ulid".to_string())).unwrap();
+
+        let mut data = HashMap::new();
+        generator.populate_id("users", "default", &mut data).await.unwrap();
+        match data.get("id") {
+            Some(DataValue::String(s)) => {
+                assert_eq!(s.len(), 26);
+                assert!(s.bytes().all(|b| b.is_ascii_digit() || b.is_ascii_uppercase()));
+            }
+            other => panic!("ULID 生成了意外的ID: {:?}", other),
+        }
+
+        // 表名会传给生成器
+        register_id_generator("test_table_aware", Arc::new(OrderIdGenerator { counter: AtomicU64::new(1) }));
+        let generator = IdGenerator::new(IdStrategy::Custom("test_table_aware".to_string())).unwrap();
+        assert_eq!(
+            generator.generate_for("orders", "default").await.unwrap(),
+            DataValue::String("orders-1".to_string())
+        );
+
+        // 未注册的生成器返回错误
+        assert!(unregister_id_generator("test_table_aware").is_some());
+        assert!(generator.generate_for("orders", "default").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_populate_id_odm_generated() {
+        register_id_generator("order", Arc::new(OrderIdGenerator { counter: AtomicU64::new(1) }));
+        let strategies = vec![
+            IdStrategy::Uuid,
+            IdStrategy::UuidV7,
+            IdStrategy::Ulid,
+            IdStrategy::Snowflake { machine_id: 1, datacenter_id: 1 },
+            IdStrategy::ObjectId,
+            IdStrategy::Custom("order".to_string()),
+        ];
+
+        for strategy in strategies {
+            let generator = IdGenerator::new(strategy.clone()).unwrap();
+            let empty_ids = vec![
+                None,
+                Some(DataValue::Null),
+                Some(DataValue::String(String::new())),
+                Some(DataValue::Int(0)),
+            ];
+
+            for empty in empty_ids {
+                let mut data = HashMap::new();
+                if let Some(empty) = empty {
+                    data.insert("id".to_string(), empty.clone());
+                    data.insert("_id".to_string(), empty);
+                }
+                generator.populate_id("users", "default", &mut data).await.unwrap();
+
+                // 空ID由ODM生成，并统一写入id字段
+                assert!(!data.contains_key("_id"), "{:?}", strategy);
+                match data.get("id") {
+                    Some(DataValue::String(s)) => assert!(!s.is_empty(), "{:?}", strategy),
+                    other => panic!("{:?} 生成了意外的ID: {:?}", strategy, other),
+                }
+            }
+
+            // 用户提供的ID保持不变（无论来自id还是_id）
+            for key in ["id", "_id"] {
+                let mut data = HashMap::new();
+                data.insert(key.to_string(), DataValue::String("user-provided".to_string()));
+                generator.populate_id("users", "default", &mut data).await.unwrap();
+                assert_eq!(data.get("id"), Some(&DataValue::String("user-provided".to_string())));
+                assert!(!data.contains_key("_id"));
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_mongo_auto_increment_generator() {
         let generator = MongoAutoIncrementGenerator::new("test_collection".to_string());
@@ -148,6 +148,7 @@ macro_rules! define_join_table {
                             crate::types::query::QueryOperator::Lt => "<",
                             crate::types::query::QueryOperator::Lte => "<=",
                             crate::types::query::QueryOperator::Contains => "LIKE",
+                            crate::types::query::QueryOperator::IContains => "ILIKE",
                             crate::types::query::QueryOperator::StartsWith => "LIKE",
                             crate::types::query::QueryOperator::EndsWith => "LIKE",
                             crate::types::query::QueryOperator::In => "IN",
@@ -156,10 +157,29 @@ macro_rules! define_join_table {
                             crate::types::query::QueryOperator::Exists => "IS NOT NULL",
                             crate::types::query::QueryOperator::IsNull => "IS NULL",
                             crate::types::query::QueryOperator::IsNotNull => "IS NOT NULL",
+                            crate::types::query::QueryOperator::ArrayContainsAll => "@>",
+                            crate::types::query::QueryOperator::ArraySize => "=",
+                            crate::types::query::QueryOperator::Raw => "",
                         };
 
                         if matches!(condition.operator, crate::types::query::QueryOperator::IsNull | crate::types::query::QueryOperator::IsNotNull | crate::types::query::QueryOperator::Exists) {
                             clause_parts.push(format!("{} {}", condition.field, op_str));
+                        } else if matches!(condition.operator, crate::types::query::QueryOperator::Raw) {
+                            // 原始条件片段按已有参数数重新编号，片段无效时不匹配任何记录
+                            let mut index = params.len() + 1;
+                            match crate::adapter::raw_condition_clause(crate::types::DatabaseType::PostgreSQL, condition, &mut index) {
+                                Ok((clause, raw_params)) => {
+                                    clause_parts.push(clause);
+                                    params.extend(raw_params);
+                                }
+                                Err(e) => {
+                                    rat_logger::warn!("忽略无效的原始条件: {}", e);
+                                    clause_parts.push("1 = 0".to_string());
+                                }
+                            }
+                        } else if matches!(condition.operator, crate::types::query::QueryOperator::ArraySize) {
+                            clause_parts.push(format!("jsonb_array_length({}) {} {}", condition.field, op_str, placeholder));
+                            params.push(condition.value.clone());
                         } else {
                             clause_parts.push(format!("{} {} {}", condition.field, op_str, placeholder));
                             params.push(condition.value.clone());
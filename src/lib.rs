@@ -28,18 +28,25 @@ pub mod cache;
 pub mod join_macro;
 pub mod id_generator;
 pub mod stored_procedure;
+pub mod transaction;
+
+// 测试辅助模块（仅在启用 testing 特性时编译）
+#[cfg(feature = "testing")]
+pub mod testing;
 
 // Python API 模块（仅在启用 python-bindings 特性时编译）
 #[cfg(feature = "python-bindings")]
 pub mod python_api;
 
 // 重新导出常用类型和函数
-pub use error::{QuickDbError, QuickDbResult};
+pub use error::{QuickDbError, QuickDbResult, ErrorKind};
 pub use types::*;
-pub use pool::DatabaseConnection;
+pub use pool::{DatabaseConnection, PoolSample, PoolStatus};
 pub use manager::{
-    add_database, get_aliases, set_default_alias, health_check,
-    table_exists, drop_table, register_model
+    add_database, get_aliases, set_default_alias, get_capabilities, health_check, pool_history,
+    get_pool_status, get_all_pool_status,
+    is_saturated, try_operation,
+    table_exists, drop_table, rename_table, register_model, get_registered_model, get_raw_connection
 };
 
 pub use manager::{
@@ -47,7 +54,8 @@ pub use manager::{
 };
 pub use odm::{AsyncOdmManager, get_odm_manager, get_odm_manager_mut, OdmOperations};
 pub use model::{
-    Model, ModelOperations, ModelManager, FieldType, FieldDefinition, ModelMeta, IndexDefinition,
+    Model, ModelOperations, ModelManager, DynamicModel, Row, FieldType, FieldDefinition, FieldInfo, FieldNormalizer, ArrayElementPolicy, DefaultExpr, ModelMeta, IndexDefinition,
+    ReadCoercion, set_read_coercion, read_coercion, register_normalizer, unregister_normalizer,
     array_field, list_field, string_field, integer_field, float_field, boolean_field,
     datetime_field, uuid_field, json_field, dict_field, reference_field
 };
@@ -66,20 +74,23 @@ pub use task_queue::{
 pub use table::{TableManager, TableSchema, ColumnDefinition, ColumnType, IndexType};
 
 // 条件导出缓存相关类型
-pub use cache::{CacheManager, CacheStats};
+pub use cache::{CacheManager, CacheStats, CacheWarmSpec};
 
 // 导出ID生成器相关类型
-pub use id_generator::{IdGenerator, MongoAutoIncrementGenerator};
+pub use id_generator::{IdGenerator, MongoAutoIncrementGenerator, IdGeneratorTrait, register_id_generator, unregister_id_generator};
 
 // 导出存储过程相关类型
 pub use stored_procedure::*;
 
+// 导出事务相关类型
+pub use transaction::{IsolationLevel, SavepointAction, TransactionRetryConfig, retry_transaction};
+
 // ODM 操作函数改为内部公开，仅用于框架内部使用
 pub(crate) use odm::{create, find_by_id, find, find_with_groups, update, update_by_id, delete, delete_by_id, count, exists};
 pub(crate) use odm::{create_stored_procedure, execute_stored_procedure};
 
 // 保留有用的工具函数公开导出
-pub use odm::get_server_version;
+pub use odm::{get_server_version, flush_write_behind, seed, upsert, upsert_many, subscribe, unsubscribe, SubscriptionId};
 
 // Python API 导出（仅在启用 python-bindings 特性时）
 // 注意：Python绑定相关的导出已移至专门的Python绑定库中
@@ -139,7 +150,8 @@ pub fn generate_object_id() -> String {
 /// 根据模型元数据处理DataValue中的字段类型转换
 ///
 /// 这是一个通用的数据后处理工具，用于处理数据库适配器返回的原始数据
-/// 根据模型元数据将字符串形式的复杂数据（JSON、数组等）转换为正确的DataValue类型
+/// 根据模型元数据将字符串形式的复杂数据（JSON、数组等）转换为正确的DataValue类型；
+/// 读取转换模式为 [`ReadCoercion::Lenient`] 时，还会把类型不一致的值转换为字段声明的类型
 ///
 /// # 参数
 /// * `data_map` - 从数据库读取的原始数据映射
@@ -154,8 +166,10 @@ pub fn process_data_fields_from_metadata(
     for (field_name, field_def) in fields {
         if let Some(current_value) = data_map.get::<str>(field_name) {
             let converted_value = match current_value {
-                // 处理字符串类型的JSON数据
-                DataValue::String(json_str) if json_str.starts_with('[') || json_str.starts_with('{') => {
+                // 处理字符串类型的JSON数据（仅限元数据声明为JSON/数组/对象的字段，
+                // 普通字符串字段即使内容形如 "[1,2,3]" 也保持原样）
+                DataValue::String(json_str) if field_def.field_type.is_json_like()
+                    && (json_str.starts_with('[') || json_str.starts_with('{')) => {
                     // 尝试解析JSON
                     match serde_json::from_str::<serde_json::Value>(json_str.as_str()) {
                         Ok(json_value) => {
@@ -169,6 +183,20 @@ pub fn process_data_fields_from_metadata(
                         }
                     }
                 },
+                // 浮点字段的十进制字符串转换（PostgreSQL NUMERIC / MySQL DECIMAL 以精确字符串返回）
+                DataValue::String(num_str) if matches!(
+                    field_def.field_type,
+                    crate::model::FieldType::Float { .. } | crate::model::FieldType::Double
+                ) => {
+                    num_str.parse::<f64>().ok().map(DataValue::Float)
+                },
+                // 十进制字段保持精确字符串，SQLite 以 REAL 存储返回的数值也转为字符串
+                DataValue::Int(_) | DataValue::Float(_) if matches!(
+                    field_def.field_type,
+                    crate::model::FieldType::Decimal { .. }
+                ) => {
+                    Some(DataValue::String(current_value.to_string()))
+                },
                 // 处理布尔字段的整数转换（SQLite等数据库的兼容性）
                 DataValue::Int(int_val) if matches!(field_def.field_type, crate::model::FieldType::Boolean) => {
                     if *int_val == 0 || *int_val == 1 {
@@ -182,6 +210,20 @@ pub fn process_data_fields_from_metadata(
                 _ => None, // 其他类型保持不变
             };
 
+            // 宽松模式下再按字段类型转换仍不一致的值
+            let converted_value = if crate::model::read_coercion() == crate::model::ReadCoercion::Lenient {
+                let value = converted_value.as_ref().unwrap_or(current_value);
+                match crate::model::coercion::coerce_read_value(value, &field_def.field_type) {
+                    Some(coerced) => {
+                        debug!("字段 {} 类型兼容转换: {} -> {}", field_name, value.pretty(), coerced.pretty());
+                        Some(coerced)
+                    }
+                    None => converted_value,
+                }
+            } else {
+                converted_value
+            };
+
             // 如果有转换结果，更新数据映射
             if let Some(converted) = converted_value {
                 data_map.insert(field_name.clone(), converted);
@@ -1,7 +1,7 @@
   //! 数据库操作相关方法
 
 use crate::error::{QuickDbError, QuickDbResult};
-use crate::pool::{ConnectionPool, PooledConnection, ExtendedPoolConfig};
+use crate::pool::{ConnectionPool, PooledConnection, ExtendedPoolConfig, DatabaseConnection};
 use crate::types::{DatabaseConfig, DatabaseType, IdType};
 use crate::id_generator::{IdGenerator, MongoAutoIncrementGenerator};
 use crate::cache::{CacheManager, CacheStats};
@@ -29,6 +29,9 @@ impl PoolManager {
             self.remove_database(&alias).await?;
         }
         
+        // 按配置创建缺失的数据库，只在建立任何连接池之前执行一次
+        crate::adapter::create_database_if_missing(&config).await?;
+
         // 初始化缓存管理器（如果配置了缓存）
         let cache_manager_arc = if let Some(cache_config) = &config.cache {
             let cache_manager = CacheManager::new(cache_config.clone()).await.map_err(|e| {
@@ -222,4 +225,107 @@ impl PoolManager {
     pub fn get_connection_pools(&self) -> Arc<DashMap<String, Arc<ConnectionPool>>> {
         self.pools.clone()
     }
+
+    /// 检查别名是否允许写入，只读别名返回 `QuickDbError::ReadOnly`
+    ///
+    /// 别名不存在时不报错，由后续操作返回 `AliasNotFound`
+    pub fn ensure_writable(&self, alias: &str) -> QuickDbResult<()> {
+        match self.pools.get(alias) {
+            Some(pool) if pool.db_config.read_only => Err(QuickDbError::ReadOnly {
+                alias: alias.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// 检查别名的连接池是否已饱和
+    ///
+    /// 未完成的操作数达到 `max_connections` 时返回 `true`，可用于在过载时提前拒绝请求；
+    /// 别名不存在时返回 `false`
+    pub fn is_saturated(&self, alias: &str) -> bool {
+        self.pools.get(alias).map(|pool| pool.is_saturated()).unwrap_or(false)
+    }
+
+    /// 连接池未饱和时执行操作，饱和时立即返回 `QuickDbError::PoolTimeout` 而不排队等待
+    ///
+    /// 检查与执行之间不加锁，并发调用时仍可能有少量操作排队；
+    /// 别名不存在时不报错，由 `operation` 自身返回 `AliasNotFound`
+    pub async fn try_operation<T, F>(&self, alias: &str, operation: F) -> QuickDbResult<T>
+    where
+        F: std::future::Future<Output = QuickDbResult<T>>,
+    {
+        if self.is_saturated(alias) {
+            debug!("连接池已饱和，快速失败: 别名={}", alias);
+            return Err(QuickDbError::PoolTimeout {
+                alias: alias.to_string(),
+            });
+        }
+        operation.await
+    }
+
+    /// 获取底层原生连接句柄（逃生通道）
+    ///
+    /// 用于访问ODM未覆盖的驱动功能（如PostgreSQL LISTEN/NOTIFY、MongoDB watch）。
+    /// 连接仍由本库统一管理，但通过原生句柄执行的操作会绕过缓存、数据验证和ID生成，
+    /// 写入后相关缓存需要自行清理
+    pub async fn raw_connection(&self, alias: Option<&str>) -> QuickDbResult<DatabaseConnection> {
+        let target_alias = match alias {
+            Some(a) => a.to_string(),
+            None => self.get_default_alias().await
+                .ok_or_else(|| crate::quick_error!(config, "没有配置默认数据库别名"))?,
+        };
+
+        let pool = self.pools.get(&target_alias)
+            .map(|p| p.clone())
+            .ok_or_else(|| crate::quick_error!(alias_not_found, target_alias))?;
+        pool.raw_connection().await
+    }
+
+    /// 获取底层sqlx SQLite连接池，数据库类型不匹配时返回None
+    ///
+    /// 注意事项同 [`PoolManager::raw_connection`]
+    #[cfg(feature = "sqlite-support")]
+    pub async fn raw_sqlite_pool(&self, alias: Option<&str>) -> QuickDbResult<Option<sqlx::SqlitePool>> {
+        match self.raw_connection(alias).await? {
+            DatabaseConnection::SQLite(pool) => Ok(Some(pool)),
+            #[allow(unreachable_patterns)]
+            _ => Ok(None),
+        }
+    }
+
+    /// 获取底层sqlx PostgreSQL连接池，数据库类型不匹配时返回None
+    ///
+    /// 注意事项同 [`PoolManager::raw_connection`]
+    #[cfg(feature = "postgres-support")]
+    pub async fn raw_postgres_pool(&self, alias: Option<&str>) -> QuickDbResult<Option<sqlx::PgPool>> {
+        match self.raw_connection(alias).await? {
+            DatabaseConnection::PostgreSQL(pool) => Ok(Some(pool)),
+            #[allow(unreachable_patterns)]
+            _ => Ok(None),
+        }
+    }
+
+    /// 获取底层sqlx MySQL连接池，数据库类型不匹配时返回None
+    ///
+    /// 注意事项同 [`PoolManager::raw_connection`]
+    #[cfg(feature = "mysql-support")]
+    pub async fn raw_mysql_pool(&self, alias: Option<&str>) -> QuickDbResult<Option<sqlx::MySqlPool>> {
+        match self.raw_connection(alias).await? {
+            DatabaseConnection::MySQL(pool) => Ok(Some(pool)),
+            #[allow(unreachable_patterns)]
+            _ => Ok(None),
+        }
+    }
+
+    /// 获取底层MongoDB数据库句柄，数据库类型不匹配时返回None
+    ///
+    /// 注意事项同 [`PoolManager::raw_connection`]
+    #[cfg(feature = "mongodb-support")]
+    pub async fn raw_mongo_database(&self, alias: Option<&str>) -> QuickDbResult<Option<mongodb::Database>> {
+        match self.raw_connection(alias).await? {
+            DatabaseConnection::MongoDB(db) => Ok(Some(db)),
+            #[allow(unreachable_patterns)]
+            _ => Ok(None),
+        }
+    }
 }
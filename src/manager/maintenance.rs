@@ -41,6 +41,37 @@ impl PoolManager {
         health_status
     }
 
+    /// 获取连接池使用情况的采样历史（按时间顺序）
+    ///
+    /// 需要在连接池配置中启用 `sampling`，未启用时返回空列表
+    pub fn pool_history(&self, alias: &str) -> QuickDbResult<Vec<crate::pool::PoolSample>> {
+        let pool = self.pools.get(alias).ok_or_else(|| QuickDbError::AliasNotFound {
+            alias: alias.to_string(),
+        })?;
+        Ok(pool.usage.history())
+    }
+
+    /// 获取别名对应连接池的实时状态，别名不存在时返回 `None`
+    ///
+    /// SQL数据库的连接数读取sqlx连接池的实时计数；MongoDB驱动不暴露连接池状态，
+    /// 以执行中的操作数作为使用中连接数。工作负载连接池不计入
+    pub fn get_pool_status(&self, alias: &str) -> Option<crate::pool::PoolStatus> {
+        self.pools.get(alias).map(|pool| pool.usage.status(&pool.config.base))
+    }
+
+    /// 获取所有连接池的实时状态，以别名为键
+    pub fn get_all_pool_status(&self) -> HashMap<String, crate::pool::PoolStatus> {
+        self.pools.iter()
+            .map(|entry| (entry.key().clone(), entry.value().usage.status(&entry.value().config.base)))
+            .collect()
+    }
+
+    /// 按采样历史计算使用中连接数的百分位（如 0.95 表示 p95），没有样本时返回 `None`
+    pub fn pool_in_use_percentile(&self, alias: &str, percentile: f64) -> QuickDbResult<Option<u32>> {
+        let samples = self.pool_history(alias)?;
+        Ok(crate::pool::in_use_percentile(&samples, percentile))
+    }
+
     /// 获取所有活跃连接池的详细状态信息
     /// 
     /// 返回包含每个连接池状态的详细信息，包括：
@@ -110,6 +141,7 @@ impl PoolManager {
                     "max_lifetime": pool.config.base.max_lifetime,
                     "max_retries": pool.config.max_retries,
                     "retry_interval_ms": pool.config.retry_interval_ms,
+                    "retry_attempts": pool.config.base.retry_attempts,
                     "keepalive_interval_sec": pool.config.keepalive_interval_sec,
                     "health_check_timeout_sec": pool.config.health_check_timeout_sec
                 },
@@ -136,6 +168,11 @@ impl PoolManager {
     pub async fn shutdown(&self) -> QuickDbResult<()> {
         info!("开始关闭连接池管理器");
 
+        // 先刷新写后缓冲，连接池清空后已入队的数据将无法写入
+        if let Err(e) = crate::odm::write_behind::shutdown_write_behind().await {
+            error!("关闭时刷新写后缓冲失败: {}", e);
+        }
+
         // 停止清理任务
         self.stop_cleanup_task().await;
 
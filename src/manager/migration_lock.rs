@@ -0,0 +1,250 @@
+//! 迁移锁
+//!
+//! 多个实例同时启动并同步模型结构时，用数据库级别的锁保证同一时间只有一个实例执行结构变更。
+//! 其余实例等待锁释放后再继续，此时结构已是最新，同步不会产生任何变更。
+//!
+//! - PostgreSQL：会话级 advisory lock（`pg_try_advisory_lock`）
+//! - MySQL：命名锁（`GET_LOCK`）
+//! - SQLite：锁表 `_rat_quickdb_migration_lock` 中的一行
+//! - MongoDB：锁集合 `_rat_quickdb_migration_lock` 中的一个文档
+
+use std::time::Duration;
+
+use rat_logger::debug;
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::pool::DatabaseConnection;
+
+/// 锁名称（MySQL命名锁、SQLite锁行、MongoDB锁文档共用）
+const LOCK_NAME: &str = "rat_quickdb_migration";
+
+/// PostgreSQL advisory lock 的键
+#[cfg(feature = "postgres-support")]
+const ADVISORY_LOCK_KEY: i64 = 0x7261_745f_7164_62;
+
+/// SQLite锁表 / MongoDB锁集合名称
+#[cfg(any(feature = "sqlite-support", feature = "mongodb-support"))]
+const LOCK_TABLE: &str = "_rat_quickdb_migration_lock";
+
+/// 等待锁的最长时间（秒）
+const ACQUIRE_TIMEOUT_SECS: u64 = 600;
+
+/// 锁行/锁文档超过该时间（秒）仍未释放视为持有者已崩溃，允许接管
+#[cfg(any(feature = "sqlite-support", feature = "mongodb-support"))]
+const STALE_AFTER_SECS: i64 = 600;
+
+/// 重试获取锁的间隔
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 已持有的迁移锁，需调用 [`MigrationLock::release`] 释放
+pub(crate) enum MigrationLock {
+    #[cfg(feature = "sqlite-support")]
+    SQLite { pool: sqlx::SqlitePool, owner: String },
+    /// 独立于连接池的连接，锁随会话存在，进程崩溃时由数据库自动释放
+    #[cfg(feature = "postgres-support")]
+    PostgreSQL(sqlx::PgConnection),
+    #[cfg(feature = "mysql-support")]
+    MySQL(sqlx::MySqlConnection),
+    #[cfg(feature = "mongodb-support")]
+    MongoDB { database: mongodb::Database, owner: String },
+}
+
+impl MigrationLock {
+    /// 获取迁移锁，锁被其他实例持有时等待，超过等待时间返回错误
+    pub(crate) async fn acquire(connection: &DatabaseConnection) -> QuickDbResult<Self> {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(ACQUIRE_TIMEOUT_SECS);
+
+        match connection {
+            #[cfg(feature = "sqlite-support")]
+            DatabaseConnection::SQLite(pool) => {
+                sqlx::query(&format!(
+                    "CREATE TABLE IF NOT EXISTS {} (name TEXT PRIMARY KEY, owner TEXT NOT NULL, acquired_at INTEGER NOT NULL)",
+                    LOCK_TABLE
+                ))
+                .execute(pool)
+                .await
+                .map_err(|e| lock_error("创建迁移锁表", e))?;
+
+                let owner = uuid::Uuid::new_v4().to_string();
+                loop {
+                    let now = chrono::Utc::now().timestamp();
+                    sqlx::query(&format!("DELETE FROM {} WHERE name = ? AND acquired_at < ?", LOCK_TABLE))
+                        .bind(LOCK_NAME)
+                        .bind(now - STALE_AFTER_SECS)
+                        .execute(pool)
+                        .await
+                        .map_err(|e| lock_error("清理过期迁移锁", e))?;
+
+                    let inserted = sqlx::query(&format!(
+                        "INSERT OR IGNORE INTO {} (name, owner, acquired_at) VALUES (?, ?, ?)",
+                        LOCK_TABLE
+                    ))
+                    .bind(LOCK_NAME)
+                    .bind(&owner)
+                    .bind(now)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| lock_error("获取迁移锁", e))?
+                    .rows_affected();
+
+                    if inserted == 1 {
+                        debug!("已获取SQLite迁移锁");
+                        return Ok(Self::SQLite { pool: pool.clone(), owner });
+                    }
+                    wait_or_timeout(deadline).await?;
+                }
+            },
+            #[cfg(feature = "postgres-support")]
+            DatabaseConnection::PostgreSQL(pool) => {
+                use sqlx::Connection;
+
+                let mut conn = sqlx::PgConnection::connect_with(&pool.connect_options())
+                    .await
+                    .map_err(|e| lock_error("建立迁移锁连接", e))?;
+                loop {
+                    let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+                        .bind(ADVISORY_LOCK_KEY)
+                        .fetch_one(&mut conn)
+                        .await
+                        .map_err(|e| lock_error("获取迁移锁", e))?;
+                    if acquired {
+                        debug!("已获取PostgreSQL迁移锁");
+                        return Ok(Self::PostgreSQL(conn));
+                    }
+                    if let Err(e) = wait_or_timeout(deadline).await {
+                        let _ = conn.close().await;
+                        return Err(e);
+                    }
+                }
+            },
+            #[cfg(feature = "mysql-support")]
+            DatabaseConnection::MySQL(pool) => {
+                use sqlx::Connection;
+
+                let mut conn = sqlx::MySqlConnection::connect_with(&pool.connect_options())
+                    .await
+                    .map_err(|e| lock_error("建立迁移锁连接", e))?;
+                let acquired: Option<i64> = sqlx::query_scalar("SELECT GET_LOCK(?, ?)")
+                    .bind(LOCK_NAME)
+                    .bind(ACQUIRE_TIMEOUT_SECS as i64)
+                    .fetch_one(&mut conn)
+                    .await
+                    .map_err(|e| lock_error("获取迁移锁", e))?;
+                if acquired != Some(1) {
+                    let _ = conn.close().await;
+                    return Err(timeout_error());
+                }
+                debug!("已获取MySQL迁移锁");
+                Ok(Self::MySQL(conn))
+            },
+            #[cfg(feature = "mongodb-support")]
+            DatabaseConnection::MongoDB(database) => {
+                use mongodb::bson::{doc, DateTime as BsonDateTime, Document};
+
+                let collection = database.collection::<Document>(LOCK_TABLE);
+                let owner = uuid::Uuid::new_v4().to_string();
+                loop {
+                    let now = chrono::Utc::now();
+                    let stale_before = BsonDateTime::from_millis((now.timestamp() - STALE_AFTER_SECS) * 1000);
+                    collection
+                        .delete_one(doc! { "_id": LOCK_NAME, "acquired_at": { "$lt": stale_before } }, None)
+                        .await
+                        .map_err(|e| lock_error("清理过期迁移锁", e))?;
+
+                    let lock_doc = doc! {
+                        "_id": LOCK_NAME,
+                        "owner": &owner,
+                        "acquired_at": BsonDateTime::from_millis(now.timestamp_millis()),
+                    };
+                    match collection.insert_one(lock_doc, None).await {
+                        Ok(_) => {
+                            debug!("已获取MongoDB迁移锁");
+                            return Ok(Self::MongoDB { database: database.clone(), owner });
+                        }
+                        Err(e) => {
+                            // 锁文档已存在说明锁被其他实例持有，继续等待
+                            let error = lock_error("获取迁移锁", e);
+                            if !error.is_unique_violation() {
+                                return Err(error);
+                            }
+                        }
+                    }
+                    wait_or_timeout(deadline).await?;
+                }
+            },
+            // 未启用任何数据库特性时连接类型没有可用的变体
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// 释放迁移锁
+    pub(crate) async fn release(self) -> QuickDbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite-support")]
+            Self::SQLite { pool, owner } => {
+                sqlx::query(&format!("DELETE FROM {} WHERE name = ? AND owner = ?", LOCK_TABLE))
+                    .bind(LOCK_NAME)
+                    .bind(&owner)
+                    .execute(&pool)
+                    .await
+                    .map_err(|e| lock_error("释放迁移锁", e))?;
+            },
+            #[cfg(feature = "postgres-support")]
+            Self::PostgreSQL(mut conn) => {
+                use sqlx::Connection;
+
+                let result = sqlx::query("SELECT pg_advisory_unlock($1)")
+                    .bind(ADVISORY_LOCK_KEY)
+                    .execute(&mut conn)
+                    .await;
+                // 关闭连接同样会释放会话级锁，解锁失败时不影响结果
+                let _ = conn.close().await;
+                result.map_err(|e| lock_error("释放迁移锁", e))?;
+            },
+            #[cfg(feature = "mysql-support")]
+            Self::MySQL(mut conn) => {
+                use sqlx::Connection;
+
+                let result = sqlx::query("SELECT RELEASE_LOCK(?)")
+                    .bind(LOCK_NAME)
+                    .execute(&mut conn)
+                    .await;
+                let _ = conn.close().await;
+                result.map_err(|e| lock_error("释放迁移锁", e))?;
+            },
+            #[cfg(feature = "mongodb-support")]
+            Self::MongoDB { database, owner } => {
+                use mongodb::bson::{doc, Document};
+
+                database.collection::<Document>(LOCK_TABLE)
+                    .delete_one(doc! { "_id": LOCK_NAME, "owner": &owner }, None)
+                    .await
+                    .map_err(|e| lock_error("释放迁移锁", e))?;
+            },
+        }
+        debug!("已释放迁移锁");
+        Ok(())
+    }
+}
+
+/// 等待下一次重试，超过截止时间时返回超时错误
+async fn wait_or_timeout(deadline: tokio::time::Instant) -> QuickDbResult<()> {
+    if tokio::time::Instant::now() + RETRY_INTERVAL > deadline {
+        return Err(timeout_error());
+    }
+    tokio::time::sleep(RETRY_INTERVAL).await;
+    Ok(())
+}
+
+fn timeout_error() -> QuickDbError {
+    QuickDbError::QueryError {
+        message: format!("等待迁移锁超时（{}秒），可能有其他实例正在同步模型结构", ACQUIRE_TIMEOUT_SECS),
+    }
+}
+
+fn lock_error(action: &str, e: impl std::fmt::Display) -> QuickDbError {
+    QuickDbError::QueryError {
+        message: format!("{}失败: {}", action, e),
+    }
+}
@@ -7,6 +7,7 @@ mod database_ops;
 mod cache_ops;
 mod model_ops;
 mod maintenance;
+mod migration_lock;
 
 // 重新导出主要类型
 pub use manager::PoolManager;
@@ -17,8 +18,8 @@ use tokio::sync::RwLock;
 use dashmap::DashMap;
 
 use crate::error::{QuickDbError, QuickDbResult};
-use crate::pool::{ConnectionPool, PooledConnection};
-use crate::types::{DatabaseConfig, IdType};
+use crate::pool::{ConnectionPool, PooledConnection, DatabaseConnection};
+use crate::types::{Capabilities, DatabaseConfig, IdType};
 use crate::id_generator::{IdGenerator, MongoAutoIncrementGenerator};
 use crate::cache::{CacheManager, CacheStats};
 use crate::model::ModelMeta;
@@ -61,6 +62,16 @@ pub async fn release_connection(connection: &PooledConnection) -> QuickDbResult<
     get_global_pool_manager().release_connection(connection).await
 }
 
+/// 便捷函数 - 获取底层原生连接句柄
+///
+/// 通过原生句柄执行的操作会绕过ODM的缓存、数据验证和ID生成，详见 [`PoolManager::raw_connection`]
+pub async fn get_raw_connection(alias: Option<&str>) -> QuickDbResult<DatabaseConnection> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    get_global_pool_manager().raw_connection(alias).await
+}
+
 /// 便捷函数 - 获取所有别名
 pub fn get_aliases() -> Vec<String> {
     get_global_pool_manager().get_aliases()
@@ -71,6 +82,11 @@ pub async fn set_default_alias(alias: &str) -> QuickDbResult<()> {
     get_global_pool_manager().set_default_alias(alias).await
 }
 
+/// 便捷函数 - 获取别名对应数据库支持的功能
+pub fn get_capabilities(alias: &str) -> QuickDbResult<Capabilities> {
+    Ok(get_global_pool_manager().get_database_type(alias)?.capabilities())
+}
+
 
 
 /// 便捷函数 - 健康检查
@@ -81,6 +97,34 @@ pub async fn health_check() -> std::collections::HashMap<String, bool> {
     get_global_pool_manager().health_check().await
 }
 
+/// 便捷函数 - 获取连接池使用情况的采样历史
+pub fn pool_history(alias: &str) -> QuickDbResult<Vec<crate::pool::PoolSample>> {
+    get_global_pool_manager().pool_history(alias)
+}
+
+/// 便捷函数 - 获取别名对应连接池的实时状态
+pub fn get_pool_status(alias: &str) -> Option<crate::pool::PoolStatus> {
+    get_global_pool_manager().get_pool_status(alias)
+}
+
+/// 便捷函数 - 获取所有连接池的实时状态
+pub fn get_all_pool_status() -> std::collections::HashMap<String, crate::pool::PoolStatus> {
+    get_global_pool_manager().get_all_pool_status()
+}
+
+/// 便捷函数 - 检查别名的连接池是否已饱和
+pub fn is_saturated(alias: &str) -> bool {
+    get_global_pool_manager().is_saturated(alias)
+}
+
+/// 便捷函数 - 连接池未饱和时执行操作，饱和时立即返回 `QuickDbError::PoolTimeout`
+pub async fn try_operation<T, F>(alias: &str, operation: F) -> QuickDbResult<T>
+where
+    F: std::future::Future<Output = QuickDbResult<T>>,
+{
+    get_global_pool_manager().try_operation(alias, operation).await
+}
+
 /// 便捷函数 - 获取所有活跃连接池的详细状态信息
 pub async fn get_active_pools_status() -> std::collections::HashMap<String, serde_json::Value> {
     // 锁定全局操作
@@ -112,6 +156,13 @@ pub fn get_model(collection_name: &str) -> Option<ModelMeta> {
     get_global_pool_manager().get_model(collection_name)
 }
 
+/// 便捷函数 - 获取已注册模型的元数据，用于运行时反射
+///
+/// 通过 [`ModelMeta::field_infos`] 遍历字段名、类型和约束
+pub fn get_registered_model(collection_name: &str) -> Option<ModelMeta> {
+    get_global_pool_manager().get_model(collection_name)
+}
+
 /// 便捷函数 - 检查模型是否已注册
 pub fn has_model(collection_name: &str) -> bool {
     get_global_pool_manager().has_model(collection_name)
@@ -312,6 +363,28 @@ pub async fn drop_table(alias: &str, table: &str) -> QuickDbResult<()> {
     // 执行删除操作
     pool.drop_table(table).await
 }
+
+/// 便捷函数 - 重命名表/集合
+///
+/// SQL数据库执行 `ALTER TABLE ... RENAME TO ...`，MongoDB执行 `renameCollection`；
+/// 原表不存在或新名称已被占用时返回错误，成功后清理新旧两个名称的缓存
+///
+/// # 参数
+/// * `alias` - 数据库别名
+/// * `from` - 原表名或集合名
+/// * `to` - 新表名或集合名
+///
+pub async fn rename_table(alias: &str, from: &str, to: &str) -> QuickDbResult<()> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let pool = get_global_pool_manager().pools.get(alias)
+        .ok_or_else(|| QuickDbError::AliasNotFound {
+            alias: alias.to_string(),
+        })?;
+
+    pool.rename_table(from, to).await
+}
 /// 便捷函数 - 获取数据库ID策略
 pub fn get_id_strategy(alias: &str) -> QuickDbResult<IdStrategy> {
     get_global_pool_manager().get_id_strategy(alias)
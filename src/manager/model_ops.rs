@@ -2,7 +2,7 @@
 
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::pool::{ConnectionPool, PooledConnection, ExtendedPoolConfig};
-use crate::types::{DatabaseConfig, DatabaseType, IdType};
+use crate::types::{DatabaseConfig, DatabaseType, IdType, SchemaSyncReport};
 use crate::id_generator::{IdGenerator, MongoAutoIncrementGenerator};
 use crate::cache::{CacheManager, CacheStats};
 use crate::model::ModelMeta;
@@ -14,6 +14,7 @@ use tokio::time::{interval, Duration};
 use rat_logger::{info, warn, error, debug};
 
 use super::PoolManager;
+use super::migration_lock::MigrationLock;
 
 impl PoolManager {
     /// 注册模型元数据
@@ -34,8 +35,20 @@ impl PoolManager {
     }
 
     /// 获取模型元数据
+    ///
+    /// 注册表以 `别名:集合名` 为键；按集合名查找时依次尝试原始键、默认别名，
+    /// 最后在所有别名中查找同名集合
     pub fn get_model(&self, collection_name: &str) -> Option<ModelMeta> {
-        self.model_registry.get(collection_name).map(|meta| meta.clone())
+        if let Some(meta) = self.model_registry.get(collection_name) {
+            return Some(meta.clone());
+        }
+        if let Some(meta) = self.model_registry.get(&format!("default:{}", collection_name)) {
+            return Some(meta.clone());
+        }
+        self.model_registry
+            .iter()
+            .find(|entry| entry.value().collection_name == collection_name)
+            .map(|entry| entry.value().clone())
     }
 
     /// 获取指定数据库的模型元数据
@@ -46,7 +59,7 @@ impl PoolManager {
 
     /// 检查模型是否已注册
     pub fn has_model(&self, collection_name: &str) -> bool {
-        self.model_registry.contains_key(collection_name)
+        self.get_model(collection_name).is_some()
     }
 
     /// 获取所有已注册的模型
@@ -102,7 +115,7 @@ impl PoolManager {
                 }
 
                 // 创建索引
-                for index in &model_meta.indexes {
+                for index in &model_meta.effective_indexes(pool.db_config.db_type) {
                     let default_name = format!("idx_{}", index.fields.join("_"));
                     let index_name = index.name.as_deref().unwrap_or(&default_name);
                     debug!("创建索引: {} (字段: {:?}, 唯一: {})", index_name, index.fields, index.unique);
@@ -115,8 +128,7 @@ impl PoolManager {
                     if let Err(e) = pool.create_index(
                         &collection_name,
                         index_name,
-                        &index.fields,
-                        index.unique
+                        index
                     ).await {
                         // 如果是索引已存在的错误，忽略它
                         let error_msg = e.to_string().to_lowercase();
@@ -143,4 +155,94 @@ impl PoolManager {
 
         Ok(())
     }
+
+    /// 同步模型结构（仅执行增量变更）
+    ///
+    /// 依次执行：注册模型元数据、表不存在时建表、补充缺失的列、确保声明的索引存在。
+    /// 可在每次启动时安全调用，不会删除或修改已有的表、列和索引。
+    /// 执行期间持有数据库级别的迁移锁，多个实例同时同步时依次执行，后执行的实例不会重复变更。
+    pub async fn sync_model_schema(&self, model_meta: ModelMeta, alias: &str) -> QuickDbResult<SchemaSyncReport> {
+        let pool = self.pools.get(alias)
+            .map(|pool| pool.clone())
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: alias.to_string(),
+            })?;
+
+        let lock = MigrationLock::acquire(&pool.raw_connection().await?).await?;
+        let result = self.sync_model_schema_locked(&pool, model_meta, alias).await;
+        let released = lock.release().await;
+        let report = result?;
+        released?;
+        Ok(report)
+    }
+
+    /// 在已持有迁移锁的情况下同步模型结构
+    async fn sync_model_schema_locked(&self, pool: &ConnectionPool, model_meta: ModelMeta, alias: &str) -> QuickDbResult<SchemaSyncReport> {
+        let collection_name = model_meta.collection_name.clone();
+        let mut model_meta = model_meta;
+        model_meta.database_alias = Some(alias.to_string());
+        self.register_model(model_meta.clone())?;
+
+        let mut report = SchemaSyncReport::new(&collection_name, alias);
+        let schemaless = pool.db_config.db_type == DatabaseType::MongoDB;
+
+        if !pool.table_exists(&collection_name).await? {
+            info!("表 {} 不存在，正在创建", collection_name);
+            pool.create_table(&collection_name, &model_meta.fields, &pool.db_config.id_strategy).await?;
+            report.table_created = true;
+        } else if !schemaless {
+            let existing_columns = pool.get_table_columns(&collection_name).await?;
+
+            let mut field_names: Vec<&String> = model_meta.fields.keys().collect();
+            field_names.sort();
+            for field_name in field_names {
+                if field_name == "id" || existing_columns.iter().any(|c| c.eq_ignore_ascii_case(field_name)) {
+                    continue;
+                }
+                let field_definition = &model_meta.fields[field_name];
+                info!("表 {} 缺少列 {}，正在添加", collection_name, field_name);
+                pool.add_column(&collection_name, field_name, field_definition).await?;
+                if field_definition.required {
+                    report.warnings.push(format!(
+                        "字段 {} 为必填字段，新增列允许NULL以兼容已有数据，请补全数据后手动添加约束",
+                        field_name
+                    ));
+                }
+                report.added_columns.push(field_name.clone());
+            }
+
+            report.extra_columns = existing_columns.into_iter()
+                .filter(|c| c != "id" && !model_meta.fields.keys().any(|f| f.eq_ignore_ascii_case(c)))
+                .collect();
+        }
+
+        let existing_indexes = pool.list_index_names(&collection_name).await?;
+        for index in &model_meta.effective_indexes(pool.db_config.db_type) {
+            let default_name = format!("idx_{}", index.fields.join("_"));
+            let index_name = index.name.as_deref().unwrap_or(&default_name);
+
+            if existing_indexes.iter().any(|name| name == index_name) {
+                report.existing_indexes.push(index_name.to_string());
+                continue;
+            }
+
+            let _lock = self.acquire_index_lock(&collection_name, index_name).await;
+            match pool.create_index(&collection_name, index_name, index).await {
+                Ok(()) => report.created_indexes.push(index_name.to_string()),
+                Err(e) if is_already_exists_error(&e) => {
+                    report.existing_indexes.push(index_name.to_string());
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        debug!("模型 {} 结构同步完成: {:?}", collection_name, report);
+        Ok(report)
+    }
+}
+
+/// 判断错误是否为"对象已存在"类错误（并发创建或数据库不支持IF NOT EXISTS时出现）
+fn is_already_exists_error(error: &QuickDbError) -> bool {
+    let message = error.to_string().to_lowercase();
+    message.contains("duplicate") || message.contains("already exist")
 }
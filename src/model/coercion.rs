@@ -0,0 +1,78 @@
+//! 读取时的类型兼容转换
+//!
+//! 数据库中存储的值类型可能与模型声明的字段类型不一致，例如 SQLite 的宽松类型，
+//! 或结构变更后新旧数据混杂（字符串字段中残留整数）。宽松模式下 `from_data_map`
+//! 在反序列化前按字段类型转换这些值，而不是直接报错
+
+use crate::model::FieldType;
+use crate::types::DataValue;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 读取记录时的类型转换模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadCoercion {
+    /// 只做固定的兼容转换（整数 0/1 转布尔、浮点字段的十进制字符串转浮点数、十进制字段的数值转字符串、JSON 字符串解析），
+    /// 其余类型不一致的值在反序列化时报错（默认）
+    #[default]
+    Strict,
+    /// 额外按 [`FieldType::coerce_value`] 的规则把值转换为字段声明的类型（数组按元素转换），
+    /// 无法无损转换的值保持原样
+    Lenient,
+}
+
+/// 宽松转换开关（默认关闭）
+static LENIENT: AtomicBool = AtomicBool::new(false);
+
+/// 设置读取时的类型转换模式，对之后反序列化的所有模型生效
+pub fn set_read_coercion(mode: ReadCoercion) {
+    LENIENT.store(mode == ReadCoercion::Lenient, Ordering::SeqCst);
+}
+
+/// 当前读取时的类型转换模式
+pub fn read_coercion() -> ReadCoercion {
+    if LENIENT.load(Ordering::SeqCst) {
+        ReadCoercion::Lenient
+    } else {
+        ReadCoercion::Strict
+    }
+}
+
+/// 宽松模式下把读取到的值转换为字段类型，类型已一致或无法转换时返回 `None`
+pub(crate) fn coerce_read_value(value: &DataValue, field_type: &FieldType) -> Option<DataValue> {
+    match (field_type, value) {
+        (FieldType::Array { item_type, .. }, DataValue::Array(items)) => {
+            let mut changed = false;
+            let coerced = items
+                .iter()
+                .map(|item| match item_type.coerce_value(item) {
+                    Some(converted) => {
+                        changed = true;
+                        converted
+                    }
+                    None => item.clone(),
+                })
+                .collect();
+            changed.then_some(DataValue::Array(coerced))
+        }
+        _ => field_type.coerce_value(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_array_items_follow_item_type() {
+        let tags = FieldType::Array { item_type: Box::new(FieldType::BigInteger), max_items: None, min_items: None };
+        assert_eq!(
+            coerce_read_value(&DataValue::Array(vec![DataValue::String("1".to_string()), DataValue::Int(2)]), &tags),
+            Some(DataValue::Array(vec![DataValue::Int(1), DataValue::Int(2)]))
+        );
+        assert_eq!(coerce_read_value(&DataValue::Array(vec![DataValue::Int(2)]), &tags), None);
+        assert_eq!(
+            coerce_read_value(&DataValue::String("1".to_string()), &FieldType::Boolean),
+            Some(DataValue::Bool(true))
+        );
+    }
+}
@@ -61,6 +61,13 @@ impl ToDataValue for Vec<bool> {
     }
 }
 
+// Vec<DataValue> 实现
+impl ToDataValue for Vec<DataValue> {
+    fn to_data_value(&self) -> DataValue {
+        DataValue::Array(self.clone())
+    }
+}
+
 // HashMap<String, DataValue> 实现
 impl ToDataValue for HashMap<String, DataValue> {
     fn to_data_value(&self) -> DataValue {
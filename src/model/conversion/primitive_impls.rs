@@ -72,6 +72,13 @@ impl ToDataValue for serde_json::Value {
     }
 }
 
+// DataValue自身实现，便于在宏中直接传入已构造的值
+impl ToDataValue for DataValue {
+    fn to_data_value(&self) -> DataValue {
+        self.clone()
+    }
+}
+
 // Option类型实现
 impl<T> ToDataValue for Option<T>
 where
@@ -0,0 +1,391 @@
+//! 动态模型模块
+//!
+//! 为运行时才确定结构的模型（如由配置或租户定义的表结构）提供无需宏的注册和操作方式。
+//! 元数据通过 [`ModelMeta`] 逐个字段构建，注册后按集合名获取 [`DynamicModel`] 句柄，
+//! 所有写入和查询都会按注册的元数据验证，建表和索引创建与 `define_model!` 定义的模型一致
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::*;
+use crate::odm;
+use crate::model::field_types::ModelMeta;
+use std::collections::HashMap;
+use rat_logger::debug;
+
+/// 动态模型句柄
+///
+/// 以集合名为键操作记录，记录以 `HashMap<String, DataValue>` 表示：
+///
+/// ```ignore
+/// let meta = ModelMeta::new("tenant_orders")
+///     .field("id", string_field(None, None, None).unique())
+///     .field("sku", string_field(Some(64), Some(1), None).required())
+///     .field("quantity", integer_field(Some(1), None).required())
+///     .index(IndexDefinition::new(vec!["sku".to_string()], false));
+/// let orders = DynamicModel::register(meta)?;
+///
+/// let mut order = HashMap::new();
+/// order.insert("sku".to_string(), DataValue::String("A-1".to_string()));
+/// order.insert("quantity".to_string(), DataValue::Int(2));
+/// let id = orders.create(order).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct DynamicModel {
+    meta: ModelMeta,
+}
+
+impl DynamicModel {
+    /// 校验并注册模型元数据，返回对应的句柄
+    ///
+    /// 同名集合已注册时元数据会被替换
+    pub fn register(meta: ModelMeta) -> QuickDbResult<Self> {
+        meta.validate()?;
+        crate::manager::register_model(meta.clone())?;
+        debug!("注册动态模型: collection={}, 字段数量={}", meta.collection_name, meta.fields.len());
+        Ok(Self { meta })
+    }
+
+    /// 按集合名获取已注册模型的句柄，未注册时返回 `None`
+    pub fn get(collection_name: &str) -> Option<Self> {
+        crate::manager::get_model(collection_name).map(|meta| Self { meta })
+    }
+
+    /// 模型元数据
+    pub fn meta(&self) -> &ModelMeta {
+        &self.meta
+    }
+
+    /// 集合/表名
+    pub fn collection_name(&self) -> &str {
+        &self.meta.collection_name
+    }
+
+    /// 数据库别名
+    pub fn database_alias(&self) -> Option<&str> {
+        self.meta.database_alias.as_deref()
+    }
+
+    /// 按元数据验证完整记录
+    ///
+    /// 每个已定义字段都会参与验证，缺失按空值处理；`id`/`_id` 缺失时由ID策略生成，不参与验证。
+    /// 记录中出现未定义的字段时返回验证错误
+    pub fn validate(&self, data: &HashMap<String, DataValue>) -> QuickDbResult<()> {
+        self.check_known_fields(data.keys())?;
+        for (field_name, field_def) in &self.meta.fields {
+            let value = match data.get(field_name) {
+                Some(value) => value,
+                None if is_id_field(field_name) => continue,
+                None => &DataValue::Null,
+            };
+            field_def.validate_with_field_name(value, field_name)?;
+        }
+        Ok(())
+    }
+
+    /// 按元数据验证部分字段（用于更新）
+    pub fn validate_partial(&self, updates: &HashMap<String, DataValue>) -> QuickDbResult<()> {
+        self.check_known_fields(updates.keys())?;
+        for (field_name, value) in updates {
+            if let Some(field_def) = self.meta.fields.get(field_name) {
+                field_def.validate_with_field_name(value, field_name)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 按元数据创建表和索引
+    pub async fn create_table(&self) -> QuickDbResult<()> {
+        let alias = self.database_alias().unwrap_or("default");
+        crate::manager::ensure_table_and_indexes(self.collection_name(), alias).await
+    }
+
+    /// 验证并创建记录，返回生成的ID
+    ///
+    /// 设置了 [`ArrayElementPolicy::Coerce`](crate::model::ArrayElementPolicy) 的数组字段会先转换元素，
+    /// 设置了规范化处理的字段会先规范化
+    pub async fn create(&self, data: HashMap<String, DataValue>) -> QuickDbResult<DataValue> {
+        let data = self.prepare(data, true)?;
+        self.validate(&data)?;
+        debug!("创建动态模型记录: collection={}", self.collection_name());
+        odm::create(self.collection_name(), data, self.database_alias()).await
+    }
+
+    /// 验证并批量创建记录，按输入顺序返回生成的ID
+    ///
+    /// 任一记录验证失败时不会写入任何数据
+    pub async fn create_many(&self, data_list: Vec<HashMap<String, DataValue>>) -> QuickDbResult<Vec<DataValue>> {
+        let data_list = data_list.into_iter().map(|data| self.prepare(data, true)).collect::<QuickDbResult<Vec<_>>>()?;
+        for (index, data) in data_list.iter().enumerate() {
+            self.validate(data).map_err(|e| match e {
+                QuickDbError::ValidationError { field, message } => QuickDbError::ValidationError {
+                    field,
+                    message: format!("第{}条记录验证失败: {}", index, message),
+                },
+                other => other,
+            })?;
+        }
+        debug!("批量创建动态模型记录: collection={}, count={}", self.collection_name(), data_list.len());
+        odm::create_many(self.collection_name(), data_list, self.database_alias()).await
+    }
+
+    /// 根据ID查找记录
+    pub async fn find_by_id(&self, id: impl Into<DataValue>) -> QuickDbResult<Option<HashMap<String, DataValue>>> {
+        odm::find_by_id(self.collection_name(), id, self.database_alias()).await?
+            .map(|value| value.expect_object())
+            .transpose()
+    }
+
+    /// 查找记录
+    pub async fn find(&self, conditions: Vec<QueryCondition>, options: Option<QueryOptions>) -> QuickDbResult<Vec<HashMap<String, DataValue>>> {
+        self.check_condition_fields(&conditions)?;
+        odm::find(self.collection_name(), conditions, options, self.database_alias()).await?
+            .into_iter()
+            .map(|value| value.expect_object())
+            .collect()
+    }
+
+    /// 使用条件组查找记录
+    pub async fn find_with_groups(&self, condition_groups: Vec<QueryConditionGroup>, options: Option<QueryOptions>) -> QuickDbResult<Vec<HashMap<String, DataValue>>> {
+        odm::find_with_groups(self.collection_name(), condition_groups, options, self.database_alias()).await?
+            .into_iter()
+            .map(|value| value.expect_object())
+            .collect()
+    }
+
+    /// 统计记录数量
+    pub async fn count(&self, conditions: Vec<QueryCondition>) -> QuickDbResult<u64> {
+        self.check_condition_fields(&conditions)?;
+        odm::count(self.collection_name(), conditions, self.database_alias()).await
+    }
+
+    /// 检查是否存在满足条件的记录
+    pub async fn exists(&self, conditions: Vec<QueryCondition>) -> QuickDbResult<bool> {
+        self.check_condition_fields(&conditions)?;
+        odm::exists(self.collection_name(), conditions, self.database_alias()).await
+    }
+
+    /// 验证并根据ID更新记录
+    pub async fn update_by_id(&self, id: &str, updates: HashMap<String, DataValue>) -> QuickDbResult<bool> {
+        let updates = self.prepare(updates, false)?;
+        self.validate_partial(&updates)?;
+        odm::update_by_id(self.collection_name(), id, updates, self.database_alias()).await
+    }
+
+    /// 验证并批量更新记录，返回受影响的行数
+    pub async fn update_many(&self, conditions: Vec<QueryCondition>, updates: HashMap<String, DataValue>) -> QuickDbResult<u64> {
+        self.check_condition_fields(&conditions)?;
+        let updates = self.prepare(updates, false)?;
+        self.validate_partial(&updates)?;
+        odm::update(self.collection_name(), conditions, updates, self.database_alias()).await
+    }
+
+    /// 根据ID删除记录
+    pub async fn delete_by_id(&self, id: &str) -> QuickDbResult<bool> {
+        odm::delete_by_id(self.collection_name(), id, self.database_alias()).await
+    }
+
+    /// 批量删除记录，返回受影响的行数
+    pub async fn delete_many(&self, conditions: Vec<QueryCondition>) -> QuickDbResult<u64> {
+        self.check_condition_fields(&conditions)?;
+        odm::delete(self.collection_name(), conditions, self.database_alias()).await
+    }
+
+    /// 按字段设置转换记录中的值
+    fn coerce(&self, data: HashMap<String, DataValue>) -> HashMap<String, DataValue> {
+        data.into_iter()
+            .map(|(field_name, value)| match self.meta.fields.get(&field_name) {
+                Some(field_def) => {
+                    let value = field_def.coerce(value);
+                    (field_name, value)
+                }
+                None => (field_name, value),
+            })
+            .collect()
+    }
+
+    /// 写入前处理记录：先按字段设置转换值，再执行字段的规范化处理，最后填充自动时间戳
+    fn prepare(&self, data: HashMap<String, DataValue>, creating: bool) -> QuickDbResult<HashMap<String, DataValue>> {
+        let mut data = self.coerce(data);
+        self.meta.normalize_data(&mut data)?;
+        self.meta.stamp_timestamps(&mut data, creating);
+        Ok(data)
+    }
+
+    /// 检查字段名均在元数据中定义（`id`/`_id` 总是允许）
+    fn check_known_fields<'a>(&self, fields: impl IntoIterator<Item = &'a String>) -> QuickDbResult<()> {
+        for field in fields {
+            if !is_id_field(field) && !self.meta.fields.contains_key(field) {
+                return Err(QuickDbError::ValidationError {
+                    field: field.clone(),
+                    message: format!("模型 {} 中未定义该字段", self.collection_name()),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// 检查查询条件引用的字段，点分路径按顶层字段检查；原始SQL条件没有字段名，不做检查
+    fn check_condition_fields(&self, conditions: &[QueryCondition]) -> QuickDbResult<()> {
+        for condition in conditions.iter().filter(|c| !matches!(c.operator, QueryOperator::Raw)) {
+            let root = condition.field.split('.').next().unwrap_or_default().to_string();
+            self.check_known_fields(std::iter::once(&root))?;
+        }
+        Ok(())
+    }
+}
+
+/// 是否为ID字段
+fn is_id_field(field: &str) -> bool {
+    matches!(field, "id" | "_id")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{array_field, integer_field, string_field, ArrayElementPolicy, FieldType, IndexDefinition};
+
+    fn order_meta() -> ModelMeta {
+        ModelMeta::new("dynamic_orders")
+            .field("sku", string_field(Some(16), Some(1), None).required())
+            .field("quantity", integer_field(Some(1), None).required())
+            .field("note", string_field(None, None, None))
+            .index(IndexDefinition::new(vec!["sku".to_string()], true))
+    }
+
+    fn order(sku: &str, quantity: i64) -> HashMap<String, DataValue> {
+        let mut data = HashMap::new();
+        data.insert("sku".to_string(), DataValue::String(sku.to_string()));
+        data.insert("quantity".to_string(), DataValue::Int(quantity));
+        data
+    }
+
+    #[test]
+    fn test_meta_validation() {
+        assert!(order_meta().validate().is_ok());
+        assert!(ModelMeta::new("empty").validate().is_err());
+        assert!(ModelMeta::new(" ").field("a", string_field(None, None, None)).validate().is_err());
+
+        let bad_index = order_meta().index(IndexDefinition::new(vec!["missing".to_string()], false));
+        assert!(matches!(
+            bad_index.validate(),
+            Err(QuickDbError::ValidationError { field, .. }) if field == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_records_validated_against_meta() {
+        let model = DynamicModel { meta: order_meta() };
+        assert!(model.validate(&order("A-1", 2)).is_ok());
+
+        let mut missing = order("A-1", 2);
+        missing.remove("quantity");
+        assert!(model.validate(&missing).is_err());
+
+        let mut unknown = order("A-1", 2);
+        unknown.insert("colour".to_string(), DataValue::String("red".to_string()));
+        assert!(model.validate(&unknown).is_err());
+
+        assert!(model.validate(&order("A-1", 0)).is_err());
+
+        let mut updates = HashMap::new();
+        updates.insert("note".to_string(), DataValue::String("加急".to_string()));
+        assert!(model.validate_partial(&updates).is_ok());
+        updates.insert("quantity".to_string(), DataValue::Null);
+        assert!(model.validate_partial(&updates).is_err());
+    }
+
+    #[test]
+    fn test_normalizers_applied_before_write() {
+        use crate::model::{register_normalizer, unregister_normalizer, FieldNormalizer};
+
+        register_normalizer("dynamic_sku_dash", |value| value.replace(' ', "-"));
+        let meta = ModelMeta::new("dynamic_accounts")
+            .field("email", string_field(None, None, None)
+                .normalize(FieldNormalizer::Trim)
+                .normalize(FieldNormalizer::Lowercase))
+            .field("sku", string_field(None, None, None)
+                .normalize(FieldNormalizer::Custom("dynamic_sku_dash".to_string())));
+        let model = DynamicModel { meta };
+
+        let mut data = HashMap::new();
+        data.insert("email".to_string(), DataValue::String("  Bob@X.COM ".to_string()));
+        data.insert("sku".to_string(), DataValue::String("A 1".to_string()));
+        let prepared = model.prepare(data.clone(), true).unwrap();
+        assert_eq!(prepared.get("email"), Some(&DataValue::String("bob@x.com".to_string())));
+        assert_eq!(prepared.get("sku"), Some(&DataValue::String("A-1".to_string())));
+
+        // 未注册的自定义规范化器报告字段名，而不是静默写入原值
+        assert!(unregister_normalizer("dynamic_sku_dash"));
+        assert!(matches!(
+            model.prepare(data, true),
+            Err(QuickDbError::ValidationError { field, .. }) if field == "sku"
+        ));
+    }
+
+    #[test]
+    fn test_timestamps_filled_on_create_and_bumped_on_update() {
+        let meta = ModelMeta::new("dynamic_posts")
+            .field("title", string_field(None, None, None).required())
+            .field("created_at", crate::model::datetime_field())
+            .field("updated_at", crate::model::datetime_field())
+            .with_timestamps("created_at", "updated_at");
+        assert!(meta.validate().is_ok());
+        let model = DynamicModel { meta: meta.clone() };
+
+        let mut data = HashMap::new();
+        data.insert("title".to_string(), DataValue::String("draft".to_string()));
+        let created = model.prepare(data, true).unwrap();
+        let created_at = created.get("created_at").cloned().unwrap();
+        assert!(matches!(created_at, DataValue::DateTime(_)));
+        assert_eq!(created.get("updated_at"), Some(&created_at));
+
+        let mut updates = HashMap::new();
+        updates.insert("title".to_string(), DataValue::String("published".to_string()));
+        let updated = model.prepare(updates, false).unwrap();
+        assert!(!updated.contains_key("created_at"));
+        assert!(matches!(updated.get("updated_at"), Some(DataValue::DateTime(_))));
+
+        let invalid = meta.field("updated_at", string_field(None, None, None));
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_array_elements_coerced_or_rejected_with_index() {
+        let scores = || array_field(FieldType::Integer { min_value: None, max_value: None }, None, None);
+        let mixed = DataValue::Array(vec![
+            DataValue::Int(1),
+            DataValue::String("26".to_string()),
+            DataValue::Float(3.0),
+        ]);
+
+        let strict = DynamicModel { meta: ModelMeta::new("scores").field("scores", scores()) };
+        let mut data = HashMap::new();
+        data.insert("scores".to_string(), mixed.clone());
+        match strict.validate(&strict.coerce(data.clone())) {
+            Err(QuickDbError::ValidationError { field, message }) => {
+                assert_eq!(field, "scores");
+                assert!(message.contains("下标 1"), "{}", message);
+            }
+            other => panic!("严格模式应拒绝混合类型数组: {:?}", other),
+        }
+
+        let coercing = DynamicModel {
+            meta: ModelMeta::new("scores").field("scores", scores().array_elements(ArrayElementPolicy::Coerce)),
+        };
+        let coerced = coercing.coerce(data);
+        assert!(coercing.validate(&coerced).is_ok());
+        assert_eq!(
+            coerced.get("scores"),
+            Some(&DataValue::Array(vec![DataValue::Int(1), DataValue::Int(26), DataValue::Int(3)]))
+        );
+
+        let mut bad = HashMap::new();
+        bad.insert(
+            "scores".to_string(),
+            DataValue::Array(vec![DataValue::Int(1), DataValue::Bool(true)]),
+        );
+        assert!(matches!(
+            coercing.validate(&coercing.coerce(bad)),
+            Err(QuickDbError::ValidationError { message, .. }) if message.contains("下标 1")
+        ));
+    }
+}
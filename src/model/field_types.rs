@@ -3,11 +3,31 @@
 //! 定义模型字段的类型、验证和元数据
 
 use crate::error::{QuickDbError, QuickDbResult};
-use crate::types::DataValue;
+use crate::types::{DataValue, DatabaseType, QueryCondition, QueryOperator, SortDirection};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use dashmap::DashMap;
 use rat_logger::debug;
 
+/// 已编译正则表达式缓存，避免每次验证都重新编译
+static REGEX_CACHE: OnceLock<DashMap<String, regex::Regex>> = OnceLock::new();
+
+/// 获取编译后的正则表达式（按模式字符串缓存）
+fn compiled_regex(pattern: &str) -> QuickDbResult<regex::Regex> {
+    let cache = REGEX_CACHE.get_or_init(DashMap::new);
+    if let Some(regex) = cache.get(pattern) {
+        return Ok(regex.clone());
+    }
+    let regex = regex::Regex::new(pattern)
+        .map_err(|e| QuickDbError::ValidationError {
+            field: "regex".to_string(),
+            message: format!("正则表达式无效: {}", e)
+        })?;
+    cache.insert(pattern.to_string(), regex.clone());
+    Ok(regex)
+}
+
 /// 字段类型枚举
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FieldType {
@@ -68,6 +88,137 @@ pub enum FieldType {
     },
 }
 
+impl FieldType {
+    /// 是否为以JSON形式存储的字段类型（JSON、数组、对象）
+    ///
+    /// 读取时只有这些类型的字段才会把文本内容解析为JSON
+    pub fn is_json_like(&self) -> bool {
+        matches!(self, FieldType::Json | FieldType::Array { .. } | FieldType::Object { .. })
+    }
+
+    /// 尝试把值转换为该类型，无需或无法转换时返回 `None`
+    ///
+    /// 只做无损转换：数字字符串转整数/浮点数、整数转浮点数、无小数部分的浮点数转整数、
+    /// 数字和布尔值转字符串（十进制字段的数字也转为字符串）、`"true"`/`"false"`/`"1"`/`"0"` 转布尔值、
+    /// RFC 3339 字符串转日期时间
+    pub fn coerce_value(&self, value: &DataValue) -> Option<DataValue> {
+        match (self, value) {
+            (FieldType::Integer { .. } | FieldType::BigInteger, DataValue::String(s)) => {
+                s.trim().parse::<i64>().ok().map(DataValue::Int)
+            }
+            (FieldType::Integer { .. } | FieldType::BigInteger, DataValue::Float(f))
+                if f.fract() == 0.0 && *f >= i64::MIN as f64 && *f <= i64::MAX as f64 => {
+                Some(DataValue::Int(*f as i64))
+            }
+            (FieldType::Float { .. } | FieldType::Double, DataValue::Int(i)) => {
+                Some(DataValue::Float(*i as f64))
+            }
+            (FieldType::Float { .. } | FieldType::Double, DataValue::String(s)) => {
+                s.trim().parse::<f64>().ok().filter(|f| f.is_finite()).map(DataValue::Float)
+            }
+            (FieldType::String { .. } | FieldType::Text | FieldType::Decimal { .. }, DataValue::Int(i)) => {
+                Some(DataValue::String(i.to_string()))
+            }
+            (FieldType::String { .. } | FieldType::Text | FieldType::Decimal { .. }, DataValue::Float(f)) => {
+                Some(DataValue::String(f.to_string()))
+            }
+            (FieldType::String { .. } | FieldType::Text, DataValue::Bool(b)) => Some(DataValue::String(b.to_string())),
+            (FieldType::Boolean, DataValue::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" => Some(DataValue::Bool(true)),
+                "false" | "0" => Some(DataValue::Bool(false)),
+                _ => None,
+            },
+            (FieldType::DateTime | FieldType::Date | FieldType::Time, DataValue::String(s)) => {
+                chrono::DateTime::parse_from_rfc3339(s.trim())
+                    .ok()
+                    .map(|dt| DataValue::DateTime(dt.with_timezone(&chrono::Utc)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 数组元素与元素类型不一致时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArrayElementPolicy {
+    /// 严格校验，元素类型不符时拒绝（默认）
+    #[default]
+    Strict,
+    /// 先把可兼容的元素转换为元素类型（如字符串 `"26"` 转为整数），仍不符时拒绝
+    ///
+    /// 转换规则见 [`FieldType::coerce_value`]
+    Coerce,
+}
+
+/// 自定义规范化函数注册表
+static CUSTOM_NORMALIZERS: OnceLock<DashMap<String, Arc<dyn Fn(&str) -> String + Send + Sync>>> = OnceLock::new();
+
+/// 注册自定义规范化函数，供 [`FieldNormalizer::Custom`] 按名称引用；同名注册会覆盖
+pub fn register_normalizer<F>(name: &str, normalizer: F)
+where
+    F: Fn(&str) -> String + Send + Sync + 'static,
+{
+    CUSTOM_NORMALIZERS.get_or_init(DashMap::new).insert(name.to_string(), Arc::new(normalizer));
+}
+
+/// 移除已注册的自定义规范化函数，返回是否存在
+pub fn unregister_normalizer(name: &str) -> bool {
+    CUSTOM_NORMALIZERS.get_or_init(DashMap::new).remove(name).is_some()
+}
+
+/// 字符串字段写入前的规范化处理
+///
+/// 在保存和更新时、验证之前按声明顺序执行，存储值、唯一约束和按该值查询因此与输入的大小写和空白无关
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldNormalizer {
+    /// 去除首尾空白
+    Trim,
+    /// 转为小写
+    Lowercase,
+    /// 转为大写
+    Uppercase,
+    /// 通过 [`register_normalizer`] 注册的自定义规范化函数
+    Custom(String),
+}
+
+impl FieldNormalizer {
+    /// 规范化字符串，自定义规范化函数未注册时返回错误
+    pub fn apply(&self, value: &str) -> QuickDbResult<String> {
+        match self {
+            FieldNormalizer::Trim => Ok(value.trim().to_string()),
+            FieldNormalizer::Lowercase => Ok(value.to_lowercase()),
+            FieldNormalizer::Uppercase => Ok(value.to_uppercase()),
+            FieldNormalizer::Custom(name) => {
+                let normalizer = CUSTOM_NORMALIZERS.get_or_init(DashMap::new)
+                    .get(name)
+                    .map(|entry| entry.value().clone())
+                    .ok_or_else(|| QuickDbError::ValidationError {
+                        field: name.clone(),
+                        message: format!("未注册的规范化函数: {}", name),
+                    })?;
+                Ok(normalizer(value))
+            }
+        }
+    }
+}
+
+/// 由数据库在插入时计算的默认值表达式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum DefaultExpr {
+    /// 插入时的当前时间（SQL 的 `CURRENT_TIMESTAMP`），仅适用于日期时间字段
+    CurrentTimestamp,
+}
+
+impl DefaultExpr {
+    /// 在客户端求值，用于没有列默认值的数据库（MongoDB）
+    pub fn evaluate(&self) -> DataValue {
+        match self {
+            DefaultExpr::CurrentTimestamp => DataValue::DateTime(chrono::Utc::now()),
+        }
+    }
+}
+
 /// 字段定义
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FieldDefinition {
@@ -77,6 +228,9 @@ pub struct FieldDefinition {
     pub required: bool,
     /// 默认值
     pub default: Option<DataValue>,
+    /// 默认值表达式，与 `default` 互斥
+    #[serde(default)]
+    pub default_expr: Option<DefaultExpr>,
     /// 是否唯一
     pub unique: bool,
     /// 是否建立索引
@@ -87,6 +241,18 @@ pub struct FieldDefinition {
     pub validator: Option<String>,
     /// SQLite 布尔值兼容性
     pub sqlite_compatibility: bool,
+    /// 排序规则（仅对字符串字段生效，建表时生成 COLLATE 子句）
+    #[serde(default)]
+    pub collation: Option<String>,
+    /// 数组元素类型不一致时的处理方式（仅对数组字段生效）
+    #[serde(default)]
+    pub array_elements: ArrayElementPolicy,
+    /// 唯一约束是否不区分大小写，建表时为该字段创建不区分大小写的唯一索引
+    #[serde(default)]
+    pub unique_case_insensitive: bool,
+    /// 写入前按顺序执行的规范化处理（仅对字符串值生效）
+    #[serde(default)]
+    pub normalizers: Vec<FieldNormalizer>,
 }
 
 impl FieldDefinition {
@@ -96,11 +262,16 @@ impl FieldDefinition {
             field_type,
             required: false,
             default: None,
+            default_expr: None,
             unique: false,
             indexed: false,
             description: None,
             validator: None,
             sqlite_compatibility: false,
+            collation: None,
+            array_elements: ArrayElementPolicy::Strict,
+            unique_case_insensitive: false,
+            normalizers: Vec::new(),
         }
     }
 
@@ -111,17 +282,47 @@ impl FieldDefinition {
     }
 
     /// 设置默认值
+    ///
+    /// SQL数据库建表时生成列的 `DEFAULT` 子句，省略该列的插入（包括绕过ORM的原生SQL）
+    /// 都会得到默认值；MongoDB 没有列默认值，由ORM在插入时填充
     pub fn default_value(mut self, value: DataValue) -> Self {
         self.default = Some(value);
+        self.default_expr = None;
+        self
+    }
+
+    /// 设置由数据库计算的默认值表达式，如 [`DefaultExpr::CurrentTimestamp`]
+    pub fn default_expr(mut self, expr: DefaultExpr) -> Self {
+        self.default_expr = Some(expr);
+        self.default = None;
         self
     }
 
+    /// 省略该字段时应使用的默认值，表达式在客户端求值
+    pub fn default_for_insert(&self) -> Option<DataValue> {
+        match (&self.default_expr, &self.default) {
+            (Some(expr), _) => Some(expr.evaluate()),
+            (None, Some(DataValue::Null)) | (None, None) => None,
+            (None, Some(value)) => Some(value.clone()),
+        }
+    }
+
     /// 设置为唯一字段
     pub fn unique(mut self) -> Self {
         self.unique = true;
         self
     }
 
+    /// 设置为不区分大小写的唯一字段，`A@x.com` 与 `a@x.com` 视为重复
+    ///
+    /// 建表后自动为该字段创建唯一索引（见 [`IndexDefinition::case_insensitive`]）；
+    /// MySQL 依赖列的 `_ci` 排序规则，未设置排序规则时建表使用 `utf8mb4_unicode_ci`
+    pub fn unique_case_insensitive(mut self) -> Self {
+        self.unique = true;
+        self.unique_case_insensitive = true;
+        self
+    }
+
     /// 设置为索引字段
     pub fn indexed(mut self) -> Self {
         self.indexed = true;
@@ -140,6 +341,83 @@ impl FieldDefinition {
         self
     }
 
+    /// 设置排序规则
+    ///
+    /// 例如 PostgreSQL 的 `und-x-icu`、MySQL 的 `utf8mb4_unicode_ci`、SQLite 的 `NOCASE`；
+    /// MongoDB 没有列级排序规则，该设置会被忽略
+    pub fn collation(mut self, collation: &str) -> Self {
+        self.collation = Some(collation.to_string());
+        self
+    }
+
+    /// 追加写入前的规范化处理，如邮箱字段先 `Trim` 再 `Lowercase`
+    pub fn normalize(mut self, normalizer: FieldNormalizer) -> Self {
+        self.normalizers.push(normalizer);
+        self
+    }
+
+    /// 按声明顺序对值执行规范化处理，字符串数组逐个元素处理，其他值原样返回
+    pub fn normalize_value(&self, value: DataValue) -> QuickDbResult<DataValue> {
+        if self.normalizers.is_empty() {
+            return Ok(value);
+        }
+        match value {
+            DataValue::String(mut s) => {
+                for normalizer in &self.normalizers {
+                    s = normalizer.apply(&s)?;
+                }
+                Ok(DataValue::String(s))
+            }
+            DataValue::Array(items) => items.into_iter()
+                .map(|item| self.normalize_value(item))
+                .collect::<QuickDbResult<Vec<_>>>()
+                .map(DataValue::Array),
+            other => Ok(other),
+        }
+    }
+
+    /// 设置数组元素类型不一致时的处理方式
+    ///
+    /// 用于把半结构化的JSON写入有类型的数组字段，`Coerce` 时写入前由 [`FieldDefinition::coerce`] 转换元素
+    pub fn array_elements(mut self, policy: ArrayElementPolicy) -> Self {
+        self.array_elements = policy;
+        self
+    }
+
+    /// 按字段设置转换值，仅 `ArrayElementPolicy::Coerce` 的数组字段会转换可兼容的元素，其他值原样返回
+    pub fn coerce(&self, value: DataValue) -> DataValue {
+        match (&self.field_type, self.array_elements, value) {
+            (FieldType::Array { item_type, .. }, ArrayElementPolicy::Coerce, DataValue::Array(items)) => {
+                DataValue::Array(
+                    items
+                        .into_iter()
+                        .map(|item| item_type.coerce_value(&item).unwrap_or(item))
+                        .collect(),
+                )
+            }
+            (_, _, value) => value,
+        }
+    }
+
+    /// 逐个验证数组元素，错误信息中包含出错元素的下标
+    fn validate_array_items(&self, item_type: &FieldType, items: impl Iterator<Item = DataValue>, field_name: &str) -> QuickDbResult<()> {
+        let item_field = FieldDefinition::new(item_type.clone());
+        for (index, item) in items.enumerate() {
+            let item = match self.array_elements {
+                ArrayElementPolicy::Coerce => item_type.coerce_value(&item).unwrap_or(item),
+                ArrayElementPolicy::Strict => item,
+            };
+            item_field.validate(&item).map_err(|e| match e {
+                QuickDbError::ValidationError { message, .. } => QuickDbError::ValidationError {
+                    field: field_name.to_string(),
+                    message: format!("数组下标 {} 的元素无效（实际类型: {}）: {}", index, item.type_name(), message),
+                },
+                other => other,
+            })?;
+        }
+        Ok(())
+    }
+
     /// 设置 SQLite 兼容性
     pub fn with_sqlite_compatibility(mut self, compatible: bool) -> Self {
         self.sqlite_compatibility = compatible;
@@ -147,9 +425,8 @@ impl FieldDefinition {
     }
 
     /// 设置默认值（别名方法，提供更直观的API）
-    pub fn with_default(mut self, value: DataValue) -> Self {
-        self.default = Some(value);
-        self
+    pub fn with_default(self, value: DataValue) -> Self {
+        self.default_value(value)
     }
 
     /// 验证字段值
@@ -189,11 +466,7 @@ impl FieldDefinition {
                         }
                     }
                     if let Some(pattern) = regex {
-                        let regex = regex::Regex::new(pattern)
-                            .map_err(|e| QuickDbError::ValidationError {
-                                field: "regex".to_string(),
-                                message: format!("正则表达式无效: {}", e)
-                            })?;
+                        let regex = compiled_regex(pattern)?;
                         if !regex.is_match(s) {
                             return Err(QuickDbError::ValidationError {
                                 field: "regex_match".to_string(),
@@ -329,10 +602,7 @@ impl FieldDefinition {
                             }
                         }
                         // 验证数组中的每个元素
-                        let item_field = FieldDefinition::new((**item_type).clone());
-                        for item in arr {
-                            item_field.validate(item)?;
-                        }
+                        self.validate_array_items(item_type, arr.iter().cloned(), field_name)?;
                     },
                     DataValue::String(json_str) => {
                         // 处理JSON字符串格式的数组
@@ -355,11 +625,8 @@ impl FieldDefinition {
                                     }
                                 }
                                 // 验证数组中的每个元素
-                                let item_field = FieldDefinition::new((**item_type).clone());
-                                for item_json in arr {
-                                    let item_data_value = DataValue::from_json(item_json.clone());
-                                    item_field.validate(&item_data_value)?;
-                                }
+                                let items = arr.iter().map(|item_json| DataValue::from_json(item_json.clone()));
+                                self.validate_array_items(item_type, items, field_name)?;
                             } else {
                                 return Err(QuickDbError::ValidationError {
                                     field: "type_mismatch".to_string(),
@@ -453,7 +720,13 @@ impl FieldDefinition {
                 }
             }
             FieldType::Decimal { precision: _, scale: _ } => {
-                if !matches!(value, DataValue::Float(_)) {
+                // 精确值以十进制字符串传递，也接受整数和浮点数
+                let is_decimal = match value {
+                    DataValue::String(s) => s.trim().parse::<f64>().is_ok_and(|f| f.is_finite()),
+                    DataValue::Int(_) | DataValue::Float(_) => true,
+                    _ => false,
+                };
+                if !is_decimal {
                     return Err(QuickDbError::ValidationError {
                         field: "type_mismatch".to_string(),
                         message: "字段类型不匹配，期望十进制数类型".to_string()
@@ -479,10 +752,243 @@ pub struct ModelMeta {
     pub indexes: Vec<IndexDefinition>,
     /// 模型描述
     pub description: Option<String>,
+    /// 软删除标记列，记录被软删除时由应用写入删除时间，未删除时为空
+    ///
+    /// 设置后唯一索引自动转为只约束未删除记录的部分索引，被软删除记录的唯一值可以重新使用
+    #[serde(default)]
+    pub soft_delete_field: Option<String>,
+    /// 创建时间列，新建记录时未设置则自动写入当前时间
+    #[serde(default)]
+    pub created_at_field: Option<String>,
+    /// 更新时间列，新建记录时未设置则自动写入当前时间，更新记录时总是写入当前时间
+    #[serde(default)]
+    pub updated_at_field: Option<String>,
+}
+
+impl ModelMeta {
+    /// 创建空的模型元数据，用于在运行时逐个字段构建模型
+    ///
+    /// 构建完成后通过 [`DynamicModel::register`](crate::model::DynamicModel::register) 校验并注册
+    pub fn new(collection_name: impl Into<String>) -> Self {
+        Self {
+            collection_name: collection_name.into(),
+            database_alias: None,
+            fields: HashMap::new(),
+            indexes: Vec::new(),
+            description: None,
+            soft_delete_field: None,
+            created_at_field: None,
+            updated_at_field: None,
+        }
+    }
+
+    /// 设置数据库别名
+    pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+        self.database_alias = Some(alias.into());
+        self
+    }
+
+    /// 添加字段定义，同名字段会被覆盖
+    pub fn field(mut self, name: impl Into<String>, definition: FieldDefinition) -> Self {
+        self.fields.insert(name.into(), definition);
+        self
+    }
+
+    /// 添加索引定义
+    pub fn index(mut self, index: IndexDefinition) -> Self {
+        self.indexes.push(index);
+        self
+    }
+
+    /// 设置模型描述
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// 设置软删除标记列
+    pub fn with_soft_delete(mut self, field: impl Into<String>) -> Self {
+        self.soft_delete_field = Some(field.into());
+        self
+    }
+
+    /// 设置自动维护的创建时间列和更新时间列
+    pub fn with_timestamps(mut self, created: impl Into<String>, updated: impl Into<String>) -> Self {
+        self.created_at_field = Some(created.into());
+        self.updated_at_field = Some(updated.into());
+        self
+    }
+
+    /// 写入前填充自动时间戳
+    ///
+    /// 新建记录（`creating` 为 `true`）时为未设置或为空的创建/更新时间列写入同一个当前时间；
+    /// 更新时总是把更新时间列设置为当前时间，创建时间列不变
+    pub fn stamp_timestamps(&self, data: &mut HashMap<String, DataValue>, creating: bool) {
+        let now = DataValue::DateTime(chrono::Utc::now());
+        if creating {
+            for field in [&self.created_at_field, &self.updated_at_field].into_iter().flatten() {
+                let value = data.entry(field.clone()).or_insert(DataValue::Null);
+                if value.is_null() {
+                    *value = now.clone();
+                }
+            }
+        } else if let Some(field) = &self.updated_at_field {
+            data.insert(field.clone(), now);
+        }
+    }
+
+    /// 按字段名排序的字段描述列表，用于运行时反射（生成表单、API结构和文档）
+    ///
+    /// 唯一和索引标记综合了字段定义与声明的索引：字段出现在任意索引中即视为已建索引，
+    /// 单字段唯一索引也视为唯一
+    pub fn field_infos(&self) -> Vec<FieldInfo> {
+        let mut names: Vec<&String> = self.fields.keys().collect();
+        names.sort();
+        names.into_iter().filter_map(|name| self.field_info(name)).collect()
+    }
+
+    /// 获取单个字段的描述，字段不存在时返回 `None`
+    pub fn field_info(&self, name: &str) -> Option<FieldInfo> {
+        let definition = self.fields.get(name)?;
+        let unique = definition.unique
+            || definition.unique_case_insensitive
+            || self.indexes.iter().any(|index| index.unique && index.fields.len() == 1 && index.fields[0] == name);
+        let indexed = unique
+            || definition.indexed
+            || self.indexes.iter().any(|index| index.fields.iter().any(|field| field == name));
+        Some(FieldInfo {
+            name: name.to_string(),
+            field_type: definition.field_type.clone(),
+            required: definition.required,
+            unique,
+            indexed,
+            description: definition.description.clone(),
+        })
+    }
+
+    /// 建表时需要创建的全部索引：声明的索引，加上不区分大小写的唯一字段隐含的唯一索引
+    ///
+    /// 隐含索引命名为 `idx_{集合名}_{字段名}_ci`，字段已有声明的同类单字段索引时不再重复生成。
+    /// 设置了软删除标记列时，未声明部分索引条件的唯一索引追加 `标记列 IS NULL` 条件；
+    /// MySQL不支持部分索引，保持普通唯一索引
+    pub fn effective_indexes(&self, db_type: DatabaseType) -> Vec<IndexDefinition> {
+        let mut indexes = self.indexes.clone();
+        let mut implicit: Vec<&String> = self.fields.iter()
+            .filter(|(name, definition)| {
+                definition.unique_case_insensitive
+                    && !self.indexes.iter().any(|index| {
+                        index.unique && index.case_insensitive && index.fields.len() == 1 && &index.fields[0] == *name
+                    })
+            })
+            .map(|(name, _)| name)
+            .collect();
+        implicit.sort();
+        indexes.extend(implicit.into_iter().map(|name| {
+            IndexDefinition::new(vec![name.clone()], true)
+                .case_insensitive(true)
+                .with_name(format!("idx_{}_{}_ci", self.collection_name, name))
+        }));
+        if let Some(soft_delete_field) = &self.soft_delete_field {
+            if db_type == DatabaseType::MySQL {
+                debug!("MySQL不支持部分索引，模型 {} 的唯一索引不排除软删除记录", self.collection_name);
+            } else {
+                for index in indexes.iter_mut().filter(|index| index.unique && index.partial_filter.is_empty()) {
+                    index.partial_filter = vec![
+                        QueryCondition::new(soft_delete_field, QueryOperator::IsNull, DataValue::Null),
+                    ];
+                }
+            }
+        }
+        indexes
+    }
+
+    /// 按字段定义的规范化处理就地转换写入数据，未定义的字段原样保留
+    pub fn normalize_data(&self, data: &mut HashMap<String, DataValue>) -> QuickDbResult<()> {
+        for (field_name, value) in data.iter_mut() {
+            if let Some(definition) = self.fields.get(field_name).filter(|d| !d.normalizers.is_empty()) {
+                let normalized = definition.normalize_value(std::mem::replace(value, DataValue::Null))
+                    .map_err(|e| match e {
+                        QuickDbError::ValidationError { message, .. } => QuickDbError::ValidationError {
+                            field: field_name.clone(),
+                            message,
+                        },
+                        other => other,
+                    })?;
+                *value = normalized;
+            }
+        }
+        Ok(())
+    }
+
+    /// 校验元数据（集合名和字段非空，索引定义有效且只引用已定义的字段）
+    pub fn validate(&self) -> QuickDbResult<()> {
+        if self.collection_name.trim().is_empty() {
+            return Err(QuickDbError::ValidationError {
+                field: "collection_name".to_string(),
+                message: "集合名不能为空".to_string(),
+            });
+        }
+        if self.fields.is_empty() {
+            return Err(QuickDbError::ValidationError {
+                field: "fields".to_string(),
+                message: format!("模型 {} 至少需要一个字段", self.collection_name),
+            });
+        }
+        if let Some(soft_delete_field) = &self.soft_delete_field {
+            if !self.fields.contains_key(soft_delete_field) {
+                return Err(QuickDbError::ValidationError {
+                    field: soft_delete_field.clone(),
+                    message: format!("软删除标记列未在模型 {} 中定义", self.collection_name),
+                });
+            }
+        }
+        for timestamp_field in [&self.created_at_field, &self.updated_at_field].into_iter().flatten() {
+            match self.fields.get(timestamp_field).map(|definition| &definition.field_type) {
+                Some(FieldType::DateTime) => {}
+                Some(_) => return Err(QuickDbError::ValidationError {
+                    field: timestamp_field.clone(),
+                    message: format!("模型 {} 的时间戳列必须是日期时间字段", self.collection_name),
+                }),
+                None => return Err(QuickDbError::ValidationError {
+                    field: timestamp_field.clone(),
+                    message: format!("时间戳列未在模型 {} 中定义", self.collection_name),
+                }),
+            }
+        }
+        for index in &self.indexes {
+            index.validate()?;
+            if let Some(missing) = index.fields.iter()
+                .find(|f| !self.fields.contains_key(*f) && !matches!(f.as_str(), "id" | "_id"))
+            {
+                return Err(QuickDbError::ValidationError {
+                    field: missing.clone(),
+                    message: format!("索引引用了模型 {} 中未定义的字段", self.collection_name),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 字段描述，由 [`ModelMeta::field_infos`] 生成
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldInfo {
+    /// 字段名
+    pub name: String,
+    /// 字段类型
+    pub field_type: FieldType,
+    /// 是否必填
+    pub required: bool,
+    /// 是否唯一
+    pub unique: bool,
+    /// 是否建立了索引
+    pub indexed: bool,
+    /// 字段描述
+    pub description: Option<String>,
 }
 
 /// 索引定义
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct IndexDefinition {
     /// 索引字段
     pub fields: Vec<String>,
@@ -490,4 +996,89 @@ pub struct IndexDefinition {
     pub unique: bool,
     /// 索引名称
     pub name: Option<String>,
-}
\ No newline at end of file
+    /// 稀疏索引，仅索引包含该字段的文档（仅MongoDB生效，SQL数据库忽略）
+    #[serde(default)]
+    pub sparse: bool,
+    /// 部分索引条件（AND关系），只有满足条件的记录进入索引
+    ///
+    /// MongoDB映射为`partialFilterExpression`，PostgreSQL/SQLite映射为`CREATE INDEX ... WHERE`，
+    /// MySQL不支持部分索引
+    #[serde(default)]
+    pub partial_filter: Vec<QueryCondition>,
+    /// 各字段的排序方向，与`fields`按位置对应，缺省为升序
+    #[serde(default)]
+    pub directions: Vec<SortDirection>,
+    /// 按不区分大小写的方式索引字符串字段
+    ///
+    /// PostgreSQL映射为`lower(字段)`函数索引，SQLite映射为`COLLATE NOCASE`，
+    /// MongoDB使用强度为2的排序规则；MySQL的索引跟随列的排序规则，需要列使用`_ci`排序规则
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+impl IndexDefinition {
+    /// 创建索引定义
+    pub fn new(fields: Vec<String>, unique: bool) -> Self {
+        Self {
+            fields,
+            unique,
+            ..Default::default()
+        }
+    }
+
+    /// 设置索引名称
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// 设置稀疏索引
+    pub fn sparse(mut self, sparse: bool) -> Self {
+        self.sparse = sparse;
+        self
+    }
+
+    /// 设置部分索引条件
+    pub fn partial_filter(mut self, conditions: Vec<QueryCondition>) -> Self {
+        self.partial_filter = conditions;
+        self
+    }
+
+    /// 设置各字段的排序方向
+    pub fn directions(mut self, directions: Vec<SortDirection>) -> Self {
+        self.directions = directions;
+        self
+    }
+
+    /// 设置是否不区分大小写
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// 获取指定位置字段的排序方向
+    pub fn direction_at(&self, index: usize) -> SortDirection {
+        self.directions.get(index).cloned().unwrap_or(SortDirection::Asc)
+    }
+
+    /// 校验索引定义（字段非空，排序方向数量不超过字段数量）
+    pub fn validate(&self) -> QuickDbResult<()> {
+        if self.fields.is_empty() {
+            return Err(QuickDbError::ValidationError {
+                field: "fields".to_string(),
+                message: "索引字段不能为空".to_string(),
+            });
+        }
+        if self.directions.len() > self.fields.len() {
+            return Err(QuickDbError::ValidationError {
+                field: "directions".to_string(),
+                message: format!(
+                    "索引排序方向数量({})超过字段数量({})",
+                    self.directions.len(),
+                    self.fields.len()
+                ),
+            });
+        }
+        Ok(())
+    }
+}
@@ -89,6 +89,123 @@ macro_rules! field_types {
     };
 }
 
+/// 便捷宏：构建插入/更新用的数据映射
+///
+/// 值通过 `ToDataValue` 转换，`Option` 的 `None` 与字面量 `null` 均转为 `DataValue::Null`，
+/// `{ ... }` 生成嵌套对象，`[ ... ]` 生成数组，两者可以任意嵌套
+///
+/// ```ignore
+/// let data = data_map! {
+///     "name" => "张三",
+///     "age" => 26,
+///     "email" => null,
+///     "tags" => ["rust", "db"],
+///     "address" => { "city" => "北京", "zip" => Some(100000) },
+/// };
+/// ```
+#[macro_export]
+macro_rules! data_map {
+    (@entries $map:ident; ) => {};
+    (@entries $map:ident; $key:expr => null $(, $($rest:tt)*)?) => {
+        $map.insert(($key).to_string(), $crate::types::DataValue::Null);
+        $crate::data_map!(@entries $map; $($($rest)*)?);
+    };
+    (@entries $map:ident; $key:expr => { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        $map.insert(($key).to_string(), $crate::types::DataValue::Object($crate::data_map!{ $($inner)* }));
+        $crate::data_map!(@entries $map; $($($rest)*)?);
+    };
+    (@entries $map:ident; $key:expr => [ $($inner:tt)* ] $(, $($rest:tt)*)?) => {
+        $map.insert(($key).to_string(), $crate::data_map!(@array [] $($inner)*));
+        $crate::data_map!(@entries $map; $($($rest)*)?);
+    };
+    (@entries $map:ident; $key:expr => $value:expr $(, $($rest:tt)*)?) => {
+        $map.insert(($key).to_string(), $crate::model::conversion::ToDataValue::to_data_value(&$value));
+        $crate::data_map!(@entries $map; $($($rest)*)?);
+    };
+
+    (@array [$($done:expr,)*]) => {
+        $crate::types::DataValue::Array(vec![$($done),*])
+    };
+    (@array [$($done:expr,)*] null $(, $($rest:tt)*)?) => {
+        $crate::data_map!(@array [$($done,)* $crate::types::DataValue::Null,] $($($rest)*)?)
+    };
+    (@array [$($done:expr,)*] { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::data_map!(@array [$($done,)* $crate::types::DataValue::Object($crate::data_map!{ $($inner)* }),] $($($rest)*)?)
+    };
+    (@array [$($done:expr,)*] [ $($inner:tt)* ] $(, $($rest:tt)*)?) => {
+        $crate::data_map!(@array [$($done,)* $crate::data_map!(@array [] $($inner)*),] $($($rest)*)?)
+    };
+    (@array [$($done:expr,)*] $value:expr $(, $($rest:tt)*)?) => {
+        $crate::data_map!(@array [$($done,)* $crate::model::conversion::ToDataValue::to_data_value(&$value),] $($($rest)*)?)
+    };
+
+    () => {
+        ::std::collections::HashMap::<String, $crate::types::DataValue>::new()
+    };
+    ($($body:tt)+) => {{
+        let mut map = ::std::collections::HashMap::<String, $crate::types::DataValue>::new();
+        $crate::data_map!(@entries map; $($body)+);
+        map
+    }};
+}
+
+/// 便捷宏：定义部分更新（PATCH）结构
+///
+/// 生成的结构带有 `to_update_map()`，只包含需要修改的字段，可直接传给 `update`：
+/// `Option<T>` 为 `Some` 时写入；可空字段用 `Option<Option<T>>`，`Some(None)` 写入NULL；
+/// 也可以直接使用 `Patch<T>`。结构上的属性（如 `#[derive(Deserialize)]`）原样保留
+///
+/// ```ignore
+/// define_patch! {
+///     #[derive(Debug, Default, Deserialize)]
+///     pub struct UserPatch {
+///         pub username: Option<String>,
+///         #[serde(default, with = "rat_quickdb::types::serde_helpers::double_option")]
+///         pub email: Option<Option<String>>,
+///     }
+/// }
+///
+/// user.update(patch.to_update_map()).await?;
+/// ```
+#[macro_export]
+macro_rules! define_patch {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident: $field_type:ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                $field_vis $field: $field_type,
+            )*
+        }
+
+        impl $name {
+            /// 生成只包含需要修改字段的更新数据
+            pub fn to_update_map(&self) -> std::collections::HashMap<String, $crate::types::DataValue> {
+                let mut updates = std::collections::HashMap::new();
+                $(
+                    if let Some(value) = $crate::types::PatchField::patch_value(&self.$field) {
+                        updates.insert(stringify!($field).to_string(), value);
+                    }
+                )*
+                updates
+            }
+
+            /// 是否没有任何需要修改的字段
+            pub fn is_empty(&self) -> bool {
+                $( $crate::types::PatchField::patch_value(&self.$field).is_none() && )* true
+            }
+        }
+    };
+}
+
 /// 便捷宏：定义模型
 #[macro_export]
 macro_rules! define_model {
@@ -105,6 +222,12 @@ macro_rules! define_model {
         $(
             database = $database:expr,
         )?
+        $(
+            soft_delete = $soft_delete:expr,
+        )?
+        $(
+            timestamps = { created: $timestamp_created:expr, updated: $timestamp_updated:expr },
+        )?
         fields = {
             $(
                 $field_name:ident: $field_def:expr,
@@ -113,7 +236,7 @@ macro_rules! define_model {
         $(
             indexes = [
                 $(
-                    { fields: [$($index_field:expr),*], unique: $unique:expr $(, name: $index_name:expr)? },
+                    { fields: [$($index_field:expr),*], unique: $unique:expr $(, name: $index_name:expr)? $(, sparse: $index_sparse:expr)? $(, partial_filter: $index_filter:expr)? $(, directions: $index_directions:expr)? $(, case_insensitive: $index_case_insensitive:expr)? },
                 )*
             ],
         )?
@@ -141,6 +264,10 @@ macro_rules! define_model {
                             fields: vec![$($index_field.to_string()),*],
                             unique: $unique,
                             name: None $(.or(Some($index_name.to_string())))?,
+                            sparse: None $(.or(Some($index_sparse)))?.unwrap_or(false),
+                            partial_filter: None $(.or(Some($index_filter)))?.unwrap_or_default(),
+                            directions: None $(.or(Some($index_directions)))?.unwrap_or_default(),
+                            case_insensitive: None $(.or(Some($index_case_insensitive)))?.unwrap_or(false),
                         });
                     )*
                 )?
@@ -151,6 +278,9 @@ macro_rules! define_model {
                     fields,
                     indexes,
                     description: None,
+                    soft_delete_field: None $(.or(Some($soft_delete.to_string())))?,
+                    created_at_field: None $(.or(Some($timestamp_created.to_string())))?,
+                    updated_at_field: None $(.or(Some($timestamp_updated.to_string())))?,
                 };
 
                 // 自动注册模型元数据（仅在首次调用时注册）
@@ -166,6 +296,11 @@ macro_rules! define_model {
                 model_meta
             }
 
+            /// 按结构体声明顺序返回字段名
+            fn field_order() -> Vec<&'static str> {
+                vec![$(stringify!($field)),*]
+            }
+
             /// 高性能直接转换实现，避免 JSON 序列化开销
             fn to_data_map_direct(&self) -> $crate::error::QuickDbResult<std::collections::HashMap<String, $crate::types::DataValue>> {
                 use $crate::model::conversion::ToDataValue;
@@ -202,8 +337,9 @@ macro_rules! define_model {
         impl $name {
             /// 保存模型到数据库
             pub async fn save(&self) -> $crate::error::QuickDbResult<String> {
-                self.validate()?;
-                let data = self.to_data_map()?;
+                let meta = Self::meta();
+                self.validate_with_meta(&meta)?;
+                let data = self.to_normalized_data_map(&meta)?;
                 let collection_name = Self::collection_name();
                 let database_alias = Self::database_alias();
 
@@ -253,6 +389,8 @@ macro_rules! define_model {
             }
 
             /// 更新模型
+            ///
+            /// 声明了 `timestamps` 的模型总是同时把更新时间列设置为当前时间
             pub async fn update(&self, updates: std::collections::HashMap<String, $crate::types::DataValue>) -> $crate::error::QuickDbResult<bool> {
                 // 尝试从模型中获取ID字段，兼容 MongoDB 的 _id 和 SQL 的 id
                 let data_map = self.to_data_map()?;
@@ -289,10 +427,32 @@ macro_rules! define_model {
                 let collection_name = Self::collection_name();
                 let database_alias = Self::database_alias();
 
+                let meta = Self::meta();
+                let mut updates = updates;
+                meta.normalize_data(&mut updates)?;
+                meta.stamp_timestamps(&mut updates, false);
                 $crate::odm::update_by_id(&collection_name, &id_str, updates, database_alias.as_deref()).await
             }
 
+            /// 按空值策略更新模型
+            ///
+            /// `NullPolicy::Skip` 时值为NULL的字段不会被写入，适用于由 `Option` 字段构造的部分更新；
+            /// 需要逐字段区分"忽略"与"清空"时，使用 `Patch` 构造更新数据并保持 `NullPolicy::SetNull`
+            pub async fn update_with_policy(
+                &self,
+                updates: std::collections::HashMap<String, $crate::types::DataValue>,
+                policy: $crate::types::NullPolicy,
+            ) -> $crate::error::QuickDbResult<bool> {
+                let updates = policy.apply(updates);
+                if updates.is_empty() {
+                    return Ok(false);
+                }
+                self.update(updates).await
+            }
+
             /// 删除模型
+            ///
+            /// 启用软删除的模型只写入删除时间，见 [`ModelManager::delete_by_id`]($crate::model::ModelManager::delete_by_id)
             pub async fn delete(&self) -> $crate::error::QuickDbResult<bool> {
                 // 尝试从模型中获取ID字段，兼容 MongoDB 的 _id 和 SQL 的 id
                 let data_map = self.to_data_map()?;
@@ -326,10 +486,7 @@ macro_rules! define_model {
                     })
                 };
 
-                let collection_name = Self::collection_name();
-                let database_alias = Self::database_alias();
-
-                $crate::odm::delete_by_id(&collection_name, &id_str, database_alias.as_deref()).await
+                $crate::model::ModelManager::<Self>::delete_by_id(&id_str).await
             }
 
             /// 批量更新模型（静态方法）
@@ -338,6 +495,8 @@ macro_rules! define_model {
             pub async fn update_many(conditions: Vec<$crate::types::QueryCondition>, updates: std::collections::HashMap<String, $crate::types::DataValue>) -> $crate::error::QuickDbResult<u64> {
                 let collection_name = Self::collection_name();
                 let database_alias = Self::database_alias();
+                let mut updates = updates;
+                Self::meta().stamp_timestamps(&mut updates, false);
 
                 $crate::odm::update(
                     &collection_name,
@@ -377,4 +536,84 @@ macro_rules! define_model {
             }
         }
     };
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::types::DataValue;
+    use serde::Deserialize;
+
+    #[test]
+    fn data_map_handles_nested_values_and_nulls() {
+        let missing: Option<String> = None;
+        let data = data_map! {
+            "name" => "张三",
+            "age" => 26,
+            "score" => 9.5,
+            "email" => missing,
+            "phone" => null,
+            "tags" => ["rust", { "level" => 3 }, [1, 2]],
+            "address" => { "city" => "北京".to_string(), "zip" => Some(100000) },
+        };
+
+        assert_eq!(data.get("name"), Some(&DataValue::String("张三".to_string())));
+        assert_eq!(data.get("age"), Some(&DataValue::Int(26)));
+        assert_eq!(data.get("score"), Some(&DataValue::Float(9.5)));
+        assert_eq!(data.get("email"), Some(&DataValue::Null));
+        assert_eq!(data.get("phone"), Some(&DataValue::Null));
+
+        let tags = match data.get("tags") {
+            Some(DataValue::Array(items)) => items,
+            other => panic!("tags应为数组: {:?}", other),
+        };
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags[0], DataValue::String("rust".to_string()));
+        assert!(matches!(&tags[1], DataValue::Object(m) if m.get("level") == Some(&DataValue::Int(3))));
+        assert_eq!(tags[2], DataValue::Array(vec![DataValue::Int(1), DataValue::Int(2)]));
+
+        match data.get("address") {
+            Some(DataValue::Object(address)) => {
+                assert_eq!(address.get("city"), Some(&DataValue::String("北京".to_string())));
+                assert_eq!(address.get("zip"), Some(&DataValue::Int(100000)));
+            }
+            other => panic!("address应为对象: {:?}", other),
+        }
+
+        assert!(data_map! {}.is_empty());
+    }
+
+    define_patch! {
+        #[derive(Debug, Default, Deserialize)]
+        struct UserPatch {
+            username: Option<String>,
+            age: Option<i32>,
+            #[serde(default, with = "crate::types::serde_helpers::double_option")]
+            email: Option<Option<String>>,
+        }
+    }
+
+    #[test]
+    fn test_patch_only_includes_provided_fields() {
+        let patch = UserPatch {
+            username: Some("alice".to_string()),
+            ..Default::default()
+        };
+        let updates = patch.to_update_map();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates.get("username"), Some(&DataValue::String("alice".to_string())));
+        assert!(UserPatch::default().is_empty());
+    }
+
+    #[test]
+    fn test_patch_distinguishes_null_from_absent() {
+        let absent: UserPatch = serde_json::from_str(r#"{"age": 30}"#).unwrap();
+        let updates = absent.to_update_map();
+        assert_eq!(updates.get("age"), Some(&DataValue::Int(30)));
+        assert!(!updates.contains_key("email"));
+
+        let cleared: UserPatch = serde_json::from_str(r#"{"email": null}"#).unwrap();
+        assert_eq!(cleared.to_update_map().get("email"), Some(&DataValue::Null));
+
+        let set: UserPatch = serde_json::from_str(r#"{"email": "a@example.com"}"#).unwrap();
+        assert_eq!(set.to_update_map().get("email"), Some(&DataValue::String("a@example.com".to_string())));
+    }
+}
@@ -6,6 +6,7 @@ use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
 use crate::odm::{self, OdmOperations};
 use crate::model::traits::{Model, ModelOperations};
+use crate::model::field_types::ModelMeta;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::marker::PhantomData;
@@ -18,6 +19,9 @@ pub struct ModelManager<T: Model> {
     _phantom: PhantomData<T>,
 }
 
+/// 带进度的批量创建每块提交的记录数
+const PROGRESS_CHUNK_SIZE: usize = 500;
+
 impl<T: Model> ModelManager<T> {
     /// 创建新的模型管理器
     pub fn new() -> Self {
@@ -25,6 +29,282 @@ impl<T: Model> ModelManager<T> {
             _phantom: PhantomData,
         }
     }
+
+    /// 批量创建模型，按输入顺序返回生成的ID
+    ///
+    /// 元数据只构建一次并用于验证全部记录，任一记录验证失败时不会写入任何数据；
+    /// 验证通过后整批以单个请求提交，避免逐条 `save()` 的往返和建表检查开销
+    pub async fn create_many(items: Vec<T>) -> QuickDbResult<Vec<String>> {
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let meta = T::meta();
+        let data_list = Self::prepare_batch(&meta, &items)?;
+
+        debug!("批量创建模型: collection={}, count={}", meta.collection_name, data_list.len());
+
+        let ids = odm::create_many(
+            &meta.collection_name,
+            data_list,
+            meta.database_alias.as_deref(),
+        ).await?;
+
+        Ok(ids.into_iter().map(Self::id_to_string).collect())
+    }
+
+    /// 批量保存模型，按输入顺序返回生成的ID
+    ///
+    /// 与 [`ModelManager::create_many`] 相同：SQL数据库按批生成多行 `INSERT`（每条最多 1000 行），
+    /// MongoDB 使用 `insert_many`
+    pub async fn save_many(items: Vec<T>) -> QuickDbResult<Vec<String>> {
+        Self::create_many(items).await
+    }
+
+    /// 根据ID列表批量查找模型，返回以ID为键的映射
+    ///
+    /// 启用缓存时先读取记录缓存，未命中的ID合并为一次查询；结果中只包含找到的ID，
+    /// 调用方可据此判断哪些ID不存在。已被软删除的记录视为不存在。任一记录无法转换为模型时返回错误
+    pub async fn find_by_ids(ids: Vec<&str>) -> QuickDbResult<HashMap<String, T>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let collection_name = T::collection_name();
+        let database_alias = T::database_alias();
+
+        debug!("根据ID列表批量查找模型: collection={}, count={}", collection_name, ids.len());
+
+        let ids = ids.into_iter().map(DataValue::from).collect();
+        let result = odm::find_by_ids(
+            &collection_name,
+            ids,
+            false,
+            database_alias.as_deref(),
+        ).await?;
+
+        let mut models = HashMap::with_capacity(result.len());
+        for data_value in result {
+            let data_map = match data_value {
+                DataValue::Object(data_map) => data_map,
+                other => {
+                    debug!("批量查找收到非Object格式数据: {:?}", other);
+                    return Err(QuickDbError::SerializationError {
+                        message: format!("批量查找结果不是对象: {}", other.type_name()),
+                    });
+                }
+            };
+            if Self::is_trashed(&data_map) {
+                continue;
+            }
+            let id = data_map.get("id").or_else(|| data_map.get("_id")).cloned()
+                .ok_or_else(|| QuickDbError::SerializationError {
+                    message: "批量查找结果缺少id字段".to_string(),
+                })?;
+            models.insert(Self::id_to_string(id), T::from_data_map(data_map)?);
+        }
+
+        Ok(models)
+    }
+
+    /// 按游标分页查找模型
+    ///
+    /// 适用于无限滚动等逐页向后读取的场景：把返回的 `next_cursor` 传给
+    /// [`CursorPagination::after`] 读取下一页，`next_cursor` 为 `None` 表示已读完。
+    /// 游标字段应为主键或唯一字段。任一记录无法转换为模型时返回错误
+    pub async fn find_page(conditions: Vec<QueryCondition>, cursor: CursorPagination) -> QuickDbResult<CursorPage<T>> {
+        let collection_name = T::collection_name();
+        let database_alias = T::database_alias();
+
+        debug!("按游标分页查找模型: collection={}, field={}, limit={}", collection_name, cursor.field, cursor.limit);
+
+        let page = odm::find_page(
+            &collection_name,
+            Self::exclude_trashed(conditions),
+            cursor,
+            None,
+            database_alias.as_deref(),
+        ).await?;
+
+        let mut records = Vec::with_capacity(page.records.len());
+        for data_value in page.records {
+            match data_value {
+                DataValue::Object(data_map) => records.push(T::from_data_map(data_map)?),
+                other => {
+                    return Err(QuickDbError::SerializationError {
+                        message: format!("分页查找结果不是对象: {}", other.type_name()),
+                    });
+                }
+            }
+        }
+
+        Ok(CursorPage { records, next_cursor: page.next_cursor })
+    }
+
+    /// 批量创建模型并周期性回报进度
+    ///
+    /// 全部记录先统一验证，再按 `PROGRESS_CHUNK_SIZE` 分块写入，每完成一块调用一次 `progress`。
+    /// 分块意味着各块独立提交：某块失败时之前的块已写入，回调会收到包含剩余记录数的 `errors`
+    pub async fn create_many_with_progress<F>(items: Vec<T>, progress: F) -> QuickDbResult<Vec<String>>
+    where
+        F: Fn(ProgressEvent) + Send + Sync,
+    {
+        let total = items.len() as u64;
+        if items.is_empty() {
+            progress(ProgressEvent { processed: 0, total: Some(0), errors: 0 });
+            return Ok(Vec::new());
+        }
+
+        let meta = T::meta();
+        let mut data_list = Self::prepare_batch(&meta, &items)?;
+
+        debug!("批量创建模型(带进度): collection={}, count={}", meta.collection_name, total);
+
+        let mut ids = Vec::with_capacity(items.len());
+        while !data_list.is_empty() {
+            let rest = data_list.split_off(data_list.len().min(PROGRESS_CHUNK_SIZE));
+            let chunk = std::mem::replace(&mut data_list, rest);
+            let chunk_ids = match odm::create_many(
+                &meta.collection_name,
+                chunk,
+                meta.database_alias.as_deref(),
+            ).await {
+                Ok(chunk_ids) => chunk_ids,
+                Err(e) => {
+                    let processed = ids.len() as u64;
+                    progress(ProgressEvent { processed, total: Some(total), errors: total - processed });
+                    return Err(e);
+                }
+            };
+            ids.extend(chunk_ids.into_iter().map(Self::id_to_string));
+            progress(ProgressEvent { processed: ids.len() as u64, total: Some(total), errors: 0 });
+        }
+
+        Ok(ids)
+    }
+
+    /// 验证一批记录但不写入，返回每条无效记录的位置及其全部验证错误
+    ///
+    /// 元数据只构建一次，正则等验证器按模式缓存；结果按输入顺序排列且只包含无效记录，
+    /// 全部有效时返回空列表。适用于导入前先向用户展示"第3、17、42条无效"之类的报告
+    pub fn validate_batch(items: &[T]) -> Vec<(usize, Vec<QuickDbError>)> {
+        let meta = T::meta();
+        items.iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let errors = item.validation_errors_with_meta(&meta);
+                (!errors.is_empty()).then_some((index, errors))
+            })
+            .collect()
+    }
+
+    /// 使用同一份元数据验证全部记录并转换为数据映射
+    fn prepare_batch(meta: &ModelMeta, items: &[T]) -> QuickDbResult<Vec<HashMap<String, DataValue>>> {
+        let mut data_list = Vec::with_capacity(items.len());
+        for (index, item) in items.iter().enumerate() {
+            item.validate_with_meta(meta).map_err(|e| match e {
+                QuickDbError::ValidationError { field, message } => QuickDbError::ValidationError {
+                    field,
+                    message: format!("第{}条记录验证失败: {}", index, message),
+                },
+                other => other,
+            })?;
+            data_list.push(item.to_normalized_data_map(meta)?);
+        }
+        Ok(data_list)
+    }
+
+    /// 根据ID删除模型
+    ///
+    /// 启用软删除的模型不删除记录，而是把软删除标记列设置为当前时间，
+    /// 之后 `find`/`count`/`exists` 等查询不再返回该记录；未启用软删除时物理删除
+    pub async fn delete_by_id(id: &str) -> QuickDbResult<bool> {
+        let collection_name = T::collection_name();
+        let database_alias = T::database_alias();
+
+        match T::soft_delete_field() {
+            Some(field) => {
+                debug!("软删除模型: collection={}, id={}, field={}", collection_name, id, field);
+                let mut updates = HashMap::new();
+                updates.insert(field, DataValue::DateTime(chrono::Utc::now()));
+                odm::update_by_id(&collection_name, id, updates, database_alias.as_deref()).await
+            }
+            None => {
+                debug!("删除模型: collection={}, id={}", collection_name, id);
+                odm::delete_by_id(&collection_name, id, database_alias.as_deref()).await
+            }
+        }
+    }
+
+    /// 查找模型，包含已被软删除的记录
+    ///
+    /// 未启用软删除的模型与 `find` 相同
+    pub async fn find_with_trashed(conditions: Vec<QueryCondition>, options: Option<QueryOptions>) -> QuickDbResult<Vec<T>> {
+        let collection_name = T::collection_name();
+        let database_alias = T::database_alias();
+
+        debug!("查找模型(含软删除记录): collection={}", collection_name);
+
+        let result = odm::find(
+            &collection_name,
+            conditions,
+            options,
+            database_alias.as_deref(),
+        ).await?;
+
+        // result 已经是 Vec<DataValue>，直接处理
+        let mut models = Vec::new();
+        for data_value in result {
+            // 处理 DataValue::Object 格式的数据
+            match data_value {
+                DataValue::Object(data_map) => {
+                    debug!("查询收到的数据: {}", DataValue::pretty_fields(&data_map));
+                    let model: T = match T::from_data_map(data_map.clone()) {
+                        Ok(model) => model,
+                        Err(e) => {
+                            debug!("❌ 查询from_data_map失败: {}, 数据: {}", e, DataValue::pretty_fields(&data_map));
+                            continue;
+                        }
+                    };
+                    models.push(model);
+                },
+                _ => {
+                    // 兼容其他格式，使用直接反序列化
+                    debug!("查询收到非Object格式数据: {}", data_value.pretty());
+                    let model: T = data_value.deserialize_to()?;
+                    models.push(model);
+                }
+            }
+        }
+        Ok(models)
+    }
+
+    /// 软删除模型追加"未删除"条件，未启用软删除时原样返回
+    fn exclude_trashed(mut conditions: Vec<QueryCondition>) -> Vec<QueryCondition> {
+        if let Some(field) = T::soft_delete_field() {
+            conditions.push(QueryCondition::new(&field, QueryOperator::IsNull, DataValue::Null));
+        }
+        conditions
+    }
+
+    /// 记录是否已被软删除，未启用软删除时始终为 `false`
+    ///
+    /// 按ID查找可能命中记录缓存，无法在查询条件中排除，因此在结果上判断
+    fn is_trashed(data_map: &HashMap<String, DataValue>) -> bool {
+        T::soft_delete_field()
+            .and_then(|field| data_map.get(&field))
+            .is_some_and(|value| !matches!(value, DataValue::Null))
+    }
+
+    /// 将适配器返回的ID统一转换为字符串
+    fn id_to_string(id: DataValue) -> String {
+        match id {
+            DataValue::String(s) => s,
+            DataValue::Int(i) => i.to_string(),
+            DataValue::Uuid(u) => u.to_string(),
+            other => other.to_string(),
+        }
+    }
 }
 
 #[async_trait]
@@ -52,12 +332,16 @@ impl<T: Model> ModelOperations<T> for ModelManager<T> {
         if let Some(data_value) = result {
             // 处理 DataValue::Object 格式的数据
             match data_value {
+                DataValue::Object(data_map) if Self::is_trashed(&data_map) => {
+                    debug!("记录已被软删除: collection={}, id={}", collection_name, id);
+                    Ok(None)
+                },
                 DataValue::Object(data_map) => {
-                    debug!("从数据库收到的数据: {:?}", data_map);
+                    debug!("从数据库收到的数据: {}", DataValue::pretty_fields(&data_map));
                     let model: T = match T::from_data_map(data_map.clone()) {
                         Ok(model) => model,
                         Err(e) => {
-                            debug!("❌ from_data_map失败: {}, 数据: {:?}", e, data_map);
+                            debug!("❌ from_data_map失败: {}, 数据: {}", e, DataValue::pretty_fields(&data_map));
                             return Err(e);
                         }
                     };
@@ -65,7 +349,7 @@ impl<T: Model> ModelOperations<T> for ModelManager<T> {
                 },
                 _ => {
                     // 兼容其他格式，使用直接反序列化
-                    debug!("收到非Object格式数据: {:?}", data_value);
+                    debug!("收到非Object格式数据: {}", data_value.pretty());
                     let model: T = data_value.deserialize_to()?;
                     Ok(Some(model))
                 }
@@ -76,43 +360,21 @@ impl<T: Model> ModelOperations<T> for ModelManager<T> {
     }
 
     async fn find(conditions: Vec<QueryCondition>, options: Option<QueryOptions>) -> QuickDbResult<Vec<T>> {
+        Self::find_with_trashed(Self::exclude_trashed(conditions), options).await
+    }
+
+    async fn find_raw(conditions: Vec<QueryCondition>, options: Option<QueryOptions>) -> QuickDbResult<Vec<DataValue>> {
         let collection_name = T::collection_name();
         let database_alias = T::database_alias();
 
-        debug!("查找模型: collection={}", collection_name);
+        debug!("查找原始记录: collection={}", collection_name);
 
-        let result = odm::find(
+        odm::find(
             &collection_name,
-            conditions,
+            Self::exclude_trashed(conditions),
             options,
             database_alias.as_deref(),
-        ).await?;
-
-        // result 已经是 Vec<DataValue>，直接处理
-        let mut models = Vec::new();
-        for data_value in result {
-            // 处理 DataValue::Object 格式的数据
-            match data_value {
-                DataValue::Object(data_map) => {
-                    debug!("查询收到的数据: {:?}", data_map);
-                    let model: T = match T::from_data_map(data_map.clone()) {
-                        Ok(model) => model,
-                        Err(e) => {
-                            debug!("❌ 查询from_data_map失败: {}, 数据: {:?}", e, data_map);
-                            continue;
-                        }
-                    };
-                    models.push(model);
-                },
-                _ => {
-                    // 兼容其他格式，使用直接反序列化
-                    debug!("查询收到非Object格式数据: {:?}", data_value);
-                    let model: T = data_value.deserialize_to()?;
-                    models.push(model);
-                }
-            }
-        }
-        Ok(models)
+        ).await
     }
 
     async fn update(&self, _updates: HashMap<String, DataValue>) -> QuickDbResult<bool> {
@@ -139,7 +401,21 @@ impl<T: Model> ModelOperations<T> for ModelManager<T> {
 
         odm::count(
             &collection_name,
-            conditions,
+            Self::exclude_trashed(conditions),
+            database_alias.as_deref(),
+        ).await
+    }
+
+    async fn count_with_mode(conditions: Vec<QueryCondition>, mode: CountMode) -> QuickDbResult<u64> {
+        let collection_name = T::collection_name();
+        let database_alias = T::database_alias();
+
+        debug!("统计模型数量: collection={}, mode={:?}", collection_name, mode);
+
+        odm::count_with_mode(
+            &collection_name,
+            Self::exclude_trashed(conditions),
+            mode,
             database_alias.as_deref(),
         ).await
     }
@@ -152,7 +428,7 @@ impl<T: Model> ModelOperations<T> for ModelManager<T> {
 
         odm::exists(
             &collection_name,
-            conditions,
+            Self::exclude_trashed(conditions),
             database_alias.as_deref(),
         ).await
     }
@@ -163,6 +439,8 @@ impl<T: Model> ModelOperations<T> for ModelManager<T> {
 
         debug!("使用条件组查找模型: collection={}", collection_name);
 
+        let mut condition_groups = condition_groups;
+        condition_groups.extend(Self::exclude_trashed(Vec::new()).into_iter().map(QueryConditionGroup::Single));
         let result = odm::find_with_groups(
             &collection_name,
             condition_groups,
@@ -182,9 +460,10 @@ impl<T: Model> ModelOperations<T> for ModelManager<T> {
     /// 批量更新模型
     ///
     /// 根据条件批量更新多条记录，返回受影响的行数
-    async fn update_many(conditions: Vec<QueryCondition>, updates: HashMap<String, DataValue>) -> QuickDbResult<u64> {
+    async fn update_many(conditions: Vec<QueryCondition>, mut updates: HashMap<String, DataValue>) -> QuickDbResult<u64> {
         let collection_name = T::collection_name();
         let database_alias = T::database_alias();
+        T::meta().stamp_timestamps(&mut updates, false);
 
         debug!("批量更新模型: collection={}, 条件数量={}", collection_name, conditions.len());
 
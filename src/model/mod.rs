@@ -9,11 +9,17 @@ pub mod traits;
 pub mod manager;
 pub mod macros;
 pub mod convenience;
+pub mod row;
+pub mod dynamic;
+pub mod coercion;
 
 // 重新导出核心类型（保持向后兼容）
 pub use conversion::ToDataValue;
-pub use field_types::{FieldType, FieldDefinition, ModelMeta, IndexDefinition};
+pub use field_types::{FieldType, FieldDefinition, FieldInfo, FieldNormalizer, ModelMeta, IndexDefinition, ArrayElementPolicy, DefaultExpr, register_normalizer, unregister_normalizer};
 pub use traits::{Model, ModelOperations};
 pub use manager::ModelManager;
+pub use row::Row;
+pub use dynamic::DynamicModel;
+pub use coercion::{ReadCoercion, set_read_coercion, read_coercion};
 pub use macros::*;
 pub use convenience::*;
\ No newline at end of file
@@ -0,0 +1,162 @@
+//! 原始行访问模块
+//!
+//! 提供 `Row` 类型，用于在热点读取路径上按需读取字段，
+//! 避免 `from_data_map` → serde 的完整反序列化开销
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::DataValue;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// 原始行数据访问器
+///
+/// 包装数据库返回的 `DataValue::Object`，提供带类型的字段读取方法。
+/// 读取时会兼容不同数据库的存储差异（如 SQLite 以 0/1 存储布尔值、以字符串存储日期时间）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    data: HashMap<String, DataValue>,
+}
+
+impl Row {
+    /// 从字段映射创建行
+    pub fn new(data: HashMap<String, DataValue>) -> Self {
+        Self { data }
+    }
+
+    /// 获取原始字段值
+    pub fn get(&self, field: &str) -> Option<&DataValue> {
+        self.data.get(field)
+    }
+
+    /// 检查字段是否存在
+    pub fn contains(&self, field: &str) -> bool {
+        self.data.contains_key(field)
+    }
+
+    /// 检查字段是否为空值（字段不存在也视为空值）
+    pub fn is_null(&self, field: &str) -> bool {
+        self.data.get(field).is_none_or(|v| v.is_null())
+    }
+
+    /// 读取字符串字段
+    pub fn get_str(&self, field: &str) -> Option<&str> {
+        match self.data.get(field)? {
+            DataValue::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// 读取整数字段
+    ///
+    /// 兼容以字符串形式存储的大整数（如 SQLite 中的雪花ID）
+    pub fn get_i64(&self, field: &str) -> Option<i64> {
+        match self.data.get(field)? {
+            DataValue::Int(i) => Some(*i),
+            DataValue::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// 读取浮点数字段
+    pub fn get_f64(&self, field: &str) -> Option<f64> {
+        match self.data.get(field)? {
+            DataValue::Float(f) => Some(*f),
+            DataValue::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// 读取布尔字段
+    ///
+    /// 兼容 SQLite 等数据库以 0/1 存储的布尔值
+    pub fn get_bool(&self, field: &str) -> Option<bool> {
+        match self.data.get(field)? {
+            DataValue::Bool(b) => Some(*b),
+            DataValue::Int(0) => Some(false),
+            DataValue::Int(1) => Some(true),
+            _ => None,
+        }
+    }
+
+    /// 读取日期时间字段
+    ///
+    /// 兼容以 RFC3339 字符串存储的日期时间
+    pub fn get_datetime(&self, field: &str) -> Option<DateTime<Utc>> {
+        match self.data.get(field)? {
+            DataValue::DateTime(dt) => Some(*dt),
+            DataValue::String(s) => DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc)),
+            _ => None,
+        }
+    }
+
+    /// 读取UUID字段
+    pub fn get_uuid(&self, field: &str) -> Option<Uuid> {
+        match self.data.get(field)? {
+            DataValue::Uuid(u) => Some(*u),
+            DataValue::String(s) => Uuid::parse_str(s).ok(),
+            _ => None,
+        }
+    }
+
+    /// 获取底层字段映射的引用
+    pub fn as_map(&self) -> &HashMap<String, DataValue> {
+        &self.data
+    }
+
+    /// 取出底层字段映射
+    pub fn into_map(self) -> HashMap<String, DataValue> {
+        self.data
+    }
+}
+
+impl From<HashMap<String, DataValue>> for Row {
+    fn from(data: HashMap<String, DataValue>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl TryFrom<DataValue> for Row {
+    type Error = QuickDbError;
+
+    fn try_from(value: DataValue) -> QuickDbResult<Self> {
+        Ok(Self::new(value.expect_object()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_row() -> Row {
+        let mut data = HashMap::new();
+        data.insert("name".to_string(), DataValue::String("alice".to_string()));
+        data.insert("age".to_string(), DataValue::Int(30));
+        data.insert("is_active".to_string(), DataValue::Int(1));
+        data.insert("id".to_string(), DataValue::String("1234567890123456789".to_string()));
+        data.insert("created_at".to_string(), DataValue::String("2025-01-01T00:00:00+00:00".to_string()));
+        data.insert("deleted_at".to_string(), DataValue::Null);
+        Row::new(data)
+    }
+
+    #[test]
+    fn test_typed_getters() {
+        let row = sample_row();
+        assert_eq!(row.get_str("name"), Some("alice"));
+        assert_eq!(row.get_i64("age"), Some(30));
+        assert_eq!(row.get_f64("age"), Some(30.0));
+        assert_eq!(row.get_bool("is_active"), Some(true));
+        assert_eq!(row.get_i64("id"), Some(1234567890123456789));
+        assert!(row.get_datetime("created_at").is_some());
+        assert!(row.is_null("deleted_at"));
+        assert!(row.is_null("missing"));
+        assert_eq!(row.get_str("age"), None);
+    }
+
+    #[test]
+    fn test_try_from_non_object() {
+        assert!(Row::try_from(DataValue::Int(1)).is_err());
+    }
+}
@@ -14,6 +14,24 @@ use std::marker::PhantomData;
 use rat_logger::{debug, error, info, warn};
 use base64;
 
+/// 递归按键名排序 JSON 对象（嵌套对象来源于 HashMap，本身没有稳定顺序）
+fn sort_nested_json_keys(value: JsonValue) -> JsonValue {
+    match value {
+        JsonValue::Object(obj) => {
+            let mut entries: Vec<(String, JsonValue)> = obj.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            JsonValue::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sort_nested_json_keys(v)))
+                    .collect(),
+            )
+        }
+        JsonValue::Array(arr) => JsonValue::Array(arr.into_iter().map(sort_nested_json_keys).collect()),
+        other => other,
+    }
+}
+
 /// 模型特征
 ///
 /// 所有模型都必须实现这个特征
@@ -31,23 +49,53 @@ pub trait Model: Serialize + for<'de> Deserialize<'de> + Send + Sync {
         Self::meta().database_alias
     }
 
+    /// 获取软删除标记列，未启用软删除时为 `None`
+    fn soft_delete_field() -> Option<String> {
+        Self::meta().soft_delete_field
+    }
+
     /// 验证模型数据
     fn validate(&self) -> QuickDbResult<()> {
-        let meta = Self::meta();
-        let data = self.to_data_map()?;
+        self.validate_with_meta(&Self::meta())
+    }
+
+    /// 使用已构建的元数据验证模型数据
+    ///
+    /// 批量验证时可复用同一份元数据，避免每条记录重复构建
+    fn validate_with_meta(&self, meta: &ModelMeta) -> QuickDbResult<()> {
+        let data = self.to_normalized_data_map(meta)?;
 
         // 调试信息：打印序列化后的数据
-        debug!("🔍 验证数据映射: {:?}", data);
+        debug!("🔍 验证数据映射: {}", DataValue::pretty_fields(&data));
 
         for (field_name, field_def) in &meta.fields {
             let field_value = data.get(field_name).unwrap_or(&DataValue::Null);
-            debug!("🔍 验证字段 {}: {:?}", field_name, field_value);
+            debug!("🔍 验证字段 {}: {}", field_name, field_value.pretty());
             field_def.validate_with_field_name(field_value, field_name)?;
         }
 
         Ok(())
     }
 
+    /// 使用已构建的元数据验证模型数据，收集全部字段的错误而不是在第一个错误处停止
+    ///
+    /// 错误按字段名排序，数据无法转换为映射时只返回该转换错误
+    fn validation_errors_with_meta(&self, meta: &ModelMeta) -> Vec<QuickDbError> {
+        let data = match self.to_normalized_data_map(meta) {
+            Ok(data) => data,
+            Err(e) => return vec![e],
+        };
+
+        let mut field_names: Vec<&String> = meta.fields.keys().collect();
+        field_names.sort();
+        field_names.into_iter()
+            .filter_map(|field_name| {
+                let field_value = data.get(field_name).unwrap_or(&DataValue::Null);
+                meta.fields[field_name].validate_with_field_name(field_value, field_name).err()
+            })
+            .collect()
+    }
+
     /// 转换为数据映射（直接转换，避免 JSON 序列化开销）
     /// 子类应该重写此方法以提供高性能的直接转换
     fn to_data_map_direct(&self) -> QuickDbResult<HashMap<String, DataValue>> {
@@ -84,6 +132,63 @@ pub trait Model: Serialize + for<'de> Deserialize<'de> + Send + Sync {
         self.to_data_map_direct()
     }
 
+    /// 转换为数据映射并执行字段的规范化处理，验证和写入都基于规范化后的数据
+    ///
+    /// 声明了自动时间戳的模型同时补全未设置的创建/更新时间，未设置时间戳不会导致验证失败
+    fn to_normalized_data_map(&self, meta: &ModelMeta) -> QuickDbResult<HashMap<String, DataValue>> {
+        let mut data = self.to_data_map()?;
+        meta.normalize_data(&mut data)?;
+        meta.stamp_timestamps(&mut data, true);
+        Ok(data)
+    }
+
+    /// 字段声明顺序
+    ///
+    /// `define_model!` 生成的模型会按结构体字段的声明顺序返回；
+    /// 手动实现的模型默认返回空列表，此时有序输出按字段名字母序排列
+    fn field_order() -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// 将模型转换为有序的字段列表
+    ///
+    /// 先按 `field_order()` 的声明顺序输出，未声明的字段按字段名排序追加在后，
+    /// 保证同一条记录在任何进程中都得到相同的字段顺序
+    fn to_ordered_data_map(&self) -> QuickDbResult<Vec<(String, DataValue)>> {
+        let mut data_map = self.to_data_map()?;
+        let mut ordered = Vec::with_capacity(data_map.len());
+
+        for field in Self::field_order() {
+            if let Some(value) = data_map.remove(field) {
+                ordered.push((field.to_string(), value));
+            }
+        }
+
+        let mut rest: Vec<(String, DataValue)> = data_map.into_iter().collect();
+        rest.sort_by(|a, b| a.0.cmp(&b.0));
+        ordered.extend(rest);
+
+        Ok(ordered)
+    }
+
+    /// 将模型转换为字段顺序确定的 JSON 对象
+    ///
+    /// 顶层字段按 `to_ordered_data_map` 的顺序输出，嵌套对象按键名排序，
+    /// 可用于基于内容的缓存键和快照测试
+    fn to_ordered_json(&self) -> QuickDbResult<JsonValue> {
+        let mut obj = serde_json::Map::new();
+        for (key, value) in self.to_ordered_data_map()? {
+            obj.insert(key, sort_nested_json_keys(value.to_json_value()));
+        }
+        Ok(JsonValue::Object(obj))
+    }
+
+    /// 将模型序列化为字段顺序确定的 JSON 字符串
+    fn to_ordered_json_string(&self) -> QuickDbResult<String> {
+        serde_json::to_string(&self.to_ordered_json()?)
+            .map_err(|e| QuickDbError::SerializationError { message: format!("序列化失败: {}", e) })
+    }
+
     /// 将模型转换为带类型信息的数据映射（专门用于 PyO3 兼容序列化）
     /// 对于 None 值，会根据字段类型生成带类型标签的 DataValue
     fn to_data_map_with_types(&self) -> QuickDbResult<HashMap<String, DataValue>> {
@@ -324,8 +429,9 @@ pub trait Model: Serialize + for<'de> Deserialize<'de> + Send + Sync {
                     result
                 },
                 DataValue::String(s) => {
-                    // 对于字符串类型的DataValue，检查是否是JSON格式
-                    if (s.starts_with('[') && s.ends_with(']')) || (s.starts_with('{') && s.ends_with('}')) {
+                    // 仅当字段声明为JSON/数组/对象类型时才尝试按JSON解析
+                    let json_like = field_type.is_some_and(|t| t.is_json_like());
+                    if json_like && ((s.starts_with('[') && s.ends_with(']')) || (s.starts_with('{') && s.ends_with('}'))) {
                         match serde_json::from_str::<serde_json::Value>(&s) {
                             Ok(parsed) => parsed,
                             Err(_) => JsonValue::String(s),
@@ -399,22 +505,31 @@ pub trait ModelOperations<T: Model> {
     /// 保存模型
     async fn save(&self) -> QuickDbResult<String>;
 
-    /// 根据ID查找模型
+    /// 根据ID查找模型，软删除模型的已删除记录返回 `None`
     async fn find_by_id(id: &str) -> QuickDbResult<Option<T>>;
 
-    /// 查找多个模型
+    /// 查找多个模型，软删除模型不返回已删除的记录
     async fn find(conditions: Vec<QueryCondition>, options: Option<QueryOptions>) -> QuickDbResult<Vec<T>>;
 
+    /// 查找多个记录，返回未反序列化的原始数据
+    ///
+    /// 跳过 `from_data_map` 反序列化，适用于只需读取少量字段的高频读取路径，
+    /// 可配合 `Row` 按需读取字段
+    async fn find_raw(conditions: Vec<QueryCondition>, options: Option<QueryOptions>) -> QuickDbResult<Vec<DataValue>>;
+
     /// 更新模型
     async fn update(&self, updates: HashMap<String, DataValue>) -> QuickDbResult<bool>;
 
     /// 删除模型
     async fn delete(&self) -> QuickDbResult<bool>;
 
-    /// 统计模型数量
+    /// 统计模型数量，软删除模型不计入已删除的记录
     async fn count(conditions: Vec<QueryCondition>) -> QuickDbResult<u64>;
 
-    /// 检查模型是否存在
+    /// 按指定方式统计模型数量，[`CountMode::Estimated`] 只在没有条件时生效
+    async fn count_with_mode(conditions: Vec<QueryCondition>, mode: CountMode) -> QuickDbResult<u64>;
+
+    /// 检查模型是否存在，软删除模型忽略已删除的记录
     async fn exists(conditions: Vec<QueryCondition>) -> QuickDbResult<bool>;
 
     /// 使用条件组查找多个模型（支持复杂的AND/OR逻辑组合）
@@ -0,0 +1,225 @@
+//! # 二进制列流式读写
+//!
+//! 大二进制列按 [`BLOB_CHUNK_SIZE`] 分块经ODM后台任务读写，每块是一次独立的数据库操作，
+//! 内存占用与数据大小无关，块与块之间也不占用连接
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::odm::types::OdmRequest;
+use crate::types::{BlobLocation, BLOB_CHUNK_SIZE};
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+use tokio::sync::{mpsc, oneshot};
+
+type ChunkFuture = Pin<Box<dyn Future<Output = QuickDbResult<Option<Vec<u8>>>> + Send>>;
+
+/// 二进制列读取器，按块从数据库拉取数据
+///
+/// 读取途中记录被删除时返回 `NotFound` 类型的IO错误
+pub struct BlobReader {
+    request_sender: mpsc::UnboundedSender<OdmRequest>,
+    location: BlobLocation,
+    alias: Option<String>,
+    /// 下一块在列中的起始偏移
+    offset: u64,
+    /// 当前块及已消费的位置
+    buffer: Vec<u8>,
+    position: usize,
+    /// 已读到最后一块
+    finished: bool,
+    pending: Option<ChunkFuture>,
+}
+
+impl BlobReader {
+    /// 打开读取器并读取第一块，记录不存在时返回错误
+    pub(crate) async fn open(
+        request_sender: mpsc::UnboundedSender<OdmRequest>,
+        location: BlobLocation,
+        alias: Option<String>,
+    ) -> QuickDbResult<Self> {
+        let first = read_chunk(request_sender.clone(), location.clone(), 0, alias.clone()).await?
+            .ok_or_else(|| QuickDbError::QueryError {
+                message: format!("记录不存在: {} {}", location.table, location.id),
+            })?;
+        Ok(Self {
+            request_sender,
+            location,
+            alias,
+            offset: first.len() as u64,
+            finished: first.len() < BLOB_CHUNK_SIZE,
+            buffer: first,
+            position: 0,
+            pending: None,
+        })
+    }
+
+    /// 读取位置
+    pub fn location(&self) -> &BlobLocation {
+        &self.location
+    }
+}
+
+impl AsyncRead for BlobReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.position < this.buffer.len() {
+                let n = buf.remaining().min(this.buffer.len() - this.position);
+                buf.put_slice(&this.buffer[this.position..this.position + n]);
+                this.position += n;
+                return Poll::Ready(Ok(()));
+            }
+            if this.finished {
+                return Poll::Ready(Ok(()));
+            }
+
+            let pending = this.pending.get_or_insert_with(|| {
+                Box::pin(read_chunk(
+                    this.request_sender.clone(),
+                    this.location.clone(),
+                    this.offset,
+                    this.alias.clone(),
+                ))
+            });
+            let result = match pending.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(result) => result,
+            };
+            this.pending = None;
+
+            match result {
+                Ok(Some(chunk)) => {
+                    this.offset += chunk.len() as u64;
+                    this.finished = chunk.len() < BLOB_CHUNK_SIZE;
+                    this.buffer = chunk;
+                    this.position = 0;
+                }
+                Ok(None) => {
+                    this.finished = true;
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("记录不存在: {} {}", this.location.table, this.location.id),
+                    )));
+                }
+                Err(e) => return Poll::Ready(Err(io::Error::other(e))),
+            }
+        }
+    }
+}
+
+/// 从读取器写入二进制列，返回写入的字节数
+///
+/// 第一块覆盖原值（空数据时写入空值），之后的块追加到末尾；记录不存在时返回错误
+pub(crate) async fn write_from_reader<R>(
+    request_sender: &mpsc::UnboundedSender<OdmRequest>,
+    location: BlobLocation,
+    reader: &mut R,
+    alias: Option<String>,
+) -> QuickDbResult<u64>
+where
+    R: AsyncRead + Unpin + Send,
+{
+    let mut written = 0u64;
+    let mut append = false;
+    loop {
+        let chunk = fill_chunk(reader).await?;
+        let last = chunk.len() < BLOB_CHUNK_SIZE;
+        let len = chunk.len() as u64;
+        // 后续块为空说明上一块恰好读满后到达末尾，无需再写
+        if append && len == 0 {
+            break;
+        }
+
+        let exists = write_chunk(request_sender, location.clone(), chunk, append, alias.clone()).await?;
+        if !exists {
+            return Err(QuickDbError::QueryError {
+                message: format!("记录不存在: {} {}", location.table, location.id),
+            });
+        }
+        written += len;
+        append = true;
+        if last {
+            break;
+        }
+    }
+    Ok(written)
+}
+
+/// 从读取器填满一块，到达末尾时返回不足一块的数据
+async fn fill_chunk<R>(reader: &mut R) -> QuickDbResult<Vec<u8>>
+where
+    R: AsyncRead + Unpin + Send,
+{
+    let mut chunk = Vec::with_capacity(BLOB_CHUNK_SIZE);
+    while chunk.len() < BLOB_CHUNK_SIZE {
+        let n = (&mut *reader)
+            .take((BLOB_CHUNK_SIZE - chunk.len()) as u64)
+            .read_to_end(&mut chunk)
+            .await?;
+        if n == 0 {
+            break;
+        }
+    }
+    Ok(chunk)
+}
+
+async fn read_chunk(
+    request_sender: mpsc::UnboundedSender<OdmRequest>,
+    location: BlobLocation,
+    offset: u64,
+    alias: Option<String>,
+) -> QuickDbResult<Option<Vec<u8>>> {
+    let (sender, receiver) = oneshot::channel();
+
+    let request = OdmRequest::ReadBlobChunk {
+        location,
+        offset,
+        len: BLOB_CHUNK_SIZE as u64,
+        alias,
+        response: sender,
+    };
+
+    request_sender.send(request.with_current_task_context())
+        .map_err(|_| QuickDbError::ConnectionError {
+            message: "ODM后台任务已停止".to_string(),
+        })?;
+
+    receiver.await
+        .map_err(|_| QuickDbError::ConnectionError {
+            message: "ODM请求处理失败".to_string(),
+        })?
+}
+
+async fn write_chunk(
+    request_sender: &mpsc::UnboundedSender<OdmRequest>,
+    location: BlobLocation,
+    data: Vec<u8>,
+    append: bool,
+    alias: Option<String>,
+) -> QuickDbResult<bool> {
+    let (sender, receiver) = oneshot::channel();
+
+    let request = OdmRequest::WriteBlobChunk {
+        location,
+        data,
+        append,
+        alias,
+        response: sender,
+    };
+
+    request_sender.send(request.with_current_task_context())
+        .map_err(|_| QuickDbError::ConnectionError {
+            message: "ODM后台任务已停止".to_string(),
+        })?;
+
+    receiver.await
+        .map_err(|_| QuickDbError::ConnectionError {
+            message: "ODM请求处理失败".to_string(),
+        })?
+}
@@ -0,0 +1,102 @@
+//! # 进程内模型变更事件
+//!
+//! ODM写操作成功后按集合向订阅者发布 [`ChangeEvent`]，订阅方无需被模型引用，
+//! 可用于缓存失效、搜索索引同步、Webhook等。与数据库变更订阅（`watch`）不同，
+//! 只能收到本进程经ODM发起的写操作，但不依赖数据库特性。
+//!
+//! 每个订阅者在独立的Tokio任务中执行，返回的错误只记录日志，不影响写操作结果。
+
+use crate::error::QuickDbResult;
+use crate::types::*;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use rat_logger::{debug, error};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use futures::future::BoxFuture;
+
+/// 订阅者回调
+type Subscriber = Arc<dyn Fn(ChangeEvent) -> BoxFuture<'static, QuickDbResult<()>> + Send + Sync>;
+
+/// 各集合的订阅者
+static SUBSCRIBERS: Lazy<DashMap<String, Vec<(SubscriptionId, Subscriber)>>> =
+    Lazy::new(DashMap::new);
+
+static NEXT_SUBSCRIPTION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 订阅标识，用于取消订阅
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// 订阅集合的变更事件
+///
+/// 创建、更新、删除成功后调用 `handler`。按ID的写操作携带记录ID；按条件的批量更新和删除
+/// 无法得知受影响的ID，发布一条ID为 `DataValue::Null` 的事件（未影响任何记录时不发布）
+pub fn subscribe<F, Fut>(collection: &str, handler: F) -> SubscriptionId
+where
+    F: Fn(ChangeEvent) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = QuickDbResult<()>> + Send + 'static,
+{
+    let id = SubscriptionId(NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::Relaxed));
+    let subscriber: Subscriber = Arc::new(move |event| Box::pin(handler(event)));
+    SUBSCRIBERS.entry(collection.to_string()).or_default().push((id, subscriber));
+    debug!("添加变更事件订阅: collection={}, id={:?}", collection, id);
+    id
+}
+
+/// 取消订阅，订阅不存在时返回 `false`
+pub fn unsubscribe(id: SubscriptionId) -> bool {
+    let mut removed = false;
+    SUBSCRIBERS.retain(|_, subscribers| {
+        let before = subscribers.len();
+        subscribers.retain(|(subscription, _)| *subscription != id);
+        removed |= subscribers.len() != before;
+        !subscribers.is_empty()
+    });
+    removed
+}
+
+/// 写操作是否需要发布事件：集合没有订阅者时跳过事件数据的构造，
+/// dry_run别名的写入最终会回滚，同样不发布
+pub(crate) fn has_subscribers(collection: &str, db_config: &DatabaseConfig) -> bool {
+    !db_config.dry_run && SUBSCRIBERS.contains_key(collection)
+}
+
+/// 向集合的所有订阅者发布事件
+pub(crate) fn publish(event: ChangeEvent) {
+    let subscribers: Vec<Subscriber> = match SUBSCRIBERS.get(&event.table) {
+        Some(subscribers) => subscribers.iter().map(|(_, subscriber)| subscriber.clone()).collect(),
+        None => return,
+    };
+
+    for subscriber in subscribers {
+        let event = event.clone();
+        tokio::spawn(async move {
+            let table = event.table.clone();
+            let op = event.op;
+            if let Err(e) = subscriber(event).await {
+                error!("变更事件订阅者执行失败: collection={}, op={:?}, error={}", table, op, e);
+            }
+        });
+    }
+}
+
+/// 发布写入数据的事件，数据中缺少的ID（如数据库自增生成的）会补回，便于订阅者获得完整的写入内容
+pub(crate) fn publish_write(
+    collection: &str,
+    op: ChangeOperation,
+    id: DataValue,
+    mut data: HashMap<String, DataValue>,
+) {
+    if !id.is_null() && !data.contains_key("id") && !data.contains_key("_id") {
+        data.insert("id".to_string(), id.clone());
+    }
+    publish(ChangeEvent {
+        table: collection.to_string(),
+        op,
+        id,
+        document: Some(DataValue::Object(data)),
+    });
+}
@@ -1,7 +1,7 @@
 
 //! # 全局ODM管理器和便捷函数
 
-use crate::error::QuickDbResult;
+use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
 use crate::odm::manager_core::AsyncOdmManager;
 use crate::odm::traits::OdmOperations;
@@ -40,6 +40,107 @@ pub async fn create(
     manager.create(collection, data, alias).await
 }
 
+/// 便捷函数：复制一条记录并应用修改，返回新记录的ID
+///
+/// 常用于"将文章复制为草稿"等场景，唯一字段需要在 `overrides` 中给出新值
+pub async fn duplicate(
+    collection: &str,
+    id: impl Into<DataValue>,
+    overrides: HashMap<String, DataValue>,
+    alias: Option<&str>,
+) -> QuickDbResult<DataValue> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.duplicate(collection, id.into(), overrides, alias).await
+}
+
+/// 便捷函数：创建记录，唯一字段冲突时用新生成的值重试
+///
+/// 用于生成不重复的 slug、用户名等，返回生成的ID和最终写入的唯一字段值
+pub async fn create_with_unique<G>(
+    collection: &str,
+    data: HashMap<String, DataValue>,
+    unique_field: &str,
+    generator: G,
+    alias: Option<&str>,
+) -> QuickDbResult<(DataValue, DataValue)>
+where
+    G: Fn(u32) -> DataValue + Send + Sync,
+{
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.create_with_unique(collection, data, unique_field, generator, alias).await
+}
+
+/// 便捷函数：批量创建记录，按输入顺序返回生成的ID
+///
+/// 【注意】这是一个内部函数，建议通过ModelManager的create_many方法进行操作
+#[doc(hidden)]
+pub async fn create_many(
+    collection: &str,
+    data_list: Vec<HashMap<String, DataValue>>,
+    alias: Option<&str>,
+) -> QuickDbResult<Vec<DataValue>> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.create_many(collection, data_list, alias).await
+}
+
+/// 批量插入或更新记录，按 `conflict_fields` 判断记录是否已存在，返回插入与更新的数量
+///
+/// 用于幂等的数据同步任务，详见 [`OdmOperations::upsert_many`]
+pub async fn upsert_many(
+    collection: &str,
+    rows: Vec<HashMap<String, DataValue>>,
+    conflict_fields: &[&str],
+    alias: Option<&str>,
+) -> QuickDbResult<UpsertResult> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.upsert_many(collection, rows, conflict_fields, alias).await
+}
+
+/// 插入或更新单条记录，按 `conflict_fields` 判断记录是否已存在，返回插入了新记录还是更新了已有记录
+///
+/// ```ignore
+/// let outcome = odm::upsert("users", data, &["email"], None).await?;
+/// if outcome.is_inserted() { /* 新用户 */ }
+/// ```
+pub async fn upsert(
+    collection: &str,
+    data: HashMap<String, DataValue>,
+    conflict_fields: &[&str],
+    alias: Option<&str>,
+) -> QuickDbResult<UpsertOutcome> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.upsert(collection, data, conflict_fields, alias).await
+}
+
+/// 在单个事务中加载一组初始数据
+///
+/// 表按引用关系排序后依次写入，任一记录失败时整体回滚，返回每张表插入的行数
+pub async fn seed(
+    fixtures: HashMap<String, Vec<HashMap<String, DataValue>>>,
+    alias: Option<&str>,
+) -> QuickDbResult<SeedReport> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.seed(fixtures, alias).await
+}
+
 
 /// 便捷函数：根据ID查询记录
 ///
@@ -48,14 +149,34 @@ pub async fn create(
 #[doc(hidden)]
 pub async fn find_by_id(
     collection: &str,
-    id: &str,
+    id: impl Into<DataValue>,
     alias: Option<&str>,
 ) -> QuickDbResult<Option<DataValue>> {
     // 锁定全局操作
     crate::lock_global_operations();
 
     let manager = get_odm_manager().await;
-    manager.find_by_id(collection, id, alias).await
+    manager.find_by_id(collection, id.into(), alias).await
+}
+
+/// 便捷函数：根据ID列表批量查找记录
+///
+/// 使用单条 `SELECT ... WHERE id IN (...)`（MongoDB为 `$in`）完成查询，启用缓存时先读取记录缓存，
+/// 只查询未命中的ID。不存在的ID不出现在结果中，重复的ID只返回一条记录。
+///
+/// `IN` 查询不保证结果顺序；`ordered` 为 `true` 时查询后在客户端按输入ID的顺序重排，
+/// 需要额外建立一次以ID为键的索引，结果较多时有相应的开销
+pub async fn find_by_ids(
+    collection: &str,
+    ids: Vec<DataValue>,
+    ordered: bool,
+    alias: Option<&str>,
+) -> QuickDbResult<Vec<DataValue>> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.find_by_ids(collection, ids, ordered, alias).await
 }
 
 /// 便捷函数：查询记录
@@ -130,6 +251,22 @@ pub async fn update_by_id(
     manager.update_by_id(collection, id, updates, alias).await
 }
 
+/// 便捷函数：根据ID更新记录，返回匹配数与修改数
+///
+/// 与`update_by_id`不同，可区分记录不存在（matched = 0）与内容未变化（modified = 0）
+pub async fn update_by_id_detailed(
+    collection: &str,
+    id: impl Into<DataValue>,
+    updates: HashMap<String, DataValue>,
+    alias: Option<&str>,
+) -> QuickDbResult<WriteResult> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.update_by_id_detailed(collection, id.into(), updates, alias).await
+}
+
 /// 便捷函数：使用操作数组更新记录
 ///
 /// 【注意】这是一个内部函数，建议通过ModelManager或模型的update_many_with_operations方法进行操作
@@ -182,6 +319,69 @@ pub async fn delete_by_id(
     manager.delete_by_id(collection, id, alias).await
 }
 
+/// 便捷函数：根据ID删除记录，返回匹配数与删除数
+pub async fn delete_by_id_detailed(
+    collection: &str,
+    id: impl Into<DataValue>,
+    alias: Option<&str>,
+) -> QuickDbResult<WriteResult> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.delete_by_id_detailed(collection, id.into(), alias).await
+}
+
+/// 便捷函数：根据ID列表批量删除记录
+///
+/// 使用单条 `DELETE ... WHERE id IN (...)`（MongoDB为 `deleteMany`）完成删除，返回删除数量
+pub async fn delete_by_ids(
+    collection: &str,
+    ids: Vec<DataValue>,
+    alias: Option<&str>,
+) -> QuickDbResult<u64> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.delete_by_ids(collection, ids, alias).await
+}
+
+/// 便捷函数：以流的方式读取记录的二进制列
+///
+/// 按 [`BLOB_CHUNK_SIZE`](crate::types::BLOB_CHUNK_SIZE) 分块读取；MongoDB配置了
+/// `gridfs_bucket` 时从 GridFS 读取，否则不支持
+pub async fn read_blob(
+    collection: &str,
+    id: DataValue,
+    column: &str,
+    alias: Option<&str>,
+) -> QuickDbResult<crate::odm::BlobReader> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.read_blob(collection, id, column, alias).await
+}
+
+/// 便捷函数：把读取器中的数据写入记录的二进制列，返回写入的字节数
+///
+/// SQL数据库每追加一块都会重写整列，写入的数据越大开销越高；
+/// MongoDB配置了 `gridfs_bucket` 时写入 GridFS，记录中不保存数据
+pub async fn write_blob<R: tokio::io::AsyncRead + Unpin + Send>(
+    collection: &str,
+    id: DataValue,
+    column: &str,
+    reader: &mut R,
+    alias: Option<&str>,
+) -> QuickDbResult<u64> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.write_blob(collection, id, column, reader, alias).await
+}
+
 /// 便捷函数：统计记录数量
 ///
 /// 【注意】这是一个内部函数，建议通过ModelManager或模型的count方法进行操作
@@ -199,6 +399,82 @@ pub async fn count(
     manager.count(collection, conditions, alias).await
 }
 
+/// 便捷函数：按指定方式统计记录数量
+///
+/// [`CountMode::Estimated`] 在没有条件时使用估算计数（MongoDB 读取集合元数据，不扫描文档），
+/// 适用于总数展示等允许近似值的场景；带条件时总是精确计数
+pub async fn count_with_mode(
+    collection: &str,
+    conditions: Vec<QueryCondition>,
+    mode: CountMode,
+    alias: Option<&str>,
+) -> QuickDbResult<u64> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.count_with_mode(collection, conditions, mode, alias).await
+}
+
+/// 便捷函数：按时间段统计记录数量
+///
+/// 用于"每天/每小时的数量"等时间序列汇总，例如 `time_bucket("orders", "created_at", Interval::Day, vec![], None)`
+/// 返回 `(当天零点, 当天订单数)` 的列表。时间按UTC截断，按周统计时每周从周一开始；
+/// 时间字段为空的记录和没有记录的时间段不出现在结果中。MongoDB 需要 5.0+（`$dateTrunc`）
+pub async fn time_bucket(
+    collection: &str,
+    field: &str,
+    interval: Interval,
+    conditions: Vec<QueryCondition>,
+    alias: Option<&str>,
+) -> QuickDbResult<Vec<(DataValue, u64)>> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.time_bucket(collection, field, interval, conditions, alias).await
+}
+
+/// 便捷函数：聚合统计
+///
+/// 例如按客户汇总订单金额：
+/// `aggregate("orders", vec![Aggregation::new(AggFunc::Sum, "amount", "total")], vec![], vec!["customer_id".to_string()], None)`
+/// 返回每个客户一行 `{customer_id, total}`，在数据库中完成计算，不把记录读入内存
+pub async fn aggregate(
+    collection: &str,
+    aggregations: Vec<Aggregation>,
+    conditions: Vec<QueryCondition>,
+    group_by: Vec<String>,
+    alias: Option<&str>,
+) -> QuickDbResult<Vec<DataValue>> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.aggregate(collection, aggregations, conditions, group_by, alias).await
+}
+
+/// 原样执行MongoDB聚合管道，仅MongoDB别名可用
+///
+/// ```ignore
+/// let rows = odm::mongo_aggregate("orders", serde_json::json!([
+///     { "$match": { "status": "paid" } },
+///     { "$lookup": { "from": "customers", "localField": "customer_id", "foreignField": "_id", "as": "customer" } },
+///     { "$unwind": "$customer" },
+/// ]), None).await?;
+/// ```
+pub async fn mongo_aggregate(
+    collection: &str,
+    pipeline_json: serde_json::Value,
+    alias: Option<&str>,
+) -> QuickDbResult<Vec<DataValue>> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.mongo_aggregate(collection, pipeline_json, alias).await
+}
+
 /// 便捷函数：检查记录是否存在
 ///
 /// 【注意】这是一个内部函数，建议通过ModelManager或模型的exists方法进行操作
@@ -225,6 +501,197 @@ pub async fn get_server_version(alias: Option<&str>) -> QuickDbResult<String> {
     manager.get_server_version(alias).await
 }
 
+/// 执行原始SQL查询，每行结果以 `DataValue::Object` 返回
+pub async fn query_raw(sql: &str, params: Vec<DataValue>, alias: Option<&str>) -> QuickDbResult<Vec<DataValue>> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.query_raw(alias, sql, params).await
+}
+
+/// 执行只读的原始SQL查询（窗口函数、CTE等），每行结果以 `DataValue::Object` 返回
+///
+/// 写入语句返回验证错误，请使用 [`execute_raw_update`]；MongoDB 别名返回不支持的错误
+///
+/// ```ignore
+/// let rows = odm::execute_raw(
+///     "SELECT username, RANK() OVER (ORDER BY age DESC) AS age_rank FROM users WHERE age > ?",
+///     vec![DataValue::Int(18)],
+///     None,
+/// ).await?;
+/// ```
+pub async fn execute_raw(sql: &str, params: Vec<DataValue>, alias: Option<&str>) -> QuickDbResult<Vec<DataValue>> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.execute_raw(alias, sql, params).await
+}
+
+/// 执行原始SQL写入语句（INSERT/UPDATE/DELETE等），返回受影响的行数
+///
+/// ```ignore
+/// let affected = odm::execute_raw_update("UPDATE users SET age = age + 1 WHERE age < ?", vec![DataValue::Int(18)], None).await?;
+/// ```
+pub async fn execute_raw_update(sql: &str, params: Vec<DataValue>, alias: Option<&str>) -> QuickDbResult<u64> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.execute_raw_update(alias, sql, params).await
+}
+
+/// 按顺序执行包含多条语句的SQL脚本，返回执行的语句数
+pub async fn execute_batch(sql: &str, alias: Option<&str>) -> QuickDbResult<u64> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.execute_batch(alias, sql).await
+}
+
+/// 执行返回单个值的查询，取第一行唯一一列并转换为目标类型
+///
+/// ```ignore
+/// let total: Option<i64> = odm::query_scalar("SELECT COUNT(*) FROM users WHERE age > ?", vec![DataValue::Int(18)], None).await?;
+/// ```
+pub async fn query_scalar<T: FromDataValue + Send + 'static>(
+    sql: &str,
+    params: Vec<DataValue>,
+    alias: Option<&str>,
+) -> QuickDbResult<Option<T>> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.query_scalar::<T>(alias, sql, params).await
+}
+
+/// 以流的形式逐条读取查询结果
+///
+/// 每次往返拉取 `fetch_size` 条记录，未指定时使用 [`default_fetch_size`](crate::odm::default_fetch_size)。
+/// 大批量导出可以调大以减少往返，内存受限的消费者可以调小。记录按页查询而不使用服务端游标，
+/// 不需要保持事务（PostgreSQL 的服务端游标必须在事务内使用，这里不涉及）；读取期间有写入时，
+/// 页边界可能跳过或重复记录，需要一致快照时应在只读副本或静止的数据上使用
+pub async fn find_stream(
+    collection: &str,
+    conditions: Vec<QueryCondition>,
+    options: Option<QueryOptions>,
+    fetch_size: Option<u64>,
+    alias: Option<&str>,
+) -> QuickDbResult<crate::odm::RecordStream> {
+    let fetch_size = match fetch_size {
+        Some(fetch_size) => fetch_size,
+        None => {
+            let alias = match alias {
+                Some(alias) => alias.to_string(),
+                None => crate::manager::get_global_pool_manager().get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string()),
+            };
+            let db_type = crate::manager::get_global_pool_manager().get_database_type(&alias)?;
+            crate::odm::default_fetch_size(db_type)
+        }
+    };
+
+    Ok(crate::odm::stream::page_stream(
+        collection.to_string(),
+        conditions,
+        options,
+        fetch_size,
+        alias.map(str::to_string),
+    ))
+}
+
+/// 按游标分页读取一页记录
+///
+/// 返回本页记录和下一页的游标值（本页最后一条记录的游标字段），把它传给
+/// [`CursorPagination::after`] 即可读取下一页。内部多读取一条记录判断是否还有下一页，
+/// 最后一页的 `next_cursor` 为 `None`。`options` 可指定字段选择等其他选项，
+/// 排序由游标字段决定，不能再设置 `pagination`
+pub async fn find_page(
+    collection: &str,
+    conditions: Vec<QueryCondition>,
+    cursor: CursorPagination,
+    options: Option<QueryOptions>,
+    alias: Option<&str>,
+) -> QuickDbResult<CursorPage<DataValue>> {
+    let field = cursor.field.clone();
+    let limit = cursor.limit;
+    let mut options = options.unwrap_or_default();
+    if !options.fields.is_empty() && !options.fields.contains(&field) {
+        options.fields.push(field.clone());
+    }
+    // 每页记录数为 0 时原样传递，由查询返回验证错误
+    let fetch_limit = if limit == 0 { 0 } else { limit.saturating_add(1) };
+    options.cursor = Some(CursorPagination { limit: fetch_limit, ..cursor });
+
+    let mut records = find(collection, conditions, Some(options), alias).await?;
+    if records.len() as u64 <= limit {
+        return Ok(CursorPage { records, next_cursor: None });
+    }
+
+    records.truncate(limit as usize);
+    let next_cursor = match records.last() {
+        Some(DataValue::Object(record)) => record.get(&field)
+            .or_else(|| if field == "id" { record.get("_id") } else { None })
+            .cloned(),
+        _ => None,
+    };
+    match next_cursor {
+        Some(next_cursor) => Ok(CursorPage { records, next_cursor: Some(next_cursor) }),
+        None => Err(QuickDbError::ValidationError {
+            field: field.clone(),
+            message: format!("查询结果缺少游标字段 {}，无法生成下一页游标", field),
+        }),
+    }
+}
+
+/// 订阅表/集合的数据变更
+///
+/// 返回的事件流独立于ODM后台任务运行，丢弃即取消订阅。
+/// 通过此接口收到的变更可用于跨进程缓存失效等场景
+pub async fn watch(collection: &str, alias: Option<&str>) -> QuickDbResult<ChangeStream> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.watch(collection, alias).await
+}
+
+/// 同步模型结构（建表、补充缺失的列、确保索引），返回本次执行的变更报告
+///
+/// 只执行增量变更，可在每次启动时调用
+pub async fn sync_model<T: crate::model::Model + 'static>(alias: Option<&str>) -> QuickDbResult<SchemaSyncReport> {
+    let manager = get_odm_manager().await;
+    manager.sync_model::<T>(alias).await
+}
+
+/// 开启显式事务
+///
+/// 返回的事务句柄独占一个连接，需调用 `commit` 提交，丢弃或 `rollback` 时回滚
+pub async fn transaction(alias: Option<&str>) -> QuickDbResult<crate::odm::Transaction> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.transaction(alias).await
+}
+
+/// 以指定隔离级别开启显式事务
+///
+/// `IsolationLevel::BackendDefault` 与 [`transaction`] 相同；MongoDB 不支持读未提交
+pub async fn transaction_with_isolation(
+    isolation: crate::transaction::IsolationLevel,
+    alias: Option<&str>,
+) -> QuickDbResult<crate::odm::Transaction> {
+    // 锁定全局操作
+    crate::lock_global_operations();
+
+    let manager = get_odm_manager().await;
+    manager.transaction_with_isolation(isolation, alias).await
+}
+
 /// 创建存储过程
 pub async fn create_stored_procedure(
     config: crate::stored_procedure::StoredProcedureConfig,
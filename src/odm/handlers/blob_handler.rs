@@ -0,0 +1,80 @@
+//! # 二进制流处理器
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::*;
+use crate::manager::get_global_pool_manager;
+use crate::odm::manager_core::AsyncOdmManager;
+use crate::pool::DatabaseOperation;
+use rat_logger::debug;
+use tokio::sync::oneshot;
+
+impl AsyncOdmManager {
+    /// 处理读取二进制片段请求
+    #[doc(hidden)]
+    pub async fn handle_read_blob_chunk(
+        location: BlobLocation,
+        offset: u64,
+        len: u64,
+        alias: Option<String>,
+    ) -> QuickDbResult<Option<Vec<u8>>> {
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::ReadBlobChunk {
+            location,
+            offset,
+            len,
+            response: response_tx,
+        };
+        Self::dispatch_blob_operation(operation, response_rx, alias).await
+    }
+
+    /// 处理写入二进制片段请求
+    #[doc(hidden)]
+    pub async fn handle_write_blob_chunk(
+        location: BlobLocation,
+        data: Vec<u8>,
+        append: bool,
+        alias: Option<String>,
+    ) -> QuickDbResult<bool> {
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::WriteBlobChunk {
+            location,
+            data,
+            append,
+            response: response_tx,
+        };
+        Self::dispatch_blob_operation(operation, response_rx, alias).await
+    }
+
+    /// 把二进制片段操作发送到别名对应的连接池并等待结果
+    async fn dispatch_blob_operation<T>(
+        operation: DatabaseOperation,
+        response_rx: oneshot::Receiver<QuickDbResult<T>>,
+        alias: Option<String>,
+    ) -> QuickDbResult<T> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理二进制片段请求: alias={}", actual_alias);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })?
+    }
+}
@@ -4,6 +4,7 @@ use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
 use crate::manager::get_global_pool_manager;
 use crate::odm::manager_core::AsyncOdmManager;
+use super::extract_created_id;
 use rat_logger::{debug, info, warn, error};
 use tokio::sync::oneshot;
 use std::collections::HashMap;
@@ -26,6 +27,7 @@ impl AsyncOdmManager {
             }
         };
         debug!("处理创建请求: collection={}, alias={}", collection, actual_alias);
+        manager.ensure_writable(&actual_alias)?;
 
     
         // 确保表和索引存在（基于注册的模型元数据）
@@ -48,72 +50,27 @@ impl AsyncOdmManager {
         let mut processed_data = data.clone();
 
         if let Ok(id_generator) = manager.get_id_generator(&actual_alias) {
-            match id_generator.strategy() {
-                crate::types::IdStrategy::AutoIncrement => {
-                    // AutoIncrement策略：移除用户传入的id字段，让数据库自动生成
-                    debug!("AutoIncrement策略，移除id字段让数据库自动生成");
-                    processed_data.remove("id");
-                    processed_data.remove("_id");
-                },
-                _ => {
-                    // 检查是否有有效的ID字段（非空、非零）
-                    let id_is_valid = match processed_data.get("id") {
-                        Some(crate::types::DataValue::String(s)) => {
-                            !s.is_empty()
-                        },
-                        Some(crate::types::DataValue::Int(i)) => {
-                            *i > 0
-                        },
-                        Some(crate::types::DataValue::Null) => {
-                            false
-                        },
-                        Some(_) => {
-                            true // 其他非空类型认为是有效ID
-                        },
-                        None => {
-                            false
-                        },
-                    };
-                    let _id_is_valid = match processed_data.get("_id") {
-                        Some(crate::types::DataValue::String(s)) => !s.is_empty(),
-                        Some(crate::types::DataValue::Int(i)) => *i > 0,
-                        Some(crate::types::DataValue::Null) => false,
-                        Some(_) => true, // 其他非空类型认为是有效ID
-                        None => false,
-                    };
-                    let has_valid_id = id_is_valid || _id_is_valid;
-
-                    if !has_valid_id {
-                        debug!("数据中没有有效ID字段，使用IdGenerator生成ID");
-                        match id_generator.generate().await {
-                            Ok(id_type) => {
-                                let id_value = match &id_type {
-                                    crate::types::IdType::Number(n) => DataValue::Int(*n),
-                                    crate::types::IdType::String(s) => DataValue::String(s.clone()),
-                                };
-                                // 根据数据库类型决定使用"id"还是"_id"字段
-                                match connection_pool.db_config.db_type {
-                                    crate::types::DatabaseType::MongoDB => {
-                                        debug!("为MongoDB生成_id字段");
-                                        processed_data.insert("_id".to_string(), id_value);
-                                    },
-                                    _ => {
-                                        debug!("为SQL数据库生成id字段");
-                                        processed_data.insert("id".to_string(), id_value);
-                                    }
-                                }
-                            },
-                            Err(e) => {
-                                return Err(QuickDbError::Other(e));
-                            }
-                        }
-                    }
-                }
-            }
+            // 按策略填充ID：自增由数据库生成，其他策略在ID为空时由ODM生成
+            debug!("按ID策略填充ID字段: {:?}", id_generator.strategy());
+            id_generator.populate_id(collection, &actual_alias, &mut processed_data).await
+                .map_err(QuickDbError::Other)?;
         } else {
             warn!("获取IdGenerator失败，使用原始数据");
         }
 
+        // 开启写后缓冲时只入队，由后台任务批量写入
+        if let Some(write_behind) = connection_pool.db_config.write_behind {
+            let id = processed_data.get("id")
+                .or_else(|| processed_data.get("_id"))
+                .cloned()
+                .unwrap_or(DataValue::Null);
+            crate::odm::write_behind::enqueue_write_behind(&actual_alias, write_behind, collection, processed_data);
+            return Ok(id);
+        }
+
+        // 有变更事件订阅者时保留写入数据用于发布
+        let event_data = crate::odm::events::has_subscribers(collection, &connection_pool.db_config).then(|| processed_data.clone());
+
         // 创建oneshot通道用于接收响应
         let (response_tx, response_rx) = oneshot::channel();
 
@@ -126,7 +83,7 @@ impl AsyncOdmManager {
             response: response_tx,
         };
         
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -137,22 +94,356 @@ impl AsyncOdmManager {
                 message: "等待连接池响应超时".to_string(),
             })??;
         
-        // 从返回的Object中提取id字段
-        match result {
-            DataValue::Object(map) => {
-                // 优先查找"id"字段（SQL数据库），如果没有则查找"_id"字段（MongoDB）
-                if let Some(id_value) = map.get("id") {
-                    Ok(id_value.clone())
-                } else if let Some(id_value) = map.get("_id") {
-                    Ok(id_value.clone())
-                } else {
-                    Err(QuickDbError::QueryError {
-                        message: "创建操作返回的数据中缺少id字段".to_string(),
-                    })
+        let id = extract_created_id(result)?;
+        if let Some(data) = event_data {
+            crate::odm::events::publish_write(collection, ChangeOperation::Insert, id.clone(), data);
+        }
+        Ok(id)
+    }
+
+    /// 处理批量创建请求
+    ///
+    /// 表检查只执行一次，ID按策略逐条填充后以单个连接池操作提交
+    #[doc(hidden)]
+    pub async fn handle_create_many(
+        collection: &str,
+        data_list: Vec<HashMap<String, DataValue>>,
+        alias: Option<String>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if data_list.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理批量创建请求: collection={}, alias={}, count={}", collection, actual_alias, data_list.len());
+        manager.ensure_writable(&actual_alias)?;
+
+        if let Err(e) = manager.ensure_table_and_indexes(collection, &actual_alias).await {
+            debug!("自动创建表和索引失败: {}", e);
+        }
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let id_strategy = connection_pool.db_config.id_strategy.clone();
+
+        let mut processed_list = data_list;
+        if let Ok(id_generator) = manager.get_id_generator(&actual_alias) {
+            for data in processed_list.iter_mut() {
+                id_generator.populate_id(collection, &actual_alias, data).await
+                    .map_err(QuickDbError::Other)?;
+            }
+        } else {
+            warn!("获取IdGenerator失败，使用原始数据");
+        }
+
+        let event_data = crate::odm::events::has_subscribers(collection, &connection_pool.db_config).then(|| processed_list.clone());
+
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let operation = crate::pool::DatabaseOperation::CreateMany {
+            table: collection.to_string(),
+            data_list: processed_list,
+            id_strategy,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        let results = response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })??;
+
+        let ids = results.into_iter().map(extract_created_id).collect::<QuickDbResult<Vec<_>>>()?;
+        if let Some(data_list) = event_data {
+            for (id, data) in ids.iter().zip(data_list) {
+                crate::odm::events::publish_write(collection, ChangeOperation::Insert, id.clone(), data);
+            }
+        }
+        Ok(ids)
+    }
+
+    /// 处理批量插入或更新请求
+    ///
+    /// 与批量创建相同，表检查只执行一次、ID按策略逐条填充，整批以单个连接池操作提交
+    #[doc(hidden)]
+    pub async fn handle_upsert_many(
+        collection: &str,
+        rows: Vec<HashMap<String, DataValue>>,
+        conflict_fields: Vec<String>,
+        alias: Option<String>,
+    ) -> QuickDbResult<UpsertResult> {
+        if conflict_fields.is_empty() {
+            return Err(QuickDbError::ValidationError {
+                field: "conflict_fields".to_string(),
+                message: "批量插入或更新必须指定至少一个冲突字段".to_string(),
+            });
+        }
+        if rows.is_empty() {
+            return Ok(UpsertResult::default());
+        }
+
+        // ID 在填充后统一以 "id" 为键，冲突字段与之保持一致
+        let conflict_fields: Vec<String> = conflict_fields
+            .into_iter()
+            .map(|field| if field == "_id" { "id".to_string() } else { field })
+            .collect();
+
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理批量插入或更新请求: collection={}, alias={}, count={}, conflict_fields={:?}", collection, actual_alias, rows.len(), conflict_fields);
+        manager.ensure_writable(&actual_alias)?;
+
+        if let Err(e) = manager.ensure_table_and_indexes(collection, &actual_alias).await {
+            debug!("自动创建表和索引失败: {}", e);
+        }
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let id_strategy = connection_pool.db_config.id_strategy.clone();
+
+        let mut processed_rows = rows;
+        if let Ok(id_generator) = manager.get_id_generator(&actual_alias) {
+            for data in processed_rows.iter_mut() {
+                id_generator.populate_id(collection, &actual_alias, data).await
+                    .map_err(QuickDbError::Other)?;
+            }
+        } else {
+            warn!("获取IdGenerator失败，使用原始数据");
+        }
+
+        // PostgreSQL 拒绝在一条语句中两次更新同一行，各数据库统一在提交前检查批内重复
+        let mut seen_keys = std::collections::HashSet::new();
+        for (index, row) in processed_rows.iter().enumerate() {
+            let key: Vec<String> = conflict_fields.iter()
+                .map(|field| row.get(field).map(|value| value.to_json_value().to_string()).unwrap_or_default())
+                .collect();
+            if !seen_keys.insert(key) {
+                return Err(QuickDbError::ValidationError {
+                    field: "conflict_fields".to_string(),
+                    message: format!("第 {} 条记录的冲突字段值与前面的记录重复", index + 1),
+                });
+            }
+        }
+
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let operation = crate::pool::DatabaseOperation::UpsertMany {
+            table: collection.to_string(),
+            rows: processed_rows,
+            conflict_fields,
+            id_strategy,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })?
+    }
+
+    /// 处理单条插入或更新请求
+    #[doc(hidden)]
+    pub async fn handle_upsert(
+        collection: &str,
+        data: HashMap<String, DataValue>,
+        conflict_fields: Vec<String>,
+        alias: Option<String>,
+    ) -> QuickDbResult<UpsertOutcome> {
+        if conflict_fields.is_empty() {
+            return Err(QuickDbError::ValidationError {
+                field: "conflict_fields".to_string(),
+                message: "插入或更新必须指定至少一个冲突字段".to_string(),
+            });
+        }
+
+        // ID 在填充后统一以 "id" 为键，冲突字段与之保持一致
+        let conflict_fields: Vec<String> = conflict_fields
+            .into_iter()
+            .map(|field| if field == "_id" { "id".to_string() } else { field })
+            .collect();
+
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理插入或更新请求: collection={}, alias={}, conflict_fields={:?}", collection, actual_alias, conflict_fields);
+        manager.ensure_writable(&actual_alias)?;
+
+        if let Err(e) = manager.ensure_table_and_indexes(collection, &actual_alias).await {
+            debug!("自动创建表和索引失败: {}", e);
+        }
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let id_strategy = connection_pool.db_config.id_strategy.clone();
+
+        let mut processed_data = data;
+        if let Ok(id_generator) = manager.get_id_generator(&actual_alias) {
+            id_generator.populate_id(collection, &actual_alias, &mut processed_data).await
+                .map_err(QuickDbError::Other)?;
+        } else {
+            warn!("获取IdGenerator失败，使用原始数据");
+        }
+
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let operation = crate::pool::DatabaseOperation::Upsert {
+            table: collection.to_string(),
+            data: processed_data,
+            conflict_fields,
+            id_strategy,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })?
+    }
+}
+
+impl AsyncOdmManager {
+    /// 处理初始数据加载请求
+    ///
+    /// 按引用关系排序、建表和填充ID都在事务外完成，随后以单个连接池操作在一个事务中写入全部记录
+    #[doc(hidden)]
+    pub async fn handle_seed(
+        fixtures: HashMap<String, Vec<HashMap<String, DataValue>>>,
+        alias: Option<String>,
+    ) -> QuickDbResult<SeedReport> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+
+        manager.ensure_writable(&actual_alias)?;
+
+        let tables: Vec<String> = fixtures.keys().cloned().collect();
+        let order = crate::types::seed::dependency_order(&tables, |table| {
+            manager.get_model_with_alias(table, &actual_alias)
+                .or_else(|| crate::manager::get_model(table))
+                .map(|meta| referenced_collections(&meta))
+                .unwrap_or_default()
+        })?;
+        debug!("处理初始数据加载请求: alias={}, 写入顺序={:?}", actual_alias, order);
+
+        // 建表包含DDL，必须在开启事务前完成
+        for table in &order {
+            if let Err(e) = manager.ensure_table_and_indexes(table, &actual_alias).await {
+                debug!("自动创建表和索引失败: table={}, error={}", table, e);
+            }
+        }
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let id_strategy = connection_pool.db_config.id_strategy.clone();
+        let id_generator = manager.get_id_generator(&actual_alias).ok();
+        if id_generator.is_none() {
+            warn!("获取IdGenerator失败，使用原始数据");
+        }
+
+        let mut fixtures = fixtures;
+        let mut ordered = Vec::with_capacity(order.len());
+        for table in order {
+            let mut records = fixtures.remove(&table).unwrap_or_default();
+            if let Some(id_generator) = &id_generator {
+                for data in records.iter_mut() {
+                    id_generator.populate_id(&table, &actual_alias, data).await
+                        .map_err(QuickDbError::Other)?;
                 }
-            },
-            // 如果返回的不是Object，可能是其他数据库的直接ID值，直接返回
-            other => Ok(other),
+            }
+            ordered.push((table, records));
         }
+
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let operation = crate::pool::DatabaseOperation::Seed {
+            fixtures: ordered,
+            id_strategy,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        let inserted = response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })??;
+
+        info!("初始数据加载完成: alias={}, 表数量={}", actual_alias, inserted.len());
+        Ok(SeedReport {
+            alias: actual_alias,
+            inserted,
+        })
     }
 }
+
+/// 模型中引用字段（含引用数组）指向的集合
+fn referenced_collections(meta: &crate::model::ModelMeta) -> Vec<String> {
+    use crate::model::FieldType;
+    meta.fields
+        .values()
+        .filter_map(|field| match &field.field_type {
+            FieldType::Reference { target_collection } => Some(target_collection.clone()),
+            FieldType::Array { item_type, .. } => match item_type.as_ref() {
+                FieldType::Reference { target_collection } => Some(target_collection.clone()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
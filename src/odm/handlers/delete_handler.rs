@@ -43,7 +43,7 @@ impl AsyncOdmManager {
             response: response_tx,
         };
         
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -54,6 +54,9 @@ impl AsyncOdmManager {
                 message: "等待连接池响应超时".to_string(),
             })??;
         
+        if affected_rows > 0 {
+            publish_delete(collection, &connection_pool.db_config, DataValue::Null);
+        }
         Ok(affected_rows)
     }
     
@@ -91,7 +94,7 @@ impl AsyncOdmManager {
             response: response_tx,
         };
         
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -102,14 +105,120 @@ impl AsyncOdmManager {
                 message: "等待连接池响应超时".to_string(),
             })??;
         
+        if result {
+            publish_delete(collection, &connection_pool.db_config, DataValue::String(id.to_string()));
+        }
         Ok(result)
     }
+
+    /// 处理根据ID删除请求，返回匹配数与删除数
+    #[doc(hidden)]
+    pub async fn handle_delete_by_id_detailed(
+        collection: &str,
+        id: DataValue,
+        alias: Option<String>,
+    ) -> QuickDbResult<WriteResult> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理根据ID删除请求(详细结果): collection={}, id={}, alias={}", collection, id, actual_alias);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let operation = DatabaseOperation::DeleteByIdDetailed {
+            table: collection.to_string(),
+            id: id.clone(),
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        let result = response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })??;
+
+        if result.modified > 0 {
+            publish_delete(collection, &connection_pool.db_config, id);
+        }
+        Ok(result)
+    }
+
+    /// 处理根据ID列表批量删除请求
+    #[doc(hidden)]
+    pub async fn handle_delete_by_ids(
+        collection: &str,
+        ids: Vec<DataValue>,
+        alias: Option<String>,
+    ) -> QuickDbResult<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理批量ID删除请求: collection={}, ids={}, alias={}", collection, ids.len(), actual_alias);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let event_ids = crate::odm::events::has_subscribers(collection, &connection_pool.db_config).then(|| ids.clone());
+
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::DeleteByIds {
+            table: collection.to_string(),
+            ids,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        let deleted = response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })??;
+
+        // 无法得知哪些ID实际存在，只要有删除就为每个请求的ID发布事件
+        if let Some(ids) = event_ids.filter(|_| deleted > 0) {
+            for id in ids {
+                publish_delete(collection, &connection_pool.db_config, id);
+            }
+        }
+        Ok(deleted)
+    }
     
     /// 处理计数请求
     #[doc(hidden)]
     pub async fn handle_count(
         collection: &str,
         conditions: Vec<QueryCondition>,
+        mode: CountMode,
         alias: Option<String>,
     ) -> QuickDbResult<u64> {
         let manager = get_global_pool_manager();
@@ -120,7 +229,7 @@ impl AsyncOdmManager {
                     .unwrap_or_else(|| "default".to_string())
             }
         };
-        debug!("处理计数请求: collection={}, alias={}", collection, actual_alias);
+        debug!("处理计数请求: collection={}, mode={:?}, alias={}", collection, mode, actual_alias);
         
         let manager = get_global_pool_manager();
         let connection_pools = manager.get_connection_pools();
@@ -136,10 +245,11 @@ impl AsyncOdmManager {
         let operation = DatabaseOperation::Count {
             table: collection.to_string(),
             conditions,
+            mode,
             response: response_tx,
         };
         
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -153,6 +263,144 @@ impl AsyncOdmManager {
         Ok(count)
     }
     
+    /// 处理按时间段计数请求
+    #[doc(hidden)]
+    pub async fn handle_time_bucket(
+        collection: &str,
+        field: &str,
+        interval: Interval,
+        conditions: Vec<QueryCondition>,
+        alias: Option<String>,
+    ) -> QuickDbResult<Vec<(DataValue, u64)>> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理按时间段计数请求: collection={}, field={}, interval={:?}, alias={}", collection, field, interval, actual_alias);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::TimeBucket {
+            table: collection.to_string(),
+            field: field.to_string(),
+            interval,
+            conditions,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })?
+    }
+
+    /// 处理聚合统计请求
+    #[doc(hidden)]
+    pub async fn handle_aggregate(
+        collection: &str,
+        aggregations: Vec<Aggregation>,
+        conditions: Vec<QueryCondition>,
+        group_by: Vec<String>,
+        alias: Option<String>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理聚合统计请求: collection={}, aggregations={:?}, group_by={:?}, alias={}", collection, aggregations, group_by, actual_alias);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::Aggregate {
+            table: collection.to_string(),
+            aggregations,
+            conditions,
+            group_by,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })?
+    }
+
+    /// 处理MongoDB聚合管道请求
+    #[doc(hidden)]
+    pub async fn handle_mongo_aggregate(
+        collection: &str,
+        pipeline: serde_json::Value,
+        alias: Option<String>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let serde_json::Value::Array(pipeline) = pipeline else {
+            return Err(QuickDbError::ValidationError {
+                field: "pipeline".to_string(),
+                message: "聚合管道必须是由阶段对象组成的JSON数组".to_string(),
+            });
+        };
+
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理MongoDB聚合管道请求: collection={}, stages={}, alias={}", collection, pipeline.len(), actual_alias);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::MongoAggregate {
+            table: collection.to_string(),
+            pipeline,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })?
+    }
+
     /// 处理存在性检查请求
     #[doc(hidden)]
     pub async fn handle_exists(
@@ -185,7 +433,7 @@ impl AsyncOdmManager {
             response: response_tx,
         };
 
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -224,7 +472,7 @@ impl AsyncOdmManager {
             response: response_tx,
         };
 
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -237,3 +485,15 @@ impl AsyncOdmManager {
         Ok(result)
     }
 }
+
+/// 有订阅者且别名非dry_run时发布删除事件
+fn publish_delete(collection: &str, db_config: &DatabaseConfig, id: DataValue) {
+    if crate::odm::events::has_subscribers(collection, db_config) {
+        crate::odm::events::publish(ChangeEvent {
+            table: collection.to_string(),
+            op: ChangeOperation::Delete,
+            id,
+            document: None,
+        });
+    }
+}
@@ -7,10 +7,36 @@ pub mod read_handler;
 pub mod update_handler;
 pub mod delete_handler;
 pub mod stored_procedure_handler;
+pub mod schema_handler;
+pub mod blob_handler;
 
 // 重新导出所有处理器以保持API兼容性
 pub use create_handler::*;
 pub use read_handler::*;
 pub use update_handler::*;
 pub use delete_handler::*;
-pub use stored_procedure_handler::*;
\ No newline at end of file
+pub use stored_procedure_handler::*;
+pub use schema_handler::*;
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::DataValue;
+
+/// 从创建结果中提取ID
+pub(crate) fn extract_created_id(result: DataValue) -> QuickDbResult<DataValue> {
+    match result {
+        DataValue::Object(map) => {
+            // 优先查找"id"字段（SQL数据库），如果没有则查找"_id"字段（MongoDB）
+            if let Some(id_value) = map.get("id") {
+                Ok(id_value.clone())
+            } else if let Some(id_value) = map.get("_id") {
+                Ok(id_value.clone())
+            } else {
+                Err(QuickDbError::QueryError {
+                    message: "创建操作返回的数据中缺少id字段".to_string(),
+                })
+            }
+        },
+        // 如果返回的不是Object，可能是其他数据库的直接ID值，直接返回
+        other => Ok(other),
+    }
+}
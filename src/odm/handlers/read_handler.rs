@@ -13,7 +13,7 @@ impl AsyncOdmManager {
     #[doc(hidden)]
     pub async fn handle_find_by_id(
         collection: &str,
-        id: &str,
+        id: DataValue,
         alias: Option<String>,
     ) -> QuickDbResult<Option<DataValue>> {
         let manager = get_global_pool_manager();
@@ -39,11 +39,11 @@ impl AsyncOdmManager {
         // 发送DatabaseOperation::FindById请求到连接池
         let operation = DatabaseOperation::FindById {
             table: collection.to_string(),
-            id: DataValue::String(id.to_string()),
+            id,
             response: response_tx,
         };
         
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -55,6 +55,66 @@ impl AsyncOdmManager {
             })?
     }
     
+    /// 处理根据ID列表批量查询请求
+    #[doc(hidden)]
+    pub async fn handle_find_by_ids(
+        collection: &str,
+        ids: Vec<DataValue>,
+        ordered: bool,
+        alias: Option<String>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids = dedup_ids(ids);
+        let order = ordered.then(|| ids.clone());
+
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理批量ID查询请求: collection={}, ids={}, alias={}", collection, ids.len(), actual_alias);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::FindByIds {
+            table: collection.to_string(),
+            ids,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        let records = response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })??;
+
+        let Some(order) = order else {
+            return Ok(records);
+        };
+        // 按输入顺序在客户端重排，未找到的ID直接跳过
+        let mut by_id: std::collections::HashMap<String, DataValue> = records
+            .into_iter()
+            .filter_map(|record| Some((record_id_key(&record)?, record)))
+            .collect();
+        Ok(order.iter()
+            .filter_map(|id| by_id.remove(&id_key(id)?))
+            .collect())
+    }
+
     /// 处理查询请求
     #[doc(hidden)]
     pub async fn handle_find(
@@ -83,15 +143,21 @@ impl AsyncOdmManager {
         // 创建oneshot通道用于接收响应
         let (response_tx, response_rx) = oneshot::channel();
         
+        let mut conditions = conditions;
+        let mut options = options.unwrap_or_default();
+        if let Some(cursor_condition) = options.resolve_cursor()? {
+            conditions.push(cursor_condition);
+        }
+
         // 发送DatabaseOperation::Find请求到连接池
         let operation = DatabaseOperation::Find {
             table: collection.to_string(),
             conditions,
-            options: options.unwrap_or_default(),
+            options,
             response: response_tx,
         };
         
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -132,14 +198,20 @@ impl AsyncOdmManager {
         let (response_tx, response_rx) = oneshot::channel();
         
         // 发送DatabaseOperation::FindWithGroups请求到连接池
+        let mut condition_groups = condition_groups;
+        let mut options = options.unwrap_or_default();
+        if let Some(cursor_condition) = options.resolve_cursor()? {
+            condition_groups.push(QueryConditionGroup::Single(cursor_condition));
+        }
+
         let operation = DatabaseOperation::FindWithGroups {
             table: collection.to_string(),
             condition_groups,
-            options: options.unwrap_or_default(),
+            options,
             response: response_tx,
         };
         
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -150,4 +222,105 @@ impl AsyncOdmManager {
                 message: "等待连接池响应超时".to_string(),
             })?
     }
+
+    /// 处理变更订阅请求
+    #[doc(hidden)]
+    pub async fn handle_watch(
+        collection: &str,
+        alias: Option<String>,
+    ) -> QuickDbResult<ChangeStream> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理变更订阅请求: collection={}, alias={}", collection, actual_alias);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::Watch {
+            table: collection.to_string(),
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })?
+    }
+
+    /// 处理原始SQL查询请求
+    #[doc(hidden)]
+    pub async fn handle_query_raw(
+        sql: String,
+        params: Vec<DataValue>,
+        alias: Option<String>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理原始查询请求: alias={}, sql={}", actual_alias, sql);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::QueryRaw {
+            sql,
+            params,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })?
+    }
+}
+
+/// ID的比较键，数字和字符串形式的同一ID得到相同的键
+fn id_key(id: &DataValue) -> Option<String> {
+    IdType::from_data_value(id).map(|id| id.to_string())
+}
+
+/// 记录中ID字段的比较键
+fn record_id_key(record: &DataValue) -> Option<String> {
+    match record {
+        DataValue::Object(map) => id_key(map.get("id").or_else(|| map.get("_id"))?),
+        _ => None,
+    }
+}
+
+/// 去除重复的ID，保留首次出现的位置
+fn dedup_ids(ids: Vec<DataValue>) -> Vec<DataValue> {
+    let mut seen = std::collections::HashSet::new();
+    ids.into_iter()
+        .filter(|id| id_key(id).map_or(true, |key| seen.insert(key)))
+        .collect()
 }
@@ -0,0 +1,130 @@
+//! # 模型结构同步与表结构变更处理器
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::*;
+use crate::manager::get_global_pool_manager;
+use crate::model::ModelMeta;
+use crate::pool::DatabaseOperation;
+use crate::odm::manager_core::AsyncOdmManager;
+use rat_logger::debug;
+use tokio::sync::oneshot;
+
+impl AsyncOdmManager {
+    /// 处理模型结构同步请求
+    #[doc(hidden)]
+    pub async fn handle_sync_model(
+        model_meta: ModelMeta,
+        alias: Option<String>,
+    ) -> QuickDbResult<SchemaSyncReport> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias.or_else(|| model_meta.database_alias.clone()) {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理模型结构同步请求: collection={}, alias={}", model_meta.collection_name, actual_alias);
+
+        manager.sync_model_schema(model_meta, &actual_alias).await
+    }
+
+    /// 处理重命名表请求
+    #[doc(hidden)]
+    pub async fn handle_rename_table(
+        from: String,
+        to: String,
+        alias: Option<String>,
+    ) -> QuickDbResult<()> {
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                get_global_pool_manager().get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理重命名表请求: {} -> {}, alias={}", from, to, actual_alias);
+
+        crate::manager::rename_table(&actual_alias, &from, &to).await
+    }
+
+    /// 处理SQL脚本执行请求
+    #[doc(hidden)]
+    pub async fn handle_execute_batch(
+        sql: String,
+        alias: Option<String>,
+    ) -> QuickDbResult<u64> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理SQL脚本执行请求: alias={}", actual_alias);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::ExecuteBatch {
+            sql,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })?
+    }
+
+    /// 处理原始SQL写入请求
+    #[doc(hidden)]
+    pub async fn handle_execute_raw_update(
+        sql: String,
+        params: Vec<DataValue>,
+        alias: Option<String>,
+    ) -> QuickDbResult<u64> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理原始SQL写入请求: alias={}, sql={}", actual_alias, sql);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let (response_tx, response_rx) = oneshot::channel();
+        let operation = DatabaseOperation::ExecuteRawUpdate {
+            sql,
+            params,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })?
+    }
+}
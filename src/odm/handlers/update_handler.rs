@@ -38,6 +38,8 @@ impl AsyncOdmManager {
         // 创建oneshot通道用于接收响应
         let (response_tx, response_rx) = oneshot::channel();
         
+        let event_data = crate::odm::events::has_subscribers(collection, &connection_pool.db_config).then(|| updates.clone());
+
         // 发送DatabaseOperation::Update请求到连接池
         let operation = DatabaseOperation::Update {
             table: collection.to_string(),
@@ -46,7 +48,7 @@ impl AsyncOdmManager {
             response: response_tx,
         };
         
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -57,6 +59,9 @@ impl AsyncOdmManager {
                 message: "等待连接池响应超时".to_string(),
             })??;
         
+        if let Some(data) = event_data.filter(|_| affected_rows > 0) {
+            crate::odm::events::publish_write(collection, ChangeOperation::Update, DataValue::Null, data);
+        }
         Ok(affected_rows)
     }
 
@@ -96,7 +101,7 @@ impl AsyncOdmManager {
             response: response_tx,
         };
 
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -107,6 +112,15 @@ impl AsyncOdmManager {
                 message: "等待连接池响应超时".to_string(),
             })??;
 
+        // 操作数组的结果值取决于数据库中的原值，事件不携带数据
+        if affected_rows > 0 && crate::odm::events::has_subscribers(collection, &connection_pool.db_config) {
+            crate::odm::events::publish(ChangeEvent {
+                table: collection.to_string(),
+                op: ChangeOperation::Update,
+                id: DataValue::Null,
+                document: None,
+            });
+        }
         Ok(affected_rows)
     }
 
@@ -138,6 +152,8 @@ impl AsyncOdmManager {
         // 创建oneshot通道用于接收响应
         let (response_tx, response_rx) = oneshot::channel();
         
+        let event_data = crate::odm::events::has_subscribers(collection, &connection_pool.db_config).then(|| updates.clone());
+
         // 发送DatabaseOperation::UpdateById请求到连接池
         let operation = DatabaseOperation::UpdateById {
             table: collection.to_string(),
@@ -146,7 +162,7 @@ impl AsyncOdmManager {
             response: response_tx,
         };
         
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -157,6 +173,60 @@ impl AsyncOdmManager {
                 message: "等待连接池响应超时".to_string(),
             })??;
         
+        if let Some(data) = event_data.filter(|_| result) {
+            crate::odm::events::publish_write(collection, ChangeOperation::Update, DataValue::String(id.to_string()), data);
+        }
+        Ok(result)
+    }
+
+    /// 处理根据ID更新请求，返回匹配数与修改数
+    #[doc(hidden)]
+    pub async fn handle_update_by_id_detailed(
+        collection: &str,
+        id: DataValue,
+        updates: HashMap<String, DataValue>,
+        alias: Option<String>,
+    ) -> QuickDbResult<WriteResult> {
+        let manager = get_global_pool_manager();
+        let actual_alias = match alias {
+            Some(a) => a,
+            None => {
+                manager.get_default_alias().await
+                    .unwrap_or_else(|| "default".to_string())
+            }
+        };
+        debug!("处理根据ID更新请求(详细结果): collection={}, id={}, alias={}", collection, id, actual_alias);
+
+        let connection_pools = manager.get_connection_pools();
+        let connection_pool = connection_pools.get(&actual_alias)
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: actual_alias.clone(),
+            })?;
+
+        let event_data = crate::odm::events::has_subscribers(collection, &connection_pool.db_config).then(|| (id.clone(), updates.clone()));
+
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let operation = DatabaseOperation::UpdateByIdDetailed {
+            table: collection.to_string(),
+            id,
+            data: updates,
+            response: response_tx,
+        };
+
+        connection_pool.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "连接池操作通道已关闭".to_string(),
+            })?;
+
+        let result = response_rx.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "等待连接池响应超时".to_string(),
+            })??;
+
+        if let Some((id, data)) = event_data.filter(|_| result.modified > 0) {
+            crate::odm::events::publish_write(collection, ChangeOperation::Update, id, data);
+        }
         Ok(result)
     }
 }
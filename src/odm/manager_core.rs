@@ -21,10 +21,15 @@ pub struct AsyncOdmManager {
 impl AsyncOdmManager {
     /// 创建新的异步ODM管理器
     pub fn new() -> Self {
+        Self::new_on(&tokio::runtime::Handle::current())
+    }
+
+    /// 在指定运行时上创建异步ODM管理器，后台任务的生命周期跟随该运行时
+    pub(crate) fn new_on(runtime: &tokio::runtime::Handle) -> Self {
         let (sender, receiver) = mpsc::unbounded_channel();
         
         // 启动后台处理任务
-        let task_handle = tokio::spawn(Self::process_requests(receiver));
+        let task_handle = runtime.spawn(Self::process_requests(receiver));
         
         info!("创建异步ODM管理器");
         
@@ -51,69 +56,169 @@ impl AsyncOdmManager {
         info!("启动ODM后台处理任务");
         
         while let Some(request) = receiver.recv().await {
-            match request {
-                OdmRequest::Create { collection, data, alias, response } => {
-                    let result = Self::handle_create(&collection, data, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::FindById { collection, id, alias, response } => {
-                    let result = Self::handle_find_by_id(&collection, &id, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::Find { collection, conditions, options, alias, response } => {
-                    let result = Self::handle_find(&collection, conditions, options, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::FindWithGroups { collection, condition_groups, options, alias, response } => {
-                    let result = Self::handle_find_with_groups(&collection, condition_groups, options, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::Update { collection, conditions, updates, alias, response } => {
-                    let result = Self::handle_update(&collection, conditions, updates, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::UpdateWithOperations { collection, conditions, operations, alias, response } => {
-                    let result = Self::handle_update_with_operations(&collection, conditions, operations, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::UpdateById { collection, id, updates, alias, response } => {
-                    let result = Self::handle_update_by_id(&collection, &id, updates, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::Delete { collection, conditions, alias, response } => {
-                    let result = Self::handle_delete(&collection, conditions, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::DeleteById { collection, id, alias, response } => {
-                    let result = Self::handle_delete_by_id(&collection, &id, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::Count { collection, conditions, alias, response } => {
-                    let result = Self::handle_count(&collection, conditions, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::Exists { collection, conditions, alias, response } => {
-                    let result = Self::handle_exists(&collection, conditions, alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::GetServerVersion { alias, response } => {
-                    let result = Self::handle_get_server_version(alias).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::CreateStoredProcedure { config, response } => {
-                    let result = Self::handle_create_stored_procedure(config).await;
-                    let _ = response.send(result);
-                },
-                OdmRequest::ExecuteStoredProcedure { procedure_name, database_alias, params, response } => {
-                    let result = Self::handle_execute_stored_procedure(&procedure_name, database_alias.as_deref(), params).await;
-                    let _ = response.send(result);
-                },
+            let (session_context, class, request) = request.split_task_context();
+            match class {
+                // 批量请求单独派发，不阻塞后续的交互请求
+                OperationClass::Batch => {
+                    tokio::spawn(with_operation_class(class, Self::dispatch_in_context(session_context, request)));
+                }
+                OperationClass::Interactive => Self::dispatch_in_context(session_context, request).await,
             }
         }
 
         warn!("ODM后台处理任务结束");
     }
 
+    /// 在会话上下文（如果有）中分发请求
+    async fn dispatch_in_context(session_context: Option<SessionContext>, request: OdmRequest) {
+        match session_context {
+            Some(context) => with_session_context(context, Self::dispatch_request(request)).await,
+            None => Self::dispatch_request(request).await,
+        }
+    }
+
+    /// 分发单个请求到对应的处理函数
+    async fn dispatch_request(request: OdmRequest) {
+        match request {
+            OdmRequest::Create { collection, data, alias, response } => {
+                let result = Self::handle_create(&collection, data, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::CreateMany { collection, data_list, alias, response } => {
+                let result = Self::handle_create_many(&collection, data_list, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::UpsertMany { collection, rows, conflict_fields, alias, response } => {
+                let result = Self::handle_upsert_many(&collection, rows, conflict_fields, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::Upsert { collection, data, conflict_fields, alias, response } => {
+                let result = Self::handle_upsert(&collection, data, conflict_fields, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::Seed { fixtures, alias, response } => {
+                let result = Self::handle_seed(fixtures, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::FindById { collection, id, alias, response } => {
+                let result = Self::handle_find_by_id(&collection, id, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::FindByIds { collection, ids, ordered, alias, response } => {
+                let result = Self::handle_find_by_ids(&collection, ids, ordered, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::Find { collection, conditions, options, alias, response } => {
+                let result = Self::handle_find(&collection, conditions, options, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::FindWithGroups { collection, condition_groups, options, alias, response } => {
+                let result = Self::handle_find_with_groups(&collection, condition_groups, options, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::Update { collection, conditions, updates, alias, response } => {
+                let result = Self::handle_update(&collection, conditions, updates, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::UpdateWithOperations { collection, conditions, operations, alias, response } => {
+                let result = Self::handle_update_with_operations(&collection, conditions, operations, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::UpdateById { collection, id, updates, alias, response } => {
+                let result = Self::handle_update_by_id(&collection, &id, updates, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::UpdateByIdDetailed { collection, id, updates, alias, response } => {
+                let result = Self::handle_update_by_id_detailed(&collection, id, updates, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::Delete { collection, conditions, alias, response } => {
+                let result = Self::handle_delete(&collection, conditions, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::DeleteById { collection, id, alias, response } => {
+                let result = Self::handle_delete_by_id(&collection, &id, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::DeleteByIdDetailed { collection, id, alias, response } => {
+                let result = Self::handle_delete_by_id_detailed(&collection, id, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::DeleteByIds { collection, ids, alias, response } => {
+                let result = Self::handle_delete_by_ids(&collection, ids, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::ReadBlobChunk { location, offset, len, alias, response } => {
+                let result = Self::handle_read_blob_chunk(location, offset, len, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::WriteBlobChunk { location, data, append, alias, response } => {
+                let result = Self::handle_write_blob_chunk(location, data, append, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::Count { collection, conditions, mode, alias, response } => {
+                let result = Self::handle_count(&collection, conditions, mode, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::TimeBucket { collection, field, interval, conditions, alias, response } => {
+                let result = Self::handle_time_bucket(&collection, &field, interval, conditions, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::Aggregate { collection, aggregations, conditions, group_by, alias, response } => {
+                let result = Self::handle_aggregate(&collection, aggregations, conditions, group_by, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::MongoAggregate { collection, pipeline, alias, response } => {
+                let result = Self::handle_mongo_aggregate(&collection, pipeline, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::Exists { collection, conditions, alias, response } => {
+                let result = Self::handle_exists(&collection, conditions, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::GetServerVersion { alias, response } => {
+                let result = Self::handle_get_server_version(alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::QueryRaw { sql, params, alias, response } => {
+                let result = Self::handle_query_raw(sql, params, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::ExecuteRawUpdate { sql, params, alias, response } => {
+                let result = Self::handle_execute_raw_update(sql, params, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::ExecuteBatch { sql, alias, response } => {
+                let result = Self::handle_execute_batch(sql, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::SyncModel { model_meta, alias, response } => {
+                let result = Self::handle_sync_model(model_meta, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::RenameTable { from, to, alias, response } => {
+                let result = Self::handle_rename_table(from, to, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::CreateStoredProcedure { config, response } => {
+                let result = Self::handle_create_stored_procedure(config).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::ExecuteStoredProcedure { procedure_name, database_alias, params, response } => {
+                let result = Self::handle_execute_stored_procedure(&procedure_name, database_alias.as_deref(), params).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::Watch { collection, alias, response } => {
+                let result = Self::handle_watch(&collection, alias).await;
+                let _ = response.send(result);
+            },
+            OdmRequest::WithSessionContext { .. } | OdmRequest::WithOperationClass { .. } => {
+                // 会话上下文和操作类别在接收循环中已展开，这里不会出现
+                error!("ODM请求上下文未展开");
+            },
+        }
+    }
+
     /// 处理存储过程创建请求
     #[doc(hidden)]
     pub async fn handle_create_stored_procedure(
@@ -141,7 +246,7 @@ impl AsyncOdmManager {
         };
 
         // 发送请求到连接池
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -194,7 +299,7 @@ impl AsyncOdmManager {
         };
 
         // 发送请求到连接池
-        connection_pool.operation_sender.send(operation)
+        connection_pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
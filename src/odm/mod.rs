@@ -17,10 +17,30 @@ pub mod operations;
 // 全局管理器模块
 pub mod global;
 
+// 二进制流模块
+pub mod blob;
+
+// 写后缓冲模块
+pub mod write_behind;
+
+// 查询结果流模块
+pub mod stream;
+
+// 进程内变更事件模块
+pub mod events;
+
+// 显式事务模块
+pub mod transaction;
+
 // 重新导出所有公共类型以保持API兼容性
-pub use traits::{OdmOperations};
+pub use traits::{OdmOperations, UNIQUE_VALUE_MAX_ATTEMPTS};
 pub use types::{OdmRequest};
+pub use blob::BlobReader;
+pub use stream::{RecordStream, default_fetch_size};
+pub use events::{SubscriptionId, subscribe, unsubscribe};
+pub use transaction::Transaction;
 pub use manager_core::{AsyncOdmManager};
 pub use handlers::*;
 pub use operations::*;
-pub use global::*;
\ No newline at end of file
+pub use global::*;
+pub use write_behind::flush_write_behind;
\ No newline at end of file
@@ -27,7 +27,7 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
         
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -37,23 +37,129 @@ impl OdmOperations for AsyncOdmManager {
                 message: "ODM请求处理失败".to_string(),
             })?
     }
+
+    async fn create_many(
+        &self,
+        collection: &str,
+        data_list: Vec<HashMap<String, DataValue>>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::CreateMany {
+            collection: collection.to_string(),
+            data_list,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
     
+    async fn upsert_many(
+        &self,
+        collection: &str,
+        rows: Vec<HashMap<String, DataValue>>,
+        conflict_fields: &[&str],
+        alias: Option<&str>,
+    ) -> QuickDbResult<UpsertResult> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::UpsertMany {
+            collection: collection.to_string(),
+            rows,
+            conflict_fields: conflict_fields.iter().map(|s| s.to_string()).collect(),
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn upsert(
+        &self,
+        collection: &str,
+        data: HashMap<String, DataValue>,
+        conflict_fields: &[&str],
+        alias: Option<&str>,
+    ) -> QuickDbResult<UpsertOutcome> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::Upsert {
+            collection: collection.to_string(),
+            data,
+            conflict_fields: conflict_fields.iter().map(|s| s.to_string()).collect(),
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn seed(
+        &self,
+        fixtures: HashMap<String, Vec<HashMap<String, DataValue>>>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<SeedReport> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::Seed {
+            fixtures,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
     async fn find_by_id(
         &self,
         collection: &str,
-        id: &str,
+        id: DataValue,
         alias: Option<&str>,
     ) -> QuickDbResult<Option<DataValue>> {
         let (sender, receiver) = oneshot::channel();
         
         let request = OdmRequest::FindById {
             collection: collection.to_string(),
-            id: id.to_string(),
+            id,
             alias: alias.map(|s| s.to_string()),
             response: sender,
         };
         
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -64,6 +170,34 @@ impl OdmOperations for AsyncOdmManager {
             })?
     }
     
+    async fn find_by_ids(
+        &self,
+        collection: &str,
+        ids: Vec<DataValue>,
+        ordered: bool,
+        alias: Option<&str>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::FindByIds {
+            collection: collection.to_string(),
+            ids,
+            ordered,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
     async fn find(
         &self,
         collection: &str,
@@ -81,7 +215,7 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
         
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -109,7 +243,7 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
         
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -137,7 +271,7 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
         
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -165,7 +299,7 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
 
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -193,7 +327,7 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
         
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -219,7 +353,7 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
         
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -245,7 +379,7 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
         
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -255,23 +389,146 @@ impl OdmOperations for AsyncOdmManager {
                 message: "ODM请求处理失败".to_string(),
             })?
     }
+
+    async fn update_by_id_detailed(
+        &self,
+        collection: &str,
+        id: DataValue,
+        updates: HashMap<String, DataValue>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<WriteResult> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::UpdateByIdDetailed {
+            collection: collection.to_string(),
+            id,
+            updates,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn delete_by_id_detailed(
+        &self,
+        collection: &str,
+        id: DataValue,
+        alias: Option<&str>,
+    ) -> QuickDbResult<WriteResult> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::DeleteByIdDetailed {
+            collection: collection.to_string(),
+            id,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn delete_by_ids(
+        &self,
+        collection: &str,
+        ids: Vec<DataValue>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<u64> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::DeleteByIds {
+            collection: collection.to_string(),
+            ids,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn read_blob(
+        &self,
+        collection: &str,
+        id: DataValue,
+        column: &str,
+        alias: Option<&str>,
+    ) -> QuickDbResult<crate::odm::BlobReader> {
+        let location = BlobLocation::new(collection, id, column);
+        crate::odm::BlobReader::open(
+            self.request_sender.clone(),
+            location,
+            alias.map(|s| s.to_string()),
+        ).await
+    }
+
+    async fn write_blob<R: tokio::io::AsyncRead + Unpin + Send>(
+        &self,
+        collection: &str,
+        id: DataValue,
+        column: &str,
+        reader: &mut R,
+        alias: Option<&str>,
+    ) -> QuickDbResult<u64> {
+        let location = BlobLocation::new(collection, id, column);
+        crate::odm::blob::write_from_reader(
+            &self.request_sender,
+            location,
+            reader,
+            alias.map(|s| s.to_string()),
+        ).await
+    }
     
     async fn count(
         &self,
         collection: &str,
         conditions: Vec<QueryCondition>,
         alias: Option<&str>,
+    ) -> QuickDbResult<u64> {
+        self.count_with_mode(collection, conditions, CountMode::Exact, alias).await
+    }
+
+    async fn count_with_mode(
+        &self,
+        collection: &str,
+        conditions: Vec<QueryCondition>,
+        mode: CountMode,
+        alias: Option<&str>,
     ) -> QuickDbResult<u64> {
         let (sender, receiver) = oneshot::channel();
         
         let request = OdmRequest::Count {
             collection: collection.to_string(),
             conditions,
+            mode,
             alias: alias.map(|s| s.to_string()),
             response: sender,
         };
         
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -282,6 +539,92 @@ impl OdmOperations for AsyncOdmManager {
             })?
     }
     
+    async fn time_bucket(
+        &self,
+        collection: &str,
+        field: &str,
+        interval: Interval,
+        conditions: Vec<QueryCondition>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<Vec<(DataValue, u64)>> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::TimeBucket {
+            collection: collection.to_string(),
+            field: field.to_string(),
+            interval,
+            conditions,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn aggregate(
+        &self,
+        collection: &str,
+        aggregations: Vec<Aggregation>,
+        conditions: Vec<QueryCondition>,
+        group_by: Vec<String>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::Aggregate {
+            collection: collection.to_string(),
+            aggregations,
+            conditions,
+            group_by,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+    
+    async fn mongo_aggregate(
+        &self,
+        collection: &str,
+        pipeline: serde_json::Value,
+        alias: Option<&str>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::MongoAggregate {
+            collection: collection.to_string(),
+            pipeline,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
     async fn exists(
         &self,
         collection: &str,
@@ -297,7 +640,7 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
         
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -319,7 +662,133 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
 
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn query_raw(
+        &self,
+        alias: Option<&str>,
+        sql: &str,
+        params: Vec<DataValue>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::QueryRaw {
+            sql: sql.to_string(),
+            params,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn execute_raw_update(
+        &self,
+        alias: Option<&str>,
+        sql: &str,
+        params: Vec<DataValue>,
+    ) -> QuickDbResult<u64> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::ExecuteRawUpdate {
+            sql: sql.to_string(),
+            params,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn execute_batch(
+        &self,
+        alias: Option<&str>,
+        sql: &str,
+    ) -> QuickDbResult<u64> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::ExecuteBatch {
+            sql: sql.to_string(),
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn sync_model_meta(
+        &self,
+        model_meta: crate::model::ModelMeta,
+        alias: Option<&str>,
+    ) -> QuickDbResult<SchemaSyncReport> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::SyncModel {
+            model_meta,
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn rename_table(
+        &self,
+        from: &str,
+        to: &str,
+        alias: Option<&str>,
+    ) -> QuickDbResult<()> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::RenameTable {
+            from: from.to_string(),
+            to: to.to_string(),
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -341,7 +810,7 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
 
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -367,7 +836,31 @@ impl OdmOperations for AsyncOdmManager {
             response: sender,
         };
 
-        self.request_sender.send(request)
+        self.request_sender.send(request.with_current_task_context())
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM后台任务已停止".to_string(),
+            })?;
+
+        receiver.await
+            .map_err(|_| QuickDbError::ConnectionError {
+                message: "ODM请求处理失败".to_string(),
+            })?
+    }
+
+    async fn watch(
+        &self,
+        collection: &str,
+        alias: Option<&str>,
+    ) -> QuickDbResult<ChangeStream> {
+        let (sender, receiver) = oneshot::channel();
+
+        let request = OdmRequest::Watch {
+            collection: collection.to_string(),
+            alias: alias.map(|s| s.to_string()),
+            response: sender,
+        };
+
+        self.request_sender.send(request.with_current_task_context())
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "ODM后台任务已停止".to_string(),
             })?;
@@ -377,4 +870,20 @@ impl OdmOperations for AsyncOdmManager {
                 message: "ODM请求处理失败".to_string(),
             })?
     }
+
+    async fn transaction(
+        &self,
+        alias: Option<&str>,
+    ) -> QuickDbResult<crate::odm::Transaction> {
+        // 事务句柄在调用方任务中开启，嵌套检查才能对应到调用方
+        crate::odm::Transaction::begin(alias, crate::transaction::IsolationLevel::BackendDefault).await
+    }
+
+    async fn transaction_with_isolation(
+        &self,
+        isolation: crate::transaction::IsolationLevel,
+        alias: Option<&str>,
+    ) -> QuickDbResult<crate::odm::Transaction> {
+        crate::odm::Transaction::begin(alias, isolation).await
+    }
 }
@@ -0,0 +1,98 @@
+//! # 查询结果流式读取
+//!
+//! 按 `fetch_size` 分页经ODM后台任务拉取记录，每页是一次独立的查询，
+//! 内存占用只与页大小有关，页与页之间也不占用连接
+
+use crate::error::QuickDbResult;
+use crate::types::{DataValue, DatabaseType, PaginationConfig, QueryCondition, QueryOptions, SortConfig, SortDirection};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+
+/// 查询结果流，逐条产出记录
+pub type RecordStream = BoxStream<'static, QuickDbResult<DataValue>>;
+
+/// 各数据库默认每次往返拉取的记录数
+///
+/// SQLite 在进程内读取，较小的页即可避免长时间占用连接；其余数据库按网络往返成本取较大的页
+pub fn default_fetch_size(db_type: DatabaseType) -> u64 {
+    match db_type {
+        DatabaseType::SQLite => 500,
+        DatabaseType::PostgreSQL | DatabaseType::MySQL | DatabaseType::MongoDB => 1000,
+    }
+}
+
+/// 分页读取的进度
+struct PageCursor {
+    collection: String,
+    conditions: Vec<QueryCondition>,
+    options: QueryOptions,
+    alias: Option<String>,
+    fetch_size: u64,
+    /// 下一页的起始偏移
+    offset: u64,
+    /// 剩余可读取的记录数，`None` 表示不限制
+    remaining: Option<u64>,
+}
+
+/// 创建按页拉取的结果流
+///
+/// 未指定排序时按 `id` 升序，保证分页边界稳定；`options` 中的分页作为整体的起点和上限
+pub(crate) fn page_stream(
+    collection: String,
+    conditions: Vec<QueryCondition>,
+    options: Option<QueryOptions>,
+    fetch_size: u64,
+    alias: Option<String>,
+) -> RecordStream {
+    let mut options = options.unwrap_or_default();
+    if options.sort.is_empty() {
+        options.sort.push(SortConfig { field: "id".to_string(), direction: SortDirection::Asc });
+    }
+    let (offset, remaining) = match options.pagination.take() {
+        Some(pagination) => (pagination.skip, (pagination.limit > 0).then_some(pagination.limit)),
+        None => (0, None),
+    };
+
+    let cursor = PageCursor {
+        collection,
+        conditions,
+        options,
+        alias,
+        fetch_size: fetch_size.max(1),
+        offset,
+        remaining,
+    };
+
+    stream::try_unfold(cursor, next_page)
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+}
+
+/// 拉取下一页，已读完时返回 `None`
+async fn next_page(mut cursor: PageCursor) -> QuickDbResult<Option<(Vec<DataValue>, PageCursor)>> {
+    let limit = cursor.remaining.map_or(cursor.fetch_size, |r| r.min(cursor.fetch_size));
+    if limit == 0 {
+        return Ok(None);
+    }
+
+    let mut options = cursor.options.clone();
+    options.pagination = Some(PaginationConfig { skip: cursor.offset, limit });
+    let page = crate::odm::find(
+        &cursor.collection,
+        cursor.conditions.clone(),
+        Some(options),
+        cursor.alias.as_deref(),
+    ).await?;
+
+    let fetched = page.len() as u64;
+    if fetched == 0 {
+        return Ok(None);
+    }
+    cursor.offset += fetched;
+    cursor.remaining = cursor.remaining.map(|r| r - fetched);
+    // 不足一页说明已读到末尾，下一轮直接结束
+    if fetched < limit {
+        cursor.remaining = Some(0);
+    }
+    Ok(Some((page, cursor)))
+}
@@ -1,11 +1,14 @@
 //! # ODM操作接口定义
 
-use crate::error::{QuickDbResult};
+use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::*;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use tokio::sync::oneshot;
 
+/// `create_with_unique` 最多尝试的次数
+pub const UNIQUE_VALUE_MAX_ATTEMPTS: u32 = 20;
+
 /// ODM操作接口
 #[async_trait]
 pub trait OdmOperations {
@@ -17,14 +20,139 @@ pub trait OdmOperations {
         alias: Option<&str>,
     ) -> QuickDbResult<DataValue>;
 
+    /// 创建记录，唯一字段冲突时用新生成的值重试
+    ///
+    /// 第 `attempt` 次（从1开始）写入前调用 `generator(attempt)` 生成 `unique_field` 的值，
+    /// 例如第1次返回 `"my-title"`、之后返回 `"my-title-2"`、`"my-title-3"`。
+    /// 只有该字段的唯一约束冲突会触发重试，其他错误直接返回；
+    /// 尝试 [`UNIQUE_VALUE_MAX_ATTEMPTS`] 次仍冲突时返回最后一次的冲突错误。
+    /// 成功时返回生成的ID和最终写入的唯一字段值
+    async fn create_with_unique<G>(
+        &self,
+        collection: &str,
+        data: HashMap<String, DataValue>,
+        unique_field: &str,
+        generator: G,
+        alias: Option<&str>,
+    ) -> QuickDbResult<(DataValue, DataValue)>
+    where
+        Self: Sync,
+        G: Fn(u32) -> DataValue + Send + Sync,
+    {
+        let mut attempt = 1;
+        loop {
+            let value = generator(attempt);
+            let mut record = data.clone();
+            record.insert(unique_field.to_string(), value.clone());
+            match self.create(collection, record, alias).await {
+                Ok(id) => return Ok((id, value)),
+                Err(e) if attempt < UNIQUE_VALUE_MAX_ATTEMPTS && is_unique_conflict_on(&e, unique_field, &data) => {
+                    rat_logger::debug!("唯一字段冲突，重新生成后重试: collection={}, field={}, attempt={}", collection, unique_field, attempt);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// 复制一条记录并应用修改，返回新记录的ID
+    ///
+    /// 读取 `id` 对应的记录，去掉ID字段后用 `overrides` 覆盖同名字段再插入，新ID按别名的ID策略生成。
+    /// 唯一字段需要在 `overrides` 中给出新值，否则插入时返回唯一约束冲突；记录不存在时返回错误
+    async fn duplicate(
+        &self,
+        collection: &str,
+        id: DataValue,
+        overrides: HashMap<String, DataValue>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<DataValue>
+    where
+        Self: Sync,
+    {
+        let record = self.find_by_id(collection, id.clone(), alias).await?
+            .ok_or_else(|| QuickDbError::QueryError {
+                message: format!("记录不存在: {} {}", collection, id),
+            })?;
+        let mut data = match record {
+            DataValue::Object(data) => data,
+            other => return Err(QuickDbError::SerializationError {
+                message: format!("查询结果不是对象: {}", other.type_name()),
+            }),
+        };
+        data.remove("id");
+        data.remove("_id");
+        data.extend(overrides);
+
+        rat_logger::debug!("复制记录: collection={}, source_id={}", collection, id);
+        self.create(collection, data, alias).await
+    }
+
+    /// 批量创建记录，按输入顺序返回生成的ID
+    async fn create_many(
+        &self,
+        collection: &str,
+        data_list: Vec<HashMap<String, DataValue>>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<Vec<DataValue>>;
+
+    /// 批量插入或更新记录：按 `conflict_fields` 判断记录是否已存在，已存在的记录用新值覆盖其余字段
+    ///
+    /// SQL数据库生成多行 `INSERT ... ON CONFLICT DO UPDATE`（MySQL 为 `ON DUPLICATE KEY UPDATE`），
+    /// 冲突列上必须有唯一索引或主键；MongoDB 以一条 `update` 命令批量执行 upsert。
+    /// 所有记录的字段集合必须一致，数据库能够区分时返回插入与更新各自的数量
+    async fn upsert_many(
+        &self,
+        collection: &str,
+        rows: Vec<HashMap<String, DataValue>>,
+        conflict_fields: &[&str],
+        alias: Option<&str>,
+    ) -> QuickDbResult<UpsertResult>;
+
+    /// 插入或更新单条记录：`conflict_fields` 上已存在记录时用新值覆盖其余字段，否则插入新记录
+    ///
+    /// 以一条语句原子地完成，避免先查询再分支的竞争。SQL数据库生成 `INSERT ... ON CONFLICT DO UPDATE`
+    /// （MySQL 为 `ON DUPLICATE KEY UPDATE`），MongoDB 执行 `upsert: true` 的更新。
+    /// 冲突字段必须对应一个唯一索引或主键，否则返回 `QuickDbError::ValidationError`；
+    /// 返回插入了新记录还是更新了已有记录
+    async fn upsert(
+        &self,
+        collection: &str,
+        data: HashMap<String, DataValue>,
+        conflict_fields: &[&str],
+        alias: Option<&str>,
+    ) -> QuickDbResult<UpsertOutcome>;
+
+    /// 在单个事务中加载一组初始数据（表名 → 记录列表），任一记录失败时整体回滚
+    ///
+    /// 表按模型元数据中的引用字段排序，被引用的表先写入；返回每张表插入的行数。
+    /// 用于测试准备和初始数据导入，MongoDB 不支持
+    async fn seed(
+        &self,
+        fixtures: HashMap<String, Vec<HashMap<String, DataValue>>>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<SeedReport>;
+
     /// 根据ID查找记录
+    ///
+    /// ID可以是整数、字符串、UUID等任意 `DataValue`
     async fn find_by_id(
         &self,
         collection: &str,
-        id: &str,
+        id: DataValue,
         alias: Option<&str>,
     ) -> QuickDbResult<Option<DataValue>>;
 
+    /// 根据ID列表批量查找记录，不存在的ID不出现在结果中
+    ///
+    /// 输入中重复的ID只查询一次；`ordered` 为 `true` 时结果按输入ID的顺序在客户端重排
+    async fn find_by_ids(
+        &self,
+        collection: &str,
+        ids: Vec<DataValue>,
+        ordered: bool,
+        alias: Option<&str>,
+    ) -> QuickDbResult<Vec<DataValue>>;
+
     /// 查找记录
     async fn find(
         &self,
@@ -69,6 +197,17 @@ pub trait OdmOperations {
         updates: HashMap<String, DataValue>,
         alias: Option<&str>,
     ) -> QuickDbResult<bool>;
+
+    /// 根据ID更新记录，返回匹配数与修改数
+    ///
+    /// 可区分"记录不存在"（matched = 0）与"记录存在但内容未变化"（modified = 0）
+    async fn update_by_id_detailed(
+        &self,
+        collection: &str,
+        id: DataValue,
+        updates: HashMap<String, DataValue>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<WriteResult>;
     
     /// 删除记录
     async fn delete(
@@ -85,6 +224,46 @@ pub trait OdmOperations {
         id: &str,
         alias: Option<&str>,
     ) -> QuickDbResult<bool>;
+
+    /// 根据ID删除记录，返回匹配数与删除数
+    async fn delete_by_id_detailed(
+        &self,
+        collection: &str,
+        id: DataValue,
+        alias: Option<&str>,
+    ) -> QuickDbResult<WriteResult>;
+
+    /// 根据ID列表批量删除记录，返回删除数量
+    async fn delete_by_ids(
+        &self,
+        collection: &str,
+        ids: Vec<DataValue>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<u64>;
+
+    /// 以流的方式读取记录的二进制列
+    ///
+    /// 数据按块读取，不会一次性载入内存；记录不存在时返回错误，列为空时读到空数据
+    async fn read_blob(
+        &self,
+        collection: &str,
+        id: DataValue,
+        column: &str,
+        alias: Option<&str>,
+    ) -> QuickDbResult<crate::odm::BlobReader>;
+
+    /// 把读取器中的数据写入记录的二进制列，覆盖原值，返回写入的字节数
+    ///
+    /// 按块写入，第一块覆盖原值、之后的块依次追加，整体不是原子操作：
+    /// 中途失败时列中保留已写入的部分
+    async fn write_blob<R: tokio::io::AsyncRead + Unpin + Send>(
+        &self,
+        collection: &str,
+        id: DataValue,
+        column: &str,
+        reader: &mut R,
+        alias: Option<&str>,
+    ) -> QuickDbResult<u64>;
     
     /// 统计记录数量
     async fn count(
@@ -93,6 +272,55 @@ pub trait OdmOperations {
         conditions: Vec<QueryCondition>,
         alias: Option<&str>,
     ) -> QuickDbResult<u64>;
+
+    /// 按指定方式统计记录数量
+    ///
+    /// [`CountMode::Estimated`] 只在没有条件时生效，带条件的计数总是精确计数
+    async fn count_with_mode(
+        &self,
+        collection: &str,
+        conditions: Vec<QueryCondition>,
+        mode: CountMode,
+        alias: Option<&str>,
+    ) -> QuickDbResult<u64>;
+
+    /// 按时间段统计记录数量
+    ///
+    /// 时间字段按UTC截断到 `interval` 的起点后分组计数，结果按时间段升序排列，
+    /// 没有记录的时间段不出现在结果中
+    async fn time_bucket(
+        &self,
+        collection: &str,
+        field: &str,
+        interval: Interval,
+        conditions: Vec<QueryCondition>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<Vec<(DataValue, u64)>>;
+
+    /// 聚合统计
+    ///
+    /// 按 `group_by` 分组计算各聚合项，每组返回一个 `DataValue::Object`，
+    /// 键为分组字段名和聚合项的 `alias`
+    async fn aggregate(
+        &self,
+        collection: &str,
+        aggregations: Vec<Aggregation>,
+        conditions: Vec<QueryCondition>,
+        group_by: Vec<String>,
+        alias: Option<&str>,
+    ) -> QuickDbResult<Vec<DataValue>>;
+
+    /// 原样执行MongoDB聚合管道
+    ///
+    /// `pipeline` 为阶段对象组成的JSON数组，转换为BSON后交给 `collection.aggregate` 执行，
+    /// 可使用 `$lookup`、`$unwind`、`$facet` 等任意阶段，每个输出文档以 `DataValue::Object` 返回。
+    /// 含 `$out` 或 `$merge` 阶段时视为写操作；非MongoDB别名返回 `QuickDbError::UnsupportedDatabase`
+    async fn mongo_aggregate(
+        &self,
+        collection: &str,
+        pipeline: serde_json::Value,
+        alias: Option<&str>,
+    ) -> QuickDbResult<Vec<DataValue>>;
     
     /// 检查记录是否存在
     async fn exists(
@@ -108,6 +336,104 @@ pub trait OdmOperations {
         alias: Option<&str>,
     ) -> QuickDbResult<String>;
 
+    /// 执行原始SQL查询，每行结果以 `DataValue::Object` 返回
+    ///
+    /// 参数按占位符顺序绑定，仅SQL数据库支持
+    async fn query_raw(
+        &self,
+        alias: Option<&str>,
+        sql: &str,
+        params: Vec<DataValue>,
+    ) -> QuickDbResult<Vec<DataValue>>;
+
+    /// 执行返回单个值的查询（计数、求和、最大值等），取第一行唯一一列并转换为目标类型
+    ///
+    /// 没有结果行或值为 NULL 时返回 `None`；查询返回多列时报错
+    async fn query_scalar<T: FromDataValue + Send + 'static>(
+        &self,
+        alias: Option<&str>,
+        sql: &str,
+        params: Vec<DataValue>,
+    ) -> QuickDbResult<Option<T>> {
+        let rows = self.query_raw(alias, sql, params).await?;
+        let Some(row) = rows.into_iter().next() else {
+            return Ok(None);
+        };
+        match crate::types::data_value::single_column_value(row)? {
+            DataValue::Null => Ok(None),
+            value => T::from_data_value(value).map(Some),
+        }
+    }
+
+    /// 执行只读的原始SQL查询（窗口函数、CTE等查询构建器无法表达的报表查询），每行结果以 `DataValue::Object` 返回
+    ///
+    /// 与 `query_raw` 走同一条连接池通道，但只接受单条只读查询，写入语句返回验证错误，
+    /// 应改用 `execute_raw_update`。仅SQL数据库支持，MongoDB 返回 [`QuickDbError::UnsupportedDatabase`]
+    async fn execute_raw(
+        &self,
+        alias: Option<&str>,
+        sql: &str,
+        params: Vec<DataValue>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        if !crate::pool::types::is_read_only_sql(sql) {
+            return Err(QuickDbError::ValidationError {
+                field: "sql".to_string(),
+                message: "execute_raw 只执行只读查询，写入语句请使用 execute_raw_update".to_string(),
+            });
+        }
+        self.query_raw(alias, sql, params).await
+    }
+
+    /// 执行原始SQL写入语句（INSERT/UPDATE/DELETE等），返回受影响的行数
+    ///
+    /// 参数按占位符顺序绑定，在当前作用域事务中执行。由于无法判断语句涉及的表，
+    /// 执行后清空该别名的全部缓存，仅SQL数据库支持
+    async fn execute_raw_update(
+        &self,
+        alias: Option<&str>,
+        sql: &str,
+        params: Vec<DataValue>,
+    ) -> QuickDbResult<u64>;
+
+    /// 按顺序执行包含多条 `;` 分隔语句的SQL脚本（迁移脚本、建表语句等），返回执行的语句数
+    ///
+    /// 字符串和注释中的分号不会拆分语句。SQLite 和 PostgreSQL 在同一事务中逐条执行，
+    /// 任一语句失败时整体回滚；MySQL 使用驱动的多语句支持执行整个脚本，其中的DDL会隐式提交。
+    /// 执行后清空该别名的全部缓存，仅SQL数据库支持
+    async fn execute_batch(
+        &self,
+        alias: Option<&str>,
+        sql: &str,
+    ) -> QuickDbResult<u64>;
+
+    /// 同步模型结构：建表、补充缺失的列、确保声明的索引存在
+    ///
+    /// 只执行增量变更，不会删除或修改已有结构，可在每次启动时调用。
+    /// 未指定别名时使用模型声明的数据库别名，再退回默认别名。
+    async fn sync_model<T: crate::model::Model + 'static>(
+        &self,
+        alias: Option<&str>,
+    ) -> QuickDbResult<SchemaSyncReport> {
+        self.sync_model_meta(T::meta(), alias).await
+    }
+
+    /// 按模型元数据同步结构，供无法使用泛型的场景（如Python绑定）调用
+    async fn sync_model_meta(
+        &self,
+        model_meta: crate::model::ModelMeta,
+        alias: Option<&str>,
+    ) -> QuickDbResult<SchemaSyncReport>;
+
+    /// 重命名表/集合，原表不存在或新名称已被占用时返回错误
+    ///
+    /// 可用于“建新表、回填数据、交换表名”的不停机迁移；成功后清理新旧两个名称的缓存
+    async fn rename_table(
+        &self,
+        from: &str,
+        to: &str,
+        alias: Option<&str>,
+    ) -> QuickDbResult<()>;
+
     /// 创建存储过程
     async fn create_stored_procedure(
         &self,
@@ -121,7 +447,49 @@ pub trait OdmOperations {
         database_alias: Option<&str>,
         params: Option<std::collections::HashMap<String, crate::types::DataValue>>,
     ) -> QuickDbResult<crate::stored_procedure::StoredProcedureQueryResult>;
+
+    /// 订阅表/集合的数据变更
+    ///
+    /// PostgreSQL基于LISTEN/NOTIFY，需先安装 `postgres_change_trigger_sql` 生成的触发器；
+    /// MongoDB基于Change Stream，需要副本集部署；其他数据库返回不支持错误
+    async fn watch(
+        &self,
+        collection: &str,
+        alias: Option<&str>,
+    ) -> QuickDbResult<ChangeStream>;
+
+    /// 开启显式事务，返回独占一个连接的事务句柄
+    ///
+    /// 当前任务已有进行中的事务时返回错误；MongoDB需要副本集或分片集群部署
+    async fn transaction(
+        &self,
+        alias: Option<&str>,
+    ) -> QuickDbResult<crate::odm::Transaction>;
+
+    /// 以指定隔离级别开启显式事务
+    ///
+    /// PostgreSQL/MySQL 执行 `SET TRANSACTION ISOLATION LEVEL`，SQLite 始终串行化，
+    /// MongoDB 映射为事务的读关注和写关注（不支持读未提交）
+    async fn transaction_with_isolation(
+        &self,
+        isolation: crate::transaction::IsolationLevel,
+        alias: Option<&str>,
+    ) -> QuickDbResult<crate::odm::Transaction>;
 }
 
 // 重新导出OdmRequest类型
 pub use super::types::OdmRequest;
+
+/// 是否为指定字段的唯一约束冲突
+///
+/// 错误中能识别出字段名且是记录中的其他字段时视为其他字段冲突；
+/// 无法识别字段名（如 PostgreSQL、MySQL 的约束名或 SQLite 表达式索引）时按该字段冲突处理
+fn is_unique_conflict_on(error: &QuickDbError, unique_field: &str, data: &HashMap<String, DataValue>) -> bool {
+    if !error.is_unique_violation() {
+        return false;
+    }
+    match error.field_name() {
+        Some(field) => field == unique_field || !data.contains_key(field),
+        None => true,
+    }
+}
@@ -0,0 +1,325 @@
+//! # 显式事务
+//!
+//! [`Transaction`] 开启时从连接池取出一个连接并开启数据库事务，提交或回滚前一直独占该连接，
+//! 通过它执行的写入和查询都在同一个事务中进行。SQL数据库使用 sqlx 事务，
+//! MongoDB使用客户端会话上的事务（需要副本集或分片集群部署）。
+//! 句柄未提交就被丢弃时事务自动回滚。
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+
+use dashmap::DashSet;
+use once_cell::sync::Lazy;
+use rat_logger::{debug, warn};
+
+use crate::adapter::DatabaseAdapter;
+use crate::adapter::transaction::AdapterTransaction;
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::manager::get_global_pool_manager;
+use crate::pool::DatabaseConnection;
+use crate::transaction::{IsolationLevel, SavepointAction};
+use crate::types::{DataValue, DatabaseType, IdStrategy, QueryCondition, QueryOptions};
+
+/// 持有进行中事务的任务，不在任务中运行时（如 `block_on`）以线程区分
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TransactionOwner {
+    Task(tokio::task::Id),
+    Thread(std::thread::ThreadId),
+}
+
+impl TransactionOwner {
+    fn current() -> Self {
+        match tokio::task::try_id() {
+            Some(id) => Self::Task(id),
+            None => Self::Thread(std::thread::current().id()),
+        }
+    }
+}
+
+/// 已开启事务的任务，同一任务中再次开启事务时返回错误
+static ACTIVE_OWNERS: Lazy<DashSet<TransactionOwner>> = Lazy::new(DashSet::new);
+
+/// 进行中的数据库事务
+///
+/// 通过 [`crate::odm::transaction`] 或 [`crate::odm::OdmOperations::transaction`] 开启，
+/// 需显式调用 [`Transaction::commit`] 提交；调用 [`Transaction::rollback`] 或直接丢弃句柄都会回滚。
+/// 事务中不会自动建表，写入的表需在开启事务前存在；事务内的写入不发布进程内变更事件，
+/// 提交后清理涉及表的缓存。需要部分回滚时使用 [`Transaction::savepoint`] 和 [`Transaction::rollback_to`]。
+pub struct Transaction {
+    alias: String,
+    db_type: DatabaseType,
+    connection: DatabaseConnection,
+    adapter: Box<dyn DatabaseAdapter>,
+    id_strategy: IdStrategy,
+    datetime_precision: Option<u8>,
+    inner: Option<AdapterTransaction>,
+    owner: TransactionOwner,
+    /// 事务中写入过的表，提交后清理缓存
+    written_tables: std::sync::Mutex<HashSet<String>>,
+}
+
+impl Transaction {
+    /// 以指定隔离级别在别名上开启事务
+    ///
+    /// 当前任务已有进行中的事务时返回错误，不会把两个事务合并提交
+    pub(crate) async fn begin(alias: Option<&str>, isolation: IsolationLevel) -> QuickDbResult<Self> {
+        let owner = TransactionOwner::current();
+        if !ACTIVE_OWNERS.insert(owner) {
+            return Err(QuickDbError::TransactionError {
+                message: "当前任务已有进行中的事务，不支持嵌套事务，需要部分回滚时请使用保存点".to_string(),
+            });
+        }
+
+        let result = Self::begin_for(owner, alias, isolation).await;
+        if result.is_err() {
+            ACTIVE_OWNERS.remove(&owner);
+        }
+        result
+    }
+
+    async fn begin_for(owner: TransactionOwner, alias: Option<&str>, isolation: IsolationLevel) -> QuickDbResult<Self> {
+        let manager = get_global_pool_manager();
+        let alias = match alias {
+            Some(a) => a.to_string(),
+            None => manager.get_default_alias().await
+                .unwrap_or_else(|| "default".to_string()),
+        };
+
+        let pool = manager.get_connection_pools().get(&alias)
+            .map(|pool| pool.clone())
+            .ok_or_else(|| QuickDbError::AliasNotFound {
+                alias: alias.clone(),
+            })?;
+        if pool.db_config.dry_run {
+            return Err(QuickDbError::UnsupportedDatabase {
+                db_type: format!("别名 {} 处于试运行模式，不支持显式事务", alias),
+            });
+        }
+
+        let (connection, inner) = match pool.db_config.db_type {
+            // 会话只能用于同一客户端创建的数据库句柄
+            #[cfg(feature = "mongodb-support")]
+            DatabaseType::MongoDB => {
+                let client = crate::pool::multi_connection_manager::mongo_client(&alias)
+                    .ok_or_else(|| QuickDbError::ConnectionError {
+                        message: format!("别名 {} 没有可用的MongoDB客户端", alias),
+                    })?;
+                let database = match &pool.db_config.connection {
+                    crate::types::ConnectionConfig::MongoDB { database, .. } => client.database(database),
+                    _ => return Err(QuickDbError::ConfigError {
+                        message: "MongoDB连接配置类型不匹配".to_string(),
+                    }),
+                };
+                let inner = AdapterTransaction::begin_mongo(&client, isolation).await?;
+                (DatabaseConnection::MongoDB(database), inner)
+            }
+            _ => {
+                let connection = pool.raw_connection().await?;
+                let inner = AdapterTransaction::begin(&connection, isolation).await?;
+                (connection, inner)
+            }
+        };
+        let adapter = crate::adapter::create_configured_adapter(&pool.db_config)?;
+        debug!("开启事务: alias={}, isolation={:?}", alias, isolation);
+
+        Ok(Self {
+            db_type: pool.db_config.db_type,
+            id_strategy: pool.db_config.id_strategy.clone(),
+            datetime_precision: pool.db_config.datetime_precision,
+            alias,
+            connection,
+            adapter,
+            inner: Some(inner),
+            owner,
+            written_tables: std::sync::Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// 事务所在的数据库别名
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /// 在事务中创建记录，返回记录ID
+    pub async fn create(&self, collection: &str, data: HashMap<String, DataValue>) -> QuickDbResult<DataValue> {
+        let manager = get_global_pool_manager();
+        manager.ensure_writable(&self.alias)?;
+        self.ensure_table(collection).await?;
+
+        let mut data = data;
+        if let Ok(id_generator) = manager.get_id_generator(&self.alias) {
+            id_generator.populate_id(collection, &self.alias, &mut data).await
+                .map_err(QuickDbError::Other)?;
+        }
+        self.truncate_data(&mut data);
+
+        let result = self.run(self.adapter.create(&self.connection, collection, &data, &self.id_strategy)).await?;
+        self.mark_written(collection);
+        crate::odm::handlers::extract_created_id(result)
+    }
+
+    /// 在事务中按条件更新记录，返回受影响的行数
+    pub async fn update(
+        &self,
+        collection: &str,
+        conditions: Vec<QueryCondition>,
+        updates: HashMap<String, DataValue>,
+    ) -> QuickDbResult<u64> {
+        get_global_pool_manager().ensure_writable(&self.alias)?;
+
+        let mut conditions = conditions;
+        let mut updates = updates;
+        self.truncate_conditions(&mut conditions);
+        self.truncate_data(&mut updates);
+
+        let affected = self.run(self.adapter.update(&self.connection, collection, &conditions, &updates)).await?;
+        self.mark_written(collection);
+        Ok(affected)
+    }
+
+    /// 在事务中按条件删除记录，返回删除的行数
+    pub async fn delete(&self, collection: &str, conditions: Vec<QueryCondition>) -> QuickDbResult<u64> {
+        get_global_pool_manager().ensure_writable(&self.alias)?;
+
+        let mut conditions = conditions;
+        self.truncate_conditions(&mut conditions);
+
+        let affected = self.run(self.adapter.delete(&self.connection, collection, &conditions)).await?;
+        self.mark_written(collection);
+        Ok(affected)
+    }
+
+    /// 在事务中查询记录，可读到本事务中尚未提交的写入
+    pub async fn find(
+        &self,
+        collection: &str,
+        conditions: Vec<QueryCondition>,
+        options: Option<QueryOptions>,
+    ) -> QuickDbResult<Vec<DataValue>> {
+        let mut conditions = conditions;
+        let mut options = options.unwrap_or_default();
+        if let Some(cursor_condition) = options.resolve_cursor()? {
+            conditions.push(cursor_condition);
+        }
+        self.truncate_conditions(&mut conditions);
+
+        self.run(self.adapter.find(&self.connection, collection, &conditions, &options)).await
+    }
+
+    /// 创建保存点，之后可用 [`Transaction::rollback_to`] 只撤销保存点之后的修改
+    ///
+    /// 名称只允许字母、数字和下划线且不能以数字开头；MongoDB 事务不支持保存点
+    pub async fn savepoint(&self, name: &str) -> QuickDbResult<()> {
+        self.savepoint_action(SavepointAction::Create, name).await
+    }
+
+    /// 回滚到保存点，撤销其后的修改，事务继续进行；保存点保留，可以再次回滚
+    pub async fn rollback_to(&self, name: &str) -> QuickDbResult<()> {
+        self.savepoint_action(SavepointAction::RollbackTo, name).await
+    }
+
+    /// 释放保存点，其后的修改并入事务
+    pub async fn release(&self, name: &str) -> QuickDbResult<()> {
+        self.savepoint_action(SavepointAction::Release, name).await
+    }
+
+    async fn savepoint_action(&self, action: SavepointAction, name: &str) -> QuickDbResult<()> {
+        let inner = self.inner.as_ref().ok_or_else(finished)?;
+        inner.savepoint(action, name).await?;
+        debug!("事务保存点: alias={}, action={:?}, name={}", self.alias, action, name);
+        Ok(())
+    }
+
+    /// 提交事务，成功后清理事务中写入过的表的缓存
+    pub async fn commit(mut self) -> QuickDbResult<()> {
+        let inner = self.take_inner()?;
+        inner.commit().await?;
+        debug!("提交事务: alias={}", self.alias);
+
+        let written_tables = std::mem::take(&mut *self.written_tables.lock().unwrap_or_else(|e| e.into_inner()));
+        if let Ok(cache_manager) = get_global_pool_manager().get_cache_manager(&self.alias) {
+            for table in written_tables {
+                if let Err(e) = cache_manager.invalidate_table(&table).await {
+                    warn!("事务提交后清理表 {} 的缓存失败: {}", table, e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 回滚事务
+    pub async fn rollback(mut self) -> QuickDbResult<()> {
+        let inner = self.take_inner()?;
+        inner.rollback().await?;
+        debug!("回滚事务: alias={}", self.alias);
+        Ok(())
+    }
+
+    /// 在事务作用域中执行适配器调用
+    async fn run<T, F: Future<Output = QuickDbResult<T>>>(&self, future: F) -> QuickDbResult<T> {
+        match &self.inner {
+            Some(inner) => inner.scope(future).await,
+            None => Err(finished()),
+        }
+    }
+
+    fn take_inner(&mut self) -> QuickDbResult<AdapterTransaction> {
+        self.inner.take().ok_or_else(finished)
+    }
+
+    /// 事务中不自动建表：DDL会在事务外的连接上执行，可能与事务互相等待
+    async fn ensure_table(&self, collection: &str) -> QuickDbResult<()> {
+        if self.db_type == DatabaseType::MongoDB || self.adapter.table_exists(&self.connection, collection).await? {
+            return Ok(());
+        }
+        Err(QuickDbError::ValidationError {
+            field: "table".to_string(),
+            message: format!("事务中不会自动建表，表 {} 不存在，请在开启事务前创建", collection),
+        })
+    }
+
+    fn mark_written(&self, collection: &str) {
+        self.written_tables.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(collection.to_string());
+    }
+
+    fn truncate_data(&self, data: &mut HashMap<String, DataValue>) {
+        if let Some(precision) = self.datetime_precision {
+            data.values_mut().for_each(|value| value.truncate_datetime(precision));
+        }
+    }
+
+    fn truncate_conditions(&self, conditions: &mut [QueryCondition]) {
+        if let Some(precision) = self.datetime_precision {
+            conditions.iter_mut().for_each(|condition| condition.value.truncate_datetime(precision));
+        }
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        ACTIVE_OWNERS.remove(&self.owner);
+        // 事务随连接归还连接池（MongoDB随会话结束）时由驱动回滚
+        if self.inner.take().is_some() {
+            warn!("事务未提交即被丢弃，已自动回滚: alias={}", self.alias);
+        }
+    }
+}
+
+impl std::fmt::Debug for Transaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transaction")
+            .field("alias", &self.alias)
+            .field("db_type", &self.db_type)
+            .field("active", &self.inner.is_some())
+            .finish()
+    }
+}
+
+/// 事务已结束的错误
+fn finished() -> QuickDbError {
+    QuickDbError::TransactionError {
+        message: "事务已提交或回滚".to_string(),
+    }
+}
@@ -14,12 +14,44 @@ pub enum OdmRequest {
         alias: Option<String>,
         response: oneshot::Sender<QuickDbResult<DataValue>>,
     },
+    CreateMany {
+        collection: String,
+        data_list: Vec<HashMap<String, DataValue>>,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<Vec<DataValue>>>,
+    },
+    UpsertMany {
+        collection: String,
+        rows: Vec<HashMap<String, DataValue>>,
+        conflict_fields: Vec<String>,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<UpsertResult>>,
+    },
+    Upsert {
+        collection: String,
+        data: HashMap<String, DataValue>,
+        conflict_fields: Vec<String>,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<UpsertOutcome>>,
+    },
+    Seed {
+        fixtures: HashMap<String, Vec<HashMap<String, DataValue>>>,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<SeedReport>>,
+    },
     FindById {
         collection: String,
-        id: String,
+        id: DataValue,
         alias: Option<String>,
         response: oneshot::Sender<QuickDbResult<Option<DataValue>>>,
     },
+    FindByIds {
+        collection: String,
+        ids: Vec<DataValue>,
+        ordered: bool,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<Vec<DataValue>>>,
+    },
     Find {
         collection: String,
         conditions: Vec<QueryCondition>,
@@ -55,6 +87,13 @@ pub enum OdmRequest {
         alias: Option<String>,
         response: oneshot::Sender<QuickDbResult<bool>>,
     },
+    UpdateByIdDetailed {
+        collection: String,
+        id: DataValue,
+        updates: HashMap<String, DataValue>,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<WriteResult>>,
+    },
     Delete {
         collection: String,
         conditions: Vec<QueryCondition>,
@@ -67,12 +106,61 @@ pub enum OdmRequest {
         alias: Option<String>,
         response: oneshot::Sender<QuickDbResult<bool>>,
     },
+    DeleteByIdDetailed {
+        collection: String,
+        id: DataValue,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<WriteResult>>,
+    },
+    DeleteByIds {
+        collection: String,
+        ids: Vec<DataValue>,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<u64>>,
+    },
+    ReadBlobChunk {
+        location: BlobLocation,
+        offset: u64,
+        len: u64,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<Option<Vec<u8>>>>,
+    },
+    WriteBlobChunk {
+        location: BlobLocation,
+        data: Vec<u8>,
+        append: bool,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<bool>>,
+    },
     Count {
         collection: String,
         conditions: Vec<QueryCondition>,
+        mode: CountMode,
         alias: Option<String>,
         response: oneshot::Sender<QuickDbResult<u64>>,
     },
+    TimeBucket {
+        collection: String,
+        field: String,
+        interval: Interval,
+        conditions: Vec<QueryCondition>,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<Vec<(DataValue, u64)>>>,
+    },
+    Aggregate {
+        collection: String,
+        aggregations: Vec<Aggregation>,
+        conditions: Vec<QueryCondition>,
+        group_by: Vec<String>,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<Vec<DataValue>>>,
+    },
+    MongoAggregate {
+        collection: String,
+        pipeline: serde_json::Value,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<Vec<DataValue>>>,
+    },
     Exists {
         collection: String,
         conditions: Vec<QueryCondition>,
@@ -83,6 +171,34 @@ pub enum OdmRequest {
         alias: Option<String>,
         response: oneshot::Sender<QuickDbResult<String>>,
     },
+    QueryRaw {
+        sql: String,
+        params: Vec<DataValue>,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<Vec<DataValue>>>,
+    },
+    ExecuteRawUpdate {
+        sql: String,
+        params: Vec<DataValue>,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<u64>>,
+    },
+    ExecuteBatch {
+        sql: String,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<u64>>,
+    },
+    SyncModel {
+        model_meta: crate::model::ModelMeta,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<SchemaSyncReport>>,
+    },
+    RenameTable {
+        from: String,
+        to: String,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<()>>,
+    },
     CreateStoredProcedure {
         config: crate::stored_procedure::StoredProcedureConfig,
         response: oneshot::Sender<QuickDbResult<crate::stored_procedure::StoredProcedureCreateResult>>,
@@ -93,4 +209,61 @@ pub enum OdmRequest {
         params: Option<std::collections::HashMap<String, crate::types::DataValue>>,
         response: oneshot::Sender<QuickDbResult<crate::stored_procedure::StoredProcedureQueryResult>>,
     },
+    Watch {
+        collection: String,
+        alias: Option<String>,
+        response: oneshot::Sender<QuickDbResult<ChangeStream>>,
+    },
+    /// 携带会话上下文的请求，由后台任务展开后在该上下文中处理内部请求
+    WithSessionContext {
+        context: SessionContext,
+        request: Box<OdmRequest>,
+    },
+    /// 携带操作类别的请求，由后台任务展开后以该类别处理内部请求
+    WithOperationClass {
+        class: OperationClass,
+        request: Box<OdmRequest>,
+    },
+}
+
+impl OdmRequest {
+    /// 附加当前任务的会话上下文和操作类别（如果有）
+    ///
+    /// 交互操作是默认类别，不需要附加
+    pub(crate) fn with_current_task_context(self) -> Self {
+        let request = match current_session_context() {
+            Some(context) => OdmRequest::WithSessionContext {
+                context,
+                request: Box::new(self),
+            },
+            None => self,
+        };
+        match current_operation_class() {
+            OperationClass::Interactive => request,
+            class => OdmRequest::WithOperationClass {
+                class,
+                request: Box::new(request),
+            },
+        }
+    }
+
+    /// 拆分出会话上下文、操作类别与实际要处理的请求，嵌套时以最内层设置为准
+    pub(crate) fn split_task_context(self) -> (Option<SessionContext>, OperationClass, OdmRequest) {
+        let mut context = None;
+        let mut class = OperationClass::default();
+        let mut request = self;
+        loop {
+            request = match request {
+                OdmRequest::WithSessionContext { context: inner_context, request: inner } => {
+                    context = Some(inner_context);
+                    *inner
+                }
+                OdmRequest::WithOperationClass { class: inner_class, request: inner } => {
+                    class = inner_class;
+                    *inner
+                }
+                request => return (context, class, request),
+            };
+        }
+    }
 }
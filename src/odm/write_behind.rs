@@ -0,0 +1,181 @@
+//! # 写后缓冲
+//!
+//! 为配置了 [`WriteBehindConfig`] 的别名缓冲单条创建请求，由每个别名独立的后台刷新任务
+//! 在缓冲区达到 `max_batch` 条或每隔 `max_delay_ms` 毫秒时，按集合分组以批量插入写入数据库。
+//! 后台刷新失败的数据只记录错误日志，无法再通知原调用方。
+
+use crate::error::QuickDbResult;
+use crate::odm::manager_core::AsyncOdmManager;
+use crate::types::*;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rat_logger::{debug, error, info};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+/// 各别名的写后缓冲区
+static WRITE_BEHIND_BUFFERS: Lazy<DashMap<String, Arc<WriteBehindBuffer>>> =
+    Lazy::new(DashMap::new);
+
+/// 单个别名的写后缓冲区
+struct WriteBehindBuffer {
+    alias: String,
+    config: WriteBehindConfig,
+    /// 按入队顺序排列的待写入记录（集合名，数据）
+    pending: Mutex<Vec<(String, HashMap<String, DataValue>)>>,
+    /// 缓冲区达到批量阈值时唤醒刷新任务
+    notify: Notify,
+    /// 串行化刷新，保证批次按入队顺序写入
+    flush_lock: tokio::sync::Mutex<()>,
+    /// 后台刷新任务句柄
+    task_handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl WriteBehindBuffer {
+    fn new(alias: &str, config: WriteBehindConfig) -> Arc<Self> {
+        let buffer = Arc::new(Self {
+            alias: alias.to_string(),
+            config,
+            pending: Mutex::new(Vec::new()),
+            notify: Notify::new(),
+            flush_lock: tokio::sync::Mutex::new(()),
+            task_handle: Mutex::new(None),
+        });
+
+        let handle = tokio::spawn(Self::run_flusher(buffer.clone()));
+        *buffer.task_handle.lock() = Some(handle);
+
+        info!("启动写后缓冲刷新任务: alias={}, max_batch={}, max_delay_ms={}",
+            alias, config.max_batch, config.max_delay_ms);
+        buffer
+    }
+
+    /// 后台刷新循环：超时或缓冲区满时刷新
+    async fn run_flusher(buffer: Arc<Self>) {
+        let max_delay = Duration::from_millis(buffer.config.max_delay_ms.max(1));
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(max_delay) => {},
+                _ = buffer.notify.notified() => {},
+            }
+            if let Err(e) = buffer.flush().await {
+                error!("写后缓冲刷新失败，本批数据已丢弃: alias={}, error={}", buffer.alias, e);
+            }
+        }
+    }
+
+    fn enqueue(&self, collection: &str, data: HashMap<String, DataValue>) {
+        let len = {
+            let mut pending = self.pending.lock();
+            pending.push((collection.to_string(), data));
+            pending.len()
+        };
+        if len >= self.config.max_batch.max(1) {
+            self.notify.notify_one();
+        }
+    }
+
+    /// 取出当前缓冲区的全部记录并写入数据库，返回写入的记录数
+    ///
+    /// 连续相同集合的记录合并为一批，单批不超过 `max_batch` 条；
+    /// 某一批失败时其余批次仍会写入，返回第一个错误
+    async fn flush(&self) -> QuickDbResult<usize> {
+        let _guard = self.flush_lock.lock().await;
+        let records = std::mem::take(&mut *self.pending.lock());
+        if records.is_empty() {
+            return Ok(0);
+        }
+
+        let total = records.len();
+        let max_batch = self.config.max_batch.max(1);
+        let mut batches: Vec<(String, Vec<HashMap<String, DataValue>>)> = Vec::new();
+        for (collection, data) in records {
+            match batches.last_mut() {
+                Some((last, list)) if *last == collection && list.len() < max_batch => list.push(data),
+                _ => batches.push((collection, vec![data])),
+            }
+        }
+
+        debug!("刷新写后缓冲: alias={}, records={}, batches={}", self.alias, total, batches.len());
+
+        let mut first_error = None;
+        for (collection, data_list) in batches {
+            let count = data_list.len();
+            if let Err(e) = AsyncOdmManager::handle_create_many(&collection, data_list, Some(self.alias.clone())).await {
+                error!("写后缓冲批量写入失败: alias={}, collection={}, count={}, error={}",
+                    self.alias, collection, count, e);
+                first_error.get_or_insert(e);
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(total),
+        }
+    }
+
+    fn stop(&self) {
+        if let Some(handle) = self.task_handle.lock().take() {
+            handle.abort();
+        }
+    }
+}
+
+/// 将一条已填充ID的记录放入别名的写后缓冲区，首次使用时启动该别名的刷新任务
+pub(crate) fn enqueue_write_behind(
+    alias: &str,
+    config: WriteBehindConfig,
+    collection: &str,
+    data: HashMap<String, DataValue>,
+) {
+    let buffer = WRITE_BEHIND_BUFFERS
+        .entry(alias.to_string())
+        .or_insert_with(|| WriteBehindBuffer::new(alias, config))
+        .clone();
+    buffer.enqueue(collection, data);
+}
+
+/// 立即刷新写后缓冲区，返回写入的记录数
+///
+/// `alias` 为 `None` 时刷新所有别名。写入失败的批次会被丢弃并返回第一个错误，
+/// 需要确认数据已落库时（例如读取刚写入的数据前）应先调用此函数
+pub async fn flush_write_behind(alias: Option<&str>) -> QuickDbResult<usize> {
+    let buffers: Vec<Arc<WriteBehindBuffer>> = match alias {
+        Some(alias) => WRITE_BEHIND_BUFFERS.get(alias).map(|b| b.clone()).into_iter().collect(),
+        None => WRITE_BEHIND_BUFFERS.iter().map(|b| b.value().clone()).collect(),
+    };
+
+    let mut total = 0;
+    let mut first_error = None;
+    for buffer in buffers {
+        match buffer.flush().await {
+            Ok(count) => total += count,
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(total),
+    }
+}
+
+/// 关闭时刷新所有写后缓冲区并停止刷新任务
+pub(crate) async fn shutdown_write_behind() -> QuickDbResult<()> {
+    let result = flush_write_behind(None).await;
+    for buffer in WRITE_BEHIND_BUFFERS.iter() {
+        buffer.stop();
+    }
+    WRITE_BEHIND_BUFFERS.clear();
+    result.map(|count| {
+        if count > 0 {
+            info!("关闭前已刷新写后缓冲记录: {}", count);
+        }
+    })
+}
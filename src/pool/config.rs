@@ -1,5 +1,9 @@
 //! 连接池配置模块
 
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
 use crate::types::*;
 
 /// 连接池配置扩展
@@ -39,10 +43,18 @@ impl ExtendedPoolConfig {
                 connection_timeout: pool_config.connection_timeout,
                 idle_timeout: pool_config.idle_timeout,
                 max_lifetime: pool_config.max_lifetime,
+                max_lifetime_jitter: pool_config.max_lifetime_jitter,
                 max_retries: pool_config.max_retries,
                 retry_interval_ms: pool_config.retry_interval_ms,
+                retry_attempts: pool_config.retry_attempts,
+                retry_base_delay_ms: pool_config.retry_base_delay_ms,
+                retry_max_delay_ms: pool_config.retry_max_delay_ms,
                 keepalive_interval_sec: pool_config.keepalive_interval_sec,
                 health_check_timeout_sec: pool_config.health_check_timeout_sec,
+                test_before_acquire: pool_config.test_before_acquire,
+                sampling: pool_config.sampling,
+                reserved_interactive_connections: pool_config.reserved_interactive_connections,
+                max_batch_operations: pool_config.max_batch_operations,
             },
             max_retries: pool_config.max_retries,
             retry_interval_ms: pool_config.retry_interval_ms,
@@ -50,4 +62,109 @@ impl ExtendedPoolConfig {
             health_check_timeout_sec: pool_config.health_check_timeout_sec,
         }
     }
+
+    /// 按交互保留连接数拆分出交互通道和批量通道的配置，未保留连接时返回 `None`
+    ///
+    /// 交互通道使用保留的连接，批量通道使用其余连接，两者的最小连接数不超过各自的最大连接数
+    pub fn split_batch_lane(&self) -> Option<(Self, Self)> {
+        let reserved = self.base.reserved_interactive_connections;
+        if reserved == 0 || reserved >= self.base.max_connections {
+            return None;
+        }
+        let mut interactive = self.clone();
+        interactive.base.max_connections = reserved;
+        interactive.base.min_connections = self.base.min_connections.min(reserved);
+        let mut batch = self.clone();
+        batch.base.max_connections = self.base.max_connections - reserved;
+        batch.base.min_connections = self.base.min_connections.min(batch.base.max_connections);
+        Some((interactive, batch))
+    }
+
+    /// 生存时间抖动设置，未配置抖动时返回 `None`
+    ///
+    /// 每次调用生成新的随机种子，一个连接池使用同一份设置
+    pub(crate) fn lifetime_jitter(&self) -> Option<LifetimeJitter> {
+        let jitter = self.base.max_lifetime_jitter.min(self.base.max_lifetime);
+        (jitter > 0).then(|| LifetimeJitter {
+            window_start: Duration::from_secs(self.base.max_lifetime - jitter),
+            jitter: Duration::from_secs(jitter),
+            seed: rand::random(),
+        })
+    }
+
+    /// 第 `attempt` 次重试（从0开始）前的等待时间
+    ///
+    /// 以 `retry_base_delay_ms` 为基数、`retry_max_delay_ms` 为上限，按共用的退避规则取值，
+    /// `roll` 为 `[0, 1)` 区间内的随机数，避免连接中断后各工作器同时重连
+    pub(crate) fn retry_delay(&self, attempt: u32, roll: f64) -> Duration {
+        crate::transaction::retry::jittered_backoff(
+            self.base.retry_base_delay_ms,
+            self.base.retry_max_delay_ms,
+            attempt,
+            roll,
+        )
+    }
+}
+
+/// 连接生存时间抖动
+///
+/// 每个连接的退役年龄由连接池的随机种子和数据库端连接ID哈希得到，连接创建后即固定，
+/// 不同连接均匀分布在 `[max_lifetime - max_lifetime_jitter, max_lifetime)` 区间内。
+/// 超过 `max_lifetime` 的连接由连接池本身回收，这里不再处理
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LifetimeJitter {
+    /// 抖动窗口起点，更年轻的连接无需判断
+    pub(crate) window_start: Duration,
+    jitter: Duration,
+    seed: u64,
+}
+
+impl LifetimeJitter {
+    /// 数据库端ID为 `connection_id` 的连接的退役年龄
+    pub(crate) fn retire_age(&self, connection_id: u64) -> Duration {
+        let mut hasher = DefaultHasher::new();
+        (self.seed, connection_id).hash(&mut hasher);
+        let fraction = (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64;
+        self.window_start + self.jitter.mul_f64(fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifetime_jitter_fixes_retire_age_per_connection() {
+        let mut config = ExtendedPoolConfig::default();
+        config.base.max_lifetime = 600;
+        config.base.max_lifetime_jitter = 60;
+        let jitter = config.lifetime_jitter().unwrap();
+        assert_eq!(jitter.window_start, Duration::from_secs(540));
+
+        // 同一连接每次获取时退役年龄相同，不同连接分散在窗口内
+        let ages: Vec<Duration> = (0..200).map(|id| jitter.retire_age(id)).collect();
+        assert!(ages.iter().all(|age| *age >= Duration::from_secs(540) && *age < Duration::from_secs(600)));
+        assert!((0..200).all(|id| jitter.retire_age(id) == ages[id as usize]));
+        assert!(ages.iter().any(|age| *age < Duration::from_secs(560)));
+        assert!(ages.iter().any(|age| *age >= Duration::from_secs(580)));
+
+        // 未配置抖动时不检查
+        config.base.max_lifetime_jitter = 0;
+        assert!(config.lifetime_jitter().is_none());
+    }
+
+    #[test]
+    fn test_retry_delay_grows_exponentially_up_to_cap() {
+        let mut config = ExtendedPoolConfig::default();
+        config.base.retry_base_delay_ms = 100;
+        config.base.retry_max_delay_ms = 1000;
+
+        assert_eq!(config.retry_delay(0, 0.0), Duration::from_millis(50));
+        assert_eq!(config.retry_delay(0, 0.99), Duration::from_millis(99));
+        assert_eq!(config.retry_delay(1, 0.0), Duration::from_millis(100));
+        assert_eq!(config.retry_delay(2, 0.5), Duration::from_millis(300));
+        // 超过上限后不再增长
+        assert_eq!(config.retry_delay(4, 0.0), Duration::from_millis(500));
+        assert_eq!(config.retry_delay(40, 0.0), Duration::from_millis(500));
+    }
 }
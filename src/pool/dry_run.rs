@@ -0,0 +1,97 @@
+//! 试运行模块
+//!
+//! 配置了 `dry_run` 的别名上，插入、更新和删除照常完成校验、生成并执行语句，
+//! 但在适配器事务作用域中执行，结束后总是回滚。调用方得到的返回值（影响行数、插入的ID等）
+//! 即为实际执行时的预期结果，数据库不会发生任何变化。MongoDB 在会话事务中执行，结束后总是中止，
+//! 需要副本集或分片集群部署。
+
+use std::future::Future;
+use rat_logger::{error, info};
+
+use crate::adapter::DatabaseAdapter;
+use crate::adapter::transaction::AdapterTransaction;
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::transaction::IsolationLevel;
+use super::{DatabaseConnection, DatabaseOperation};
+
+/// 一次进行中的试运行，持有最终要回滚的事务
+pub(crate) struct DryRun {
+    transaction: AdapterTransaction,
+    alias: String,
+    operation: &'static str,
+    table: String,
+}
+
+impl DryRun {
+    /// 为写操作开启试运行事务
+    ///
+    /// 操作不支持试运行，或目标表不存在（试运行不会自动建表）时返回错误，调用方应以该错误拒绝操作
+    pub(crate) async fn begin(
+        alias: &str,
+        adapter: &dyn DatabaseAdapter,
+        connection: &DatabaseConnection,
+        operation: &DatabaseOperation,
+    ) -> QuickDbResult<Self> {
+        let (name, table) = Self::target(alias, adapter, connection, operation).await?;
+        AdapterTransaction::ensure_supported(connection)?;
+        let transaction = AdapterTransaction::begin(connection, IsolationLevel::BackendDefault).await?;
+        Ok(Self {
+            transaction,
+            alias: alias.to_string(),
+            operation: name,
+            table: table.to_string(),
+        })
+    }
+
+    /// 为MongoDB写操作开启试运行会话事务，结束后总是中止
+    ///
+    /// `client` 必须是创建 `connection` 的客户端；会话事务需要副本集或分片集群部署
+    #[cfg(feature = "mongodb-support")]
+    pub(crate) async fn begin_mongo(
+        alias: &str,
+        adapter: &dyn DatabaseAdapter,
+        connection: &DatabaseConnection,
+        client: &mongodb::Client,
+        operation: &DatabaseOperation,
+    ) -> QuickDbResult<Self> {
+        let (name, table) = Self::target(alias, adapter, connection, operation).await?;
+        let transaction = AdapterTransaction::begin_mongo(client, IsolationLevel::BackendDefault).await?;
+        Ok(Self {
+            transaction,
+            alias: alias.to_string(),
+            operation: name,
+            table: table.to_string(),
+        })
+    }
+
+    /// 检查操作可以试运行且目标表存在，返回操作名称和目标表
+    async fn target<'a>(
+        alias: &str,
+        adapter: &dyn DatabaseAdapter,
+        connection: &DatabaseConnection,
+        operation: &'a DatabaseOperation,
+    ) -> QuickDbResult<(&'static str, &'a str)> {
+        let (name, table) = operation.dry_run_target().ok_or_else(|| QuickDbError::UnsupportedDatabase {
+            db_type: format!("别名 {} 处于试运行模式，只能执行插入、更新和删除", alias),
+        })?;
+
+        if !adapter.table_exists(connection, table).await? {
+            return Err(QuickDbError::ValidationError {
+                field: "table".to_string(),
+                message: format!("试运行不会自动建表，表 {} 不存在", table),
+            });
+        }
+        Ok((name, table))
+    }
+
+    /// 在试运行事务中执行操作，结束后回滚
+    pub(crate) async fn run<F: Future<Output = QuickDbResult<()>>>(self, future: F) -> QuickDbResult<()> {
+        info!("试运行: alias={}, operation={}, table={}", self.alias, self.operation, self.table);
+        let result = self.transaction.scope(future).await;
+        match self.transaction.rollback().await {
+            Ok(()) => info!("试运行结束，已回滚: alias={}, operation={}, table={}", self.alias, self.operation, self.table),
+            Err(e) => error!("回滚试运行事务失败: alias={}, error={}", self.alias, e),
+        }
+        result
+    }
+}
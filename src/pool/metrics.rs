@@ -0,0 +1,222 @@
+//! 连接池使用情况采样模块
+//!
+//! 工作器维护排队和执行中的操作计数，并登记当前的底层连接；
+//! 启用采样时后台任务按间隔读取这些数据写入环形缓冲区
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{PoolConfig, PoolSamplingConfig};
+use super::DatabaseConnection;
+
+/// 连接池使用情况样本
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolSample {
+    /// 采样时间
+    pub timestamp: DateTime<Utc>,
+    /// 当前打开的连接数
+    pub connections: u32,
+    /// 使用中的连接数
+    pub in_use: u32,
+    /// 空闲连接数
+    pub idle: u32,
+    /// 已提交但尚未被工作器取出的操作数
+    pub waiters: usize,
+}
+
+/// 连接池实时状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolStatus {
+    /// 当前打开的连接数
+    pub total_connections: u32,
+    /// 使用中的连接数
+    pub active_connections: u32,
+    /// 空闲连接数
+    pub idle_connections: u32,
+    /// 等待连接的操作数（已提交但尚未被工作器取出，含等待批量操作许可的操作）
+    pub waiting_connections: usize,
+    /// 配置的最小连接数
+    pub min_connections: u32,
+    /// 配置的最大连接数
+    pub max_connections: u32,
+    /// 配置的批量操作并发上限，0 表示不限制
+    pub max_batch_operations: u32,
+}
+
+/// 连接池使用情况
+#[derive(Debug, Default)]
+pub struct PoolUsage {
+    /// 排队中的操作数
+    queued: AtomicUsize,
+    /// 执行中的操作数
+    active: AtomicUsize,
+    /// 工作器当前使用的底层连接
+    connection: RwLock<Option<DatabaseConnection>>,
+    /// 采样历史
+    history: Mutex<VecDeque<PoolSample>>,
+}
+
+impl PoolUsage {
+    /// 操作进入队列
+    pub(crate) fn operation_queued(&self) {
+        self.queued.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 工作器取出操作开始执行
+    pub(crate) fn operation_started(&self) {
+        // 工作器可能在计数前就取到了操作，避免下溢
+        let _ = self.queued.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+        self.active.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 操作执行完成
+    pub(crate) fn operation_finished(&self) {
+        let _ = self.active.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+    }
+
+    /// 已提交但尚未完成的操作数（排队中与执行中之和）
+    pub fn in_flight(&self) -> usize {
+        self.queued.load(Ordering::Relaxed) + self.active.load(Ordering::Relaxed)
+    }
+
+    /// 登记工作器当前使用的底层连接（启动和重连时调用）
+    pub(crate) fn set_connection(&self, connection: DatabaseConnection) {
+        *self.connection.write() = Some(connection);
+    }
+
+    /// 读取当前使用情况
+    ///
+    /// SQL数据库读取sqlx连接池的连接数和空闲数；MongoDB驱动不暴露连接池状态，
+    /// 以执行中的操作数作为使用中连接数
+    pub fn sample(&self) -> PoolSample {
+        let active = self.active.load(Ordering::Relaxed) as u32;
+        let (connections, idle) = match &*self.connection.read() {
+            #[cfg(feature = "sqlite-support")]
+            Some(DatabaseConnection::SQLite(pool)) => (pool.size(), pool.num_idle() as u32),
+            #[cfg(feature = "postgres-support")]
+            Some(DatabaseConnection::PostgreSQL(pool)) => (pool.size(), pool.num_idle() as u32),
+            #[cfg(feature = "mysql-support")]
+            Some(DatabaseConnection::MySQL(pool)) => (pool.size(), pool.num_idle() as u32),
+            _ => (active, 0),
+        };
+
+        PoolSample {
+            timestamp: Utc::now(),
+            connections,
+            in_use: connections.saturating_sub(idle),
+            idle,
+            waiters: self.queued.load(Ordering::Relaxed),
+        }
+    }
+
+    /// 按连接池配置读取实时状态
+    ///
+    /// 连接数来自 [`PoolUsage::sample`]，与采样历史的口径一致
+    pub fn status(&self, config: &PoolConfig) -> PoolStatus {
+        let sample = self.sample();
+        PoolStatus {
+            total_connections: sample.connections,
+            active_connections: sample.in_use,
+            idle_connections: sample.idle,
+            waiting_connections: sample.waiters,
+            min_connections: config.min_connections,
+            max_connections: config.max_connections,
+            max_batch_operations: config.max_batch_operations,
+        }
+    }
+
+    /// 写入一个样本，超出容量时丢弃最早的样本
+    fn record(&self, sample: PoolSample, capacity: usize) {
+        let mut history = self.history.lock();
+        while history.len() >= capacity {
+            history.pop_front();
+        }
+        history.push_back(sample);
+    }
+
+    /// 按时间顺序返回保留的样本
+    pub fn history(&self) -> Vec<PoolSample> {
+        self.history.lock().iter().cloned().collect()
+    }
+}
+
+/// 启动采样任务，连接池及其工作器释放后任务自动结束
+pub(crate) fn spawn_sampler(usage: &Arc<PoolUsage>, config: PoolSamplingConfig) {
+    let usage: Weak<PoolUsage> = Arc::downgrade(usage);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_millis(config.interval_ms.max(1)));
+        loop {
+            ticker.tick().await;
+            let Some(usage) = usage.upgrade() else {
+                break;
+            };
+            let sample = usage.sample();
+            usage.record(sample, config.capacity.max(1));
+        }
+    });
+}
+
+/// 计算样本中使用中连接数的百分位（`percentile` 取值 0.0-1.0），没有样本时返回 `None`
+pub fn in_use_percentile(samples: &[PoolSample], percentile: f64) -> Option<u32> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut values: Vec<u32> = samples.iter().map(|sample| sample.in_use).collect();
+    values.sort_unstable();
+    let rank = (percentile.clamp(0.0, 1.0) * values.len() as f64).ceil() as usize;
+    Some(values[rank.saturating_sub(1).min(values.len() - 1)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(in_use: u32) -> PoolSample {
+        PoolSample {
+            timestamp: Utc::now(),
+            connections: 10,
+            in_use,
+            idle: 10 - in_use,
+            waiters: 0,
+        }
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let usage = PoolUsage::default();
+        for i in 0..5 {
+            usage.record(sample(i), 3);
+        }
+        let in_use: Vec<u32> = usage.history().iter().map(|s| s.in_use).collect();
+        assert_eq!(in_use, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_operation_counters() {
+        let usage = PoolUsage::default();
+        usage.operation_queued();
+        usage.operation_queued();
+        usage.operation_started();
+        assert_eq!(usage.sample().waiters, 1);
+        usage.operation_finished();
+        usage.operation_finished();
+        usage.operation_started();
+        usage.operation_started();
+        assert_eq!(usage.sample().waiters, 0);
+    }
+
+    #[test]
+    fn test_in_use_percentile() {
+        assert_eq!(in_use_percentile(&[], 0.95), None);
+
+        let samples: Vec<PoolSample> = (1..=10).map(sample).collect();
+        assert_eq!(in_use_percentile(&samples, 0.95), Some(10));
+        assert_eq!(in_use_percentile(&samples, 0.5), Some(5));
+        assert_eq!(in_use_percentile(&samples, 0.0), Some(1));
+    }
+}
@@ -10,6 +10,8 @@ pub mod config;
 pub mod pool;
 pub mod sqlite_worker;
 pub mod multi_connection_manager;
+pub mod metrics;
+pub(crate) mod dry_run;
 
 // 重新导出主要的公共类型和结构体
 pub use types::{PooledConnection, DatabaseOperation, DatabaseConnection, ConnectionWorker};
@@ -17,4 +19,5 @@ pub use config::ExtendedPoolConfig;
 pub use pool::ConnectionPool;
 #[cfg(feature = "sqlite-support")]
 pub use sqlite_worker::SqliteWorker;
-pub use multi_connection_manager::MultiConnectionManager;
\ No newline at end of file
+pub use multi_connection_manager::MultiConnectionManager;
+pub use metrics::{PoolSample, PoolStatus, PoolUsage, in_use_percentile};
\ No newline at end of file
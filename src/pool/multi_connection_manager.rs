@@ -13,6 +13,27 @@ use crate::types::*;
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::adapter::DatabaseAdapter;
 use super::{ConnectionWorker, DatabaseConnection, DatabaseOperation, ExtendedPoolConfig};
+use super::types::{empty_on_missing_table, retry_transient};
+use super::dry_run::DryRun;
+use crate::types::query_log::with_query_log;
+
+/// 各别名最近创建的MongoDB客户端
+///
+/// `mongodb::Database` 不暴露所属客户端，显式事务需要从客户端开启会话
+#[cfg(feature = "mongodb-support")]
+static MONGO_CLIENTS: once_cell::sync::Lazy<dashmap::DashMap<String, mongodb::Client>> =
+    once_cell::sync::Lazy::new(dashmap::DashMap::new);
+
+#[cfg(feature = "mongodb-support")]
+fn register_mongo_client(alias: &str, client: mongodb::Client) {
+    MONGO_CLIENTS.insert(alias.to_string(), client);
+}
+
+/// 获取别名对应的MongoDB客户端
+#[cfg(feature = "mongodb-support")]
+pub(crate) fn mongo_client(alias: &str) -> Option<mongodb::Client> {
+    MONGO_CLIENTS.get(alias).map(|client| client.clone())
+}
 
 /// 多连接工作器管理器（用于MySQL/PostgreSQL/MongoDB）
 pub struct MultiConnectionManager {
@@ -30,6 +51,12 @@ pub struct MultiConnectionManager {
     pub(crate) keepalive_handle: Option<tokio::task::JoinHandle<()>>,
     /// 缓存管理器（可选）
     pub(crate) cache_manager: Option<Arc<crate::cache::CacheManager>>,
+    /// 连接池使用情况
+    pub(crate) usage: Arc<super::PoolUsage>,
+    /// N+1 查询检测器（可选）
+    pub(crate) n_plus_one: Option<Arc<crate::types::n_plus_one::NPlusOneDetector>>,
+    /// 是否为批量通道，批量通道不登记连接用于采样
+    pub(crate) batch_lane: bool,
 }
 impl MultiConnectionManager {
     /// 创建初始连接
@@ -37,6 +64,9 @@ impl MultiConnectionManager {
         
         // 只创建1个worker进行测试
         let worker = self.create_connection_worker(0).await?;
+        if !self.batch_lane {
+            self.usage.set_connection(worker.connection.clone());
+        }
         self.workers.push(worker);
         self.available_workers.push(0);
 
@@ -45,16 +75,24 @@ impl MultiConnectionManager {
     
     /// 创建连接工作器
     async fn create_connection_worker(&self, index: usize) -> QuickDbResult<ConnectionWorker> {
+        #[cfg(feature = "mongodb-support")]
+        let (connection, mongo_client) = match self.db_config.db_type {
+            DatabaseType::MongoDB => {
+                let client = self.create_mongo_client().await?;
+                (DatabaseConnection::MongoDB(client.database(self.mongo_database_name()?)), Some(client))
+            }
+            _ => (self.create_database_connection().await?, None),
+        };
+        #[cfg(not(feature = "mongodb-support"))]
         let connection = self.create_database_connection().await?;
         
-        // 创建适配器
-        use crate::adapter::{create_adapter, create_adapter_with_cache};
+        use crate::adapter::CachedDatabaseAdapter;
+        let base_adapter = crate::adapter::create_configured_adapter(&self.db_config)?;
         let (adapter, adapter_type) = if let Some(cache_manager) = &self.cache_manager {
-            let adapter = create_adapter_with_cache(&self.db_config.db_type, cache_manager.clone())?;
+            let adapter: Box<dyn DatabaseAdapter> = Box::new(CachedDatabaseAdapter::new(base_adapter, cache_manager.clone()));
             (adapter, "缓存适配器")
         } else {
-            let adapter = create_adapter(&self.db_config.db_type)?;
-            (adapter, "普通适配器")
+            (base_adapter, "普通适配器")
         };
         
         debug!("数据库 '{}' 使用 {}", self.db_config.alias, adapter_type);
@@ -68,6 +106,8 @@ impl MultiConnectionManager {
             retry_count: 0,
             db_type: self.db_config.db_type.clone(),
             adapter,
+            #[cfg(feature = "mongodb-support")]
+            mongo_client,
         })
     }
     
@@ -77,10 +117,14 @@ impl MultiConnectionManager {
             #[cfg(feature = "postgres-support")]
             DatabaseType::PostgreSQL => {
                 let connection_string = match &self.db_config.connection {
-                    crate::types::ConnectionConfig::PostgreSQL { host, port, database, username, password, ssl_mode: _, tls_config: _ } => {
+                    crate::types::ConnectionConfig::PostgreSQL { host, port, database, username, password, ssl_mode: _, tls_config: _, create_database_if_missing: _ } => {
                         // 对密码进行 URL 编码以处理特殊字符
                         let encoded_password = urlencoding::encode(password);
-                        format!("postgresql://{}:{}@{}:{}/{}", username, encoded_password, host, port, database)
+                        let application_name = urlencoding::encode(&self.db_config.effective_application_name()).into_owned();
+                        format!(
+                            "postgresql://{}:{}@{}:{}/{}?application_name={}",
+                            username, encoded_password, host, port, database, application_name
+                        )
                     }
                     _ => return Err(QuickDbError::ConfigError {
                         message: "PostgreSQL连接配置类型不匹配".to_string(),
@@ -89,12 +133,24 @@ impl MultiConnectionManager {
 
   
                 // 使用PgPoolOptions创建连接池 - 使用配置值
-                let pool = sqlx::postgres::PgPoolOptions::new()
+                let mut pool_options = sqlx::postgres::PgPoolOptions::new()
                     .max_connections(self.config.base.max_connections)
                     .min_connections(self.config.base.min_connections)
                     .max_lifetime(std::time::Duration::from_secs(self.config.base.max_lifetime))
                     .idle_timeout(std::time::Duration::from_secs(self.config.base.idle_timeout))
                     .acquire_timeout(std::time::Duration::from_millis(self.config.base.connection_timeout))
+                    .test_before_acquire(self.config.base.test_before_acquire);
+                if let Some(jitter) = self.config.lifetime_jitter() {
+                    pool_options = pool_options.before_acquire(move |conn, meta| Box::pin(async move {
+                        if meta.age < jitter.window_start {
+                            return Ok(true);
+                        }
+                        // 进入抖动窗口后才查询连接ID，按该连接固定的退役年龄判断
+                        let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()").fetch_one(&mut *conn).await?;
+                        Ok(meta.age < jitter.retire_age(pid as u64))
+                    }));
+                }
+                let pool = pool_options
                     .connect(&connection_string)
                     .await
                     .map_err(|e| QuickDbError::ConnectionError {
@@ -106,7 +162,7 @@ impl MultiConnectionManager {
             #[cfg(feature = "mysql-support")]
             DatabaseType::MySQL => {
                 let connection_string = match &self.db_config.connection {
-                    crate::types::ConnectionConfig::MySQL { host, port, database, username, password, ssl_opts: _, tls_config: _ } => {
+                    crate::types::ConnectionConfig::MySQL { host, port, database, username, password, ssl_opts: _, tls_config: _, create_database_if_missing: _ } => {
                         // 对密码进行 URL 编码以处理特殊字符
                         let encoded_password = urlencoding::encode(password);
                         format!("mysql://{}:{}@{}:{}/{}", username, encoded_password, host, port, database)
@@ -117,12 +173,24 @@ impl MultiConnectionManager {
                 };
 
                 // 创建带有连接池配置的MySQL连接池
-                let mysql_pool = sqlx::mysql::MySqlPoolOptions::new()
+                let mut pool_options = sqlx::mysql::MySqlPoolOptions::new()
                     .min_connections(self.config.base.min_connections)
                     .max_connections(self.config.base.max_connections)
                     .acquire_timeout(std::time::Duration::from_millis(self.config.base.connection_timeout))
-                    .idle_timeout(std::time::Duration::from_millis(self.config.base.idle_timeout))
-                    .max_lifetime(std::time::Duration::from_millis(self.config.base.max_lifetime))
+                    .idle_timeout(std::time::Duration::from_secs(self.config.base.idle_timeout))
+                    .max_lifetime(std::time::Duration::from_secs(self.config.base.max_lifetime))
+                    .test_before_acquire(self.config.base.test_before_acquire);
+                if let Some(jitter) = self.config.lifetime_jitter() {
+                    pool_options = pool_options.before_acquire(move |conn, meta| Box::pin(async move {
+                        if meta.age < jitter.window_start {
+                            return Ok(true);
+                        }
+                        // 进入抖动窗口后才查询连接ID，按该连接固定的退役年龄判断
+                        let pid: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()").fetch_one(&mut *conn).await?;
+                        Ok(meta.age < jitter.retire_age(pid))
+                    }));
+                }
+                let mysql_pool = pool_options
                     .connect(&connection_string)
                     .await
                     .map_err(|e| QuickDbError::ConnectionError {
@@ -133,78 +201,94 @@ impl MultiConnectionManager {
             },
             #[cfg(feature = "mongodb-support")]
             DatabaseType::MongoDB => {
-                let connection_uri = match &self.db_config.connection {
-                    crate::types::ConnectionConfig::MongoDB {
-                        host, port, database, username, password,
-                        auth_source, direct_connection, tls_config,
-                        zstd_config, options
-                    } => {
-                        // 使用构建器生成连接URI
-                        let mut builder = crate::types::MongoDbConnectionBuilder::new(
-                            host.clone(),
-                            *port,
-                            database.clone()
-                        );
+                let client = self.create_mongo_client().await?;
+                Ok(DatabaseConnection::MongoDB(client.database(self.mongo_database_name()?)))
+            },
+            _ => Err(QuickDbError::ConfigError {
+                message: "不支持的数据库类型用于多连接管理器（可能需要启用相应的feature）".to_string(),
+            }),
+        }
+    }
+    
+    /// 创建MongoDB客户端并登记为该别名最近创建的客户端
+    #[cfg(feature = "mongodb-support")]
+    async fn create_mongo_client(&self) -> QuickDbResult<mongodb::Client> {
+        let connection_uri = match &self.db_config.connection {
+            crate::types::ConnectionConfig::MongoDB {
+                host, port, database, username, password,
+                auth_source, direct_connection, tls_config,
+                zstd_config, options
+            } => {
+                // 使用构建器生成连接URI
+                let mut builder = crate::types::MongoDbConnectionBuilder::new(
+                    host.clone(),
+                    *port,
+                    database.clone()
+                );
 
-                        // 设置认证信息
-                        if let (Some(user), Some(pass)) = (username, password) {
-                            builder = builder.with_auth(user.clone(), pass.clone());
-                        }
+                // 设置认证信息
+                if let (Some(user), Some(pass)) = (username, password) {
+                    builder = builder.with_auth(user.clone(), pass.clone());
+                }
 
-                        // 设置认证数据库
-                        if let Some(auth_src) = auth_source {
-                            builder = builder.with_auth_source(auth_src.clone());
-                        }
+                // 设置认证数据库
+                if let Some(auth_src) = auth_source {
+                    builder = builder.with_auth_source(auth_src.clone());
+                }
 
-                        // 设置直接连接
-                        builder = builder.with_direct_connection(*direct_connection);
+                // 设置直接连接
+                builder = builder.with_direct_connection(*direct_connection);
 
-                        // 设置TLS配置
-                        if let Some(tls) = tls_config {
-                            builder = builder.with_tls_config(tls.clone());
-                        }
+                // 上报应用名称，自定义选项中的 appName 优先
+                builder = builder.with_option("appName", self.db_config.effective_application_name());
 
-                        // 设置ZSTD压缩配置
-                        if let Some(zstd) = zstd_config {
-                            builder = builder.with_zstd_config(zstd.clone());
-                        }
+                // 设置TLS配置
+                if let Some(tls) = tls_config {
+                    builder = builder.with_tls_config(tls.clone());
+                }
 
-                        // 添加自定义选项
-                        if let Some(opts) = options {
-                            for (key, value) in opts {
-                                builder = builder.with_option(key.clone(), value.clone());
-                            }
-                        }
+                // 设置ZSTD压缩配置
+                if let Some(zstd) = zstd_config {
+                    builder = builder.with_zstd_config(zstd.clone());
+                }
 
-                        builder.build_uri()
+                // 添加自定义选项
+                if let Some(opts) = options {
+                    for (key, value) in opts {
+                        builder = builder.with_option(key.clone(), value.clone());
                     }
-                    _ => return Err(QuickDbError::ConfigError {
-                        message: "MongoDB连接配置类型不匹配".to_string(),
-                    }),
-                };
+                }
 
-                debug!("MongoDB连接URI: {}", connection_uri);
+                builder.build_uri()
+            }
+            _ => return Err(QuickDbError::ConfigError {
+                message: "MongoDB连接配置类型不匹配".to_string(),
+            }),
+        };
 
-                let client = mongodb::Client::with_uri_str(&connection_uri)
-                    .await
-                    .map_err(|e| QuickDbError::ConnectionError {
-                        message: format!("MongoDB连接失败: {}", e),
-                    })?;
+        debug!("MongoDB连接URI: {}", connection_uri);
 
-                let database_name = match &self.db_config.connection {
-                    crate::types::ConnectionConfig::MongoDB { database, .. } => database.clone(),
-                    _ => unreachable!(),
-                };
+        let client = mongodb::Client::with_uri_str(&connection_uri)
+            .await
+            .map_err(|e| QuickDbError::ConnectionError {
+                message: format!("MongoDB连接失败: {}", e),
+            })?;
 
-                let db = client.database(&database_name);
-                Ok(DatabaseConnection::MongoDB(db))
-            },
+        register_mongo_client(&self.db_config.alias, client.clone());
+        Ok(client)
+    }
+
+    /// MongoDB连接配置中的数据库名
+    #[cfg(feature = "mongodb-support")]
+    fn mongo_database_name(&self) -> QuickDbResult<&str> {
+        match &self.db_config.connection {
+            crate::types::ConnectionConfig::MongoDB { database, .. } => Ok(database),
             _ => Err(QuickDbError::ConfigError {
-                message: "不支持的数据库类型用于多连接管理器（可能需要启用相应的feature）".to_string(),
+                message: "MongoDB连接配置类型不匹配".to_string(),
             }),
         }
     }
-    
+
     /// 启动连接保活任务
     pub fn start_keepalive_task(&mut self) {
         let keepalive_interval = Duration::from_secs(self.config.keepalive_interval_sec);
@@ -238,7 +322,16 @@ impl MultiConnectionManager {
         self.start_keepalive_task();
         
         while let Some(operation) = self.operation_receiver.recv().await {
-            if let Err(e) = self.handle_operation(operation).await {
+            self.usage.operation_started();
+            let (_batch_permit, operation) = operation.split_batch_permit();
+            let (session_context, operation) = operation.split_session_context();
+            let query_log = operation.query_log_scope(&self.db_config, self.n_plus_one.clone());
+            let result = match session_context {
+                Some(context) => with_session_context(context, with_query_log(query_log, self.process_operation(operation))).await,
+                None => with_query_log(query_log, self.process_operation(operation)).await,
+            };
+            self.usage.operation_finished();
+            if let Err(e) = result {
                 error!("多连接操作处理失败: {}", e);
             }
         }
@@ -246,6 +339,39 @@ impl MultiConnectionManager {
         debug!("多连接管理器停止运行");
     }
     
+    /// 执行操作，试运行别名上的写操作在回滚的事务中执行
+    ///
+    /// 各工作器连接的是同一个数据库，试运行事务从第一个工作器的连接池开启，
+    /// 适配器在作用域中执行语句时加入该事务，与实际处理操作的工作器无关。
+    /// MongoDB 的会话只能用于同一客户端创建的数据库句柄，管理器只创建一个工作器，会话从该工作器的客户端开启
+    async fn process_operation(&mut self, mut operation: DatabaseOperation) -> QuickDbResult<()> {
+        if let Some(precision) = self.db_config.datetime_precision {
+            operation.truncate_datetimes(precision);
+        }
+        if !(self.db_config.dry_run && operation.is_write()) {
+            return self.handle_operation(operation).await;
+        }
+        let Some(worker) = self.workers.first() else {
+            return Err(QuickDbError::ConnectionError {
+                message: "没有可用的数据库连接".to_string(),
+            });
+        };
+        #[cfg(feature = "mongodb-support")]
+        let dry_run = match &worker.mongo_client {
+            Some(client) => DryRun::begin_mongo(&self.db_config.alias, worker.adapter.as_ref(), &worker.connection, client, &operation).await,
+            None => DryRun::begin(&self.db_config.alias, worker.adapter.as_ref(), &worker.connection, &operation).await,
+        };
+        #[cfg(not(feature = "mongodb-support"))]
+        let dry_run = DryRun::begin(&self.db_config.alias, worker.adapter.as_ref(), &worker.connection, &operation).await;
+        match dry_run {
+            Ok(dry_run) => dry_run.run(self.handle_operation(operation)).await,
+            Err(e) => {
+                operation.reject(e);
+                Ok(())
+            }
+        }
+    }
+
     /// 处理数据库操作
     async fn handle_operation(&mut self, operation: DatabaseOperation) -> QuickDbResult<()> {
         // 获取可用工作器
@@ -259,94 +385,206 @@ impl MultiConnectionManager {
             }
         };
         
+        let missing_table_returns_empty = self.db_config.missing_table_returns_empty;
+
         // 获取工作器的连接
         let worker = &mut self.workers[worker_index];
         worker.last_used = Instant::now();
         
         // 处理具体操作
+        // 写操作只在请求发出之前的连接失败时重试；追加写入、SQL脚本、存储过程和变更流不重试
+        let retry = &self.config;
+        let is_write = operation.is_write();
         let result = match operation {
             DatabaseOperation::Create { table, data, id_strategy, response } => {
-                let result = worker.adapter.create(&worker.connection, &table, &data, &id_strategy).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.create(&worker.connection, &table, &data, &id_strategy)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::CreateMany { table, data_list, id_strategy, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.create_many(&worker.connection, &table, &data_list, &id_strategy)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::UpsertMany { table, rows, conflict_fields, id_strategy, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.upsert_many(&worker.connection, &table, &rows, &conflict_fields, &id_strategy)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::Upsert { table, data, conflict_fields, id_strategy, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.upsert(&worker.connection, &table, &data, &conflict_fields, &id_strategy)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::Seed { fixtures, id_strategy, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.seed(&worker.connection, &fixtures, &id_strategy)).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::FindById { table, id, response } => {
-                let result = worker.adapter.find_by_id(&worker.connection, &table, &id).await;
+                let result = empty_on_missing_table(retry_transient(retry, is_write, || worker.adapter.find_by_id(&worker.connection, &table, &id)).await, missing_table_returns_empty);
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::FindByIds { table, ids, response } => {
+                let result = empty_on_missing_table(retry_transient(retry, is_write, || worker.adapter.find_by_ids(&worker.connection, &table, &ids)).await, missing_table_returns_empty);
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::Find { table, conditions, options, response } => {
-                let result = worker.adapter.find(&worker.connection, &table, &conditions, &options).await;
+                let result = empty_on_missing_table(retry_transient(retry, is_write, || worker.adapter.find(&worker.connection, &table, &conditions, &options)).await, missing_table_returns_empty);
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::FindWithGroups { table, condition_groups, options, response } => {
-                let result = worker.adapter.find_with_groups(&worker.connection, &table, &condition_groups, &options).await;
+                let result = empty_on_missing_table(retry_transient(retry, is_write, || worker.adapter.find_with_groups(&worker.connection, &table, &condition_groups, &options)).await, missing_table_returns_empty);
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::Update { table, conditions, data, response } => {
-                let result = worker.adapter.update(&worker.connection, &table, &conditions, &data).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.update(&worker.connection, &table, &conditions, &data)).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::UpdateWithOperations { table, conditions, operations, response } => {
-                let result = worker.adapter.update_with_operations(&worker.connection, &table, &conditions, &operations).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.update_with_operations(&worker.connection, &table, &conditions, &operations)).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::UpdateById { table, id, data, response } => {
-                let result = worker.adapter.update_by_id(&worker.connection, &table, &id, &data).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.update_by_id(&worker.connection, &table, &id, &data)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::UpdateByIdDetailed { table, id, data, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.update_by_id_detailed(&worker.connection, &table, &id, &data)).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::Delete { table, conditions, response } => {
-                let result = worker.adapter.delete(&worker.connection, &table, &conditions).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.delete(&worker.connection, &table, &conditions)).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::DeleteById { table, id, response } => {
-                let result = worker.adapter.delete_by_id(&worker.connection, &table, &id).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.delete_by_id(&worker.connection, &table, &id)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::DeleteByIdDetailed { table, id, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.delete_by_id_detailed(&worker.connection, &table, &id)).await;
                 let _ = response.send(result);
                 Ok(())
             },
-            DatabaseOperation::Count { table, conditions, response } => {
-                let result = worker.adapter.count(&worker.connection, &table, &conditions).await;
+            DatabaseOperation::DeleteByIds { table, ids, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.delete_by_ids(&worker.connection, &table, &ids)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::ReadBlobChunk { location, offset, len, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.read_blob_chunk(&worker.connection, &location, offset, len, self.db_config.gridfs_bucket.as_deref())).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::WriteBlobChunk { location, data, append, response } => {
+                let result = worker.adapter.write_blob_chunk(&worker.connection, &location, &data, append, self.db_config.gridfs_bucket.as_deref()).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::Count { table, conditions, mode, response } => {
+                let result = retry_transient(retry, is_write, || if mode.uses_estimate(&conditions) {
+                    worker.adapter.estimated_count(&worker.connection, &table)
+                } else {
+                    worker.adapter.count(&worker.connection, &table, &conditions)
+                }).await;
+                let result = empty_on_missing_table(result, missing_table_returns_empty);
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::TimeBucket { table, field, interval, conditions, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.time_bucket(&worker.connection, &table, &field, interval, &conditions)).await;
+                let result = empty_on_missing_table(result, missing_table_returns_empty);
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::Aggregate { table, aggregations, conditions, group_by, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.aggregate(&worker.connection, &table, &aggregations, &conditions, &group_by)).await;
+                let result = empty_on_missing_table(result, missing_table_returns_empty);
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::MongoAggregate { table, pipeline, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.mongo_aggregate(&worker.connection, &table, &pipeline)).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::Exists { table, conditions, response } => {
-                let result = worker.adapter.exists(&worker.connection, &table, &conditions).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.exists(&worker.connection, &table, &conditions)).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::CreateTable { table, fields, id_strategy, response } => {
-                let result = worker.adapter.create_table(&worker.connection, &table, &fields, &id_strategy).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.create_table(&worker.connection, &table, &fields, &id_strategy)).await;
                 let _ = response.send(result);
                 Ok(())
             },
-            DatabaseOperation::CreateIndex { table, index_name, fields, unique, response } => {
-                let result = worker.adapter.create_index(&worker.connection, &table, &index_name, &fields, unique).await;
+            DatabaseOperation::CreateIndex { table, index_name, index, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.create_index(&worker.connection, &table, &index_name, &index)).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::TableExists { table, response } => {
-                let result = worker.adapter.table_exists(&worker.connection, &table).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.table_exists(&worker.connection, &table)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::GetTableColumns { table, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.get_table_columns(&worker.connection, &table)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::ListIndexNames { table, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.list_index_names(&worker.connection, &table)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::AddColumn { table, column, field_definition, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.add_column(&worker.connection, &table, &column, &field_definition)).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::DropTable { table, response } => {
-                let result = worker.adapter.drop_table(&worker.connection, &table).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.drop_table(&worker.connection, &table)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::RenameTable { from, to, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.rename_table(&worker.connection, &from, &to)).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::GetServerVersion { response } => {
-                let result = worker.adapter.get_server_version(&worker.connection).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.get_server_version(&worker.connection)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::QueryRaw { sql, params, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.query_raw(&worker.connection, &sql, &params)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::ExecuteRawUpdate { sql, params, response } => {
+                let result = retry_transient(retry, is_write, || worker.adapter.execute_raw_update(&worker.connection, &sql, &params)).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::ExecuteBatch { sql, response } => {
+                let result = worker.adapter.execute_batch(&worker.connection, &sql).await;
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::CreateStoredProcedure { config, response } => {
-                let result = worker.adapter.create_stored_procedure(&worker.connection, &config).await;
+                let result = retry_transient(retry, is_write, || worker.adapter.create_stored_procedure(&worker.connection, &config)).await;
                 let _ = response.send(result);
                 Ok(())
             },
@@ -355,6 +593,27 @@ impl MultiConnectionManager {
                 let _ = response.send(result);
                 Ok(())
             },
+            DatabaseOperation::Watch { table, response } => {
+                let result = worker.adapter.watch(&worker.connection, &table).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::GetRawConnection { response } => {
+                let _ = response.send(Ok(worker.connection.clone()));
+                Ok(())
+            },
+            DatabaseOperation::WithSessionContext { .. } => {
+                // 会话上下文在 run 循环中已展开，这里不会出现
+                Err(QuickDbError::QueryError {
+                    message: "会话上下文未展开".to_string(),
+                })
+            },
+            DatabaseOperation::WithBatchPermit { .. } => {
+                // 批量操作许可在 run 循环中已展开，这里不会出现
+                Err(QuickDbError::QueryError {
+                    message: "批量操作许可未展开".to_string(),
+                })
+            },
         };
         
         // 处理连接错误和重试逻辑
@@ -374,6 +633,9 @@ impl MultiConnectionManager {
                 // 尝试重新创建连接，但不退出程序
                 match self.create_connection_worker(worker_index).await {
                     Ok(new_worker) => {
+                        if !self.batch_lane {
+                            self.usage.set_connection(new_worker.connection.clone());
+                        }
                         self.workers[worker_index] = new_worker;
                         debug!("工作器 {} 连接已重新创建", worker_index);
                     },
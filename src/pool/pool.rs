@@ -4,7 +4,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::collections::HashMap;
 use crossbeam_queue::SegQueue;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, Semaphore};
 use rat_logger::{debug, info, warn, error};
 
 use crate::types::*;
@@ -13,7 +13,7 @@ use crate::adapter::DatabaseAdapter;
 use super::{DatabaseOperation, ExtendedPoolConfig, MultiConnectionManager, PooledConnection, DatabaseConnection};
 #[cfg(feature = "sqlite-support")]
 use super::SqliteWorker;
-use crate::model::FieldDefinition;
+use crate::model::{FieldDefinition, IndexDefinition};
 
 /// 新的连接池 - 基于生产者/消费者模式
 #[derive(Debug)]
@@ -24,10 +24,29 @@ pub struct ConnectionPool {
     pub config: ExtendedPoolConfig,
     /// 操作请求发送器
     pub operation_sender: mpsc::UnboundedSender<DatabaseOperation>,
+    /// 批量通道的操作请求发送器，保留了交互连接时启用
+    pub batch_sender: Option<mpsc::UnboundedSender<DatabaseOperation>>,
+    /// 批量操作并发许可，设置了批量操作上限时启用
+    pub batch_permits: Option<Arc<Semaphore>>,
     /// 数据库类型
     pub db_type: DatabaseType,
     /// 缓存管理器（可选）
     pub cache_manager: Option<Arc<crate::cache::CacheManager>>,
+    /// 连接池使用情况
+    pub usage: Arc<super::PoolUsage>,
+    /// N+1 查询检测器，由各工作器共享
+    pub(crate) n_plus_one: Option<Arc<crate::types::n_plus_one::NPlusOneDetector>>,
+    /// 按名称划分的工作负载通道
+    pub(crate) workloads: HashMap<String, WorkloadLane>,
+}
+
+/// 工作负载通道，使用独立的连接和使用情况统计，不计入主连接池的饱和判断
+#[derive(Debug)]
+pub(crate) struct WorkloadLane {
+    /// 操作请求发送器
+    sender: mpsc::UnboundedSender<DatabaseOperation>,
+    /// 通道使用情况
+    usage: Arc<super::PoolUsage>,
 }
 
 impl ConnectionPool {
@@ -44,40 +63,149 @@ impl ConnectionPool {
     ) -> QuickDbResult<Self> {
         let (operation_sender, operation_receiver) = mpsc::unbounded_channel();
         
-        let pool = Self {
+        // 保留了交互连接时拆分出独立的批量通道
+        let lanes = config.split_batch_lane();
+        let (batch_sender, batch_receiver) = match lanes {
+            Some(_) => {
+                let (sender, receiver) = mpsc::unbounded_channel();
+                (Some(sender), Some(receiver))
+            }
+            None => (None, None),
+        };
+        let batch_permits = match config.base.max_batch_operations {
+            0 => None,
+            limit => Some(Arc::new(Semaphore::new(limit as usize))),
+        };
+        
+        let mut pool = Self {
             db_type: db_config.db_type.clone(),
             db_config: db_config.clone(),
             config: config.clone(),
             operation_sender,
+            batch_sender,
+            batch_permits,
             cache_manager: cache_manager.clone(),
+            usage: Arc::new(super::PoolUsage::default()),
+            n_plus_one: db_config.detect_n_plus_one
+                .map(|detection| Arc::new(crate::types::n_plus_one::NPlusOneDetector::new(&db_config.alias, detection))),
+            workloads: HashMap::new(),
+        };
+        
+        let (interactive_config, batch_config) = match lanes {
+            Some((interactive, batch)) => (interactive, Some(batch)),
+            None => (config, None),
         };
         
         // 根据数据库类型启动对应的工作器
         match &db_config.db_type {
             #[cfg(feature = "sqlite-support")]
             DatabaseType::SQLite => {
-                pool.start_sqlite_worker(operation_receiver, db_config, config).await?;
+                // SQLite 内存数据库每个连接池相互独立，批量通道与交互通道共用同一个连接池
+                let connection = pool.create_sqlite_connection().await?;
+                pool.start_sqlite_worker(operation_receiver, connection.clone(), db_config.clone(), interactive_config, pool.usage.clone()).await?;
+                if let (Some(receiver), Some(batch_config)) = (batch_receiver, batch_config) {
+                    pool.start_sqlite_worker(receiver, connection.clone(), db_config.clone(), batch_config, pool.usage.clone()).await?;
+                }
+                // SQLite 只有一个连接池，工作负载通道只在排队上与主通道隔离
+                for (name, workload_config) in &db_config.workloads {
+                    let (sender, receiver) = mpsc::unbounded_channel();
+                    let usage = Arc::new(super::PoolUsage::default());
+                    let config = ExtendedPoolConfig::from_pool_config(workload_config.clone());
+                    pool.start_sqlite_worker(receiver, connection.clone(), db_config.clone(), config, usage.clone()).await?;
+                    pool.workloads.insert(name.clone(), WorkloadLane { sender, usage });
+                }
             },
             #[cfg(feature = "postgres-support")]
             DatabaseType::PostgreSQL => {
-                pool.start_multi_connection_manager(operation_receiver, db_config, config).await?;
+                pool.start_multi_connection_lanes(operation_receiver, batch_receiver, db_config, interactive_config, batch_config).await?;
+                pool.start_workload_lanes().await?;
             },
             #[cfg(feature = "mysql-support")]
             DatabaseType::MySQL => {
-                pool.start_multi_connection_manager(operation_receiver, db_config, config).await?;
+                pool.start_multi_connection_lanes(operation_receiver, batch_receiver, db_config, interactive_config, batch_config).await?;
+                pool.start_workload_lanes().await?;
             },
             #[cfg(feature = "mongodb-support")]
             DatabaseType::MongoDB => {
-                pool.start_multi_connection_manager(operation_receiver, db_config, config).await?;
+                pool.start_multi_connection_lanes(operation_receiver, batch_receiver, db_config, interactive_config, batch_config).await?;
+                pool.start_workload_lanes().await?;
             },
             _ => Err(QuickDbError::ConfigError {
                 message: "不支持的数据库类型（可能需要启用相应的feature）".to_string(),
             })?,
         }
         
+        if !pool.workloads.is_empty() {
+            debug!("工作负载通道已启用: 别名={}, 工作负载={:?}", pool.db_config.alias, pool.workloads.keys().collect::<Vec<_>>());
+        }
+
+        if pool.batch_sender.is_some() {
+            debug!("批量通道已启用: 别名={}, 交互保留连接数={}", pool.db_config.alias, pool.config.base.reserved_interactive_connections);
+        }
+        
+        if let Some(sampling) = pool.config.base.sampling {
+            super::metrics::spawn_sampler(&pool.usage, sampling);
+            debug!("连接池使用情况采样已启用: 别名={}, 间隔={}ms", pool.db_config.alias, sampling.interval_ms);
+        }
+        
         Ok(pool)
     }
     
+    /// 发送操作到工作器，自动附加当前任务的会话上下文
+    ///
+    /// 查询选项指定了工作负载时，操作进入该工作负载的通道，未配置的工作负载直接以配置错误响应。
+    /// 当前任务的操作类别为批量时，操作进入批量通道（如果启用）；达到批量操作上限时
+    /// 在后台等待许可后再发送，许可随操作执行完成释放。只读别名上的写操作不发送，
+    /// 直接以 `QuickDbError::ReadOnly` 响应调用方
+    pub fn dispatch_operation(&self, operation: DatabaseOperation) -> Result<(), mpsc::error::SendError<DatabaseOperation>> {
+        if self.db_config.read_only && operation.is_write() {
+            debug!("只读别名拒绝写操作: 别名={}", self.db_config.alias);
+            operation.reject_read_only(&self.db_config.alias);
+            return Ok(());
+        }
+        if let Some(workload) = operation.workload() {
+            let Some(lane) = self.workloads.get(workload) else {
+                let message = format!("别名 {} 未配置工作负载 {}", self.db_config.alias, workload);
+                operation.reject(QuickDbError::ConfigError { message });
+                return Ok(());
+            };
+            lane.usage.operation_queued();
+            return lane.sender.send(operation.with_current_session_context());
+        }
+        self.usage.operation_queued();
+        let operation = operation.with_current_session_context();
+        if current_operation_class() != OperationClass::Batch {
+            return self.operation_sender.send(operation);
+        }
+
+        let sender = self.batch_sender.as_ref().unwrap_or(&self.operation_sender);
+        let Some(permits) = &self.batch_permits else {
+            return sender.send(operation);
+        };
+        match permits.clone().try_acquire_owned() {
+            Ok(permit) => sender.send(operation.with_batch_permit(permit)),
+            Err(_) => {
+                debug!("批量操作达到上限，等待许可: 别名={}", self.db_config.alias);
+                let sender = sender.clone();
+                let permits = permits.clone();
+                tokio::spawn(async move {
+                    // 信号量不会被关闭；发送失败时操作连同响应通道一起丢弃，调用方会收到通道关闭错误
+                    if let Ok(permit) = permits.acquire_owned().await {
+                        let _ = sender.send(operation.with_batch_permit(permit));
+                    }
+                });
+                Ok(())
+            }
+        }
+    }
+
+    /// 连接池是否已饱和
+    ///
+    /// 未完成的操作数达到 `max_connections` 时视为饱和，此时新操作需要排队等待
+    pub fn is_saturated(&self) -> bool {
+        self.usage.in_flight() >= self.config.base.max_connections.max(1) as usize
+    }
+
     /// 设置缓存管理器
     pub fn set_cache_manager(&mut self, cache_manager: Arc<crate::cache::CacheManager>) {
         self.cache_manager = Some(cache_manager);
@@ -88,26 +216,31 @@ impl ConnectionPool {
     async fn start_sqlite_worker(
         &self,
         operation_receiver: mpsc::UnboundedReceiver<DatabaseOperation>,
+        connection: DatabaseConnection,
         db_config: DatabaseConfig,
         config: ExtendedPoolConfig,
+        usage: Arc<super::PoolUsage>,
     ) -> QuickDbResult<()> {
-        let connection = self.create_sqlite_connection().await?;
-        
+
         // 创建启动同步通道
         let (startup_tx, startup_rx) = oneshot::channel();
         
-        // 创建适配器
-        use crate::adapter::{create_adapter, create_adapter_with_cache};
+        // 创建适配器，按配置设置数据库忙时的重试次数
+        use crate::adapter::{CachedDatabaseAdapter, DatabaseAdapter, SqliteAdapter, DEFAULT_BUSY_RETRY_COUNT};
+        let base_adapter: Box<dyn DatabaseAdapter> = Box::new(
+            SqliteAdapter::new()
+                .with_busy_retry_count(db_config.busy_retry_count.unwrap_or(DEFAULT_BUSY_RETRY_COUNT)),
+        );
         let (adapter, adapter_type) = if let Some(cache_manager) = &self.cache_manager {
-            let adapter = create_adapter_with_cache(&db_config.db_type, cache_manager.clone())?;
+            let adapter: Box<dyn DatabaseAdapter> = Box::new(CachedDatabaseAdapter::new(base_adapter, cache_manager.clone()));
             (adapter, "缓存适配器")
         } else {
-            let adapter = create_adapter(&db_config.db_type)?;
-            (adapter, "普通适配器")
+            (base_adapter, "普通适配器")
         };
         
         info!("数据库 '{}' 使用 {}", db_config.alias, adapter_type);
         
+        usage.set_connection(connection.clone());
         let worker = SqliteWorker {
             connection,
             operation_receiver,
@@ -120,6 +253,8 @@ impl ConnectionPool {
             is_healthy: true,
             cache_manager: self.cache_manager.clone(),
             adapter,
+            usage,
+            n_plus_one: self.n_plus_one.clone(),
         };
         
         // 启动工作器
@@ -138,12 +273,42 @@ impl ConnectionPool {
         Ok(())
     }
     
+    /// 启动交互通道和批量通道（如果启用）的多连接管理器
+    async fn start_multi_connection_lanes(
+        &self,
+        operation_receiver: mpsc::UnboundedReceiver<DatabaseOperation>,
+        batch_receiver: Option<mpsc::UnboundedReceiver<DatabaseOperation>>,
+        db_config: DatabaseConfig,
+        config: ExtendedPoolConfig,
+        batch_config: Option<ExtendedPoolConfig>,
+    ) -> QuickDbResult<()> {
+        self.start_multi_connection_manager(operation_receiver, db_config.clone(), config, false, self.usage.clone()).await?;
+        if let (Some(receiver), Some(batch_config)) = (batch_receiver, batch_config) {
+            self.start_multi_connection_manager(receiver, db_config, batch_config, true, self.usage.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// 为每个配置的工作负载启动使用独立连接池的多连接管理器
+    async fn start_workload_lanes(&mut self) -> QuickDbResult<()> {
+        for (name, workload_config) in self.db_config.workloads.clone() {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            let usage = Arc::new(super::PoolUsage::default());
+            let config = ExtendedPoolConfig::from_pool_config(workload_config);
+            self.start_multi_connection_manager(receiver, self.db_config.clone(), config, false, usage.clone()).await?;
+            self.workloads.insert(name, WorkloadLane { sender, usage });
+        }
+        Ok(())
+    }
+    
     /// 启动多连接管理器
     async fn start_multi_connection_manager(
         &self,
         operation_receiver: mpsc::UnboundedReceiver<DatabaseOperation>,
         db_config: DatabaseConfig,
         config: ExtendedPoolConfig,
+        batch_lane: bool,
+        usage: Arc<super::PoolUsage>,
     ) -> QuickDbResult<()> {
         let manager = MultiConnectionManager {
             workers: Vec::new(),
@@ -153,6 +318,9 @@ impl ConnectionPool {
             config,
             keepalive_handle: None,
             cache_manager: self.cache_manager.clone(),
+            usage,
+            n_plus_one: self.n_plus_one.clone(),
+            batch_lane,
         };
         
         // 启动管理器
@@ -247,7 +415,7 @@ impl ConnectionPool {
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -272,7 +440,7 @@ impl ConnectionPool {
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -299,7 +467,7 @@ impl ConnectionPool {
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -326,7 +494,7 @@ impl ConnectionPool {
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -353,7 +521,7 @@ impl ConnectionPool {
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -378,7 +546,7 @@ impl ConnectionPool {
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -403,7 +571,7 @@ impl ConnectionPool {
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -425,10 +593,11 @@ impl ConnectionPool {
         let operation = DatabaseOperation::Count {
             table: table.to_string(),
             conditions: conditions.to_vec(),
+            mode: CountMode::Exact,
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -453,7 +622,7 @@ impl ConnectionPool {
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -480,7 +649,7 @@ impl ConnectionPool {
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -496,20 +665,18 @@ impl ConnectionPool {
         &self,
         table: &str,
         index_name: &str,
-        fields: &[String],
-        unique: bool,
+        index: &IndexDefinition,
     ) -> QuickDbResult<()> {
         let (response_sender, response_receiver) = oneshot::channel();
         
         let operation = DatabaseOperation::CreateIndex {
             table: table.to_string(),
             index_name: index_name.to_string(),
-            fields: fields.to_vec(),
-            unique,
+            index: index.clone(),
             response: response_sender,
         };
         
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -529,7 +696,69 @@ impl ConnectionPool {
             response: response_sender,
         };
 
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::QueryError {
+                message: "发送操作失败".to_string(),
+            })?;
+
+        response_receiver.await
+            .map_err(|_| QuickDbError::QueryError {
+                message: "接收响应失败".to_string(),
+            })?
+    }
+
+    /// 获取表列名
+    pub async fn get_table_columns(&self, table: &str) -> QuickDbResult<Vec<String>> {
+        let (response_sender, response_receiver) = oneshot::channel();
+
+        let operation = DatabaseOperation::GetTableColumns {
+            table: table.to_string(),
+            response: response_sender,
+        };
+
+        self.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::QueryError {
+                message: "发送操作失败".to_string(),
+            })?;
+
+        response_receiver.await
+            .map_err(|_| QuickDbError::QueryError {
+                message: "接收响应失败".to_string(),
+            })?
+    }
+
+    /// 获取表索引名
+    pub async fn list_index_names(&self, table: &str) -> QuickDbResult<Vec<String>> {
+        let (response_sender, response_receiver) = oneshot::channel();
+
+        let operation = DatabaseOperation::ListIndexNames {
+            table: table.to_string(),
+            response: response_sender,
+        };
+
+        self.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::QueryError {
+                message: "发送操作失败".to_string(),
+            })?;
+
+        response_receiver.await
+            .map_err(|_| QuickDbError::QueryError {
+                message: "接收响应失败".to_string(),
+            })?
+    }
+
+    /// 为已存在的表添加列
+    pub async fn add_column(&self, table: &str, column: &str, field_definition: &FieldDefinition) -> QuickDbResult<()> {
+        let (response_sender, response_receiver) = oneshot::channel();
+
+        let operation = DatabaseOperation::AddColumn {
+            table: table.to_string(),
+            column: column.to_string(),
+            field_definition: field_definition.clone(),
+            response: response_sender,
+        };
+
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -549,7 +778,7 @@ impl ConnectionPool {
             response: response_sender,
         };
 
-        self.operation_sender.send(operation)
+        self.dispatch_operation(operation)
             .map_err(|_| QuickDbError::QueryError {
                 message: "发送操作失败".to_string(),
             })?;
@@ -560,6 +789,48 @@ impl ConnectionPool {
             })?
     }
     
+    /// 重命名表
+    pub async fn rename_table(&self, from: &str, to: &str) -> QuickDbResult<()> {
+        let (response_sender, response_receiver) = oneshot::channel();
+
+        let operation = DatabaseOperation::RenameTable {
+            from: from.to_string(),
+            to: to.to_string(),
+            response: response_sender,
+        };
+
+        self.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::QueryError {
+                message: "发送操作失败".to_string(),
+            })?;
+
+        response_receiver.await
+            .map_err(|_| QuickDbError::QueryError {
+                message: "接收响应失败".to_string(),
+            })?
+    }
+    
+    /// 获取底层原生连接句柄
+    ///
+    /// 返回工作器持有的sqlx连接池或MongoDB数据库句柄的克隆
+    pub async fn raw_connection(&self) -> QuickDbResult<DatabaseConnection> {
+        let (response_sender, response_receiver) = oneshot::channel();
+
+        let operation = DatabaseOperation::GetRawConnection {
+            response: response_sender,
+        };
+
+        self.dispatch_operation(operation)
+            .map_err(|_| QuickDbError::QueryError {
+                message: "发送操作失败".to_string(),
+            })?;
+
+        response_receiver.await
+            .map_err(|_| QuickDbError::QueryError {
+                message: "接收响应失败".to_string(),
+            })?
+    }
+
     /// 获取数据库类型
     pub fn get_database_type(&self) -> &DatabaseType {
         &self.db_config.db_type
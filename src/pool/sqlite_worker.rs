@@ -11,6 +11,9 @@ use crate::types::*;
 use crate::error::{QuickDbError, QuickDbResult};
 use crate::adapter::DatabaseAdapter;
 use super::{DatabaseConnection, DatabaseOperation, ExtendedPoolConfig};
+use super::types::empty_on_missing_table;
+use super::dry_run::DryRun;
+use crate::types::query_log::with_query_log;
 
 /// SQLite 单线程工作器
 #[cfg(feature = "sqlite-support")]
@@ -37,6 +40,10 @@ pub struct SqliteWorker {
     pub(crate) cache_manager: Option<Arc<crate::cache::CacheManager>>,
     /// 数据库适配器（持久化，避免重复创建）
     pub(crate) adapter: Box<dyn crate::adapter::DatabaseAdapter + Send + Sync>,
+    /// 连接池使用情况
+    pub(crate) usage: Arc<super::PoolUsage>,
+    /// N+1 查询检测器（可选）
+    pub(crate) n_plus_one: Option<Arc<crate::types::n_plus_one::NPlusOneDetector>>,
 }
 
 #[cfg(feature = "sqlite-support")]
@@ -66,16 +73,27 @@ impl SqliteWorker {
         let health_check_handle = self.start_health_check_task().await;
         
         while let Some(operation) = self.operation_receiver.recv().await {
+            self.usage.operation_started();
             // 检查连接健康状态
             if !self.is_healthy {
                 warn!("SQLite连接不健康，尝试重新连接");
                 if let Err(e) = self.reconnect().await {
                     error!("SQLite重新连接失败: {}", e);
+                    self.usage.operation_finished();
                     continue;
                 }
             }
             
-            match self.handle_operation(operation).await {
+            let (_batch_permit, operation) = operation.split_batch_permit();
+            let (session_context, operation) = operation.split_session_context();
+            let query_log = operation.query_log_scope(&self.db_config, self.n_plus_one.clone());
+            let result = match session_context {
+                Some(context) => with_session_context(context, with_query_log(query_log, self.process_operation(operation))).await,
+                None => with_query_log(query_log, self.process_operation(operation)).await,
+            };
+            self.usage.operation_finished();
+
+            match result {
                 Ok(_) => {
                     self.retry_count = 0; // 重置重试计数
                     self.is_healthy = true; // 标记连接健康
@@ -131,6 +149,7 @@ impl SqliteWorker {
         info!("正在重新连接SQLite数据库: 别名={}", self.db_config.alias);
 
         let new_connection = self.create_sqlite_connection().await?;
+        self.usage.set_connection(new_connection.clone());
         self.connection = new_connection;
         self.is_healthy = true;
         self.retry_count = 0;
@@ -236,11 +255,30 @@ impl SqliteWorker {
         self.is_healthy
     }
     
+    /// 执行操作，试运行别名上的写操作在回滚的事务中执行
+    async fn process_operation(&mut self, mut operation: DatabaseOperation) -> QuickDbResult<()> {
+        if let Some(precision) = self.db_config.datetime_precision {
+            operation.truncate_datetimes(precision);
+        }
+        if !(self.db_config.dry_run && operation.is_write()) {
+            return self.handle_operation(operation).await;
+        }
+        match DryRun::begin(&self.db_config.alias, self.adapter.as_ref(), &self.connection, &operation).await {
+            Ok(dry_run) => dry_run.run(self.handle_operation(operation)).await,
+            Err(e) => {
+                operation.reject(e);
+                Ok(())
+            }
+        }
+    }
+
     /// 处理数据库操作（带 panic 捕获）
     async fn handle_operation(&mut self, operation: DatabaseOperation) -> QuickDbResult<()> {
         // 执行健康检查
         self.perform_health_check().await;
         
+        let missing_table_returns_empty = self.db_config.missing_table_returns_empty;
+
         // 执行数据库操作，使用 Result 来处理错误而不是 panic 捕获
         let operation_result = match operation {
             DatabaseOperation::Create { table, data, id_strategy, response } => {
@@ -248,18 +286,43 @@ impl SqliteWorker {
                 let _ = response.send(result);
                 Ok(())
             },
+            DatabaseOperation::CreateMany { table, data_list, id_strategy, response } => {
+                let result = self.adapter.create_many(&self.connection, &table, &data_list, &id_strategy).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::UpsertMany { table, rows, conflict_fields, id_strategy, response } => {
+                let result = self.adapter.upsert_many(&self.connection, &table, &rows, &conflict_fields, &id_strategy).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::Upsert { table, data, conflict_fields, id_strategy, response } => {
+                let result = self.adapter.upsert(&self.connection, &table, &data, &conflict_fields, &id_strategy).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::Seed { fixtures, id_strategy, response } => {
+                let result = self.adapter.seed(&self.connection, &fixtures, &id_strategy).await;
+                let _ = response.send(result);
+                Ok(())
+            },
             DatabaseOperation::FindById { table, id, response } => {
-                let result = self.adapter.find_by_id(&self.connection, &table, &id).await;
+                let result = empty_on_missing_table(self.adapter.find_by_id(&self.connection, &table, &id).await, missing_table_returns_empty);
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::FindByIds { table, ids, response } => {
+                let result = empty_on_missing_table(self.adapter.find_by_ids(&self.connection, &table, &ids).await, missing_table_returns_empty);
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::Find { table, conditions, options, response } => {
-                let result = self.adapter.find(&self.connection, &table, &conditions, &options).await;
+                let result = empty_on_missing_table(self.adapter.find(&self.connection, &table, &conditions, &options).await, missing_table_returns_empty);
                 let _ = response.send(result);
                 Ok(())
             },
             DatabaseOperation::FindWithGroups { table, condition_groups, options, response } => {
-                let result = self.adapter.find_with_groups(&self.connection, &table, &condition_groups, &options).await;
+                let result = empty_on_missing_table(self.adapter.find_with_groups(&self.connection, &table, &condition_groups, &options).await, missing_table_returns_empty);
                 let _ = response.send(result);
                 Ok(())
             },
@@ -278,6 +341,11 @@ impl SqliteWorker {
                 let _ = response.send(result);
                 Ok(())
             },
+            DatabaseOperation::UpdateByIdDetailed { table, id, data, response } => {
+                let result = self.adapter.update_by_id_detailed(&self.connection, &table, &id, &data).await;
+                let _ = response.send(result);
+                Ok(())
+            },
             DatabaseOperation::Delete { table, conditions, response } => {
                 let result = self.adapter.delete(&self.connection, &table, &conditions).await;
                 let _ = response.send(result);
@@ -288,8 +356,50 @@ impl SqliteWorker {
                 let _ = response.send(result);
                 Ok(())
             },
-            DatabaseOperation::Count { table, conditions, response } => {
-                let result = self.adapter.count(&self.connection, &table, &conditions).await;
+            DatabaseOperation::DeleteByIdDetailed { table, id, response } => {
+                let result = self.adapter.delete_by_id_detailed(&self.connection, &table, &id).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::DeleteByIds { table, ids, response } => {
+                let result = self.adapter.delete_by_ids(&self.connection, &table, &ids).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::ReadBlobChunk { location, offset, len, response } => {
+                let result = self.adapter.read_blob_chunk(&self.connection, &location, offset, len, self.db_config.gridfs_bucket.as_deref()).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::WriteBlobChunk { location, data, append, response } => {
+                let result = self.adapter.write_blob_chunk(&self.connection, &location, &data, append, self.db_config.gridfs_bucket.as_deref()).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::Count { table, conditions, mode, response } => {
+                let result = if mode.uses_estimate(&conditions) {
+                    self.adapter.estimated_count(&self.connection, &table).await
+                } else {
+                    self.adapter.count(&self.connection, &table, &conditions).await
+                };
+                let result = empty_on_missing_table(result, missing_table_returns_empty);
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::TimeBucket { table, field, interval, conditions, response } => {
+                let result = self.adapter.time_bucket(&self.connection, &table, &field, interval, &conditions).await;
+                let result = empty_on_missing_table(result, missing_table_returns_empty);
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::Aggregate { table, aggregations, conditions, group_by, response } => {
+                let result = self.adapter.aggregate(&self.connection, &table, &aggregations, &conditions, &group_by).await;
+                let result = empty_on_missing_table(result, missing_table_returns_empty);
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::MongoAggregate { table, pipeline, response } => {
+                let result = self.adapter.mongo_aggregate(&self.connection, &table, &pipeline).await;
                 let _ = response.send(result);
                 Ok(())
             },
@@ -303,8 +413,8 @@ impl SqliteWorker {
                 let _ = response.send(result);
                 Ok(())
             },
-            DatabaseOperation::CreateIndex { table, index_name, fields, unique, response } => {
-                let result = self.adapter.create_index(&self.connection, &table, &index_name, &fields, unique).await;
+            DatabaseOperation::CreateIndex { table, index_name, index, response } => {
+                let result = self.adapter.create_index(&self.connection, &table, &index_name, &index).await;
                 let _ = response.send(result);
                 Ok(())
             },
@@ -313,16 +423,51 @@ impl SqliteWorker {
                 let _ = response.send(result);
                 Ok(())
             },
+            DatabaseOperation::GetTableColumns { table, response } => {
+                let result = self.adapter.get_table_columns(&self.connection, &table).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::ListIndexNames { table, response } => {
+                let result = self.adapter.list_index_names(&self.connection, &table).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::AddColumn { table, column, field_definition, response } => {
+                let result = self.adapter.add_column(&self.connection, &table, &column, &field_definition).await;
+                let _ = response.send(result);
+                Ok(())
+            },
             DatabaseOperation::DropTable { table, response } => {
                 let result = self.adapter.drop_table(&self.connection, &table).await;
                 let _ = response.send(result);
                 Ok(())
             },
+            DatabaseOperation::RenameTable { from, to, response } => {
+                let result = self.adapter.rename_table(&self.connection, &from, &to).await;
+                let _ = response.send(result);
+                Ok(())
+            },
             DatabaseOperation::GetServerVersion { response } => {
                 let result = self.adapter.get_server_version(&self.connection).await;
                 let _ = response.send(result);
                 Ok(())
             },
+            DatabaseOperation::QueryRaw { sql, params, response } => {
+                let result = self.adapter.query_raw(&self.connection, &sql, &params).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::ExecuteRawUpdate { sql, params, response } => {
+                let result = self.adapter.execute_raw_update(&self.connection, &sql, &params).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::ExecuteBatch { sql, response } => {
+                let result = self.adapter.execute_batch(&self.connection, &sql).await;
+                let _ = response.send(result);
+                Ok(())
+            },
             DatabaseOperation::CreateStoredProcedure { config, response } => {
                 let result = self.adapter.create_stored_procedure(&self.connection, &config).await;
                 let _ = response.send(result);
@@ -333,6 +478,27 @@ impl SqliteWorker {
                 let _ = response.send(result);
                 Ok(())
             },
+            DatabaseOperation::Watch { table, response } => {
+                let result = self.adapter.watch(&self.connection, &table).await;
+                let _ = response.send(result);
+                Ok(())
+            },
+            DatabaseOperation::GetRawConnection { response } => {
+                let _ = response.send(Ok(self.connection.clone()));
+                Ok(())
+            },
+            DatabaseOperation::WithSessionContext { .. } => {
+                // 会话上下文在 run 循环中已展开，这里不会出现
+                Err(QuickDbError::QueryError {
+                    message: "会话上下文未展开".to_string(),
+                })
+            },
+            DatabaseOperation::WithBatchPermit { .. } => {
+                // 批量操作许可在 run 循环中已展开，这里不会出现
+                Err(QuickDbError::QueryError {
+                    message: "批量操作许可未展开".to_string(),
+                })
+            },
         };
         
         operation_result
@@ -7,7 +7,7 @@ use tokio::sync::oneshot;
 
 use crate::types::*;
 use crate::error::{QuickDbError, QuickDbResult};
-use crate::model::{FieldType, FieldDefinition};
+use crate::model::{FieldType, FieldDefinition, IndexDefinition};
 use super::ExtendedPoolConfig;
 
 /// 池化连接 - 用于兼容旧接口
@@ -31,12 +31,47 @@ pub enum DatabaseOperation {
         id_strategy: IdStrategy,
         response: oneshot::Sender<QuickDbResult<DataValue>>,
     },
+    /// 批量创建记录
+    CreateMany {
+        table: String,
+        data_list: Vec<HashMap<String, DataValue>>,
+        id_strategy: IdStrategy,
+        response: oneshot::Sender<QuickDbResult<Vec<DataValue>>>,
+    },
+    /// 批量插入或更新记录，冲突列上已存在的记录被更新
+    UpsertMany {
+        table: String,
+        rows: Vec<HashMap<String, DataValue>>,
+        conflict_fields: Vec<String>,
+        id_strategy: IdStrategy,
+        response: oneshot::Sender<QuickDbResult<UpsertResult>>,
+    },
+    /// 插入或更新单条记录
+    Upsert {
+        table: String,
+        data: HashMap<String, DataValue>,
+        conflict_fields: Vec<String>,
+        id_strategy: IdStrategy,
+        response: oneshot::Sender<QuickDbResult<UpsertOutcome>>,
+    },
+    /// 在单个事务中按顺序写入多张表的记录
+    Seed {
+        fixtures: Vec<(String, Vec<HashMap<String, DataValue>>)>,
+        id_strategy: IdStrategy,
+        response: oneshot::Sender<QuickDbResult<Vec<(String, u64)>>>,
+    },
     /// 根据ID查找记录
     FindById {
         table: String,
         id: DataValue,
         response: oneshot::Sender<QuickDbResult<Option<DataValue>>>,
     },
+    /// 根据ID列表批量查找记录
+    FindByIds {
+        table: String,
+        ids: Vec<DataValue>,
+        response: oneshot::Sender<QuickDbResult<Vec<DataValue>>>,
+    },
     /// 查找记录
     Find {
         table: String,
@@ -72,6 +107,13 @@ pub enum DatabaseOperation {
         data: HashMap<String, DataValue>,
         response: oneshot::Sender<QuickDbResult<bool>>,
     },
+    /// 根据ID更新记录，返回匹配数与修改数
+    UpdateByIdDetailed {
+        table: String,
+        id: DataValue,
+        data: HashMap<String, DataValue>,
+        response: oneshot::Sender<QuickDbResult<WriteResult>>,
+    },
     /// 删除记录
     Delete {
         table: String,
@@ -84,12 +126,61 @@ pub enum DatabaseOperation {
         id: DataValue,
         response: oneshot::Sender<QuickDbResult<bool>>,
     },
+    /// 根据ID删除记录，返回匹配数与删除数
+    DeleteByIdDetailed {
+        table: String,
+        id: DataValue,
+        response: oneshot::Sender<QuickDbResult<WriteResult>>,
+    },
+    /// 根据ID列表批量删除记录
+    DeleteByIds {
+        table: String,
+        ids: Vec<DataValue>,
+        response: oneshot::Sender<QuickDbResult<u64>>,
+    },
+    /// 读取二进制列的一段数据
+    ReadBlobChunk {
+        location: BlobLocation,
+        offset: u64,
+        len: u64,
+        response: oneshot::Sender<QuickDbResult<Option<Vec<u8>>>>,
+    },
+    /// 写入二进制列的一段数据
+    WriteBlobChunk {
+        location: BlobLocation,
+        data: Vec<u8>,
+        append: bool,
+        response: oneshot::Sender<QuickDbResult<bool>>,
+    },
     /// 统计记录
     Count {
         table: String,
         conditions: Vec<QueryCondition>,
+        mode: CountMode,
         response: oneshot::Sender<QuickDbResult<u64>>,
     },
+    /// 按时间段统计记录
+    TimeBucket {
+        table: String,
+        field: String,
+        interval: Interval,
+        conditions: Vec<QueryCondition>,
+        response: oneshot::Sender<QuickDbResult<Vec<(DataValue, u64)>>>,
+    },
+    /// 聚合统计
+    Aggregate {
+        table: String,
+        aggregations: Vec<Aggregation>,
+        conditions: Vec<QueryCondition>,
+        group_by: Vec<String>,
+        response: oneshot::Sender<QuickDbResult<Vec<DataValue>>>,
+    },
+    /// 原样执行MongoDB聚合管道
+    MongoAggregate {
+        table: String,
+        pipeline: Vec<serde_json::Value>,
+        response: oneshot::Sender<QuickDbResult<Vec<DataValue>>>,
+    },
     /// 检查存在
     Exists {
         table: String,
@@ -107,8 +198,7 @@ pub enum DatabaseOperation {
     CreateIndex {
         table: String,
         index_name: String,
-        fields: Vec<String>,
-        unique: bool,
+        index: IndexDefinition,
         response: oneshot::Sender<QuickDbResult<()>>,
     },
     /// 检查表是否存在
@@ -116,15 +206,55 @@ pub enum DatabaseOperation {
         table: String,
         response: oneshot::Sender<QuickDbResult<bool>>,
     },
+    /// 获取表列名
+    GetTableColumns {
+        table: String,
+        response: oneshot::Sender<QuickDbResult<Vec<String>>>,
+    },
+    /// 获取表索引名
+    ListIndexNames {
+        table: String,
+        response: oneshot::Sender<QuickDbResult<Vec<String>>>,
+    },
+    /// 为已存在的表添加列
+    AddColumn {
+        table: String,
+        column: String,
+        field_definition: FieldDefinition,
+        response: oneshot::Sender<QuickDbResult<()>>,
+    },
     /// 删除表
     DropTable {
         table: String,
         response: oneshot::Sender<QuickDbResult<()>>,
     },
+    /// 重命名表
+    RenameTable {
+        from: String,
+        to: String,
+        response: oneshot::Sender<QuickDbResult<()>>,
+    },
     /// 获取服务器版本
     GetServerVersion {
         response: oneshot::Sender<QuickDbResult<String>>,
     },
+    /// 执行原始SQL查询
+    QueryRaw {
+        sql: String,
+        params: Vec<crate::types::DataValue>,
+        response: oneshot::Sender<QuickDbResult<Vec<crate::types::DataValue>>>,
+    },
+    /// 执行原始SQL写入语句
+    ExecuteRawUpdate {
+        sql: String,
+        params: Vec<crate::types::DataValue>,
+        response: oneshot::Sender<QuickDbResult<u64>>,
+    },
+    /// 执行包含多条语句的SQL脚本
+    ExecuteBatch {
+        sql: String,
+        response: oneshot::Sender<QuickDbResult<u64>>,
+    },
     /// 创建存储过程
     CreateStoredProcedure {
         config: crate::stored_procedure::StoredProcedureConfig,
@@ -137,10 +267,281 @@ pub enum DatabaseOperation {
         params: Option<std::collections::HashMap<String, crate::types::DataValue>>,
         response: oneshot::Sender<QuickDbResult<crate::stored_procedure::StoredProcedureQueryResult>>,
     },
+    /// 订阅数据变更
+    Watch {
+        table: String,
+        response: oneshot::Sender<QuickDbResult<crate::types::ChangeStream>>,
+    },
+    /// 获取底层原生连接句柄
+    GetRawConnection {
+        response: oneshot::Sender<QuickDbResult<DatabaseConnection>>,
+    },
+    /// 携带会话上下文的操作，由工作器展开后在该上下文中执行内部操作
+    WithSessionContext {
+        context: crate::types::SessionContext,
+        operation: Box<DatabaseOperation>,
+    },
+    /// 持有批量操作许可的操作，许可在内部操作执行完成后释放
+    WithBatchPermit {
+        permit: tokio::sync::OwnedSemaphorePermit,
+        operation: Box<DatabaseOperation>,
+    },
+}
+
+impl DatabaseOperation {
+    /// 附加当前任务的会话上下文（如果有）
+    pub(crate) fn with_current_session_context(self) -> Self {
+        match crate::types::current_session_context() {
+            Some(context) => DatabaseOperation::WithSessionContext {
+                context,
+                operation: Box::new(self),
+            },
+            None => self,
+        }
+    }
+
+    /// 附加批量操作许可
+    pub(crate) fn with_batch_permit(self, permit: tokio::sync::OwnedSemaphorePermit) -> Self {
+        DatabaseOperation::WithBatchPermit {
+            permit,
+            operation: Box::new(self),
+        }
+    }
+
+    /// 拆分出批量操作许可与实际要执行的操作，调用方需持有许可直到操作执行完成
+    pub(crate) fn split_batch_permit(self) -> (Option<tokio::sync::OwnedSemaphorePermit>, DatabaseOperation) {
+        match self {
+            DatabaseOperation::WithBatchPermit { permit, operation } => (Some(permit), *operation),
+            operation => (None, operation),
+        }
+    }
+
+    /// 是否为写操作（含建表、加列、删表等DDL），只读别名会拒绝这些操作
+    ///
+    /// 聚合管道含 `$out` 或 `$merge` 阶段时会写入集合，同样视为写操作；
+    /// 原始SQL除非是单条只读查询（见 [`is_read_only_sql`]）都视为写操作，存储过程调用可能修改数据，也视为写操作
+    pub(crate) fn is_write(&self) -> bool {
+        if let DatabaseOperation::MongoAggregate { pipeline, .. } = self {
+            return pipeline.iter().any(|stage| {
+                stage.as_object().is_some_and(|stage| stage.contains_key("$out") || stage.contains_key("$merge"))
+            });
+        }
+        if let DatabaseOperation::QueryRaw { sql, .. } = self {
+            return !is_read_only_sql(sql);
+        }
+        matches!(
+            self,
+            DatabaseOperation::Create { .. }
+                | DatabaseOperation::CreateMany { .. }
+                | DatabaseOperation::UpsertMany { .. }
+                | DatabaseOperation::Upsert { .. }
+                | DatabaseOperation::Seed { .. }
+                | DatabaseOperation::Update { .. }
+                | DatabaseOperation::UpdateWithOperations { .. }
+                | DatabaseOperation::UpdateById { .. }
+                | DatabaseOperation::UpdateByIdDetailed { .. }
+                | DatabaseOperation::Delete { .. }
+                | DatabaseOperation::DeleteById { .. }
+                | DatabaseOperation::DeleteByIdDetailed { .. }
+                | DatabaseOperation::DeleteByIds { .. }
+                | DatabaseOperation::WriteBlobChunk { .. }
+                | DatabaseOperation::CreateTable { .. }
+                | DatabaseOperation::CreateIndex { .. }
+                | DatabaseOperation::AddColumn { .. }
+                | DatabaseOperation::DropTable { .. }
+                | DatabaseOperation::RenameTable { .. }
+                | DatabaseOperation::ExecuteRawUpdate { .. }
+                | DatabaseOperation::ExecuteBatch { .. }
+                | DatabaseOperation::CreateStoredProcedure { .. }
+                | DatabaseOperation::ExecuteStoredProcedure { .. }
+        )
+    }
+
+    /// 以只读错误直接响应写操作的调用方，操作不会发送到工作器；非写操作不做处理
+    pub(crate) fn reject_read_only(self, alias: &str) {
+        self.reject(QuickDbError::ReadOnly {
+            alias: alias.to_string(),
+        });
+    }
+
+    /// 查询选项指定的工作负载名称，只有带查询选项的查询可以指定
+    pub(crate) fn workload(&self) -> Option<&str> {
+        match self {
+            DatabaseOperation::Find { options, .. }
+            | DatabaseOperation::FindWithGroups { options, .. } => options.workload.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// 以给定错误直接响应写操作或带查询选项的查询的调用方，操作不会执行；其他操作不做处理
+    pub(crate) fn reject(self, error: QuickDbError) {
+        fn reply<T>(response: oneshot::Sender<QuickDbResult<T>>, error: QuickDbError) {
+            let _ = response.send(Err(error));
+        }
+
+        match self {
+            DatabaseOperation::Create { response, .. } => reply(response, error),
+            DatabaseOperation::CreateMany { response, .. } => reply(response, error),
+            DatabaseOperation::UpsertMany { response, .. } => reply(response, error),
+            DatabaseOperation::Upsert { response, .. } => reply(response, error),
+            DatabaseOperation::MongoAggregate { response, .. } => reply(response, error),
+            DatabaseOperation::Seed { response, .. } => reply(response, error),
+            DatabaseOperation::Update { response, .. } => reply(response, error),
+            DatabaseOperation::UpdateWithOperations { response, .. } => reply(response, error),
+            DatabaseOperation::UpdateById { response, .. } => reply(response, error),
+            DatabaseOperation::UpdateByIdDetailed { response, .. } => reply(response, error),
+            DatabaseOperation::Delete { response, .. } => reply(response, error),
+            DatabaseOperation::DeleteById { response, .. } => reply(response, error),
+            DatabaseOperation::DeleteByIdDetailed { response, .. } => reply(response, error),
+            DatabaseOperation::DeleteByIds { response, .. } => reply(response, error),
+            DatabaseOperation::WriteBlobChunk { response, .. } => reply(response, error),
+            DatabaseOperation::CreateTable { response, .. } => reply(response, error),
+            DatabaseOperation::CreateIndex { response, .. } => reply(response, error),
+            DatabaseOperation::AddColumn { response, .. } => reply(response, error),
+            DatabaseOperation::DropTable { response, .. } => reply(response, error),
+            DatabaseOperation::RenameTable { response, .. } => reply(response, error),
+            DatabaseOperation::ExecuteRawUpdate { response, .. } => reply(response, error),
+            DatabaseOperation::ExecuteBatch { response, .. } => reply(response, error),
+            DatabaseOperation::CreateStoredProcedure { response, .. } => reply(response, error),
+            DatabaseOperation::ExecuteStoredProcedure { response, .. } => reply(response, error),
+            DatabaseOperation::QueryRaw { response, .. } => reply(response, error),
+            DatabaseOperation::Find { response, .. } => reply(response, error),
+            DatabaseOperation::FindWithGroups { response, .. } => reply(response, error),
+            _ => {}
+        }
+    }
+
+    /// 可以试运行的写操作的名称和目标表，其他操作返回 `None`
+    ///
+    /// 只包含执行时会加入适配器事务作用域的插入、更新和删除，
+    /// 自行提交事务或执行DDL的写操作无法回滚，不能试运行
+    pub(crate) fn dry_run_target(&self) -> Option<(&'static str, &str)> {
+        match self {
+            DatabaseOperation::Create { table, .. } => Some(("create", table)),
+            DatabaseOperation::CreateMany { table, .. } => Some(("create_many", table)),
+            DatabaseOperation::Update { table, .. } => Some(("update", table)),
+            DatabaseOperation::UpdateWithOperations { table, .. } => Some(("update_with_operations", table)),
+            DatabaseOperation::UpdateById { table, .. } => Some(("update_by_id", table)),
+            DatabaseOperation::Delete { table, .. } => Some(("delete", table)),
+            DatabaseOperation::DeleteById { table, .. } => Some(("delete_by_id", table)),
+            DatabaseOperation::DeleteByIds { table, .. } => Some(("delete_by_ids", table)),
+            _ => None,
+        }
+    }
+
+    /// 将写入数据和查询条件中的日期时间截断到 `precision` 位小数秒
+    ///
+    /// 写入与查询使用相同的精度，精确匹配查询才能命中按列精度存储的值
+    pub(crate) fn truncate_datetimes(&mut self, precision: u8) {
+        fn truncate_data(data: &mut HashMap<String, DataValue>, precision: u8) {
+            data.values_mut().for_each(|value| value.truncate_datetime(precision));
+        }
+        fn truncate_conditions(conditions: &mut [QueryCondition], precision: u8) {
+            conditions.iter_mut().for_each(|condition| condition.value.truncate_datetime(precision));
+        }
+        fn truncate_groups(groups: &mut [QueryConditionGroup], precision: u8) {
+            for group in groups {
+                match group {
+                    QueryConditionGroup::Single(condition) => condition.value.truncate_datetime(precision),
+                    QueryConditionGroup::Group { conditions, .. } => truncate_groups(conditions, precision),
+                }
+            }
+        }
+
+        match self {
+            DatabaseOperation::Create { data, .. }
+            | DatabaseOperation::UpdateById { data, .. }
+            | DatabaseOperation::UpdateByIdDetailed { data, .. } => truncate_data(data, precision),
+            DatabaseOperation::CreateMany { data_list: rows, .. }
+            | DatabaseOperation::UpsertMany { rows, .. } => {
+                rows.iter_mut().for_each(|data| truncate_data(data, precision));
+            }
+            DatabaseOperation::Upsert { data, .. } => truncate_data(data, precision),
+            DatabaseOperation::Seed { fixtures, .. } => {
+                fixtures.iter_mut()
+                    .flat_map(|(_, records)| records.iter_mut())
+                    .for_each(|data| truncate_data(data, precision));
+            }
+            DatabaseOperation::Update { conditions, data, .. } => {
+                truncate_conditions(conditions, precision);
+                truncate_data(data, precision);
+            }
+            DatabaseOperation::UpdateWithOperations { conditions, operations, .. } => {
+                truncate_conditions(conditions, precision);
+                operations.iter_mut().for_each(|operation| operation.value.truncate_datetime(precision));
+            }
+            DatabaseOperation::Find { conditions, .. }
+            | DatabaseOperation::Delete { conditions, .. }
+            | DatabaseOperation::Count { conditions, .. }
+            | DatabaseOperation::TimeBucket { conditions, .. }
+            | DatabaseOperation::Aggregate { conditions, .. }
+            | DatabaseOperation::Exists { conditions, .. } => truncate_conditions(conditions, precision),
+            DatabaseOperation::FindWithGroups { condition_groups, .. } => truncate_groups(condition_groups, precision),
+            DatabaseOperation::WithSessionContext { operation, .. }
+            | DatabaseOperation::WithBatchPermit { operation, .. } => operation.truncate_datetimes(precision),
+            _ => {}
+        }
+    }
+
+    /// 按数据库配置建立查询日志作用域，并收集本次操作中需要脱敏的值
+    pub(crate) fn query_log_scope(
+        &self,
+        config: &DatabaseConfig,
+        detector: Option<Arc<crate::types::n_plus_one::NPlusOneDetector>>,
+    ) -> Option<crate::types::query_log::QueryLogScope> {
+        let mut scope = crate::types::query_log::QueryLogScope::new(config, detector)?;
+        match self {
+            DatabaseOperation::Create { data, .. }
+            | DatabaseOperation::UpdateById { data, .. }
+            | DatabaseOperation::UpdateByIdDetailed { data, .. } => scope.add_data(data),
+            DatabaseOperation::CreateMany { data_list: rows, .. }
+            | DatabaseOperation::UpsertMany { rows, .. } => {
+                for data in rows {
+                    scope.add_data(data);
+                }
+            }
+            DatabaseOperation::Upsert { data, .. } => scope.add_data(data),
+            DatabaseOperation::Seed { fixtures, .. } => {
+                for data in fixtures.iter().flat_map(|(_, records)| records) {
+                    scope.add_data(data);
+                }
+            }
+            DatabaseOperation::Update { conditions, data, .. } => {
+                scope.add_conditions(conditions);
+                scope.add_data(data);
+            }
+            DatabaseOperation::UpdateWithOperations { conditions, operations, .. } => {
+                scope.add_conditions(conditions);
+                scope.add_update_operations(operations);
+            }
+            DatabaseOperation::Find { conditions, .. }
+            | DatabaseOperation::Delete { conditions, .. }
+            | DatabaseOperation::Count { conditions, .. }
+            | DatabaseOperation::TimeBucket { conditions, .. }
+            | DatabaseOperation::Aggregate { conditions, .. }
+            | DatabaseOperation::Exists { conditions, .. } => scope.add_conditions(conditions),
+            DatabaseOperation::FindWithGroups { condition_groups, .. } => scope.add_condition_groups(condition_groups),
+            _ => {}
+        }
+        Some(scope)
+    }
+
+    /// 拆分出会话上下文与实际要执行的操作，嵌套时以最内层上下文为准
+    pub(crate) fn split_session_context(self) -> (Option<crate::types::SessionContext>, DatabaseOperation) {
+        let mut context = None;
+        let mut operation = self;
+        while let DatabaseOperation::WithSessionContext { context: inner_context, operation: inner } = operation {
+            context = Some(inner_context);
+            operation = *inner;
+        }
+        (context, operation)
+    }
 }
 
 /// 原生数据库连接枚举 - 直接持有数据库连接，不使用Arc包装
-#[derive(Debug)]
+///
+/// 内部的sqlx连接池和MongoDB数据库句柄本身即为引用计数实现，克隆开销很小
+#[derive(Debug, Clone)]
 pub enum DatabaseConnection {
     #[cfg(feature = "sqlite-support")]
     SQLite(sqlx::SqlitePool),
@@ -170,6 +571,9 @@ pub struct ConnectionWorker {
     pub db_type: DatabaseType,
     /// 数据库适配器（持久化，避免重复创建）
     pub adapter: Box<dyn crate::adapter::DatabaseAdapter + Send + Sync>,
+    /// 创建 `connection` 的MongoDB客户端，会话事务只能用于同一客户端创建的数据库句柄
+    #[cfg(feature = "mongodb-support")]
+    pub mongo_client: Option<mongodb::Client>,
 }
 
 impl std::fmt::Debug for ConnectionWorker {
@@ -185,3 +589,217 @@ impl std::fmt::Debug for ConnectionWorker {
             .finish()
     }
 }
+
+/// 按配置将"表不存在"错误转换为空结果，未开启时原样返回
+pub(crate) fn empty_on_missing_table<T: Default>(result: QuickDbResult<T>, enabled: bool) -> QuickDbResult<T> {
+    match result {
+        Err(e) if enabled && e.is_missing_table() => {
+            rat_logger::debug!("表不存在，按配置返回空结果: {}", e);
+            Ok(T::default())
+        }
+        other => other,
+    }
+}
+
+/// 原始SQL是否为单条只读查询
+///
+/// 跳过注释、字符串字面量和带引号的标识符后，第一个关键字必须是 `SELECT`、`WITH`、`SHOW`、`DESCRIBE`，
+/// 且不含分隔的多条语句和 `INSERT`、`UPDATE`、`DELETE`、`INTO` 等写入关键字（如 PostgreSQL 的可写CTE、
+/// `SELECT ... INTO`、`FOR UPDATE`）。无法确定时（如字面量中含反斜杠）按写操作处理
+pub(crate) fn is_read_only_sql(sql: &str) -> bool {
+    const READ_KEYWORDS: &[&str] = &["SELECT", "WITH", "SHOW", "DESCRIBE", "DESC"];
+    const WRITE_KEYWORDS: &[&str] = &[
+        "INSERT", "UPDATE", "DELETE", "MERGE", "UPSERT", "INTO", "CREATE", "ALTER", "DROP", "TRUNCATE",
+        "GRANT", "REVOKE", "CALL", "EXEC", "EXECUTE", "DO", "COPY", "LOCK", "SET", "PRAGMA", "ATTACH", "VACUUM",
+    ];
+
+    let chars: Vec<char> = sql.chars().collect();
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            '\'' | '"' | '`' => {
+                // 字符串字面量和带引号的标识符，连续两个引号表示转义
+                i += 1;
+                while i < chars.len() {
+                    if chars[i] == c {
+                        if chars.get(i + 1) == Some(&c) {
+                            i += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    if chars[i] == '\\' {
+                        // 反斜杠在 MySQL 中是转义符，在 PostgreSQL 标准字符串中不是，无法确定字面量在哪里结束
+                        return false;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            ';' => {
+                // 结尾的分号之后只能有空白
+                if chars[i + 1..].iter().any(|c| !c.is_whitespace()) {
+                    return false;
+                }
+                i += 1;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                words.push(chars[start..i].iter().collect::<String>().to_uppercase());
+            }
+            _ => i += 1,
+        }
+    }
+
+    match words.first() {
+        Some(first) if READ_KEYWORDS.contains(&first.as_str()) => {
+            !words.iter().any(|word| WRITE_KEYWORDS.contains(&word.as_str()))
+        }
+        _ => false,
+    }
+}
+
+/// 执行适配器调用，遇到瞬时错误时按连接池配置退避后重新执行
+///
+/// 只读操作（`is_write` 为假）遇到 [`QuickDbError::is_retryable`] 的错误都会重试；写操作在连接中断或超时时
+/// 服务端可能已经执行了语句，重新执行会重复插入或重复自增，只在请求发出之前的连接失败
+/// （[`QuickDbError::is_connect_failure`]）时重试。最多重试 `retry_attempts` 次，闭包每次调用都会重新发起操作。
+/// 处于适配器事务作用域（试运行、`seed`）时不重试，事务所在的连接已经失效，只能由调用方整体重来。
+/// 重试后仍失败时，错误消息中附带总尝试次数
+pub(crate) async fn retry_transient<T, F, Fut>(config: &ExtendedPoolConfig, is_write: bool, mut call: F) -> QuickDbResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = QuickDbResult<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match call().await {
+            Err(e) if (if is_write { e.is_connect_failure() } else { e.is_retryable() })
+                && attempt < config.base.retry_attempts
+                && !crate::adapter::transaction::in_transaction() =>
+            {
+                let delay = config.retry_delay(attempt, rand::random::<f64>());
+                rat_logger::warn!("操作遇到瞬时错误，{}ms 后第 {} 次重试: {}", delay.as_millis(), attempt + 1, e);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) if attempt > 0 => return Err(with_attempt_count(e, attempt + 1)),
+            result => return result,
+        }
+    }
+}
+
+/// 在错误消息末尾附上总尝试次数，尽量保持原有错误类别
+fn with_attempt_count(error: QuickDbError, attempts: u32) -> QuickDbError {
+    let suffix = format!("（共尝试 {} 次）", attempts);
+    match error {
+        QuickDbError::ConnectionError { message } => QuickDbError::ConnectionError { message: message + &suffix },
+        QuickDbError::PoolError { message } => QuickDbError::PoolError { message: message + &suffix },
+        QuickDbError::QueryError { message } => QuickDbError::QueryError { message: message + &suffix },
+        QuickDbError::TransactionError { message } => QuickDbError::TransactionError { message: message + &suffix },
+        other => QuickDbError::ConnectionError { message: format!("{}{}", other, suffix) },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn retry_config(attempts: u32) -> ExtendedPoolConfig {
+        let mut config = ExtendedPoolConfig::default();
+        config.base.retry_attempts = attempts;
+        config.base.retry_base_delay_ms = 1;
+        config.base.retry_max_delay_ms = 2;
+        config
+    }
+
+    #[test]
+    fn test_is_read_only_sql() {
+        assert!(is_read_only_sql("SELECT * FROM users WHERE note = 'delete me';"));
+        assert!(is_read_only_sql("  -- 报表\n(SELECT count(*) FROM \"update\")"));
+        assert!(is_read_only_sql("WITH recent AS (SELECT id FROM orders) SELECT * FROM recent"));
+        assert!(is_read_only_sql("SELECT REPLACE(name, 'a', 'b') FROM users"));
+
+        assert!(!is_read_only_sql("DELETE FROM users"));
+        assert!(!is_read_only_sql("/* SELECT */ UPDATE users SET age = 1"));
+        assert!(!is_read_only_sql("SELECT 1; DROP TABLE users"));
+        assert!(!is_read_only_sql("WITH gone AS (DELETE FROM users RETURNING id) SELECT * FROM gone"));
+        assert!(!is_read_only_sql("SELECT * INTO backup FROM users"));
+        assert!(!is_read_only_sql("SELECT * FROM users FOR UPDATE"));
+        assert!(!is_read_only_sql("EXPLAIN ANALYZE DELETE FROM users"));
+        assert!(!is_read_only_sql("SELECT 'a\\'; DELETE FROM users; --'"));
+        assert!(!is_read_only_sql(""));
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_retries_only_retryable_errors() {
+        // 连接错误重试后成功
+        let calls = AtomicU32::new(0);
+        let result = retry_transient(&retry_config(3), false, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(crate::quick_error!(connection, "connection refused")),
+                _ => Ok(42),
+            }
+        }).await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // 重试用尽后返回最后一次错误并附带尝试次数
+        let calls = AtomicU32::new(0);
+        let result: QuickDbResult<()> = retry_transient(&retry_config(2), false, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(crate::quick_error!(connection, "connection refused"))
+        }).await;
+        let err = result.unwrap_err();
+        assert!(err.is_connection_error());
+        assert!(err.to_string().contains("共尝试 3 次"), "{}", err);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // 唯一约束冲突不重试
+        let calls = AtomicU32::new(0);
+        let result: QuickDbResult<()> = retry_transient(&retry_config(3), false, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(crate::quick_error!(query, "duplicate key value violates unique constraint \"users_email_key\""))
+        }).await;
+        assert!(!result.unwrap_err().to_string().contains("共尝试"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // 写操作在连接中断后不重试，服务端可能已经执行了语句
+        let calls = AtomicU32::new(0);
+        let result: QuickDbResult<()> = retry_transient(&retry_config(3), true, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(crate::quick_error!(query, "error communicating with database: Connection reset by peer"))
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // 写操作在请求发出之前的连接失败时重试
+        let calls = AtomicU32::new(0);
+        let result = retry_transient(&retry_config(3), true, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(crate::quick_error!(query, "pool timed out while waiting for an open connection")),
+                _ => Ok(1u64),
+            }
+        }).await;
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -97,11 +97,16 @@ impl PostgresJsonProcessor {
                         field_type: crate::model::FieldType::String { max_length: None, min_length: None, regex: None },
                         required: false,
                         default: None,
+                        default_expr: None,
                         unique: false,
                         indexed: false,
                         description: None,
                         validator: None,
                         sqlite_compatibility: false,
+                        collation: None,
+                        array_elements: crate::model::ArrayElementPolicy::Strict,
+                        unique_case_insensitive: false,
+                        normalizers: Vec::new(),
                     }))
                     .collect::<Result<Vec<_>, String>>()?;
                 Ok(DataValue::Array(data_array))
@@ -113,11 +118,16 @@ impl PostgresJsonProcessor {
                             field_type: crate::model::FieldType::String { max_length: None, min_length: None, regex: None },
                             required: false,
                             default: None,
+                            default_expr: None,
                             unique: false,
                             indexed: false,
                             description: None,
                             validator: None,
                             sqlite_compatibility: false,
+                            collation: None,
+                            array_elements: crate::model::ArrayElementPolicy::Strict,
+                            unique_case_insensitive: false,
+                            normalizers: Vec::new(),
                         }).map(|val| (k.clone(), val))
                     })
                     .collect::<Result<HashMap<String, DataValue>, String>>()?;
@@ -2,7 +2,9 @@
 //!
 //! 使用JSON字符串与Python进行通信，通过全局任务队列系统执行数据库操作
 
+use chrono::{Datelike, Timelike};
 use pyo3::prelude::*;
+use pyo3::types::{timezone_utc_bound, PyBytes, PyDateTime, PyDict, PyList};
 use serde_json::{Value, json};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -110,6 +112,72 @@ impl PyJsonQueueBridge {
         })
     }
 
+    /// 查询记录，返回Python原生对象（字典列表）
+    pub fn find_objects(&self, py: Python<'_>, table: String, conditions_json: String, options_json: Option<String>) -> PyResult<PyObject> {
+        let rt = tokio::runtime::Runtime::new().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("无法创建运行时: {}", e))
+        })?;
+
+        let records = rt.block_on(async {
+            // 解析查询条件
+            let conditions: Vec<Value> = serde_json::from_str(&conditions_json)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("无效的查询条件JSON: {}", e)))?;
+
+            let query_conditions: Vec<QueryCondition> = conditions
+                .into_iter()
+                .map(|v| parse_query_condition(v))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // 解析查询选项
+            let options = if let Some(options_json) = options_json {
+                let opts: Value = serde_json::from_str(&options_json)
+                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("无效的查询选项JSON: {}", e)))?;
+                Some(parse_query_options(opts)?)
+            } else {
+                None
+            };
+
+            // 通过全局任务队列执行
+            let task_queue = get_global_task_queue();
+            task_queue.find_values(table, query_conditions, options).await
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("数据库查询失败: {}", e)))
+        })?;
+
+        let list = PyList::empty_bound(py);
+        for record in &records {
+            list.append(data_value_to_py(py, record)?)?;
+        }
+        Ok(list.into_py(py))
+    }
+
+    /// 根据ID查询记录，返回Python原生对象（字典或None）
+    pub fn find_by_id_object(&self, py: Python<'_>, table: String, id: String, options_json: Option<String>) -> PyResult<PyObject> {
+        let rt = tokio::runtime::Runtime::new().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("无法创建运行时: {}", e))
+        })?;
+
+        let record = rt.block_on(async {
+            // 解析查询选项
+            let options = if let Some(options_json) = options_json {
+                let opts: Value = serde_json::from_str(&options_json)
+                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("无效的查询选项JSON: {}", e)))?;
+                Some(parse_query_options(opts)?)
+            } else {
+                None
+            };
+
+            // 通过全局任务队列执行
+            let task_queue = get_global_task_queue();
+            task_queue.find_by_id_value(table, id, options).await
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("数据库查询失败: {}", e)))
+        })?;
+
+        match record {
+            Some(record) => data_value_to_py(py, &record),
+            None => Ok(py.None()),
+        }
+    }
+
     /// 更新记录
     pub fn update(&self, table: String, conditions_json: String, data_json: String, options_json: Option<String>) -> PyResult<u64> {
         let rt = tokio::runtime::Runtime::new().map_err(|e| {
@@ -315,6 +383,83 @@ pub(crate) fn json_value_to_data_value(value: Value) -> DataValue {
     }
 }
 
+/// 将DataValue转换为Python原生对象
+///
+/// 整数、浮点数、布尔值和字符串映射为对应的Python标量，数组映射为list，对象映射为dict，
+/// 日期时间映射为带UTC时区的datetime，二进制映射为bytes，UUID映射为字符串
+pub(crate) fn data_value_to_py(py: Python<'_>, value: &DataValue) -> PyResult<PyObject> {
+    Ok(match value {
+        DataValue::Null => py.None(),
+        DataValue::Bool(b) => b.into_py(py),
+        DataValue::Int(i) => i.into_py(py),
+        DataValue::Float(f) => f.into_py(py),
+        DataValue::String(s) => s.as_str().into_py(py),
+        DataValue::Bytes(bytes) => PyBytes::new_bound(py, bytes).into_py(py),
+        DataValue::DateTime(dt) => {
+            let utc = timezone_utc_bound(py);
+            PyDateTime::new_bound(
+                py,
+                dt.year(),
+                dt.month() as u8,
+                dt.day() as u8,
+                dt.hour() as u8,
+                dt.minute() as u8,
+                dt.second() as u8,
+                dt.timestamp_subsec_micros().min(999_999),
+                Some(&utc),
+            )?.into_py(py)
+        },
+        DataValue::Uuid(uuid) => uuid.to_string().into_py(py),
+        DataValue::Json(json) => json_value_to_py(py, json)?,
+        DataValue::Array(arr) => {
+            let list = PyList::empty_bound(py);
+            for item in arr {
+                list.append(data_value_to_py(py, item)?)?;
+            }
+            list.into_py(py)
+        },
+        DataValue::Object(obj) => {
+            let dict = PyDict::new_bound(py);
+            for (key, item) in obj {
+                dict.set_item(key, data_value_to_py(py, item)?)?;
+            }
+            dict.into_py(py)
+        },
+    })
+}
+
+/// 将JSON值转换为Python原生对象
+fn json_value_to_py(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_py(py),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                i.into_py(py)
+            } else if let Some(u) = n.as_u64() {
+                u.into_py(py)
+            } else {
+                n.as_f64().unwrap_or_default().into_py(py)
+            }
+        },
+        Value::String(s) => s.as_str().into_py(py),
+        Value::Array(arr) => {
+            let list = PyList::empty_bound(py);
+            for item in arr {
+                list.append(json_value_to_py(py, item)?)?;
+            }
+            list.into_py(py)
+        },
+        Value::Object(obj) => {
+            let dict = PyDict::new_bound(py);
+            for (key, item) in obj {
+                dict.set_item(key, json_value_to_py(py, item)?)?;
+            }
+            dict.into_py(py)
+        },
+    })
+}
+
 /// 解析查询条件
 pub fn parse_query_condition(value: Value) -> PyResult<QueryCondition> {
     let obj = value.as_object().ok_or_else(||
@@ -338,6 +483,7 @@ pub fn parse_query_condition(value: Value) -> PyResult<QueryCondition> {
         "lt" => crate::types::QueryOperator::Lt,
         "lte" => crate::types::QueryOperator::Lte,
         "contains" => crate::types::QueryOperator::Contains,
+        "iContains" => crate::types::QueryOperator::IContains,
         "startsWith" => crate::types::QueryOperator::StartsWith,
         "endsWith" => crate::types::QueryOperator::EndsWith,
         "in" => crate::types::QueryOperator::In,
@@ -346,6 +492,8 @@ pub fn parse_query_condition(value: Value) -> PyResult<QueryCondition> {
         "exists" => crate::types::QueryOperator::Exists,
         "isNull" => crate::types::QueryOperator::IsNull,
         "isNotNull" => crate::types::QueryOperator::IsNotNull,
+        "all" => crate::types::QueryOperator::ArrayContainsAll,
+        "size" => crate::types::QueryOperator::ArraySize,
         _ => return Err(pyo3::exceptions::PyValueError::new_err(format!("不支持的操作符: {}", operator_str))),
     };
 
@@ -12,7 +12,7 @@ use rat_logger::{info, warn, error};
 use chrono;
 
 // 导入必要的模块和类型
-use crate::types::{DataValue, DatabaseConfig, QueryOperator, QueryCondition};
+use crate::types::{DataValue, DatabaseConfig, IdType, QueryOperator, QueryCondition};
 use crate::manager::{get_global_pool_manager, add_database};
 use crate::model::ModelMeta;
 use crate::odm::OdmOperations;
@@ -125,6 +125,7 @@ impl SimpleQueueBridge {
             "register_model" => self.handle_register_model_odm(data).await,
             "create_table" => self.handle_create_table_odm(data).await,
             "drop_table" => self.handle_drop_table_odm(data).await,
+            "rename_table" => self.handle_rename_table_odm(data).await,
             "add_database" => self.handle_add_database_odm(data).await,
             _ => Err(format!("不支持的请求类型: {}", request_type)),
         };
@@ -371,31 +372,36 @@ impl SimpleQueueBridge {
             .ok_or("缺少表名")?;
 
         // 解析ID - 支持多种格式：字符串、DataValue格式、整数等
-        let id_str = if let Some(id_str) = request["id"].as_str() {
-            // 简单字符串格式
-            id_str.to_string()
+        let id: DataValue = if let Some(id_str) = request["id"].as_str() {
+            // 简单字符串格式，根据内容解析为数字或字符串ID
+            IdType::parse(id_str).into()
         } else if let Some(id_obj) = request["id"].as_object() {
-            // DataValue格式，如 {"String": "test_001"}
+            // DataValue格式，如 {"String": "test_001"}，显式类型标签保持原样
             if let Some(s) = id_obj.get("String").and_then(|v| v.as_str()) {
-                s.to_string()
+                DataValue::String(s.to_string())
             } else if let Some(i) = id_obj.get("Int").and_then(|v| v.as_i64()) {
-                i.to_string()
+                DataValue::Int(i)
+            } else if let Some(u) = id_obj.get("Uuid").and_then(|v| v.as_str()) {
+                Uuid::parse_str(u)
+                    .map(DataValue::Uuid)
+                    .map_err(|e| format!("UUID格式不正确: {}", e))?
             } else {
-                return Err("ID格式不支持，必须是String或Int类型".to_string());
+                return Err("ID格式不支持，必须是String、Int或Uuid类型".to_string());
             }
         } else if let Some(i) = request["id"].as_i64() {
             // 整数格式
-            i.to_string()
+            DataValue::Int(i)
         } else {
             return Err("缺少记录ID或ID格式不正确".to_string());
         };
+        let id_str = id.to_string();
 
         let alias = request.get("alias").and_then(|v| v.as_str());
 
         // 通过ODM层执行ID查询操作
         use crate::odm::get_odm_manager;
         let odm_manager = get_odm_manager().await;
-        let result = odm_manager.find_by_id(table, &id_str, alias).await
+        let result = odm_manager.find_by_id(table, id, alias).await
             .map_err(|e| format!("ODM ID查询操作失败: {}", e))?;
 
         match result {
@@ -592,6 +598,31 @@ impl SimpleQueueBridge {
         }).to_string())
     }
 
+    /// 使用ODM层处理表重命名操作
+    async fn handle_rename_table_odm(&self, data: &str) -> Result<String, String> {
+        let request: serde_json::Value = serde_json::from_str(data)
+            .map_err(|e| format!("解析表重命名请求失败: {}", e))?;
+
+        let from = request.get("from").and_then(|v| v.as_str())
+            .ok_or("缺少原表名")?;
+        let to = request.get("to").and_then(|v| v.as_str())
+            .ok_or("缺少新表名")?;
+        let alias = request.get("alias").and_then(|v| v.as_str());
+
+        info!("处理表重命名请求: {} -> {}, 数据库={:?}", from, to, alias);
+
+        use crate::odm::get_odm_manager;
+        let odm_manager = get_odm_manager().await;
+        odm_manager.rename_table(from, to, alias).await
+            .map_err(|e| format!("重命名表失败: {}", e))?;
+
+        info!("表重命名成功: {} -> {}", from, to);
+        Ok(serde_json::json!({
+            "success": true,
+            "message": "表重命名成功"
+        }).to_string())
+    }
+
         /// 解析查询条件
     fn parse_query_conditions(&self, conditions_value: serde_json::Value) -> Result<Vec<crate::types::QueryCondition>, String> {
         match conditions_value {
@@ -616,11 +647,13 @@ impl SimpleQueueBridge {
                             "lt" => QueryOperator::Lt,
                             "lte" => QueryOperator::Lte,
                             "like" => QueryOperator::Contains,
-                            "ilike" => QueryOperator::Contains,
+                            "ilike" => QueryOperator::IContains,
                             "in" => QueryOperator::In,
                             "not_in" => QueryOperator::NotIn,
                             "is_null" => QueryOperator::IsNull,
                             "is_not_null" => QueryOperator::IsNotNull,
+                            "all" => QueryOperator::ArrayContainsAll,
+                            "size" => QueryOperator::ArraySize,
                             _ => return Err(format!("不支持的操作符: {}", operator_str)),
                         };
 
@@ -40,6 +40,10 @@ pub struct SerializerConfig {
     pub datetime_format: Option<String>,
     /// 数字精度（浮点数小数位数）
     pub float_precision: Option<usize>,
+    /// 美化输出时的缩进空格数
+    pub indent: usize,
+    /// 是否按键名排序（递归作用于嵌套对象和数组，用于生成确定性输出）
+    pub sort_keys: bool,
 }
 
 impl Default for SerializerConfig {
@@ -50,6 +54,8 @@ impl Default for SerializerConfig {
             include_null: true,
             datetime_format: None,
             float_precision: None,
+            indent: 2,
+            sort_keys: false,
         }
     }
 }
@@ -90,6 +96,18 @@ impl SerializerConfig {
         self
     }
 
+    /// 设置美化输出的缩进空格数
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// 设置是否按键名排序
+    pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
     /// 创建PyO3兼容配置
     pub fn for_pyo3() -> Self {
         Self {
@@ -98,6 +116,8 @@ impl SerializerConfig {
             include_null: true,
             datetime_format: Some("%Y-%m-%dT%H:%M:%S%.3fZ".to_string()),
             float_precision: Some(6),
+            indent: 2,
+            sort_keys: false,
         }
     }
 
@@ -109,6 +129,8 @@ impl SerializerConfig {
             include_null: false,
             datetime_format: None,
             float_precision: None,
+            indent: 2,
+            sort_keys: false,
         }
     }
 
@@ -120,6 +142,8 @@ impl SerializerConfig {
             include_null: true,
             datetime_format: Some("%Y-%m-%d %H:%M:%S".to_string()),
             float_precision: Some(2),
+            indent: 2,
+            sort_keys: false,
         }
     }
 }
@@ -217,17 +241,13 @@ impl DataSerializer {
         match self.config.format {
             OutputFormat::JsonString => {
                 let json_obj = data_map_to_json_value(&processed_data)?;
-                let json_str = if self.config.pretty {
-                    serde_json::to_string_pretty(&json_obj)
-                } else {
-                    serde_json::to_string(&json_obj)
-                }.map_err(|e| QuickDbError::SerializationError { message: format!("序列化失败: {}", e) })?;
+                let json_str = self.to_json_string(json_obj)?;
                 
                 Ok(SerializationResult::JsonString(json_str))
             }
             OutputFormat::JsonObject => {
                 let json_obj = data_map_to_json_value(&processed_data)?;
-                Ok(SerializationResult::JsonObject(json_obj))
+                Ok(SerializationResult::JsonObject(self.apply_key_order(json_obj)))
             }
             OutputFormat::RawData => {
                 Ok(SerializationResult::RawData(processed_data))
@@ -253,11 +273,7 @@ impl DataSerializer {
                     json_array.push(json_obj);
                 }
                 
-                let json_str = if self.config.pretty {
-                    serde_json::to_string_pretty(&json_array)
-                } else {
-                    serde_json::to_string(&json_array)
-                }.map_err(|e| QuickDbError::SerializationError { message: format!("序列化失败: {}", e) })?;
+                let json_str = self.to_json_string(JsonValue::Array(json_array))?;
                 
                 Ok(SerializationResult::JsonString(json_str))
             }
@@ -267,7 +283,7 @@ impl DataSerializer {
                     let json_obj = data_map_to_json_value(&record)?;
                     json_array.push(json_obj);
                 }
-                Ok(SerializationResult::JsonObject(JsonValue::Array(json_array)))
+                Ok(SerializationResult::JsonObject(self.apply_key_order(JsonValue::Array(json_array))))
             }
             OutputFormat::RawData => {
                 // 对于原始数据格式，我们返回一个特殊的数据结构
@@ -336,17 +352,13 @@ impl DataSerializer {
         match self.config.format {
             OutputFormat::JsonString => {
                 let json_obj = data_map_to_json_value(&result_data)?;
-                let json_str = if self.config.pretty {
-                    serde_json::to_string_pretty(&json_obj)
-                } else {
-                    serde_json::to_string(&json_obj)
-                }.map_err(|e| QuickDbError::SerializationError { message: format!("序列化失败: {}", e) })?;
+                let json_str = self.to_json_string(json_obj)?;
                 
                 Ok(SerializationResult::JsonString(json_str))
             }
             OutputFormat::JsonObject => {
                 let json_obj = data_map_to_json_value(&result_data)?;
-                Ok(SerializationResult::JsonObject(json_obj))
+                Ok(SerializationResult::JsonObject(self.apply_key_order(json_obj)))
             }
             OutputFormat::RawData => {
                 Ok(SerializationResult::RawData(result_data))
@@ -354,6 +366,34 @@ impl DataSerializer {
         }
     }
 
+    /// 按配置将JSON值输出为字符串（应用键排序、美化和缩进）
+    fn to_json_string(&self, value: JsonValue) -> QuickDbResult<String> {
+        let value = self.apply_key_order(value);
+
+        if !self.config.pretty {
+            return serde_json::to_string(&value)
+                .map_err(|e| QuickDbError::SerializationError { message: format!("序列化失败: {}", e) });
+        }
+
+        let indent = " ".repeat(self.config.indent);
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+        let mut buffer = Vec::new();
+        let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+        value.serialize(&mut serializer)
+            .map_err(|e| QuickDbError::SerializationError { message: format!("序列化失败: {}", e) })?;
+        String::from_utf8(buffer)
+            .map_err(|e| QuickDbError::SerializationError { message: format!("序列化结果不是有效的UTF-8: {}", e) })
+    }
+
+    /// 按配置对JSON对象的键排序
+    fn apply_key_order(&self, value: JsonValue) -> JsonValue {
+        if self.config.sort_keys {
+            sort_json_keys(value)
+        } else {
+            value
+        }
+    }
+
     /// 处理数据（应用配置选项）
     fn process_data(&self, mut data: HashMap<String, DataValue>) -> QuickDbResult<HashMap<String, DataValue>> {
         // 移除空值字段（如果配置要求）
@@ -396,6 +436,21 @@ fn data_map_to_json_value(data: &HashMap<String, DataValue>) -> QuickDbResult<Js
     Ok(JsonValue::Object(json_map))
 }
 
+/// 递归地按键名排序JSON对象（包括嵌套对象和数组中的对象）
+fn sort_json_keys(value: JsonValue) -> JsonValue {
+    match value {
+        JsonValue::Object(obj) => {
+            let mut entries: Vec<(String, JsonValue)> = obj.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            JsonValue::Object(entries.into_iter()
+                .map(|(k, v)| (k, sort_json_keys(v)))
+                .collect())
+        }
+        JsonValue::Array(arr) => JsonValue::Array(arr.into_iter().map(sort_json_keys).collect()),
+        other => other,
+    }
+}
+
 /// 将JsonValue转换为DataValue映射
 fn json_value_to_data_map(json: &JsonValue) -> QuickDbResult<HashMap<String, DataValue>> {
     let mut data_map = HashMap::new();
@@ -471,3 +526,28 @@ pub fn serialize_query_result_for_rust(
     result.to_json_object()
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_keys_and_indent() {
+        let mut nested = HashMap::new();
+        nested.insert("z".to_string(), DataValue::Int(1));
+        nested.insert("a".to_string(), DataValue::Int(2));
+
+        let mut data = HashMap::new();
+        data.insert("name".to_string(), DataValue::String("alice".to_string()));
+        data.insert("age".to_string(), DataValue::Int(30));
+        data.insert("tags".to_string(), DataValue::Array(vec![DataValue::Object(nested)]));
+
+        let serializer = DataSerializer::new(SerializerConfig::new().pretty(true).indent(4).sort_keys(true));
+        let json_str = match serializer.serialize_record(data).unwrap() {
+            SerializationResult::JsonString(s) => s,
+            other => panic!("意外的序列化结果: {:?}", other),
+        };
+
+        let expected = "{\n    \"age\": 30,\n    \"name\": \"alice\",\n    \"tags\": [\n        {\n            \"a\": 2,\n            \"z\": 1\n        }\n    ]\n}";
+        assert_eq!(json_str, expected);
+    }
+}
@@ -294,6 +294,38 @@ impl TableManager {
         result
     }
     
+    /// 重命名表
+    ///
+    /// 成功后清除新旧两个表名的存在性和结构缓存
+    pub async fn rename_table(&self, from: &str, to: &str) -> QuickDbResult<()> {
+        let pools = self.pool_manager.get_connection_pools();
+        let pool = pools.get("default")
+            .ok_or_else(|| QuickDbError::ConfigError {
+                message: "无法获取默认连接池".to_string(),
+            })?
+            .clone();
+        
+        let result = pool.rename_table(from, to).await;
+        
+        if result.is_ok() {
+            {
+                let mut cache = self.existence_cache.write().await;
+                cache.remove(from);
+                cache.remove(to);
+            }
+            
+            {
+                let mut cache = self.schema_cache.write().await;
+                cache.remove(from);
+                cache.remove(to);
+            }
+            
+            info!("成功重命名表: {} -> {}", from, to);
+        }
+        
+        result
+    }
+    
     /// 删除并重建表
     /// 
     /// 这个方法会先删除指定的表，然后根据提供的模式重新创建表
@@ -108,13 +108,24 @@ impl TaskQueueManager {
         }
     }
     
-    /// 提交查询任务
+    /// 提交查询任务，返回JSON字符串形式的记录列表
     pub async fn find(
         &self,
         table: String,
         conditions: Vec<QueryCondition>,
         options: Option<QueryOptions>,
     ) -> QuickDbResult<String> {
+        let result = self.find_values(table, conditions, options).await?;
+        Ok(serde_json::to_string(&result).unwrap_or_default())
+    }
+
+    /// 提交查询任务，返回未序列化的记录
+    pub async fn find_values(
+        &self,
+        table: String,
+        conditions: Vec<QueryCondition>,
+        options: Option<QueryOptions>,
+    ) -> QuickDbResult<Vec<DataValue>> {
         let (response_tx, response_rx) = oneshot::channel();
         
         let task = DbTask::Find {
@@ -134,13 +145,24 @@ impl TaskQueueManager {
         }
     }
     
-    /// 提交根据ID查询任务
+    /// 提交根据ID查询任务，返回字符串形式的记录
     pub async fn find_by_id(
         &self,
         table: String,
         id: String,
         options: Option<QueryOptions>,
     ) -> QuickDbResult<Option<String>> {
+        let result = self.find_by_id_value(table, id, options).await?;
+        Ok(result.map(|v| v.to_string()))
+    }
+
+    /// 提交根据ID查询任务，返回未序列化的记录
+    pub async fn find_by_id_value(
+        &self,
+        table: String,
+        id: String,
+        options: Option<QueryOptions>,
+    ) -> QuickDbResult<Option<DataValue>> {
         let (response_tx, response_rx) = oneshot::channel();
         
         let task = DbTask::FindById {
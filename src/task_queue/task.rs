@@ -34,8 +34,8 @@ pub enum DbTask {
         conditions: Vec<QueryCondition>,
         /// 查询选项
         options: Option<QueryOptions>,
-        /// 结果返回通道
-        response_tx: oneshot::Sender<QuickDbResult<String>>,
+        /// 结果返回通道，返回未序列化的记录
+        response_tx: oneshot::Sender<QuickDbResult<Vec<DataValue>>>,
     },
 
     /// 使用条件组合查询记录任务
@@ -58,8 +58,8 @@ pub enum DbTask {
         id: String,
         /// 查询选项
         options: Option<QueryOptions>,
-        /// 结果返回通道
-        response_tx: oneshot::Sender<QuickDbResult<Option<String>>>,
+        /// 结果返回通道，返回未序列化的记录
+        response_tx: oneshot::Sender<QuickDbResult<Option<DataValue>>>,
     },
     
     /// 更新记录任务
@@ -358,17 +358,7 @@ pub enum ConflictStrategy {
 }
 
 /// 隔离级别
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum IsolationLevel {
-    /// 读未提交
-    ReadUncommitted,
-    /// 读已提交
-    ReadCommitted,
-    /// 可重复读
-    RepeatableRead,
-    /// 串行化
-    Serializable,
-}
+pub use crate::transaction::IsolationLevel;
 
 
 
@@ -422,7 +412,7 @@ impl Default for ExistsOptions {
 impl Default for TransactionOptions {
     fn default() -> Self {
         Self {
-            isolation_level: IsolationLevel::ReadCommitted,
+            isolation_level: IsolationLevel::BackendDefault,
             read_only: false,
             timeout: Some(60000), // 事务默认60秒超时
         }
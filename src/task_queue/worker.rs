@@ -13,7 +13,7 @@ use rat_logger::{info, warn, error, debug};
 use crate::error::{QuickDbResult, QuickDbError};
 use crate::pool::{ConnectionPool, DatabaseOperation};
 use crate::table::TableManager;
-use crate::types::{DataValue, QueryCondition, QueryConditionGroup, QueryOptions};
+use crate::types::{CountMode, DataValue, IdType, QueryCondition, QueryConditionGroup, QueryOptions};
 use super::task::{CreateOptions, UpdateOptions, DeleteOptions, CountOptions, ExistsOptions, TransactionOptions};
 
 use super::task::*;
@@ -260,7 +260,7 @@ impl TaskWorker {
         };
         
         // 发送操作到连接池
-        pool.operation_sender.send(operation)
+        pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -279,7 +279,7 @@ impl TaskWorker {
         table: &str,
         conditions: Vec<QueryCondition>,
         options: Option<QueryOptions>,
-    ) -> QuickDbResult<String> {
+    ) -> QuickDbResult<Vec<DataValue>> {
         let pool = self.get_connection_pool(None)?;
 
         let (tx, rx) = tokio::sync::oneshot::channel();
@@ -290,17 +290,15 @@ impl TaskWorker {
             response: tx,
         };
 
-        pool.operation_sender.send(operation)
+        pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
 
-        let result = rx.await
+        rx.await
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "等待数据库操作结果超时".to_string(),
-            })??;
-
-        Ok(serde_json::to_string(&result).unwrap_or_default())
+            })?
     }
 
     async fn handle_find_with_groups(
@@ -319,7 +317,7 @@ impl TaskWorker {
             response: tx,
         };
 
-        pool.operation_sender.send(operation)
+        pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -337,27 +335,25 @@ impl TaskWorker {
         table: &str,
         id: &str,
         options: Option<QueryOptions>,
-    ) -> QuickDbResult<Option<String>> {
+    ) -> QuickDbResult<Option<DataValue>> {
         let pool = self.get_connection_pool(None)?;
         
         let (tx, rx) = tokio::sync::oneshot::channel();
         let operation = DatabaseOperation::FindById {
             table: table.to_string(),
-            id: DataValue::String(id.to_string()),
+            id: IdType::parse(id).into(),
             response: tx,
         };
         
-        pool.operation_sender.send(operation)
+        pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
         
-        let result = rx.await
+        rx.await
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "等待数据库操作结果超时".to_string(),
-            })??;
-        
-        Ok(result.map(|v| v.to_string()))
+            })?
     }
     
     async fn handle_update(
@@ -377,7 +373,7 @@ impl TaskWorker {
             response: tx,
         };
         
-        pool.operation_sender.send(operation)
+        pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -407,7 +403,7 @@ impl TaskWorker {
             response: tx,
         };
         
-        pool.operation_sender.send(operation)
+        pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -435,7 +431,7 @@ impl TaskWorker {
             response: tx,
         };
         
-        pool.operation_sender.send(operation)
+        pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -463,7 +459,7 @@ impl TaskWorker {
             response: tx,
         };
         
-        pool.operation_sender.send(operation)
+        pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -480,18 +476,23 @@ impl TaskWorker {
         &self,
         table: &str,
         conditions: Vec<QueryCondition>,
-        _options: Option<CountOptions>,
+        options: Option<CountOptions>,
     ) -> QuickDbResult<u64> {
         let pool = self.get_connection_pool(None)?;
         
         let (tx, rx) = tokio::sync::oneshot::channel();
+        let mode = match options {
+            Some(options) if options.approximate => CountMode::Estimated,
+            _ => CountMode::Exact,
+        };
         let operation = DatabaseOperation::Count {
             table: table.to_string(),
             conditions,
+            mode,
             response: tx,
         };
         
-        pool.operation_sender.send(operation)
+        pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -519,7 +520,7 @@ impl TaskWorker {
             response: tx,
         };
         
-        pool.operation_sender.send(operation)
+        pool.dispatch_operation(operation)
             .map_err(|_| QuickDbError::ConnectionError {
                 message: "连接池操作通道已关闭".to_string(),
             })?;
@@ -552,7 +553,7 @@ impl TaskWorker {
                 response: tx,
             };
             
-            pool.operation_sender.send(operation)
+            pool.dispatch_operation(operation)
                 .map_err(|_| QuickDbError::ConnectionError {
                     message: "连接池操作通道已关闭".to_string(),
                 })?;
@@ -588,7 +589,7 @@ impl TaskWorker {
                 response: tx,
             };
             
-            pool.operation_sender.send(operation)
+            pool.dispatch_operation(operation)
                 .map_err(|_| QuickDbError::ConnectionError {
                     message: "连接池操作通道已关闭".to_string(),
                 })?;
@@ -623,7 +624,7 @@ impl TaskWorker {
                 response: tx,
             };
             
-            pool.operation_sender.send(operation)
+            pool.dispatch_operation(operation)
                 .map_err(|_| QuickDbError::ConnectionError {
                     message: "连接池操作通道已关闭".to_string(),
                 })?;
@@ -0,0 +1,236 @@
+//! 测试辅助模块（需启用 `testing` 特性）
+//!
+//! [`TestDb`] 为每个测试注册一个独立别名的数据库，免去构建连接池配置、添加数据库和清理的样板代码：
+//!
+//! ```ignore
+//! #[tokio::test]
+//! async fn creates_user() -> QuickDbResult<()> {
+//!     let db = TestDb::sqlite_memory().await?;
+//!     let ids = db.seed(&[User::sample()]).await?;
+//!     assert_eq!(ids.len(), 1);
+//!     db.teardown().await
+//! }
+//! ```
+//!
+//! 每个 `TestDb` 使用进程内唯一的别名，测试可以并行运行。SQLite 内存数据库随别名移除而销毁；
+//! PostgreSQL 等共享数据库只隔离别名，`seed` 写入的表会在清理时删除，并行测试应使用不同的表名。
+//! 注册时直接调用连接池管理器，不受"查询开始后禁止添加数据库"的全局锁限制。
+//!
+//! `#[tokio::test]` 为每个测试创建独立的运行时，而全局ODM管理器的后台任务默认运行在首次使用它的运行时上，
+//! 该测试结束后任务随之停止。因此 `TestDb` 会把ODM后台任务迁移到一个常驻的专用运行时。
+
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rat_logger::{debug, warn};
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::manager::get_global_pool_manager;
+use crate::model::Model;
+use crate::odm::AsyncOdmManager;
+use crate::types::*;
+
+/// 测试别名计数器
+static TEST_DB_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 承载ODM后台任务的常驻运行时，不随单个测试结束而关闭
+static ODM_RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("quickdb-test-odm")
+        .enable_all()
+        .build()
+        .expect("创建测试ODM运行时失败")
+});
+
+/// ODM管理器是否已迁移到常驻运行时
+static ODM_INSTALLED: tokio::sync::OnceCell<()> = tokio::sync::OnceCell::const_new();
+
+/// 确保全局ODM管理器运行在常驻运行时上
+async fn ensure_odm_runtime() {
+    ODM_INSTALLED
+        .get_or_init(|| async {
+            *crate::odm::get_odm_manager_mut().await = AsyncOdmManager::new_on(ODM_RUNTIME.handle());
+            debug!("测试ODM管理器已迁移到常驻运行时");
+        })
+        .await;
+}
+
+/// 生成进程内唯一的测试别名
+fn unique_alias(prefix: &str) -> String {
+    let n = TEST_DB_COUNTER.fetch_add(1, Ordering::SeqCst);
+    format!("test_{}_{}_{}", prefix, std::process::id(), n)
+}
+
+/// 测试用连接池配置：连接数少、超时短
+fn test_pool_config() -> QuickDbResult<PoolConfig> {
+    PoolConfig::builder()
+        .min_connections(1)
+        .max_connections(4)
+        .connection_timeout(10)
+        .idle_timeout(300)
+        .max_lifetime(1800)
+        .max_retries(1)
+        .retry_interval_ms(100)
+        .keepalive_interval_sec(60)
+        .health_check_timeout_sec(5)
+        .build()
+}
+
+/// 测试数据库
+///
+/// 离开作用域时尽力在后台移除别名和 `seed` 创建的表；需要确定清理完成时调用 [`TestDb::teardown`]
+pub struct TestDb {
+    alias: String,
+    db_type: DatabaseType,
+    /// 需要在清理时删除的表
+    tables: Mutex<BTreeSet<String>>,
+    torn_down: bool,
+}
+
+impl TestDb {
+    /// 使用给定配置注册测试数据库，配置中的别名会被替换为唯一别名
+    pub async fn with_config(mut config: DatabaseConfig) -> QuickDbResult<Self> {
+        crate::init();
+        ensure_odm_runtime().await;
+        config.alias = unique_alias(config.db_type.as_str());
+        let alias = config.alias.clone();
+        let db_type = config.db_type;
+
+        get_global_pool_manager().add_database(config).await?;
+        debug!("注册测试数据库: alias={}, type={:?}", alias, db_type);
+
+        Ok(Self {
+            alias,
+            db_type,
+            tables: Mutex::new(BTreeSet::new()),
+            torn_down: false,
+        })
+    }
+
+    /// 创建 SQLite 内存数据库
+    #[cfg(feature = "sqlite-support")]
+    pub async fn sqlite_memory() -> QuickDbResult<Self> {
+        let config = DatabaseConfig::builder()
+            .db_type(DatabaseType::SQLite)
+            .connection(ConnectionConfig::SQLite {
+                path: ":memory:".to_string(),
+                create_if_missing: true,
+            })
+            .pool(test_pool_config()?)
+            .alias("test_sqlite")
+            .id_strategy(IdStrategy::AutoIncrement)
+            .build()?;
+        Self::with_config(config).await
+    }
+
+    /// 按环境变量连接 PostgreSQL，未设置 `QUICKDB_TEST_PG_HOST` 时返回 `None` 以便跳过测试
+    ///
+    /// 读取的环境变量：`QUICKDB_TEST_PG_HOST`、`QUICKDB_TEST_PG_PORT`（默认 5432）、
+    /// `QUICKDB_TEST_PG_DATABASE`（默认 postgres）、`QUICKDB_TEST_PG_USER`（默认 postgres）、
+    /// `QUICKDB_TEST_PG_PASSWORD`（默认为空）
+    #[cfg(feature = "postgres-support")]
+    pub async fn postgres_from_env() -> QuickDbResult<Option<Self>> {
+        let host = match std::env::var("QUICKDB_TEST_PG_HOST") {
+            Ok(host) => host,
+            Err(_) => return Ok(None),
+        };
+        let env_or = |name: &str, default: &str| std::env::var(name).unwrap_or_else(|_| default.to_string());
+        let port = env_or("QUICKDB_TEST_PG_PORT", "5432").parse::<u16>()
+            .map_err(|e| crate::quick_error!(config, format!("QUICKDB_TEST_PG_PORT 无效: {}", e)))?;
+
+        let config = DatabaseConfig::builder()
+            .db_type(DatabaseType::PostgreSQL)
+            .connection(ConnectionConfig::PostgreSQL {
+                host,
+                port,
+                database: env_or("QUICKDB_TEST_PG_DATABASE", "postgres"),
+                username: env_or("QUICKDB_TEST_PG_USER", "postgres"),
+                password: env_or("QUICKDB_TEST_PG_PASSWORD", ""),
+                ssl_mode: Some("prefer".to_string()),
+                tls_config: None,
+                create_database_if_missing: false,
+            })
+            .pool(test_pool_config()?)
+            .alias("test_postgres")
+            .id_strategy(IdStrategy::AutoIncrement)
+            .build()?;
+        Self::with_config(config).await.map(Some)
+    }
+
+    /// 测试数据库的别名
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /// 测试数据库的类型
+    pub fn db_type(&self) -> DatabaseType {
+        self.db_type
+    }
+
+    /// 将模型实例批量写入测试数据库，按输入顺序返回生成的ID
+    ///
+    /// 写入的表会被记录，清理时删除
+    pub async fn seed<T: Model>(&self, items: &[T]) -> QuickDbResult<Vec<DataValue>> {
+        // 确保模型元数据已注册，写入时据此建表
+        let meta = T::meta();
+        let collection = T::collection_name();
+        self.track_table(&collection);
+
+        // 与模型写入一致地应用字段规范化，夹具数据才与线上数据形态相同
+        let data_list = items
+            .iter()
+            .map(|item| item.to_normalized_data_map(&meta))
+            .collect::<QuickDbResult<Vec<_>>>()?;
+        crate::odm::create_many(&collection, data_list, Some(&self.alias)).await
+    }
+
+    /// 记录需要在清理时删除的表（例如测试中直接写入的表）
+    pub fn track_table(&self, table: &str) {
+        self.tables.lock().insert(table.to_string());
+    }
+
+    /// 删除记录的表并移除别名
+    pub async fn teardown(mut self) -> QuickDbResult<()> {
+        self.torn_down = true;
+        let tables = std::mem::take(&mut *self.tables.lock());
+        cleanup(self.alias.clone(), tables).await
+    }
+}
+
+/// 删除表并移除别名，删除失败不影响别名移除
+async fn cleanup(alias: String, tables: BTreeSet<String>) -> QuickDbResult<()> {
+    let manager = get_global_pool_manager();
+    if let Some(pool) = manager.get_connection_pools().get(&alias).map(|pool| pool.clone()) {
+        for table in &tables {
+            if let Err(e) = pool.drop_table(table).await {
+                warn!("清理测试表失败: alias={}, table={}, error={}", alias, table, e);
+            }
+        }
+    }
+    match manager.remove_database(&alias).await {
+        Err(QuickDbError::AliasNotFound { .. }) => Ok(()),
+        other => other,
+    }
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        if self.torn_down {
+            return;
+        }
+        let tables = std::mem::take(&mut *self.tables.lock());
+        let alias = self.alias.clone();
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    if let Err(e) = cleanup(alias.clone(), tables).await {
+                        warn!("测试数据库后台清理失败: alias={}, error={}", alias, e);
+                    }
+                });
+            }
+            Err(_) => warn!("没有可用的Tokio运行时，测试数据库未清理: alias={}", self.alias),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! 事务隔离级别
+//!
+//! PostgreSQL/MySQL 在事务开始时执行 `SET TRANSACTION ISOLATION LEVEL ...`；
+//! SQLite 的事务始终是串行化的，其他级别仅做校验后忽略；
+//! MongoDB 会话映射为对应的读关注/写关注
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::DatabaseType;
+
+/// 事务隔离级别
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IsolationLevel {
+    /// 使用数据库默认级别（PostgreSQL 为读已提交，MySQL 为可重复读），不发出任何设置语句
+    #[default]
+    BackendDefault,
+    /// 读未提交
+    ReadUncommitted,
+    /// 读已提交
+    ReadCommitted,
+    /// 可重复读
+    RepeatableRead,
+    /// 串行化
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// SQL 标准中的级别名称，`BackendDefault` 没有对应名称
+    pub fn as_sql(&self) -> Option<&'static str> {
+        match self {
+            IsolationLevel::BackendDefault => None,
+            IsolationLevel::ReadUncommitted => Some("READ UNCOMMITTED"),
+            IsolationLevel::ReadCommitted => Some("READ COMMITTED"),
+            IsolationLevel::RepeatableRead => Some("REPEATABLE READ"),
+            IsolationLevel::Serializable => Some("SERIALIZABLE"),
+        }
+    }
+
+    /// 生成事务开始后需要执行的设置语句，无需设置时返回 `None`
+    ///
+    /// PostgreSQL 需在 `BEGIN` 之后、第一条查询之前执行；
+    /// MySQL 的 `SET TRANSACTION` 作用于下一个事务，需在 `BEGIN` 之前执行
+    pub fn set_transaction_sql(&self, db_type: &DatabaseType) -> QuickDbResult<Option<String>> {
+        match db_type {
+            DatabaseType::PostgreSQL | DatabaseType::MySQL => {
+                Ok(self.as_sql().map(|level| format!("SET TRANSACTION ISOLATION LEVEL {}", level)))
+            }
+            DatabaseType::SQLite => {
+                self.validate_for(db_type)?;
+                Ok(None)
+            }
+            DatabaseType::MongoDB => Ok(None),
+        }
+    }
+
+    /// 校验该级别在指定数据库上是否可用
+    ///
+    /// SQLite 的事务始终串行化，比所请求的级别更严格，因此任何级别都可接受；
+    /// 读未提交只在共享缓存模式下生效，这里同样按串行化执行
+    pub fn validate_for(&self, db_type: &DatabaseType) -> QuickDbResult<()> {
+        match (db_type, self) {
+            (DatabaseType::MongoDB, IsolationLevel::ReadUncommitted) => Err(QuickDbError::ConfigError {
+                message: "MongoDB 事务不支持读未提交隔离级别".to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// MongoDB 会话使用的读关注与写关注
+    ///
+    /// 读已提交对应 `majority` 读关注；可重复读和串行化对应 `snapshot` 读关注，
+    /// 写关注均为 `majority`，保证提交的数据不会被回滚。`BackendDefault` 沿用客户端默认设置
+    #[cfg(feature = "mongodb-support")]
+    pub fn mongodb_concerns(&self) -> QuickDbResult<Option<(mongodb::options::ReadConcern, mongodb::options::WriteConcern)>> {
+        use mongodb::options::{Acknowledgment, ReadConcern, WriteConcern};
+
+        self.validate_for(&DatabaseType::MongoDB)?;
+        let read_concern = match self {
+            IsolationLevel::BackendDefault | IsolationLevel::ReadUncommitted => return Ok(None),
+            IsolationLevel::ReadCommitted => ReadConcern::majority(),
+            IsolationLevel::RepeatableRead | IsolationLevel::Serializable => ReadConcern::snapshot(),
+        };
+        let write_concern = WriteConcern::builder().w(Acknowledgment::Majority).build();
+        Ok(Some((read_concern, write_concern)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_transaction_sql() {
+        assert_eq!(IsolationLevel::default(), IsolationLevel::BackendDefault);
+        assert_eq!(IsolationLevel::BackendDefault.set_transaction_sql(&DatabaseType::PostgreSQL).unwrap(), None);
+        assert_eq!(
+            IsolationLevel::Serializable.set_transaction_sql(&DatabaseType::MySQL).unwrap().as_deref(),
+            Some("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+        );
+        assert_eq!(IsolationLevel::ReadCommitted.set_transaction_sql(&DatabaseType::SQLite).unwrap(), None);
+        assert!(IsolationLevel::ReadUncommitted.validate_for(&DatabaseType::MongoDB).is_err());
+    }
+}
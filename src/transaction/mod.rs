@@ -0,0 +1,11 @@
+//! 事务支持
+//!
+//! 提供事务相关的通用设施，如隔离级别、保存点语句和死锁/序列化冲突的自动重试
+
+pub mod isolation;
+pub mod retry;
+pub mod savepoint;
+
+pub use isolation::IsolationLevel;
+pub use retry::{TransactionRetryConfig, retry_transaction};
+pub use savepoint::SavepointAction;
@@ -0,0 +1,145 @@
+//! 事务冲突自动重试
+//!
+//! 可串行化（SERIALIZABLE）或可重复读（REPEATABLE READ）隔离级别下，
+//! 事务可能因死锁（MySQL 1213、PostgreSQL 40P01）或序列化失败（40001）而被数据库中止。
+//! 这类错误可以安全地从头重新执行整个事务，[`retry_transaction`] 负责重试循环，
+//! 其他错误立即返回，不做重试。
+
+use std::future::Future;
+use std::time::Duration;
+use rat_logger::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::error::QuickDbResult;
+
+/// 事务冲突重试配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionRetryConfig {
+    /// 最大重试次数（不含首次执行），为 0 时不重试
+    pub max_retries: u32,
+    /// 首次重试前的等待时间（毫秒），之后每次翻倍
+    pub initial_backoff_ms: u64,
+    /// 单次等待时间上限（毫秒）
+    pub max_backoff_ms: u64,
+}
+
+impl Default for TransactionRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 1000,
+        }
+    }
+}
+
+impl TransactionRetryConfig {
+    /// 第 `attempt` 次重试（从0开始）前的等待时间，按 [`jittered_backoff`] 的规则取随机值，
+    /// 避免冲突的事务同时重试再次冲突
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        jittered_backoff(self.initial_backoff_ms, self.max_backoff_ms, attempt, rand::random())
+    }
+}
+
+/// 第 `attempt` 次重试（从0开始）前的等待时间
+///
+/// 以 `initial_ms` 为基数指数增长，不超过 `max_ms`，再按 `roll`（`[0, 1)` 区间内的随机数）
+/// 在一半到全部之间取值。事务冲突重试、SQLite 忙重试和连接池操作重试共用这一规则
+pub(crate) fn jittered_backoff(initial_ms: u64, max_ms: u64, attempt: u32, roll: f64) -> Duration {
+    let delay = initial_ms
+        .saturating_mul(1u64 << attempt.min(32))
+        .min(max_ms);
+    let half = delay / 2;
+    Duration::from_millis(half + ((delay - half) as f64 * roll) as u64)
+}
+
+/// 执行事务闭包，遇到死锁或序列化失败时按配置退避后整体重试
+///
+/// 闭包每次重试都会被重新调用，必须从头完成整个事务（开启、读写、提交），
+/// 且不能依赖上一次执行留下的状态。
+///
+/// ```ignore
+/// let result = retry_transaction(TransactionRetryConfig::default(), || async {
+///     // 开启事务、执行读写并提交
+///     Ok(())
+/// }).await?;
+/// ```
+pub async fn retry_transaction<T, F, Fut>(config: TransactionRetryConfig, mut transaction: F) -> QuickDbResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = QuickDbResult<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match transaction().await {
+            Err(e) if e.is_retryable_conflict() && attempt < config.max_retries => {
+                let delay = config.backoff(attempt);
+                attempt += 1;
+                warn!("事务因冲突中止，{}ms 后进行第 {}/{} 次重试: {}",
+                      delay.as_millis(), attempt, config.max_retries, e);
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::QuickDbError;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_jittered_backoff_grows_exponentially_up_to_cap() {
+        assert_eq!(jittered_backoff(100, 1000, 0, 0.0), Duration::from_millis(50));
+        assert_eq!(jittered_backoff(100, 1000, 0, 0.99), Duration::from_millis(99));
+        assert_eq!(jittered_backoff(100, 1000, 2, 0.5), Duration::from_millis(300));
+        assert_eq!(jittered_backoff(100, 1000, 40, 0.0), Duration::from_millis(500));
+        assert_eq!(jittered_backoff(0, 1000, 3, 0.5), Duration::ZERO);
+
+        let config = TransactionRetryConfig::default();
+        for attempt in 0..40 {
+            assert!(config.backoff(attempt) <= Duration::from_millis(config.max_backoff_ms));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_transaction_on_conflict() {
+        let config = TransactionRetryConfig { max_retries: 3, initial_backoff_ms: 1, max_backoff_ms: 2 };
+
+        // 前两次死锁，第三次成功
+        let calls = AtomicU32::new(0);
+        let result = retry_transaction(config, || async {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(QuickDbError::QueryError {
+                    message: "执行PostgreSQL更新失败: error returned from database: deadlock detected".to_string(),
+                })
+            } else {
+                Ok(42)
+            }
+        }).await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // 非冲突错误立即返回
+        let calls = AtomicU32::new(0);
+        let result: QuickDbResult<()> = retry_transaction(config, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(QuickDbError::QueryError { message: "语法错误".to_string() })
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // 超过重试次数后返回最后一次的错误
+        let calls = AtomicU32::new(0);
+        let result: QuickDbResult<()> = retry_transaction(config, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(QuickDbError::QueryError {
+                message: "1213 (40001): Deadlock found when trying to get lock; try restarting transaction".to_string(),
+            })
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+}
@@ -0,0 +1,87 @@
+//! 事务保存点
+//!
+//! 在较大的事务中标记一个位置，后续步骤失败时只回滚到该位置，事务的其余部分继续执行，
+//! 用于"尝试一个可选步骤，失败也继续"的场景。SQLite、PostgreSQL 和 MySQL 都支持
+//! `SAVEPOINT`/`ROLLBACK TO SAVEPOINT`/`RELEASE SAVEPOINT`，MongoDB 事务没有保存点
+//!
+//! 显式事务中通过 [`Transaction::savepoint`](crate::odm::Transaction::savepoint)、
+//! [`Transaction::rollback_to`](crate::odm::Transaction::rollback_to) 和
+//! [`Transaction::release`](crate::odm::Transaction::release) 使用
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{QuickDbError, QuickDbResult};
+use crate::types::DatabaseType;
+
+/// 保存点操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SavepointAction {
+    /// 创建保存点，同名保存点已存在时新的保存点会遮蔽旧的
+    Create,
+    /// 回滚到保存点，保存点本身保留，可以再次回滚
+    RollbackTo,
+    /// 释放保存点，其后的修改并入外层事务
+    Release,
+}
+
+impl SavepointAction {
+    /// 生成在事务中执行的保存点语句
+    ///
+    /// 名称直接作为标识符写入语句，只允许字母、数字和下划线且不能以数字开头；
+    /// MongoDB 返回不支持的错误
+    pub fn sql(&self, db_type: &DatabaseType, name: &str) -> QuickDbResult<String> {
+        if *db_type == DatabaseType::MongoDB {
+            return Err(QuickDbError::UnsupportedDatabase {
+                db_type: "MongoDB 事务不支持保存点".to_string(),
+            });
+        }
+        validate_savepoint_name(name)?;
+        Ok(match self {
+            SavepointAction::Create => format!("SAVEPOINT {}", name),
+            SavepointAction::RollbackTo => format!("ROLLBACK TO SAVEPOINT {}", name),
+            SavepointAction::Release => format!("RELEASE SAVEPOINT {}", name),
+        })
+    }
+}
+
+/// 校验保存点名称
+fn validate_savepoint_name(name: &str) -> QuickDbResult<()> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(QuickDbError::ValidationError {
+            field: "savepoint".to_string(),
+            message: format!("保存点名称无效: {}，只允许字母、数字和下划线且不能以数字开头", name),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_savepoint_sql() {
+        assert_eq!(
+            SavepointAction::Create.sql(&DatabaseType::PostgreSQL, "optional_step").unwrap(),
+            "SAVEPOINT optional_step"
+        );
+        assert_eq!(
+            SavepointAction::RollbackTo.sql(&DatabaseType::SQLite, "optional_step").unwrap(),
+            "ROLLBACK TO SAVEPOINT optional_step"
+        );
+        assert_eq!(
+            SavepointAction::Release.sql(&DatabaseType::MySQL, "optional_step").unwrap(),
+            "RELEASE SAVEPOINT optional_step"
+        );
+        assert!(SavepointAction::Create.sql(&DatabaseType::PostgreSQL, "1st").is_err());
+        assert!(SavepointAction::Create.sql(&DatabaseType::PostgreSQL, "a; DROP TABLE users").is_err());
+        assert!(matches!(
+            SavepointAction::Create.sql(&DatabaseType::MongoDB, "optional_step"),
+            Err(QuickDbError::UnsupportedDatabase { .. })
+        ));
+    }
+}
@@ -0,0 +1,39 @@
+//! 二进制流类型
+//!
+//! `read_blob`/`write_blob` 按 [`BLOB_CHUNK_SIZE`] 分块读写二进制列，每块是一次独立的数据库操作，
+//! 内存中只保留一块数据。SQL 数据库直接读写列中的片段；MongoDB 需要配置 GridFS 存储桶，
+//! 数据按 GridFS 格式存放在存储桶中，以 [`BlobLocation::gridfs_filename`] 作为文件名
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::DataValue;
+
+/// 每次读写的块大小（255 KiB，与 GridFS 默认块大小一致）
+pub const BLOB_CHUNK_SIZE: usize = 255 * 1024;
+
+/// 二进制流的位置：表、记录ID和二进制列
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlobLocation {
+    /// 表/集合名
+    pub table: String,
+    /// 记录ID
+    pub id: DataValue,
+    /// 二进制列名
+    pub column: String,
+}
+
+impl BlobLocation {
+    /// 创建二进制流位置
+    pub fn new(table: &str, id: DataValue, column: &str) -> Self {
+        Self {
+            table: table.to_string(),
+            id,
+            column: column.to_string(),
+        }
+    }
+
+    /// 在 GridFS 中使用的文件名，格式为 `表/ID/列`
+    pub fn gridfs_filename(&self) -> String {
+        format!("{}/{}/{}", self.table, self.id, self.column)
+    }
+}
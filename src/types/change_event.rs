@@ -0,0 +1,74 @@
+//! 数据变更事件类型
+//!
+//! 定义变更订阅（PostgreSQL LISTEN/NOTIFY、MongoDB Change Stream）和进程内事件总线共用的事件结构
+
+use crate::error::QuickDbResult;
+use crate::types::DataValue;
+use futures::stream::{BoxStream, Stream};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use serde::{Deserialize, Serialize};
+
+/// 变更操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeOperation {
+    /// 插入
+    Insert,
+    /// 更新（MongoDB的替换操作也归为更新）
+    Update,
+    /// 删除
+    Delete,
+}
+
+impl ChangeOperation {
+    /// 从字符串解析操作类型（兼容PostgreSQL触发器的 TG_OP 和 MongoDB 的 operationType）
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "insert" => Some(ChangeOperation::Insert),
+            "update" | "replace" => Some(ChangeOperation::Update),
+            "delete" => Some(ChangeOperation::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// 数据变更事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    /// 表名/集合名
+    pub table: String,
+    /// 操作类型
+    pub op: ChangeOperation,
+    /// 变更记录的ID
+    pub id: DataValue,
+    /// 变更的数据：数据库订阅中为收到变更时的当前完整记录，进程内事件中为本次写入的字段（删除事件为None）
+    pub document: Option<DataValue>,
+}
+
+/// 变更事件流
+///
+/// 实现了 `futures::Stream`，丢弃即取消订阅
+pub struct ChangeStream {
+    inner: BoxStream<'static, QuickDbResult<ChangeEvent>>,
+}
+
+impl ChangeStream {
+    /// 包装底层事件流
+    pub fn new(inner: BoxStream<'static, QuickDbResult<ChangeEvent>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl std::fmt::Debug for ChangeStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChangeStream").finish_non_exhaustive()
+    }
+}
+
+impl Stream for ChangeStream {
+    type Item = QuickDbResult<ChangeEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
@@ -61,7 +61,109 @@ impl std::fmt::Debug for DataValue {
     }
 }
 
+/// 便于阅读的 `DataValue` 展示形式，用于日志和错误信息
+///
+/// 以紧凑的类 JSON 文本展示：字符串加引号，日期时间为 RFC 3339，字节数组只显示长度，
+/// 对象按键名排序。使用 `{:#}` 格式化时按两个空格缩进换行。
+/// 仅用于展示，不保证能解析回原值；精确表示仍使用 `{:?}`
+pub struct PrettyDataValue<'a>(PrettyTarget<'a>);
+
+enum PrettyTarget<'a> {
+    Value(&'a DataValue),
+    Fields(&'a HashMap<String, DataValue>),
+}
+
+impl std::fmt::Display for PrettyDataValue<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let indent = f.alternate().then_some(0);
+        match self.0 {
+            PrettyTarget::Value(value) => write_pretty(f, value, indent),
+            PrettyTarget::Fields(fields) => write_pretty_object(f, fields, indent),
+        }
+    }
+}
+
+/// 写入单个值，`indent` 为 `Some(当前层级)` 时换行缩进
+fn write_pretty(f: &mut std::fmt::Formatter<'_>, value: &DataValue, indent: Option<usize>) -> std::fmt::Result {
+    match value {
+        DataValue::Null => write!(f, "null"),
+        DataValue::Bool(b) => write!(f, "{}", b),
+        DataValue::Int(i) => write!(f, "{}", i),
+        DataValue::Float(fl) => write!(f, "{:?}", fl),
+        DataValue::String(s) => write!(f, "{}", serde_json::Value::from(s.as_str())),
+        DataValue::Bytes(bytes) => write!(f, "<{} bytes>", bytes.len()),
+        DataValue::DateTime(dt) => write!(f, "{}", dt.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)),
+        DataValue::Uuid(uuid) => write!(f, "{}", uuid),
+        DataValue::Json(json) => write!(f, "{}", json),
+        DataValue::Array(items) => {
+            if items.is_empty() {
+                return write!(f, "[]");
+            }
+            write!(f, "[")?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ",")?;
+                }
+                match indent {
+                    Some(level) => {
+                        write!(f, "\n{}", "  ".repeat(level + 1))?;
+                        write_pretty(f, item, Some(level + 1))?;
+                    }
+                    None => {
+                        if i > 0 {
+                            write!(f, " ")?;
+                        }
+                        write_pretty(f, item, None)?;
+                    }
+                }
+            }
+            match indent {
+                Some(level) => write!(f, "\n{}]", "  ".repeat(level)),
+                None => write!(f, "]"),
+            }
+        }
+        DataValue::Object(fields) => write_pretty_object(f, fields, indent),
+    }
+}
+
+/// 写入对象，键按名称排序，保证同一数据的输出稳定
+fn write_pretty_object(f: &mut std::fmt::Formatter<'_>, fields: &HashMap<String, DataValue>, indent: Option<usize>) -> std::fmt::Result {
+    if fields.is_empty() {
+        return write!(f, "{{}}");
+    }
+    let mut keys: Vec<&String> = fields.keys().collect();
+    keys.sort();
+    write!(f, "{{")?;
+    for (i, key) in keys.into_iter().enumerate() {
+        if i > 0 {
+            write!(f, ",")?;
+        }
+        match indent {
+            Some(level) => write!(f, "\n{}", "  ".repeat(level + 1))?,
+            None if i > 0 => write!(f, " ")?,
+            None => {}
+        }
+        write!(f, "{}: ", serde_json::Value::from(key.as_str()))?;
+        write_pretty(f, &fields[key], indent.map(|level| level + 1))?;
+    }
+    match indent {
+        Some(level) => write!(f, "\n{}}}", "  ".repeat(level)),
+        None => write!(f, "}}"),
+    }
+}
+
 impl DataValue {
+    /// 将日期时间截断到 `precision` 位小数秒，数组和对象中的日期时间一并处理
+    pub(crate) fn truncate_datetime(&mut self, precision: u8) {
+        use chrono::SubsecRound;
+        match self {
+            DataValue::DateTime(dt) => *dt = dt.trunc_subsecs(precision as u16),
+            DataValue::Array(items) => items.iter_mut().for_each(|item| item.truncate_datetime(precision)),
+            DataValue::Object(fields) => fields.values_mut().for_each(|value| value.truncate_datetime(precision)),
+            _ => {}
+        }
+    }
+
     /// 获取数据类型名称
     pub fn type_name(&self) -> &'static str {
         match self {
@@ -84,6 +186,21 @@ impl DataValue {
         matches!(self, DataValue::Null)
     }
 
+    /// 便于阅读的展示形式，适合写入日志和错误信息，`{:#}` 格式化时缩进换行
+    pub fn pretty(&self) -> PrettyDataValue<'_> {
+        PrettyDataValue(PrettyTarget::Value(self))
+    }
+
+    /// 以便于阅读的形式展示字段映射（如模型的数据映射），格式与 [`DataValue::pretty`] 相同
+    pub fn pretty_fields(fields: &HashMap<String, DataValue>) -> PrettyDataValue<'_> {
+        PrettyDataValue(PrettyTarget::Fields(fields))
+    }
+
+    /// 转换为便于阅读的紧凑文本
+    pub fn to_pretty_string(&self) -> String {
+        self.pretty().to_string()
+    }
+
     /// 转换为 JSON 字符串
     pub fn to_json_string(&self) -> Result<String, crate::error::QuickDbError> {
         serde_json::to_string(self).map_err(|e| {
@@ -559,6 +676,18 @@ impl From<Vec<f64>> for DataValue {
     }
 }
 
+impl From<Vec<DataValue>> for DataValue {
+    fn from(value: Vec<DataValue>) -> Self {
+        DataValue::Array(value)
+    }
+}
+
+impl From<HashMap<String, DataValue>> for DataValue {
+    fn from(value: HashMap<String, DataValue>) -> Self {
+        DataValue::Object(value)
+    }
+}
+
 impl<T> From<Option<T>> for DataValue
 where
     T: Into<DataValue>,
@@ -570,3 +699,219 @@ where
         }
     }
 }
+
+/// 从 DataValue 转换为 Rust 标量类型
+///
+/// 用于 `query_scalar` 等只关心单个值的场景。数值类型接受整数、浮点数和可解析的字符串
+/// （部分数据库以字符串返回 DECIMAL 聚合结果），布尔类型额外接受 0/1（SQLite 以整数存储布尔值）。
+/// `DataValue::Null` 不在此处处理，由调用方映射为 `None`
+pub trait FromDataValue: Sized {
+    /// 执行转换，类型不兼容时返回序列化错误
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self>;
+}
+
+/// 构造类型不兼容的转换错误
+fn conversion_error(value: &DataValue, target: &str) -> crate::error::QuickDbError {
+    crate::quick_error!(serialization, format!("无法将 {} 转换为 {}", value.type_name(), target))
+}
+
+impl FromDataValue for DataValue {
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self> {
+        Ok(value)
+    }
+}
+
+impl FromDataValue for i64 {
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self> {
+        match &value {
+            DataValue::Int(i) => Ok(*i),
+            DataValue::Bool(b) => Ok(i64::from(*b)),
+            DataValue::Float(f) if f.fract() == 0.0 && *f >= i64::MIN as f64 && *f <= i64::MAX as f64 => Ok(*f as i64),
+            DataValue::String(s) => s.trim().parse::<i64>().map_err(|_| conversion_error(&value, "i64")),
+            _ => Err(conversion_error(&value, "i64")),
+        }
+    }
+}
+
+impl FromDataValue for i32 {
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self> {
+        let wide = i64::from_data_value(value.clone())?;
+        i32::try_from(wide).map_err(|_| conversion_error(&value, "i32"))
+    }
+}
+
+impl FromDataValue for u64 {
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self> {
+        match &value {
+            DataValue::String(s) => s.trim().parse::<u64>().map_err(|_| conversion_error(&value, "u64")),
+            _ => {
+                let wide = i64::from_data_value(value.clone())?;
+                u64::try_from(wide).map_err(|_| conversion_error(&value, "u64"))
+            }
+        }
+    }
+}
+
+impl FromDataValue for f64 {
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self> {
+        match &value {
+            DataValue::Float(f) => Ok(*f),
+            DataValue::Int(i) => Ok(*i as f64),
+            DataValue::String(s) => s.trim().parse::<f64>().map_err(|_| conversion_error(&value, "f64")),
+            _ => Err(conversion_error(&value, "f64")),
+        }
+    }
+}
+
+impl FromDataValue for f32 {
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self> {
+        f64::from_data_value(value).map(|f| f as f32)
+    }
+}
+
+impl FromDataValue for bool {
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self> {
+        match &value {
+            DataValue::Bool(b) => Ok(*b),
+            DataValue::Int(0) => Ok(false),
+            DataValue::Int(1) => Ok(true),
+            DataValue::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+                "true" | "t" | "1" => Ok(true),
+                "false" | "f" | "0" => Ok(false),
+                _ => Err(conversion_error(&value, "bool")),
+            },
+            _ => Err(conversion_error(&value, "bool")),
+        }
+    }
+}
+
+impl FromDataValue for String {
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self> {
+        match value {
+            DataValue::String(s) => Ok(s),
+            DataValue::Int(i) => Ok(i.to_string()),
+            DataValue::Float(f) => Ok(f.to_string()),
+            DataValue::Bool(b) => Ok(b.to_string()),
+            DataValue::Uuid(u) => Ok(u.to_string()),
+            DataValue::DateTime(dt) => Ok(dt.to_rfc3339()),
+            other => Err(conversion_error(&other, "String")),
+        }
+    }
+}
+
+impl FromDataValue for DateTime<Utc> {
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self> {
+        match &value {
+            DataValue::DateTime(dt) => Ok(*dt),
+            DataValue::String(s) => DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|_| conversion_error(&value, "DateTime<Utc>")),
+            _ => Err(conversion_error(&value, "DateTime<Utc>")),
+        }
+    }
+}
+
+impl FromDataValue for Uuid {
+    fn from_data_value(value: DataValue) -> crate::error::QuickDbResult<Self> {
+        match &value {
+            DataValue::Uuid(u) => Ok(*u),
+            DataValue::String(s) => s.parse::<Uuid>().map_err(|_| conversion_error(&value, "Uuid")),
+            _ => Err(conversion_error(&value, "Uuid")),
+        }
+    }
+}
+
+/// 取出单行结果的唯一列值
+///
+/// 行结果以无序映射返回，无法确定"第一列"，因此要求查询恰好返回一列
+pub(crate) fn single_column_value(row: DataValue) -> crate::error::QuickDbResult<DataValue> {
+    match row {
+        DataValue::Object(map) if map.len() == 1 => Ok(map.into_values().next().unwrap_or(DataValue::Null)),
+        DataValue::Object(map) => {
+            let mut columns: Vec<_> = map.into_keys().collect();
+            columns.sort();
+            Err(crate::quick_error!(query, format!("标量查询应只返回一列，实际返回 {} 列: {}", columns.len(), columns.join(", "))))
+        },
+        other => Ok(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_data_value_numeric() {
+        assert_eq!(i64::from_data_value(DataValue::Int(42)).unwrap(), 42);
+        assert_eq!(i64::from_data_value(DataValue::String("7".into())).unwrap(), 7);
+        assert_eq!(i32::from_data_value(DataValue::Float(3.0)).unwrap(), 3);
+        assert!(i32::from_data_value(DataValue::Int(i64::MAX)).is_err());
+        assert!(u64::from_data_value(DataValue::Int(-1)).is_err());
+        assert_eq!(f64::from_data_value(DataValue::Int(2)).unwrap(), 2.0);
+        assert_eq!(f64::from_data_value(DataValue::String("12.50".into())).unwrap(), 12.5);
+        assert!(i64::from_data_value(DataValue::Float(1.5)).is_err());
+    }
+
+    #[test]
+    fn test_from_data_value_bool_and_string() {
+        assert!(bool::from_data_value(DataValue::Int(1)).unwrap());
+        assert!(!bool::from_data_value(DataValue::Bool(false)).unwrap());
+        assert!(bool::from_data_value(DataValue::Int(2)).is_err());
+        assert_eq!(String::from_data_value(DataValue::Int(5)).unwrap(), "5");
+        assert!(String::from_data_value(DataValue::Null).is_err());
+    }
+
+    #[test]
+    fn test_pretty_rendering() {
+        let mut fields = HashMap::new();
+        fields.insert("name".to_string(), DataValue::String("a\"b".to_string()));
+        fields.insert("avatar".to_string(), DataValue::Bytes(vec![0; 2048]));
+        fields.insert(
+            "created_at".to_string(),
+            DataValue::DateTime(DateTime::parse_from_rfc3339("2024-05-01T08:30:00Z").unwrap().with_timezone(&Utc)),
+        );
+        fields.insert("tags".to_string(), DataValue::Array(vec![DataValue::Int(1), DataValue::Float(2.0)]));
+        let value = DataValue::Object(fields);
+
+        assert_eq!(
+            value.to_pretty_string(),
+            r#"{"avatar": <2048 bytes>, "created_at": 2024-05-01T08:30:00Z, "name": "a\"b", "tags": [1, 2.0]}"#
+        );
+        assert_eq!(
+            format!("{:#}", value.pretty()),
+            "{\n  \"avatar\": <2048 bytes>,\n  \"created_at\": 2024-05-01T08:30:00Z,\n  \"name\": \"a\\\"b\",\n  \"tags\": [\n    1,\n    2.0\n  ]\n}"
+        );
+        assert_eq!(DataValue::Array(vec![]).to_pretty_string(), "[]");
+        assert_eq!(DataValue::Object(HashMap::new()).to_pretty_string(), "{}");
+    }
+
+    #[test]
+    fn test_single_column_value() {
+        let mut row = HashMap::new();
+        row.insert("total".to_string(), DataValue::Int(3));
+        assert_eq!(single_column_value(DataValue::Object(row.clone())).unwrap(), DataValue::Int(3));
+
+        row.insert("other".to_string(), DataValue::Int(4));
+        assert!(single_column_value(DataValue::Object(row)).is_err());
+    }
+
+    #[test]
+    fn test_truncate_datetime_to_precision() {
+        use chrono::TimeZone;
+        let dt = Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap();
+        let mut value = DataValue::Object(HashMap::from([
+            ("at".to_string(), DataValue::DateTime(dt)),
+            ("history".to_string(), DataValue::Array(vec![DataValue::DateTime(dt)])),
+        ]));
+        value.truncate_datetime(3);
+
+        let expected = DataValue::DateTime(Utc.timestamp_opt(1_700_000_000, 123_000_000).unwrap());
+        let DataValue::Object(fields) = &value else { unreachable!() };
+        assert_eq!(fields["at"], expected);
+        assert_eq!(fields["history"], DataValue::Array(vec![expected]));
+
+        let mut seconds = DataValue::DateTime(dt);
+        seconds.truncate_datetime(0);
+        assert_eq!(seconds, DataValue::DateTime(Utc.timestamp_opt(1_700_000_000, 0).unwrap()));
+    }
+}
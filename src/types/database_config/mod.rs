@@ -37,6 +37,69 @@ impl DatabaseType {
             _ => Err(crate::quick_error!(unsupported_db, s)),
         }
     }
+
+    /// 当前编译启用的数据库类型
+    pub fn all_supported() -> Vec<DatabaseType> {
+        [DatabaseType::SQLite, DatabaseType::PostgreSQL, DatabaseType::MySQL, DatabaseType::MongoDB]
+            .into_iter()
+            .filter(DatabaseType::is_supported)
+            .collect()
+    }
+
+    /// 当前编译是否启用了该数据库类型的支持（对应的 `*-support` feature）
+    pub fn is_supported(&self) -> bool {
+        match self {
+            DatabaseType::SQLite => cfg!(feature = "sqlite-support"),
+            DatabaseType::PostgreSQL => cfg!(feature = "postgres-support"),
+            DatabaseType::MySQL => cfg!(feature = "mysql-support"),
+            DatabaseType::MongoDB => cfg!(feature = "mongodb-support"),
+        }
+    }
+
+    /// 该数据库类型支持的功能
+    pub fn capabilities(&self) -> Capabilities {
+        match self {
+            DatabaseType::SQLite => Capabilities {
+                transactions: true,
+                native_json: false,
+                full_text: false,
+                regex: false,
+            },
+            DatabaseType::PostgreSQL => Capabilities {
+                transactions: true,
+                native_json: true,
+                full_text: true,
+                regex: false,
+            },
+            DatabaseType::MySQL => Capabilities {
+                transactions: true,
+                native_json: true,
+                full_text: true,
+                regex: true,
+            },
+            DatabaseType::MongoDB => Capabilities {
+                transactions: false,
+                native_json: true,
+                full_text: true,
+                regex: true,
+            },
+        }
+    }
+}
+
+/// 数据库功能支持情况
+///
+/// 供上层代码在调用前判断功能是否可用并降级处理，避免在查询深处才返回错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// 跨多表的事务性写入（如 `seed`）
+    pub transactions: bool,
+    /// 原生 JSON 列类型，不支持时 JSON 和数组以文本存储
+    pub native_json: bool,
+    /// 数据库原生的全文索引
+    pub full_text: bool,
+    /// 正则表达式匹配（`QueryOperator::Regex`），SQLite 和 PostgreSQL 不支持其渲染的 `REGEXP` 语法
+    pub regex: bool,
 }
 
 /// 数据库连接配置
@@ -54,6 +117,196 @@ pub struct DatabaseConfig {
     pub cache: Option<CacheConfig>,
     /// ID 生成策略
     pub id_strategy: IdStrategy,
+    /// 查询不存在的表时返回空结果而不是报错
+    ///
+    /// MongoDB 查询不存在的集合本就返回空结果，开启后 SQL 数据库的查询、按ID查询和计数
+    /// 在表不存在时同样返回空结果（计数为 0），便于跨数据库测试。默认关闭：
+    /// 开启后表名拼写错误也会被静默当作空表，生产环境应谨慎使用
+    #[serde(default)]
+    pub missing_table_returns_empty: bool,
+    /// 写后缓冲配置（可选），设置后该别名下的单条创建改为批量异步写入
+    #[serde(default)]
+    pub write_behind: Option<WriteBehindConfig>,
+    /// 查询日志模式（默认关闭），开启后在 debug 级别输出执行的 SQL 或 MongoDB 命令
+    #[serde(default)]
+    pub log_queries: QueryLogMode,
+    /// 查询日志脱敏的字段名（不区分大小写），仅在 `SqlWithRedactedParams` 模式下生效
+    #[serde(default)]
+    pub sensitive_fields: Vec<String>,
+    /// 只读别名（默认关闭），开启后该别名上的写操作和建表、删表等DDL在发送到数据库前
+    /// 返回 [`QuickDbError::ReadOnly`](crate::error::QuickDbError::ReadOnly)，读操作不受影响
+    #[serde(default)]
+    pub read_only: bool,
+    /// 试运行别名（默认关闭），开启后该别名上的插入、更新和删除在总是回滚的事务中执行，
+    /// 返回实际执行时的影响行数等结果但不修改数据；其他写操作和DDL直接返回错误。
+    /// MongoDB 在总是中止的会话事务中执行，需要副本集或分片集群部署
+    #[serde(default)]
+    pub dry_run: bool,
+    /// N+1 查询检测配置（可选，仅建议开发环境开启），详见 [`NPlusOneConfig`]
+    #[serde(default)]
+    pub detect_n_plus_one: Option<NPlusOneConfig>,
+    /// MongoDB 二进制流使用的 GridFS 存储桶名称（可选，仅 MongoDB 有效）
+    ///
+    /// 配置后 `read_blob`/`write_blob` 把数据存放在 `{bucket}.files` 和 `{bucket}.chunks` 中；
+    /// 未配置时 MongoDB 不支持二进制流
+    #[serde(default)]
+    pub gridfs_bucket: Option<String>,
+    /// SQLite 写语句遇到数据库忙（`database is locked`）时的重试次数（可选，仅 SQLite 有效）
+    ///
+    /// 未配置时使用 [`DEFAULT_BUSY_RETRY_COUNT`](crate::adapter::DEFAULT_BUSY_RETRY_COUNT)，
+    /// 为 0 时不重试；每次重试前按带随机抖动的指数退避等待
+    #[serde(default)]
+    pub busy_retry_count: Option<u32>,
+    /// MongoDB 主键 `_id` 在查询结果中使用的字段名（可选，仅 MongoDB 有效）
+    ///
+    /// 未配置时映射为 `id`，与SQL数据库的结果一致，详见 [`MongoIdField`]
+    #[serde(default)]
+    pub mongo_id_field: Option<MongoIdField>,
+    /// MongoDB 空值条件的匹配语义（可选，仅 MongoDB 有效）
+    ///
+    /// 未配置时 `IsNull` 同时匹配 null 和缺失字段，详见 [`MongoNullMatching`]
+    #[serde(default)]
+    pub mongo_null_matching: Option<MongoNullMatching>,
+    /// 连接时上报给数据库的应用名称（可选），用于在进程列表中识别本服务
+    ///
+    /// PostgreSQL 设置为 `application_name`（见 `pg_stat_activity`），MongoDB 设置为 `appName`；
+    /// 未配置时使用 `rat_quickdb-{别名}`。SQLite 和 MySQL 不支持
+    #[serde(default)]
+    pub application_name: Option<String>,
+    /// 日期时间的小数秒位数（可选，0~6）
+    ///
+    /// 设置后写入数据和查询条件中的日期时间统一截断到该精度，MySQL 建表为 `DATETIME(n)`、
+    /// PostgreSQL 建表为 `TIMESTAMPTZ(n)`，写入值与存储值一致，按时间精确匹配的查询不会因
+    /// 数据库舍入而落空。未配置时不截断，列类型保持数据库默认精度（MySQL 为秒）
+    #[serde(default)]
+    pub datetime_precision: Option<u8>,
+    /// 是否对 MySQL 的 TEXT/VARCHAR 列进行JSON内容探测（默认关闭，仅 MySQL 有效）
+    ///
+    /// 关闭时文本列按字符串返回，只有模型元数据声明为JSON/数组/对象的字段才会被解析。
+    /// 没有注册模型元数据的表可开启此选项，使以 `{` 或 `[` 开头且为合法JSON的文本内容
+    /// 被解析为对应的DataValue
+    #[serde(default)]
+    pub mysql_json_text_detection: bool,
+    /// 按工作负载命名的独立连接池（可选）
+    ///
+    /// 每个工作负载在同一数据库上使用自己的连接池和超时设置，查询通过
+    /// [`QueryOptions::with_workload`](crate::types::QueryOptions::with_workload) 指定使用哪一个，
+    /// 未指定的操作使用 `pool`。报表等耗时查询放在独立的小连接池中，不会占满主连接池
+    #[serde(default)]
+    pub workloads: HashMap<String, PoolConfig>,
+}
+
+impl DatabaseConfig {
+    /// 实际上报的应用名称，未配置时为 crate 名加别名
+    pub fn effective_application_name(&self) -> String {
+        self.application_name.clone()
+            .unwrap_or_else(|| format!("{}-{}", env!("CARGO_PKG_NAME"), self.alias))
+    }
+}
+
+/// MongoDB 主键 `_id` 在查询结果中使用的字段名
+///
+/// 写入和查询条件中的 `id` 与 `_id` 始终都映射到 `_id`，此设置只影响读取结果，
+/// 使模型的ID字段无论叫 `id` 还是 `_id` 都能直接反序列化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MongoIdField {
+    /// 映射为 `id`，模型在SQL数据库和MongoDB上使用同一个字段名（默认）
+    #[default]
+    Id,
+    /// 保持 `_id`，用于ID字段命名为 `_id` 的模型
+    UnderscoreId,
+}
+
+impl MongoIdField {
+    /// 结果中的字段名
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            MongoIdField::Id => "id",
+            MongoIdField::UnderscoreId => "_id",
+        }
+    }
+}
+
+/// MongoDB 空值条件的匹配语义
+///
+/// MongoDB 中 `{field: null}` 同时匹配值为 null 和缺少该字段的文档，这与 SQL 的 `IS NULL` 不同。
+/// 该设置影响 `IsNull`、`IsNotNull`、`Eq(Null)` 和 `Ne(Null)` 条件，两种模式下
+/// "为空"与"不为空"始终互补，计数之和等于集合总数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MongoNullMatching {
+    /// 值为 null 或字段缺失都算为空（默认）
+    ///
+    /// 为空渲染为 `{field: {$eq: null}}`，不为空渲染为 `{field: {$ne: null}}`
+    #[default]
+    NullOrMissing,
+    /// 只有显式存储的 null 才算为空，缺失字段按"不为空"处理
+    ///
+    /// 为空渲染为 `{field: {$type: 10}}`，不为空渲染为 `{field: {$not: {$type: 10}}}`
+    NullOnly,
+}
+
+/// 查询日志模式
+///
+/// 日志通过 `rat_logger` 在 debug 级别输出。SQL 数据库输出语句和绑定参数；
+/// MongoDB 输出命令名、集合和命令文档，`SqlOnly` 模式下文档中的值替换为 `?`，只保留结构
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum QueryLogMode {
+    /// 不输出查询日志
+    #[default]
+    Off,
+    /// 只输出语句，不输出参数值
+    SqlOnly,
+    /// 输出语句和全部参数值，参数可能包含敏感信息，仅用于开发环境
+    SqlWithParams,
+    /// 输出语句和参数值，`sensitive_fields` 中字段的值替换为 `***`
+    SqlWithRedactedParams,
+}
+
+/// N+1 查询检测配置
+///
+/// 同一查询形状（参数化的 SQL 语句，或去掉取值后的 MongoDB 命令）在 `window_ms` 毫秒内
+/// 执行超过 `threshold` 次时输出一条 warn 日志，提示改为批量查询。统计按别名进行，
+/// 每种查询在一个窗口内只提示一次。检测复用查询日志的埋点，未开启时没有额外开销
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NPlusOneConfig {
+    /// 窗口内允许的执行次数，超过后提示（至少为2）
+    pub threshold: u32,
+    /// 统计窗口（毫秒）
+    pub window_ms: u64,
+}
+
+impl Default for NPlusOneConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 10,
+            window_ms: 1000,
+        }
+    }
+}
+
+/// 写后缓冲（write-behind）配置
+///
+/// 开启后单条创建只写入内存缓冲区并立即返回，缓冲区达到 `max_batch` 条或每隔
+/// `max_delay_ms` 毫秒时以批量插入方式写入数据库，用多行插入摊薄往返开销。
+///
+/// 持久性取舍：返回成功只代表数据已入队，进程崩溃时尚未刷新的数据会丢失，
+/// 批量写入失败也只记录日志而无法通知调用方。仅适用于可以容忍少量丢失的遥测类数据。
+/// 自增ID策略下插入前无法得知ID，创建返回 `DataValue::Null`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriteBehindConfig {
+    /// 单批最大记录数，缓冲区达到该数量时立即刷新
+    pub max_batch: usize,
+    /// 刷新间隔（毫秒），到期后即使未满也会刷新
+    pub max_delay_ms: u64,
+}
+
+impl Default for WriteBehindConfig {
+    fn default() -> Self {
+        Self {
+            max_batch: 500,
+            max_delay_ms: 200,
+        }
+    }
 }
 
 /// 连接配置
@@ -82,6 +335,9 @@ pub enum ConnectionConfig {
         ssl_mode: Option<String>,
         /// TLS 配置选项
         tls_config: Option<TlsConfig>,
+        /// 是否在数据库不存在时创建（经维护库 `postgres` 执行，需要 `CREATEDB` 权限）
+        #[serde(default)]
+        create_database_if_missing: bool,
     },
     /// MySQL 连接配置
     MySQL {
@@ -99,6 +355,9 @@ pub enum ConnectionConfig {
         ssl_opts: Option<HashMap<String, String>>,
         /// TLS 配置选项
         tls_config: Option<TlsConfig>,
+        /// 是否在数据库不存在时创建（连接时不指定默认库，需要 `CREATE` 权限）
+        #[serde(default)]
+        create_database_if_missing: bool,
     },
     /// MongoDB 连接配置
     MongoDB {
@@ -264,14 +523,85 @@ pub struct PoolConfig {
     pub idle_timeout: u64,
     /// 连接最大生存时间（秒）
     pub max_lifetime: u64,
+    /// 连接生存时间的随机抖动范围（秒），`0` 表示不抖动（默认）
+    ///
+    /// 大于0时每个连接在 `[max_lifetime - max_lifetime_jitter, max_lifetime]` 区间内随机退役，
+    /// 避免启动时一起创建的连接同时到期、集中重连。仅对 MySQL/PostgreSQL 生效
+    #[serde(default)]
+    pub max_lifetime_jitter: u64,
     /// 最大重试次数
     pub max_retries: u32,
     /// 重试间隔（毫秒）
     pub retry_interval_ms: u64,
+    /// 操作遇到瞬时错误（见 [`QuickDbError::is_retryable`](crate::error::QuickDbError::is_retryable)）时的重试次数，`0` 表示不重试（默认）
+    ///
+    /// 重试间隔按 `retry_base_delay_ms` 指数增长并加入随机抖动，不超过 `retry_max_delay_ms`。
+    /// 仅对 MySQL/PostgreSQL/MongoDB 生效
+    #[serde(default)]
+    pub retry_attempts: u32,
+    /// 首次重试前的基础等待时间（毫秒）
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+    /// 重试等待时间上限（毫秒）
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub retry_max_delay_ms: u64,
     /// 保活检测间隔（秒）
     pub keepalive_interval_sec: u64,
     /// 连接健康检查超时（秒）
     pub health_check_timeout_sec: u64,
+    /// 获取连接前先检测连接是否存活，失效连接会被透明替换
+    ///
+    /// 用于应对数据库空闲超时或防火墙断开的连接，仅对 MySQL/PostgreSQL 生效，默认开启
+    #[serde(default = "default_test_before_acquire")]
+    pub test_before_acquire: bool,
+    /// 连接池使用情况采样配置，`None` 表示不采样（默认）
+    #[serde(default)]
+    pub sampling: Option<PoolSamplingConfig>,
+    /// 为交互操作保留的连接数，`0` 表示不区分操作类别（默认）
+    ///
+    /// 大于0时批量操作（见 [`OperationClass`](crate::types::OperationClass)）进入独立的批量通道，
+    /// 使用其余 `max_connections - reserved_interactive_connections` 个连接，不会排在交互操作之前
+    #[serde(default)]
+    pub reserved_interactive_connections: u32,
+    /// 同时进行的批量操作上限，`0` 表示不限制（默认），超出的批量操作排队等待
+    #[serde(default)]
+    pub max_batch_operations: u32,
+}
+
+/// 连接池使用情况采样配置
+///
+/// 启用后后台任务按固定间隔记录连接数、使用中/空闲连接数和排队操作数，
+/// 保存在容量固定的环形缓冲区中，可通过 `PoolManager::pool_history` 读取，用于评估 `max_connections` 是否合适
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolSamplingConfig {
+    /// 采样间隔（毫秒）
+    pub interval_ms: u64,
+    /// 最多保留的样本数，超出后丢弃最早的样本
+    pub capacity: usize,
+}
+
+impl Default for PoolSamplingConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 1000,
+            capacity: 3600,
+        }
+    }
+}
+
+/// 默认开启获取连接前检测
+fn default_test_before_acquire() -> bool {
+    true
+}
+
+/// 默认重试基础等待时间（毫秒）
+fn default_retry_base_delay_ms() -> u64 {
+    100
+}
+
+/// 默认重试等待时间上限（毫秒）
+fn default_retry_max_delay_ms() -> u64 {
+    5000
 }
 
 impl Default for PoolConfig {
@@ -282,10 +612,68 @@ impl Default for PoolConfig {
             connection_timeout: 30,
             idle_timeout: 600,
             max_lifetime: 3600,
+            max_lifetime_jitter: 0,
             max_retries: 3,
             retry_interval_ms: 1000,
+            retry_attempts: 0,
+            retry_base_delay_ms: default_retry_base_delay_ms(),
+            retry_max_delay_ms: default_retry_max_delay_ms(),
             keepalive_interval_sec: 30,
             health_check_timeout_sec: 5,
+            test_before_acquire: true,
+            sampling: None,
+            reserved_interactive_connections: 0,
+            max_batch_operations: 0,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_types_follow_features() {
+        let supported = DatabaseType::all_supported();
+        assert_eq!(supported.contains(&DatabaseType::SQLite), cfg!(feature = "sqlite-support"));
+        assert_eq!(supported.contains(&DatabaseType::MongoDB), cfg!(feature = "mongodb-support"));
+        assert!(supported.iter().all(DatabaseType::is_supported));
+
+        assert!(DatabaseType::PostgreSQL.capabilities().transactions);
+        assert!(!DatabaseType::MongoDB.capabilities().transactions);
+        assert!(!DatabaseType::SQLite.capabilities().regex);
+    }
+
+    #[test]
+    fn test_application_name_defaults_to_crate_and_alias() {
+        let builder = || DatabaseConfig::builder()
+            .db_type(DatabaseType::SQLite)
+            .connection(ConnectionConfig::SQLite { path: ":memory:".to_string(), create_if_missing: true })
+            .pool(PoolConfig::default())
+            .alias("reports")
+            .id_strategy(IdStrategy::AutoIncrement);
+
+        let mut config = builder().build().unwrap();
+        assert_eq!(config.effective_application_name(), "rat_quickdb-reports");
+        config.application_name = Some("billing".to_string());
+        assert_eq!(config.effective_application_name(), "billing");
+
+        assert!(builder().application_name("billing").build().is_err());
+    }
+
+    #[test]
+    fn test_create_database_if_missing_defaults_to_false() {
+        let connection: ConnectionConfig = serde_json::from_value(serde_json::json!({
+            "MySQL": {
+                "host": "localhost",
+                "port": 3306,
+                "database": "app",
+                "username": "root",
+                "password": "",
+                "ssl_opts": null,
+                "tls_config": null
+            }
+        })).unwrap();
+        assert!(matches!(connection, ConnectionConfig::MySQL { create_database_if_missing: false, .. }));
+    }
+}
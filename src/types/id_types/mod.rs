@@ -1,3 +1,4 @@
+use crate::types::DataValue;
 use serde::{Deserialize, Serialize};
 
 /// ID 生成策略
@@ -7,6 +8,10 @@ pub enum IdStrategy {
     AutoIncrement,
     /// UUID v4（字符串）
     Uuid,
+    /// UUID v7（36位字符串，按时间有序，适合作为索引键）
+    UuidV7,
+    /// ULID（26位 Crockford Base32 字符串，按时间有序）
+    Ulid,
     /// 雪花算法 ID（字符串）
     Snowflake {
         /// 机器 ID（0-1023）
@@ -16,7 +21,7 @@ pub enum IdStrategy {
     },
     /// MongoDB ObjectId（字符串）
     ObjectId,
-    /// 自定义 ID 生成器
+    /// 自定义 ID 生成器，按名称使用通过 `register_id_generator` 注册的生成器
     Custom(String),
 }
 
@@ -50,6 +55,16 @@ impl IdStrategy {
         Self::Uuid
     }
 
+    /// 创建 UUID v7 策略
+    pub fn uuid_v7() -> Self {
+        Self::UuidV7
+    }
+
+    /// 创建 ULID 策略
+    pub fn ulid() -> Self {
+        Self::Ulid
+    }
+
     /// 创建雪花算法策略
     pub fn snowflake(machine_id: u16, datacenter_id: u8) -> Self {
         Self::Snowflake {
@@ -86,3 +101,37 @@ impl From<&str> for IdType {
         Self::String(value.to_string())
     }
 }
+
+impl IdType {
+    /// 根据内容解析ID字符串
+    ///
+    /// 纯数字且在 i64 范围内（不含前导零）解析为数字ID，其余（UUID、ObjectId等）保持字符串
+    pub fn parse(value: &str) -> Self {
+        let is_canonical_number = !value.is_empty()
+            && value.bytes().all(|b| b.is_ascii_digit())
+            && (value == "0" || !value.starts_with('0'));
+        match value.parse::<i64>() {
+            Ok(n) if is_canonical_number => Self::Number(n),
+            _ => Self::String(value.to_string()),
+        }
+    }
+
+    /// 从 DataValue 转换，支持整数、字符串和UUID
+    pub fn from_data_value(value: &DataValue) -> Option<Self> {
+        match value {
+            DataValue::Int(n) => Some(Self::Number(*n)),
+            DataValue::String(s) => Some(Self::String(s.clone())),
+            DataValue::Uuid(u) => Some(Self::String(u.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl From<IdType> for DataValue {
+    fn from(value: IdType) -> Self {
+        match value {
+            IdType::Number(n) => DataValue::Int(n),
+            IdType::String(s) => DataValue::String(s),
+        }
+    }
+}
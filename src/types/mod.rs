@@ -10,12 +10,30 @@ pub mod cache_config;
 pub mod id_types;
 pub mod update_operations;
 pub mod mongo_builder;
+pub mod change_event;
+pub mod write_result;
+pub mod schema_sync;
+pub mod seed;
+pub mod progress;
+pub mod session_context;
+pub mod operation_class;
+pub mod query_log;
+pub mod n_plus_one;
+pub mod blob;
 
 // 重新导出所有公共类型以保持API兼容性
-pub use database_config::{DatabaseConfig, DatabaseType, ConnectionConfig, TlsConfig, ZstdConfig, PoolConfig};
-pub use data_value::DataValue;
-pub use query::{QueryCondition, QueryOperator, LogicalOperator, QueryConditionGroup, SortConfig, SortDirection, PaginationConfig, QueryOptions};
+pub use database_config::{DatabaseConfig, DatabaseType, Capabilities, ConnectionConfig, TlsConfig, ZstdConfig, PoolConfig, PoolSamplingConfig, WriteBehindConfig, QueryLogMode, NPlusOneConfig, MongoIdField, MongoNullMatching};
+pub use data_value::{DataValue, FromDataValue, PrettyDataValue};
+pub use query::{QueryCondition, QueryOperator, LogicalOperator, QueryConditionGroup, SortConfig, SortDirection, PaginationConfig, CursorPagination, CursorPage, QueryOptions, CountMode, Interval, AggFunc, Aggregation, Expr, BinaryOperator, ScalarFunction};
 pub use cache_config::{CacheConfig, CacheStrategy, L1CacheConfig, L2CacheConfig, TtlConfig, CompressionConfig, CompressionAlgorithm};
 pub use id_types::{IdStrategy, IdType};
-pub use update_operations::{UpdateOperator, UpdateOperation};
-pub use mongo_builder::MongoDbConnectionBuilder;
\ No newline at end of file
+pub use update_operations::{UpdateOperator, UpdateOperation, NullPolicy, Patch, PatchField};
+pub use mongo_builder::MongoDbConnectionBuilder;
+pub use change_event::{ChangeOperation, ChangeEvent, ChangeStream};
+pub use write_result::{WriteResult, UpsertResult, UpsertOutcome};
+pub use schema_sync::SchemaSyncReport;
+pub use seed::SeedReport;
+pub use blob::{BlobLocation, BLOB_CHUNK_SIZE};
+pub use progress::ProgressEvent;
+pub use session_context::{SessionContext, with_session_context, current_session_context};
+pub use operation_class::{OperationClass, with_operation_class, current_operation_class};
\ No newline at end of file
@@ -0,0 +1,108 @@
+//! N+1 查询检测
+//!
+//! 开发模式下的诊断辅助：工作器为每个操作建立的查询日志作用域持有本别名的检测器，
+//! 适配器输出查询日志时同时把查询形状交给检测器计数。同一形状在统计窗口内执行超过阈值时
+//! 输出 warn 日志，提示把循环中的逐条查询改为批量查询。未配置 [`NPlusOneConfig`] 时
+//! 不创建检测器，也不会因此建立日志作用域
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use parking_lot::Mutex;
+use rat_logger::warn;
+
+use crate::types::NPlusOneConfig;
+
+/// 记录的查询形状超过该数量时清理已过期的窗口
+const MAX_TRACKED_SHAPES: usize = 1024;
+
+/// 单个查询形状的统计窗口
+#[derive(Debug)]
+struct ShapeWindow {
+    started: Instant,
+    count: u32,
+    warned: bool,
+}
+
+/// 按别名统计查询形状执行次数的检测器，由连接池的各工作器共享
+#[derive(Debug)]
+pub(crate) struct NPlusOneDetector {
+    alias: String,
+    threshold: u32,
+    window: Duration,
+    windows: Mutex<HashMap<String, ShapeWindow>>,
+}
+
+impl NPlusOneDetector {
+    /// 按配置创建检测器
+    pub(crate) fn new(alias: &str, config: NPlusOneConfig) -> Self {
+        Self {
+            alias: alias.to_string(),
+            threshold: config.threshold,
+            window: Duration::from_millis(config.window_ms),
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 记录一次查询，超过阈值时输出警告
+    pub(crate) fn record(&self, shape: &str) {
+        if let Some(count) = self.record_at(shape, Instant::now()) {
+            warn!(
+                "[N+1检测] alias={} 同一查询在{}ms内执行了{}次，可能是在循环中逐条查询，建议改用 In 条件一次查询（写入可用 create_many 等批量操作）: {}",
+                self.alias,
+                self.window.as_millis(),
+                count,
+                shape
+            );
+        }
+    }
+
+    /// 记录一次查询，本窗口内首次超过阈值时返回已执行次数
+    fn record_at(&self, shape: &str, now: Instant) -> Option<u32> {
+        let mut windows = self.windows.lock();
+        if windows.len() >= MAX_TRACKED_SHAPES && !windows.contains_key(shape) {
+            windows.retain(|_, window| now.duration_since(window.started) < self.window);
+        }
+
+        let window = windows.entry(shape.to_string()).or_insert(ShapeWindow {
+            started: now,
+            count: 0,
+            warned: false,
+        });
+        if now.duration_since(window.started) >= self.window {
+            *window = ShapeWindow {
+                started: now,
+                count: 0,
+                warned: false,
+            };
+        }
+
+        window.count += 1;
+        if window.count > self.threshold && !window.warned {
+            window.warned = true;
+            return Some(window.count);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warns_once_per_window() {
+        let detector = NPlusOneDetector::new("default", NPlusOneConfig { threshold: 3, window_ms: 100 });
+        let start = Instant::now();
+        let sql = "SELECT * FROM users WHERE id = ?";
+
+        let results: Vec<_> = (0..5).map(|_| detector.record_at(sql, start)).collect();
+        assert_eq!(results, vec![None, None, None, Some(4), None]);
+        // 不同形状分别计数
+        assert_eq!(detector.record_at("SELECT * FROM orders WHERE user_id = ?", start), None);
+
+        // 窗口过期后重新计数
+        let later = start + Duration::from_millis(150);
+        let results: Vec<_> = (0..4).map(|_| detector.record_at(sql, later)).collect();
+        assert_eq!(results, vec![None, None, None, Some(4)]);
+    }
+}
@@ -0,0 +1,57 @@
+//! 操作类别
+//!
+//! 区分交互操作（面向用户请求的短查询）和批量操作（导入、报表等长时间操作），
+//! 使批量操作不会占满连接或阻塞交互操作。
+//!
+//! 类别通过 [`with_operation_class`] 作用于一段异步代码，其中发出的每个操作都会携带它，
+//! 未指定时按交互操作处理。批量操作不在ODM请求循环中串行执行，而是单独派发；
+//! 连接池配置了 `reserved_interactive_connections` 时批量操作进入独立的批量通道，
+//! 配置了 `max_batch_operations` 时超出上限的批量操作排队等待。
+
+use std::future::Future;
+use serde::{Deserialize, Serialize};
+
+tokio::task_local! {
+    static OPERATION_CLASS: OperationClass;
+}
+
+/// 操作类别
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OperationClass {
+    /// 交互操作（默认）
+    #[default]
+    Interactive,
+    /// 批量操作
+    Batch,
+}
+
+/// 以指定操作类别执行一段异步代码
+///
+/// 类别只对 `future` 内发出的操作生效；嵌套调用时内层类别覆盖外层
+pub async fn with_operation_class<F: Future>(class: OperationClass, future: F) -> F::Output {
+    OPERATION_CLASS.scope(class, future).await
+}
+
+/// 获取当前生效的操作类别，未指定时为 [`OperationClass::Interactive`]
+pub fn current_operation_class() -> OperationClass {
+    OPERATION_CLASS.try_with(|class| *class).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_operation_class_scope() {
+        assert_eq!(current_operation_class(), OperationClass::Interactive);
+
+        let nested = with_operation_class(OperationClass::Batch, async {
+            let outer = current_operation_class();
+            let inner = with_operation_class(OperationClass::Interactive, async { current_operation_class() }).await;
+            (outer, inner)
+        }).await;
+
+        assert_eq!(nested, (OperationClass::Batch, OperationClass::Interactive));
+        assert_eq!(current_operation_class(), OperationClass::Interactive);
+    }
+}
@@ -0,0 +1,34 @@
+//! 长时间批处理操作的进度事件
+//!
+//! 供批量写入等操作周期性回报进度，调用方无需轮询即可驱动进度条
+
+use serde::{Deserialize, Serialize};
+
+/// 批处理进度事件
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgressEvent {
+    /// 已处理的记录数
+    pub processed: u64,
+    /// 记录总数，无法预知时为 `None`
+    pub total: Option<u64>,
+    /// 已发生的错误数
+    pub errors: u64,
+}
+
+impl ProgressEvent {
+    /// 是否已处理完全部记录（总数未知时始终为 `false`）
+    pub fn is_complete(&self) -> bool {
+        matches!(self.total, Some(total) if self.processed >= total)
+    }
+
+    /// 完成比例（0.0 ~ 1.0），总数未知时返回 `None`
+    pub fn ratio(&self) -> Option<f64> {
+        self.total.map(|total| {
+            if total == 0 {
+                1.0
+            } else {
+                self.processed as f64 / total as f64
+            }
+        })
+    }
+}
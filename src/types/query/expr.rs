@@ -0,0 +1,143 @@
+//! 计算表达式
+//!
+//! 用于在查询结果中附加派生字段（如 `price * quantity AS total`），
+//! SQL 数据库渲染为 SELECT 列表中的表达式，MongoDB 渲染为聚合表达式
+
+use serde::{Deserialize, Serialize};
+use crate::types::data_value::DataValue;
+
+/// 二元算术运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BinaryOperator {
+    /// 加
+    Add,
+    /// 减
+    Sub,
+    /// 乘
+    Mul,
+    /// 除（整数相除时 SQLite/PostgreSQL 截断取整，MySQL 返回小数）
+    Div,
+    /// 取模
+    Mod,
+}
+
+/// 可移植的标量函数
+///
+/// 只包含各数据库都有等价实现的函数，避免表达式在切换数据库后失效
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScalarFunction {
+    /// 转小写，1个参数
+    Lower,
+    /// 转大写，1个参数
+    Upper,
+    /// 字符串长度（按字符计），1个参数
+    Length,
+    /// 绝对值，1个参数
+    Abs,
+    /// 四舍五入，1个参数或2个参数（第二个为保留的小数位数）
+    Round,
+    /// 返回第一个非空参数，至少1个参数
+    Coalesce,
+    /// 字符串拼接，至少1个参数
+    Concat,
+}
+
+impl ScalarFunction {
+    /// 允许的参数个数范围（含），上限为 `None` 表示不限
+    pub fn arity(&self) -> (usize, Option<usize>) {
+        match self {
+            ScalarFunction::Lower
+            | ScalarFunction::Upper
+            | ScalarFunction::Length
+            | ScalarFunction::Abs => (1, Some(1)),
+            ScalarFunction::Round => (1, Some(2)),
+            ScalarFunction::Coalesce | ScalarFunction::Concat => (1, None),
+        }
+    }
+}
+
+/// 计算表达式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Expr {
+    /// 字段引用
+    Column(String),
+    /// 字面量
+    Literal(DataValue),
+    /// 二元运算
+    Binary {
+        left: Box<Expr>,
+        op: BinaryOperator,
+        right: Box<Expr>,
+    },
+    /// 函数调用
+    Function {
+        function: ScalarFunction,
+        args: Vec<Expr>,
+    },
+}
+
+impl Expr {
+    /// 字段引用
+    pub fn col(name: &str) -> Self {
+        Expr::Column(name.to_string())
+    }
+
+    /// 字面量
+    pub fn lit(value: impl Into<DataValue>) -> Self {
+        Expr::Literal(value.into())
+    }
+
+    /// 函数调用
+    pub fn func(function: ScalarFunction, args: Vec<Expr>) -> Self {
+        Expr::Function { function, args }
+    }
+
+    /// 二元运算
+    pub fn binary(self, op: BinaryOperator, right: Expr) -> Self {
+        Expr::Binary {
+            left: Box::new(self),
+            op,
+            right: Box::new(right),
+        }
+    }
+
+    /// 检查表达式中所有函数的参数个数是否合法
+    pub fn validate(&self) -> Result<(), crate::error::QuickDbError> {
+        match self {
+            Expr::Column(_) | Expr::Literal(_) => Ok(()),
+            Expr::Binary { left, right, .. } => {
+                left.validate()?;
+                right.validate()
+            }
+            Expr::Function { function, args } => {
+                let (min, max) = function.arity();
+                if args.len() < min || max.is_some_and(|max| args.len() > max) {
+                    return Err(crate::error::QuickDbError::ValidationError {
+                        field: format!("{:?}", function),
+                        message: format!("函数 {:?} 的参数个数不合法: {}", function, args.len()),
+                    });
+                }
+                args.iter().try_for_each(Expr::validate)
+            }
+        }
+    }
+}
+
+// 算术运算符重载，支持 `Expr::col("price") * Expr::col("quantity")` 的写法
+macro_rules! impl_expr_op {
+    ($trait:ident, $method:ident, $op:expr) => {
+        impl std::ops::$trait for Expr {
+            type Output = Expr;
+
+            fn $method(self, right: Expr) -> Expr {
+                self.binary($op, right)
+            }
+        }
+    };
+}
+
+impl_expr_op!(Add, add, BinaryOperator::Add);
+impl_expr_op!(Sub, sub, BinaryOperator::Sub);
+impl_expr_op!(Mul, mul, BinaryOperator::Mul);
+impl_expr_op!(Div, div, BinaryOperator::Div);
+impl_expr_op!(Rem, rem, BinaryOperator::Mod);
@@ -1,10 +1,17 @@
 use serde::{Deserialize, Serialize};
+use crate::error::{QuickDbError, QuickDbResult};
 use crate::types::data_value::DataValue;
 
+pub mod expr;
+pub use expr::{Expr, BinaryOperator, ScalarFunction};
+
 /// 查询条件
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QueryCondition {
     /// 字段名
+    ///
+    /// 内嵌对象字段可以用点分路径查询子字段（如 `address.city`）：MongoDB 直接使用原生路径，
+    /// SQL 数据库在该字段为 Object/Json 类型时生成 JSON 提取表达式
     pub field: String,
     /// 操作符
     pub operator: QueryOperator,
@@ -19,9 +26,30 @@ pub enum LogicalOperator {
     And,
     /// OR 逻辑
     Or,
+    /// NOT 逻辑（对组内条件的 AND 结果取反）
+    Not,
 }
 
 /// 查询条件组合
+///
+/// 可以使用 `and` / `or` / `not` / `cond` 构造嵌套条件树，例如
+/// `status = 'active' AND (age >= 18 OR (role = 'admin' AND NOT (name LIKE 'test%')))`：
+///
+/// ```
+/// use rat_quickdb::types::{QueryConditionGroup as G, QueryOperator};
+///
+/// let filter = G::and(vec![
+///     G::cond("status", QueryOperator::Eq, "active"),
+///     G::or(vec![
+///         G::cond("age", QueryOperator::Gte, 18i64),
+///         G::and(vec![
+///             G::cond("role", QueryOperator::Eq, "admin"),
+///             G::not(G::cond("name", QueryOperator::StartsWith, "test")),
+///         ]),
+///     ]),
+/// ]);
+/// # let _ = filter;
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum QueryConditionGroup {
     /// 单个条件
@@ -35,6 +63,89 @@ pub enum QueryConditionGroup {
     },
 }
 
+impl QueryCondition {
+    /// 创建查询条件
+    pub fn new(field: &str, operator: QueryOperator, value: impl Into<DataValue>) -> Self {
+        Self {
+            field: field.to_string(),
+            operator,
+            value: value.into(),
+        }
+    }
+
+    /// 原始SQL条件片段，用于构建器无法表达的数据库专有谓词（如 `age = ANY($1)`、`ts_rank(...) > $2`）
+    ///
+    /// 片段中用 `$1`、`$2` ... 引用 `params` 中的参数，构建语句时按整条语句的参数列表重新编号，
+    /// 可以与其他条件任意组合；每个参数都必须被引用。片段原样拼入SQL，不能包含未经处理的用户输入。
+    /// MongoDB 不支持，会返回 `UnsupportedDatabase`
+    pub fn raw(fragment: impl Into<String>, params: Vec<DataValue>) -> Self {
+        Self {
+            field: fragment.into(),
+            operator: QueryOperator::Raw,
+            value: DataValue::Array(params),
+        }
+    }
+
+    /// `ArrayContainsAll` 条件的元素列表，条件值不是数组时返回验证错误
+    pub(crate) fn array_elements(&self) -> QuickDbResult<&[DataValue]> {
+        match &self.value {
+            DataValue::Array(values) => Ok(values),
+            other => Err(QuickDbError::ValidationError {
+                field: self.field.clone(),
+                message: format!("ArrayContainsAll 操作符需要数组类型的值，实际为 {}", other.type_name()),
+            }),
+        }
+    }
+
+    /// `ArraySize` 条件的数组长度，条件值不是非负整数时返回验证错误
+    pub(crate) fn array_size(&self) -> QuickDbResult<i64> {
+        match &self.value {
+            DataValue::Int(size) if *size >= 0 => Ok(*size),
+            other => Err(QuickDbError::ValidationError {
+                field: self.field.clone(),
+                message: format!("ArraySize 操作符需要非负整数，实际为 {:?}", other),
+            }),
+        }
+    }
+}
+
+impl QueryConditionGroup {
+    /// 单个条件
+    pub fn cond(field: &str, operator: QueryOperator, value: impl Into<DataValue>) -> Self {
+        Self::Single(QueryCondition::new(field, operator, value))
+    }
+
+    /// AND 组合
+    pub fn and(conditions: Vec<QueryConditionGroup>) -> Self {
+        Self::Group {
+            operator: LogicalOperator::And,
+            conditions,
+        }
+    }
+
+    /// OR 组合
+    pub fn or(conditions: Vec<QueryConditionGroup>) -> Self {
+        Self::Group {
+            operator: LogicalOperator::Or,
+            conditions,
+        }
+    }
+
+    /// NOT 取反
+    pub fn not(group: QueryConditionGroup) -> Self {
+        Self::Group {
+            operator: LogicalOperator::Not,
+            conditions: vec![group],
+        }
+    }
+}
+
+impl From<QueryCondition> for QueryConditionGroup {
+    fn from(condition: QueryCondition) -> Self {
+        Self::Single(condition)
+    }
+}
+
 /// 查询操作符
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum QueryOperator {
@@ -51,10 +162,16 @@ pub enum QueryOperator {
     /// 小于等于
     Lte,
     /// 包含（字符串）
+    ///
+    /// SQL 渲染为 `LIKE '%值%'`，值中的 `%`、`_` 按字面匹配；大小写敏感性取决于数据库和排序规则
     Contains,
-    /// 开始于（字符串）
+    /// 包含（字符串，不区分大小写）
+    ///
+    /// PostgreSQL 渲染为 `ILIKE`，SQLite 和 MySQL 两侧取 `LOWER()` 后比较，MongoDB 使用 `$options: "i"`
+    IContains,
+    /// 开始于（字符串），值中的 LIKE 元字符按字面匹配
     StartsWith,
-    /// 结束于（字符串）
+    /// 结束于（字符串），值中的 LIKE 元字符按字面匹配
     EndsWith,
     /// 在列表中
     In,
@@ -64,10 +181,29 @@ pub enum QueryOperator {
     Regex,
     /// 存在（字段存在）
     Exists,
-    /// 为空
+    /// 为空，不需要条件值
+    ///
+    /// SQL 渲染为 `IS NULL`；MongoDB 默认同时匹配值为 null 和字段缺失的文档，
+    /// 可通过别名配置 `mongo_null_matching` 改为只匹配显式 null
     IsNull,
-    /// 不为空
+    /// 不为空，不需要条件值
+    ///
+    /// SQL 渲染为 `IS NOT NULL`；MongoDB 默认不匹配字段缺失的文档，
+    /// 只匹配显式 null 模式下缺失字段视为不为空
     IsNotNull,
+    /// 数组包含全部给定元素，条件值为元素数组
+    ///
+    /// MongoDB 渲染为 `$all`；SQL 数据库对 JSON 数组列逐个判断元素是否存在。
+    /// 条件值为空数组时不匹配任何记录，与 MongoDB 的 `$all: []` 一致
+    ArrayContainsAll,
+    /// 数组长度等于给定值，条件值为非负整数
+    ///
+    /// MongoDB 渲染为 `$size`；SQL 数据库比较 JSON 数组列的长度
+    ArraySize,
+    /// 原始SQL条件片段，由 [`QueryCondition::raw`] 构造
+    ///
+    /// 片段放在 `field` 中，参数以数组放在 `value` 中；MongoDB 不支持
+    Raw,
 }
 
 /// 排序配置
@@ -88,15 +224,166 @@ pub enum SortDirection {
     Desc,
 }
 
+/// 计数方式
+///
+/// MongoDB 的精确计数（`count_documents`）需要扫描集合，估算计数（`estimated_document_count`）
+/// 直接读取集合元数据，开销与集合大小无关，但在非正常关闭或分片集合上存在孤儿文档时可能不准确。
+/// 估算只适用于统计整个集合：带条件的计数总是精确计数；SQL 数据库总是精确计数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CountMode {
+    /// 精确计数（默认）
+    #[default]
+    Exact,
+    /// 没有查询条件时使用估算计数，适用于允许近似值的总数展示
+    Estimated,
+}
+
+impl CountMode {
+    /// 在给定条件下是否使用估算计数
+    pub(crate) fn uses_estimate(self, conditions: &[QueryCondition]) -> bool {
+        self == CountMode::Estimated && conditions.is_empty()
+    }
+}
+
+/// 按时间段统计时的时间间隔
+///
+/// 时间按UTC截断到所在时间段的起点，按周统计时每周从周一开始
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Interval {
+    /// 分钟
+    Minute,
+    /// 小时
+    Hour,
+    /// 天
+    Day,
+    /// 周（从周一开始）
+    Week,
+    /// 月
+    Month,
+}
+
+/// 聚合函数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AggFunc {
+    /// 计数，字段为 `*` 时统计记录数，否则只统计字段非空的记录
+    Count,
+    /// 求和
+    Sum,
+    /// 平均值
+    Avg,
+    /// 最小值
+    Min,
+    /// 最大值
+    Max,
+}
+
+/// 聚合项，结果以 `alias` 为字段名返回
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Aggregation {
+    /// 聚合函数
+    pub func: AggFunc,
+    /// 聚合字段，`AggFunc::Count` 可以使用 `*`
+    pub field: String,
+    /// 结果字段名
+    pub alias: String,
+}
+
+impl Aggregation {
+    /// 创建聚合项
+    pub fn new(func: AggFunc, field: &str, alias: &str) -> Self {
+        Self {
+            func,
+            field: field.to_string(),
+            alias: alias.to_string(),
+        }
+    }
+
+    /// 统计记录数（`COUNT(*)`）
+    pub fn count(alias: &str) -> Self {
+        Self::new(AggFunc::Count, "*", alias)
+    }
+
+    /// 校验聚合项：至少一项，结果字段名不重复且不与分组字段同名，只有计数可以使用 `*`
+    pub(crate) fn validate(aggregations: &[Aggregation], group_by: &[String]) -> QuickDbResult<()> {
+        if aggregations.is_empty() {
+            return Err(QuickDbError::ValidationError {
+                field: "aggregations".to_string(),
+                message: "至少需要一个聚合项".to_string(),
+            });
+        }
+        let mut names: Vec<&str> = group_by.iter().map(String::as_str).collect();
+        for aggregation in aggregations {
+            if aggregation.field == "*" && aggregation.func != AggFunc::Count {
+                return Err(QuickDbError::ValidationError {
+                    field: aggregation.alias.clone(),
+                    message: format!("{:?} 不能使用 * 作为聚合字段", aggregation.func),
+                });
+            }
+            if names.contains(&aggregation.alias.as_str()) {
+                return Err(QuickDbError::ValidationError {
+                    field: aggregation.alias.clone(),
+                    message: "聚合结果字段名与其他聚合项或分组字段重复".to_string(),
+                });
+            }
+            names.push(&aggregation.alias);
+        }
+        Ok(())
+    }
+}
+
 /// 分页配置
+///
+/// 分页结果的稳定性依赖排序字段的唯一性。MongoDB适配器在排序字段不唯一时
+/// 会自动追加`_id`作为决胜键；SQL数据库建议在排序中包含主键或唯一字段。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaginationConfig {
     /// 跳过的记录数
     pub skip: u64,
-    /// 限制返回的记录数
+    /// 限制返回的记录数，0 表示不限制
+    pub limit: u64,
+}
+
+/// 游标分页配置
+///
+/// 按 `field` 升序读取 `after` 之后的 `limit` 条记录，渲染为 `WHERE field > ? ORDER BY field LIMIT ?`。
+/// 与 `skip` 分页相比不随偏移量变慢，翻页期间插入的记录也不会导致重复或遗漏。
+/// `field` 的值必须唯一（如主键），否则与游标值相等的记录会被跳过；不能与 `pagination` 同时使用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CursorPagination {
+    /// 游标字段
+    pub field: String,
+    /// 上一页最后一条记录的游标值，`None` 表示从第一页开始
+    pub after: Option<DataValue>,
+    /// 每页记录数，必须大于 0
     pub limit: u64,
 }
 
+impl CursorPagination {
+    /// 从第一页开始的游标分页
+    pub fn new(field: &str, limit: u64) -> Self {
+        Self {
+            field: field.to_string(),
+            after: None,
+            limit,
+        }
+    }
+
+    /// 从给定游标值之后继续读取
+    pub fn after(mut self, value: DataValue) -> Self {
+        self.after = Some(value);
+        self
+    }
+}
+
+/// 游标分页查询的一页结果
+#[derive(Debug, Clone)]
+pub struct CursorPage<T> {
+    /// 本页记录
+    pub records: Vec<T>,
+    /// 下一页的游标值，`None` 表示已是最后一页
+    pub next_cursor: Option<DataValue>,
+}
+
 /// 查询选项
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct QueryOptions {
@@ -108,6 +395,18 @@ pub struct QueryOptions {
     pub pagination: Option<PaginationConfig>,
     /// 选择的字段（空表示选择所有字段）
     pub fields: Vec<String>,
+    /// 排序规则（SQL 数据库为 COLLATE 名称，MongoDB 为 collation locale），作用于字符串比较和排序
+    #[serde(default)]
+    pub collation: Option<String>,
+    /// 附加的计算字段（表达式, 结果字段名），结果中与原字段一并返回
+    #[serde(default)]
+    pub select_exprs: Vec<(Expr, String)>,
+    /// 执行查询的工作负载连接池名称，`None` 表示使用别名的主连接池
+    #[serde(default)]
+    pub workload: Option<String>,
+    /// 游标分页配置，与 `pagination` 互斥
+    #[serde(default)]
+    pub cursor: Option<CursorPagination>,
 }
 
 impl QueryOptions {
@@ -139,4 +438,65 @@ impl QueryOptions {
         self.fields = fields;
         self
     }
+
+    /// 设置排序规则
+    ///
+    /// 例如 PostgreSQL 的 `und-x-icu`、MySQL 的 `utf8mb4_unicode_ci`、SQLite 的 `NOCASE`、MongoDB 的 `sv`
+    pub fn with_collation(mut self, collation: &str) -> Self {
+        self.collation = Some(collation.to_string());
+        self
+    }
+
+    /// 添加计算字段
+    ///
+    /// 例如 `Expr::col("price") * Expr::col("quantity")` 以 `total` 为名返回
+    pub fn with_select_expr(mut self, expr: Expr, alias: &str) -> Self {
+        self.select_exprs.push((expr, alias.to_string()));
+        self
+    }
+
+    /// 指定执行查询的工作负载连接池
+    ///
+    /// 工作负载需在 [`DatabaseConfigBuilder::workload`](crate::config::DatabaseConfigBuilder::workload)
+    /// 中配置，未配置的名称使查询返回配置错误
+    pub fn with_workload(mut self, workload: &str) -> Self {
+        self.workload = Some(workload.to_string());
+        self
+    }
+
+    /// 设置游标分页
+    pub fn with_cursor(mut self, cursor: CursorPagination) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// 把游标分页展开为排序和分页，返回需要追加的游标条件
+    ///
+    /// 游标分页会替换原有排序，与 `pagination` 同时设置时返回验证错误
+    pub(crate) fn resolve_cursor(&mut self) -> QuickDbResult<Option<QueryCondition>> {
+        let cursor = match self.cursor.take() {
+            Some(cursor) => cursor,
+            None => return Ok(None),
+        };
+        if self.pagination.is_some() {
+            return Err(QuickDbError::ValidationError {
+                field: "cursor".to_string(),
+                message: "游标分页不能与 skip/limit 分页同时使用".to_string(),
+            });
+        }
+        if cursor.limit == 0 {
+            return Err(QuickDbError::ValidationError {
+                field: "cursor".to_string(),
+                message: "游标分页的每页记录数必须大于0".to_string(),
+            });
+        }
+
+        self.sort = vec![SortConfig { field: cursor.field.clone(), direction: SortDirection::Asc }];
+        self.pagination = Some(PaginationConfig { skip: 0, limit: cursor.limit });
+        Ok(cursor.after.map(|after| QueryCondition {
+            field: cursor.field,
+            operator: QueryOperator::Gt,
+            value: after,
+        }))
+    }
 }
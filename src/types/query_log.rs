@@ -0,0 +1,274 @@
+//! 查询日志
+//!
+//! 工作器在执行每个操作前按 [`DatabaseConfig::log_queries`] 建立日志作用域，适配器执行 SQL
+//! 或 MongoDB 命令时在该作用域内输出 debug 日志；模式为 `Off` 时不建立作用域，没有额外开销。
+//!
+//! SQL 参数按位置绑定，无法从语句得知参数对应哪个字段，因此脱敏按值进行：操作数据和查询条件中
+//! 敏感字段的值会被收集，绑定参数与其中任一值相等时输出为 `***`。MongoDB 命令文档带有字段名，
+//! 直接按键名脱敏。
+//!
+//! 配置了 N+1 查询检测时即使日志关闭也会建立作用域，执行的语句交给
+//! [`NPlusOneDetector`] 计数，但不输出查询日志
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use rat_logger::debug;
+
+use crate::types::n_plus_one::NPlusOneDetector;
+use crate::types::{DataValue, DatabaseConfig, QueryCondition, QueryConditionGroup, QueryLogMode, QueryOperator, UpdateOperation};
+
+tokio::task_local! {
+    static QUERY_LOG: QueryLogScope;
+}
+
+/// 脱敏后输出的占位文本
+pub(crate) const REDACTED: &str = "***";
+
+/// 单次操作的查询日志设置
+#[derive(Debug, Clone)]
+pub(crate) struct QueryLogScope {
+    alias: String,
+    mode: QueryLogMode,
+    /// 敏感字段名（小写）
+    sensitive_fields: Vec<String>,
+    /// 本次操作中敏感字段的值
+    sensitive_values: Vec<DataValue>,
+    /// N+1 查询检测器（未开启检测时为 `None`）
+    detector: Option<Arc<NPlusOneDetector>>,
+}
+
+impl QueryLogScope {
+    /// 按数据库配置创建日志作用域，未开启查询日志也没有检测器时返回 `None`
+    pub(crate) fn new(config: &DatabaseConfig, detector: Option<Arc<NPlusOneDetector>>) -> Option<Self> {
+        if config.log_queries == QueryLogMode::Off && detector.is_none() {
+            return None;
+        }
+        Some(Self {
+            alias: config.alias.clone(),
+            mode: config.log_queries,
+            sensitive_fields: config.sensitive_fields.iter().map(|f| f.to_lowercase()).collect(),
+            sensitive_values: Vec::new(),
+            detector,
+        })
+    }
+
+    /// 日志模式
+    pub(crate) fn mode(&self) -> QueryLogMode {
+        self.mode
+    }
+
+    /// 是否需要按值脱敏
+    fn redacts(&self) -> bool {
+        self.mode == QueryLogMode::SqlWithRedactedParams && !self.sensitive_fields.is_empty()
+    }
+
+    /// 字段是否需要脱敏（点分路径按最后一段判断）
+    pub(crate) fn is_sensitive_field(&self, field: &str) -> bool {
+        if self.mode != QueryLogMode::SqlWithRedactedParams {
+            return false;
+        }
+        let name = field.rsplit('.').next().unwrap_or(field).to_lowercase();
+        self.sensitive_fields.contains(&name)
+    }
+
+    /// 记录一个敏感值，数组按元素展开以覆盖 IN 条件的逐个绑定
+    fn add_sensitive_value(&mut self, value: &DataValue) {
+        if let DataValue::Array(values) = value {
+            self.sensitive_values.extend(values.iter().cloned());
+        }
+        self.sensitive_values.push(value.clone());
+    }
+
+    /// 收集记录数据中敏感字段的值（含内嵌对象）
+    pub(crate) fn add_data(&mut self, data: &HashMap<String, DataValue>) {
+        if !self.redacts() {
+            return;
+        }
+        for (field, value) in data {
+            if self.is_sensitive_field(field) {
+                self.add_sensitive_value(value);
+            } else if let DataValue::Object(nested) = value {
+                self.add_data(nested);
+            }
+        }
+    }
+
+    /// 收集查询条件中敏感字段的值
+    pub(crate) fn add_conditions(&mut self, conditions: &[QueryCondition]) {
+        if !self.redacts() {
+            return;
+        }
+        for condition in conditions {
+            if !self.is_sensitive_field(&condition.field) {
+                continue;
+            }
+            self.add_sensitive_value(&condition.value);
+            // LIKE 类条件绑定的是拼接通配符后的模式；PostgreSQL JSON 字段的包含查询不转义元字符
+            if let (
+                QueryOperator::Contains | QueryOperator::IContains | QueryOperator::StartsWith | QueryOperator::EndsWith,
+                DataValue::String(s),
+            ) = (&condition.operator, &condition.value)
+            {
+                self.sensitive_values.push(crate::adapter::like_pattern(&condition.operator, &condition.value));
+                self.sensitive_values.push(DataValue::String(format!("%{}%", s)));
+            }
+        }
+    }
+
+    /// 收集条件组中敏感字段的值
+    pub(crate) fn add_condition_groups(&mut self, groups: &[QueryConditionGroup]) {
+        for group in groups {
+            match group {
+                QueryConditionGroup::Single(condition) => self.add_conditions(std::slice::from_ref(condition)),
+                QueryConditionGroup::Group { conditions, .. } => self.add_condition_groups(conditions),
+            }
+        }
+    }
+
+    /// 收集更新操作中敏感字段的值
+    pub(crate) fn add_update_operations(&mut self, operations: &[UpdateOperation]) {
+        if !self.redacts() {
+            return;
+        }
+        for operation in operations {
+            if self.is_sensitive_field(&operation.field) {
+                self.add_sensitive_value(&operation.value);
+            }
+        }
+    }
+
+    /// 按当前模式渲染绑定参数
+    fn render_params(&self, params: &[DataValue]) -> String {
+        params
+            .iter()
+            .map(|param| {
+                if self.redacts() && self.sensitive_values.contains(param) {
+                    REDACTED.to_string()
+                } else {
+                    param.to_json_value().to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// 在查询日志作用域中执行一段异步代码，作用域为 `None` 时直接执行
+pub(crate) async fn with_query_log<F: Future>(scope: Option<QueryLogScope>, future: F) -> F::Output {
+    match scope {
+        Some(scope) => QUERY_LOG.scope(scope, future).await,
+        None => future.await,
+    }
+}
+
+/// 在当前查询日志作用域内执行闭包，未开启查询日志时不执行
+fn with_current_query_log(f: impl FnOnce(&QueryLogScope)) {
+    let _ = QUERY_LOG.try_with(f);
+}
+
+/// 输出一条 SQL 查询日志，参数化的语句同时作为查询形状交给 N+1 检测
+pub(crate) fn log_sql(sql: &str, params: &[DataValue]) {
+    with_current_query_log(|scope| {
+        if let Some(detector) = &scope.detector {
+            detector.record(sql);
+        }
+        match scope.mode {
+            QueryLogMode::Off => {}
+            QueryLogMode::SqlOnly => debug!("[查询日志] alias={} sql={}", scope.alias, sql),
+            QueryLogMode::SqlWithParams | QueryLogMode::SqlWithRedactedParams => {
+                debug!("[查询日志] alias={} sql={} params=[{}]", scope.alias, sql, scope.render_params(params))
+            }
+        }
+    });
+}
+
+/// 输出一条非 SQL 命令日志，`render` 按作用域的模式渲染命令文档，仅在建立了作用域时调用
+///
+/// 开启 N+1 检测时以 `SqlOnly` 模式渲染出不含取值的文档作为查询形状
+pub(crate) fn log_command(command: &str, collection: &str, render: impl Fn(&QueryLogScope) -> String) {
+    with_current_query_log(|scope| {
+        if let Some(detector) = &scope.detector {
+            let shape_scope = QueryLogScope {
+                mode: QueryLogMode::SqlOnly,
+                detector: None,
+                ..scope.clone()
+            };
+            detector.record(&format!("{} {} {}", command, collection, render(&shape_scope)));
+        }
+        if scope.mode != QueryLogMode::Off {
+            debug!("[查询日志] alias={} command={} collection={} document={}", scope.alias, command, collection, render(scope))
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(mode: QueryLogMode) -> QueryLogScope {
+        QueryLogScope {
+            alias: "default".to_string(),
+            mode,
+            sensitive_fields: vec!["password".to_string()],
+            sensitive_values: Vec::new(),
+            detector: None,
+        }
+    }
+
+    #[test]
+    fn test_redacts_sensitive_values_only() {
+        let mut data = HashMap::new();
+        data.insert("username".to_string(), DataValue::String("alice".to_string()));
+        data.insert("Password".to_string(), DataValue::String("s3cret".to_string()));
+
+        let mut redacted = scope(QueryLogMode::SqlWithRedactedParams);
+        redacted.add_data(&data);
+        redacted.add_conditions(&[QueryCondition::new(
+            "password",
+            QueryOperator::In,
+            DataValue::Array(vec![DataValue::String("old".to_string())]),
+        )]);
+        let params = [
+            DataValue::String("alice".to_string()),
+            DataValue::String("s3cret".to_string()),
+            DataValue::String("old".to_string()),
+        ];
+        assert_eq!(redacted.render_params(&params), "\"alice\", ***, ***");
+
+        let mut plain = scope(QueryLogMode::SqlWithParams);
+        plain.add_data(&data);
+        assert_eq!(plain.render_params(&params), "\"alice\", \"s3cret\", \"old\"");
+        assert!(!plain.is_sensitive_field("password"));
+        assert!(redacted.is_sensitive_field("profile.password"));
+    }
+
+    #[test]
+    fn test_redacts_like_patterns_on_sensitive_fields() {
+        let mut redacted = scope(QueryLogMode::SqlWithRedactedParams);
+        redacted.add_condition_groups(&[
+            QueryConditionGroup::Single(QueryCondition::new(
+                "password",
+                QueryOperator::Contains,
+                DataValue::String("50%_off".to_string()),
+            )),
+            QueryConditionGroup::Single(QueryCondition::new(
+                "password",
+                QueryOperator::StartsWith,
+                DataValue::String("s3".to_string()),
+            )),
+            QueryConditionGroup::Single(QueryCondition::new(
+                "username",
+                QueryOperator::IContains,
+                DataValue::String("ali".to_string()),
+            )),
+        ]);
+        let params = [
+            DataValue::String("%50!%!_off%".to_string()),
+            DataValue::String("%50%_off%".to_string()),
+            DataValue::String("s3%".to_string()),
+            DataValue::String("%ali%".to_string()),
+        ];
+        assert_eq!(redacted.render_params(&params), "***, ***, ***, \"%ali%\"");
+    }
+}
@@ -0,0 +1,44 @@
+//! 模型结构同步结果类型
+//!
+//! 记录一次模型结构同步中实际执行的动作，便于启动时输出或审计
+
+use serde::{Deserialize, Serialize};
+
+/// 模型结构同步报告
+///
+/// 同步只会执行增量操作（建表、加列、建索引），不会删除或修改已有结构
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaSyncReport {
+    /// 集合/表名
+    pub collection: String,
+    /// 数据库别名
+    pub alias: String,
+    /// 本次是否新建了表/集合
+    pub table_created: bool,
+    /// 本次新增的列
+    pub added_columns: Vec<String>,
+    /// 表中存在但模型未声明的列（不会被删除）
+    pub extra_columns: Vec<String>,
+    /// 本次新建的索引
+    pub created_indexes: Vec<String>,
+    /// 已存在而跳过的索引
+    pub existing_indexes: Vec<String>,
+    /// 需要人工关注的提示，例如必填字段以可空列的形式补充
+    pub warnings: Vec<String>,
+}
+
+impl SchemaSyncReport {
+    /// 创建空报告
+    pub fn new(collection: impl Into<String>, alias: impl Into<String>) -> Self {
+        Self {
+            collection: collection.into(),
+            alias: alias.into(),
+            ..Default::default()
+        }
+    }
+
+    /// 本次同步是否修改了数据库结构
+    pub fn has_changes(&self) -> bool {
+        self.table_created || !self.added_columns.is_empty() || !self.created_indexes.is_empty()
+    }
+}
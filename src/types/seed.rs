@@ -0,0 +1,113 @@
+//! 初始数据加载结果类型
+//!
+//! 记录一次 `seed` 中按依赖顺序写入的表及每张表插入的行数
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::error::{QuickDbError, QuickDbResult};
+
+/// 初始数据加载报告
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeedReport {
+    /// 数据库别名
+    pub alias: String,
+    /// 按写入顺序排列的表名及插入行数
+    pub inserted: Vec<(String, u64)>,
+}
+
+impl SeedReport {
+    /// 指定表插入的行数，未包含该表时返回 `None`
+    pub fn rows(&self, table: &str) -> Option<u64> {
+        self.inserted.iter().find(|(name, _)| name == table).map(|(_, rows)| *rows)
+    }
+
+    /// 插入的总行数
+    pub fn total_rows(&self) -> u64 {
+        self.inserted.iter().map(|(_, rows)| rows).sum()
+    }
+}
+
+/// 按引用关系排列表的写入顺序，被引用的表排在引用它的表之前
+///
+/// `references` 返回一张表引用的其他表，不在 `tables` 中的表和自引用会被忽略；
+/// 没有依赖关系的表按名称排序，保证顺序稳定。存在循环引用时返回验证错误
+pub(crate) fn dependency_order<F>(tables: &[String], references: F) -> QuickDbResult<Vec<String>>
+where
+    F: Fn(&str) -> Vec<String>,
+{
+    let all: BTreeSet<&str> = tables.iter().map(String::as_str).collect();
+    let mut pending: BTreeMap<&str, BTreeSet<String>> = all
+        .iter()
+        .map(|table| {
+            let parents = references(table)
+                .into_iter()
+                .filter(|parent| parent != table && all.contains(parent.as_str()))
+                .collect();
+            (*table, parents)
+        })
+        .collect();
+
+    let mut order = Vec::with_capacity(pending.len());
+    while !pending.is_empty() {
+        let ready: Vec<&str> = pending
+            .iter()
+            .filter(|(_, parents)| parents.is_empty())
+            .map(|(table, _)| *table)
+            .collect();
+        if ready.is_empty() {
+            let cycle: Vec<&str> = pending.keys().copied().collect();
+            return Err(QuickDbError::ValidationError {
+                field: "seed".to_string(),
+                message: format!("表之间存在循环引用，无法确定写入顺序: {}", cycle.join(", ")),
+            });
+        }
+        for table in ready {
+            pending.remove(table);
+            for parents in pending.values_mut() {
+                parents.remove(table);
+            }
+            order.push(table.to_string());
+        }
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tables(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parents_ordered_before_children() {
+        let references = |table: &str| match table {
+            "order_items" => tables(&["orders", "products"]),
+            "orders" => tables(&["customers", "orders"]),
+            "products" => tables(&["suppliers"]),
+            _ => Vec::new(),
+        };
+        let order = dependency_order(&tables(&["order_items", "orders", "products", "customers"]), references).unwrap();
+        assert_eq!(order, tables(&["customers", "products", "orders", "order_items"]));
+
+        let cyclic = |table: &str| match table {
+            "a" => tables(&["b"]),
+            "b" => tables(&["a"]),
+            _ => Vec::new(),
+        };
+        assert!(dependency_order(&tables(&["a", "b", "c"]), cyclic).is_err());
+    }
+
+    #[test]
+    fn test_report_totals() {
+        let report = SeedReport {
+            alias: "default".to_string(),
+            inserted: vec![("customers".to_string(), 2), ("orders".to_string(), 3)],
+        };
+        assert_eq!(report.rows("orders"), Some(3));
+        assert_eq!(report.rows("missing"), None);
+        assert_eq!(report.total_rows(), 5);
+    }
+}
@@ -51,4 +51,35 @@ pub mod hashmap_datavalue {
         }
         Ok(result)
     }
-}
\ No newline at end of file
+}
+/// `Option<Option<T>>` 反序列化辅助模块
+///
+/// serde 默认把缺失字段和 `null` 都反序列化为 `None`。配合 `#[serde(default, with = "...")]` 使用后，
+/// 缺失字段为 `None`（不修改），`null` 为 `Some(None)`（清空），有值为 `Some(Some(v))`
+///
+/// ```ignore
+/// #[serde(default, with = "rat_quickdb::types::serde_helpers::double_option")]
+/// email: Option<Option<String>>,
+/// ```
+pub mod double_option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(value: &Option<Option<T>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        match value {
+            Some(inner) => inner.serialize(serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Option::<T>::deserialize(deserializer).map(Some)
+    }
+}
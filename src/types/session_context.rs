@@ -0,0 +1,100 @@
+//! 会话上下文
+//!
+//! 为单次操作附加数据库会话设置（如 PostgreSQL 的角色、`app.current_tenant` 等自定义变量），
+//! 配合行级安全策略（RLS）在数据库层完成租户隔离。
+//!
+//! 上下文通过 [`with_session_context`] 作用于一段异步代码，其中发出的每个操作都会携带它。
+//! PostgreSQL 适配器会为这些操作开启事务，先以 `set_config(key, value, true)`
+//! （等价于 `SET LOCAL`）应用全部设置，再执行查询并提交，因此设置只在该操作的事务内有效，
+//! 事务结束即自动复位，不会泄漏到连接池中的其他请求。
+//!
+//! 其他数据库没有对应的会话机制，会忽略上下文。
+//! 携带上下文的查询会绕过查询缓存，避免不同租户之间共享缓存结果。
+
+use std::future::Future;
+
+tokio::task_local! {
+    static SESSION_CONTEXT: SessionContext;
+}
+
+/// 角色设置使用的键，`set_config('role', ..., true)` 与 `SET LOCAL ROLE` 等价
+const ROLE_KEY: &str = "role";
+
+/// 单次操作的会话设置
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionContext {
+    /// 按添加顺序应用的设置项
+    settings: Vec<(String, String)>,
+}
+
+impl SessionContext {
+    /// 创建空的会话上下文
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一项会话设置，同名设置会覆盖之前的值
+    pub fn set(mut self, key: &str, value: &str) -> Self {
+        self.settings.retain(|(k, _)| k != key);
+        self.settings.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// 切换执行操作的数据库角色
+    pub fn role(self, role: &str) -> Self {
+        self.set(ROLE_KEY, role)
+    }
+
+    /// 全部设置项
+    pub fn settings(&self) -> &[(String, String)] {
+        &self.settings
+    }
+
+    /// 是否没有任何设置
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+    }
+}
+
+/// 在指定会话上下文中执行一段异步代码
+///
+/// 上下文只对 `future` 内发出的操作生效；嵌套调用时内层上下文覆盖外层
+pub async fn with_session_context<F: Future>(context: SessionContext, future: F) -> F::Output {
+    SESSION_CONTEXT.scope(context, future).await
+}
+
+/// 获取当前生效的会话上下文，没有或为空时返回 `None`
+pub fn current_session_context() -> Option<SessionContext> {
+    SESSION_CONTEXT
+        .try_with(|context| context.clone())
+        .ok()
+        .filter(|context| !context.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_session_context_scope() {
+        assert!(current_session_context().is_none());
+
+        let context = SessionContext::new()
+            .role("tenant_user")
+            .set("app.current_tenant", "a")
+            .set("app.current_tenant", "b");
+        let settings = with_session_context(context, async {
+            current_session_context().map(|c| c.settings().to_vec())
+        }).await;
+
+        assert_eq!(settings, Some(vec![
+            ("role".to_string(), "tenant_user".to_string()),
+            ("app.current_tenant".to_string(), "b".to_string()),
+        ]));
+        assert!(current_session_context().is_none());
+
+        // 空上下文视为未设置
+        let empty = with_session_context(SessionContext::new(), async { current_session_context() }).await;
+        assert!(empty.is_none());
+    }
+}
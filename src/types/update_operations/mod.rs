@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use crate::error::QuickDbResult;
 use crate::types::data_value::DataValue;
+use std::collections::HashMap;
 
 /// 更新操作符
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -18,6 +20,8 @@ pub enum UpdateOperator {
     PercentIncrease,
     /// 百分比减少 (值是百分比，如10表示减少10%)
     PercentDecrease,
+    /// JSON合并补丁（RFC 7396）：对象按键递归合并，值为 null 的键被删除，其他值直接替换
+    JsonMerge,
 }
 
 /// 更新操作定义
@@ -94,4 +98,128 @@ impl UpdateOperation {
             value: DataValue::Float(percentage),
         }
     }
-}
\ No newline at end of file
+
+    /// 创建一个JSON合并补丁操作
+    ///
+    /// 把 `patch` 深度合并到JSON列中，只修改补丁中出现的键，
+    /// 并发更新同一列的不同键时不会互相覆盖。`patch` 必须是对象
+    pub fn json_merge(field: impl Into<String>, patch: impl Into<DataValue>) -> Self {
+        Self {
+            field: field.into(),
+            operation: UpdateOperator::JsonMerge,
+            value: patch.into(),
+        }
+    }
+
+    /// 取出JSON合并补丁的对象，补丁不是对象时返回验证错误
+    pub(crate) fn json_merge_patch(&self) -> QuickDbResult<serde_json::Map<String, serde_json::Value>> {
+        match self.value.to_json_value() {
+            serde_json::Value::Object(patch) => Ok(patch),
+            _ => Err(crate::quick_error!(
+                validation,
+                self.field.clone(),
+                format!("JSON合并补丁必须是对象，但收到: {}", self.value.type_name())
+            )),
+        }
+    }
+}
+/// 更新时空值的处理策略
+///
+/// `Option<T>` 字段为 `None` 时会被转换为 `DataValue::Null`，
+/// 该策略决定这些空值是写入NULL还是视为"不修改该字段"
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NullPolicy {
+    /// 空值写入为NULL（默认行为）
+    #[default]
+    SetNull,
+    /// 忽略空值字段，保持数据库中的原值不变
+    Skip,
+}
+
+impl NullPolicy {
+    /// 按策略处理更新数据
+    pub fn apply(self, updates: HashMap<String, DataValue>) -> HashMap<String, DataValue> {
+        match self {
+            NullPolicy::SetNull => updates,
+            NullPolicy::Skip => updates
+                .into_iter()
+                .filter(|(_, value)| !matches!(value, DataValue::Null))
+                .collect(),
+        }
+    }
+}
+
+/// 单个字段的更新意图
+///
+/// 用于在更新结构中明确区分"不修改"、"清空为NULL"和"设置新值"，
+/// 避免 `Option<T>` 的 `None` 同时表示"忽略"和"清空"造成的歧义
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Patch<T> {
+    /// 不修改该字段
+    #[default]
+    Keep,
+    /// 将该字段清空为NULL
+    Clear,
+    /// 设置为新值
+    Set(T),
+}
+
+impl<T> Patch<T> {
+    /// 由 `Option` 创建，`None` 表示不修改
+    pub fn from_option(value: Option<T>) -> Self {
+        match value {
+            Some(v) => Patch::Set(v),
+            None => Patch::Keep,
+        }
+    }
+
+    /// 是否为不修改
+    pub fn is_keep(&self) -> bool {
+        matches!(self, Patch::Keep)
+    }
+}
+
+impl<T: Into<DataValue>> Patch<T> {
+    /// 转换为更新值，`Keep` 返回 `None`
+    pub fn into_data_value(self) -> Option<DataValue> {
+        match self {
+            Patch::Keep => None,
+            Patch::Clear => Some(DataValue::Null),
+            Patch::Set(v) => Some(v.into()),
+        }
+    }
+
+    /// 将字段更新写入更新数据，`Keep` 不写入任何内容
+    pub fn apply_to(self, updates: &mut HashMap<String, DataValue>, field: impl Into<String>) {
+        if let Some(value) = self.into_data_value() {
+            updates.insert(field.into(), value);
+        }
+    }
+}
+
+/// 补丁结构中的单个字段
+///
+/// 决定字段是否写入更新数据：`Option<T>` 为 `Some` 时写入，`None` 时不修改；
+/// 可空字段使用 `Option<Option<T>>`，`Some(None)` 表示显式清空为NULL；
+/// `Patch<T>` 按其 `Keep`/`Clear`/`Set` 语义处理
+pub trait PatchField {
+    /// 返回需要写入的更新值，`None` 表示不修改该字段
+    fn patch_value(&self) -> Option<DataValue>;
+}
+
+impl<T: crate::model::ToDataValue> PatchField for Option<T> {
+    fn patch_value(&self) -> Option<DataValue> {
+        self.as_ref().map(|value| value.to_data_value())
+    }
+}
+
+impl<T: crate::model::ToDataValue> PatchField for Patch<T> {
+    fn patch_value(&self) -> Option<DataValue> {
+        match self {
+            Patch::Keep => None,
+            Patch::Clear => Some(DataValue::Null),
+            Patch::Set(value) => Some(value.to_data_value()),
+        }
+    }
+}
+
@@ -0,0 +1,86 @@
+//! 写操作结果类型
+//!
+//! 区分"记录不存在"与"记录存在但内容未变化"两种情况
+
+use serde::{Deserialize, Serialize};
+
+/// 写操作的详细影响行数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriteResult {
+    /// 匹配到的记录数
+    pub matched: u64,
+    /// 实际被修改（或删除）的记录数
+    ///
+    /// MongoDB 会如实区分匹配与修改；SQL 数据库对匹配行总是执行写入，
+    /// 因此该值与 `matched` 相同
+    pub modified: u64,
+}
+
+impl WriteResult {
+    /// 匹配数与修改数相同的结果
+    pub fn uniform(count: u64) -> Self {
+        Self {
+            matched: count,
+            modified: count,
+        }
+    }
+
+    /// 是否匹配到了记录
+    pub fn is_found(&self) -> bool {
+        self.matched > 0
+    }
+
+    /// 是否匹配到记录但内容没有变化
+    pub fn is_unchanged(&self) -> bool {
+        self.matched > 0 && self.modified == 0
+    }
+}
+
+/// 批量插入或更新（upsert）的结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpsertResult {
+    /// 提交写入的记录数
+    pub total: u64,
+    /// 新插入的记录数
+    ///
+    /// PostgreSQL 和 MongoDB 能够区分插入与更新；SQLite 和 MySQL 无法区分，此时为 `None`
+    pub inserted: Option<u64>,
+    /// 更新已有记录的数量，无法区分时为 `None`
+    pub updated: Option<u64>,
+}
+
+impl UpsertResult {
+    /// 无法区分插入与更新时的结果
+    pub fn undistinguished(total: u64) -> Self {
+        Self {
+            total,
+            inserted: None,
+            updated: None,
+        }
+    }
+
+    /// 已知插入数量时的结果，其余记录视为更新
+    pub fn with_inserted(total: u64, inserted: u64) -> Self {
+        Self {
+            total,
+            inserted: Some(inserted),
+            updated: Some(total.saturating_sub(inserted)),
+        }
+    }
+}
+
+/// 单条插入或更新（upsert）的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UpsertOutcome {
+    /// 冲突字段上没有已存在的记录，插入了新记录
+    Inserted,
+    /// 冲突字段上已存在记录，用新值更新了该记录
+    Updated,
+}
+
+impl UpsertOutcome {
+    /// 是否插入了新记录
+    pub fn is_inserted(&self) -> bool {
+        matches!(self, UpsertOutcome::Inserted)
+    }
+}
@@ -0,0 +1,2452 @@
+//! TestDb 测试辅助模块的集成测试
+
+#![cfg(all(feature = "testing", feature = "sqlite-support"))]
+
+use rat_quickdb::testing::TestDb;
+use rat_quickdb::*;
+use rat_quickdb::types::{DataValue, QueryCondition, QueryOperator};
+use rat_logger::debug;
+
+define_model! {
+    /// 测试用户模型
+    struct HarnessUser {
+        id: String,
+        username: String,
+        age: i32,
+    }
+    collection = "harness_users",
+    fields = {
+        id: string_field(None, None, None).required().unique(),
+        username: string_field(None, None, None).required(),
+        age: integer_field(None, None).required(),
+    }
+}
+
+define_model! {
+    /// 带标签数组的测试文章模型
+    struct HarnessArticle {
+        id: String,
+        title: String,
+        tags: Option<Vec<String>>,
+    }
+    collection = "harness_articles",
+    fields = {
+        id: string_field(None, None, None).required().unique(),
+        title: string_field(None, None, None).required(),
+        tags: array_field(field_types!(string), None, None),
+    }
+}
+
+fn article(title: &str, tags: &[&str]) -> HarnessArticle {
+    HarnessArticle {
+        id: String::new(),
+        title: title.to_string(),
+        tags: Some(tags.iter().map(|t| t.to_string()).collect()),
+    }
+}
+
+fn user(username: &str, age: i32) -> HarnessUser {
+    HarnessUser {
+        id: String::new(),
+        username: username.to_string(),
+        age,
+    }
+}
+
+#[tokio::test]
+async fn test_sqlite_memory_databases_are_isolated() -> QuickDbResult<()> {
+    let first = TestDb::sqlite_memory().await?;
+    let second = TestDb::sqlite_memory().await?;
+    assert_ne!(first.alias(), second.alias());
+
+    let ids = first.seed(&[user("alice", 30), user("bob", 25)]).await?;
+    assert_eq!(ids.len(), 2);
+    second.seed(&[user("carol", 40)]).await?;
+
+    let adults = vec![QueryCondition::new("age", QueryOperator::Gte, DataValue::Int(18))];
+    assert_eq!(odm::count("harness_users", adults.clone(), Some(first.alias())).await?, 2);
+    assert_eq!(odm::count("harness_users", adults, Some(second.alias())).await?, 1);
+
+    let alias = first.alias().to_string();
+    first.teardown().await?;
+    second.teardown().await?;
+    assert!(!get_aliases().contains(&alias));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_scalar_on_sqlite() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.seed(&[user("alice", 30), user("bob", 25)]).await?;
+
+    let total: Option<i64> = odm::query_scalar(
+        "SELECT COUNT(*) FROM harness_users WHERE age > ?",
+        vec![DataValue::Int(18)],
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(total, Some(2));
+
+    let oldest: Option<String> = odm::query_scalar(
+        "SELECT username FROM harness_users ORDER BY age DESC",
+        vec![],
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(oldest.as_deref(), Some("alice"));
+
+    let missing: Option<i64> = odm::query_scalar(
+        "SELECT MAX(age) FROM harness_users WHERE age > ?",
+        vec![DataValue::Int(100)],
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(missing, None);
+
+    let multi = odm::query_scalar::<i64>("SELECT age, username FROM harness_users", vec![], Some(db.alias())).await;
+    assert!(multi.is_err());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_pool_sampling_records_bounded_history() -> QuickDbResult<()> {
+    let pool = PoolConfig::builder()
+        .min_connections(1)
+        .max_connections(2)
+        .connection_timeout(10)
+        .idle_timeout(300)
+        .max_lifetime(1800)
+        .max_retries(1)
+        .retry_interval_ms(100)
+        .keepalive_interval_sec(60)
+        .health_check_timeout_sec(5)
+        .sampling(PoolSamplingConfig { interval_ms: 10, capacity: 3 })
+        .build()?;
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(pool)
+        .alias("sampled")
+        .id_strategy(IdStrategy::AutoIncrement)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.seed(&[user("alice", 30)]).await?;
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    let history = pool_history(db.alias())?;
+    assert_eq!(history.len(), 3);
+    assert!(history.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+    assert!(history.iter().all(|s| s.in_use + s.idle == s.connections));
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_pool_status_reports_live_counts_and_limits() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.seed(&[user("alice", 30)]).await?;
+
+    let status = get_pool_status(db.alias()).expect("已注册的别名应有连接池状态");
+    assert!(status.total_connections >= 1);
+    assert_eq!(status.active_connections + status.idle_connections, status.total_connections);
+    assert_eq!(status.waiting_connections, 0);
+    assert!(status.max_connections >= status.min_connections);
+
+    assert_eq!(get_all_pool_status().get(db.alias()), Some(&status));
+    assert!(get_pool_status("no_such_alias").is_none());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_array_contains_all_and_size_on_sqlite() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.seed(&[
+        article("rust-db", &["rust", "db"]),
+        article("rust-web-db", &["rust", "web", "db"]),
+        article("go", &["go"]),
+    ]).await?;
+
+    let tags = |values: &[&str]| DataValue::Array(values.iter().map(|v| DataValue::String(v.to_string())).collect());
+    let contains_all = vec![QueryCondition::new("tags", QueryOperator::ArrayContainsAll, tags(&["db", "rust"]))];
+    assert_eq!(odm::count("harness_articles", contains_all, Some(db.alias())).await?, 2);
+
+    let size = vec![QueryCondition::new("tags", QueryOperator::ArraySize, 3i64)];
+    let found = odm::find("harness_articles", size, None, Some(db.alias())).await?;
+    assert_eq!(found.len(), 1);
+
+    let both = vec![
+        QueryCondition::new("tags", QueryOperator::ArrayContainsAll, tags(&["rust"])),
+        QueryCondition::new("tags", QueryOperator::ArraySize, 2i64),
+    ];
+    assert_eq!(odm::count("harness_articles", both, Some(db.alias())).await?, 1);
+
+    let invalid = vec![QueryCondition::new("tags", QueryOperator::ArraySize, -1i64)];
+    assert!(odm::count("harness_articles", invalid, Some(db.alias())).await.is_err());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_dynamic_model_crud_on_sqlite() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let db = TestDb::sqlite_memory().await?;
+    db.track_table("tenant_orders");
+    let meta = ModelMeta::new("tenant_orders")
+        .with_alias(db.alias())
+        .field("id", integer_field(None, None))
+        .field("sku", string_field(Some(16), Some(1), None).required())
+        .field("quantity", integer_field(Some(1), None).required())
+        .index(IndexDefinition::new(vec!["sku".to_string()], true));
+    let orders = DynamicModel::register(meta)?;
+    assert!(DynamicModel::get("tenant_orders").is_some());
+
+    let order = |sku: &str, quantity: i64| {
+        let mut data = HashMap::new();
+        data.insert("sku".to_string(), DataValue::String(sku.to_string()));
+        data.insert("quantity".to_string(), DataValue::Int(quantity));
+        data
+    };
+    let id = orders.create(order("A-1", 2)).await?;
+    orders.create_many(vec![order("B-2", 5), order("C-3", 1)]).await?;
+    assert!(orders.create(order("D-4", 0)).await.is_err());
+    // 唯一索引由注册的元数据创建
+    assert!(orders.create(order("A-1", 3)).await.is_err());
+
+    let bulk = vec![QueryCondition::new("quantity", QueryOperator::Gte, DataValue::Int(2))];
+    assert_eq!(orders.count(bulk.clone()).await?, 2);
+    assert!(orders.count(vec![QueryCondition::new("colour", QueryOperator::Eq, "red")]).await.is_err());
+
+    let id = id.to_string();
+    let mut updates = HashMap::new();
+    updates.insert("quantity".to_string(), DataValue::Int(7));
+    assert!(orders.update_by_id(&id, updates).await?);
+    let found = orders.find_by_id(id.as_str()).await?.expect("记录应存在");
+    assert_eq!(found.get("quantity"), Some(&DataValue::Int(7)));
+
+    assert!(orders.delete_by_id(&id).await?);
+    assert_eq!(orders.find(vec![], None).await?.len(), 2);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_query_logging_does_not_affect_results() -> QuickDbResult<()> {
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_query_log")
+        .id_strategy(IdStrategy::AutoIncrement)
+        .log_queries(QueryLogMode::SqlWithRedactedParams)
+        .sensitive_fields(vec!["username".to_string()])
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.seed(&[user("alice", 30), user("bob", 25)]).await?;
+
+    let conditions = vec![QueryCondition::new("username", QueryOperator::Eq, "alice")];
+    assert_eq!(odm::count("harness_users", conditions, Some(db.alias())).await?, 1);
+    let conditions = vec![QueryCondition::new("username", QueryOperator::StartsWith, "al")];
+    assert_eq!(odm::count("harness_users", conditions, Some(db.alias())).await?, 1);
+
+    db.teardown().await
+}
+
+#[test]
+fn test_pool_retry_policy_config() -> QuickDbResult<()> {
+    let pool_builder = || PoolConfig::builder()
+        .min_connections(1)
+        .max_connections(2)
+        .connection_timeout(10)
+        .idle_timeout(300)
+        .max_lifetime(1800)
+        .max_retries(1)
+        .retry_interval_ms(100)
+        .keepalive_interval_sec(60)
+        .health_check_timeout_sec(5);
+
+    // 默认不重试
+    let pool = pool_builder().build()?;
+    assert_eq!(pool.retry_attempts, 0);
+    assert_eq!((pool.retry_base_delay_ms, pool.retry_max_delay_ms), (100, 5000));
+
+    let pool = pool_builder().retry_attempts(3).retry_base_delay_ms(50).retry_max_delay_ms(800).build()?;
+    assert_eq!((pool.retry_attempts, pool.retry_base_delay_ms, pool.retry_max_delay_ms), (3, 50, 800));
+    assert!(pool_builder().retry_base_delay_ms(1000).retry_max_delay_ms(500).build().is_err());
+
+    // 旧配置文件缺少重试字段时使用默认值
+    let mut json = serde_json::to_value(PoolConfig::default())?;
+    for field in ["retry_attempts", "retry_base_delay_ms", "retry_max_delay_ms"] {
+        json.as_object_mut().unwrap().remove(field);
+    }
+    let pool: PoolConfig = serde_json::from_value(json)?;
+    assert_eq!((pool.retry_attempts, pool.retry_base_delay_ms, pool.retry_max_delay_ms), (0, 100, 5000));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_operations_use_separate_lane() -> QuickDbResult<()> {
+    let pool_builder = || PoolConfig::builder()
+        .min_connections(1)
+        .max_connections(4)
+        .connection_timeout(10)
+        .idle_timeout(300)
+        .max_lifetime(1800)
+        .max_retries(1)
+        .retry_interval_ms(100)
+        .keepalive_interval_sec(60)
+        .health_check_timeout_sec(5);
+    assert!(pool_builder().reserved_interactive_connections(4).build().is_err());
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(pool_builder().reserved_interactive_connections(1).max_batch_operations(1).build()?)
+        .alias("test_batch_lane")
+        .id_strategy(IdStrategy::AutoIncrement)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.seed(&[user("alice", 30), user("bob", 25)]).await?;
+
+    // 批量通道与交互通道共用同一个内存数据库，超出上限的批量操作排队后仍会完成
+    let alias = db.alias().to_string();
+    let batches: Vec<_> = (0..3)
+        .map(|_| {
+            let alias = alias.clone();
+            tokio::spawn(with_operation_class(OperationClass::Batch, async move {
+                odm::count("harness_users", vec![], Some(&alias)).await
+            }))
+        })
+        .collect();
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 2);
+    for batch in batches {
+        assert_eq!(batch.await.expect("批量任务异常退出")?, 2);
+    }
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_seed_fixtures_roll_back_on_failure() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let db = TestDb::sqlite_memory().await?;
+    db.track_table("harness_users");
+    db.track_table("harness_articles");
+    HarnessUser::meta();
+    HarnessArticle::meta();
+
+    let users = [user("alice", 30), user("bob", 25)]
+        .iter()
+        .map(|item| item.to_data_map_direct())
+        .collect::<QuickDbResult<Vec<_>>>()?;
+    let articles = vec![article("intro", &["rust"]).to_data_map_direct()?];
+    let mut fixtures = HashMap::new();
+    fixtures.insert("harness_users".to_string(), users);
+    fixtures.insert("harness_articles".to_string(), articles);
+
+    let report = odm::seed(fixtures, Some(db.alias())).await?;
+    assert_eq!(report.rows("harness_users"), Some(2));
+    assert_eq!(report.rows("harness_articles"), Some(1));
+    assert_eq!(report.total_rows(), 3);
+
+    // 任一记录写入失败时整批回滚，先写入的其他表记录也不会保留
+    let mut broken = user("carol", 40).to_data_map_direct()?;
+    broken.insert("missing_column".to_string(), DataValue::Int(1));
+    let mut fixtures = HashMap::new();
+    fixtures.insert("harness_articles".to_string(), vec![article("draft", &[]).to_data_map_direct()?]);
+    fixtures.insert("harness_users".to_string(), vec![user("dave", 35).to_data_map_direct()?, broken]);
+    assert!(odm::seed(fixtures, Some(db.alias())).await.is_err());
+
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 2);
+    assert_eq!(odm::count("harness_articles", vec![], Some(db.alias())).await?, 1);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_read_only_alias_rejects_writes() -> QuickDbResult<()> {
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_read_only")
+        .id_strategy(IdStrategy::AutoIncrement)
+        .read_only(true)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    HarnessUser::meta();
+
+    let created = odm::create("harness_users", user("alice", 30).to_data_map_direct()?, Some(db.alias())).await;
+    assert_eq!(created.unwrap_err().kind(), ErrorKind::ReadOnly);
+    let conditions = vec![QueryCondition::new("age", QueryOperator::Gt, DataValue::Int(18))];
+    assert!(matches!(
+        odm::delete("harness_users", conditions, Some(db.alias())).await,
+        Err(QuickDbError::ReadOnly { .. })
+    ));
+    assert!(matches!(drop_table(db.alias(), "harness_users").await, Err(QuickDbError::ReadOnly { .. })));
+
+    // 原始SQL只有单条只读查询可以执行，其余按写操作拒绝
+    let raw_delete = odm::query_raw("DELETE FROM harness_users", vec![], Some(db.alias())).await;
+    assert!(matches!(raw_delete, Err(QuickDbError::ReadOnly { .. })));
+    let raw_select = odm::query_raw("SELECT 1 AS one", vec![], Some(db.alias())).await?;
+    assert_eq!(raw_select.len(), 1);
+
+    // 读操作照常执行；写操作没有到达数据库，表也没有被自动创建
+    let missing = odm::count("harness_users", vec![], Some(db.alias())).await.unwrap_err();
+    assert!(missing.is_missing_table());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_dry_run_alias_rolls_back_mutations() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("dry_run.db").to_string_lossy().to_string();
+    let config = |dry_run: bool| {
+        DatabaseConfig::builder()
+            .db_type(DatabaseType::SQLite)
+            .connection(ConnectionConfig::SQLite {
+                path: path.clone(),
+                create_if_missing: true,
+            })
+            .pool(PoolConfig::default())
+            .alias("test_dry_run")
+            .id_strategy(IdStrategy::AutoIncrement)
+            .dry_run(dry_run)
+            .build()
+    };
+    // 两个别名指向同一个文件，试运行别名的修改在另一个别名上不可见
+    let db = TestDb::with_config(config(false)?).await?;
+    let dry = TestDb::with_config(config(true)?).await?;
+    db.track_table("harness_users");
+    db.seed(&[user("alice", 30), user("bob", 17), user("carol", 45)]).await?;
+
+    let adults = vec![QueryCondition::new("age", QueryOperator::Gte, DataValue::Int(18))];
+    let mut updates = HashMap::new();
+    updates.insert("age".to_string(), DataValue::Int(99));
+    assert_eq!(odm::update("harness_users", adults.clone(), updates, Some(dry.alias())).await?, 2);
+    assert_eq!(odm::delete("harness_users", adults.clone(), Some(dry.alias())).await?, 2);
+    let mut data = user("dave", 20).to_data_map_direct()?;
+    data.remove("id");
+    odm::create("harness_users", data, Some(dry.alias())).await?;
+
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 3);
+    assert_eq!(odm::count("harness_users", adults, Some(db.alias())).await?, 2);
+
+    // 无法回滚的写操作和不存在的表直接返回错误
+    let rows = vec![user("erin", 50).to_data_map_direct()?];
+    assert!(odm::upsert_many("harness_users", rows, &["username"], Some(dry.alias())).await.is_err());
+    assert!(drop_table(dry.alias(), "harness_users").await.is_err());
+    assert!(odm::query_raw("DELETE FROM harness_users", vec![], Some(dry.alias())).await.is_err());
+    let missing = odm::create("harness_missing", HashMap::new(), Some(dry.alias())).await.unwrap_err();
+    assert_eq!(missing.kind(), ErrorKind::Validation);
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 3);
+
+    dry.teardown().await?;
+    db.teardown().await
+}
+
+define_model! {
+    /// 邮箱不区分大小写唯一的测试账户模型
+    struct HarnessAccount {
+        id: String,
+        email: String,
+    }
+    collection = "harness_accounts",
+    fields = {
+        id: string_field(None, None, None).required().unique(),
+        email: string_field(None, None, None).required().unique_case_insensitive(),
+    }
+}
+
+#[tokio::test]
+async fn test_case_insensitive_unique_on_sqlite() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    // 索引随模型元数据在建表时创建，需要按测试别名注册
+    register_model(HarnessAccount::meta().with_alias(db.alias()))?;
+    let account = |email: &str| HarnessAccount { id: String::new(), email: email.to_string() };
+    db.seed(&[account("Alice@Example.com")]).await?;
+
+    let duplicate = db.seed(&[account("alice@example.COM")]).await.unwrap_err();
+    assert_eq!(duplicate.kind(), ErrorKind::UniqueViolation);
+    db.seed(&[account("bob@example.com")]).await?;
+    assert_eq!(odm::count("harness_accounts", vec![], Some(db.alias())).await?, 2);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_find_by_ids_keys_models_by_id() -> QuickDbResult<()> {
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_find_by_ids")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    let ids = db.seed(&[user("alice", 30), user("bob", 25), user("carol", 40)]).await?;
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    // ModelManager 按模型元数据中的别名查询，未指定时使用默认别名
+    set_default_alias(db.alias()).await?;
+
+    let requested = vec![ids[0].as_str(), ids[2].as_str(), "missing"];
+    let users = ModelManager::<HarnessUser>::find_by_ids(requested).await?;
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[&ids[0]].username, "alice");
+    assert_eq!(users[&ids[2]].username, "carol");
+    assert!(!users.contains_key("missing"));
+
+    assert!(ModelManager::<HarnessUser>::find_by_ids(vec![]).await?.is_empty());
+
+    // 按输入顺序返回，跳过不存在的ID，重复的ID只返回一次
+    let requested = [&ids[2], "missing", &ids[0], &ids[1], &ids[2]]
+        .iter()
+        .map(|id| DataValue::from(*id))
+        .collect();
+    let ordered = odm::find_by_ids("harness_users", requested, true, Some(db.alias())).await?;
+    let names: Vec<String> = ordered
+        .iter()
+        .map(|record| match record {
+            DataValue::Object(map) => map["username"].to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+    assert_eq!(names, vec!["carol", "alice", "bob"]);
+
+    db.teardown().await
+}
+
+/// 按调用次数生成ID的自定义生成器
+struct SequenceIdGenerator(std::sync::atomic::AtomicU64);
+
+#[async_trait::async_trait]
+impl IdGeneratorTrait for SequenceIdGenerator {
+    async fn generate(&self, table: &str, _alias: &str) -> QuickDbResult<DataValue> {
+        let next = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+        Ok(DataValue::String(format!("{}-{}", table, next)))
+    }
+}
+
+#[tokio::test]
+async fn test_id_strategies_populate_ids_on_sqlite() -> QuickDbResult<()> {
+    register_id_generator("harness_sequence", std::sync::Arc::new(SequenceIdGenerator(Default::default())));
+    let strategies = vec![
+        IdStrategy::AutoIncrement,
+        IdStrategy::Uuid,
+        IdStrategy::UuidV7,
+        IdStrategy::Ulid,
+        IdStrategy::Snowflake { machine_id: 1, datacenter_id: 1 },
+        IdStrategy::ObjectId,
+        IdStrategy::Custom("harness_sequence".to_string()),
+    ];
+
+    for strategy in strategies {
+        let config = DatabaseConfig::builder()
+            .db_type(DatabaseType::SQLite)
+            .connection(ConnectionConfig::SQLite {
+                path: ":memory:".to_string(),
+                create_if_missing: true,
+            })
+            .pool(PoolConfig::default())
+            .alias("test_id_strategy")
+            .id_strategy(strategy.clone())
+            .build()?;
+        let db = TestDb::with_config(config).await?;
+
+        // 未提供ID时由数据库（AutoIncrement）或ODM（其余策略）生成
+        let ids = db.seed(&[user("alice", 30), user("bob", 25)]).await?;
+        assert_ne!(ids[0], ids[1], "{:?}", strategy);
+        let generated = ids[0].to_string();
+        match &strategy {
+            IdStrategy::AutoIncrement => assert!(matches!(ids[0], DataValue::Int(id) if id > 0), "{:?}", ids[0]),
+            IdStrategy::Uuid | IdStrategy::UuidV7 => assert_eq!(generated.len(), 36, "{}", generated),
+            IdStrategy::Ulid => assert_eq!(generated.len(), 26, "{}", generated),
+            IdStrategy::ObjectId => {
+                assert_eq!(generated.len(), 24, "{}", generated);
+                assert!(generated.chars().all(|c| c.is_ascii_hexdigit()), "{}", generated);
+            }
+            IdStrategy::Snowflake { .. } => assert!(generated.parse::<u64>().is_ok(), "{}", generated),
+            IdStrategy::Custom(_) => assert!(generated.starts_with("harness_users-"), "{}", generated),
+        }
+
+        // 提供ID时ODM生成的策略保留传入值，AutoIncrement忽略传入值
+        let mut explicit = user("carol", 40);
+        explicit.id = "explicit-id".to_string();
+        let explicit_id = db.seed(&[explicit]).await?.remove(0);
+        if matches!(strategy, IdStrategy::AutoIncrement) {
+            assert!(matches!(explicit_id, DataValue::Int(_)), "{:?}", explicit_id);
+        } else {
+            assert_eq!(explicit_id.to_string(), "explicit-id");
+        }
+
+        for id in ids.iter().chain(std::iter::once(&explicit_id)) {
+            let found = odm::find_by_id("harness_users", id.clone(), Some(db.alias())).await?;
+            assert!(found.is_some(), "{:?}: {:?}", strategy, id);
+        }
+        db.teardown().await?;
+    }
+
+    unregister_id_generator("harness_sequence");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_with_unique_retries_on_collision() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let db = TestDb::sqlite_memory().await?;
+    db.track_table("harness_accounts");
+    register_model(HarnessAccount::meta().with_alias(db.alias()))?;
+    let email = |attempt: u32| match attempt {
+        1 => DataValue::String("alice@example.com".to_string()),
+        n => DataValue::String(format!("alice-{}@example.com", n)),
+    };
+
+    let (_, first) = odm::create_with_unique("harness_accounts", HashMap::new(), "email", email, Some(db.alias())).await?;
+    assert_eq!(first, DataValue::String("alice@example.com".to_string()));
+    let (_, second) = odm::create_with_unique("harness_accounts", HashMap::new(), "email", email, Some(db.alias())).await?;
+    assert_eq!(second, DataValue::String("alice-2@example.com".to_string()));
+    let (_, third) = odm::create_with_unique("harness_accounts", HashMap::new(), "email", email, Some(db.alias())).await?;
+    assert_eq!(third, DataValue::String("alice-3@example.com".to_string()));
+
+    // 生成器始终返回同一个值时，达到尝试上限后返回唯一约束冲突
+    let fixed = |_: u32| DataValue::String("ALICE@example.com".to_string());
+    let exhausted = odm::create_with_unique("harness_accounts", HashMap::new(), "email", fixed, Some(db.alias())).await.unwrap_err();
+    assert_eq!(exhausted.kind(), ErrorKind::UniqueViolation);
+    assert_eq!(odm::count("harness_accounts", vec![], Some(db.alias())).await?, 3);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_blob_streams_in_chunks() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+    use tokio::io::AsyncReadExt;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_blob_streams")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_files");
+    register_model(
+        ModelMeta::new("harness_files")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("name", string_field(None, None, None).required())
+            .field("content", FieldDefinition::new(FieldType::Binary)),
+    )?;
+    let mut data = HashMap::new();
+    data.insert("name".to_string(), DataValue::String("report.bin".to_string()));
+    data.insert("content".to_string(), DataValue::Bytes(vec![1]));
+    let id = odm::create("harness_files", data, Some(db.alias())).await?;
+
+    // 超过两块且包含 0x00 与非法 UTF-8 字节
+    let content: Vec<u8> = (0..BLOB_CHUNK_SIZE * 2 + 1000).map(|i| (i % 256) as u8).collect();
+    let written = odm::write_blob("harness_files", id.clone(), "content", &mut content.as_slice(), Some(db.alias())).await?;
+    assert_eq!(written, content.len() as u64);
+
+    let mut reader = odm::read_blob("harness_files", id.clone(), "content", Some(db.alias())).await?;
+    let mut read_back = Vec::new();
+    reader.read_to_end(&mut read_back).await?;
+    assert_eq!(read_back, content);
+
+    // 覆盖写入更短的数据
+    let written = odm::write_blob("harness_files", id.clone(), "content", &mut &b"\xff\x00"[..], Some(db.alias())).await?;
+    assert_eq!(written, 2);
+    let mut read_back = Vec::new();
+    odm::read_blob("harness_files", id, "content", Some(db.alias())).await?
+        .read_to_end(&mut read_back).await?;
+    assert_eq!(read_back, b"\xff\x00");
+
+    let missing = DataValue::String("missing".to_string());
+    assert!(odm::read_blob("harness_files", missing.clone(), "content", Some(db.alias())).await.is_err());
+    let err = odm::write_blob("harness_files", missing, "content", &mut &b"x"[..], Some(db.alias())).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_json_merge_patch_on_sqlite() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_json_merge")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_profiles");
+    register_model(
+        ModelMeta::new("harness_profiles")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("profile", FieldDefinition::new(FieldType::Json)),
+    )?;
+    let mut data = HashMap::new();
+    data.insert("profile".to_string(), DataValue::Json(serde_json::json!({
+        "name": "a",
+        "prefs": { "theme": "dark", "lang": "en" },
+        "tmp": 1
+    })));
+    let id = odm::create("harness_profiles", data, Some(db.alias())).await?;
+    let by_id = vec![QueryCondition::new("id", QueryOperator::Eq, id.clone())];
+
+    let patch = serde_json::json!({ "prefs": { "lang": "zh" }, "tmp": null, "age": 3 });
+    let updated = odm::update_with_operations(
+        "harness_profiles",
+        by_id.clone(),
+        vec![types::UpdateOperation::json_merge("profile", DataValue::Json(patch))],
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(updated, 1);
+
+    let record = match odm::find_by_id("harness_profiles", id, Some(db.alias())).await? {
+        Some(DataValue::Object(record)) => record,
+        other => panic!("记录应存在: {:?}", other),
+    };
+    let profile = match record.get("profile") {
+        Some(DataValue::String(text)) => serde_json::from_str(text)?,
+        Some(other) => other.to_json_value(),
+        None => serde_json::Value::Null,
+    };
+    assert_eq!(profile, serde_json::json!({
+        "name": "a",
+        "prefs": { "theme": "dark", "lang": "zh" },
+        "age": 3
+    }));
+
+    // 补丁必须是对象
+    let err = odm::update_with_operations(
+        "harness_profiles",
+        by_id,
+        vec![types::UpdateOperation::json_merge("profile", DataValue::Int(1))],
+        Some(db.alias()),
+    ).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Validation);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_concurrent_sqlite_writers_retry_on_busy() -> QuickDbResult<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("busy.db").to_string_lossy().to_string();
+    let config = || {
+        DatabaseConfig::builder()
+            .db_type(DatabaseType::SQLite)
+            .connection(ConnectionConfig::SQLite {
+                path: path.clone(),
+                create_if_missing: true,
+            })
+            .pool(PoolConfig::default())
+            .alias("test_busy_retry")
+            .id_strategy(IdStrategy::Uuid)
+            .busy_retry_count(20)
+            .build()
+    };
+    // 两个别名指向同一个文件，各自的连接池互相争用写锁
+    let first = TestDb::with_config(config()?).await?;
+    let second = TestDb::with_config(config()?).await?;
+    first.track_table("harness_users");
+    first.seed(&[user("setup", 1)]).await?;
+
+    let mut writers = Vec::new();
+    for writer in 0..16 {
+        let alias = if writer % 2 == 0 { first.alias() } else { second.alias() }.to_string();
+        writers.push(tokio::spawn(async move {
+            for i in 0..20 {
+                let mut data = user(&format!("writer-{}-{}", writer, i), i).to_data_map()?;
+                data.remove("id");
+                odm::create("harness_users", data, Some(&alias)).await?;
+            }
+            QuickDbResult::Ok(())
+        }));
+    }
+    for writer in writers {
+        writer.await.expect("写入任务异常退出")?;
+    }
+
+    assert_eq!(odm::count("harness_users", vec![], Some(second.alias())).await?, 16 * 20 + 1);
+    second.teardown().await?;
+    first.teardown().await
+}
+
+#[tokio::test]
+async fn test_saturated_pool_fails_fast() -> QuickDbResult<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("saturated.db").to_string_lossy().to_string();
+    let config = |max_connections: u32| -> QuickDbResult<DatabaseConfig> {
+        let pool = PoolConfig::builder()
+            .min_connections(1)
+            .max_connections(max_connections)
+            .connection_timeout(10)
+            .idle_timeout(300)
+            .max_lifetime(1800)
+            .max_retries(1)
+            .retry_interval_ms(100)
+            .keepalive_interval_sec(60)
+            .health_check_timeout_sec(5)
+            .build()?;
+        DatabaseConfig::builder()
+            .db_type(DatabaseType::SQLite)
+            .connection(ConnectionConfig::SQLite {
+                path: path.clone(),
+                create_if_missing: true,
+            })
+            .pool(pool)
+            .alias("test_saturated")
+            .id_strategy(IdStrategy::Uuid)
+            .busy_retry_count(0)
+            .build()
+    };
+    let db = TestDb::with_config(config(1)?).await?;
+    let blocker = TestDb::with_config(config(2)?).await?;
+    db.track_table("harness_users");
+    db.seed(&[user("setup", 1)]).await?;
+    assert!(!is_saturated(db.alias()));
+
+    // 另一个连接池持有写锁，使下面的写入停在执行中
+    let DatabaseConnection::SQLite(raw) = get_raw_connection(Some(blocker.alias())).await? else {
+        panic!("应为SQLite连接");
+    };
+    let mut lock = raw.acquire().await.expect("获取连接失败");
+    sqlx::query("BEGIN IMMEDIATE").execute(&mut *lock).await.expect("加锁失败");
+
+    let alias = db.alias().to_string();
+    let writer = tokio::spawn(async move {
+        let mut data = user("blocked", 2).to_data_map()?;
+        data.remove("id");
+        odm::create("harness_users", data, Some(&alias)).await
+    });
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while !is_saturated(db.alias()) {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+    }).await.expect("连接池应进入饱和状态");
+
+    let err = try_operation(db.alias(), odm::count("harness_users", vec![], Some(db.alias())))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, QuickDbError::PoolTimeout { .. }));
+    assert_eq!(err.kind(), ErrorKind::Timeout);
+
+    sqlx::query("COMMIT").execute(&mut *lock).await.expect("释放锁失败");
+    drop(lock);
+    writer.await.expect("写入任务异常退出")?;
+    assert!(!is_saturated(db.alias()));
+    let count = try_operation(db.alias(), odm::count("harness_users", vec![], Some(db.alias()))).await?;
+    assert_eq!(count, 2);
+
+    blocker.teardown().await?;
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_column_defaults_apply_to_raw_inserts() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_column_defaults")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_tickets");
+    register_model(
+        ModelMeta::new("harness_tickets")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("title", string_field(None, None, None).required())
+            .field("status", string_field(None, None, None).default_value(DataValue::String("open".to_string())))
+            .field("created_at", datetime_field().default_expr(DefaultExpr::CurrentTimestamp)),
+    )?;
+
+    let mut data = HashMap::new();
+    data.insert("title".to_string(), DataValue::String("orm".to_string()));
+    odm::create("harness_tickets", data, Some(db.alias())).await?;
+
+    // 绕过ORM的原生插入同样得到数据库默认值
+    let DatabaseConnection::SQLite(raw) = get_raw_connection(Some(db.alias())).await? else {
+        panic!("应为SQLite连接");
+    };
+    sqlx::query("INSERT INTO harness_tickets (id, title) VALUES ('raw-1', 'raw')")
+        .execute(&raw)
+        .await
+        .expect("原生插入失败");
+
+    let records = odm::find("harness_tickets", vec![], None, Some(db.alias())).await?;
+    assert_eq!(records.len(), 2);
+    for record in records {
+        let DataValue::Object(record) = record else {
+            panic!("记录应为对象");
+        };
+        assert_eq!(record.get("status"), Some(&DataValue::String("open".to_string())));
+        assert!(!matches!(record.get("created_at"), None | Some(DataValue::Null)), "{:?}", record);
+    }
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_rename_table_on_sqlite() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.track_table("harness_users");
+    db.track_table("harness_users_v2");
+    db.seed(&[user("alice", 30), user("bob", 25)]).await?;
+
+    let manager = get_odm_manager().await;
+    manager.rename_table("harness_users", "harness_users_v2", Some(db.alias())).await?;
+    assert_eq!(odm::count("harness_users_v2", vec![], Some(db.alias())).await?, 2);
+    assert!(!table_exists(db.alias(), "harness_users").await?);
+
+    // 原表不存在、目标名称已被占用或名称不合法时返回错误
+    assert!(manager.rename_table("harness_users", "harness_users_v3", Some(db.alias())).await.is_err());
+    db.seed(&[user("carol", 40)]).await?;
+    assert!(manager.rename_table("harness_users", "harness_users_v2", Some(db.alias())).await.is_err());
+    assert!(rename_table(db.alias(), "harness_users", "bad; DROP TABLE x").await.is_err());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_upsert_many_on_sqlite() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_upsert_many")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_contacts");
+    register_model(
+        ModelMeta::new("harness_contacts")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("email", string_field(None, None, None).required())
+            .field("name", string_field(None, None, None))
+            .index(IndexDefinition::new(vec!["email".to_string()], true)),
+    )?;
+    let contact = |email: &str, name: &str| {
+        let mut row = HashMap::new();
+        row.insert("email".to_string(), DataValue::String(email.to_string()));
+        row.insert("name".to_string(), DataValue::String(name.to_string()));
+        row
+    };
+
+    let first = upsert_many(
+        "harness_contacts",
+        vec![contact("a@example.com", "A"), contact("b@example.com", "B")],
+        &["email"],
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(first.total, 2);
+    let by_email = vec![QueryCondition::new("email", QueryOperator::Eq, DataValue::String("a@example.com".to_string()))];
+    let original = odm::find("harness_contacts", by_email.clone(), None, Some(db.alias())).await?;
+
+    // 重复同步时已存在的记录只更新其余字段，ID保持不变
+    let second = upsert_many(
+        "harness_contacts",
+        vec![contact("a@example.com", "A2"), contact("c@example.com", "C")],
+        &["email"],
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(second.total, 2);
+    assert_eq!(second.inserted, None);
+    assert_eq!(odm::count("harness_contacts", vec![], Some(db.alias())).await?, 3);
+    let updated = odm::find("harness_contacts", by_email, None, Some(db.alias())).await?;
+    let (DataValue::Object(before), DataValue::Object(after)) = (&original[0], &updated[0]) else {
+        panic!("记录应为对象");
+    };
+    assert_eq!(after.get("name"), Some(&DataValue::String("A2".to_string())));
+    assert_eq!(after.get("id"), before.get("id"));
+
+    // 字段不一致、冲突字段缺失或批内重复时直接报错
+    let mut partial = contact("d@example.com", "D");
+    partial.remove("name");
+    assert!(upsert_many("harness_contacts", vec![contact("d@example.com", "D"), partial], &["email"], Some(db.alias())).await.is_err());
+    assert!(upsert_many("harness_contacts", vec![contact("d@example.com", "D")], &[], Some(db.alias())).await.is_err());
+    assert!(upsert_many(
+        "harness_contacts",
+        vec![contact("d@example.com", "D"), contact("d@example.com", "E")],
+        &["email"],
+        Some(db.alias()),
+    ).await.is_err());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_upsert_reports_insert_or_update_on_sqlite() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_upsert")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_members");
+    register_model(
+        ModelMeta::new("harness_members")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("email", string_field(None, None, None).required())
+            .field("name", string_field(None, None, None))
+            .index(IndexDefinition::new(vec!["email".to_string()], true)),
+    )?;
+    let member = |email: &str, name: &str| {
+        let mut row = HashMap::new();
+        row.insert("email".to_string(), DataValue::String(email.to_string()));
+        row.insert("name".to_string(), DataValue::String(name.to_string()));
+        row
+    };
+
+    assert_eq!(upsert("harness_members", member("a@example.com", "A"), &["email"], Some(db.alias())).await?, UpsertOutcome::Inserted);
+    // 内容未变时仍视为更新已有记录
+    assert_eq!(upsert("harness_members", member("a@example.com", "A"), &["email"], Some(db.alias())).await?, UpsertOutcome::Updated);
+    assert_eq!(upsert("harness_members", member("a@example.com", "A2"), &["email"], Some(db.alias())).await?, UpsertOutcome::Updated);
+    assert_eq!(odm::count("harness_members", vec![], Some(db.alias())).await?, 1);
+    let by_email = vec![QueryCondition::new("email", QueryOperator::Eq, DataValue::String("a@example.com".to_string()))];
+    let found = odm::find("harness_members", by_email, None, Some(db.alias())).await?;
+    let DataValue::Object(found) = &found[0] else {
+        panic!("记录应为对象");
+    };
+    assert_eq!(found.get("name"), Some(&DataValue::String("A2".to_string())));
+
+    // 冲突字段上没有唯一索引时返回明确的验证错误
+    let err = upsert("harness_members", member("b@example.com", "B"), &["name"], Some(db.alias())).await;
+    assert!(matches!(err, Err(QuickDbError::ValidationError { ref field, .. }) if field == "conflict_fields"), "{:?}", err);
+    assert!(upsert("harness_members", member("b@example.com", "B"), &[], Some(db.alias())).await.is_err());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_like_operators_match_metacharacters_literally() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.seed(&[
+        user("50% off", 1),
+        user("500 off", 2),
+        user("a_b", 3),
+        user("axb", 4),
+        user("C:\\Temp", 5),
+        user("Alice", 6),
+    ]).await?;
+
+    let count = |operator: QueryOperator, value: &str| {
+        let condition = QueryCondition::new("username", operator, DataValue::String(value.to_string()));
+        odm::count("harness_users", vec![condition], Some(db.alias()))
+    };
+    assert_eq!(count(QueryOperator::Contains, "0%").await?, 1);
+    assert_eq!(count(QueryOperator::StartsWith, "a_").await?, 1);
+    assert_eq!(count(QueryOperator::EndsWith, "\\Temp").await?, 1);
+    assert_eq!(count(QueryOperator::StartsWith, "C:\\").await?, 1);
+    assert_eq!(count(QueryOperator::IContains, "ALI").await?, 1);
+    assert_eq!(count(QueryOperator::IContains, "%").await?, 1);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_raw_condition_combines_with_structured_filters() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.seed(&[user("alice", 30), user("bob", 25), user("carol", 40)]).await?;
+
+    let conditions = vec![
+        QueryCondition::new("username", QueryOperator::Ne, DataValue::String("carol".to_string())),
+        QueryCondition::raw("age % $1 = 0 OR age > $2", vec![DataValue::Int(10), DataValue::Int(100)]),
+    ];
+    assert_eq!(odm::count("harness_users", conditions, Some(db.alias())).await?, 1);
+
+    let invalid = vec![QueryCondition::raw("age > ?", vec![DataValue::Int(1)])];
+    assert!(odm::count("harness_users", invalid, Some(db.alias())).await.is_err());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_lenient_read_coercion_converts_mismatched_values() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    // 模拟结构变更后残留的旧数据：字符串字段存了整数，整数字段存了字符串
+    let mut data = HashMap::new();
+    data.insert("id".to_string(), DataValue::String("legacy-1".to_string()));
+    data.insert("username".to_string(), DataValue::Int(42));
+    data.insert("age".to_string(), DataValue::String("30".to_string()));
+
+    assert_eq!(read_coercion(), ReadCoercion::Strict);
+    assert!(HarnessUser::from_data_map(data.clone()).is_err());
+
+    set_read_coercion(ReadCoercion::Lenient);
+    let user = HarnessUser::from_data_map(data);
+    set_read_coercion(ReadCoercion::Strict);
+
+    let user = user?;
+    assert_eq!(user.username, "42");
+    assert_eq!(user.age, 30);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_warm_query_cache_populates_live_keys() -> QuickDbResult<()> {
+    let cache = CacheConfig {
+        enabled: true,
+        strategy: CacheStrategy::Lru,
+        l1_config: L1CacheConfig { max_capacity: 100, max_memory_mb: 16, enable_stats: true },
+        l2_config: None,
+        ttl_config: TtlConfig { default_ttl_secs: 300, max_ttl_secs: 3600, check_interval_secs: 60 },
+        compression_config: CompressionConfig {
+            enabled: false,
+            algorithm: CompressionAlgorithm::Lz4,
+            threshold_bytes: 1024,
+        },
+        version: "v1".to_string(),
+    };
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_cache_warm")
+        .id_strategy(IdStrategy::Uuid)
+        .cache(cache)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.seed(&[user("alice", 30), user("bob", 17)]).await?;
+
+    let adults = vec![QueryCondition {
+        field: "age".to_string(),
+        operator: QueryOperator::Gte,
+        value: DataValue::Int(18),
+    }];
+    let specs = vec![
+        CacheWarmSpec::new(db.alias(), "harness_users", adults.clone()),
+        CacheWarmSpec::new(db.alias(), "harness_users", vec![]),
+    ];
+    let manager = get_cache_manager(db.alias())?;
+    assert_eq!(manager.warm_all(&specs).await.expect("预热失败"), 2);
+    // 已缓存的查询不重复预热
+    assert_eq!(manager.warm_all(&specs).await.expect("预热失败"), 0);
+
+    // 实际查询命中预热的缓存条目
+    let key = manager.generate_find_cache_key("harness_users", &adults, &QueryOptions::default());
+    let keys = manager.list_table_cache_keys("harness_users").await.expect("读取缓存键失败");
+    assert!(keys.contains(&key), "{:?}", keys);
+    let hits = manager.get_performance_stats().await.expect("读取统计失败").hits;
+    let found = odm::find("harness_users", adults, None, Some(db.alias())).await?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(manager.get_performance_stats().await.expect("读取统计失败").hits, hits + 1);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_sort_by_computed_column_on_sqlite() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let db = TestDb::sqlite_memory().await?;
+    db.track_table("harness_order_lines");
+    register_model(
+        ModelMeta::new("harness_order_lines")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("product", string_field(None, None, None).required())
+            .field("price", integer_field(None, None).required())
+            .field("quantity", integer_field(None, None).required()),
+    )?;
+    for (product, price, quantity) in [("pen", 3, 10), ("book", 20, 1), ("ink", 7, 4)] {
+        let mut data = HashMap::new();
+        data.insert("product".to_string(), DataValue::String(product.to_string()));
+        data.insert("price".to_string(), DataValue::Int(price));
+        data.insert("quantity".to_string(), DataValue::Int(quantity));
+        odm::create("harness_order_lines", data, Some(db.alias())).await?;
+    }
+
+    let options = QueryOptions::new()
+        .with_select_expr(Expr::col("price") * Expr::col("quantity"), "revenue")
+        .with_sort(vec![SortConfig { field: "revenue".to_string(), direction: SortDirection::Desc }]);
+    let records = odm::find("harness_order_lines", vec![], Some(options), Some(db.alias())).await?;
+    let products: Vec<_> = records
+        .iter()
+        .map(|record| match record {
+            DataValue::Object(map) => map.get("product").cloned(),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        products,
+        ["pen", "ink", "book"].map(|p| Some(DataValue::String(p.to_string()))).to_vec()
+    );
+
+    // 既不是字段也不是结果别名的排序字段被拒绝
+    let unknown = QueryOptions::new().with_sort(vec![SortConfig { field: "revenue".to_string(), direction: SortDirection::Desc }]);
+    let err = odm::find("harness_order_lines", vec![], Some(unknown), Some(db.alias())).await;
+    assert!(matches!(err, Err(QuickDbError::ValidationError { .. })), "{:?}", err);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_execute_batch_runs_script_in_transaction() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.track_table("harness_notes");
+
+    let script = "
+        -- 建表脚本; 注释中的分号不拆分语句
+        CREATE TABLE harness_notes (id INTEGER PRIMARY KEY, body TEXT NOT NULL);
+        INSERT INTO harness_notes (body) VALUES ('a;b');
+        /* 第二条; */ INSERT INTO harness_notes (body) VALUES ('it''s');
+    ";
+    assert_eq!(odm::execute_batch(script, Some(db.alias())).await?, 3);
+    let count: Option<i64> = odm::query_scalar(
+        "SELECT COUNT(*) FROM harness_notes WHERE body IN ('a;b', 'it''s')",
+        vec![],
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(count, Some(2));
+
+    // 任一语句失败时整体回滚
+    let failing = "INSERT INTO harness_notes (body) VALUES ('c'); INSERT INTO harness_notes (body) VALUES (NULL);";
+    let err = odm::execute_batch(failing, Some(db.alias())).await;
+    assert!(matches!(err, Err(QuickDbError::QueryError { .. })), "{:?}", err);
+    let count: Option<i64> = odm::query_scalar("SELECT COUNT(*) FROM harness_notes", vec![], Some(db.alias())).await?;
+    assert_eq!(count, Some(2));
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_execute_raw_update_returns_affected_rows() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.seed(&[user("alice", 30), user("bob", 17), user("carol", 15)]).await?;
+
+    let affected = odm::execute_raw_update(
+        "UPDATE harness_users SET age = age + 1 WHERE age < ?",
+        vec![DataValue::Int(18)],
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(affected, 2);
+
+    let rows = odm::query_raw(
+        "SELECT username FROM harness_users WHERE age IN (?, ?) ORDER BY username",
+        vec![DataValue::Int(18), DataValue::Int(16)],
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(rows.len(), 2);
+
+    // 没有匹配的行时返回0
+    let affected = odm::execute_raw_update(
+        "DELETE FROM harness_users WHERE age > ?",
+        vec![DataValue::Int(100)],
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(affected, 0);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_execute_raw_reads_rows_and_rejects_writes() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.seed(&[user("alice", 30), user("bob", 17), user("carol", 45)]).await?;
+
+    let rows = odm::execute_raw(
+        "WITH adults AS (SELECT username, age FROM harness_users WHERE age > ?) \
+         SELECT username, RANK() OVER (ORDER BY age DESC) AS age_rank FROM adults ORDER BY age_rank",
+        vec![DataValue::Int(18)],
+        Some(db.alias()),
+    ).await?;
+    let ranked: Vec<(String, String)> = rows
+        .iter()
+        .map(|row| match row {
+            DataValue::Object(map) => (map["username"].to_string(), map["age_rank"].to_string()),
+            other => panic!("意外的行: {:?}", other),
+        })
+        .collect();
+    assert_eq!(ranked, vec![("carol".to_string(), "1".to_string()), ("alice".to_string(), "2".to_string())]);
+
+    // 写入语句应改用 execute_raw_update，不会发送到数据库
+    let write = odm::execute_raw("DELETE FROM harness_users", vec![], Some(db.alias())).await.unwrap_err();
+    assert!(matches!(write, QuickDbError::ValidationError { .. }), "{:?}", write);
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 3);
+
+    db.teardown().await
+}
+
+#[cfg(feature = "mongodb-support")]
+#[tokio::test]
+async fn test_execute_raw_is_unsupported_on_mongodb() -> QuickDbResult<()> {
+    // 原始SQL在发送前即被适配器拒绝，不需要可用的MongoDB服务
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::MongoDB)
+        .connection(ConnectionConfig::MongoDB {
+            host: "127.0.0.1".to_string(),
+            port: 1,
+            database: "harness".to_string(),
+            username: None,
+            password: None,
+            auth_source: None,
+            direct_connection: true,
+            tls_config: None,
+            zstd_config: None,
+            options: None,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_mongo_raw")
+        .id_strategy(IdStrategy::ObjectId)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+
+    let query = odm::execute_raw("SELECT 1", vec![], Some(db.alias())).await.unwrap_err();
+    assert!(matches!(query, QuickDbError::UnsupportedDatabase { .. }), "{:?}", query);
+    let update = odm::execute_raw_update("UPDATE users SET age = 1", vec![], Some(db.alias())).await.unwrap_err();
+    assert!(matches!(update, QuickDbError::UnsupportedDatabase { .. }), "{:?}", update);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_mongo_aggregate_is_rejected_on_sql_aliases() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.seed(&[user("alice", 30)]).await?;
+
+    let pipeline = serde_json::json!([{ "$match": { "age": { "$gt": 18 } } }]);
+    let err = odm::mongo_aggregate("harness_users", pipeline, Some(db.alias())).await;
+    assert!(matches!(err, Err(QuickDbError::UnsupportedDatabase { .. })), "{:?}", err);
+
+    // 管道必须是阶段数组
+    let err = odm::mongo_aggregate("harness_users", serde_json::json!({ "$match": {} }), Some(db.alias())).await;
+    assert!(matches!(err, Err(QuickDbError::ValidationError { .. })), "{:?}", err);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_estimated_count_falls_back_to_exact_on_sqlite() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.seed(&[user("alice", 30), user("bob", 17), user("carol", 45)]).await?;
+
+    // SQL 数据库没有估算计数，按精确计数返回
+    assert_eq!(odm::count_with_mode("harness_users", vec![], CountMode::Estimated, Some(db.alias())).await?, 3);
+    // 带条件时总是精确计数
+    let adults = vec![QueryCondition::new("age", QueryOperator::Gte, DataValue::Int(18))];
+    assert_eq!(odm::count_with_mode("harness_users", adults, CountMode::Estimated, Some(db.alias())).await?, 2);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_soft_deleted_unique_value_can_be_reused() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let db = TestDb::sqlite_memory().await?;
+    db.track_table("harness_members");
+    register_model(
+        ModelMeta::new("harness_members")
+            .with_alias(db.alias())
+            .with_soft_delete("deleted_at")
+            .field("id", string_field(None, None, None).required().unique())
+            .field("username", string_field(None, None, None).required())
+            .field("deleted_at", datetime_field())
+            .index(IndexDefinition::new(vec!["username".to_string()], true)),
+    )?;
+    let member = |username: &str| {
+        let mut data = HashMap::new();
+        data.insert("username".to_string(), DataValue::String(username.to_string()));
+        data.insert("deleted_at".to_string(), DataValue::Null);
+        data
+    };
+
+    odm::create("harness_members", member("alice"), Some(db.alias())).await?;
+    let duplicate = odm::create("harness_members", member("alice"), Some(db.alias())).await.unwrap_err();
+    assert_eq!(duplicate.kind(), ErrorKind::UniqueViolation);
+
+    // 软删除后唯一索引不再约束该记录，用户名可以重新使用
+    let mut deleted = HashMap::new();
+    deleted.insert("deleted_at".to_string(), DataValue::DateTime(chrono::Utc::now()));
+    let active_alice = vec![
+        QueryCondition::new("username", QueryOperator::Eq, DataValue::String("alice".to_string())),
+        QueryCondition::new("deleted_at", QueryOperator::IsNull, DataValue::Null),
+    ];
+    assert_eq!(odm::update("harness_members", active_alice, deleted, Some(db.alias())).await?, 1);
+    odm::create("harness_members", member("alice"), Some(db.alias())).await?;
+    assert_eq!(odm::count("harness_members", vec![], Some(db.alias())).await?, 2);
+
+    db.teardown().await
+}
+
+define_model! {
+    /// 软删除测试笔记模型
+    struct HarnessNote {
+        id: String,
+        title: String,
+        deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+    collection = "harness_notes",
+    soft_delete = "deleted_at",
+    fields = {
+        id: string_field(None, None, None).required().unique(),
+        title: string_field(None, None, None).required(),
+        deleted_at: datetime_field(),
+    }
+}
+
+#[tokio::test]
+async fn test_soft_deleted_models_are_hidden_from_queries() -> QuickDbResult<()> {
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_soft_delete")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_notes");
+    set_default_alias(db.alias()).await?;
+
+    let mut ids = Vec::new();
+    for title in ["keep", "drop", "other"] {
+        let note = HarnessNote { id: String::new(), title: title.to_string(), deleted_at: None };
+        ids.push(note.save().await?);
+    }
+    let dropped = ModelManager::<HarnessNote>::find_by_id(&ids[1]).await?.expect("记录应存在");
+    assert!(dropped.delete().await?);
+
+    // 记录仍在表中，只是写入了删除时间
+    assert_eq!(odm::count("harness_notes", vec![], Some(db.alias())).await?, 3);
+    let titled_drop = vec![QueryCondition::new("title", QueryOperator::Eq, DataValue::String("drop".to_string()))];
+    assert!(!ModelManager::<HarnessNote>::exists(titled_drop.clone()).await?);
+    assert_eq!(ModelManager::<HarnessNote>::count(vec![]).await?, 2);
+    let mut titles: Vec<_> = ModelManager::<HarnessNote>::find(vec![], None).await?
+        .into_iter().map(|note| note.title).collect();
+    titles.sort();
+    assert_eq!(titles, ["keep", "other"]);
+
+    let trashed = ModelManager::<HarnessNote>::find_with_trashed(titled_drop, None).await?;
+    assert_eq!(trashed.len(), 1);
+    assert!(trashed[0].deleted_at.is_some());
+    assert_eq!(ModelManager::<HarnessNote>::find_with_trashed(vec![], None).await?.len(), 3);
+
+    // 按ID查找同样视已删除的记录为不存在，只能通过 find_with_trashed 取回
+    assert!(ModelManager::<HarnessNote>::find_by_id(&ids[1]).await?.is_none());
+    assert!(ModelManager::<HarnessNote>::find_by_id(&ids[0]).await?.is_some());
+    let found = ModelManager::<HarnessNote>::find_by_ids(ids.iter().map(String::as_str).collect()).await?;
+    assert_eq!(found.len(), 2);
+    assert!(!found.contains_key(&ids[1]));
+
+    db.teardown().await
+}
+
+define_model! {
+    /// 自动时间戳测试帖子模型
+    struct HarnessPost {
+        id: String,
+        title: String,
+        created_at: Option<chrono::DateTime<chrono::Utc>>,
+        updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+    collection = "harness_posts",
+    timestamps = { created: "created_at", updated: "updated_at" },
+    fields = {
+        id: string_field(None, None, None).required().unique(),
+        title: string_field(None, None, None).required(),
+        created_at: datetime_field().required(),
+        updated_at: datetime_field().required(),
+    }
+}
+
+#[tokio::test]
+async fn test_timestamps_are_stamped_on_save_and_update() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_timestamps")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_posts");
+    set_default_alias(db.alias()).await?;
+
+    // 必填的时间戳未设置时由 save() 补全，不会导致验证失败
+    let before = chrono::Utc::now();
+    let post = HarnessPost { id: String::new(), title: "draft".to_string(), created_at: None, updated_at: None };
+    post.validate()?;
+    let id = post.save().await?;
+    let saved = ModelManager::<HarnessPost>::find_by_id(&id).await?.expect("记录应存在");
+    let created_at = saved.created_at.expect("应写入创建时间");
+    assert!(created_at >= before - chrono::Duration::seconds(1));
+    assert_eq!(saved.updated_at, Some(created_at));
+
+    // 已设置的创建时间保持不变
+    let imported_at = chrono::Utc::now() - chrono::Duration::days(30);
+    let imported = HarnessPost { id: String::new(), title: "imported".to_string(), created_at: Some(imported_at), updated_at: None };
+    let imported_id = imported.save().await?;
+    let imported = ModelManager::<HarnessPost>::find_by_id(&imported_id).await?.expect("记录应存在");
+    assert_eq!(imported.created_at.map(|t| t.timestamp()), Some(imported_at.timestamp()));
+
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    let mut updates = HashMap::new();
+    updates.insert("title".to_string(), DataValue::String("published".to_string()));
+    assert!(saved.update(updates).await?);
+    let updated = ModelManager::<HarnessPost>::find_by_id(&id).await?.expect("记录应存在");
+    assert_eq!(updated.title, "published");
+    assert_eq!(updated.created_at, Some(created_at));
+    assert!(updated.updated_at.expect("应写入更新时间") > created_at);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_registered_model_fields_are_reflectable() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    register_model(
+        ModelMeta::new("harness_catalog")
+            .with_alias(db.alias())
+            .field("sku", string_field(Some(16), None, None).required().description("商品编码"))
+            .field("price", float_field(None, None).required())
+            .field("category", string_field(None, None, None))
+            .index(IndexDefinition::new(vec!["sku".to_string()], true))
+            .index(IndexDefinition::new(vec!["category".to_string(), "price".to_string()], false)),
+    )?;
+
+    let meta = get_registered_model("harness_catalog").expect("模型应已注册");
+    let fields = meta.field_infos();
+    let names: Vec<_> = fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["category", "price", "sku"]);
+
+    let sku = meta.field_info("sku").expect("sku字段应存在");
+    assert!(sku.required && sku.unique && sku.indexed);
+    assert_eq!(sku.description.as_deref(), Some("商品编码"));
+    assert!(matches!(sku.field_type, FieldType::String { max_length: Some(16), .. }));
+    let category = &fields[0];
+    assert!(!category.required && !category.unique && category.indexed);
+    assert!(meta.field_info("missing").is_none());
+
+    db.teardown().await
+}
+
+define_model! {
+    /// 带字段约束的导入测试模型
+    struct HarnessImportRow {
+        id: String,
+        code: String,
+        quantity: i32,
+    }
+    collection = "harness_import_rows",
+    fields = {
+        id: string_field(None, None, None),
+        code: string_field(Some(8), Some(2), None).required(),
+        quantity: integer_field(Some(0), Some(100)).required(),
+    }
+}
+
+#[test]
+fn test_validate_batch_reports_every_invalid_row() {
+    let row = |code: &str, quantity: i32| HarnessImportRow { id: String::new(), code: code.to_string(), quantity };
+    let rows = vec![row("A1", 5), row("X", 500), row("B2", 10), row("C3", -1)];
+
+    let report = ModelManager::<HarnessImportRow>::validate_batch(&rows);
+    let invalid: Vec<_> = report.iter().map(|(index, errors)| (*index, errors.len())).collect();
+    assert_eq!(invalid, [(1, 2), (3, 1)]);
+    assert!(report[0].1.iter().all(|e| matches!(e, QuickDbError::ValidationError { .. })));
+    assert!(ModelManager::<HarnessImportRow>::validate_batch(&rows[..1]).is_empty());
+}
+
+#[tokio::test]
+async fn test_find_stream_reads_in_pages() -> QuickDbResult<()> {
+    use futures::TryStreamExt;
+
+    let db = TestDb::sqlite_memory().await?;
+    let users: Vec<_> = (1..=7).map(|age| user(&format!("user{}", age), age)).collect();
+    db.seed(&users).await?;
+    let by_age = || QueryOptions::new()
+        .with_sort(vec![SortConfig { field: "age".to_string(), direction: SortDirection::Asc }]);
+    let ages = |records: Vec<DataValue>| -> Vec<DataValue> {
+        records.into_iter()
+            .filter_map(|record| match record {
+                DataValue::Object(map) => map.get("age").cloned(),
+                _ => None,
+            })
+            .collect()
+    };
+
+    let all: Vec<_> = odm::find_stream("harness_users", vec![], Some(by_age()), Some(3), Some(db.alias()))
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(ages(all), (1..=7).map(DataValue::Int).collect::<Vec<_>>());
+
+    // 查询选项中的分页作为整体的起点和上限，默认页大小同样适用
+    let window = by_age().with_pagination(PaginationConfig { skip: 2, limit: 4 });
+    let partial: Vec<_> = odm::find_stream("harness_users", vec![], Some(window), None, Some(db.alias()))
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(ages(partial), (3..=6).map(DataValue::Int).collect::<Vec<_>>());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_duplicate_copies_record_with_new_id() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_duplicate")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_posts");
+    register_model(
+        ModelMeta::new("harness_posts")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("slug", string_field(None, None, None).required())
+            .field("title", string_field(None, None, None).required())
+            .field("status", string_field(None, None, None).required())
+            .index(IndexDefinition::new(vec!["slug".to_string()], true)),
+    )?;
+    let mut post = HashMap::new();
+    post.insert("slug".to_string(), DataValue::String("hello".to_string()));
+    post.insert("title".to_string(), DataValue::String("Hello".to_string()));
+    post.insert("status".to_string(), DataValue::String("published".to_string()));
+    let original = odm::create("harness_posts", post, Some(db.alias())).await?;
+
+    let mut overrides = HashMap::new();
+    overrides.insert("slug".to_string(), DataValue::String("hello-draft".to_string()));
+    overrides.insert("status".to_string(), DataValue::String("draft".to_string()));
+    let copy = odm::duplicate("harness_posts", original.clone(), overrides, Some(db.alias())).await?;
+    assert_ne!(copy, original);
+
+    let Some(DataValue::Object(draft)) = odm::find_by_id("harness_posts", copy, Some(db.alias())).await? else {
+        panic!("复制的记录应存在");
+    };
+    assert_eq!(draft.get("title"), Some(&DataValue::String("Hello".to_string())));
+    assert_eq!(draft.get("status"), Some(&DataValue::String("draft".to_string())));
+
+    // 未覆盖唯一字段时插入冲突，源记录不存在时报错
+    let conflict = odm::duplicate("harness_posts", original, HashMap::new(), Some(db.alias())).await.unwrap_err();
+    assert_eq!(conflict.kind(), ErrorKind::UniqueViolation);
+    assert!(odm::duplicate("harness_posts", "missing", HashMap::new(), Some(db.alias())).await.is_err());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_datetime_precision_truncates_writes_and_queries() -> QuickDbResult<()> {
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_datetime_precision")
+        .id_strategy(IdStrategy::AutoIncrement)
+        .datetime_precision(3)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_events");
+    register_model(
+        ModelMeta::new("harness_events")
+            .with_alias(db.alias())
+            .field("name", string_field(None, None, None).required())
+            .field("happened_at", datetime_field().required()),
+    )?;
+
+    let written = chrono::Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap();
+    let mut event = HashMap::new();
+    event.insert("name".to_string(), DataValue::String("deploy".to_string()));
+    event.insert("happened_at".to_string(), DataValue::DateTime(written));
+    odm::create("harness_events", event, Some(db.alias())).await?;
+
+    // 按写入时的原值精确查询，条件同样截断后命中存储的值
+    let exact = vec![QueryCondition::new("happened_at", QueryOperator::Eq, DataValue::DateTime(written))];
+    let found = odm::find("harness_events", exact, None, Some(db.alias())).await?;
+    assert_eq!(found.len(), 1);
+    let DataValue::Object(record) = &found[0] else { panic!("记录应为对象") };
+    // SQLite 以文本存储日期时间，读取结果按字符串比较
+    let stored = chrono::Utc.timestamp_opt(1_700_000_000, 123_000_000).unwrap();
+    assert_eq!(record.get("happened_at").map(|v| v.to_string()), Some(stored.to_rfc3339()));
+
+    assert!(DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite { path: ":memory:".to_string(), create_if_missing: true })
+        .pool(PoolConfig::default())
+        .id_strategy(IdStrategy::AutoIncrement)
+        .datetime_precision(9)
+        .build()
+        .is_err());
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_subscribers_receive_model_change_events() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_events")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_events");
+    register_model(
+        ModelMeta::new("harness_events")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("name", string_field(None, None, None).required()),
+    )?;
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let subscription = subscribe("harness_events", move |event: ChangeEvent| {
+        let tx = tx.clone();
+        async move {
+            let _ = tx.send(event);
+            Ok(())
+        }
+    });
+    // 订阅者出错只记录日志，不影响写操作
+    let failing = subscribe("harness_events", |_event: ChangeEvent| async {
+        Err(QuickDbError::Other(anyhow::anyhow!("订阅者故障")))
+    });
+
+    let mut data = HashMap::new();
+    data.insert("name".to_string(), DataValue::String("first".to_string()));
+    let id = odm::create("harness_events", data, Some(db.alias())).await?;
+    let id_str = id.to_string();
+
+    let mut updates = HashMap::new();
+    updates.insert("name".to_string(), DataValue::String("second".to_string()));
+    assert!(odm::update_by_id("harness_events", &id_str, updates, Some(db.alias())).await?);
+    assert!(odm::delete_by_id("harness_events", &id_str, Some(db.alias())).await?);
+
+    let mut events = Vec::new();
+    for _ in 0..3 {
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await
+            .expect("应在超时前收到事件")
+            .expect("事件通道不应关闭");
+        events.push(event);
+    }
+    // 订阅者在独立任务中执行，按操作类型排序后比较
+    events.sort_by_key(|event| event.op as u8);
+    assert_eq!(events.iter().map(|event| event.op).collect::<Vec<_>>(),
+        vec![ChangeOperation::Insert, ChangeOperation::Update, ChangeOperation::Delete]);
+    assert!(events.iter().all(|event| event.table == "harness_events" && event.id.to_string() == id_str));
+    let Some(DataValue::Object(inserted)) = &events[0].document else {
+        panic!("创建事件应携带写入数据");
+    };
+    assert_eq!(inserted.get("name"), Some(&DataValue::String("first".to_string())));
+    assert!(events[2].document.is_none());
+
+    assert!(unsubscribe(subscription));
+    assert!(unsubscribe(failing));
+    assert!(!unsubscribe(subscription));
+
+    db.teardown().await
+}
+
+define_model! {
+    /// 十进制金额字段的测试模型
+    struct HarnessPrice {
+        id: String,
+        amount: String,
+        ratio: f64,
+    }
+    collection = "harness_prices",
+    fields = {
+        id: string_field(None, None, None).required().unique(),
+        amount: FieldDefinition::new(FieldType::Decimal { precision: 20, scale: 2 }).required(),
+        ratio: FieldDefinition::new(FieldType::Double).required(),
+    }
+}
+
+#[tokio::test]
+async fn test_decimal_fields_round_trip_as_exact_strings() -> QuickDbResult<()> {
+    let price = HarnessPrice {
+        id: "price-1".to_string(),
+        amount: "12345678901234567.89".to_string(),
+        ratio: 0.5,
+    };
+    price.validate()?;
+    let mut row = price.to_data_map_direct()?;
+    assert_eq!(row.get("amount"), Some(&DataValue::String("12345678901234567.89".to_string())));
+
+    // PostgreSQL NUMERIC / MySQL DECIMAL 以精确字符串返回：十进制字段保持原样，浮点字段转换为浮点数
+    row.insert("ratio".to_string(), DataValue::String("0.5".to_string()));
+    let read = HarnessPrice::from_data_map(row)?;
+    assert_eq!(read.amount, "12345678901234567.89");
+    assert_eq!(read.ratio, 0.5);
+
+    // SQLite 以 REAL 存储十进制字段，读回的数值转为字符串
+    let db = TestDb::sqlite_memory().await?;
+    db.track_table("harness_prices");
+    let mut data = HarnessPrice { id: "price-2".to_string(), amount: "1234.5".to_string(), ratio: 0.25 }
+        .to_data_map_direct()?;
+    data.remove("id");
+    odm::create("harness_prices", data, Some(db.alias())).await?;
+    let found = odm::find("harness_prices", vec![], None, Some(db.alias())).await?;
+    let DataValue::Object(mut record) = found[0].clone() else { panic!("记录应为对象") };
+    // 内存库使用自增ID
+    record.insert("id".to_string(), DataValue::String(record["id"].to_string()));
+    let read = HarnessPrice::from_data_map(record)?;
+    assert_eq!((read.amount.as_str(), read.ratio), ("1234.5", 0.25));
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_dry_run_alias_publishes_no_change_events() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("dry_run_events.db").to_string_lossy().to_string();
+    let config = |alias: &str, dry_run: bool| {
+        DatabaseConfig::builder()
+            .db_type(DatabaseType::SQLite)
+            .connection(ConnectionConfig::SQLite {
+                path: path.clone(),
+                create_if_missing: true,
+            })
+            .pool(PoolConfig::default())
+            .alias(alias)
+            .id_strategy(IdStrategy::Uuid)
+            .dry_run(dry_run)
+            .build()
+    };
+    let db = TestDb::with_config(config("test_dry_events", false)?).await?;
+    let dry = TestDb::with_config(config("test_dry_events_dry", true)?).await?;
+    db.track_table("harness_dry_events");
+    register_model(
+        ModelMeta::new("harness_dry_events")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("name", string_field(None, None, None).required()),
+    )?;
+
+    let mut data = HashMap::new();
+    data.insert("name".to_string(), DataValue::String("first".to_string()));
+    let id_str = odm::create("harness_dry_events", data.clone(), Some(db.alias())).await?.to_string();
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let subscription = subscribe("harness_dry_events", move |event: ChangeEvent| {
+        let tx = tx.clone();
+        async move {
+            let _ = tx.send(event);
+            Ok(())
+        }
+    });
+
+    // 试运行别名的写入会被回滚，不应发布任何事件
+    let mut updates = HashMap::new();
+    updates.insert("name".to_string(), DataValue::String("second".to_string()));
+    odm::create("harness_dry_events", data, Some(dry.alias())).await?;
+    assert!(odm::update_by_id("harness_dry_events", &id_str, updates.clone(), Some(dry.alias())).await?);
+    assert!(odm::delete_by_id("harness_dry_events", &id_str, Some(dry.alias())).await?);
+
+    // 普通别名的写入照常发布，且是通道中唯一的事件
+    assert!(odm::update_by_id("harness_dry_events", &id_str, updates, Some(db.alias())).await?);
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await
+        .expect("应在超时前收到事件")
+        .expect("事件通道不应关闭");
+    assert_eq!(event.op, ChangeOperation::Update);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(rx.try_recv().is_err());
+
+    assert!(unsubscribe(subscription));
+    dry.teardown().await?;
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_query_cache_keeps_projections_apart() -> QuickDbResult<()> {
+    let cache = CacheConfig {
+        enabled: true,
+        strategy: CacheStrategy::Lru,
+        l1_config: L1CacheConfig { max_capacity: 100, max_memory_mb: 16, enable_stats: true },
+        l2_config: None,
+        ttl_config: TtlConfig { default_ttl_secs: 300, max_ttl_secs: 3600, check_interval_secs: 60 },
+        compression_config: CompressionConfig {
+            enabled: false,
+            algorithm: CompressionAlgorithm::Lz4,
+            threshold_bytes: 1024,
+        },
+        version: "v1".to_string(),
+    };
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_cache_projection")
+        .id_strategy(IdStrategy::Uuid)
+        .cache(cache)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.seed(&[user("alice", 30)]).await?;
+
+    let adults = vec![QueryCondition {
+        field: "age".to_string(),
+        operator: QueryOperator::Gte,
+        value: DataValue::Int(18),
+    }];
+    // 按投影查询并返回结果中的字段名
+    async fn columns(alias: &str, conditions: &[QueryCondition], fields: &[&str]) -> QuickDbResult<Vec<String>> {
+        let options = QueryOptions::new().with_fields(fields.iter().map(|f| f.to_string()).collect());
+        let found = odm::find("harness_users", conditions.to_vec(), Some(options), Some(alias)).await?;
+        let Some(DataValue::Object(record)) = found.into_iter().next() else {
+            panic!("应查询到一条记录");
+        };
+        let mut keys: Vec<String> = record.into_keys().collect();
+        keys.sort();
+        Ok(keys)
+    }
+
+    // 每个投影查询两次，第二次命中缓存，仍只返回所选字段
+    for _ in 0..2 {
+        assert_eq!(columns(db.alias(), &adults, &["id", "username"]).await?, vec!["id", "username"]);
+        assert_eq!(columns(db.alias(), &adults, &["age"]).await?, vec!["age"]);
+        assert_eq!(columns(db.alias(), &adults, &[]).await?, vec!["age", "id", "username"]);
+    }
+
+    // 字段顺序不同的相同投影共用缓存
+    let manager = get_cache_manager(db.alias())?;
+    let hits = manager.get_performance_stats().await.expect("读取统计失败").hits;
+    assert_eq!(columns(db.alias(), &adults, &["username", "id"]).await?, vec!["id", "username"]);
+    assert_eq!(manager.get_performance_stats().await.expect("读取统计失败").hits, hits + 1);
+
+    db.teardown().await
+}
+
+define_model! {
+    /// 邮箱写入前去除空白并转为小写的联系人模型
+    struct HarnessContact {
+        id: String,
+        email: String,
+    }
+    collection = "harness_contacts",
+    fields = {
+        id: string_field(None, None, None).required().unique(),
+        email: string_field(None, None, None)
+            .required()
+            .normalize(FieldNormalizer::Trim)
+            .normalize(FieldNormalizer::Lowercase),
+    }
+}
+
+#[tokio::test]
+async fn test_field_normalizers_applied_on_write() -> QuickDbResult<()> {
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_field_normalizers")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.seed(&[HarnessContact { id: String::new(), email: "  Bob@X.COM ".to_string() }]).await?;
+
+    let found = odm::find(
+        "harness_contacts",
+        vec![QueryCondition {
+            field: "email".to_string(),
+            operator: QueryOperator::Eq,
+            value: DataValue::String("bob@x.com".to_string()),
+        }],
+        None,
+        Some(db.alias()),
+    ).await?;
+    assert_eq!(found.len(), 1);
+
+    // 规范化后才验证，全是空白的邮箱视为空字符串
+    let meta = HarnessContact::meta();
+    let blank = HarnessContact { id: String::new(), email: "   ".to_string() };
+    assert_eq!(
+        blank.to_normalized_data_map(&meta)?.get("email"),
+        Some(&DataValue::String(String::new()))
+    );
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_queries_routed_to_workload_pools() -> QuickDbResult<()> {
+    let analytics = PoolConfig::builder()
+        .min_connections(1)
+        .max_connections(1)
+        .connection_timeout(60)
+        .idle_timeout(300)
+        .max_lifetime(1800)
+        .max_retries(1)
+        .retry_interval_ms(100)
+        .keepalive_interval_sec(60)
+        .health_check_timeout_sec(5)
+        .build()?;
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .workload("analytics", analytics)
+        .alias("test_workload_pools")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.seed(&[user("alice", 30), user("bob", 25)]).await?;
+
+    let options = QueryOptions::new().with_workload("analytics");
+    let found = odm::find("harness_users", vec![], Some(options), Some(db.alias())).await?;
+    assert_eq!(found.len(), 2);
+
+    let options = QueryOptions::new().with_workload("reporting");
+    let result = odm::find("harness_users", vec![], Some(options), Some(db.alias())).await;
+    assert!(matches!(result, Err(QuickDbError::ConfigError { .. })));
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_time_bucket_counts_per_interval_on_sqlite() -> QuickDbResult<()> {
+    use chrono::{TimeZone, Utc};
+    use std::collections::HashMap;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_time_bucket")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_page_views");
+    register_model(
+        ModelMeta::new("harness_page_views")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("page", string_field(None, None, None).required())
+            .field("viewed_at", datetime_field()),
+    )?;
+
+    let at = |d: u32, h: u32, m: u32| DataValue::DateTime(Utc.with_ymd_and_hms(2024, 3, d, h, m, 0).unwrap());
+    // 2024-03-04 是周一，2024-03-10 是周日
+    for (page, viewed_at) in [
+        ("home", at(4, 9, 15)),
+        ("home", at(4, 17, 40)),
+        ("about", at(5, 8, 0)),
+        ("home", at(10, 23, 59)),
+        ("home", DataValue::Null),
+    ] {
+        let mut data = HashMap::new();
+        data.insert("page".to_string(), DataValue::String(page.to_string()));
+        data.insert("viewed_at".to_string(), viewed_at);
+        odm::create("harness_page_views", data, Some(db.alias())).await?;
+    }
+
+    let days = odm::time_bucket("harness_page_views", "viewed_at", Interval::Day, vec![], Some(db.alias())).await?;
+    assert_eq!(days, vec![(at(4, 0, 0), 2), (at(5, 0, 0), 1), (at(10, 0, 0), 1)]);
+
+    let weeks = odm::time_bucket("harness_page_views", "viewed_at", Interval::Week, vec![], Some(db.alias())).await?;
+    assert_eq!(weeks, vec![(at(4, 0, 0), 4)]);
+
+    let home = vec![QueryCondition {
+        field: "page".to_string(),
+        operator: QueryOperator::Eq,
+        value: DataValue::String("home".to_string()),
+    }];
+    let hours = odm::time_bucket("harness_page_views", "viewed_at", Interval::Hour, home, Some(db.alias())).await?;
+    assert_eq!(hours, vec![(at(4, 9, 0), 1), (at(4, 17, 0), 1), (at(10, 23, 0), 1)]);
+
+    let months = odm::time_bucket("harness_page_views", "viewed_at", Interval::Month, vec![], Some(db.alias())).await?;
+    assert_eq!(months, vec![(at(1, 0, 0), 4)]);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_aggregate_totals_per_group_on_sqlite() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_aggregate")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.track_table("harness_orders");
+    register_model(
+        ModelMeta::new("harness_orders")
+            .with_alias(db.alias())
+            .field("id", string_field(None, None, None).required().unique())
+            .field("customer", string_field(None, None, None).required())
+            .field("amount", integer_field(None, None).required())
+            .field("coupon", string_field(None, None, None)),
+    )?;
+
+    for (customer, amount, coupon) in [("alice", 30, None), ("bob", 5, Some("SPRING")), ("alice", 12, Some("SPRING")), ("bob", 20, None), ("carol", 7, None)] {
+        let mut data = HashMap::new();
+        data.insert("customer".to_string(), DataValue::String(customer.to_string()));
+        data.insert("amount".to_string(), DataValue::Int(amount));
+        data.insert("coupon".to_string(), coupon.map_or(DataValue::Null, |c| DataValue::String(c.to_string())));
+        odm::create("harness_orders", data, Some(db.alias())).await?;
+    }
+
+    let aggregations = vec![
+        Aggregation::new(AggFunc::Sum, "amount", "total"),
+        Aggregation::new(AggFunc::Max, "amount", "largest"),
+        Aggregation::new(AggFunc::Count, "coupon", "with_coupon"),
+        Aggregation::count("orders"),
+    ];
+    let rows = odm::aggregate("harness_orders", aggregations, vec![], vec!["customer".to_string()], Some(db.alias())).await?;
+    let summary: Vec<_> = rows.into_iter()
+        .map(|row| {
+            let DataValue::Object(row) = row else { panic!("聚合结果应为对象: {:?}", row) };
+            (row["customer"].clone(), row["total"].clone(), row["largest"].clone(), row["with_coupon"].clone(), row["orders"].clone())
+        })
+        .collect();
+    let s = |v: &str| DataValue::String(v.to_string());
+    assert_eq!(summary, vec![
+        (s("alice"), DataValue::Int(42), DataValue::Int(30), DataValue::Int(1), DataValue::Int(2)),
+        (s("bob"), DataValue::Int(25), DataValue::Int(20), DataValue::Int(1), DataValue::Int(2)),
+        (s("carol"), DataValue::Int(7), DataValue::Int(7), DataValue::Int(0), DataValue::Int(1)),
+    ]);
+
+    let nobody = vec![QueryCondition {
+        field: "customer".to_string(),
+        operator: QueryOperator::Eq,
+        value: DataValue::String("dave".to_string()),
+    }];
+    let rows = odm::aggregate("harness_orders", vec![Aggregation::count("orders")], nobody, vec![], Some(db.alias())).await?;
+    assert_eq!(rows.len(), 1);
+    let DataValue::Object(row) = &rows[0] else { panic!("聚合结果应为对象") };
+    assert_eq!(row["orders"], DataValue::Int(0));
+
+    let result = odm::aggregate("harness_orders", vec![], vec![], vec![], Some(db.alias())).await;
+    assert!(matches!(result, Err(QuickDbError::ValidationError { .. })));
+
+    db.teardown().await
+}
+
+define_model! {
+    /// 测试迁移锁的模型
+    struct HarnessMigration {
+        id: String,
+        name: String,
+    }
+    collection = "harness_migrations",
+    fields = {
+        id: string_field(None, None, None).required().unique(),
+        name: string_field(None, None, None).required(),
+    }
+}
+
+#[tokio::test]
+async fn test_sync_model_serialized_by_migration_lock() -> QuickDbResult<()> {
+    let db = TestDb::sqlite_memory().await?;
+    db.track_table("harness_migrations");
+    db.track_table("_rat_quickdb_migration_lock");
+    let DatabaseConnection::SQLite(raw) = get_raw_connection(Some(db.alias())).await? else {
+        panic!("应为SQLite连接");
+    };
+
+    // 同时同步：只有一个实例建表，另一个等待后不产生变更
+    let (first, second) = tokio::join!(
+        odm::sync_model::<HarnessMigration>(Some(db.alias())),
+        odm::sync_model::<HarnessMigration>(Some(db.alias())),
+    );
+    let (first, second) = (first?, second?);
+    assert!(first.table_created ^ second.table_created, "{:?} / {:?}", first, second);
+
+    // 其他实例持有锁时等待，锁释放后继续
+    sqlx::query("INSERT INTO _rat_quickdb_migration_lock (name, owner, acquired_at) VALUES ('rat_quickdb_migration', 'other', ?)")
+        .bind(chrono::Utc::now().timestamp())
+        .execute(&raw)
+        .await
+        .expect("写入锁行失败");
+    let alias = db.alias().to_string();
+    let waiting = tokio::spawn(async move { odm::sync_model::<HarnessMigration>(Some(&alias)).await });
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    assert!(!waiting.is_finished(), "锁被占用时应等待");
+    sqlx::query("DELETE FROM _rat_quickdb_migration_lock WHERE owner = 'other'")
+        .execute(&raw)
+        .await
+        .expect("删除锁行失败");
+    let report = waiting.await.expect("同步任务异常退出")?;
+    assert!(!report.table_created);
+
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _rat_quickdb_migration_lock")
+        .fetch_one(&raw)
+        .await
+        .expect("查询锁表失败");
+    assert_eq!(remaining, 0, "完成后应释放迁移锁");
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_explicit_transaction_on_sqlite() -> QuickDbResult<()> {
+    use std::collections::HashMap;
+
+    let db = TestDb::sqlite_memory().await?;
+    db.track_table("harness_users");
+    db.seed(&[user("alice", 30)]).await?;
+    let by_name = |name: &str| vec![QueryCondition {
+        field: "username".to_string(),
+        operator: QueryOperator::Eq,
+        value: DataValue::String(name.to_string()),
+    }];
+    let new_user = |name: &str, age: i64| HashMap::from([
+        ("username".to_string(), DataValue::String(name.to_string())),
+        ("age".to_string(), DataValue::Int(age)),
+    ]);
+
+    // 事务内可读到未提交的写入，回滚后消失
+    let tx = odm::transaction(Some(db.alias())).await?;
+    tx.create("harness_users", new_user("bob", 25)).await?;
+    assert_eq!(tx.find("harness_users", by_name("bob"), None).await?.len(), 1);
+    // 同一任务中的嵌套事务返回错误
+    assert!(matches!(
+        odm::transaction(Some(db.alias())).await,
+        Err(QuickDbError::TransactionError { .. })
+    ));
+    tx.rollback().await?;
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 1);
+
+    // 未提交就丢弃的事务自动回滚
+    {
+        let tx = odm::transaction(Some(db.alias())).await?;
+        assert_eq!(tx.delete("harness_users", vec![]).await?, 1);
+    }
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 1);
+
+    // 提交后所有写入一起生效
+    let tx = odm::transaction(Some(db.alias())).await?;
+    let updates = HashMap::from([("age".to_string(), DataValue::Int(31))]);
+    assert_eq!(tx.update("harness_users", by_name("alice"), updates).await?, 1);
+    tx.create("harness_users", new_user("carol", 40)).await?;
+    tx.commit().await?;
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 2);
+    let alice = odm::find("harness_users", by_name("alice"), None, Some(db.alias())).await?;
+    let DataValue::Object(alice) = &alice[0] else {
+        panic!("记录应为对象");
+    };
+    assert_eq!(alice.get("age"), Some(&DataValue::Int(31)));
+
+    // 事务中不会自动建表
+    let tx = odm::transaction(Some(db.alias())).await?;
+    assert!(matches!(
+        tx.create("harness_missing", new_user("dave", 50)).await,
+        Err(QuickDbError::ValidationError { .. })
+    ));
+    tx.rollback().await?;
+
+    // 回滚到保存点只撤销其后的修改，之前的写入随事务一起提交
+    let tx = odm::transaction(Some(db.alias())).await?;
+    tx.create("harness_users", new_user("frank", 33)).await?;
+    tx.savepoint("optional_step").await?;
+    tx.create("harness_users", new_user("grace", 27)).await?;
+    assert_eq!(tx.delete("harness_users", by_name("alice")).await?, 1);
+    tx.rollback_to("optional_step").await?;
+    tx.release("optional_step").await?;
+    assert!(matches!(
+        tx.rollback_to("optional_step").await,
+        Err(QuickDbError::TransactionError { .. })
+    ));
+    assert!(matches!(tx.savepoint("1st").await, Err(QuickDbError::ValidationError { .. })));
+    tx.commit().await?;
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 3);
+    assert_eq!(odm::count("harness_users", by_name("frank"), Some(db.alias())).await?, 1);
+    assert_eq!(odm::count("harness_users", by_name("grace"), Some(db.alias())).await?, 0);
+    assert_eq!(odm::count("harness_users", by_name("alice"), Some(db.alias())).await?, 1);
+
+    // SQLite 事务始终串行化，指定任何隔离级别都按串行化执行
+    let tx = odm::transaction_with_isolation(IsolationLevel::Serializable, Some(db.alias())).await?;
+    tx.create("harness_users", new_user("erin", 22)).await?;
+    tx.commit().await?;
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 4);
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_find_page_walks_cursor_to_last_page() -> QuickDbResult<()> {
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_find_page")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    db.seed(&[user("dave", 35), user("alice", 30), user("eve", 28), user("carol", 40), user("bob", 25)]).await?;
+    set_default_alias(db.alias()).await?;
+
+    let mut cursor = CursorPagination::new("username", 2);
+    let mut pages = Vec::new();
+    loop {
+        let page = ModelManager::<HarnessUser>::find_page(vec![], cursor.clone()).await?;
+        pages.push(page.records.iter().map(|u| u.username.clone()).collect::<Vec<_>>());
+        match page.next_cursor {
+            Some(next) => cursor = cursor.after(next),
+            None => break,
+        }
+    }
+    assert_eq!(pages, vec![
+        vec!["alice".to_string(), "bob".to_string()],
+        vec!["carol".to_string(), "dave".to_string()],
+        vec!["eve".to_string()],
+    ]);
+
+    // 游标条件与其他条件一同生效，且不能与 skip/limit 分页同时使用
+    let older = vec![QueryCondition {
+        field: "age".to_string(),
+        operator: QueryOperator::Gte,
+        value: DataValue::Int(30),
+    }];
+    let options = QueryOptions::new()
+        .with_cursor(CursorPagination::new("username", 10).after(DataValue::String("alice".to_string())));
+    let records = odm::find("harness_users", older.clone(), Some(options), Some(db.alias())).await?;
+    assert_eq!(records.len(), 2);
+    let options = QueryOptions::new()
+        .with_cursor(CursorPagination::new("username", 10))
+        .with_pagination(PaginationConfig { skip: 0, limit: 10 });
+    assert!(matches!(
+        odm::find("harness_users", older, Some(options), Some(db.alias())).await,
+        Err(QuickDbError::ValidationError { .. })
+    ));
+
+    db.teardown().await
+}
+
+#[tokio::test]
+async fn test_save_many_returns_ids_in_input_order() -> QuickDbResult<()> {
+    // 自增ID由数据库生成，超过单条语句的行数上限时拆成多条多行插入
+    let db = TestDb::sqlite_memory().await?;
+    set_default_alias(db.alias()).await?;
+    let users: Vec<HarnessUser> = (0..2500).map(|i| user(&format!("user{}", i), i % 90)).collect();
+    let ids = ModelManager::<HarnessUser>::save_many(users).await?;
+    assert_eq!(ids, (1..=2500).map(|id| id.to_string()).collect::<Vec<_>>());
+    assert_eq!(odm::count("harness_users", vec![], Some(db.alias())).await?, 2500);
+    db.teardown().await?;
+
+    let config = DatabaseConfig::builder()
+        .db_type(DatabaseType::SQLite)
+        .connection(ConnectionConfig::SQLite {
+            path: ":memory:".to_string(),
+            create_if_missing: true,
+        })
+        .pool(PoolConfig::default())
+        .alias("test_save_many")
+        .id_strategy(IdStrategy::Uuid)
+        .build()?;
+    let db = TestDb::with_config(config).await?;
+    set_default_alias(db.alias()).await?;
+
+    // 可选字段有无交替时按写入列分批，数组字段与单条插入的存储格式一致
+    let articles = vec![
+        article("a", &["x"]),
+        HarnessArticle { id: String::new(), title: "b".to_string(), tags: None },
+        article("c", &["y", "z"]),
+    ];
+    let ids = ModelManager::<HarnessArticle>::save_many(articles).await?;
+    let found = ModelManager::<HarnessArticle>::find_by_id(&ids[2]).await?.expect("按返回的ID应能找到记录");
+    assert_eq!(found.title, "c");
+    assert_eq!(found.tags, Some(vec!["y".to_string(), "z".to_string()]));
+    let found = ModelManager::<HarnessArticle>::find_by_id(&ids[1]).await?.expect("按返回的ID应能找到记录");
+    assert_eq!(found.title, "b");
+    assert_eq!(found.tags, None);
+
+    db.teardown().await
+}